@@ -0,0 +1,13 @@
+-- Add down migration script here
+ALTER TABLE withdraws
+    ALTER COLUMN withdraw_amount TYPE INTEGER;
+
+ALTER TABLE "transfers"
+    ALTER COLUMN transfer_amount TYPE INTEGER;
+
+ALTER TABLE "topups"
+    ALTER COLUMN topup_amount TYPE INTEGER;
+
+ALTER TABLE "saldo"
+    ALTER COLUMN total_balance TYPE INTEGER,
+    ALTER COLUMN withdraw_amount TYPE INTEGER;
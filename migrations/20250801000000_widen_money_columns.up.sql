@@ -0,0 +1,13 @@
+-- Add up migration script here
+ALTER TABLE "saldo"
+    ALTER COLUMN total_balance TYPE BIGINT,
+    ALTER COLUMN withdraw_amount TYPE BIGINT;
+
+ALTER TABLE "topups"
+    ALTER COLUMN topup_amount TYPE BIGINT;
+
+ALTER TABLE "transfers"
+    ALTER COLUMN transfer_amount TYPE BIGINT;
+
+ALTER TABLE withdraws
+    ALTER COLUMN withdraw_amount TYPE BIGINT;
@@ -0,0 +1,3 @@
+-- Add up migration script here
+ALTER TABLE "users"
+    ADD COLUMN IF NOT EXISTS role VARCHAR(20) NOT NULL DEFAULT 'user';
@@ -0,0 +1,6 @@
+-- Add down migration script here
+ALTER TABLE "withdraws" DROP COLUMN IF EXISTS deleted_at;
+ALTER TABLE "transfers" DROP COLUMN IF EXISTS deleted_at;
+ALTER TABLE "saldo" DROP COLUMN IF EXISTS deleted_at;
+ALTER TABLE "topups" DROP COLUMN IF EXISTS deleted_at;
+ALTER TABLE "users" DROP COLUMN IF EXISTS deleted_at;
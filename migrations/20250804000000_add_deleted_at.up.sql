@@ -0,0 +1,6 @@
+-- Add up migration script here
+ALTER TABLE "users" ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP;
+ALTER TABLE "topups" ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP;
+ALTER TABLE "saldo" ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP;
+ALTER TABLE "transfers" ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP;
+ALTER TABLE "withdraws" ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP;
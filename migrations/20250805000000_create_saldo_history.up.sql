@@ -0,0 +1,13 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS "saldo_history" (
+    saldo_history_id SERIAL PRIMARY KEY,
+    saldo_id INTEGER NOT NULL REFERENCES saldo(saldo_id),
+    user_id INTEGER NOT NULL REFERENCES users(user_id),
+    old_balance BIGINT NOT NULL,
+    new_balance BIGINT NOT NULL,
+    reason VARCHAR(20) NOT NULL,
+    reference_id INTEGER,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_saldo_history_user_id ON saldo_history(user_id, created_at DESC);
@@ -0,0 +1,7 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS uq_saldo_user_currency;
+
+ALTER TABLE "withdraws" DROP COLUMN IF EXISTS currency;
+ALTER TABLE "transfers" DROP COLUMN IF EXISTS currency;
+ALTER TABLE "topups" DROP COLUMN IF EXISTS currency;
+ALTER TABLE "saldo" DROP COLUMN IF EXISTS currency;
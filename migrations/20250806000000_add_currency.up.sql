@@ -0,0 +1,10 @@
+-- Add up migration script here
+-- Existing rows predate multi-currency support and are all in IDR.
+ALTER TABLE "saldo" ADD COLUMN IF NOT EXISTS currency VARCHAR(3) NOT NULL DEFAULT 'IDR';
+ALTER TABLE "topups" ADD COLUMN IF NOT EXISTS currency VARCHAR(3) NOT NULL DEFAULT 'IDR';
+ALTER TABLE "transfers" ADD COLUMN IF NOT EXISTS currency VARCHAR(3) NOT NULL DEFAULT 'IDR';
+ALTER TABLE "withdraws" ADD COLUMN IF NOT EXISTS currency VARCHAR(3) NOT NULL DEFAULT 'IDR';
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_saldo_user_currency
+    ON saldo(user_id, currency)
+    WHERE deleted_at IS NULL;
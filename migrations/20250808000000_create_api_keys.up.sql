@@ -0,0 +1,12 @@
+-- Add up migration script here
+-- Partner backends authenticate as `user_id` by signing requests with
+-- `secret`; the secret has to be kept verbatim to recompute the HMAC.
+CREATE TABLE IF NOT EXISTS "api_keys" (
+    api_key_id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    api_key VARCHAR(64) NOT NULL UNIQUE,
+    secret VARCHAR(64) NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    revoked_at TIMESTAMP
+);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+-- pg_trgm is left installed; other objects in the database may depend on it.
+DROP INDEX IF EXISTS idx_users_email_trgm;
+DROP INDEX IF EXISTS idx_users_lastname_trgm;
+DROP INDEX IF EXISTS idx_users_firstname_trgm;
@@ -0,0 +1,6 @@
+-- Add up migration script here
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_users_firstname_trgm ON users USING GIN (firstname gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_users_lastname_trgm ON users USING GIN (lastname gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_users_email_trgm ON users USING GIN (email gin_trgm_ops);
@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_transfers_due;
+
+ALTER TABLE "transfers"
+    DROP COLUMN IF EXISTS scheduled_at,
+    DROP COLUMN IF EXISTS status;
@@ -0,0 +1,8 @@
+-- Add up migration script here
+ALTER TABLE "transfers"
+    ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'completed',
+    ADD COLUMN IF NOT EXISTS scheduled_at TIMESTAMP;
+
+CREATE INDEX IF NOT EXISTS idx_transfers_due
+    ON transfers(scheduled_at)
+    WHERE status = 'pending' AND deleted_at IS NULL;
@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_withdraws_pending;
+
+ALTER TABLE "withdraws"
+    DROP COLUMN IF EXISTS status;
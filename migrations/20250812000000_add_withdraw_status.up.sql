@@ -0,0 +1,7 @@
+-- Add up migration script here
+ALTER TABLE "withdraws"
+    ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'completed';
+
+CREATE INDEX IF NOT EXISTS idx_withdraws_pending
+    ON withdraws(created_at)
+    WHERE status = 'pending' AND deleted_at IS NULL;
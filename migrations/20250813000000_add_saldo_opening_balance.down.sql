@@ -0,0 +1,3 @@
+-- Add down migration script here
+ALTER TABLE "saldo"
+    DROP COLUMN IF EXISTS opening_balance;
@@ -0,0 +1,22 @@
+-- Add up migration script here
+ALTER TABLE "saldo"
+    ADD COLUMN IF NOT EXISTS opening_balance BIGINT NOT NULL DEFAULT 0;
+
+-- Existing saldos never recorded what they were opened with, so derive it
+-- from the ledger. They reconcile as of this migration; later drift shows up.
+UPDATE "saldo" s
+SET opening_balance = s.total_balance
+    - COALESCE((SELECT SUM(t.topup_amount) FROM topups t
+        WHERE t.user_id = s.user_id AND t.currency = s.currency
+          AND t.deleted_at IS NULL), 0)
+    - COALESCE((SELECT SUM(t.transfer_amount) FROM transfers t
+        WHERE t.transfer_to = s.user_id AND t.currency = s.currency
+          AND t.deleted_at IS NULL AND t.status = 'completed'), 0)
+    + COALESCE((SELECT SUM(t.transfer_amount) FROM transfers t
+        WHERE t.transfer_from = s.user_id AND t.currency = s.currency
+          AND t.deleted_at IS NULL AND t.status = 'completed'), 0)
+    + COALESCE((SELECT SUM(w.withdraw_amount) FROM withdraws w
+        WHERE w.user_id = s.user_id AND w.currency = s.currency
+          AND w.deleted_at IS NULL AND w.status IN ('approved', 'completed')), 0)
+    - COALESCE((SELECT SUM(h.new_balance - h.old_balance) FROM saldo_history h
+        WHERE h.saldo_id = s.saldo_id AND h.reason = 'adjustment'), 0);
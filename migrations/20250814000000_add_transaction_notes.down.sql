@@ -0,0 +1,6 @@
+-- Add down migration script here
+ALTER TABLE "withdraws"
+    DROP COLUMN IF EXISTS note;
+
+ALTER TABLE "transfers"
+    DROP COLUMN IF EXISTS note;
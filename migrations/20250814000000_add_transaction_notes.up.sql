@@ -0,0 +1,6 @@
+-- Add up migration script here
+ALTER TABLE "transfers"
+    ADD COLUMN IF NOT EXISTS note VARCHAR(255);
+
+ALTER TABLE "withdraws"
+    ADD COLUMN IF NOT EXISTS note VARCHAR(255);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_transfers_reversed_transfer_id;
+
+ALTER TABLE "transfers"
+    DROP COLUMN IF EXISTS reversed_transfer_id;
@@ -0,0 +1,8 @@
+-- Add up migration script here
+ALTER TABLE "transfers"
+    ADD COLUMN IF NOT EXISTS reversed_transfer_id INTEGER REFERENCES transfers(transfer_id);
+
+-- At most one reversal per transfer, even under concurrent requests.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_transfers_reversed_transfer_id
+    ON transfers(reversed_transfer_id)
+    WHERE reversed_transfer_id IS NOT NULL;
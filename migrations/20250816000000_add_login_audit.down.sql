@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS "login_audit";
+
+ALTER TABLE "users"
+    DROP COLUMN IF EXISTS last_login_at;
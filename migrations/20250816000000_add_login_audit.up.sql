@@ -0,0 +1,18 @@
+-- Add up migration script here
+ALTER TABLE "users"
+    ADD COLUMN IF NOT EXISTS last_login_at TIMESTAMP;
+
+-- One row per login attempt that reached the password check. `user_id` is
+-- NULL when the email did not match any account.
+CREATE TABLE IF NOT EXISTS "login_audit" (
+    login_audit_id SERIAL PRIMARY KEY,
+    user_id INTEGER REFERENCES users(user_id) ON DELETE CASCADE,
+    email VARCHAR(100) NOT NULL,
+    ip VARCHAR(45),
+    user_agent VARCHAR(512),
+    success BOOLEAN NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_login_audit_user_id_created_at
+    ON login_audit(user_id, created_at DESC);
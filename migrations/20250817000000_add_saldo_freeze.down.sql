@@ -0,0 +1,4 @@
+-- Add down migration script here
+ALTER TABLE "saldo"
+    DROP COLUMN IF EXISTS frozen_reason,
+    DROP COLUMN IF EXISTS is_frozen;
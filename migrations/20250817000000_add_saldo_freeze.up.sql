@@ -0,0 +1,4 @@
+-- Add up migration script here
+ALTER TABLE "saldo"
+    ADD COLUMN IF NOT EXISTS is_frozen BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS frozen_reason VARCHAR(255);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS "fees";
+
+ALTER TABLE "transfers"
+    DROP COLUMN IF EXISTS fee;
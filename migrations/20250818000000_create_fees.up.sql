@@ -0,0 +1,17 @@
+-- Add up migration script here
+-- What the sender was charged on top of the amount, for display. The `fees`
+-- ledger below is the accounting record.
+ALTER TABLE "transfers"
+    ADD COLUMN IF NOT EXISTS fee BIGINT NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS "fees" (
+    fee_id SERIAL PRIMARY KEY,
+    transfer_id INTEGER NOT NULL REFERENCES transfers(transfer_id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    amount BIGINT NOT NULL CHECK (amount > 0),
+    currency VARCHAR(3) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_fees_user_id_currency
+    ON fees(user_id, currency);
@@ -0,0 +1,30 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::{domain::role::Role, model::api_key::ApiKey, utils::AppError};
+
+pub type DynApiKeyRepository = Arc<dyn ApiKeyRepositoryTrait + Send + Sync>;
+pub type DynApiKeyService = Arc<dyn ApiKeyServiceTrait + Send + Sync>;
+
+pub struct SignedRequest<'a> {
+    pub api_key: &'a str,
+    pub timestamp: &'a str,
+    pub nonce: &'a str,
+    pub signature: &'a str,
+    pub method: &'a str,
+    pub path: &'a str,
+    pub body: &'a [u8],
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ApiKeyRepositoryTrait {
+    async fn find_active(&self, api_key: &str) -> Result<Option<ApiKey>, AppError>;
+}
+
+#[async_trait]
+pub trait ApiKeyServiceTrait {
+    // Resolves a signed partner request to the user it acts as.
+    async fn authenticate(&self, request: &SignedRequest<'_>) -> Result<(i32, Role), AppError>;
+}
@@ -2,9 +2,19 @@ use anyhow::Result;
 use async_trait::async_trait;
 use std::sync::Arc;
 
-use crate::domain::{
-    request::auth::{LoginRequest, RegisterRequest},
-    response::{ApiResponse, ErrorResponse, user::UserResponse},
+use crate::{
+    config::Claims,
+    domain::{
+        request::auth::{
+            DeleteAccountRequest, ForgotPasswordRequest, LoginContext, LoginRequest,
+            RegisterRequest, ResendVerificationRequest, ResetPasswordRequest, VerifyEmailQuery,
+        },
+        response::{
+            ApiResponse, ErrorResponse,
+            auth::{CapabilitiesResponse, LoginAuditResponse, TokenPair},
+            user::UserResponse,
+        },
+    },
 };
 
 pub type DynAuthService = Arc<dyn AuthServiceTrait + Send + Sync>;
@@ -15,5 +25,40 @@ pub trait AuthServiceTrait {
         &self,
         input: &RegisterRequest,
     ) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
-    async fn login_user(&self, input: &LoginRequest) -> Result<ApiResponse<String>, ErrorResponse>;
+    async fn login_user(
+        &self,
+        input: &LoginRequest,
+        context: &LoginContext,
+    ) -> Result<ApiResponse<TokenPair>, ErrorResponse>;
+    async fn get_login_history(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<Vec<LoginAuditResponse>>, ErrorResponse>;
+    async fn get_capabilities(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<CapabilitiesResponse>, ErrorResponse>;
+    async fn refresh_token(&self, refresh: &str) -> Result<ApiResponse<TokenPair>, ErrorResponse>;
+    async fn logout(&self, claims: &Claims) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn delete_account(
+        &self,
+        claims: &Claims,
+        input: &DeleteAccountRequest,
+    ) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn request_password_reset(
+        &self,
+        input: &ForgotPasswordRequest,
+    ) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn reset_password(
+        &self,
+        input: &ResetPasswordRequest,
+    ) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn verify_email(
+        &self,
+        input: &VerifyEmailQuery,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
+    async fn resend_verification(
+        &self,
+        input: &ResendVerificationRequest,
+    ) -> Result<ApiResponse<()>, ErrorResponse>;
 }
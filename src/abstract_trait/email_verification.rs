@@ -0,0 +1,26 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::PgConnection;
+use std::sync::Arc;
+
+use crate::{model::email_verification::EmailVerification, utils::AppError};
+
+pub type DynEmailVerificationRepository = Arc<dyn EmailVerificationRepositoryTrait + Send + Sync>;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait EmailVerificationRepositoryTrait {
+    async fn create(
+        &self,
+        user_id: i32,
+        token_hash: &str,
+        expires_at: NaiveDateTime,
+    ) -> Result<EmailVerification, AppError>;
+    async fn consume_tx(
+        &self,
+        conn: &mut PgConnection,
+        token_hash: &str,
+    ) -> Result<Option<EmailVerification>, AppError>;
+    async fn delete_expired(&self) -> Result<u64, AppError>;
+}
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        request::{FindHistoryRequest, HistoryFilter, StatementRequest},
+        response::{ApiResponsePagination, ErrorResponse, history::HistoryEntryResponse},
+        role::Role,
+    },
+    model::history::{BalanceSnapshot, HistoryEntry},
+    utils::AppError,
+};
+
+pub type DynHistoryRepository = Arc<dyn HistoryRepositoryTrait + Send + Sync>;
+pub type DynHistoryService = Arc<dyn HistoryServiceTrait + Send + Sync>;
+pub type HistoryCsvStream = BoxStream<'static, Result<String, AppError>>;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait HistoryRepositoryTrait {
+    async fn find_by_user(
+        &self,
+        user_id: i32,
+        page: i32,
+        page_size: i32,
+        filter: HistoryFilter,
+    ) -> Result<(Vec<HistoryEntry>, i64), AppError>;
+    async fn balances_at(
+        &self,
+        user_id: i32,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<BalanceSnapshot>, AppError>;
+}
+
+#[async_trait]
+pub trait HistoryServiceTrait {
+    async fn get_history(
+        &self,
+        id: i32,
+        req: &FindHistoryRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponsePagination<Vec<HistoryEntryResponse>>, ErrorResponse>;
+    async fn export_history_csv(
+        &self,
+        id: i32,
+        req: &FindHistoryRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<HistoryCsvStream, ErrorResponse>;
+    async fn export_statement_pdf(
+        &self,
+        id: i32,
+        req: &StatementRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<Vec<u8>, ErrorResponse>;
+}
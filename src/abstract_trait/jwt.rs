@@ -1,4 +1,4 @@
-use crate::utils::AppError;
+use crate::{config::Claims, domain::role::Role, utils::AppError};
 use async_trait::async_trait;
 use std::sync::Arc;
 
@@ -6,8 +6,15 @@ use anyhow::Result;
 
 #[async_trait]
 pub trait JwtServiceTrait: Send + Sync {
-    fn generate_token(&self, user_id: i64) -> Result<String, AppError>;
-    fn verify_token(&self, token: &str) -> Result<i64, AppError>;
+    fn generate_token(&self, user_id: i64, role: Role, sid: &str) -> Result<String, AppError>;
+    fn generate_refresh_token(&self, user_id: i64, sid: &str) -> Result<String, AppError>;
+    fn verify_token(&self, token: &str) -> Result<Claims, AppError>;
+    // `false` when the token was already revoked.
+    fn revoke_token(&self, jti: &str, exp: usize) -> bool;
+    // Revokes every token carrying this `sid`, including later refreshes.
+    fn revoke_session(&self, sid: &str);
+    fn purge_expired_revocations(&self) -> usize;
+    fn verify_refresh_token(&self, token: &str) -> Result<Claims, AppError>;
 }
 
 pub type DynJwtService = Arc<dyn JwtServiceTrait + Send + Sync>;
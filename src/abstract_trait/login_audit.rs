@@ -0,0 +1,24 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::{domain::request::auth::LoginContext, model::login_audit::LoginAudit, utils::AppError};
+
+pub type DynLoginAuditRepository = Arc<dyn LoginAuditRepositoryTrait + Send + Sync>;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait LoginAuditRepositoryTrait {
+    async fn create(
+        &self,
+        user_id: Option<i32>,
+        email: &str,
+        context: &LoginContext,
+        success: bool,
+    ) -> Result<LoginAudit, AppError>;
+    async fn find_recent_by_user(
+        &self,
+        user_id: i32,
+        limit: u64,
+    ) -> Result<Vec<LoginAudit>, AppError>;
+}
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::utils::AppError;
+
+#[async_trait]
+pub trait MailerTrait: Send + Sync {
+    async fn send_password_reset(&self, email: &str, token: &str) -> Result<(), AppError>;
+    async fn send_email_verification(&self, email: &str, token: &str) -> Result<(), AppError>;
+}
+
+pub type DynMailer = Arc<dyn MailerTrait + Send + Sync>;
@@ -1,16 +1,41 @@
+pub mod api_key;
 pub mod auth;
+pub mod email_verification;
 pub mod hashing;
+pub mod history;
 pub mod jwt;
+pub mod login_audit;
+pub mod mailer;
+pub mod password_reset;
+pub mod reconciliation;
 pub mod saldo;
 pub mod topup;
 pub mod transfer;
 pub mod user;
+pub mod webhook;
 pub mod withdraw;
 
+pub use self::api_key::{
+    ApiKeyRepositoryTrait, ApiKeyServiceTrait, DynApiKeyRepository, DynApiKeyService, SignedRequest,
+};
 pub use self::auth::{AuthServiceTrait, DynAuthService};
+pub use self::email_verification::{
+    DynEmailVerificationRepository, EmailVerificationRepositoryTrait,
+};
 pub use self::hashing::{DynHashing, HashingTrait};
+pub use self::history::{
+    DynHistoryRepository, DynHistoryService, HistoryCsvStream, HistoryRepositoryTrait,
+    HistoryServiceTrait,
+};
 
 pub use self::jwt::{DynJwtService, JwtServiceTrait};
+pub use self::login_audit::{DynLoginAuditRepository, LoginAuditRepositoryTrait};
+pub use self::mailer::{DynMailer, MailerTrait};
+pub use self::password_reset::{DynPasswordResetRepository, PasswordResetRepositoryTrait};
+pub use self::reconciliation::{
+    DynReconciliationRepository, DynReconciliationService, ReconciliationRepositoryTrait,
+    ReconciliationServiceTrait,
+};
 
 pub use self::saldo::{
     DynSaldoRepository, DynSaldoService, SaldoRepositoryTrait, SaldoServiceTrait,
@@ -26,6 +51,11 @@ pub use self::transfer::{
 
 pub use self::user::{DynUserRepository, DynUserService, UserRepositoryTrait, UserServiceTrait};
 
+pub use self::webhook::{
+    DynWebhookNotifier, DynWebhookRepository, DynWebhookService, WebhookNotifierTrait,
+    WebhookRepositoryTrait, WebhookServiceTrait,
+};
+
 pub use self::withdraw::{
     DynWithdrawRepository, DynWithdrawService, WithdrawRepositoryTrait, WithdrawServiceTrait,
 };
@@ -0,0 +1,26 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::PgConnection;
+use std::sync::Arc;
+
+use crate::{model::password_reset::PasswordReset, utils::AppError};
+
+pub type DynPasswordResetRepository = Arc<dyn PasswordResetRepositoryTrait + Send + Sync>;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait PasswordResetRepositoryTrait {
+    async fn create(
+        &self,
+        user_id: i32,
+        token_hash: &str,
+        expires_at: NaiveDateTime,
+    ) -> Result<PasswordReset, AppError>;
+    async fn consume_tx(
+        &self,
+        conn: &mut PgConnection,
+        token_hash: &str,
+    ) -> Result<Option<PasswordReset>, AppError>;
+    async fn delete_expired(&self) -> Result<u64, AppError>;
+}
@@ -0,0 +1,28 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::{
+    domain::response::{ApiResponse, ErrorResponse, reconciliation::ReconcileResponse},
+    model::reconciliation::Reconciliation,
+    utils::AppError,
+};
+
+pub type DynReconciliationRepository = Arc<dyn ReconciliationRepositoryTrait + Send + Sync>;
+pub type DynReconciliationService = Arc<dyn ReconciliationServiceTrait + Send + Sync>;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ReconciliationRepositoryTrait {
+    async fn find_by_user(&self, user_id: i32) -> Result<Vec<Reconciliation>, AppError>;
+    async fn find_mismatches(&self) -> Result<Vec<Reconciliation>, AppError>;
+}
+
+#[async_trait]
+pub trait ReconciliationServiceTrait {
+    async fn reconcile_user(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<Vec<ReconcileResponse>>, ErrorResponse>;
+    async fn find_mismatches(&self) -> Result<ApiResponse<Vec<ReconcileResponse>>, ErrorResponse>;
+}
@@ -1,39 +1,119 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use sqlx::PgConnection;
 use std::sync::Arc;
 
 use crate::{
     domain::{
+        currency::Currency,
         request::{
-            CreateSaldoRequest, FindAllSaldoRequest, UpdateSaldoBalance, UpdateSaldoRequest,
-            UpdateSaldoWithdraw,
+            CreateSaldoRequest, FindAllSaldoRequest, FindSaldoHistoryRequest,
+            FindUserSaldosRequest, FreezeSaldoRequest, IncrementSaldoBalance, Search, Sort,
+            UpdateSaldoBalance, UpdateSaldoRequest, UpdateSaldoWithdraw,
         },
-        response::{ApiResponse, ApiResponsePagination, ErrorResponse, saldo::SaldoResponse},
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            saldo::{SaldoResponse, SaldoStatsResponse},
+            saldo_history::SaldoHistoryResponse,
+        },
+        role::Role,
+    },
+    model::{
+        saldo::{Saldo, SaldoStats},
+        saldo_history::SaldoHistory,
     },
-    model::saldo::Saldo,
     utils::AppError,
 };
 
 pub type DynSaldoRepository = Arc<dyn SaldoRepositoryTrait + Send + Sync>;
 pub type DynSaldoService = Arc<dyn SaldoServiceTrait + Send + Sync>;
 
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait SaldoRepositoryTrait {
     async fn find_all(
         &self,
         page: i32,
         page_size: i32,
-        search: Option<String>,
+        search: Option<Search>,
+        sort: Sort,
+        include_deleted: bool,
     ) -> Result<(Vec<Saldo>, i64), AppError>;
     async fn find_by_id(&self, id: i32) -> Result<Option<Saldo>, AppError>;
 
-    async fn find_by_users_id(&self, id: i32) -> Result<Vec<Saldo>, AppError>;
-    async fn find_by_user_id(&self, id: i32) -> Result<Option<Saldo>, AppError>;
+    async fn find_by_user_for_update(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<Vec<Saldo>, AppError>;
+    async fn find_by_users_id(
+        &self,
+        id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Saldo>, i64), AppError>;
+    async fn find_by_user_and_currency(
+        &self,
+        user_id: i32,
+        currency: Currency,
+    ) -> Result<Option<Saldo>, AppError>;
+    async fn find_by_user_and_currency_for_update(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+        currency: Currency,
+    ) -> Result<Option<Saldo>, AppError>;
     async fn create(&self, input: &CreateSaldoRequest) -> Result<Saldo, AppError>;
+    async fn create_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &CreateSaldoRequest,
+    ) -> Result<Saldo, AppError>;
+    async fn create_initial_tx(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+        currency: Currency,
+    ) -> Result<Saldo, AppError>;
     async fn update(&self, input: &UpdateSaldoRequest) -> Result<Saldo, AppError>;
     async fn update_balance(&self, input: &UpdateSaldoBalance) -> Result<Saldo, AppError>;
+    async fn update_balance_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &UpdateSaldoBalance,
+    ) -> Result<Saldo, AppError>;
+    // `None` when the user has no live, unfrozen saldo in that currency.
+    async fn increment_balance_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &IncrementSaldoBalance,
+    ) -> Result<Option<Saldo>, AppError>;
     async fn update_saldo_withdraw(&self, input: &UpdateSaldoWithdraw) -> Result<Saldo, AppError>;
+    async fn update_saldo_withdraw_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &UpdateSaldoWithdraw,
+    ) -> Result<Saldo, AppError>;
     async fn delete(&self, id: i32) -> Result<(), AppError>;
+    async fn delete_by_user_tx(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<u64, AppError>;
+    async fn find_history(
+        &self,
+        user_id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<SaldoHistory>, i64), AppError>;
+    async fn restore(&self, id: i32) -> Result<Saldo, AppError>;
+    async fn set_frozen(
+        &self,
+        user_id: i32,
+        frozen: bool,
+        reason: Option<String>,
+    ) -> Result<Vec<Saldo>, AppError>;
+    async fn stats(&self) -> Result<Vec<SaldoStats>, AppError>;
 }
 
 #[async_trait]
@@ -42,15 +122,24 @@ pub trait SaldoServiceTrait {
         &self,
         req: &FindAllSaldoRequest,
     ) -> Result<ApiResponsePagination<Vec<SaldoResponse>>, ErrorResponse>;
-    async fn get_saldo(&self, id: i32)
-    -> Result<ApiResponse<Option<SaldoResponse>>, ErrorResponse>;
+    async fn get_saldo(
+        &self,
+        id: i32,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<Option<SaldoResponse>>, ErrorResponse>;
     async fn get_saldo_users(
         &self,
         id: i32,
-    ) -> Result<ApiResponse<Option<Vec<SaldoResponse>>>, ErrorResponse>;
+        req: &FindUserSaldosRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponsePagination<Vec<SaldoResponse>>, ErrorResponse>;
     async fn get_saldo_user(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<SaldoResponse>>, ErrorResponse>;
     async fn create_saldo(
         &self,
@@ -59,6 +148,31 @@ pub trait SaldoServiceTrait {
     async fn update_saldo(
         &self,
         input: &UpdateSaldoRequest,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<SaldoResponse>>, ErrorResponse>;
-    async fn delete_saldo(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn delete_saldo(
+        &self,
+        id: i32,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn restore_saldo(&self, id: i32) -> Result<ApiResponse<SaldoResponse>, ErrorResponse>;
+    async fn get_saldo_history(
+        &self,
+        id: i32,
+        req: &FindSaldoHistoryRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponsePagination<Vec<SaldoHistoryResponse>>, ErrorResponse>;
+    async fn get_saldo_stats(&self) -> Result<ApiResponse<Vec<SaldoStatsResponse>>, ErrorResponse>;
+    async fn freeze_saldo(
+        &self,
+        user_id: i32,
+        input: &FreezeSaldoRequest,
+    ) -> Result<ApiResponse<Vec<SaldoResponse>>, ErrorResponse>;
+    async fn unfreeze_saldo(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<Vec<SaldoResponse>>, ErrorResponse>;
 }
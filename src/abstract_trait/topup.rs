@@ -1,35 +1,75 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::PgConnection;
 use std::sync::Arc;
 
 use crate::{
     domain::{
-        request::{CreateTopupRequest, FindAllTopupRequest, UpdateTopupAmount, UpdateTopupRequest},
-        response::{ApiResponse, ApiResponsePagination, ErrorResponse, topup::TopupResponse},
+        currency::Currency,
+        money::Money,
+        request::{
+            CreateBulkTopupRequest, CreateTopupRequest, FindAllTopupRequest, Search, Sort,
+            TopupMonthlyStatsRequest, TransactionFilter, UpdateTopupAmount, UpdateTopupRequest,
+        },
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            topup::{BulkTopupResult, TopupMonthlyStatsResponse, TopupResponse},
+        },
+        role::Role,
     },
-    model::topup::Topup,
+    model::topup::{Topup, TopupMonthlyStats},
     utils::AppError,
 };
 
 pub type DynTopupRepository = Arc<dyn TopupRepositoryTrait + Send + Sync>;
 pub type DynTopupService = Arc<dyn TopupServiceTrait + Send + Sync>;
 
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait TopupRepositoryTrait {
     async fn find_all(
         &self,
         page: i32,
         page_size: i32,
-        search: Option<String>,
+        search: Option<Search>,
+        sort: Sort,
+        filter: TransactionFilter,
     ) -> Result<(Vec<Topup>, i64), AppError>;
 
     async fn find_by_id(&self, id: i32) -> Result<Option<Topup>, AppError>;
     async fn find_by_users(&self, id: i32) -> Result<Vec<Topup>, AppError>;
     async fn find_by_user(&self, id: i32) -> Result<Option<Topup>, AppError>;
+    async fn sum_by_user(&self, user_id: i32) -> Result<(Money, i64), AppError>;
+    async fn monthly_stats(
+        &self,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+        user_id: Option<i32>,
+        currency: Currency,
+    ) -> Result<Vec<TopupMonthlyStats>, AppError>;
     async fn create(&self, input: &CreateTopupRequest) -> Result<Topup, AppError>;
+    async fn create_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &CreateTopupRequest,
+    ) -> Result<Topup, AppError>;
     async fn update(&self, input: &UpdateTopupRequest) -> Result<Topup, AppError>;
     async fn update_amount(&self, input: &UpdateTopupAmount) -> Result<Topup, AppError>;
+    async fn update_amount_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &UpdateTopupAmount,
+    ) -> Result<Topup, AppError>;
     async fn delete(&self, id: i32) -> Result<(), AppError>;
+    async fn delete_tx(&self, conn: &mut PgConnection, id: i32) -> Result<Topup, AppError>;
+    async fn delete_by_user_tx(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<u64, AppError>;
+    async fn restore(&self, id: i32) -> Result<Topup, AppError>;
+    async fn restore_tx(&self, conn: &mut PgConnection, id: i32) -> Result<Topup, AppError>;
 }
 
 #[async_trait]
@@ -38,23 +78,51 @@ pub trait TopupServiceTrait {
         &self,
         req: &FindAllTopupRequest,
     ) -> Result<ApiResponsePagination<Vec<TopupResponse>>, ErrorResponse>;
-    async fn get_topup(&self, id: i32)
-    -> Result<ApiResponse<Option<TopupResponse>>, ErrorResponse>;
+    async fn get_topup(
+        &self,
+        id: i32,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<Option<TopupResponse>>, ErrorResponse>;
     async fn get_topup_users(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<Vec<TopupResponse>>>, ErrorResponse>;
     async fn get_topup_user(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<TopupResponse>>, ErrorResponse>;
     async fn create_topup(
         &self,
         input: &CreateTopupRequest,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<TopupResponse>, ErrorResponse>;
+    async fn create_bulk_topup(
+        &self,
+        input: &CreateBulkTopupRequest,
+    ) -> Result<ApiResponse<Vec<BulkTopupResult>>, ErrorResponse>;
     async fn update_topup(
         &self,
         input: &UpdateTopupRequest,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<TopupResponse>>, ErrorResponse>;
-    async fn delete_topup(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn delete_topup(
+        &self,
+        id: i32,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn restore_topup(&self, id: i32) -> Result<ApiResponse<TopupResponse>, ErrorResponse>;
+    async fn get_monthly_stats(
+        &self,
+        req: &TopupMonthlyStatsRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<Vec<TopupMonthlyStatsResponse>>, ErrorResponse>;
 }
@@ -1,40 +1,151 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::PgConnection;
 use std::sync::Arc;
 
 use crate::{
     domain::{
+        currency::Currency,
+        money::Money,
         request::{
-            CreateTransferRequest, FindAllTransferRequest, UpdateTransferAmountRequest,
-            UpdateTransferRequest,
+            CreateBatchTransferRequest, CreateTransferRequest, FindAllTransferRequest,
+            FindTransfersBetweenRequest, FindUserTransfersRequest, Search, Sort, TransactionFilter,
+            TransferAllRequest, TransferDirection, TransferNetFlowRequest,
+            UpdateTransferAmountRequest, UpdateTransferRequest,
         },
-        response::{ApiResponse, ApiResponsePagination, ErrorResponse, transfer::TransferResponse},
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            transfer::{NetFlowResponse, TransferReceiptResponse, TransferResponse},
+        },
+        role::Role,
+        transfer_status::TransferStatus,
     },
-    model::transfer::Transfer,
+    model::transfer::{Transfer, TransferCounterparty, TransferNetFlow, TransferReceipt},
     utils::AppError,
 };
 
 pub type DynTransferRepository = Arc<dyn TransferRepositoryTrait + Send + Sync>;
 pub type DynTransferService = Arc<dyn TransferServiceTrait + Send + Sync>;
 
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait TransferRepositoryTrait {
     async fn find_all(
         &self,
         page: i32,
         page_size: i32,
-        search: Option<String>,
+        search: Option<Search>,
+        sort: Sort,
+        filter: TransactionFilter,
     ) -> Result<(Vec<Transfer>, i64), AppError>;
     async fn find_by_id(&self, id: i32) -> Result<Option<Transfer>, AppError>;
-    async fn find_by_users(&self, id: i32) -> Result<Vec<Transfer>, AppError>;
+    async fn find_receipt(&self, id: i32) -> Result<Option<TransferReceipt>, AppError>;
+    async fn find_by_users(
+        &self,
+        id: i32,
+        direction: TransferDirection,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Transfer>, i64), AppError>;
     async fn find_by_user(&self, id: i32) -> Result<Option<Transfer>, AppError>;
+    async fn sum_sent_by_user(&self, user_id: i32) -> Result<(Money, i64), AppError>;
+    async fn sum_received_by_user(&self, user_id: i32) -> Result<(Money, i64), AppError>;
+    async fn net_flow(
+        &self,
+        user_id: i32,
+        counterparty: Option<i32>,
+        from: Option<NaiveDateTime>,
+        to: NaiveDateTime,
+        currency: Currency,
+    ) -> Result<TransferNetFlow, AppError>;
+    async fn find_counterparties(
+        &self,
+        user_id: i32,
+        currency: Currency,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<TransferCounterparty>, i64), AppError>;
+    async fn find_between_users(
+        &self,
+        from: i32,
+        to: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Transfer>, i64), AppError>;
     async fn create(&self, input: &CreateTransferRequest) -> Result<Transfer, AppError>;
-    async fn update(&self, input: &UpdateTransferRequest) -> Result<Transfer, AppError>;
+    async fn create_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &CreateTransferRequest,
+    ) -> Result<Transfer, AppError>;
+    async fn update_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &UpdateTransferRequest,
+    ) -> Result<Transfer, AppError>;
     async fn update_amount(
         &self,
         input: &UpdateTransferAmountRequest,
     ) -> Result<Transfer, AppError>;
     async fn delete(&self, id: i32) -> Result<(), AppError>;
+    async fn delete_tx(&self, conn: &mut PgConnection, id: i32) -> Result<Transfer, AppError>;
+    // Completed transfers are left alone: they are the counterparty's ledger too.
+    async fn delete_unsettled_by_user_tx(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<u64, AppError>;
+    async fn restore(&self, id: i32) -> Result<Transfer, AppError>;
+    async fn restore_tx(&self, conn: &mut PgConnection, id: i32) -> Result<Transfer, AppError>;
+    async fn create_scheduled(
+        &self,
+        input: &CreateTransferRequest,
+        scheduled_at: NaiveDateTime,
+    ) -> Result<Transfer, AppError>;
+    async fn find_scheduled(&self, transfer_from: Option<i32>) -> Result<Vec<Transfer>, AppError>;
+    async fn find_due(&self, now: NaiveDateTime, limit: u64) -> Result<Vec<Transfer>, AppError>;
+    async fn find_by_id_for_update_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+    ) -> Result<Option<Transfer>, AppError>;
+    async fn claim_pending_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+    ) -> Result<Option<Transfer>, AppError>;
+    async fn set_status_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+        status: TransferStatus,
+    ) -> Result<Transfer, AppError>;
+    async fn mark_reversed_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+    ) -> Result<Option<Transfer>, AppError>;
+    async fn create_reversal_tx(
+        &self,
+        conn: &mut PgConnection,
+        original: &Transfer,
+    ) -> Result<Transfer, AppError>;
+    // Sets the fee of `transfer`, replacing whatever was charged before; a
+    // zero fee leaves no charge behind.
+    async fn charge_fee_tx(
+        &self,
+        conn: &mut PgConnection,
+        transfer: &Transfer,
+        fee: Money,
+    ) -> Result<Transfer, AppError>;
+    // Drops the fee charge of `transfer` from the ledger but keeps
+    // `transfers.fee`, so a restore can charge the same fee again.
+    async fn refund_fee_tx(
+        &self,
+        conn: &mut PgConnection,
+        transfer: &Transfer,
+    ) -> Result<(), AppError>;
 }
 
 #[async_trait]
@@ -46,22 +157,83 @@ pub trait TransferServiceTrait {
     async fn get_transfer(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<TransferResponse>>, ErrorResponse>;
+    async fn get_transfer_receipt(
+        &self,
+        id: i32,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<TransferReceiptResponse>, ErrorResponse>;
     async fn get_transfer_users(
         &self,
         id: i32,
-    ) -> Result<ApiResponse<Option<Vec<TransferResponse>>>, ErrorResponse>;
+        req: &FindUserTransfersRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponsePagination<Vec<TransferResponse>>, ErrorResponse>;
+    async fn get_transfers_between(
+        &self,
+        req: &FindTransfersBetweenRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponsePagination<Vec<TransferResponse>>, ErrorResponse>;
+    async fn get_net_flow(
+        &self,
+        id: i32,
+        req: &TransferNetFlowRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<NetFlowResponse>, ErrorResponse>;
     async fn get_transfer_user(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<TransferResponse>>, ErrorResponse>;
     async fn create_transfer(
         &self,
         input: &CreateTransferRequest,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<TransferResponse>, ErrorResponse>;
+    async fn create_batch_transfer(
+        &self,
+        input: &CreateBatchTransferRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<Vec<TransferResponse>>, ErrorResponse>;
+    // `data` is `None` when there was nothing to move.
+    async fn transfer_all(
+        &self,
+        user_id: i32,
+        input: &TransferAllRequest,
+    ) -> Result<ApiResponse<Option<TransferResponse>>, ErrorResponse>;
     async fn update_transfer(
         &self,
         input: &UpdateTransferRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<TransferResponse>, ErrorResponse>;
+    async fn delete_transfer(
+        &self,
+        id: i32,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn restore_transfer(
+        &self,
+        id: i32,
     ) -> Result<ApiResponse<TransferResponse>, ErrorResponse>;
-    async fn delete_transfer(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn reverse_transfer(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<TransferResponse>, ErrorResponse>;
+    async fn get_scheduled_transfers(
+        &self,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<Vec<TransferResponse>>, ErrorResponse>;
+    async fn execute_due_transfers(&self) -> Result<usize, ErrorResponse>;
 }
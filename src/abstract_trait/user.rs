@@ -1,11 +1,20 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use sqlx::PgConnection;
 use std::sync::Arc;
 
 use crate::{
     domain::{
-        request::{CreateUserRequest, FindAllUserRequest, RegisterRequest, UpdateUserRequest},
-        response::{ApiResponse, ApiResponsePagination, ErrorResponse, user::UserResponse},
+        request::{
+            CreateUserRequest, FindAllUserRequest, FindUserCounterpartiesRequest,
+            FindUsersBatchRequest, GetUserQuery, RegisterRequest, Sort, UpdateUserRequest,
+            UpdateUserRoleRequest, UserSearch,
+        },
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            user::{CounterpartyResponse, UserResponse, UserSummaryResponse},
+        },
+        role::Role,
     },
     model::user::User,
     utils::AppError,
@@ -14,20 +23,42 @@ use crate::{
 pub type DynUserRepository = Arc<dyn UserRepositoryTrait + Send + Sync>;
 pub type DynUserService = Arc<dyn UserServiceTrait + Send + Sync>;
 
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait UserRepositoryTrait {
     async fn find_all(
         &self,
         page: i32,
         page_size: i32,
-        search: Option<String>,
+        search: Option<UserSearch>,
+        sort: Sort,
+        include_deleted: bool,
     ) -> Result<(Vec<User>, i64), AppError>;
     async fn find_by_email_exists(&self, email: &str) -> Result<bool, AppError>;
+    // Whether any live account holds the admin role.
+    async fn admin_exists(&self) -> Result<bool, AppError>;
     async fn create_user(&self, input: &CreateUserRequest) -> Result<User, AppError>;
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError>;
     async fn find_by_id(&self, id: i32) -> Result<Option<User>, AppError>;
+    async fn find_by_noc(&self, noc_transfer: &str) -> Result<Option<User>, AppError>;
+    async fn find_existing_ids(&self, ids: &[i32]) -> Result<Vec<i32>, AppError>;
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<User>, AppError>;
     async fn update_user(&self, input: &UpdateUserRequest) -> Result<User, AppError>;
+    async fn update_role(&self, id: i32, role: Role) -> Result<User, AppError>;
+    async fn update_noc_transfer(&self, id: i32, noc_transfer: &str) -> Result<User, AppError>;
+    async fn mark_verified(&self, id: i32) -> Result<User, AppError>;
+    async fn mark_verified_tx(&self, conn: &mut PgConnection, id: i32) -> Result<User, AppError>;
+    async fn record_login(&self, id: i32) -> Result<(), AppError>;
+    async fn update_password(&self, id: i32, password_hash: &str) -> Result<User, AppError>;
+    async fn update_password_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+        password_hash: &str,
+    ) -> Result<User, AppError>;
     async fn delete_user(&self, id: i32) -> Result<(), AppError>;
+    async fn delete_user_tx(&self, conn: &mut PgConnection, id: i32) -> Result<(), AppError>;
+    async fn restore(&self, id: i32) -> Result<User, AppError>;
 }
 
 #[async_trait]
@@ -36,7 +67,33 @@ pub trait UserServiceTrait {
         &self,
         req: &FindAllUserRequest,
     ) -> Result<ApiResponsePagination<Vec<UserResponse>>, ErrorResponse>;
-    async fn get_user(&self, id: i32) -> Result<ApiResponse<Option<UserResponse>>, ErrorResponse>;
+    async fn get_users_batch(
+        &self,
+        input: &FindUsersBatchRequest,
+    ) -> Result<ApiResponse<Vec<UserResponse>>, ErrorResponse>;
+    async fn get_user(
+        &self,
+        id: i32,
+        query: &GetUserQuery,
+        role: Role,
+    ) -> Result<ApiResponse<Option<UserResponse>>, ErrorResponse>;
+    async fn get_user_by_noc(
+        &self,
+        noc_transfer: &str,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
+    async fn get_user_summary(
+        &self,
+        id: i32,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<UserSummaryResponse>, ErrorResponse>;
+    async fn get_user_counterparties(
+        &self,
+        id: i32,
+        req: &FindUserCounterpartiesRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponsePagination<Vec<CounterpartyResponse>>, ErrorResponse>;
     async fn create_user(
         &self,
         input: &RegisterRequest,
@@ -44,6 +101,19 @@ pub trait UserServiceTrait {
     async fn update_user(
         &self,
         input: &UpdateUserRequest,
+        user_id: i32,
+        role: Role,
     ) -> Result<Option<ApiResponse<UserResponse>>, ErrorResponse>;
+    async fn rotate_noc_transfer(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
+    async fn update_user_role(
+        &self,
+        id: i32,
+        input: &UpdateUserRoleRequest,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
+    async fn seed_admin(&self, email: &str, password: Option<&str>) -> Result<(), ErrorResponse>;
     async fn delete_user(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn restore_user(&self, id: i32) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
 }
@@ -0,0 +1,41 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        request::CreateWebhookRequest,
+        response::{ApiResponse, ErrorResponse, webhook::WebhookResponse},
+        webhook::WebhookEvent,
+    },
+    model::webhook::Webhook,
+    utils::AppError,
+};
+
+pub type DynWebhookRepository = Arc<dyn WebhookRepositoryTrait + Send + Sync>;
+pub type DynWebhookService = Arc<dyn WebhookServiceTrait + Send + Sync>;
+pub type DynWebhookNotifier = Arc<dyn WebhookNotifierTrait + Send + Sync>;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait WebhookRepositoryTrait {
+    async fn find_by_user_id(&self, user_id: i32) -> Result<Vec<Webhook>, AppError>;
+    async fn create(&self, user_id: i32, url: &str, secret: &str) -> Result<Webhook, AppError>;
+}
+
+#[async_trait]
+pub trait WebhookServiceTrait {
+    async fn create_webhook(
+        &self,
+        user_id: i32,
+        input: &CreateWebhookRequest,
+    ) -> Result<ApiResponse<WebhookResponse>, ErrorResponse>;
+}
+
+// Deliberately synchronous: callers are on the request path and must never
+// wait on a webhook receiver.
+#[cfg_attr(test, mockall::automock)]
+pub trait WebhookNotifierTrait {
+    fn notify(&self, user_id: i32, event: WebhookEvent, data: Value);
+}
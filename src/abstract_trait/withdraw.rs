@@ -1,11 +1,20 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::PgConnection;
 use std::sync::Arc;
 
 use crate::{
     domain::{
-        request::{CreateWithdrawRequest, FindAllWithdrawRequest, UpdateWithdrawRequest},
+        currency::Currency,
+        money::Money,
+        request::{
+            CreateWithdrawRequest, FindAllWithdrawRequest, Search, Sort, TransactionFilter,
+            UpdateWithdrawRequest,
+        },
         response::{ApiResponse, ApiResponsePagination, ErrorResponse, withdraw::WithdrawResponse},
+        role::Role,
+        withdraw_status::WithdrawStatus,
     },
     model::withdraw::Withdraw,
     utils::AppError,
@@ -14,20 +23,69 @@ use crate::{
 pub type DynWithdrawRepository = Arc<dyn WithdrawRepositoryTrait + Send + Sync>;
 pub type DynWithdrawService = Arc<dyn WithdrawServiceTrait + Send + Sync>;
 
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait WithdrawRepositoryTrait {
     async fn find_all(
         &self,
         page: i32,
         page_size: i32,
-        search: Option<String>,
+        search: Option<Search>,
+        sort: Sort,
+        filter: TransactionFilter,
     ) -> Result<(Vec<Withdraw>, i64), AppError>;
     async fn find_by_id(&self, id: i32) -> Result<Option<Withdraw>, AppError>;
     async fn find_by_users(&self, id: i32) -> Result<Vec<Withdraw>, AppError>;
     async fn find_by_user(&self, id: i32) -> Result<Option<Withdraw>, AppError>;
-    async fn create(&self, input: &CreateWithdrawRequest) -> Result<Withdraw, AppError>;
-    async fn update(&self, input: &UpdateWithdrawRequest) -> Result<Withdraw, AppError>;
+    async fn sum_by_user(&self, user_id: i32) -> Result<(Money, i64), AppError>;
+    async fn sum_for_day_tx(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+        currency: Currency,
+        day: NaiveDate,
+    ) -> Result<Money, AppError>;
+    async fn create(
+        &self,
+        input: &CreateWithdrawRequest,
+        status: WithdrawStatus,
+    ) -> Result<Withdraw, AppError>;
+    async fn create_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &CreateWithdrawRequest,
+        status: WithdrawStatus,
+    ) -> Result<Withdraw, AppError>;
+    async fn update_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &UpdateWithdrawRequest,
+    ) -> Result<Withdraw, AppError>;
     async fn delete(&self, id: i32) -> Result<(), AppError>;
+    async fn delete_tx(&self, conn: &mut PgConnection, id: i32) -> Result<Withdraw, AppError>;
+    async fn delete_by_user_tx(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<u64, AppError>;
+    async fn restore(&self, id: i32) -> Result<Withdraw, AppError>;
+    async fn restore_tx(&self, conn: &mut PgConnection, id: i32) -> Result<Withdraw, AppError>;
+    async fn find_by_id_for_update_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+    ) -> Result<Option<Withdraw>, AppError>;
+    async fn find_pending_for_update_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+    ) -> Result<Option<Withdraw>, AppError>;
+    async fn set_status_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+        status: WithdrawStatus,
+    ) -> Result<Withdraw, AppError>;
 }
 
 #[async_trait]
@@ -39,22 +97,49 @@ pub trait WithdrawServiceTrait {
     async fn get_withdraw(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<WithdrawResponse>>, ErrorResponse>;
     async fn get_withdraw_users(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<Vec<WithdrawResponse>>>, ErrorResponse>;
     async fn get_withdraw_user(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<WithdrawResponse>>, ErrorResponse>;
     async fn create_withdraw(
         &self,
         input: &CreateWithdrawRequest,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<WithdrawResponse>, ErrorResponse>;
     async fn update_withdraw(
         &self,
         input: &UpdateWithdrawRequest,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<WithdrawResponse>>, ErrorResponse>;
-    async fn delete_withdraw(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn delete_withdraw(
+        &self,
+        id: i32,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn restore_withdraw(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<WithdrawResponse>, ErrorResponse>;
+    async fn approve_withdraw(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<WithdrawResponse>, ErrorResponse>;
+    async fn reject_withdraw(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<WithdrawResponse>, ErrorResponse>;
 }
@@ -0,0 +1,74 @@
+use std::time::Duration;
+use tokio::time::{MissedTickBehavior, interval};
+use tracing::{error, info};
+
+use crate::abstract_trait::{
+    DynEmailVerificationRepository, DynJwtService, DynPasswordResetRepository,
+};
+
+// Periodically drops rows and entries that can no longer be used: expired or
+// redeemed password reset and email verification tokens, and revocations of
+// access tokens that have expired on their own. Spawned by `AppRouter::serve`.
+pub struct CleanupTask {
+    password_resets: DynPasswordResetRepository,
+    email_verifications: DynEmailVerificationRepository,
+    jwt_service: DynJwtService,
+    period: Duration,
+}
+
+impl CleanupTask {
+    pub fn new(
+        password_resets: DynPasswordResetRepository,
+        email_verifications: DynEmailVerificationRepository,
+        jwt_service: DynJwtService,
+        period: Duration,
+    ) -> Self {
+        Self {
+            password_resets,
+            email_verifications,
+            jwt_service,
+            period,
+        }
+    }
+
+    // A failing table is logged and skipped so the others are still purged.
+    pub async fn run_once(&self) -> u64 {
+        let password_resets = self
+            .password_resets
+            .delete_expired()
+            .await
+            .unwrap_or_else(|e| {
+                error!("❌ [Cleanup] Failed to purge password resets: {e}");
+                0
+            });
+
+        let email_verifications = self
+            .email_verifications
+            .delete_expired()
+            .await
+            .unwrap_or_else(|e| {
+                error!("❌ [Cleanup] Failed to purge email verifications: {e}");
+                0
+            });
+
+        let revocations = self.jwt_service.purge_expired_revocations() as u64;
+
+        let total = password_resets + email_verifications + revocations;
+
+        info!(
+            "🧹 [Cleanup] Purged {total} record(s): password_resets={password_resets}, email_verifications={email_verifications}, revoked_tokens={revocations}"
+        );
+
+        total
+    }
+
+    pub async fn run(self) {
+        let mut ticker = interval(self.period);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+            self.run_once().await;
+        }
+    }
+}
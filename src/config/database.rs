@@ -1,27 +1,221 @@
-use sqlx::{Pool, Postgres, postgres::PgPoolOptions};
+use log::LevelFilter;
+use serde::Serialize;
+use sqlx::{Pool, Postgres, migrate::Migrator, postgres::PgPoolOptions};
+use std::{collections::BTreeSet, time::Duration};
+use tracing::{error, info};
+
+use crate::{
+    domain::response::migration::{AppliedMigrationResponse, MigrationStatusResponse},
+    model::migration::AppliedMigration,
+};
 
 pub type ConnectionPool = Pool<Postgres>;
 
+pub const DEFAULT_SLOW_QUERY_THRESHOLD_MS: u64 = 500;
+
+// The migrations compiled into this binary.
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
 pub struct ConnectionManager;
 
+#[derive(Debug, Clone, Copy)]
+pub struct PoolSettings {
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub acquire_timeout: Duration,
+    pub idle_timeout: Duration,
+    // Queries and pool acquires slower than this are logged at warn.
+    pub slow_query_threshold: Duration,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PoolStats {
+    pub size: u32,
+    pub idle: usize,
+    pub active: usize,
+    pub max_connections: u32,
+}
+
 impl ConnectionManager {
     pub async fn new_pool(
         connection_string: &str,
         run_migrations: bool,
+        settings: PoolSettings,
     ) -> anyhow::Result<ConnectionPool> {
+        info!(
+            "🗄️ [Database] Pool settings: max_connections={}, min_connections={}, acquire_timeout={:?}, idle_timeout={:?}, slow_query_threshold={:?}",
+            settings.max_connections,
+            settings.min_connections,
+            settings.acquire_timeout,
+            settings.idle_timeout,
+            settings.slow_query_threshold
+        );
+
         let pool = PgPoolOptions::new()
-            .max_connections(5)
+            .max_connections(settings.max_connections)
+            .min_connections(settings.min_connections)
+            .acquire_timeout(settings.acquire_timeout)
+            .idle_timeout(settings.idle_timeout)
+            // sqlx reports how long each acquire waited on the pool, inside
+            // whatever span is current, so contention shows up next to the
+            // query timings.
+            .acquire_time_level(LevelFilter::Debug)
+            .acquire_slow_level(LevelFilter::Warn)
+            .acquire_slow_threshold(settings.slow_query_threshold)
             .connect(connection_string)
             .await
             .map_err(|err| anyhow::anyhow!("Failed to create database connection pool: {}", err))?;
 
         if run_migrations {
-            sqlx::migrate!("./migrations")
-                .run(&pool)
-                .await
-                .map_err(|err| anyhow::anyhow!("Failed to run database migrations: {}", err))?;
+            Self::run_migrations(&pool).await?;
         }
 
         Ok(pool)
     }
+
+    // Refuses to touch a schema that has migrations this binary has never
+    // heard of, which is what a rollback to an older build looks like.
+    pub async fn run_migrations(pool: &ConnectionPool) -> anyhow::Result<()> {
+        let status = Self::migration_status(pool).await?;
+
+        if !status.unknown.is_empty() {
+            error!(
+                "❌ [Database] Schema has migrations {:?} unknown to this binary (latest known: {:?})",
+                status.unknown, status.latest_known
+            );
+
+            return Err(anyhow::anyhow!(
+                "Database schema is newer than this binary: applied migrations {:?} are unknown (latest known version is {:?}); refusing to run migrations",
+                status.unknown,
+                status.latest_known
+            ));
+        }
+
+        MIGRATOR
+            .run(pool)
+            .await
+            .map_err(|err| anyhow::anyhow!("Failed to run database migrations: {}", err))?;
+
+        info!(
+            "🗄️ [Database] Schema is at version {:?} ({} pending migrations applied)",
+            status.latest_known,
+            status.pending.len()
+        );
+
+        Ok(())
+    }
+
+    // Compares sqlx's bookkeeping table with the migrations compiled into
+    // this binary. A database that was never migrated has no such table and
+    // reports every known migration as pending.
+    pub async fn migration_status(
+        pool: &ConnectionPool,
+    ) -> anyhow::Result<MigrationStatusResponse> {
+        let has_table: bool =
+            sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+                .fetch_one(pool)
+                .await
+                .map_err(|err| anyhow::anyhow!("Failed to read migration status: {}", err))?;
+
+        let applied = if has_table {
+            sqlx::query_as::<_, AppliedMigration>(
+                "SELECT version, description, installed_on, success FROM _sqlx_migrations ORDER BY version",
+            )
+            .fetch_all(pool)
+            .await
+            .map_err(|err| anyhow::anyhow!("Failed to read migration status: {}", err))?
+        } else {
+            Vec::new()
+        };
+
+        let known: BTreeSet<i64> = MIGRATOR
+            .iter()
+            .filter(|migration| migration.migration_type.is_up_migration())
+            .map(|migration| migration.version)
+            .collect();
+        let applied_versions: BTreeSet<i64> =
+            applied.iter().map(|migration| migration.version).collect();
+
+        let pending: Vec<i64> = known.difference(&applied_versions).copied().collect();
+        let unknown: Vec<i64> = applied_versions.difference(&known).copied().collect();
+        let up_to_date = pending.is_empty()
+            && unknown.is_empty()
+            && applied.iter().all(|migration| migration.success);
+
+        Ok(MigrationStatusResponse {
+            applied_count: applied.len(),
+            applied: applied
+                .into_iter()
+                .map(AppliedMigrationResponse::from)
+                .collect(),
+            latest_known: known.last().copied(),
+            pending,
+            unknown,
+            up_to_date,
+        })
+    }
+
+    // Bounded so a readiness probe fails fast instead of waiting out the
+    // pool's acquire timeout when the database is gone.
+    pub async fn ping(pool: &ConnectionPool, timeout: Duration) -> anyhow::Result<()> {
+        tokio::time::timeout(timeout, sqlx::query("SELECT 1").execute(pool))
+            .await
+            .map_err(|_| anyhow::anyhow!("Database ping timed out after {timeout:?}"))?
+            .map_err(|err| anyhow::anyhow!("Database ping failed: {}", err))?;
+
+        Ok(())
+    }
+
+    pub fn stats(pool: &ConnectionPool) -> PoolStats {
+        let size = pool.size();
+        let idle = pool.num_idle();
+
+        PoolStats {
+            size,
+            idle,
+            active: (size as usize).saturating_sub(idle),
+            max_connections: pool.options().get_max_connections(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::test_support::FakePostgres;
+
+    #[tokio::test]
+    async fn acquire_gives_up_after_the_configured_timeout() {
+        let postgres = FakePostgres::start().await;
+        let pool = ConnectionManager::new_pool(
+            &postgres.url,
+            false,
+            PoolSettings {
+                max_connections: 1,
+                min_connections: 0,
+                acquire_timeout: Duration::from_millis(300),
+                idle_timeout: Duration::from_secs(60),
+                slow_query_threshold: Duration::from_secs(1),
+            },
+        )
+        .await
+        .unwrap();
+
+        let held = pool.acquire().await.unwrap();
+
+        let started = Instant::now();
+        let err = pool.acquire().await.unwrap_err();
+        let waited = started.elapsed();
+
+        assert!(matches!(err, sqlx::Error::PoolTimedOut), "{err:?}");
+        assert!(waited >= Duration::from_millis(300), "{waited:?}");
+        assert!(waited < Duration::from_secs(3), "{waited:?}");
+        assert_eq!(ConnectionManager::stats(&pool).max_connections, 1);
+
+        // Freed up, the connection is handed out again.
+        drop(held);
+        pool.acquire().await.unwrap();
+    }
 }
@@ -1,14 +1,140 @@
+use argon2::{
+    Algorithm, Argon2, Params, PasswordHash, PasswordHasher, PasswordVerifier, Version,
+    password_hash::SaltString,
+};
 use async_trait::async_trait;
-use bcrypt::{BcryptError, hash, verify};
+use bcrypt::{BcryptError, DEFAULT_COST, hash, verify};
+use core::fmt;
+use rand::{RngCore, rng};
+use std::{ops::RangeInclusive, str::FromStr};
 
 use crate::{abstract_trait::HashingTrait, utils::AppError};
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    #[default]
+    Bcrypt,
+    Argon2id,
+}
+
+impl HashAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Bcrypt => "bcrypt",
+            HashAlgorithm::Argon2id => "argon2id",
+        }
+    }
+
+    // For bcrypt the cost is the log2 work factor; for argon2id it is the
+    // number of passes over the default 19 MiB of memory.
+    pub fn default_cost(&self) -> u32 {
+        match self {
+            HashAlgorithm::Bcrypt => DEFAULT_COST,
+            HashAlgorithm::Argon2id => Params::DEFAULT_T_COST,
+        }
+    }
+
+    pub fn cost_range(&self) -> RangeInclusive<u32> {
+        match self {
+            HashAlgorithm::Bcrypt => 4..=31,
+            HashAlgorithm::Argon2id => Params::MIN_T_COST..=16,
+        }
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bcrypt" => Ok(HashAlgorithm::Bcrypt),
+            "argon2id" | "argon2" => Ok(HashAlgorithm::Argon2id),
+            other => Err(AppError::Custom(format!(
+                "Unknown password hash algorithm: {other}"
+            ))),
+        }
+    }
+}
+
+// New hashes use the configured algorithm and cost. Verification dispatches
+// on the stored hash instead, so switching algorithms doesn't lock anyone out.
 #[derive(Clone)]
-pub struct Hashing;
+pub struct Hashing {
+    algorithm: HashAlgorithm,
+    cost: u32,
+}
 
 impl Hashing {
     pub fn new() -> Self {
-        Hashing
+        Self::with_params(HashAlgorithm::Bcrypt, DEFAULT_COST)
+    }
+
+    pub fn with_params(algorithm: HashAlgorithm, cost: u32) -> Self {
+        Self { algorithm, cost }
+    }
+
+    fn hash_argon2(&self, password: &str) -> Result<String, BcryptError> {
+        let params = Params::new(
+            Params::DEFAULT_M_COST,
+            self.cost,
+            Params::DEFAULT_P_COST,
+            None,
+        )
+        .map_err(|e| BcryptError::from(std::io::Error::other(e.to_string())))?;
+
+        let mut salt = [0u8; 16];
+        rng().fill_bytes(&mut salt);
+        let salt = SaltString::encode_b64(&salt)
+            .map_err(|e| BcryptError::from(std::io::Error::other(e.to_string())))?;
+
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| BcryptError::from(std::io::Error::other(e.to_string())))
+    }
+
+    fn verify(hashed_password: &str, password: &str) -> Result<bool, AppError> {
+        if !hashed_password.starts_with("$argon2") {
+            return verify(password, hashed_password)
+                .map_err(|e| AppError::BcryptError(e.to_string()));
+        }
+
+        // Algorithm and parameters are read back from the PHC string.
+        let parsed =
+            PasswordHash::new(hashed_password).map_err(|e| AppError::BcryptError(e.to_string()))?;
+
+        match Argon2::default().verify_password(password.as_bytes(), &parsed) {
+            Ok(()) => Ok(true),
+            Err(argon2::password_hash::Error::Password) => Ok(false),
+            Err(e) => Err(AppError::BcryptError(e.to_string())),
+        }
+    }
+}
+
+impl Hashing {
+    fn outdated(&self, hashed_password: &str) -> Option<bool> {
+        if !hashed_password.starts_with("$argon2") {
+            // "$2b$12$..." carries the cost in the second field.
+            let cost = hashed_password.split('$').nth(2)?.parse::<u32>().ok()?;
+            return Some(self.algorithm != HashAlgorithm::Bcrypt || cost != self.cost);
+        }
+
+        let parsed = PasswordHash::new(hashed_password).ok()?;
+        let params = Params::try_from(&parsed).ok()?;
+
+        Some(
+            self.algorithm != HashAlgorithm::Argon2id
+                || parsed.algorithm != Algorithm::Argon2id.ident()
+                || params.t_cost() != self.cost
+                || params.m_cost() != Params::DEFAULT_M_COST
+                || params.p_cost() != Params::DEFAULT_P_COST,
+        )
     }
 }
 
@@ -21,7 +147,10 @@ impl Default for Hashing {
 #[async_trait]
 impl HashingTrait for Hashing {
     async fn hash_password(&self, password: &str) -> Result<String, BcryptError> {
-        hash(password, 4)
+        match self.algorithm {
+            HashAlgorithm::Bcrypt => hash(password, self.cost),
+            HashAlgorithm::Argon2id => self.hash_argon2(password),
+        }
     }
 
     async fn compare_password(
@@ -29,12 +158,99 @@ impl HashingTrait for Hashing {
         hashed_password: &str,
         password: &str,
     ) -> Result<(), AppError> {
-        match verify(password, hashed_password) {
-            Ok(true) => Ok(()),
-            Ok(false) => Err(AppError::HashingError(BcryptError::from(
+        match Self::verify(hashed_password, password)? {
+            true => Ok(()),
+            false => Err(AppError::HashingError(BcryptError::from(
                 std::io::Error::other("Passwords do not match."),
             ))),
-            Err(e) => Err(AppError::BcryptError(e.to_string())),
         }
     }
+
+    // Hashes this build can't parse are left alone rather than overwritten.
+    fn needs_rehash(&self, hashed_password: &str) -> bool {
+        self.outdated(hashed_password).unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The cheapest cost each algorithm accepts, to keep the tests fast.
+    fn hashers() -> [Hashing; 2] {
+        [
+            Hashing::with_params(HashAlgorithm::Bcrypt, 4),
+            Hashing::with_params(HashAlgorithm::Argon2id, Params::MIN_T_COST),
+        ]
+    }
+
+    #[tokio::test]
+    async fn each_algorithm_round_trips_a_password() {
+        for hashing in hashers() {
+            let hashed = hashing.hash_password("correct horse").await.unwrap();
+
+            assert_ne!(hashed, "correct horse");
+            hashing
+                .compare_password(&hashed, "correct horse")
+                .await
+                .unwrap_or_else(|e| panic!("{}: {e}", hashing.algorithm));
+            assert!(
+                hashing
+                    .compare_password(&hashed, "wrong horse")
+                    .await
+                    .is_err(),
+                "{} accepted the wrong password",
+                hashing.algorithm
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn hashes_verify_after_switching_algorithms() {
+        let [bcrypt, argon2] = hashers();
+
+        let from_bcrypt = bcrypt.hash_password("correct horse").await.unwrap();
+        let from_argon2 = argon2.hash_password("correct horse").await.unwrap();
+        assert!(from_bcrypt.starts_with("$2"), "{from_bcrypt}");
+        assert!(from_argon2.starts_with("$argon2id$"), "{from_argon2}");
+
+        argon2
+            .compare_password(&from_bcrypt, "correct horse")
+            .await
+            .unwrap();
+        bcrypt
+            .compare_password(&from_argon2, "correct horse")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn hashes_from_other_parameters_need_a_rehash() {
+        let [bcrypt, argon2] = hashers();
+        let from_bcrypt = bcrypt.hash_password("correct horse").await.unwrap();
+        let from_argon2 = argon2.hash_password("correct horse").await.unwrap();
+
+        assert!(!bcrypt.needs_rehash(&from_bcrypt));
+        assert!(!argon2.needs_rehash(&from_argon2));
+
+        assert!(Hashing::with_params(HashAlgorithm::Bcrypt, 5).needs_rehash(&from_bcrypt));
+        assert!(argon2.needs_rehash(&from_bcrypt));
+        assert!(bcrypt.needs_rehash(&from_argon2));
+
+        // Unparseable hashes are left alone.
+        assert!(!bcrypt.needs_rehash("not-a-hash"));
+    }
+
+    #[test]
+    fn algorithm_names_parse() {
+        assert_eq!(
+            "bcrypt".parse::<HashAlgorithm>().unwrap(),
+            HashAlgorithm::Bcrypt
+        );
+        assert_eq!(
+            "argon2".parse::<HashAlgorithm>().unwrap(),
+            HashAlgorithm::Argon2id
+        );
+        assert!("md5".parse::<HashAlgorithm>().is_err());
+    }
 }
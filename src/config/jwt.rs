@@ -1,46 +1,99 @@
-use crate::{abstract_trait::JwtServiceTrait, utils::AppError};
+use crate::{abstract_trait::JwtServiceTrait, domain::role::Role, utils::AppError};
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::{Duration, Utc};
+use dashmap::DashMap;
 use jsonwebtoken::{
     DecodingKey, EncodingKey, Header, Validation, decode, encode, errors::ErrorKind as JwtError,
 };
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize)]
+const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenType {
+    #[default]
+    Access,
+    Refresh,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
+    #[serde(default)]
+    pub jti: String,
     pub user_id: i64,
     pub exp: usize,
     pub iat: usize,
+    #[serde(default)]
+    pub nbf: usize,
+    #[serde(default)]
+    pub token_type: TokenType,
+    #[serde(default)]
+    pub role: Role,
+    // Login session shared by an access/refresh pair and every pair
+    // refreshed from it, so logout can revoke all of them at once.
+    #[serde(default)]
+    pub sid: String,
 }
 
 impl Claims {
-    pub fn new(user_id: i64, exp: usize, iat: usize) -> Self {
-        Claims { user_id, exp, iat }
+    pub fn new(
+        user_id: i64,
+        exp: usize,
+        iat: usize,
+        token_type: TokenType,
+        role: Role,
+        sid: &str,
+    ) -> Self {
+        Claims {
+            jti: Uuid::new_v4().to_string(),
+            user_id,
+            exp,
+            iat,
+            nbf: iat,
+            token_type,
+            role,
+            sid: sid.to_string(),
+        }
     }
 }
 
 #[derive(Clone)]
 pub struct JwtConfig {
     pub jwt_secret: String,
+    pub access_token_ttl: Duration,
+    // Revoked jti -> the token's own `exp`. In-memory, so revocations do not
+    // survive a restart or reach other instances.
+    revoked: Arc<DashMap<String, usize>>,
 }
 
 impl JwtConfig {
-    pub fn new(jwt_secret: &str) -> Self {
+    pub fn new(jwt_secret: &str, access_token_ttl_seconds: i64) -> Self {
         JwtConfig {
             jwt_secret: jwt_secret.to_string(),
+            access_token_ttl: Duration::seconds(access_token_ttl_seconds),
+            revoked: Arc::new(DashMap::new()),
         }
     }
 }
 
-#[async_trait]
-impl JwtServiceTrait for JwtConfig {
-    fn generate_token(&self, user_id: i64) -> Result<String, AppError> {
+impl JwtConfig {
+    fn encode_claims(
+        &self,
+        user_id: i64,
+        role: Role,
+        token_type: TokenType,
+        ttl: Duration,
+        sid: &str,
+    ) -> Result<String, AppError> {
         let now = Utc::now();
         let iat = now.timestamp() as usize;
-        let exp = (now + Duration::minutes(60)).timestamp() as usize;
+        let exp = (now + ttl).timestamp() as usize;
 
-        let claims = Claims::new(user_id, exp, iat);
+        let claims = Claims::new(user_id, exp, iat, token_type, role, sid);
 
         match encode(
             &Header::default(),
@@ -52,15 +105,25 @@ impl JwtServiceTrait for JwtConfig {
         }
     }
 
-    fn verify_token(&self, token: &str) -> Result<i64, AppError> {
+    fn decode_claims(&self, token: &str, expected: TokenType) -> Result<Claims, AppError> {
         let decoding_key = DecodingKey::from_secret(self.jwt_secret.as_ref());
 
-        match decode::<Claims>(token, &decoding_key, &Validation::default()) {
+        let mut validation = Validation::default();
+        validation.validate_nbf = true;
+
+        match decode::<Claims>(token, &decoding_key, &validation) {
             Ok(token_data) => {
                 let current_time = Utc::now().timestamp() as usize;
 
-                if token_data.claims.exp >= current_time {
-                    Ok(token_data.claims.user_id)
+                let claims = &token_data.claims;
+
+                if claims.token_type != expected
+                    || self.revoked.contains_key(&claims.jti)
+                    || (!claims.sid.is_empty() && self.revoked.contains_key(&claims.sid))
+                {
+                    Err(AppError::TokenValidationError)
+                } else if token_data.claims.exp >= current_time {
+                    Ok(token_data.claims)
                 } else {
                     Err(AppError::TokenExpiredError)
                 }
@@ -76,3 +139,123 @@ impl JwtServiceTrait for JwtConfig {
         }
     }
 }
+
+#[async_trait]
+impl JwtServiceTrait for JwtConfig {
+    fn generate_token(&self, user_id: i64, role: Role, sid: &str) -> Result<String, AppError> {
+        self.encode_claims(user_id, role, TokenType::Access, self.access_token_ttl, sid)
+    }
+
+    fn generate_refresh_token(&self, user_id: i64, sid: &str) -> Result<String, AppError> {
+        self.encode_claims(
+            user_id,
+            Role::default(),
+            TokenType::Refresh,
+            Duration::days(REFRESH_TOKEN_TTL_DAYS),
+            sid,
+        )
+    }
+
+    fn verify_token(&self, token: &str) -> Result<Claims, AppError> {
+        self.decode_claims(token, TokenType::Access)
+    }
+
+    fn revoke_token(&self, jti: &str, exp: usize) -> bool {
+        self.purge_expired_revocations();
+        self.revoked.insert(jti.to_string(), exp).is_none()
+    }
+
+    // No token of the session outlives a refresh token issued right now, so
+    // the entry can be dropped after that.
+    fn revoke_session(&self, sid: &str) {
+        let exp = (Utc::now() + Duration::days(REFRESH_TOKEN_TTL_DAYS)).timestamp() as usize;
+
+        self.revoke_token(sid, exp);
+    }
+
+    // Once a token has expired it is rejected anyway, so its entry can go.
+    fn purge_expired_revocations(&self) -> usize {
+        let now = Utc::now().timestamp() as usize;
+        let before = self.revoked.len();
+
+        self.revoked.retain(|_, revoked_exp| *revoked_exp >= now);
+
+        before.saturating_sub(self.revoked.len())
+    }
+
+    fn verify_refresh_token(&self, token: &str) -> Result<Claims, AppError> {
+        self.decode_claims(token, TokenType::Refresh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fresh_access_token_is_accepted() {
+        let jwt = JwtConfig::new("test-secret", 3600);
+
+        let token = jwt.generate_token(7, Role::User, "session").unwrap();
+        let claims = jwt.verify_token(&token).unwrap();
+
+        assert_eq!(claims.user_id, 7);
+        assert_eq!(claims.nbf, claims.iat);
+        assert_eq!(claims.exp - claims.iat, 3600);
+    }
+
+    #[test]
+    fn token_older_than_its_lifetime_is_rejected() {
+        // A negative lifetime issues tokens that expired two minutes ago,
+        // past the decoder's default leeway.
+        let jwt = JwtConfig::new("test-secret", -120);
+
+        let token = jwt.generate_token(7, Role::User, "session").unwrap();
+
+        assert!(matches!(
+            jwt.verify_token(&token),
+            Err(AppError::TokenExpiredError)
+        ));
+    }
+
+    #[test]
+    fn revoked_token_is_rejected() {
+        let jwt = JwtConfig::new("test-secret", 3600);
+
+        let token = jwt.generate_token(7, Role::User, "session").unwrap();
+        let claims = jwt.verify_token(&token).unwrap();
+
+        jwt.revoke_token(&claims.jti, claims.exp);
+
+        assert!(matches!(
+            jwt.verify_token(&token),
+            Err(AppError::TokenValidationError)
+        ));
+        let other = jwt.generate_token(7, Role::User, "session").unwrap();
+        assert!(jwt.verify_token(&other).is_ok());
+    }
+
+    #[test]
+    fn revoked_session_rejects_its_refresh_tokens() {
+        let jwt = JwtConfig::new("test-secret", 3600);
+
+        let refresh = jwt.generate_refresh_token(7, "session").unwrap();
+        let other = jwt.generate_refresh_token(7, "other-session").unwrap();
+
+        jwt.revoke_session("session");
+
+        assert!(matches!(
+            jwt.verify_refresh_token(&refresh),
+            Err(AppError::TokenValidationError)
+        ));
+        assert!(jwt.verify_refresh_token(&other).is_ok());
+    }
+
+    #[test]
+    fn revoking_twice_reports_the_second_time() {
+        let jwt = JwtConfig::new("test-secret", 3600);
+
+        assert!(jwt.revoke_token("jti", usize::MAX));
+        assert!(!jwt.revoke_token("jti", usize::MAX));
+    }
+}
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::{abstract_trait::MailerTrait, utils::AppError};
+
+// No SMTP integration yet: messages are written to the log so they can be
+// picked up during development.
+#[derive(Clone)]
+pub struct LogMailer;
+
+impl LogMailer {
+    pub fn new() -> Self {
+        LogMailer
+    }
+}
+
+impl Default for LogMailer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl MailerTrait for LogMailer {
+    async fn send_password_reset(&self, email: &str, token: &str) -> Result<(), AppError> {
+        info!("📧 [Mailer] Password reset for {email}: token={token}");
+        Ok(())
+    }
+
+    async fn send_email_verification(&self, email: &str, token: &str) -> Result<(), AppError> {
+        info!("📧 [Mailer] Email verification for {email}: token={token}");
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts, Registry, TextEncoder,
+};
+
+use crate::domain::money::Money;
+
+pub struct Metrics {
+    registry: Registry,
+    pub http_requests_total: IntCounterVec,
+    pub http_request_duration_seconds: HistogramVec,
+    pub transfers_created_total: IntCounter,
+    pub topups_created_total: IntCounter,
+    pub withdraw_amount_sum: IntCounter,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let http_requests_total = IntCounterVec::new(
+            Opts::new("http_requests_total", "Total HTTP requests"),
+            &["method", "route", "status"],
+        )
+        .expect("valid http_requests_total metric");
+
+        let http_request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency in seconds",
+            ),
+            &["method", "route"],
+        )
+        .expect("valid http_request_duration_seconds metric");
+
+        let transfers_created_total =
+            IntCounter::new("transfers_created_total", "Transfers created")
+                .expect("valid transfers_created_total metric");
+
+        let topups_created_total = IntCounter::new("topups_created_total", "Topups created")
+            .expect("valid topups_created_total metric");
+
+        let withdraw_amount_sum = IntCounter::new(
+            "withdraw_amount_sum",
+            "Sum of withdrawn amounts in minor currency units",
+        )
+        .expect("valid withdraw_amount_sum metric");
+
+        for collector in [
+            Box::new(http_requests_total.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(http_request_duration_seconds.clone()),
+            Box::new(transfers_created_total.clone()),
+            Box::new(topups_created_total.clone()),
+            Box::new(withdraw_amount_sum.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("metric names are unique");
+        }
+
+        Self {
+            registry,
+            http_requests_total,
+            http_request_duration_seconds,
+            transfers_created_total,
+            topups_created_total,
+            withdraw_amount_sum,
+        }
+    }
+
+    pub fn record_withdraw(&self, amount: Money) {
+        self.withdraw_amount_sum
+            .inc_by(amount.minor_units().max(0) as u64);
+    }
+
+    pub fn render(&self) -> Result<String, prometheus::Error> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+
+        String::from_utf8(buffer).map_err(|e| prometheus::Error::Msg(e.to_string()))
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
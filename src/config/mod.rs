@@ -1,9 +1,21 @@
+mod cleanup;
 mod database;
 mod hashing;
 mod jwt;
+mod mailer;
+mod metrics;
 mod myconfig;
+mod transfer_scheduler;
+mod webhook;
 
-pub use self::database::{ConnectionManager, ConnectionPool};
-pub use self::hashing::Hashing;
-pub use self::jwt::JwtConfig;
+pub use self::cleanup::CleanupTask;
+pub use self::database::{
+    ConnectionManager, ConnectionPool, DEFAULT_SLOW_QUERY_THRESHOLD_MS, PoolSettings, PoolStats,
+};
+pub use self::hashing::{HashAlgorithm, Hashing};
+pub use self::jwt::{Claims, JwtConfig, TokenType};
+pub use self::mailer::LogMailer;
+pub use self::metrics::Metrics;
 pub use self::myconfig::Config;
+pub use self::transfer_scheduler::TransferScheduler;
+pub use self::webhook::{WebhookDispatcher, WebhookWorker};
@@ -1,11 +1,67 @@
 use anyhow::{Context, Result, anyhow};
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    time::Duration,
+};
+use tracing_subscriber::EnvFilter;
+
+use crate::config::{DEFAULT_SLOW_QUERY_THRESHOLD_MS, HashAlgorithm, PoolSettings};
+
+use crate::domain::{
+    balance_policy::BalancePolicy,
+    currency::Currency,
+    fees::{FeeSchedule, MAX_FEE_BASIS_POINTS},
+    money::Money,
+    request::{pagination::DEFAULT_MAX_PAGE_SIZE, topup::MIN_TOPUP_AMOUNT},
+    topup_limits::TopupLimits,
+};
+
+use crate::utils::{Language, LogFormat};
 
 #[derive(Debug, Clone)]
 pub struct Config {
     pub database_url: String,
+    pub db_max_connections: u32,
+    pub db_min_connections: u32,
+    pub db_acquire_timeout_secs: u64,
+    pub db_idle_timeout_secs: u64,
+    // Money-moving routes answer 503 while fewer connections than this are
+    // free; `None` never sheds.
+    pub db_shed_min_available: Option<u32>,
+    pub slow_query_threshold_ms: u64,
     pub jwt_secret: String,
+    pub jwt_expiry_seconds: i64,
     pub run_migrations: bool,
+    pub host: IpAddr,
     pub port: u16,
+    pub admin_email: Option<String>,
+    pub admin_password: Option<String>,
+    pub seed_admin: bool,
+    pub password_hash_algorithm: HashAlgorithm,
+    pub password_hash_cost: u32,
+    pub login_max_attempts: u32,
+    pub login_window_secs: u64,
+    pub metrics_port: Option<u16>,
+    pub daily_withdraw_limit: Option<Money>,
+    pub withdraw_approval_threshold: Option<Money>,
+    pub max_transfer_amount: Option<Money>,
+    pub minimum_balance: Money,
+    pub allow_negative_reversal: bool,
+    pub max_page_size: i32,
+    pub base_currency: Currency,
+    pub api_signature_max_skew_secs: u64,
+    pub require_email_verification: bool,
+    pub cleanup_interval_secs: u64,
+    pub scheduled_transfer_interval_secs: u64,
+    pub bulk_topup_max_rows: usize,
+    pub min_topup_amount: Money,
+    pub max_topup_amount: Option<Money>,
+    pub transfer_fee_flat: Money,
+    pub transfer_fee_basis_points: u32,
+    pub strict_fields: bool,
+    pub locale: Option<Language>,
+    pub log_format: LogFormat,
+    pub log_level: Option<String>,
 }
 
 impl Config {
@@ -13,9 +69,74 @@ impl Config {
         let database_url =
             std::env::var("DATABASE_URL").context("Missing environment variable: DATABASE_URL")?;
 
+        let db_max_connections = match std::env::var("DB_MAX_CONNECTIONS") {
+            Ok(value) => value
+                .parse::<u32>()
+                .ok()
+                .filter(|max| *max > 0)
+                .context("DB_MAX_CONNECTIONS must be a positive integer")?,
+            Err(_) => 5,
+        };
+
+        let db_min_connections = match std::env::var("DB_MIN_CONNECTIONS") {
+            Ok(value) => value
+                .parse::<u32>()
+                .context("DB_MIN_CONNECTIONS must be a valid u32 integer")?,
+            Err(_) => 0,
+        };
+
+        if db_min_connections > db_max_connections {
+            return Err(anyhow!(
+                "DB_MIN_CONNECTIONS ({db_min_connections}) must not exceed DB_MAX_CONNECTIONS ({db_max_connections})"
+            ));
+        }
+
+        let db_acquire_timeout_secs = match std::env::var("DB_ACQUIRE_TIMEOUT_SECS") {
+            Ok(value) => value
+                .parse::<u64>()
+                .ok()
+                .filter(|secs| *secs > 0)
+                .context("DB_ACQUIRE_TIMEOUT_SECS must be a positive integer")?,
+            Err(_) => 30,
+        };
+
+        let db_idle_timeout_secs = match std::env::var("DB_IDLE_TIMEOUT_SECS") {
+            Ok(value) => value
+                .parse::<u64>()
+                .context("DB_IDLE_TIMEOUT_SECS must be a valid u64 integer")?,
+            Err(_) => 600,
+        };
+
+        let db_shed_min_available = match std::env::var("DB_SHED_MIN_AVAILABLE") {
+            Ok(value) if !value.is_empty() => Some(
+                value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|min| *min > 0)
+                    .context("DB_SHED_MIN_AVAILABLE must be a positive integer")?,
+            ),
+            _ => None,
+        };
+
+        let slow_query_threshold_ms = match std::env::var("SLOW_QUERY_THRESHOLD_MS") {
+            Ok(value) => value
+                .parse::<u64>()
+                .context("SLOW_QUERY_THRESHOLD_MS must be a valid u64 integer")?,
+            Err(_) => DEFAULT_SLOW_QUERY_THRESHOLD_MS,
+        };
+
         let jwt_secret =
             std::env::var("JWT_SECRET").context("Missing environment variable: JWT_SECRET")?;
 
+        let jwt_expiry_seconds = match std::env::var("JWT_EXPIRY_SECONDS") {
+            Ok(value) => value
+                .parse::<i64>()
+                .ok()
+                .filter(|seconds| *seconds > 0)
+                .context("JWT_EXPIRY_SECONDS must be a positive integer")?,
+            Err(_) => 3600,
+        };
+
         let run_migrations_str = std::env::var("RUN_MIGRATIONS")
             .context("Missing environment variable: RUN_MIGRATIONS")?;
 
@@ -32,15 +153,449 @@ impl Config {
             }
         };
 
+        let admin_email = std::env::var("ADMIN_EMAIL")
+            .ok()
+            .filter(|email| !email.is_empty());
+
+        let admin_password = std::env::var("ADMIN_PASSWORD")
+            .ok()
+            .filter(|password| !password.is_empty());
+
+        let seed_admin = match std::env::var("SEED_ADMIN") {
+            Ok(value) => match value.as_str() {
+                "true" => true,
+                "false" => false,
+                other => {
+                    return Err(anyhow!(
+                        "SEED_ADMIN must be 'true' or 'false', got '{}'",
+                        other
+                    ));
+                }
+            },
+            Err(_) => true,
+        };
+
+        let password_hash_algorithm = match std::env::var("PASSWORD_HASH_ALGORITHM") {
+            Ok(value) if !value.is_empty() => value
+                .parse::<HashAlgorithm>()
+                .ok()
+                .context("PASSWORD_HASH_ALGORITHM must be 'bcrypt' or 'argon2id'")?,
+            _ => HashAlgorithm::default(),
+        };
+
+        let password_hash_cost = match std::env::var("PASSWORD_HASH_COST") {
+            Ok(value) if !value.is_empty() => {
+                let range = password_hash_algorithm.cost_range();
+                value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|cost| range.contains(cost))
+                    .with_context(|| {
+                        format!(
+                            "PASSWORD_HASH_COST must be between {} and {} for {password_hash_algorithm}",
+                            range.start(),
+                            range.end()
+                        )
+                    })?
+            }
+            _ => password_hash_algorithm.default_cost(),
+        };
+
+        let login_max_attempts = match std::env::var("LOGIN_MAX_ATTEMPTS") {
+            Ok(value) => value
+                .parse::<u32>()
+                .context("LOGIN_MAX_ATTEMPTS must be a valid u32 integer")?,
+            Err(_) => 5,
+        };
+
+        let login_window_secs = match std::env::var("LOGIN_WINDOW_SECS") {
+            Ok(value) => value
+                .parse::<u64>()
+                .context("LOGIN_WINDOW_SECS must be a valid u64 integer")?,
+            Err(_) => 300,
+        };
+
+        let metrics_port = match std::env::var("METRICS_PORT") {
+            Ok(value) if !value.is_empty() => Some(
+                value
+                    .parse::<u16>()
+                    .context("METRICS_PORT must be a valid u16 integer")?,
+            ),
+            _ => None,
+        };
+
+        let daily_withdraw_limit = match std::env::var("DAILY_WITHDRAW_LIMIT") {
+            Ok(value) if !value.is_empty() => Some(
+                value
+                    .parse::<Money>()
+                    .ok()
+                    .filter(|limit| !limit.is_negative())
+                    .context("DAILY_WITHDRAW_LIMIT must be a non-negative integer amount")?,
+            ),
+            _ => None,
+        };
+
+        let withdraw_approval_threshold = match std::env::var("WITHDRAW_APPROVAL_THRESHOLD") {
+            Ok(value) if !value.is_empty() => Some(
+                value
+                    .parse::<Money>()
+                    .ok()
+                    .filter(|threshold| !threshold.is_negative())
+                    .context("WITHDRAW_APPROVAL_THRESHOLD must be a non-negative integer amount")?,
+            ),
+            _ => None,
+        };
+
+        let max_transfer_amount = match std::env::var("MAX_TRANSFER_AMOUNT") {
+            Ok(value) if !value.is_empty() => Some(
+                value
+                    .parse::<Money>()
+                    .ok()
+                    .filter(|max| *max > Money::ZERO)
+                    .context("MAX_TRANSFER_AMOUNT must be a positive integer amount")?,
+            ),
+            _ => None,
+        };
+
+        let minimum_balance = match std::env::var("MINIMUM_BALANCE") {
+            Ok(value) if !value.is_empty() => value
+                .parse::<Money>()
+                .ok()
+                .filter(|minimum| !minimum.is_negative())
+                .context("MINIMUM_BALANCE must be a non-negative integer amount")?,
+            _ => Money::ZERO,
+        };
+
+        let allow_negative_reversal = match std::env::var("ALLOW_NEGATIVE_REVERSAL") {
+            Ok(value) => match value.as_str() {
+                "true" => true,
+                "false" => false,
+                other => {
+                    return Err(anyhow!(
+                        "ALLOW_NEGATIVE_REVERSAL must be 'true' or 'false', got '{}'",
+                        other
+                    ));
+                }
+            },
+            Err(_) => false,
+        };
+
+        let max_page_size = match std::env::var("MAX_PAGE_SIZE") {
+            Ok(value) if !value.is_empty() => value
+                .parse::<i32>()
+                .ok()
+                .filter(|size| *size > 0)
+                .context("MAX_PAGE_SIZE must be a positive integer")?,
+            _ => DEFAULT_MAX_PAGE_SIZE,
+        };
+
+        let base_currency = match std::env::var("BASE_CURRENCY") {
+            Ok(value) if !value.is_empty() => value
+                .parse::<Currency>()
+                .ok()
+                .context("BASE_CURRENCY must be a three-letter ISO 4217 code")?,
+            _ => Currency::IDR,
+        };
+
+        let api_signature_max_skew_secs = match std::env::var("API_SIGNATURE_MAX_SKEW_SECS") {
+            Ok(value) => value
+                .parse::<u64>()
+                .context("API_SIGNATURE_MAX_SKEW_SECS must be a valid u64 integer")?,
+            Err(_) => 300,
+        };
+
+        let require_email_verification = match std::env::var("REQUIRE_EMAIL_VERIFICATION") {
+            Ok(value) => match value.as_str() {
+                "true" => true,
+                "false" => false,
+                other => {
+                    return Err(anyhow!(
+                        "REQUIRE_EMAIL_VERIFICATION must be 'true' or 'false', got '{}'",
+                        other
+                    ));
+                }
+            },
+            Err(_) => true,
+        };
+
+        let cleanup_interval_secs = match std::env::var("CLEANUP_INTERVAL_SECS") {
+            Ok(value) => value
+                .parse::<u64>()
+                .ok()
+                .filter(|secs| *secs > 0)
+                .context("CLEANUP_INTERVAL_SECS must be a positive integer")?,
+            Err(_) => 3600,
+        };
+
+        let scheduled_transfer_interval_secs =
+            match std::env::var("SCHEDULED_TRANSFER_INTERVAL_SECS") {
+                Ok(value) => value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|secs| *secs > 0)
+                    .context("SCHEDULED_TRANSFER_INTERVAL_SECS must be a positive integer")?,
+                Err(_) => 60,
+            };
+
+        let bulk_topup_max_rows = match std::env::var("BULK_TOPUP_MAX_ROWS") {
+            Ok(value) if !value.is_empty() => value
+                .parse::<usize>()
+                .ok()
+                .filter(|rows| *rows > 0)
+                .context("BULK_TOPUP_MAX_ROWS must be a positive integer")?,
+            _ => 500,
+        };
+
+        let min_topup_amount = match std::env::var("MIN_TOPUP_AMOUNT") {
+            Ok(value) if !value.is_empty() => value
+                .parse::<Money>()
+                .ok()
+                .filter(|min| min.minor_units() >= MIN_TOPUP_AMOUNT)
+                .with_context(|| {
+                    format!(
+                        "MIN_TOPUP_AMOUNT must be an integer amount of at least {MIN_TOPUP_AMOUNT}"
+                    )
+                })?,
+            _ => Money::new(MIN_TOPUP_AMOUNT),
+        };
+
+        let max_topup_amount = match std::env::var("MAX_TOPUP_AMOUNT") {
+            Ok(value) if !value.is_empty() => Some(
+                value
+                    .parse::<Money>()
+                    .ok()
+                    .filter(|max| *max >= min_topup_amount)
+                    .with_context(|| {
+                        format!("MAX_TOPUP_AMOUNT must be an integer amount of at least {min_topup_amount}")
+                    })?,
+            ),
+            _ => None,
+        };
+
+        let transfer_fee_flat = match std::env::var("TRANSFER_FEE_FLAT") {
+            Ok(value) if !value.is_empty() => value
+                .parse::<Money>()
+                .ok()
+                .filter(|fee| !fee.is_negative())
+                .context("TRANSFER_FEE_FLAT must be a non-negative integer amount")?,
+            _ => Money::ZERO,
+        };
+
+        let transfer_fee_basis_points = match std::env::var("TRANSFER_FEE_BASIS_POINTS") {
+            Ok(value) if !value.is_empty() => value
+                .parse::<u32>()
+                .ok()
+                .filter(|bps| *bps <= MAX_FEE_BASIS_POINTS)
+                .with_context(|| {
+                    format!(
+                        "TRANSFER_FEE_BASIS_POINTS must be an integer between 0 and {MAX_FEE_BASIS_POINTS}"
+                    )
+                })?,
+            _ => 0,
+        };
+
+        let strict_fields = match std::env::var("STRICT_FIELDS") {
+            Ok(value) => match value.as_str() {
+                "true" => true,
+                "false" => false,
+                other => {
+                    return Err(anyhow!(
+                        "STRICT_FIELDS must be 'true' or 'false', got '{}'",
+                        other
+                    ));
+                }
+            },
+            Err(_) => false,
+        };
+
+        let locale = match std::env::var("LOCALE") {
+            Ok(value) if !value.is_empty() => Some(
+                value
+                    .parse::<Language>()
+                    .ok()
+                    .context("LOCALE must be 'en' or 'id'")?,
+            ),
+            _ => None,
+        };
+
+        let log_format = match std::env::var("LOG_FORMAT") {
+            Ok(value) if !value.is_empty() => value
+                .parse::<LogFormat>()
+                .ok()
+                .context("LOG_FORMAT must be 'pretty' or 'json'")?,
+            _ => LogFormat::default(),
+        };
+
+        let log_level = match std::env::var("LOG_LEVEL") {
+            Ok(value) if !value.is_empty() => Some(
+                EnvFilter::try_new(&value)
+                    .map(|_| value)
+                    .context("LOG_LEVEL must be a valid filter directive, e.g. 'info'")?,
+            ),
+            _ => None,
+        };
+
         let port = port_str
             .parse::<u16>()
             .context("PORT must be a valid u16 integer")?;
 
+        let host = parse_host(std::env::var("HOST").ok().as_deref())?;
+
         Ok(Self {
             database_url,
+            db_max_connections,
+            db_min_connections,
+            db_acquire_timeout_secs,
+            db_idle_timeout_secs,
+            db_shed_min_available,
+            slow_query_threshold_ms,
             jwt_secret,
+            jwt_expiry_seconds,
             run_migrations,
+            host,
             port,
+            admin_email,
+            admin_password,
+            seed_admin,
+            password_hash_algorithm,
+            password_hash_cost,
+            login_max_attempts,
+            login_window_secs,
+            metrics_port,
+            daily_withdraw_limit,
+            withdraw_approval_threshold,
+            max_transfer_amount,
+            minimum_balance,
+            allow_negative_reversal,
+            max_page_size,
+            base_currency,
+            api_signature_max_skew_secs,
+            require_email_verification,
+            cleanup_interval_secs,
+            scheduled_transfer_interval_secs,
+            bulk_topup_max_rows,
+            min_topup_amount,
+            max_topup_amount,
+            transfer_fee_flat,
+            transfer_fee_basis_points,
+            strict_fields,
+            locale,
+            log_format,
+            log_level,
         })
     }
+
+    pub fn pool_settings(&self) -> PoolSettings {
+        PoolSettings {
+            max_connections: self.db_max_connections,
+            min_connections: self.db_min_connections,
+            acquire_timeout: Duration::from_secs(self.db_acquire_timeout_secs),
+            idle_timeout: Duration::from_secs(self.db_idle_timeout_secs),
+            slow_query_threshold: Duration::from_millis(self.slow_query_threshold_ms),
+        }
+    }
+
+    pub fn balance_policy(&self) -> BalancePolicy {
+        BalancePolicy {
+            minimum_balance: self.minimum_balance,
+            allow_negative_reversal: self.allow_negative_reversal,
+            daily_withdraw_limit: self.daily_withdraw_limit,
+            withdraw_approval_threshold: self.withdraw_approval_threshold,
+            max_transfer_amount: self.max_transfer_amount,
+        }
+    }
+
+    pub fn fee_schedule(&self) -> FeeSchedule {
+        FeeSchedule {
+            flat: self.transfer_fee_flat,
+            basis_points: self.transfer_fee_basis_points,
+        }
+    }
+
+    pub fn topup_limits(&self) -> TopupLimits {
+        TopupLimits {
+            min_amount: self.min_topup_amount,
+            max_amount: self.max_topup_amount,
+            bulk_max_rows: self.bulk_topup_max_rows,
+        }
+    }
+}
+
+// Unset or empty binds every interface.
+fn parse_host(value: Option<&str>) -> Result<IpAddr> {
+    match value {
+        Some(value) if !value.is_empty() => value
+            .parse::<IpAddr>()
+            .context("HOST must be a valid IP address, e.g. '0.0.0.0' or '127.0.0.1'"),
+        _ => Ok(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv6Addr;
+
+    use super::*;
+
+    #[test]
+    fn host_defaults_to_every_interface() {
+        let unspecified = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+
+        assert_eq!(parse_host(None).unwrap(), unspecified);
+        assert_eq!(parse_host(Some("")).unwrap(), unspecified);
+    }
+
+    #[test]
+    fn host_accepts_ipv4_and_ipv6() {
+        assert_eq!(
+            parse_host(Some("127.0.0.1")).unwrap(),
+            IpAddr::V4(Ipv4Addr::LOCALHOST)
+        );
+        assert_eq!(
+            parse_host(Some("::1")).unwrap(),
+            IpAddr::V6(Ipv6Addr::LOCALHOST)
+        );
+    }
+
+    #[test]
+    fn invalid_host_is_a_clear_error() {
+        let err = parse_host(Some("localhost:8000")).unwrap_err();
+
+        assert!(err.to_string().starts_with("HOST must be"), "{err:#}");
+    }
+
+    #[test]
+    fn init_picks_up_host_from_the_environment() {
+        // SAFETY: this is the only test that reads or writes these variables.
+        unsafe {
+            std::env::set_var("DATABASE_URL", "postgres://localhost/test");
+            std::env::set_var("JWT_SECRET", "test-secret");
+            std::env::set_var("RUN_MIGRATIONS", "false");
+            std::env::set_var("PORT", "8000");
+            std::env::set_var("HOST", "127.0.0.1");
+        }
+        let config = Config::init();
+
+        unsafe { std::env::set_var("HOST", "not-an-ip") };
+        let invalid = Config::init();
+
+        unsafe {
+            for key in [
+                "DATABASE_URL",
+                "JWT_SECRET",
+                "RUN_MIGRATIONS",
+                "PORT",
+                "HOST",
+            ] {
+                std::env::remove_var(key);
+            }
+        }
+
+        let config = config.expect("config with a valid HOST");
+        assert_eq!(config.host, IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert_eq!(config.port, 8000);
+
+        let err = invalid.unwrap_err();
+        assert!(err.to_string().starts_with("HOST must be"), "{err:#}");
+    }
 }
@@ -0,0 +1,39 @@
+use std::time::Duration;
+use tokio::time::{MissedTickBehavior, interval};
+use tracing::{error, info};
+
+use crate::abstract_trait::DynTransferService;
+
+// Executes scheduled transfers once they fall due. Each transfer is claimed
+// with `FOR UPDATE SKIP LOCKED`, so running several instances is safe.
+// Spawned by `AppRouter::serve`.
+pub struct TransferScheduler {
+    transfers: DynTransferService,
+    period: Duration,
+}
+
+impl TransferScheduler {
+    pub fn new(transfers: DynTransferService, period: Duration) -> Self {
+        Self { transfers, period }
+    }
+
+    pub async fn run(self) {
+        let mut ticker = interval(self.period);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            match self.transfers.execute_due_transfers().await {
+                Ok(0) => {}
+                Ok(executed) => {
+                    info!("🗓️ [Scheduler] Executed {executed} scheduled transfer(s)")
+                }
+                Err(e) => error!(
+                    "❌ [Scheduler] Failed to execute due transfers: {}",
+                    e.message
+                ),
+            }
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use chrono::Utc;
+use reqwest::{Client, header::CONTENT_TYPE};
+use serde_json::Value;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+use crate::{
+    abstract_trait::{DynWebhookRepository, WebhookNotifierTrait},
+    domain::webhook::{WebhookEvent, WebhookPayload},
+    model::webhook::Webhook,
+    utils::sign_webhook_payload,
+};
+
+const QUEUE_CAPACITY: usize = 1024;
+const MAX_ATTEMPTS: u32 = 5;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct WebhookJob {
+    user_id: i32,
+    payload: WebhookPayload,
+}
+
+// Producer side of the webhook queue, handed to services. Events are queued
+// without waiting; when the queue is full they are dropped and logged.
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    sender: mpsc::Sender<WebhookJob>,
+}
+
+// Consumer side, spawned once by `AppRouter::serve`.
+pub struct WebhookWorker {
+    receiver: mpsc::Receiver<WebhookJob>,
+    repository: DynWebhookRepository,
+    client: Client,
+}
+
+impl WebhookDispatcher {
+    pub fn new(repository: DynWebhookRepository) -> (Self, WebhookWorker) {
+        let (sender, receiver) = mpsc::channel(QUEUE_CAPACITY);
+
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("valid webhook HTTP client");
+
+        (
+            Self { sender },
+            WebhookWorker {
+                receiver,
+                repository,
+                client,
+            },
+        )
+    }
+}
+
+impl WebhookNotifierTrait for WebhookDispatcher {
+    fn notify(&self, user_id: i32, event: WebhookEvent, data: Value) {
+        let job = WebhookJob {
+            user_id,
+            payload: WebhookPayload {
+                event,
+                data,
+                timestamp: Utc::now(),
+            },
+        };
+
+        if let Err(e) = self.sender.try_send(job) {
+            warn!(
+                "⚠️ [Webhook] Dropping {} event for user_id={user_id}: {e}",
+                event.as_str()
+            );
+        }
+    }
+}
+
+impl WebhookWorker {
+    pub async fn run(mut self) {
+        info!("🪝 [Webhook] Delivery worker started");
+
+        while let Some(job) = self.receiver.recv().await {
+            let webhooks = match self.repository.find_by_user_id(job.user_id).await {
+                Ok(webhooks) => webhooks,
+                Err(e) => {
+                    error!(
+                        "❌ [Webhook] Failed to load webhooks for user_id={}: {e}",
+                        job.user_id
+                    );
+                    continue;
+                }
+            };
+
+            if webhooks.is_empty() {
+                continue;
+            }
+
+            let body = match serde_json::to_vec(&job.payload) {
+                Ok(body) => body,
+                Err(e) => {
+                    error!("❌ [Webhook] Failed to encode payload: {e}");
+                    continue;
+                }
+            };
+
+            // Each endpoint retries on its own task so one slow receiver
+            // doesn't hold up the rest of the queue.
+            for webhook in webhooks {
+                tokio::spawn(deliver(
+                    self.client.clone(),
+                    webhook,
+                    job.payload.event,
+                    body.clone(),
+                ));
+            }
+        }
+
+        info!("🪝 [Webhook] Delivery worker stopped");
+    }
+}
+
+async fn deliver(client: Client, webhook: Webhook, event: WebhookEvent, body: Vec<u8>) {
+    let signature = sign_webhook_payload(&webhook.secret, &body);
+    let mut backoff = INITIAL_BACKOFF;
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        let result = client
+            .post(&webhook.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header("X-Webhook-Event", event.as_str())
+            .header("X-Webhook-Signature", &signature)
+            .body(body.clone())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+
+        match result {
+            Ok(_) => {
+                info!(
+                    "✅ [Webhook] Delivered {} to webhook {} on attempt {attempt}",
+                    event.as_str(),
+                    webhook.webhook_id
+                );
+                return;
+            }
+            Err(e) if attempt < MAX_ATTEMPTS => {
+                warn!(
+                    "⚠️ [Webhook] Attempt {attempt} to webhook {} failed, retrying in {backoff:?}: {e}",
+                    webhook.webhook_id
+                );
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+            Err(e) => error!(
+                "❌ [Webhook] Giving up on {} for webhook {} after {MAX_ATTEMPTS} attempts: {e}",
+                event.as_str(),
+                webhook.webhook_id
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Router,
+        body::Bytes,
+        extract::State,
+        http::{HeaderMap, StatusCode},
+        routing::post,
+    };
+    use serde_json::json;
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Instant,
+    };
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::test_support::MockWebhookRepositoryTrait;
+
+    struct Received {
+        at: Instant,
+        headers: HeaderMap,
+        body: Bytes,
+    }
+
+    #[derive(Clone)]
+    struct Receiver {
+        calls: Arc<AtomicUsize>,
+        received: mpsc::UnboundedSender<Received>,
+    }
+
+    // Fails the first delivery so the worker has to retry.
+    async fn receive(
+        State(receiver): State<Receiver>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> StatusCode {
+        let call = receiver.calls.fetch_add(1, Ordering::SeqCst);
+        let _ = receiver.received.send(Received {
+            at: Instant::now(),
+            headers,
+            body,
+        });
+
+        if call == 0 {
+            StatusCode::SERVICE_UNAVAILABLE
+        } else {
+            StatusCode::OK
+        }
+    }
+
+    async fn spawn_receiver() -> (String, mpsc::UnboundedReceiver<Received>) {
+        let (sender, received) = mpsc::unbounded_channel();
+        let app = Router::new()
+            .route("/hook", post(receive))
+            .with_state(Receiver {
+                calls: Arc::new(AtomicUsize::new(0)),
+                received: sender,
+            });
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        (format!("http://{addr}/hook"), received)
+    }
+
+    async fn next(received: &mut mpsc::UnboundedReceiver<Received>) -> Received {
+        tokio::time::timeout(Duration::from_secs(10), received.recv())
+            .await
+            .expect("webhook was not delivered in time")
+            .expect("receiver closed")
+    }
+
+    #[tokio::test]
+    async fn signed_event_is_retried_until_the_receiver_accepts_it() {
+        let (url, mut received) = spawn_receiver().await;
+
+        let mut repository = MockWebhookRepositoryTrait::new();
+        repository
+            .expect_find_by_user_id()
+            .times(1)
+            .returning(move |user_id| {
+                Ok(vec![Webhook {
+                    webhook_id: 1,
+                    user_id,
+                    url: url.clone(),
+                    secret: "whsec_test".to_string(),
+                    created_at: None,
+                    updated_at: None,
+                }])
+            });
+
+        let (dispatcher, worker) = WebhookDispatcher::new(Arc::new(repository));
+        tokio::spawn(worker.run());
+
+        dispatcher.notify(
+            7,
+            WebhookEvent::TopupCreated,
+            json!({ "topup_id": 3, "topup_amount": 50000 }),
+        );
+
+        let first = next(&mut received).await;
+        let second = next(&mut received).await;
+
+        // The retry resends the same signed body after the initial backoff.
+        assert!(second.at - first.at >= INITIAL_BACKOFF);
+        assert_eq!(first.body, second.body);
+
+        let signature = second.headers["X-Webhook-Signature"].to_str().unwrap();
+        assert_eq!(signature, sign_webhook_payload("whsec_test", &second.body));
+        assert_ne!(signature, sign_webhook_payload("other", &second.body));
+        assert_eq!(second.headers["X-Webhook-Event"], "topup.created");
+        assert_eq!(second.headers[CONTENT_TYPE], "application/json");
+
+        let payload: Value = serde_json::from_slice(&second.body).unwrap();
+        assert_eq!(payload["event"], "topup.created");
+        assert_eq!(payload["data"]["topup_id"], 3);
+        assert!(payload["timestamp"].is_string());
+
+        // Accepted, so nothing further arrives.
+        assert!(
+            tokio::time::timeout(INITIAL_BACKOFF * 3, received.recv())
+                .await
+                .is_err()
+        );
+    }
+}
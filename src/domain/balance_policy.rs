@@ -0,0 +1,105 @@
+use crate::{domain::money::Money, utils::AppError};
+
+/// Deployment-wide rules applied whenever a user's balance is debited.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BalancePolicy {
+    pub minimum_balance: Money,
+    pub daily_withdraw_limit: Option<Money>,
+    pub withdraw_approval_threshold: Option<Money>,
+    pub max_transfer_amount: Option<Money>,
+    // Whether reversing a transfer may leave the receiver's balance negative
+    // when they have already spent the money.
+    pub allow_negative_reversal: bool,
+}
+
+impl BalancePolicy {
+    pub fn ensure_minimum(&self, user_id: i32, remaining: Money) -> Result<(), AppError> {
+        if remaining < self.minimum_balance {
+            return Err(AppError::Custom(format!(
+                "Balance for user_id={user_id} would drop to {remaining}, below the required minimum balance of {}",
+                self.minimum_balance
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub fn ensure_transfer_limit(&self, transfer_amount: Money) -> Result<(), AppError> {
+        if let Some(max) = self.max_transfer_amount
+            && transfer_amount > max
+        {
+            return Err(AppError::invalid_field(
+                "transfer_amount",
+                format!(
+                    "Transfer amount {transfer_amount} exceeds the maximum single transfer of {max}"
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn requires_approval(&self, withdraw_amount: Money) -> bool {
+        self.withdraw_approval_threshold
+            .is_some_and(|threshold| withdraw_amount > threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(minimum_balance: i64) -> BalancePolicy {
+        BalancePolicy {
+            minimum_balance: Money::new(minimum_balance),
+            ..BalancePolicy::default()
+        }
+    }
+
+    #[test]
+    fn ending_exactly_at_the_minimum_is_allowed() {
+        assert!(policy(10_000).ensure_minimum(1, Money::new(10_000)).is_ok());
+        assert!(policy(0).ensure_minimum(1, Money::ZERO).is_ok());
+    }
+
+    #[test]
+    fn ending_just_below_the_minimum_is_rejected() {
+        let err = policy(10_000)
+            .ensure_minimum(1, Money::new(9_999))
+            .unwrap_err();
+
+        assert!(matches!(err, AppError::Custom(_)), "{err:?}");
+    }
+
+    #[test]
+    fn transfers_up_to_the_maximum_are_allowed() {
+        let policy = BalancePolicy {
+            max_transfer_amount: Some(Money::new(1_000_000)),
+            ..BalancePolicy::default()
+        };
+
+        assert!(policy.ensure_transfer_limit(Money::new(1_000_000)).is_ok());
+        assert!(
+            BalancePolicy::default()
+                .ensure_transfer_limit(Money::new(i64::MAX))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn transfers_over_the_maximum_are_rejected_on_the_amount() {
+        let policy = BalancePolicy {
+            max_transfer_amount: Some(Money::new(1_000_000)),
+            ..BalancePolicy::default()
+        };
+
+        let err = policy
+            .ensure_transfer_limit(Money::new(1_000_001))
+            .unwrap_err();
+
+        let AppError::Validation(errors) = err else {
+            panic!("expected a validation error, got {err:?}");
+        };
+        assert!(errors.field_errors().contains_key("transfer_amount"));
+    }
+}
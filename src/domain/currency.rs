@@ -0,0 +1,106 @@
+use core::fmt;
+use std::{str::FromStr, sync::RwLock};
+
+use sea_query::Value;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sqlx::{
+    Decode, Postgres, Type,
+    error::BoxDynError,
+    postgres::{PgTypeInfo, PgValueRef},
+};
+use utoipa::ToSchema;
+
+use crate::utils::AppError;
+
+/// An ISO 4217 currency code such as `IDR` or `USD`, stored as `VARCHAR(3)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ToSchema)]
+#[schema(value_type = String, example = "IDR")]
+pub struct Currency([u8; 3]);
+
+// Set once from `Config` at startup; requests that omit a currency get this.
+static BASE_CURRENCY: RwLock<Currency> = RwLock::new(Currency::IDR);
+
+impl Currency {
+    pub const IDR: Currency = Currency(*b"IDR");
+
+    pub fn base() -> Currency {
+        *BASE_CURRENCY.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set_base(currency: Currency) {
+        *BASE_CURRENCY.write().unwrap_or_else(|e| e.into_inner()) = currency;
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only ever built from ASCII uppercase letters.
+        std::str::from_utf8(&self.0).unwrap_or_default()
+    }
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Currency::base()
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().as_bytes() {
+            &[a, b, c] if [a, b, c].iter().all(u8::is_ascii_alphabetic) => Ok(Currency([
+                a.to_ascii_uppercase(),
+                b.to_ascii_uppercase(),
+                c.to_ascii_uppercase(),
+            ])),
+            _ => Err(AppError::Custom(format!(
+                "Invalid currency code: {s:?}, expected a 3-letter ISO 4217 code"
+            ))),
+        }
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        raw.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl From<Currency> for Value {
+    fn from(value: Currency) -> Self {
+        Value::String(Some(Box::new(value.as_str().to_string())))
+    }
+}
+
+impl Type<Postgres> for Currency {
+    fn type_info() -> PgTypeInfo {
+        <String as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <String as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Currency {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let raw = <&str as Decode<Postgres>>::decode(value)?;
+        Ok(raw.parse()?)
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{domain::money::Money, utils::AppError};
+
+// Basis points in 100%.
+pub const MAX_FEE_BASIS_POINTS: u32 = 10_000;
+
+/// Deployment-wide transfer fee: a flat part plus a percentage of the amount,
+/// in basis points (50 = 0.5%). Both default to zero, i.e. free transfers.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FeeSchedule {
+    pub flat: Money,
+    pub basis_points: u32,
+}
+
+impl FeeSchedule {
+    // The percentage part is rounded half up to the nearest minor unit.
+    pub fn fee_for(&self, amount: Money) -> Result<Money, AppError> {
+        let scaled = i128::from(amount.minor_units()) * i128::from(self.basis_points);
+        let percentage =
+            (scaled + i128::from(MAX_FEE_BASIS_POINTS / 2)) / i128::from(MAX_FEE_BASIS_POINTS);
+
+        let percentage = i64::try_from(percentage)
+            .map(Money::new)
+            .map_err(|_| AppError::Custom(format!("Fee on {amount} is out of range")))?;
+
+        self.flat.checked_add(percentage)
+    }
+
+    // The largest amount whose fee still fits alongside it in `budget`, or
+    // zero when even the flat part doesn't.
+    pub fn max_amount_within(&self, budget: Money) -> Result<Money, AppError> {
+        let spendable = i128::from(budget.checked_sub(self.flat)?.minor_units());
+        if spendable <= 0 {
+            return Ok(Money::ZERO);
+        }
+
+        let scale = i128::from(MAX_FEE_BASIS_POINTS);
+        let estimate = spendable * scale / (scale + i128::from(self.basis_points));
+
+        // Rounding the fee half up can move the answer by one either way.
+        let mut amount = Money::new(i64::try_from(estimate + 1).unwrap_or(i64::MAX));
+        while amount > Money::ZERO && amount.checked_add(self.fee_for(amount)?)? > budget {
+            amount = amount.checked_sub(Money::new(1))?;
+        }
+
+        Ok(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_by_default() {
+        assert_eq!(
+            FeeSchedule::default().fee_for(Money::new(100_000)).unwrap(),
+            Money::ZERO
+        );
+    }
+
+    #[test]
+    fn flat_fee_ignores_the_amount() {
+        let schedule = FeeSchedule {
+            flat: Money::new(2_500),
+            basis_points: 0,
+        };
+
+        assert_eq!(
+            schedule.fee_for(Money::new(50_000)).unwrap(),
+            Money::new(2_500)
+        );
+        assert_eq!(
+            schedule.fee_for(Money::new(10_000_000)).unwrap(),
+            Money::new(2_500)
+        );
+    }
+
+    #[test]
+    fn percentage_fee_rounds_half_up() {
+        let schedule = FeeSchedule {
+            flat: Money::ZERO,
+            basis_points: 50,
+        };
+
+        assert_eq!(
+            schedule.fee_for(Money::new(100_000)).unwrap(),
+            Money::new(500)
+        );
+        // 0.5% of 50_101 is 250.505.
+        assert_eq!(
+            schedule.fee_for(Money::new(50_101)).unwrap(),
+            Money::new(251)
+        );
+        // 0.5% of 50_099 is 250.495.
+        assert_eq!(
+            schedule.fee_for(Money::new(50_099)).unwrap(),
+            Money::new(250)
+        );
+    }
+
+    #[test]
+    fn flat_and_percentage_add_up() {
+        let schedule = FeeSchedule {
+            flat: Money::new(1_000),
+            basis_points: 100,
+        };
+
+        assert_eq!(
+            schedule.fee_for(Money::new(200_000)).unwrap(),
+            Money::new(3_000)
+        );
+    }
+
+    #[test]
+    fn max_amount_leaves_room_for_the_fee() {
+        let schedule = FeeSchedule {
+            flat: Money::new(1_000),
+            basis_points: 50,
+        };
+
+        let budget = Money::new(500_000);
+        let amount = schedule.max_amount_within(budget).unwrap();
+        let total = amount
+            .checked_add(schedule.fee_for(amount).unwrap())
+            .unwrap();
+        let one_more = amount.checked_add(Money::new(1)).unwrap();
+
+        assert!(total <= budget, "{amount} + fee exceeds {budget}");
+        assert!(
+            one_more
+                .checked_add(schedule.fee_for(one_more).unwrap())
+                .unwrap()
+                > budget,
+            "{one_more} would still fit in {budget}"
+        );
+    }
+
+    #[test]
+    fn max_amount_is_the_whole_budget_without_fees() {
+        assert_eq!(
+            FeeSchedule::default()
+                .max_amount_within(Money::new(75_000))
+                .unwrap(),
+            Money::new(75_000)
+        );
+    }
+
+    #[test]
+    fn max_amount_is_zero_when_the_flat_fee_eats_the_budget() {
+        let schedule = FeeSchedule {
+            flat: Money::new(2_500),
+            basis_points: 0,
+        };
+
+        assert_eq!(
+            schedule.max_amount_within(Money::new(2_500)).unwrap(),
+            Money::ZERO
+        );
+        assert_eq!(
+            schedule.max_amount_within(Money::new(-10)).unwrap(),
+            Money::ZERO
+        );
+    }
+}
@@ -1,2 +1,11 @@
+pub mod balance_policy;
+pub mod currency;
+pub mod fees;
+pub mod money;
 pub mod request;
 pub mod response;
+pub mod role;
+pub mod topup_limits;
+pub mod transfer_status;
+pub mod webhook;
+pub mod withdraw_status;
@@ -0,0 +1,240 @@
+use core::fmt;
+use std::str::FromStr;
+
+use sea_query::Value;
+use serde::{Deserialize, Deserializer, Serialize, de};
+use sqlx::{
+    Decode, Postgres, Type,
+    error::BoxDynError,
+    postgres::{PgTypeInfo, PgValueRef},
+};
+use utoipa::ToSchema;
+use validator::ValidateRange;
+
+use crate::utils::{AppError, Language};
+
+/// An amount of money in minor currency units, stored as `BIGINT`.
+///
+/// Serialized as a JSON integer. Deserialization also accepts a string
+/// holding an integer (e.g. `"150000"`), so clients that encode amounts as
+/// strings to dodge float precision loss keep working.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, ToSchema,
+)]
+#[schema(value_type = i64, example = 50000)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub const fn new(minor_units: i64) -> Self {
+        Money(minor_units)
+    }
+
+    pub const fn minor_units(self) -> i64 {
+        self.0
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    pub fn checked_add(self, other: Money) -> Result<Money, AppError> {
+        self.0.checked_add(other.0).map(Money).ok_or_else(|| {
+            AppError::Custom(format!("Amount overflow: {self} + {other} is out of range"))
+        })
+    }
+
+    pub fn checked_sub(self, other: Money) -> Result<Money, AppError> {
+        self.0.checked_sub(other.0).map(Money).ok_or_else(|| {
+            AppError::Custom(format!("Amount overflow: {self} - {other} is out of range"))
+        })
+    }
+
+    pub fn checked_sum<I>(amounts: I) -> Result<Money, AppError>
+    where
+        I: IntoIterator<Item = Money>,
+    {
+        amounts
+            .into_iter()
+            .try_fold(Money::ZERO, |acc, amount| acc.checked_add(amount))
+    }
+
+    /// Formats the amount for display with digit grouping and two decimals,
+    /// e.g. `1.500.000,00` in Indonesian or `1,500,000.00` in English.
+    pub fn format(self, language: Language) -> String {
+        let (group, decimal) = match language {
+            Language::English => (',', '.'),
+            Language::Indonesian => ('.', ','),
+        };
+
+        let units = self.0.unsigned_abs();
+        let digits = (units / 100).to_string();
+
+        let mut formatted = String::with_capacity(digits.len() * 4 / 3 + 4);
+        if self.is_negative() {
+            formatted.push('-');
+        }
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                formatted.push(group);
+            }
+            formatted.push(digit);
+        }
+        formatted.push(decimal);
+        formatted.push_str(&format!("{:02}", units % 100));
+
+        formatted
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<i64> for Money {
+    fn from(value: i64) -> Self {
+        Money(value)
+    }
+}
+
+impl From<i32> for Money {
+    fn from(value: i32) -> Self {
+        Money(value as i64)
+    }
+}
+
+impl FromStr for Money {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim()
+            .parse::<i64>()
+            .map(Money)
+            .map_err(|_| AppError::Custom(format!("Invalid amount: {s:?}")))
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MoneyVisitor;
+
+        impl de::Visitor<'_> for MoneyVisitor {
+            type Value = Money;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an integer amount in minor units, or a string holding one")
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Money, E> {
+                Ok(Money(v))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Money, E> {
+                i64::try_from(v)
+                    .map(Money)
+                    .map_err(|_| E::custom("amount is out of range"))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Money, E> {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(MoneyVisitor)
+    }
+}
+
+impl ValidateRange<i64> for Money {
+    fn greater_than(&self, max: i64) -> Option<bool> {
+        Some(self.0 > max)
+    }
+
+    fn less_than(&self, min: i64) -> Option<bool> {
+        Some(self.0 < min)
+    }
+}
+
+impl From<Money> for Value {
+    fn from(value: Money) -> Self {
+        Value::BigInt(Some(value.0))
+    }
+}
+
+impl Type<Postgres> for Money {
+    fn type_info() -> PgTypeInfo {
+        <i64 as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <i64 as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Money {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        <i64 as Decode<Postgres>>::decode(value).map(Money)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_groups_digits_per_language() {
+        let amount = Money::new(150_000_000);
+
+        assert_eq!(amount.format(Language::Indonesian), "1.500.000,00");
+        assert_eq!(amount.format(Language::English), "1,500,000.00");
+    }
+
+    #[test]
+    fn format_handles_small_and_negative_amounts() {
+        assert_eq!(Money::ZERO.format(Language::English), "0.00");
+        assert_eq!(Money::new(5).format(Language::English), "0.05");
+        assert_eq!(
+            Money::new(-123_456).format(Language::Indonesian),
+            "-1.234,56"
+        );
+    }
+
+    #[test]
+    fn checked_arithmetic_reports_overflow() {
+        let max = Money::new(i64::MAX);
+        let min = Money::new(i64::MIN);
+
+        assert_eq!(
+            Money::new(40).checked_add(Money::new(2)).unwrap(),
+            Money::new(42)
+        );
+        assert_eq!(
+            Money::new(40).checked_sub(Money::new(42)).unwrap(),
+            Money::new(-2)
+        );
+
+        assert!(max.checked_add(Money::new(1)).is_err());
+        assert!(min.checked_sub(Money::new(1)).is_err());
+        assert!(Money::checked_sum([max, Money::new(1)]).is_err());
+    }
+
+    #[test]
+    fn deserializes_integers_and_strings() {
+        let parse = |json: &str| serde_json::from_str::<Money>(json);
+
+        assert_eq!(parse("150000").unwrap(), Money::new(150_000));
+        assert_eq!(parse("-2500").unwrap(), Money::new(-2500));
+        assert_eq!(parse("\"150000\"").unwrap(), Money::new(150_000));
+        assert_eq!(parse("\" 42 \"").unwrap(), Money::new(42));
+
+        assert!(parse("\"12.50\"").is_err());
+        assert!(parse("\"abc\"").is_err());
+        assert!(parse(&u64::MAX.to_string()).is_err());
+        assert!(parse("12.5").is_err());
+    }
+}
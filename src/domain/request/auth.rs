@@ -1,26 +1,32 @@
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use std::net::IpAddr;
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
+use crate::domain::request::extra::ExtraValidate;
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 pub struct RegisterRequest {
-    #[validate(length(min = 2, message = "First name must be at least 2 characters"))]
+    #[validate(length(min = 2, max = 100, message = "First name must be 2-100 characters"))]
     pub firstname: String,
 
-    #[validate(length(min = 2, message = "Last name must be at least 2 characters"))]
+    #[validate(length(min = 2, max = 100, message = "Last name must be 2-100 characters"))]
     pub lastname: String,
 
     #[validate(email(message = "Invalid email format"))]
+    #[validate(length(max = 100, message = "Email must be at most 100 characters"))]
     pub email: String,
 
-    #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
+    // bcrypt only looks at the first 72 bytes of the password.
+    #[validate(length(min = 8, max = 72, message = "Password must be 8-72 characters"))]
     pub password: String,
 
-    #[validate(length(min = 6, message = "Confirm password must be at least 6 characters"))]
-    #[validate(must_match(other = "password"))]
+    #[validate(must_match(other = "password", message = "Passwords do not match"))]
     pub confirm_password: String,
 }
 
+impl ExtraValidate for RegisterRequest {}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 pub struct LoginRequest {
     #[validate(email(message = "Invalid email format"))]
@@ -29,3 +35,94 @@ pub struct LoginRequest {
     #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
     pub password: String,
 }
+
+impl ExtraValidate for LoginRequest {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct RefreshRequest {
+    #[validate(length(min = 1, message = "Refresh token is required"))]
+    pub refresh_token: String,
+}
+
+impl ExtraValidate for RefreshRequest {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ForgotPasswordRequest {
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+}
+
+impl ExtraValidate for ForgotPasswordRequest {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ResetPasswordRequest {
+    #[validate(length(min = 1, message = "Reset token is required"))]
+    pub token: String,
+
+    #[validate(length(min = 8, max = 72, message = "Password must be 8-72 characters"))]
+    pub password: String,
+
+    #[validate(must_match(other = "password", message = "Passwords do not match"))]
+    pub confirm_password: String,
+}
+
+impl ExtraValidate for ResetPasswordRequest {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct DeleteAccountRequest {
+    #[validate(length(min = 1, message = "Password is required"))]
+    pub password: String,
+}
+
+impl ExtraValidate for DeleteAccountRequest {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+pub struct VerifyEmailQuery {
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ResendVerificationRequest {
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+}
+
+impl ExtraValidate for ResendVerificationRequest {}
+
+// Longest user agent kept in the login audit.
+const MAX_USER_AGENT_LENGTH: usize = 512;
+
+// Where a login attempt came from, taken from the connection and headers
+// rather than the body.
+#[derive(Debug, Clone, Default)]
+pub struct LoginContext {
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl LoginContext {
+    pub fn new(ip: IpAddr, user_agent: Option<&str>) -> Self {
+        Self {
+            ip: Some(ip.to_string()),
+            user_agent: user_agent
+                .map(str::trim)
+                .filter(|agent| !agent.is_empty())
+                .map(|agent| agent.chars().take(MAX_USER_AGENT_LENGTH).collect()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+pub struct MeQuery {
+    /// Comma-separated related resources to embed; only `saldo` is supported.
+    #[serde(default)]
+    pub include: Option<String>,
+}
+
+impl MeQuery {
+    pub fn includes_saldo(&self) -> bool {
+        self.include
+            .as_deref()
+            .is_some_and(|include| include.split(',').any(|item| item.trim() == "saldo"))
+    }
+}
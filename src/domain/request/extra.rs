@@ -0,0 +1,9 @@
+/// Cross-field rules that `#[validate]` attributes can't express, such as
+/// "a withdraw time goes with a withdraw amount". `SimpleValidatedJson`
+/// runs them right after `validate()`, so every body type needs an impl;
+/// most just take the default.
+pub trait ExtraValidate {
+    fn extra_validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
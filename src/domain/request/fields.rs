@@ -0,0 +1,28 @@
+use std::collections::BTreeSet;
+
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct FieldsQuery {
+    /// Comma-separated fields to keep in `data`, e.g. `id,total_balance`.
+    /// Omit for the full object.
+    #[serde(default)]
+    pub fields: Option<String>,
+}
+
+impl FieldsQuery {
+    // `None` when the client didn't ask to trim anything.
+    pub fn names(&self) -> Option<BTreeSet<String>> {
+        let names: BTreeSet<String> = self
+            .fields
+            .as_deref()?
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        (!names.is_empty()).then_some(names)
+    }
+}
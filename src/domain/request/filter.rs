@@ -0,0 +1,132 @@
+use chrono::{DateTime, Utc};
+use sea_query::{Expr, IntoColumnRef, SelectStatement};
+use validator::ValidationError;
+
+use crate::domain::money::Money;
+
+#[derive(Debug, Clone, Default)]
+pub struct TransactionFilter {
+    pub min_amount: Option<Money>,
+    pub max_amount: Option<Money>,
+    pub from_date: Option<DateTime<Utc>>,
+    pub to_date: Option<DateTime<Utc>>,
+    pub include_deleted: bool,
+    // Also match `search` against the note, for tables that have one.
+    pub search_notes: bool,
+}
+
+impl TransactionFilter {
+    // Bounds are inclusive on both ends. Timestamps are stored as UTC
+    // `TIMESTAMP`, so dates are compared in naive UTC.
+    pub fn apply(
+        &self,
+        query: &mut SelectStatement,
+        amount: impl IntoColumnRef,
+        time: impl IntoColumnRef,
+    ) {
+        let amount = Expr::col(amount);
+        let time = Expr::col(time);
+
+        if let Some(min_amount) = self.min_amount {
+            query.and_where(amount.clone().gte(min_amount));
+        }
+
+        if let Some(max_amount) = self.max_amount {
+            query.and_where(amount.lte(max_amount));
+        }
+
+        if let Some(from_date) = self.from_date {
+            query.and_where(time.clone().gte(from_date.naive_utc()));
+        }
+
+        if let Some(to_date) = self.to_date {
+            query.and_where(time.lte(to_date.naive_utc()));
+        }
+    }
+}
+
+pub fn validate_ranges(
+    min_amount: Option<Money>,
+    max_amount: Option<Money>,
+    from_date: Option<DateTime<Utc>>,
+    to_date: Option<DateTime<Utc>>,
+) -> Result<(), ValidationError> {
+    if let (Some(min), Some(max)) = (min_amount, max_amount)
+        && min > max
+    {
+        return Err(ValidationError::new("amount_range")
+            .with_message("min_amount must not be greater than max_amount".into()));
+    }
+
+    if let (Some(from), Some(to)) = (from_date, to_date)
+        && from > to
+    {
+        return Err(ValidationError::new("date_range")
+            .with_message("from_date must not be after to_date".into()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use sea_query::{Alias, PostgresQueryBuilder, Query};
+
+    use super::*;
+
+    fn march(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, day, 0, 0, 0).unwrap()
+    }
+
+    fn where_clause(filter: &TransactionFilter) -> String {
+        let mut query = Query::select();
+        query
+            .column(Alias::new("amount"))
+            .from(Alias::new("withdraws"));
+        filter.apply(&mut query, Alias::new("amount"), Alias::new("time"));
+
+        query.to_string(PostgresQueryBuilder)
+    }
+
+    #[test]
+    fn amount_and_date_bounds_combine_inclusively() {
+        let filter = TransactionFilter {
+            min_amount: Some(Money::new(1_000_000)),
+            from_date: Some(march(1)),
+            to_date: Some(march(31)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            where_clause(&filter),
+            r#"SELECT "amount" FROM "withdraws" WHERE "amount" >= 1000000 AND "time" >= '2025-03-01 00:00:00.000000' AND "time" <= '2025-03-31 00:00:00.000000'"#
+        );
+    }
+
+    #[test]
+    fn no_bounds_filter_nothing() {
+        assert_eq!(
+            where_clause(&TransactionFilter::default()),
+            r#"SELECT "amount" FROM "withdraws""#
+        );
+    }
+
+    #[test]
+    fn equal_bounds_are_a_valid_range() {
+        let amount = Some(Money::new(50_000));
+
+        assert!(validate_ranges(amount, amount, Some(march(5)), Some(march(5))).is_ok());
+        assert!(validate_ranges(None, amount, Some(march(5)), None).is_ok());
+    }
+
+    #[test]
+    fn inverted_ranges_are_rejected() {
+        let err =
+            validate_ranges(Some(Money::new(2)), Some(Money::new(1)), None, None).unwrap_err();
+        assert_eq!(err.code, "amount_range");
+
+        let err = validate_ranges(None, None, Some(march(2)), Some(march(1))).unwrap_err();
+        assert_eq!(err.code, "date_range");
+    }
+}
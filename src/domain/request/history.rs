@@ -0,0 +1,151 @@
+use chrono::{DateTime, Utc};
+use core::fmt;
+use std::str::FromStr;
+
+use sea_query::Value;
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    Decode, Postgres, Type,
+    error::BoxDynError,
+    postgres::{PgTypeInfo, PgValueRef},
+};
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+use crate::{domain::request::filter::validate_ranges, utils::AppError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryKind {
+    Topup,
+    TransferIn,
+    TransferOut,
+    Withdraw,
+}
+
+impl HistoryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HistoryKind::Topup => "topup",
+            HistoryKind::TransferIn => "transfer_in",
+            HistoryKind::TransferOut => "transfer_out",
+            HistoryKind::Withdraw => "withdraw",
+        }
+    }
+}
+
+impl fmt::Display for HistoryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for HistoryKind {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "topup" => Ok(HistoryKind::Topup),
+            "transfer_in" => Ok(HistoryKind::TransferIn),
+            "transfer_out" => Ok(HistoryKind::TransferOut),
+            "withdraw" => Ok(HistoryKind::Withdraw),
+            other => Err(AppError::Custom(format!("Unknown history kind: {other}"))),
+        }
+    }
+}
+
+impl From<HistoryKind> for Value {
+    fn from(value: HistoryKind) -> Self {
+        Value::String(Some(Box::new(value.as_str().to_string())))
+    }
+}
+
+impl Type<Postgres> for HistoryKind {
+    fn type_info() -> PgTypeInfo {
+        <String as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <String as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for HistoryKind {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let raw = <&str as Decode<Postgres>>::decode(value)?;
+        Ok(raw.parse()?)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams, Validate)]
+#[validate(schema(function = "validate_history_ranges"))]
+pub struct FindHistoryRequest {
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+
+    #[serde(default)]
+    #[param(value_type = Option<HistoryKind>)]
+    pub kind: Option<HistoryKind>,
+
+    #[serde(default)]
+    pub from_date: Option<DateTime<Utc>>,
+
+    #[serde(default)]
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+impl FindHistoryRequest {
+    pub fn filter(&self) -> HistoryFilter {
+        HistoryFilter {
+            kind: self.kind,
+            from_date: self.from_date,
+            to_date: self.to_date,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HistoryFilter {
+    pub kind: Option<HistoryKind>,
+    pub from_date: Option<DateTime<Utc>>,
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams, Validate)]
+#[validate(schema(function = "validate_statement_range"))]
+pub struct StatementRequest {
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl StatementRequest {
+    pub fn filter(&self) -> HistoryFilter {
+        HistoryFilter {
+            kind: None,
+            from_date: self.from,
+            to_date: self.to,
+        }
+    }
+}
+
+fn validate_statement_range(data: &StatementRequest) -> Result<(), ValidationError> {
+    validate_ranges(None, None, data.from, data.to)
+}
+
+fn validate_history_ranges(data: &FindHistoryRequest) -> Result<(), ValidationError> {
+    validate_ranges(None, None, data.from_date, data.to_date)
+}
+
+fn default_page() -> i32 {
+    1
+}
+
+fn default_page_size() -> i32 {
+    10
+}
@@ -1,26 +1,63 @@
 pub mod auth;
+pub mod extra;
+pub mod fields;
+pub mod filter;
+pub mod history;
+pub mod note;
+pub mod pagination;
 pub mod saldo;
+pub mod saldo_history;
+pub mod search;
+pub mod sort;
 pub mod topup;
 pub mod transfer;
 pub mod user;
+pub mod webhook;
 pub mod withdraw;
 
-pub use self::user::{CreateUserRequest, FindAllUserRequest, UpdateUserRequest};
+pub use self::user::{
+    CreateUserRequest, FindAllUserRequest, FindUserCounterpartiesRequest, FindUsersBatchRequest,
+    GetUserQuery, UpdateUserRequest, UpdateUserRoleRequest, UserSearch,
+};
+
+pub use self::history::{FindHistoryRequest, HistoryFilter, HistoryKind, StatementRequest};
+
+pub use self::saldo_history::{BalanceChangeReason, FindSaldoHistoryRequest};
+
+pub use self::extra::ExtraValidate;
+
+pub use self::pagination::{PageableRequest, normalize_page, normalize_pagination};
+
+pub use self::search::{MatchMode, Search};
 
-pub use self::auth::{LoginRequest, RegisterRequest};
+pub use self::sort::{Sort, SortOrder};
+
+pub use self::fields::FieldsQuery;
+
+pub use self::filter::TransactionFilter;
+
+pub use self::auth::{
+    DeleteAccountRequest, ForgotPasswordRequest, LoginContext, LoginRequest, MeQuery,
+    RefreshRequest, RegisterRequest, ResendVerificationRequest, ResetPasswordRequest,
+    VerifyEmailQuery,
+};
 
 pub use self::saldo::{
-    CreateSaldoRequest, FindAllSaldoRequest, UpdateSaldoBalance, UpdateSaldoRequest,
-    UpdateSaldoWithdraw,
+    CreateSaldoRequest, FindAllSaldoRequest, FindUserSaldosRequest, FreezeSaldoRequest,
+    IncrementSaldoBalance, UpdateSaldoBalance, UpdateSaldoRequest, UpdateSaldoWithdraw,
 };
 
 pub use self::transfer::{
-    CreateTransferRequest, FindAllTransferRequest, UpdateTransferAmountRequest,
-    UpdateTransferRequest,
+    BatchRecipient, CreateBatchTransferRequest, CreateTransferRequest, FindAllTransferRequest,
+    FindTransfersBetweenRequest, FindUserTransfersRequest, TransferAllRequest, TransferDirection,
+    TransferNetFlowRequest, UpdateTransferAmountRequest, UpdateTransferRequest,
 };
 
 pub use self::topup::{
-    CreateTopupRequest, FindAllTopupRequest, UpdateTopupAmount, UpdateTopupRequest,
+    BulkTopupRow, CreateBulkTopupRequest, CreateTopupRequest, FindAllTopupRequest,
+    TopupMonthlyStatsRequest, UpdateTopupAmount, UpdateTopupRequest,
 };
 
+pub use self::webhook::CreateWebhookRequest;
+
 pub use self::withdraw::{CreateWithdrawRequest, FindAllWithdrawRequest, UpdateWithdrawRequest};
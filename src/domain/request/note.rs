@@ -0,0 +1,42 @@
+use serde::{Deserialize, Deserializer};
+
+pub const MAX_NOTE_LENGTH: u64 = 255;
+
+// Free text from the payer, so control characters (newlines included) are
+// dropped and surrounding whitespace trimmed. A note that ends up empty is
+// stored as no note at all. Length is checked afterwards by `validate`.
+pub fn sanitize_note(note: &str) -> Option<String> {
+    let note: String = note.chars().filter(|c| !c.is_control()).collect();
+    let note = note.trim();
+
+    (!note.is_empty()).then(|| note.to_string())
+}
+
+pub fn deserialize_note<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<String>::deserialize(deserializer)?
+        .as_deref()
+        .and_then(sanitize_note))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trims_and_strips_control_characters() {
+        assert_eq!(sanitize_note("  rent\n"), Some("rent".to_string()));
+        assert_eq!(
+            sanitize_note("split\u{0}\tthe bill"),
+            Some("splitthe bill".to_string())
+        );
+    }
+
+    #[test]
+    fn blank_notes_become_none() {
+        assert_eq!(sanitize_note(""), None);
+        assert_eq!(sanitize_note(" \r\n\t "), None);
+    }
+}
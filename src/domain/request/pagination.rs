@@ -0,0 +1,99 @@
+use crate::domain::request::search::{MatchMode, Search};
+
+pub const DEFAULT_PAGE_SIZE: i32 = 10;
+pub const DEFAULT_MAX_PAGE_SIZE: i32 = 100;
+
+/// Defaults non-positive `page`/`page_size` and caps `page_size` at
+/// `max_page_size` (the service's configured maximum), so oversized requests
+/// are trimmed rather than rejected.
+pub fn normalize_page(page: i32, page_size: i32, max_page_size: i32) -> (i32, i32) {
+    let page = if page > 0 { page } else { 1 };
+    let page_size = if page_size > 0 {
+        page_size
+    } else {
+        DEFAULT_PAGE_SIZE
+    };
+
+    (page, page_size.min(max_page_size.max(1)))
+}
+
+/// A list request carrying the usual `page`/`page_size`/`search`/`match_mode`
+/// query params.
+pub trait PageableRequest {
+    fn page(&self) -> i32;
+    fn page_size(&self) -> i32;
+    fn search(&self) -> &str;
+    fn match_mode(&self) -> MatchMode;
+}
+
+pub fn normalize_pagination(
+    req: &impl PageableRequest,
+    max_page_size: i32,
+) -> (i32, i32, Option<Search>) {
+    let (page, page_size) = normalize_page(req.page(), req.page_size(), max_page_size);
+    // Surrounding whitespace is dropped, so a blank search filters nothing.
+    let search = Some(req.search().trim())
+        .filter(|search| !search.is_empty())
+        .map(|search| Search::new(search, req.match_mode()));
+
+    (page, page_size, search)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_size_is_capped_at_the_given_maximum() {
+        assert_eq!(normalize_page(2, 500, 100), (2, 100));
+        assert_eq!(normalize_page(2, 500, 25), (2, 25));
+        assert_eq!(normalize_page(1, 20, 25), (1, 20));
+    }
+
+    #[test]
+    fn non_positive_values_fall_back_to_defaults() {
+        assert_eq!(normalize_page(0, 0, 100), (1, DEFAULT_PAGE_SIZE));
+        assert_eq!(normalize_page(-3, -1, 5), (1, 5));
+        assert_eq!(normalize_page(1, 20, 0), (1, 1));
+    }
+
+    struct Req {
+        search: &'static str,
+    }
+
+    impl PageableRequest for Req {
+        fn page(&self) -> i32 {
+            0
+        }
+
+        fn page_size(&self) -> i32 {
+            0
+        }
+
+        fn search(&self) -> &str {
+            self.search
+        }
+
+        fn match_mode(&self) -> MatchMode {
+            MatchMode::Prefix
+        }
+    }
+
+    #[test]
+    fn empty_or_blank_search_filters_nothing() {
+        for search in ["", "   ", "\t\n"] {
+            assert_eq!(
+                normalize_pagination(&Req { search }, 100),
+                (1, DEFAULT_PAGE_SIZE, None),
+                "{search:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn search_is_trimmed_and_keeps_its_match_mode() {
+        let (_, _, search) = normalize_pagination(&Req { search: "  ali " }, 100);
+
+        assert_eq!(search, Some(Search::new("ali", MatchMode::Prefix)));
+    }
+}
@@ -3,6 +3,19 @@ use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
+use crate::domain::{
+    currency::Currency,
+    money::Money,
+    request::{
+        extra::ExtraValidate,
+        note::{MAX_NOTE_LENGTH, deserialize_note},
+        pagination::PageableRequest,
+        saldo_history::BalanceChangeReason,
+        search::MatchMode,
+        sort::SortOrder,
+    },
+};
+
 #[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
 pub struct FindAllSaldoRequest {
     #[serde(default = "default_page")]
@@ -13,6 +26,47 @@ pub struct FindAllSaldoRequest {
 
     #[serde(default)]
     pub search: String,
+
+    #[serde(default)]
+    #[param(value_type = Option<MatchMode>)]
+    pub match_mode: MatchMode,
+
+    #[serde(default)]
+    pub sort_by: Option<String>,
+
+    #[serde(default)]
+    #[param(value_type = Option<SortOrder>)]
+    pub order: SortOrder,
+
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
+impl PageableRequest for FindAllSaldoRequest {
+    fn page(&self) -> i32 {
+        self.page
+    }
+
+    fn page_size(&self) -> i32 {
+        self.page_size
+    }
+
+    fn search(&self) -> &str {
+        &self.search
+    }
+
+    fn match_mode(&self) -> MatchMode {
+        self.match_mode
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct FindUserSaldosRequest {
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
 }
 
 fn default_page() -> i32 {
@@ -24,6 +78,7 @@ fn default_page_size() -> i32 {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
 pub struct CreateSaldoRequest {
     #[serde(rename = "user_id")]
     #[validate(range(min = 1))]
@@ -31,10 +86,16 @@ pub struct CreateSaldoRequest {
 
     #[serde(rename = "total_balance")]
     #[validate(range(min = 50000))]
-    pub total_balance: i32,
+    pub total_balance: Money,
+
+    #[serde(default)]
+    pub currency: Currency,
 }
 
+impl ExtraValidate for CreateSaldoRequest {}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
 pub struct UpdateSaldoRequest {
     #[serde(rename = "saldo_id")]
     #[validate(range(min = 1))]
@@ -46,19 +107,19 @@ pub struct UpdateSaldoRequest {
 
     #[serde(rename = "total_balance")]
     #[validate(range(min = 50000))]
-    pub total_balance: i32,
+    pub total_balance: Money,
 
     #[serde(rename = "withdraw_amount")]
-    pub withdraw_amount: Option<i32>,
+    pub withdraw_amount: Option<Money>,
 
     #[serde(rename = "withdraw_time")]
     pub withdraw_time: Option<NaiveDateTime>,
 }
 
-impl UpdateSaldoRequest {
-    pub fn extra_validate(&self) -> Result<(), String> {
+impl ExtraValidate for UpdateSaldoRequest {
+    fn extra_validate(&self) -> Result<(), String> {
         if let Some(amount) = self.withdraw_amount
-            && amount < 50000
+            && amount < Money::new(50000)
         {
             return Err("Withdraw amount must be at least 50000".to_string());
         }
@@ -67,17 +128,61 @@ impl UpdateSaldoRequest {
             return Err("Either withdraw_amount or withdraw_time must be provided".to_string());
         }
 
+        if self.withdraw_amount.is_some() && self.withdraw_time.is_none() {
+            return Err(
+                "Withdraw time must be provided if withdraw amount is provided".to_string(),
+            );
+        }
+
+        if self.withdraw_amount.is_none() && self.withdraw_time.is_some() {
+            return Err(
+                "Withdraw amount must be provided if withdraw time is provided".to_string(),
+            );
+        }
+
         Ok(())
     }
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct FreezeSaldoRequest {
+    // Why compliance froze the account; cleaned up like a transfer note.
+    #[serde(default, deserialize_with = "deserialize_note")]
+    #[validate(length(max = MAX_NOTE_LENGTH, message = "Reason must be at most 255 characters"))]
+    pub reason: Option<String>,
+}
+
+impl ExtraValidate for FreezeSaldoRequest {}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
 pub struct UpdateSaldoBalance {
     #[validate(range(min = 50000))]
-    pub total_balance: i32,
+    pub total_balance: Money,
 
     #[validate(range(min = 1))]
     pub user_id: i32,
+
+    pub currency: Currency,
+
+    pub reason: BalanceChangeReason,
+
+    pub reference_id: Option<i32>,
+}
+
+// Adds `amount` to the balance in place rather than writing back a total
+// computed from an earlier read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncrementSaldoBalance {
+    pub user_id: i32,
+
+    pub currency: Currency,
+
+    pub amount: Money,
+
+    pub reason: BalanceChangeReason,
+
+    pub reference_id: Option<i32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
@@ -86,21 +191,27 @@ pub struct UpdateSaldoWithdraw {
     #[validate(range(min = 1))]
     pub user_id: i32,
 
+    #[serde(rename = "currency")]
+    pub currency: Currency,
+
     #[serde(rename = "total_balance")]
     #[validate(range(min = 50000))]
-    pub total_balance: i32,
+    pub total_balance: Money,
 
     #[serde(rename = "withdraw_amount")]
-    pub withdraw_amount: Option<i32>,
+    pub withdraw_amount: Option<Money>,
 
     #[serde(rename = "withdraw_time")]
     pub withdraw_time: Option<DateTime<Utc>>,
+
+    #[serde(rename = "reference_id")]
+    pub reference_id: Option<i32>,
 }
 
-impl UpdateSaldoWithdraw {
-    pub fn extra_validate(&self) -> Result<(), String> {
+impl ExtraValidate for UpdateSaldoWithdraw {
+    fn extra_validate(&self) -> Result<(), String> {
         match self.withdraw_amount {
-            Some(amount) if amount <= 0 => {
+            Some(amount) if amount <= Money::ZERO => {
                 return Err("Withdraw amount must be greater than 0".to_string());
             }
             Some(amount) if amount > self.total_balance => {
@@ -124,3 +235,52 @@ impl UpdateSaldoWithdraw {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(
+        withdraw_amount: Option<i64>,
+        withdraw_time: Option<NaiveDateTime>,
+    ) -> UpdateSaldoRequest {
+        UpdateSaldoRequest {
+            saldo_id: 1,
+            user_id: 1,
+            total_balance: Money::new(100000),
+            withdraw_amount: withdraw_amount.map(Money::new),
+            withdraw_time,
+        }
+    }
+
+    #[test]
+    fn withdraw_amount_without_time_is_invalid() {
+        let error = request(Some(60000), None).extra_validate().unwrap_err();
+
+        assert_eq!(
+            error,
+            "Withdraw time must be provided if withdraw amount is provided"
+        );
+    }
+
+    #[test]
+    fn withdraw_time_without_amount_is_invalid() {
+        let now = Utc::now().naive_utc();
+
+        assert!(request(None, Some(now)).extra_validate().is_err());
+    }
+
+    #[test]
+    fn withdraw_below_the_minimum_is_invalid() {
+        let now = Utc::now().naive_utc();
+
+        assert!(request(Some(10000), Some(now)).extra_validate().is_err());
+    }
+
+    #[test]
+    fn paired_withdraw_is_valid() {
+        let now = Utc::now().naive_utc();
+
+        assert!(request(Some(60000), Some(now)).extra_validate().is_ok());
+    }
+}
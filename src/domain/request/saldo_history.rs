@@ -0,0 +1,95 @@
+use core::fmt;
+use std::str::FromStr;
+
+use sea_query::Value;
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    Decode, Postgres, Type,
+    error::BoxDynError,
+    postgres::{PgTypeInfo, PgValueRef},
+};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::utils::AppError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BalanceChangeReason {
+    Topup,
+    Transfer,
+    Withdraw,
+    Adjustment,
+}
+
+impl BalanceChangeReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BalanceChangeReason::Topup => "topup",
+            BalanceChangeReason::Transfer => "transfer",
+            BalanceChangeReason::Withdraw => "withdraw",
+            BalanceChangeReason::Adjustment => "adjustment",
+        }
+    }
+}
+
+impl fmt::Display for BalanceChangeReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BalanceChangeReason {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "topup" => Ok(BalanceChangeReason::Topup),
+            "transfer" => Ok(BalanceChangeReason::Transfer),
+            "withdraw" => Ok(BalanceChangeReason::Withdraw),
+            "adjustment" => Ok(BalanceChangeReason::Adjustment),
+            other => Err(AppError::Custom(format!(
+                "Unknown balance change reason: {other}"
+            ))),
+        }
+    }
+}
+
+impl From<BalanceChangeReason> for Value {
+    fn from(value: BalanceChangeReason) -> Self {
+        Value::String(Some(Box::new(value.as_str().to_string())))
+    }
+}
+
+impl Type<Postgres> for BalanceChangeReason {
+    fn type_info() -> PgTypeInfo {
+        <String as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <String as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for BalanceChangeReason {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let raw = <&str as Decode<Postgres>>::decode(value)?;
+        Ok(raw.parse()?)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct FindSaldoHistoryRequest {
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+}
+
+fn default_page() -> i32 {
+    1
+}
+
+fn default_page_size() -> i32 {
+    10
+}
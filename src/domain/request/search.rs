@@ -0,0 +1,157 @@
+use sea_query::{Alias, Expr, IntoColumnRef, LikeExpr, SimpleExpr};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchMode {
+    #[default]
+    Contains,
+    // Anchored at the start, so a btree index on the column can serve it.
+    Prefix,
+    Exact,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Search {
+    pub term: String,
+    pub mode: MatchMode,
+}
+
+impl Search {
+    pub fn new(term: impl Into<String>, mode: MatchMode) -> Self {
+        Self {
+            term: term.into(),
+            mode,
+        }
+    }
+
+    // LIKE wildcards in the term are escaped so `%` and `_` match literally.
+    pub fn pattern(&self) -> String {
+        let escaped = self
+            .term
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+
+        match self.mode {
+            MatchMode::Contains => format!("%{escaped}%"),
+            MatchMode::Prefix => format!("{escaped}%"),
+            MatchMode::Exact => escaped,
+        }
+    }
+
+    fn like_pattern(&self) -> LikeExpr {
+        LikeExpr::new(self.pattern()).escape('\\')
+    }
+
+    // Case-sensitive; exact matches compare with `=` rather than LIKE.
+    pub fn matches(&self, expr: impl Into<SimpleExpr>) -> SimpleExpr {
+        let expr = Expr::expr(expr);
+
+        match self.mode {
+            MatchMode::Exact => expr.eq(self.term.as_str()),
+            _ => expr.like(self.like_pattern()),
+        }
+    }
+
+    // Integer id columns are matched as text, so prefix and contains work on
+    // the id's digits.
+    pub fn matches_id(&self, column: impl IntoColumnRef) -> SimpleExpr {
+        self.matches(Expr::col(column).cast_as(Alias::new("TEXT")))
+    }
+
+    // Written out by hand: sea-query's `ilike` wraps the pattern and its
+    // ESCAPE clause in parentheses, which Postgres rejects.
+    pub fn matches_ignore_case(&self, expr: impl Into<SimpleExpr>) -> SimpleExpr {
+        Expr::cust_with_exprs(
+            r"$1 ILIKE $2 ESCAPE E'\\'",
+            [expr.into(), Expr::val(self.pattern()).into()],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_query::{Alias, PostgresQueryBuilder, Query};
+
+    use super::*;
+
+    fn where_clause(condition: SimpleExpr) -> String {
+        Query::select()
+            .column(Alias::new("name"))
+            .from(Alias::new("users"))
+            .and_where(condition)
+            .to_string(PostgresQueryBuilder)
+    }
+
+    #[test]
+    fn contains_wraps_the_term() {
+        let search = Search::new("ali", MatchMode::Contains);
+
+        assert_eq!(search.pattern(), "%ali%");
+        assert_eq!(
+            where_clause(search.matches(Expr::col(Alias::new("name")))),
+            r#"SELECT "name" FROM "users" WHERE "name" LIKE '%ali%' ESCAPE E'\\'"#
+        );
+    }
+
+    #[test]
+    fn prefix_anchors_at_the_start() {
+        let search = Search::new("TOPUP-", MatchMode::Prefix);
+
+        assert_eq!(search.pattern(), "TOPUP-%");
+        assert_eq!(
+            where_clause(search.matches_ignore_case(Expr::col(Alias::new("name")))),
+            r#"SELECT "name" FROM "users" WHERE "name" ILIKE 'TOPUP-%' ESCAPE E'\\'"#
+        );
+    }
+
+    #[test]
+    fn ignore_case_pattern_is_bound_alongside_other_values() {
+        let search = Search::new("ali", MatchMode::Contains);
+
+        let (sql, values) = Query::select()
+            .column(Alias::new("name"))
+            .from(Alias::new("users"))
+            .and_where(Expr::col(Alias::new("id")).gt(7))
+            .and_where(search.matches_ignore_case(Expr::col(Alias::new("name"))))
+            .build(PostgresQueryBuilder);
+
+        assert_eq!(
+            sql,
+            r#"SELECT "name" FROM "users" WHERE "id" > $1 AND ("name" ILIKE $2 ESCAPE E'\\')"#
+        );
+        assert_eq!(values.0.len(), 2);
+    }
+
+    #[test]
+    fn exact_compares_with_equality() {
+        let search = Search::new("alice", MatchMode::Exact);
+
+        assert_eq!(
+            where_clause(search.matches(Expr::col(Alias::new("name")))),
+            r#"SELECT "name" FROM "users" WHERE "name" = 'alice'"#
+        );
+    }
+
+    #[test]
+    fn ids_are_matched_on_their_digits() {
+        let search = Search::new("12", MatchMode::Prefix);
+
+        assert_eq!(
+            where_clause(search.matches_id(Alias::new("user_id"))),
+            r#"SELECT "name" FROM "users" WHERE CAST("user_id" AS TEXT) LIKE '12%' ESCAPE E'\\'"#
+        );
+    }
+
+    #[test]
+    fn wildcards_in_the_term_match_literally() {
+        assert_eq!(
+            Search::new("50%_off", MatchMode::Contains).pattern(),
+            r"%50\%\_off%"
+        );
+        assert_eq!(Search::new(r"a\b", MatchMode::Prefix).pattern(), r"a\\b%");
+        assert_eq!(Search::new("100%", MatchMode::Exact).pattern(), r"100\%");
+    }
+}
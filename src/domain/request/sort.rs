@@ -0,0 +1,44 @@
+use sea_query::Order;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::utils::AppError;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl From<SortOrder> for Order {
+    fn from(value: SortOrder) -> Self {
+        match value {
+            SortOrder::Asc => Order::Asc,
+            SortOrder::Desc => Order::Desc,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Sort {
+    pub by: Option<String>,
+    pub order: SortOrder,
+}
+
+impl Sort {
+    pub fn new(by: Option<String>, order: SortOrder) -> Self {
+        Self { by, order }
+    }
+}
+
+pub fn invalid_sort_by(sort_by: &str, allowed: &[&str]) -> AppError {
+    AppError::invalid_field(
+        "sort_by",
+        format!(
+            "Invalid sort_by '{sort_by}', expected one of: {}",
+            allowed.join(", ")
+        ),
+    )
+}
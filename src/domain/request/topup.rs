@@ -1,8 +1,25 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
-#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+use crate::{
+    domain::{
+        currency::Currency,
+        money::Money,
+        request::{
+            extra::ExtraValidate,
+            filter::{TransactionFilter, validate_ranges},
+            pagination::PageableRequest,
+            search::MatchMode,
+            sort::SortOrder,
+        },
+    },
+    utils::AppError,
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams, Validate)]
+#[validate(schema(function = "validate_find_all_ranges"))]
 pub struct FindAllTopupRequest {
     #[serde(default = "default_page")]
     pub page: i32,
@@ -12,8 +29,100 @@ pub struct FindAllTopupRequest {
 
     #[serde(default)]
     pub search: String,
+
+    #[serde(default)]
+    #[param(value_type = Option<MatchMode>)]
+    pub match_mode: MatchMode,
+
+    #[serde(default)]
+    pub sort_by: Option<String>,
+
+    #[serde(default)]
+    #[param(value_type = Option<SortOrder>)]
+    pub order: SortOrder,
+
+    #[serde(default)]
+    pub include_deleted: bool,
+
+    #[serde(default)]
+    pub min_amount: Option<Money>,
+
+    #[serde(default)]
+    pub max_amount: Option<Money>,
+
+    #[serde(default)]
+    pub from_date: Option<DateTime<Utc>>,
+
+    #[serde(default)]
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+impl PageableRequest for FindAllTopupRequest {
+    fn page(&self) -> i32 {
+        self.page
+    }
+
+    fn page_size(&self) -> i32 {
+        self.page_size
+    }
+
+    fn search(&self) -> &str {
+        &self.search
+    }
+
+    fn match_mode(&self) -> MatchMode {
+        self.match_mode
+    }
+}
+
+impl FindAllTopupRequest {
+    pub fn filter(&self) -> TransactionFilter {
+        TransactionFilter {
+            min_amount: self.min_amount,
+            max_amount: self.max_amount,
+            from_date: self.from_date,
+            to_date: self.to_date,
+            include_deleted: self.include_deleted,
+            search_notes: false,
+        }
+    }
+}
+
+fn validate_find_all_ranges(data: &FindAllTopupRequest) -> Result<(), ValidationError> {
+    validate_ranges(
+        data.min_amount,
+        data.max_amount,
+        data.from_date,
+        data.to_date,
+    )
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams, Validate)]
+#[validate(schema(function = "validate_monthly_stats_range"))]
+pub struct TopupMonthlyStatsRequest {
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+
+    // Required for non-admins, who may only ask about themselves.
+    #[serde(default)]
+    pub user_id: Option<i32>,
+
+    // Defaults to the base currency; amounts are never summed across currencies.
+    #[serde(default)]
+    pub currency: Option<Currency>,
 }
 
+fn validate_monthly_stats_range(data: &TopupMonthlyStatsRequest) -> Result<(), ValidationError> {
+    validate_ranges(None, None, data.from, data.to)
+}
+
+// The smallest topup any deployment accepts. `MIN_TOPUP_AMOUNT` in the
+// config may raise it, but not lower it.
+pub const MIN_TOPUP_AMOUNT: i64 = 10000;
+
 fn default_page() -> i32 {
     1
 }
@@ -22,6 +131,7 @@ fn default_page_size() -> i32 {
     10
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
 pub struct CreateTopupRequest {
     #[validate(range(min = 1))]
     pub user_id: i32,
@@ -29,14 +139,20 @@ pub struct CreateTopupRequest {
     #[validate(length(min = 1, message = "Top-up number is required"))]
     pub topup_no: String,
 
-    #[validate(range(min = 1, message = "Top-up amount must be at least 1"))]
-    pub topup_amount: i32,
+    #[validate(range(min = MIN_TOPUP_AMOUNT, message = "Top-up amount must be at least 10,000"))]
+    pub topup_amount: Money,
 
     #[validate(length(min = 1, message = "Top-up method is required"))]
     pub topup_method: String,
+
+    #[serde(default)]
+    pub currency: Currency,
 }
 
+impl ExtraValidate for CreateTopupRequest {}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
 pub struct UpdateTopupRequest {
     #[validate(range(min = 1))]
     pub user_id: i32,
@@ -44,18 +160,133 @@ pub struct UpdateTopupRequest {
     #[validate(range(min = 1))]
     pub topup_id: i32,
 
-    #[validate(range(min = 1, message = "Top-up amount must be at least 1"))]
-    pub topup_amount: i32,
+    #[validate(range(min = MIN_TOPUP_AMOUNT, message = "Top-up amount must be at least 10,000"))]
+    pub topup_amount: Money,
 
     #[validate(length(min = 1, message = "Top-up method is required"))]
     pub topup_method: String,
 }
 
+impl ExtraValidate for UpdateTopupRequest {}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
 pub struct UpdateTopupAmount {
     #[validate(range(min = 1, message = "Top-up ID must be a positive integer"))]
     pub topup_id: i32,
 
     #[validate(range(min = 1, message = "Top-up amount must be at least 1"))]
-    pub topup_amount: i32,
+    pub topup_amount: Money,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+pub struct BulkTopupRow {
+    #[validate(range(min = 1, message = "User ID must be a positive integer"))]
+    pub user_id: i32,
+
+    #[validate(range(min = MIN_TOPUP_AMOUNT, message = "Top-up amount must be at least 10,000"))]
+    pub amount: Money,
+
+    #[serde(default)]
+    pub currency: Currency,
+}
+
+// Sent either as a bare JSON array of rows or as CSV with a
+// `user_id,amount[,currency]` header.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(transparent)]
+pub struct CreateBulkTopupRequest {
+    #[validate(length(min = 1, message = "At least one row is required"))]
+    #[validate(nested)]
+    pub rows: Vec<BulkTopupRow>,
+}
+
+impl CreateBulkTopupRequest {
+    pub fn from_csv(text: &str) -> Result<Self, AppError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        let header: Vec<&str> = match lines.next() {
+            Some((_, header)) => header.split(',').map(str::trim).collect(),
+            None => return Ok(Self { rows: Vec::new() }),
+        };
+
+        if !matches!(
+            header.as_slice(),
+            ["user_id", "amount"] | ["user_id", "amount", "currency"]
+        ) {
+            return Err(AppError::validation(
+                "CSV header must be user_id,amount or user_id,amount,currency",
+            ));
+        }
+
+        let rows = lines
+            .map(|(number, line)| {
+                let invalid = |reason: String| {
+                    AppError::validation(format!("Invalid CSV row on line {number}: {reason}"))
+                };
+
+                let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+
+                if fields.len() != header.len() {
+                    return Err(invalid(format!(
+                        "expected {} fields, got {}",
+                        header.len(),
+                        fields.len()
+                    )));
+                }
+
+                let user_id = fields[0]
+                    .parse::<i32>()
+                    .map_err(|_| invalid(format!("invalid user_id {:?}", fields[0])))?;
+                let amount = fields[1]
+                    .parse::<Money>()
+                    .map_err(|e| invalid(e.to_string()))?;
+                let currency = match fields.get(2) {
+                    Some(currency) if !currency.is_empty() => currency
+                        .parse::<Currency>()
+                        .map_err(|e| invalid(e.to_string()))?,
+                    _ => Currency::default(),
+                };
+
+                Ok(BulkTopupRow {
+                    user_id,
+                    amount,
+                    currency,
+                })
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+
+        Ok(Self { rows })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(amount: i64) -> CreateTopupRequest {
+        CreateTopupRequest {
+            user_id: 1,
+            topup_no: "TOPUP-1".to_string(),
+            topup_amount: Money::new(amount),
+            topup_method: "bank_transfer".to_string(),
+            currency: Currency::default(),
+        }
+    }
+
+    #[test]
+    fn topup_at_the_floor_is_valid() {
+        assert!(request(MIN_TOPUP_AMOUNT).validate().is_ok());
+    }
+
+    #[test]
+    fn topup_below_the_floor_is_invalid() {
+        let errors = request(MIN_TOPUP_AMOUNT - 1).validate().unwrap_err();
+
+        assert!(errors.field_errors().contains_key("topup_amount"));
+        assert!(request(0).validate().is_err());
+    }
 }
@@ -1,8 +1,24 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use utoipa::{IntoParams, ToSchema};
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
-#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+use crate::domain::{
+    currency::Currency,
+    money::Money,
+    request::{
+        extra::ExtraValidate,
+        filter::{TransactionFilter, validate_ranges},
+        note::{MAX_NOTE_LENGTH, deserialize_note},
+        pagination::PageableRequest,
+        search::MatchMode,
+        sort::SortOrder,
+    },
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams, Validate)]
+#[validate(schema(function = "validate_find_all_ranges"))]
 pub struct FindAllTransferRequest {
     #[serde(default = "default_page")]
     pub page: i32,
@@ -12,6 +28,134 @@ pub struct FindAllTransferRequest {
 
     #[serde(default)]
     pub search: String,
+
+    #[serde(default)]
+    #[param(value_type = Option<MatchMode>)]
+    pub match_mode: MatchMode,
+
+    #[serde(default)]
+    pub sort_by: Option<String>,
+
+    #[serde(default)]
+    #[param(value_type = Option<SortOrder>)]
+    pub order: SortOrder,
+
+    #[serde(default)]
+    pub include_deleted: bool,
+
+    // Match `search` against notes too, case-insensitively.
+    #[serde(default)]
+    pub search_notes: bool,
+
+    #[serde(default)]
+    pub min_amount: Option<Money>,
+
+    #[serde(default)]
+    pub max_amount: Option<Money>,
+
+    #[serde(default)]
+    pub from_date: Option<DateTime<Utc>>,
+
+    #[serde(default)]
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+impl PageableRequest for FindAllTransferRequest {
+    fn page(&self) -> i32 {
+        self.page
+    }
+
+    fn page_size(&self) -> i32 {
+        self.page_size
+    }
+
+    fn search(&self) -> &str {
+        &self.search
+    }
+
+    fn match_mode(&self) -> MatchMode {
+        self.match_mode
+    }
+}
+
+impl FindAllTransferRequest {
+    pub fn filter(&self) -> TransactionFilter {
+        TransactionFilter {
+            min_amount: self.min_amount,
+            max_amount: self.max_amount,
+            from_date: self.from_date,
+            to_date: self.to_date,
+            include_deleted: self.include_deleted,
+            search_notes: self.search_notes,
+        }
+    }
+}
+
+fn validate_find_all_ranges(data: &FindAllTransferRequest) -> Result<(), ValidationError> {
+    validate_ranges(
+        data.min_amount,
+        data.max_amount,
+        data.from_date,
+        data.to_date,
+    )
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct FindTransfersBetweenRequest {
+    pub from: i32,
+
+    pub to: i32,
+
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams, Validate)]
+#[validate(schema(function = "validate_net_flow_range"))]
+pub struct TransferNetFlowRequest {
+    // Only count transfers exchanged with this user.
+    #[serde(default)]
+    pub counterparty: Option<i32>,
+
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+
+    // Defaults to the base currency; amounts are never summed across currencies.
+    #[serde(default)]
+    pub currency: Option<Currency>,
+}
+
+fn validate_net_flow_range(data: &TransferNetFlowRequest) -> Result<(), ValidationError> {
+    validate_ranges(None, None, data.from, data.to)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferDirection {
+    Incoming,
+    Outgoing,
+    #[default]
+    All,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct FindUserTransfersRequest {
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+
+    // A user is the sender of outgoing and the receiver of incoming transfers.
+    #[serde(default)]
+    #[param(value_type = Option<TransferDirection>)]
+    pub direction: TransferDirection,
 }
 
 fn default_page() -> i32 {
@@ -23,18 +167,59 @@ fn default_page_size() -> i32 {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+#[validate(schema(function = "validate_receiver"))]
 pub struct CreateTransferRequest {
     #[validate(range(min = 1, message = "Transfer from must be a positive integer"))]
     pub transfer_from: i32,
 
-    #[validate(range(min = 1, message = "Transfer to must be a positive integer"))]
+    // The receiver is named by exactly one of `transfer_to` (user id) and
+    // `transfer_to_noc` (their card/account number).
+    #[serde(default)]
     pub transfer_to: i32,
 
+    #[serde(default)]
+    pub transfer_to_noc: Option<String>,
+
     #[validate(range(min = 50000, message = "Transfer amount must be at least 50,000"))]
-    pub transfer_amount: i32,
+    pub transfer_amount: Money,
+
+    #[serde(default)]
+    pub currency: Currency,
+
+    // When in the future the transfer is stored as pending and executed at
+    // that time instead of immediately.
+    #[serde(default)]
+    pub scheduled_at: Option<DateTime<Utc>>,
+
+    #[serde(default, deserialize_with = "deserialize_note")]
+    #[validate(length(max = MAX_NOTE_LENGTH, message = "Note must be at most 255 characters"))]
+    pub note: Option<String>,
+}
+
+impl ExtraValidate for CreateTransferRequest {
+    fn extra_validate(&self) -> Result<(), String> {
+        if self.transfer_from == self.transfer_to {
+            return Err("Cannot transfer to self".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_receiver(data: &CreateTransferRequest) -> Result<(), ValidationError> {
+    match data.transfer_to_noc {
+        Some(_) if data.transfer_to != 0 => Err(ValidationError::new("ambiguous_receiver")
+            .with_message("Give either transfer_to or transfer_to_noc, not both".into())),
+        Some(_) => Ok(()),
+        None if data.transfer_to < 1 => Err(ValidationError::new("range")
+            .with_message("Transfer to must be a positive integer".into())),
+        None => Ok(()),
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
 pub struct UpdateTransferRequest {
     #[validate(range(min = 1, message = "Transfer ID must be a positive integer"))]
     pub transfer_id: i32,
@@ -46,14 +231,111 @@ pub struct UpdateTransferRequest {
     pub transfer_to: i32,
 
     #[validate(range(min = 50000, message = "Transfer amount must be at least 50,000"))]
-    pub transfer_amount: i32,
+    pub transfer_amount: Money,
 }
 
+impl ExtraValidate for UpdateTransferRequest {}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
 pub struct UpdateTransferAmountRequest {
     #[validate(range(min = 1, message = "Transfer ID must be a positive integer"))]
     pub transfer_id: i32,
 
     #[validate(range(min = 50000, message = "Transfer amount must be at least 50,000"))]
-    pub transfer_amount: i32,
+    pub transfer_amount: Money,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct BatchRecipient {
+    #[validate(range(min = 1, message = "Transfer to must be a positive integer"))]
+    pub transfer_to: i32,
+
+    #[validate(range(min = 50000, message = "Transfer amount must be at least 50,000"))]
+    pub transfer_amount: Money,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+#[validate(schema(function = "validate_batch_recipients"))]
+pub struct CreateBatchTransferRequest {
+    #[validate(range(min = 1, message = "Transfer from must be a positive integer"))]
+    pub transfer_from: i32,
+
+    #[validate(length(min = 1, message = "At least one recipient is required"))]
+    #[validate(nested)]
+    pub recipients: Vec<BatchRecipient>,
+
+    #[serde(default)]
+    pub currency: Currency,
+}
+
+impl ExtraValidate for CreateBatchTransferRequest {}
+
+fn validate_batch_recipients(data: &CreateBatchTransferRequest) -> Result<(), ValidationError> {
+    let mut seen = HashSet::new();
+
+    for recipient in &data.recipients {
+        if recipient.transfer_to == data.transfer_from {
+            return Err(ValidationError::new("batch_self_transfer")
+                .with_message("Sender cannot be one of the recipients".into()));
+        }
+
+        if !seen.insert(recipient.transfer_to) {
+            let mut error = ValidationError::new("batch_duplicate_recipient")
+                .with_message("Each recipient may only appear once in a batch".into());
+            error.add_param("transfer_to".into(), &recipient.transfer_to);
+            return Err(error);
+        }
+    }
+
+    Ok(())
+}
+
+// Sweeps the caller's whole balance in `currency`, less the minimum balance
+// and the fee, to `transfer_to`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct TransferAllRequest {
+    #[validate(range(min = 1, message = "Transfer to must be a positive integer"))]
+    pub transfer_to: i32,
+
+    #[serde(default)]
+    pub currency: Currency,
+}
+
+impl ExtraValidate for TransferAllRequest {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(amount: i64) -> CreateTransferRequest {
+        CreateTransferRequest {
+            transfer_from: 1,
+            transfer_to: 2,
+            transfer_to_noc: None,
+            transfer_amount: Money::new(amount),
+            currency: Currency::default(),
+            scheduled_at: None,
+            note: None,
+        }
+    }
+
+    #[test]
+    fn transfer_at_the_minimum_is_valid() {
+        assert!(request(50_000).validate().is_ok());
+    }
+
+    #[test]
+    fn zero_and_negative_amounts_are_invalid() {
+        for amount in [0, -1, -50_000] {
+            let errors = request(amount).validate().unwrap_err();
+
+            assert!(
+                errors.field_errors().contains_key("transfer_amount"),
+                "{amount}: {errors:?}"
+            );
+        }
+    }
 }
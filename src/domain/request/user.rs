@@ -1,6 +1,17 @@
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
-use validator::Validate;
+use validator::{Validate, ValidationError};
+
+use crate::domain::{
+    currency::Currency,
+    request::{
+        extra::ExtraValidate,
+        pagination::PageableRequest,
+        search::{MatchMode, Search},
+        sort::SortOrder,
+    },
+    role::Role,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
 pub struct FindAllUserRequest {
@@ -12,6 +23,72 @@ pub struct FindAllUserRequest {
 
     #[serde(default)]
     pub search: String,
+
+    #[serde(default)]
+    #[param(value_type = Option<MatchMode>)]
+    pub match_mode: MatchMode,
+
+    #[serde(default)]
+    pub sort_by: Option<String>,
+
+    #[serde(default)]
+    #[param(value_type = Option<SortOrder>)]
+    pub order: SortOrder,
+
+    #[serde(default)]
+    pub include_deleted: bool,
+
+    // Typo-tolerant trigram matching, ranked by similarity.
+    #[serde(default)]
+    pub fuzzy: bool,
+
+    /// Return `noc_transfer` unmasked.
+    #[serde(default)]
+    pub full: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, IntoParams)]
+pub struct GetUserQuery {
+    /// Return `noc_transfer` unmasked; admin only.
+    #[serde(default)]
+    pub full: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct FindUserCounterpartiesRequest {
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+
+    // Defaults to the base currency; amounts are never summed across currencies.
+    #[serde(default)]
+    pub currency: Option<Currency>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UserSearch {
+    Match(Search),
+    Fuzzy(String),
+}
+
+impl PageableRequest for FindAllUserRequest {
+    fn page(&self) -> i32 {
+        self.page
+    }
+
+    fn page_size(&self) -> i32 {
+        self.page_size
+    }
+
+    fn search(&self) -> &str {
+        &self.search
+    }
+
+    fn match_mode(&self) -> MatchMode {
+        self.match_mode
+    }
 }
 
 fn default_page() -> i32 {
@@ -22,7 +99,24 @@ fn default_page_size() -> i32 {
     10
 }
 
+// Enough for a page of transfers, each naming two users.
+pub const MAX_USER_BATCH_SIZE: u64 = 100;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct FindUsersBatchRequest {
+    #[validate(length(
+        min = 1,
+        max = MAX_USER_BATCH_SIZE,
+        message = "Between 1 and 100 ids may be requested at once"
+    ))]
+    pub ids: Vec<i32>,
+}
+
+impl ExtraValidate for FindUsersBatchRequest {}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
 pub struct CreateUserRequest {
     #[validate(length(min = 1, message = "First name is required"))]
     pub firstname: String,
@@ -44,6 +138,8 @@ pub struct CreateUserRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+#[validate(schema(function = "validate_password_confirmation"))]
 pub struct UpdateUserRequest {
     #[validate(range(min = 1))]
     pub id: i32,
@@ -55,12 +151,32 @@ pub struct UpdateUserRequest {
     pub lastname: Option<String>,
 
     #[validate(email(message = "Invalid email format"))]
+    #[validate(length(max = 100, message = "Email must be at most 100 characters"))]
     pub email: Option<String>,
 
-    #[validate(length(min = 6, message = "Password must be at least 6 characters"))]
-    pub password: String,
+    // Only changed when present; hashed by the service before it is stored.
+    #[validate(length(min = 8, max = 72, message = "Password must be 8-72 characters"))]
+    pub password: Option<String>,
 
-    #[validate(length(min = 6, message = "Confirm password must be at least 6 characters"))]
-    #[validate(must_match(other = "password"))]
-    pub confirm_password: String,
+    pub confirm_password: Option<String>,
+}
+
+impl ExtraValidate for UpdateUserRequest {}
+
+fn validate_password_confirmation(req: &UpdateUserRequest) -> Result<(), ValidationError> {
+    if req.password != req.confirm_password {
+        return Err(
+            ValidationError::new("must_match").with_message("Passwords do not match".into())
+        );
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateUserRoleRequest {
+    pub role: Role,
 }
+
+impl ExtraValidate for UpdateUserRoleRequest {}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+use crate::domain::request::extra::ExtraValidate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct CreateWebhookRequest {
+    #[validate(url(message = "Invalid URL"))]
+    #[validate(length(max = 2048, message = "URL must be at most 2048 characters"))]
+    #[validate(custom(function = "validate_http_scheme"))]
+    pub url: String,
+}
+
+impl ExtraValidate for CreateWebhookRequest {}
+
+fn validate_http_scheme(url: &str) -> Result<(), ValidationError> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        return Ok(());
+    }
+
+    Err(ValidationError::new("webhook_scheme")
+        .with_message("Webhook URL must use http or https".into()))
+}
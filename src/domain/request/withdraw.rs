@@ -3,7 +3,21 @@ use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 use validator::{Validate, ValidationError};
 
-#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+use crate::domain::{
+    currency::Currency,
+    money::Money,
+    request::{
+        extra::ExtraValidate,
+        filter::{TransactionFilter, validate_ranges},
+        note::{MAX_NOTE_LENGTH, deserialize_note},
+        pagination::PageableRequest,
+        search::MatchMode,
+        sort::SortOrder,
+    },
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams, Validate)]
+#[validate(schema(function = "validate_find_all_ranges"))]
 pub struct FindAllWithdrawRequest {
     #[serde(default = "default_page")]
     pub page: i32,
@@ -13,6 +27,76 @@ pub struct FindAllWithdrawRequest {
 
     #[serde(default)]
     pub search: String,
+
+    #[serde(default)]
+    #[param(value_type = Option<MatchMode>)]
+    pub match_mode: MatchMode,
+
+    #[serde(default)]
+    pub sort_by: Option<String>,
+
+    #[serde(default)]
+    #[param(value_type = Option<SortOrder>)]
+    pub order: SortOrder,
+
+    #[serde(default)]
+    pub include_deleted: bool,
+
+    // Match `search` against notes too, case-insensitively.
+    #[serde(default)]
+    pub search_notes: bool,
+
+    #[serde(default)]
+    pub min_amount: Option<Money>,
+
+    #[serde(default)]
+    pub max_amount: Option<Money>,
+
+    #[serde(default)]
+    pub from_date: Option<DateTime<Utc>>,
+
+    #[serde(default)]
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+impl PageableRequest for FindAllWithdrawRequest {
+    fn page(&self) -> i32 {
+        self.page
+    }
+
+    fn page_size(&self) -> i32 {
+        self.page_size
+    }
+
+    fn search(&self) -> &str {
+        &self.search
+    }
+
+    fn match_mode(&self) -> MatchMode {
+        self.match_mode
+    }
+}
+
+impl FindAllWithdrawRequest {
+    pub fn filter(&self) -> TransactionFilter {
+        TransactionFilter {
+            min_amount: self.min_amount,
+            max_amount: self.max_amount,
+            from_date: self.from_date,
+            to_date: self.to_date,
+            include_deleted: self.include_deleted,
+            search_notes: self.search_notes,
+        }
+    }
+}
+
+fn validate_find_all_ranges(data: &FindAllWithdrawRequest) -> Result<(), ValidationError> {
+    validate_ranges(
+        data.min_amount,
+        data.max_amount,
+        data.from_date,
+        data.to_date,
+    )
 }
 
 fn default_page() -> i32 {
@@ -24,18 +108,29 @@ fn default_page_size() -> i32 {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
 #[validate(schema(function = "validate_create_not_future"))]
 pub struct CreateWithdrawRequest {
     #[validate(range(min = 1, message = "User ID must be positive"))]
     pub user_id: i32,
 
     #[validate(range(min = 50001, message = "Withdraw amount must be at least 50,001"))]
-    pub withdraw_amount: i32,
+    pub withdraw_amount: Money,
 
     pub withdraw_time: DateTime<Utc>,
+
+    #[serde(default)]
+    pub currency: Currency,
+
+    #[serde(default, deserialize_with = "deserialize_note")]
+    #[validate(length(max = MAX_NOTE_LENGTH, message = "Note must be at most 255 characters"))]
+    pub note: Option<String>,
 }
 
+impl ExtraValidate for CreateWithdrawRequest {}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+#[serde(deny_unknown_fields)]
 #[validate(schema(function = "validate_update_not_future"))]
 pub struct UpdateWithdrawRequest {
     #[validate(range(min = 1, message = "User ID must be positive"))]
@@ -45,11 +140,13 @@ pub struct UpdateWithdrawRequest {
     pub withdraw_id: i32,
 
     #[validate(range(min = 50001, message = "Withdraw amount must be at least 50,001"))]
-    pub withdraw_amount: i32,
+    pub withdraw_amount: Money,
 
     pub withdraw_time: DateTime<Utc>,
 }
 
+impl ExtraValidate for UpdateWithdrawRequest {}
+
 fn validate_create_not_future(data: &CreateWithdrawRequest) -> Result<(), ValidationError> {
     if data.withdraw_time > Utc::now() {
         let mut error = ValidationError::new("withdraw_time_in_future");
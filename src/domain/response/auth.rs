@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    domain::response::{saldo::SaldoResponse, user::UserResponse},
+    model::login_audit::LoginAudit,
+};
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct TokenPair {
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct MeResponse {
+    pub user: UserResponse,
+    // The base-currency saldo, if the user has one.
+    pub saldo: Option<SaldoResponse>,
+}
+
+// What the caller may currently do, so clients can gate their UI on the
+// same rules the server enforces.
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CapabilitiesResponse {
+    pub can_transfer: bool,
+    pub can_withdraw: bool,
+    pub is_admin: bool,
+    // There is no second factor yet, so this is always false.
+    pub requires_2fa: bool,
+    pub is_frozen: bool,
+    pub is_verified: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct LoginAuditResponse {
+    pub id: i32,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub success: bool,
+    #[schema(format = "date-time")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<LoginAudit> for LoginAuditResponse {
+    fn from(value: LoginAudit) -> Self {
+        LoginAuditResponse {
+            id: value.login_audit_id,
+            ip: value.ip,
+            user_agent: value.user_agent,
+            success: value.success,
+            created_at: DateTime::from_naive_utc_and_offset(value.created_at, Utc),
+        }
+    }
+}
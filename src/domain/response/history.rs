@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    domain::{money::Money, request::history::HistoryKind},
+    model::history::HistoryEntry,
+};
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct HistoryEntryResponse {
+    pub id: i32,
+    pub kind: HistoryKind,
+    pub amount: Money,
+    pub timestamp: DateTime<Utc>,
+    pub counterparty: Option<i32>,
+}
+
+impl From<HistoryEntry> for HistoryEntryResponse {
+    fn from(value: HistoryEntry) -> Self {
+        HistoryEntryResponse {
+            id: value.source_id,
+            kind: value.kind,
+            amount: value.amount,
+            timestamp: DateTime::from_naive_utc_and_offset(value.occurred_at, Utc),
+            counterparty: value.counterparty,
+        }
+    }
+}
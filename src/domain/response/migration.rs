@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::model::migration::AppliedMigration;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct AppliedMigrationResponse {
+    pub version: i64,
+    pub description: String,
+    #[schema(format = "date-time")]
+    pub installed_on: DateTime<Utc>,
+    pub success: bool,
+}
+
+impl From<AppliedMigration> for AppliedMigrationResponse {
+    fn from(value: AppliedMigration) -> Self {
+        AppliedMigrationResponse {
+            version: value.version,
+            description: value.description,
+            installed_on: value.installed_on,
+            success: value.success,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct MigrationStatusResponse {
+    pub applied: Vec<AppliedMigrationResponse>,
+    pub applied_count: usize,
+    // Newest migration version compiled into this binary.
+    pub latest_known: Option<i64>,
+    // Known to the binary but not yet applied.
+    pub pending: Vec<i64>,
+    // Applied to the database but unknown to the binary, i.e. the schema is
+    // newer than the code.
+    pub unknown: Vec<i64>,
+    pub up_to_date: bool,
+}
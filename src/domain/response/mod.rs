@@ -1,16 +1,32 @@
-use crate::{domain::response::pagination::Pagination, utils::AppError};
+use crate::{
+    domain::{money::Money, response::pagination::Pagination},
+    utils::{AppError, FieldErrors, MessageId, format_validation_errors, validation_error_map},
+};
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
 use core::fmt;
 use serde::{Deserialize, Serialize};
 use std::fmt::Formatter;
 use utoipa::ToSchema;
 
+pub mod auth;
+pub mod history;
+pub mod migration;
 pub mod pagination;
+pub mod reconciliation;
 pub mod saldo;
+pub mod saldo_history;
 pub mod topup;
 pub mod transfer;
 pub mod user;
+pub mod webhook;
 pub mod withdraw;
 
+// `middleware::request_id` adds `timestamp` and `request_id` to the
+// serialized envelope, here and in `ApiResponsePagination`.
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ApiResponse<T> {
     pub status: String,
@@ -49,10 +65,117 @@ impl<T: Serialize> fmt::Display for ApiResponsePagination<T> {
 pub struct ErrorResponse {
     pub status: String,
     pub message: String,
+    // Lets the language middleware swap `message` for a translation. The
+    // English `message` may carry more detail than the translated one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<MessageId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<FieldErrors>,
+    // Stable identifier for errors clients are expected to branch on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Box<ErrorDetails>>,
+    #[serde(skip)]
+    pub code: StatusCode,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum ErrorDetails {
+    InsufficientBalance(InsufficientBalanceDetails),
+    UnknownField(UnknownFieldDetails),
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UnknownFieldDetails {
+    pub field: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InsufficientBalanceDetails {
+    pub required: Money,
+    pub available: Money,
+    // How much more the user needs, so clients don't have to do the math.
+    pub shortfall: Money,
 }
 
 impl From<AppError> for ErrorResponse {
     fn from(error: AppError) -> Self {
+        let code = match error {
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::EmailAlreadyExists | AppError::NocTransferTaken | AppError::Conflict(_) => {
+                StatusCode::CONFLICT
+            }
+            AppError::InsufficientBalance { .. } => StatusCode::BAD_REQUEST,
+            AppError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::InvalidCredentials
+            | AppError::Unauthorized(_)
+            | AppError::TokenExpiredError
+            | AppError::TokenValidationError
+            | AppError::InvalidApiKey
+            | AppError::InvalidSignature
+            | AppError::StaleRequest(_) => StatusCode::UNAUTHORIZED,
+            AppError::Custom(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        let errors = match error {
+            AppError::Validation(ref errs) => Some(validation_error_map(errs)),
+            _ => None,
+        };
+
+        let (error_code, details) = match error {
+            AppError::InsufficientBalance {
+                required,
+                available,
+            } => (
+                Some("insufficient_balance".to_string()),
+                Some(Box::new(ErrorDetails::InsufficientBalance(
+                    InsufficientBalanceDetails {
+                        required,
+                        available,
+                        shortfall: Money::new(
+                            required
+                                .minor_units()
+                                .saturating_sub(available.minor_units())
+                                .max(0),
+                        ),
+                    },
+                ))),
+            ),
+            _ => (None, None),
+        };
+
+        let message_id = match error {
+            AppError::NotFound(_) => Some(MessageId::NotFound),
+            AppError::Forbidden(_) => Some(MessageId::Forbidden),
+            AppError::Unauthorized(_) => Some(MessageId::Unauthorized),
+            AppError::InvalidCredentials => Some(MessageId::InvalidCredentials),
+            AppError::TokenExpiredError => Some(MessageId::TokenExpired),
+            AppError::TokenValidationError => Some(MessageId::TokenInvalid),
+            AppError::InvalidApiKey => Some(MessageId::InvalidApiKey),
+            AppError::InvalidSignature => Some(MessageId::InvalidSignature),
+            AppError::EmailAlreadyExists => Some(MessageId::EmailAlreadyExists),
+            AppError::NocTransferTaken => Some(MessageId::NocTransferTaken),
+            AppError::Validation(_) => Some(MessageId::ValidationFailed),
+            AppError::InsufficientBalance { .. } => Some(MessageId::InsufficientBalance),
+            AppError::TooManyRequests(_) => Some(MessageId::TooManyRequests),
+            AppError::SqlxError(_)
+            | AppError::HashingError(_)
+            | AppError::TokenGenerationError(_)
+            | AppError::BcryptError(_)
+            | AppError::InternalError(_) => Some(MessageId::InternalError),
+            // Free-form messages without a fixed meaning stay in English.
+            AppError::StaleRequest(_)
+            | AppError::ServiceUnavailable(_)
+            | AppError::Conflict(_)
+            | AppError::Custom(_) => None,
+        };
+
         let (status, message) = match error {
             AppError::SqlxError(e) => {
                 ("error".to_string(), format!("Database error occurred: {e}"))
@@ -66,6 +189,11 @@ impl From<AppError> for ErrorResponse {
             AppError::TokenValidationError => {
                 ("error".to_string(), "Token validation failed".to_string())
             }
+            AppError::InvalidApiKey => ("error".to_string(), "Invalid API key".to_string()),
+            AppError::InvalidSignature => {
+                ("error".to_string(), "Invalid request signature".to_string())
+            }
+            AppError::StaleRequest(ref msg) => ("error".to_string(), msg.clone()),
             AppError::TokenGenerationError(_) => {
                 ("error".to_string(), "Token generation failed".to_string())
             }
@@ -73,15 +201,39 @@ impl From<AppError> for ErrorResponse {
             AppError::InvalidCredentials => {
                 ("error".to_string(), "Invalid credentials".to_string())
             }
+            AppError::Unauthorized(ref msg) => ("error".to_string(), msg.clone()),
+            AppError::Forbidden(ref msg) => ("error".to_string(), msg.clone()),
             AppError::EmailAlreadyExists => {
                 ("error".to_string(), "Email already exists".to_string())
             }
-            AppError::ValidationError(_) => ("error".to_string(), "Validation error".to_string()),
+            AppError::Conflict(ref msg) => ("error".to_string(), msg.clone()),
+            AppError::NocTransferTaken => (
+                "error".to_string(),
+                "noc_transfer is already in use".to_string(),
+            ),
+            AppError::Validation(ref errs) => ("error".to_string(), format_validation_errors(errs)),
+            AppError::InsufficientBalance { .. } => ("error".to_string(), error.to_string()),
+            AppError::TooManyRequests(ref msg) => ("error".to_string(), msg.clone()),
+            AppError::ServiceUnavailable(ref msg) => ("error".to_string(), msg.clone()),
             AppError::InternalError(ref msg) => ("error".to_string(), msg.clone()),
 
             AppError::Custom(ref msg) => ("error".to_string(), msg.clone()),
         };
-        ErrorResponse { status, message }
+        ErrorResponse {
+            status,
+            message,
+            message_id,
+            errors,
+            error_code,
+            details,
+            code,
+        }
+    }
+}
+
+impl IntoResponse for ErrorResponse {
+    fn into_response(self) -> Response {
+        (self.code, Json(self)).into_response()
     }
 }
 
@@ -90,3 +242,68 @@ impl fmt::Display for ErrorResponse {
         write!(f, "Status: {}, Message: {}", self.status, self.message)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn insufficient_balance_serializes_structured_payload() {
+        let response = ErrorResponse::from(AppError::InsufficientBalance {
+            required: Money::new(75_000),
+            available: Money::new(20_000),
+        });
+
+        assert_eq!(response.code, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            json!({
+                "status": "error",
+                "message": "Insufficient balance: required 75000, available 20000",
+                "message_id": "insufficient_balance",
+                "error_code": "insufficient_balance",
+                "details": {
+                    "required": 75_000,
+                    "available": 20_000,
+                    "shortfall": 55_000,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn errors_map_to_their_status_codes() {
+        for (error, status) in [
+            (AppError::NotFound("topup 9".into()), StatusCode::NOT_FOUND),
+            (AppError::InvalidCredentials, StatusCode::UNAUTHORIZED),
+            (
+                AppError::Unauthorized("revoked".into()),
+                StatusCode::UNAUTHORIZED,
+            ),
+            (AppError::EmailAlreadyExists, StatusCode::CONFLICT),
+            (AppError::Conflict("taken".into()), StatusCode::CONFLICT),
+            (
+                AppError::Validation(validator::ValidationErrors::new()),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (AppError::Custom("bad".into()), StatusCode::BAD_REQUEST),
+            (
+                AppError::InternalError("boom".into()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+        ] {
+            let label = format!("{error:?}");
+            assert_eq!(ErrorResponse::from(error).code, status, "{label}");
+        }
+    }
+
+    #[test]
+    fn response_carries_the_mapped_status() {
+        let response =
+            ErrorResponse::from(AppError::NotFound("Topup 9 not found".into())).into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -7,4 +7,63 @@ pub struct Pagination {
     pub page_size: i32,
     pub total_items: i64,
     pub total_pages: i32,
+    pub has_next: bool,
+    pub has_prev: bool,
+}
+
+impl Pagination {
+    /// An empty list still has one (empty) page, so `total_pages` is never
+    /// below 1. Counted in integers rather than through an `f64` ceil, which
+    /// stops being exact for very large counts.
+    pub fn new(page: i32, page_size: i32, total_items: i64) -> Self {
+        let page_size_items = i64::from(page_size.max(1));
+        let total_pages = (total_items.max(0) + page_size_items - 1) / page_size_items;
+        let total_pages = i32::try_from(total_pages.max(1)).unwrap_or(i32::MAX);
+
+        Self {
+            page,
+            page_size,
+            total_items,
+            total_pages,
+            has_next: page < total_pages,
+            has_prev: page > 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_list_has_one_page() {
+        let pagination = Pagination::new(1, 10, 0);
+
+        assert_eq!(pagination.total_pages, 1);
+        assert!(!pagination.has_next);
+        assert!(!pagination.has_prev);
+    }
+
+    #[test]
+    fn exact_multiple_does_not_add_a_page() {
+        assert_eq!(Pagination::new(1, 10, 20).total_pages, 2);
+        assert_eq!(
+            Pagination::new(1, 7, 7_000_000_000).total_pages,
+            1_000_000_000
+        );
+    }
+
+    #[test]
+    fn one_item_over_a_multiple_adds_a_page() {
+        assert_eq!(Pagination::new(1, 10, 21).total_pages, 3);
+        assert_eq!(Pagination::new(1, 10, 19).total_pages, 2);
+        assert_eq!(Pagination::new(1, 10, 1).total_pages, 1);
+    }
+
+    #[test]
+    fn has_next_stops_on_the_last_page() {
+        assert!(Pagination::new(1, 10, 20).has_next);
+        assert!(!Pagination::new(2, 10, 20).has_next);
+        assert!(Pagination::new(2, 10, 21).has_next);
+    }
 }
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    domain::{currency::Currency, money::Money},
+    model::reconciliation::Reconciliation,
+    utils::AppError,
+};
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ReconcileResponse {
+    pub saldo_id: i32,
+    pub user_id: i32,
+    pub currency: Currency,
+    pub stored: Money,
+    pub computed: Money,
+    pub matches: bool,
+    // Stored minus computed; positive means the saldo holds more than its
+    // ledger accounts for.
+    pub difference: Money,
+}
+
+impl TryFrom<Reconciliation> for ReconcileResponse {
+    type Error = AppError;
+
+    fn try_from(value: Reconciliation) -> Result<Self, Self::Error> {
+        Ok(ReconcileResponse {
+            saldo_id: value.saldo_id,
+            user_id: value.user_id,
+            currency: value.currency,
+            stored: value.stored,
+            computed: value.computed,
+            matches: value.stored == value.computed,
+            difference: value.stored.checked_sub(value.computed)?,
+        })
+    }
+}
@@ -2,20 +2,34 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::model::saldo::Saldo;
+use crate::{
+    domain::{currency::Currency, money::Money},
+    model::saldo::{Saldo, SaldoStats},
+};
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct SaldoResponse {
     pub id: i32,
     pub user_id: i32,
-    pub total_balance: i32,
-    pub withdraw_amount: Option<i32>,
+    pub total_balance: Money,
+    pub currency: Currency,
+    pub withdraw_amount: Option<Money>,
+    #[schema(format = "date-time")]
     pub withdraw_time: Option<DateTime<Utc>>,
+
     #[schema(format = "date-time")]
     pub created_at: Option<DateTime<Utc>>,
 
     #[schema(format = "date-time")]
     pub updated_at: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = "date-time")]
+    pub deleted_at: Option<DateTime<Utc>>,
+
+    pub is_frozen: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frozen_reason: Option<String>,
 }
 
 impl From<Saldo> for SaldoResponse {
@@ -24,6 +38,7 @@ impl From<Saldo> for SaldoResponse {
             id: value.saldo_id,
             user_id: value.user_id,
             total_balance: value.total_balance,
+            currency: value.currency,
             withdraw_amount: value.withdraw_amount,
             withdraw_time: value
                 .withdraw_time
@@ -34,6 +49,87 @@ impl From<Saldo> for SaldoResponse {
             updated_at: value
                 .updated_at
                 .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            deleted_at: value
+                .deleted_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            is_frozen: value.is_frozen,
+            frozen_reason: value.frozen_reason,
+        }
+    }
+}
+
+// Balances are summed per currency; amounts in different currencies are
+// never added together.
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct SaldoStatsResponse {
+    pub currency: Currency,
+    pub total_balance: Money,
+    pub account_count: i64,
+    pub average_balance: Money,
+    pub median_balance: Money,
+    // Should always be zero; anything else means a balance check was bypassed.
+    pub negative_count: i64,
+}
+
+impl From<SaldoStats> for SaldoStatsResponse {
+    fn from(value: SaldoStats) -> Self {
+        SaldoStatsResponse {
+            currency: value.currency,
+            total_balance: value.total_balance,
+            account_count: value.account_count,
+            average_balance: value.average_balance,
+            median_balance: value.median_balance,
+            negative_count: value.negative_count,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn saldo_timestamps_are_read_as_utc() {
+        let withdraw_time = NaiveDate::from_ymd_opt(2024, 3, 1)
+            .unwrap()
+            .and_hms_opt(9, 30, 0)
+            .unwrap();
+        let created_at = NaiveDate::from_ymd_opt(2024, 1, 15)
+            .unwrap()
+            .and_hms_opt(23, 59, 59)
+            .unwrap();
+        let updated_at = NaiveDate::from_ymd_opt(2024, 3, 1)
+            .unwrap()
+            .and_hms_opt(9, 30, 1)
+            .unwrap();
+
+        let response = SaldoResponse::from(Saldo {
+            saldo_id: 3,
+            user_id: 7,
+            total_balance: Money::new(40_000),
+            currency: Currency::IDR,
+            withdraw_amount: Some(Money::new(60_000)),
+            withdraw_time: Some(withdraw_time),
+            created_at: Some(created_at),
+            updated_at: Some(updated_at),
+            deleted_at: None,
+            is_frozen: false,
+            frozen_reason: None,
+        });
+
+        assert_eq!(response.id, 3);
+        assert_eq!(response.user_id, 7);
+        assert_eq!(response.withdraw_amount, Some(Money::new(60_000)));
+        assert_eq!(response.withdraw_time, Some(withdraw_time.and_utc()));
+        assert_eq!(response.created_at, Some(created_at.and_utc()));
+        assert_eq!(response.updated_at, Some(updated_at.and_utc()));
+        assert_eq!(response.deleted_at, None);
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["withdraw_time"], "2024-03-01T09:30:00Z");
+        assert_eq!(json["created_at"], "2024-01-15T23:59:59Z");
+        assert!(json.get("deleted_at").is_none());
+    }
+}
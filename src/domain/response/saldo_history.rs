@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    domain::{money::Money, request::saldo_history::BalanceChangeReason},
+    model::saldo_history::SaldoHistory,
+};
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct SaldoHistoryResponse {
+    pub id: i32,
+    pub saldo_id: i32,
+    pub user_id: i32,
+    pub old_balance: Money,
+    pub new_balance: Money,
+    pub reason: BalanceChangeReason,
+    pub reference_id: Option<i32>,
+    #[schema(format = "date-time")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl From<SaldoHistory> for SaldoHistoryResponse {
+    fn from(value: SaldoHistory) -> Self {
+        SaldoHistoryResponse {
+            id: value.saldo_history_id,
+            saldo_id: value.saldo_id,
+            user_id: value.user_id,
+            old_balance: value.old_balance,
+            new_balance: value.new_balance,
+            reason: value.reason,
+            reference_id: value.reference_id,
+            created_at: value
+                .created_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+        }
+    }
+}
@@ -1,15 +1,19 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::model::topup::Topup;
+use crate::{
+    domain::{currency::Currency, money::Money},
+    model::topup::Topup,
+};
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct TopupResponse {
     pub topup_id: i32,
     pub user_id: i32,
     pub topup_no: String,
-    pub topup_amount: i32,
+    pub topup_amount: Money,
+    pub currency: Currency,
     pub topup_method: String,
     pub topup_time: DateTime<Utc>,
     #[schema(format = "date-time")]
@@ -17,6 +21,10 @@ pub struct TopupResponse {
 
     #[schema(format = "date-time")]
     pub updated_at: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = "date-time")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl From<Topup> for TopupResponse {
@@ -26,6 +34,7 @@ impl From<Topup> for TopupResponse {
             user_id: value.user_id,
             topup_no: value.topup_no,
             topup_amount: value.topup_amount,
+            currency: value.currency,
             topup_method: value.topup_method,
             topup_time: DateTime::from_naive_utc_and_offset(value.topup_time, Utc),
             created_at: value
@@ -34,6 +43,35 @@ impl From<Topup> for TopupResponse {
             updated_at: value
                 .updated_at
                 .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            deleted_at: value
+                .deleted_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkTopupStatus {
+    Success,
+    Failed,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct BulkTopupResult {
+    pub user_id: i32,
+    pub status: BulkTopupStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topup_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct TopupMonthlyStatsResponse {
+    // First day of the month, UTC.
+    pub month: NaiveDate,
+    pub currency: Currency,
+    pub count: i64,
+    pub total_amount: Money,
+}
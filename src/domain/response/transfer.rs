@@ -2,20 +2,39 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::model::transfer::Transfer;
+use crate::{
+    domain::{currency::Currency, money::Money, transfer_status::TransferStatus},
+    model::transfer::{Transfer, TransferReceipt},
+};
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct TransferResponse {
     pub transfer_id: i32,
     pub transfer_from: i32,
     pub transfer_to: i32,
-    pub transfer_amount: i32,
+    pub transfer_amount: Money,
+    pub fee: Money,
+    pub currency: Currency,
     pub transfer_time: DateTime<Utc>,
+    pub status: TransferStatus,
+    pub note: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reversed_transfer_id: Option<i32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = "date-time")]
+    pub scheduled_at: Option<DateTime<Utc>>,
+
     #[schema(format = "date-time")]
     pub created_at: Option<DateTime<Utc>>,
 
     #[schema(format = "date-time")]
     pub updated_at: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = "date-time")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl From<Transfer> for TransferResponse {
@@ -25,13 +44,79 @@ impl From<Transfer> for TransferResponse {
             transfer_from: value.transfer_from,
             transfer_to: value.transfer_to,
             transfer_amount: value.transfer_amount,
+            fee: value.fee,
+            currency: value.currency,
             transfer_time: DateTime::from_naive_utc_and_offset(value.transfer_time, Utc),
+            status: value.status,
+            note: value.note,
+            reversed_transfer_id: value.reversed_transfer_id,
+            scheduled_at: value
+                .scheduled_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
             created_at: value
                 .created_at
                 .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
             updated_at: value
                 .updated_at
                 .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            deleted_at: value
+                .deleted_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct TransferReceiptResponse {
+    pub transfer_id: i32,
+    pub from_name: String,
+    pub to_name: String,
+    pub amount: Money,
+    pub currency: Currency,
+    pub time: DateTime<Utc>,
+    pub reference: String,
+}
+
+impl TransferReceiptResponse {
+    // Stable for a given transfer, e.g. `TRF-20250806-00000042`.
+    pub fn reference(transfer_id: i32, time: DateTime<Utc>) -> String {
+        format!("TRF-{}-{transfer_id:08}", time.format("%Y%m%d"))
+    }
+}
+
+impl From<TransferReceipt> for TransferReceiptResponse {
+    fn from(value: TransferReceipt) -> Self {
+        let time = DateTime::from_naive_utc_and_offset(value.transfer_time, Utc);
+
+        TransferReceiptResponse {
+            transfer_id: value.transfer_id,
+            from_name: format!("{} {}", value.from_firstname, value.from_lastname),
+            to_name: format!("{} {}", value.to_firstname, value.to_lastname),
+            amount: value.transfer_amount,
+            currency: value.currency,
+            time,
+            reference: Self::reference(value.transfer_id, time),
         }
     }
 }
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct NetFlowPeriod {
+    // Absent when counting from the user's first transfer.
+    #[schema(format = "date-time")]
+    pub from: Option<DateTime<Utc>>,
+    pub to: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct NetFlowResponse {
+    pub user_id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counterparty: Option<i32>,
+    pub currency: Currency,
+    pub incoming: Money,
+    pub outgoing: Money,
+    // incoming - outgoing; negative when the user paid out more.
+    pub net: Money,
+    pub period: NetFlowPeriod,
+}
@@ -1,4 +1,8 @@
-use crate::model::user::User;
+use crate::{
+    domain::{currency::Currency, money::Money, role::Role},
+    model::{transfer::TransferCounterparty, user::User},
+    utils::mask_noc,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -9,12 +13,34 @@ pub struct UserResponse {
     pub firstname: String,
     pub lastname: String,
     pub email: String,
+    // Masked to the last four digits unless an admin asks for `full=true`,
+    // or the caller looked the user up by this very number.
     pub noc_transfer: String,
+    pub role: Role,
+    pub is_verified: bool,
+    #[schema(format = "date-time")]
+    pub last_login_at: Option<DateTime<Utc>>,
+
     #[schema(format = "date-time")]
     pub created_at: Option<DateTime<Utc>>,
 
     #[schema(format = "date-time")]
     pub updated_at: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = "date-time")]
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl UserResponse {
+    pub fn unmasked(value: User) -> Self {
+        let noc_transfer = value.noc_transfer.clone();
+
+        UserResponse {
+            noc_transfer,
+            ..UserResponse::from(value)
+        }
+    }
 }
 
 impl From<User> for UserResponse {
@@ -24,13 +50,57 @@ impl From<User> for UserResponse {
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
-            noc_transfer: value.noc_transfer,
+            noc_transfer: mask_noc(&value.noc_transfer),
+            role: value.role,
+            is_verified: value.is_verified,
+            last_login_at: value
+                .last_login_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
             created_at: value
                 .created_at
                 .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
             updated_at: value
                 .updated_at
                 .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            deleted_at: value
+                .deleted_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UserSummaryResponse {
+    pub total_topup: Money,
+    pub total_withdraw: Money,
+    pub total_sent: Money,
+    pub total_received: Money,
+    pub current_balance: Money,
+    pub transaction_count: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CounterpartyResponse {
+    pub counterparty_id: i32,
+    pub counterparty_name: String,
+    pub currency: Currency,
+    pub total_sent: Money,
+    pub total_received: Money,
+    pub transfer_count: i64,
+    #[schema(format = "date-time")]
+    pub last_transfer_at: DateTime<Utc>,
+}
+
+impl From<TransferCounterparty> for CounterpartyResponse {
+    fn from(value: TransferCounterparty) -> Self {
+        CounterpartyResponse {
+            counterparty_id: value.counterparty_id,
+            counterparty_name: format!("{} {}", value.firstname, value.lastname),
+            currency: value.currency,
+            total_sent: value.total_sent,
+            total_received: value.total_received,
+            transfer_count: value.transfer_count,
+            last_transfer_at: DateTime::from_naive_utc_and_offset(value.last_transfer_at, Utc),
         }
     }
 }
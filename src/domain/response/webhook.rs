@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::model::webhook::Webhook;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct WebhookResponse {
+    pub id: i32,
+    pub user_id: i32,
+    pub url: String,
+    // Key for verifying the `X-Webhook-Signature` header on deliveries.
+    pub secret: String,
+    #[schema(format = "date-time")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl From<Webhook> for WebhookResponse {
+    fn from(value: Webhook) -> Self {
+        WebhookResponse {
+            id: value.webhook_id,
+            user_id: value.user_id,
+            url: value.url,
+            secret: value.secret,
+            created_at: value
+                .created_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+        }
+    }
+}
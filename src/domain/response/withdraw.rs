@@ -1,4 +1,7 @@
-use crate::model::withdraw::Withdraw;
+use crate::{
+    domain::{currency::Currency, money::Money, withdraw_status::WithdrawStatus},
+    model::withdraw::Withdraw,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -7,13 +10,21 @@ use utoipa::ToSchema;
 pub struct WithdrawResponse {
     pub withdraw_id: i32,
     pub user_id: i32,
-    pub withdraw_amount: i32,
+    pub withdraw_amount: Money,
+    pub currency: Currency,
+    pub status: WithdrawStatus,
     pub withdraw_time: DateTime<Utc>,
+    pub note: Option<String>,
+
     #[schema(format = "date-time")]
     pub created_at: Option<DateTime<Utc>>,
 
     #[schema(format = "date-time")]
     pub updated_at: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(format = "date-time")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl From<Withdraw> for WithdrawResponse {
@@ -22,13 +33,19 @@ impl From<Withdraw> for WithdrawResponse {
             withdraw_id: value.withdraw_id,
             user_id: value.user_id,
             withdraw_amount: value.withdraw_amount,
+            currency: value.currency,
+            status: value.status,
             withdraw_time: DateTime::from_naive_utc_and_offset(value.withdraw_time, Utc),
+            note: value.note,
             created_at: value
                 .created_at
                 .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
             updated_at: value
                 .updated_at
                 .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            deleted_at: value
+                .deleted_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
         }
     }
 }
@@ -0,0 +1,89 @@
+use crate::{
+    domain::{money::Money, request::topup::MIN_TOPUP_AMOUNT},
+    utils::AppError,
+};
+
+/// Deployment-wide bounds on topups, on top of the request floor.
+#[derive(Debug, Clone, Copy)]
+pub struct TopupLimits {
+    pub min_amount: Money,
+    pub max_amount: Option<Money>,
+    pub bulk_max_rows: usize,
+}
+
+impl Default for TopupLimits {
+    fn default() -> Self {
+        Self {
+            min_amount: Money::new(MIN_TOPUP_AMOUNT),
+            max_amount: None,
+            bulk_max_rows: 500,
+        }
+    }
+}
+
+impl TopupLimits {
+    pub fn ensure_amount(&self, topup_amount: Money) -> Result<(), AppError> {
+        if topup_amount < self.min_amount {
+            return Err(AppError::invalid_field(
+                "topup_amount",
+                format!(
+                    "Topup amount {topup_amount} is below the minimum topup of {}",
+                    self.min_amount
+                ),
+            ));
+        }
+
+        if let Some(max) = self.max_amount
+            && topup_amount > max
+        {
+            return Err(AppError::invalid_field(
+                "topup_amount",
+                format!("Topup amount {topup_amount} exceeds the maximum single topup of {max}"),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> TopupLimits {
+        TopupLimits {
+            min_amount: Money::new(20000),
+            max_amount: Some(Money::new(1_000_000)),
+            ..TopupLimits::default()
+        }
+    }
+
+    #[test]
+    fn amounts_at_the_bounds_are_allowed() {
+        assert!(limits().ensure_amount(Money::new(20000)).is_ok());
+        assert!(limits().ensure_amount(Money::new(1_000_000)).is_ok());
+    }
+
+    #[test]
+    fn amount_below_the_minimum_is_rejected() {
+        let err = limits().ensure_amount(Money::new(19999)).unwrap_err();
+
+        assert!(err.to_string().contains("below the minimum topup"), "{err}");
+    }
+
+    #[test]
+    fn amount_over_the_cap_is_rejected() {
+        let err = limits().ensure_amount(Money::new(1_000_001)).unwrap_err();
+
+        assert!(err.to_string().contains("maximum single topup"), "{err}");
+    }
+
+    #[test]
+    fn no_cap_by_default() {
+        assert!(
+            TopupLimits::default()
+                .ensure_amount(Money::new(i64::MAX))
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use core::fmt;
+use std::str::FromStr;
+
+use sea_query::Value;
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    Decode, Postgres, Type,
+    error::BoxDynError,
+    postgres::{PgTypeInfo, PgValueRef},
+};
+use utoipa::ToSchema;
+
+use crate::utils::AppError;
+
+// Only settled transfers have moved money. Scheduled transfers start as
+// `Pending` and end up `Completed` or `Failed` when they come due. A
+// `Reversed` transfer did move money, which a compensating transfer (linked
+// through `reversed_transfer_id`) has since moved back.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferStatus {
+    Pending,
+    #[default]
+    Completed,
+    Failed,
+    Reversed,
+}
+
+impl TransferStatus {
+    pub const SETTLED: [TransferStatus; 2] = [TransferStatus::Completed, TransferStatus::Reversed];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferStatus::Pending => "pending",
+            TransferStatus::Completed => "completed",
+            TransferStatus::Failed => "failed",
+            TransferStatus::Reversed => "reversed",
+        }
+    }
+
+    pub fn is_settled(&self) -> bool {
+        Self::SETTLED.contains(self)
+    }
+}
+
+impl fmt::Display for TransferStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TransferStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(TransferStatus::Pending),
+            "completed" => Ok(TransferStatus::Completed),
+            "failed" => Ok(TransferStatus::Failed),
+            "reversed" => Ok(TransferStatus::Reversed),
+            other => Err(AppError::Custom(format!(
+                "Unknown transfer status: {other}"
+            ))),
+        }
+    }
+}
+
+impl From<TransferStatus> for Value {
+    fn from(value: TransferStatus) -> Self {
+        Value::String(Some(Box::new(value.as_str().to_string())))
+    }
+}
+
+impl Type<Postgres> for TransferStatus {
+    fn type_info() -> PgTypeInfo {
+        <String as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <String as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for TransferStatus {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let raw = <&str as Decode<Postgres>>::decode(value)?;
+        Ok(raw.parse()?)
+    }
+}
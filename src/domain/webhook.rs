@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookEvent {
+    TopupCreated,
+    TransferCreated,
+    WithdrawCreated,
+}
+
+impl WebhookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEvent::TopupCreated => "topup.created",
+            WebhookEvent::TransferCreated => "transfer.created",
+            WebhookEvent::WithdrawCreated => "withdraw.created",
+        }
+    }
+}
+
+impl Serialize for WebhookEvent {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Body POSTed to a registered endpoint. Its raw bytes are what the
+/// `X-Webhook-Signature` header signs.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookPayload {
+    pub event: WebhookEvent,
+    pub data: Value,
+    pub timestamp: DateTime<Utc>,
+}
@@ -0,0 +1,88 @@
+use core::fmt;
+use std::str::FromStr;
+
+use sea_query::Value;
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    Decode, Postgres, Type,
+    error::BoxDynError,
+    postgres::{PgTypeInfo, PgValueRef},
+};
+use utoipa::ToSchema;
+
+use crate::utils::AppError;
+
+// Withdrawals at or below the approval threshold are `Completed` right away.
+// Larger ones start `Pending` and are `Approved` or `Rejected` by an admin;
+// only `Approved` and `Completed` withdrawals have debited the balance.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum WithdrawStatus {
+    Pending,
+    Approved,
+    Rejected,
+    #[default]
+    Completed,
+}
+
+impl WithdrawStatus {
+    pub const SETTLED: [WithdrawStatus; 2] = [WithdrawStatus::Approved, WithdrawStatus::Completed];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WithdrawStatus::Pending => "pending",
+            WithdrawStatus::Approved => "approved",
+            WithdrawStatus::Rejected => "rejected",
+            WithdrawStatus::Completed => "completed",
+        }
+    }
+
+    pub fn is_settled(&self) -> bool {
+        Self::SETTLED.contains(self)
+    }
+}
+
+impl fmt::Display for WithdrawStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for WithdrawStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(WithdrawStatus::Pending),
+            "approved" => Ok(WithdrawStatus::Approved),
+            "rejected" => Ok(WithdrawStatus::Rejected),
+            "completed" => Ok(WithdrawStatus::Completed),
+            other => Err(AppError::Custom(format!(
+                "Unknown withdraw status: {other}"
+            ))),
+        }
+    }
+}
+
+impl From<WithdrawStatus> for Value {
+    fn from(value: WithdrawStatus) -> Self {
+        Value::String(Some(Box::new(value.as_str().to_string())))
+    }
+}
+
+impl Type<Postgres> for WithdrawStatus {
+    fn type_info() -> PgTypeInfo {
+        <String as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <String as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for WithdrawStatus {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let raw = <&str as Decode<Postgres>>::decode(value)?;
+        Ok(raw.parse()?)
+    }
+}
@@ -0,0 +1,58 @@
+use axum::{
+    Json, extract::Extension, http::StatusCode, middleware, response::IntoResponse, routing::get,
+};
+use serde_json::{Value, json};
+use std::sync::Arc;
+use tracing::error;
+use utoipa_axum::router::OpenApiRouter;
+
+use crate::{
+    config::{ConnectionManager, ConnectionPool},
+    domain::response::{ApiResponse, ErrorResponse, migration::MigrationStatusResponse},
+    middleware::jwt,
+    state::AppState,
+    utils::AppError,
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/migrations",
+    tag = "Admin",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "Applied migrations and whether the schema matches this binary", body = ApiResponse<MigrationStatusResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn get_migrations(
+    Extension(pool): Extension<ConnectionPool>,
+) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+    match ConnectionManager::migration_status(&pool).await {
+        Ok(status) => Ok((
+            StatusCode::OK,
+            Json(json!(ApiResponse {
+                status: "success".to_string(),
+                message: "Migration status retrieved successfully".to_string(),
+                data: status,
+            })),
+        )),
+        Err(e) => {
+            error!("❌ [Admin] Failed to read migration status: {e}");
+            let e = ErrorResponse::from(AppError::InternalError(e.to_string()));
+            Err((e.code, Json(json!(e))))
+        }
+    }
+}
+
+pub fn admin_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    OpenApiRouter::new()
+        .route("/api/admin/migrations", get(get_migrations))
+        .route_layer(middleware::from_fn(jwt::require_admin))
+        .route_layer(middleware::from_fn(jwt::auth))
+        .layer(Extension(app_state.db_pool.clone()))
+        .layer(Extension(app_state.jwt_service.clone()))
+}
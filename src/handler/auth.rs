@@ -1,22 +1,38 @@
 use axum::{
     Extension, Json,
-    http::StatusCode,
+    extract::{ConnectInfo, Query},
+    http::{HeaderMap, StatusCode, header},
     middleware,
     response::IntoResponse,
     routing::{get, post},
 };
 use serde_json::{Value, json};
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 use utoipa_axum::router::OpenApiRouter;
 
 use crate::{
-    abstract_trait::{DynAuthService, DynUserService},
+    abstract_trait::{DynAuthService, DynSaldoService, DynUserService},
+    config::Claims,
     domain::{
-        request::{LoginRequest, RegisterRequest},
-        response::{ApiResponse, user::UserResponse},
+        request::{
+            DeleteAccountRequest, ForgotPasswordRequest, GetUserQuery, LoginContext, LoginRequest,
+            MeQuery, RefreshRequest, RegisterRequest, ResendVerificationRequest,
+            ResetPasswordRequest, VerifyEmailQuery,
+        },
+        response::{
+            ApiResponse, ErrorResponse,
+            auth::{CapabilitiesResponse, LoginAuditResponse, MeResponse, TokenPair},
+            user::UserResponse,
+        },
+        role::Role,
+    },
+    middleware::{
+        jwt,
+        rate_limit::{LoginAttemptKey, LoginRateLimiter, login_rate_limit},
+        validation::SimpleValidatedJson,
     },
-    middleware::{jwt, validation::SimpleValidatedJson},
     state::AppState,
+    utils::AppError,
 };
 
 #[utoipa::path(
@@ -24,8 +40,9 @@ use crate::{
     path = "/api/auth/register",
     request_body = RegisterRequest,
     responses(
-        (status = 200, description = "Login successful", body = ApiResponse<UserResponse>),
-        (status = 401, description = "Unauthorized")
+        (status = 201, description = "User registered successfully", body = ApiResponse<UserResponse>),
+        (status = 409, description = "Email already exists"),
+        (status = 422, description = "Invalid registration data")
     ),
     tag = "Auth"
 )]
@@ -34,8 +51,8 @@ pub async fn register_user_handler(
     SimpleValidatedJson(body): SimpleValidatedJson<RegisterRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
     match service.register_user(&body).await {
-        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -44,26 +61,179 @@ pub async fn register_user_handler(
     path = "/api/auth/login",
     request_body = LoginRequest,
     responses(
-        (status = 200, description = "Login successful", body = ApiResponse<String>),
-        (status = 401, description = "Unauthorized")
+        (status = 200, description = "Login successful", body = ApiResponse<TokenPair>),
+        (status = 401, description = "Invalid credentials or email not verified"),
+        (status = 429, description = "Too many failed login attempts, see Retry-After")
     ),
     tag = "Auth"
 )]
 pub async fn login_user_handler(
     Extension(service): Extension<DynAuthService>,
+    Extension(limiter): Extension<Arc<LoginRateLimiter>>,
+    Extension(attempt_key): Extension<LoginAttemptKey>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     SimpleValidatedJson(body): SimpleValidatedJson<LoginRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
-    match service.login_user(&body).await {
+    let user_agent = headers
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok());
+    let context = LoginContext::new(addr.ip(), user_agent);
+
+    match service.login_user(&body, &context).await {
+        Ok(response) => {
+            limiter.reset(&attempt_key);
+            Ok((StatusCode::OK, Json(json!(response))))
+        }
+        Err(e) => {
+            limiter.record_failure(&attempt_key);
+            Err((StatusCode::UNAUTHORIZED, Json(json!(e))))
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/refresh",
+    request_body = RefreshRequest,
+    responses(
+        (status = 200, description = "Token refreshed successfully", body = ApiResponse<TokenPair>),
+        (status = 401, description = "Invalid, expired, already used or logged out refresh token")
+    ),
+    tag = "Auth"
+)]
+pub async fn refresh_token_handler(
+    Extension(service): Extension<DynAuthService>,
+    SimpleValidatedJson(body): SimpleValidatedJson<RefreshRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+    match service.refresh_token(&body.refresh_token).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
         Err(e) => Err((StatusCode::UNAUTHORIZED, Json(json!(e)))),
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/forgot-password",
+    request_body = ForgotPasswordRequest,
+    responses(
+        (status = 200, description = "Reset link sent if the email is registered", body = serde_json::Value),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Auth"
+)]
+pub async fn forgot_password_handler(
+    Extension(service): Extension<DynAuthService>,
+    SimpleValidatedJson(body): SimpleValidatedJson<ForgotPasswordRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+    match service.request_password_reset(&body).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/reset-password",
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 200, description = "Password reset successfully", body = serde_json::Value),
+        (status = 422, description = "Invalid, expired or already used reset token")
+    ),
+    tag = "Auth"
+)]
+pub async fn reset_password_handler(
+    Extension(service): Extension<DynAuthService>,
+    SimpleValidatedJson(body): SimpleValidatedJson<ResetPasswordRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+    match service.reset_password(&body).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/auth/verify",
+    params(VerifyEmailQuery),
+    responses(
+        (status = 200, description = "Email verified successfully", body = ApiResponse<UserResponse>),
+        (status = 422, description = "Invalid, expired or already used verification token")
+    ),
+    tag = "Auth"
+)]
+pub async fn verify_email_handler(
+    Extension(service): Extension<DynAuthService>,
+    Query(params): Query<VerifyEmailQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+    match service.verify_email(&params).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/resend-verification",
+    request_body = ResendVerificationRequest,
+    responses(
+        (status = 200, description = "Verification link sent if the email is registered and unverified", body = serde_json::Value),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Auth"
+)]
+pub async fn resend_verification_handler(
+    Extension(service): Extension<DynAuthService>,
+    SimpleValidatedJson(body): SimpleValidatedJson<ResendVerificationRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+    match service.resend_verification(&body).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+// Logout and account deletion act on the caller's login session, which only a
+// bearer token has; API-key requests get the same user and role but no claims.
+fn session_claims(claims: Option<Extension<Claims>>) -> Result<Claims, (StatusCode, Json<Value>)> {
+    claims.map(|Extension(claims)| claims).ok_or_else(|| {
+        let e = ErrorResponse::from(AppError::Forbidden(
+            "This endpoint needs a bearer token; API keys cannot use it".into(),
+        ));
+        (e.code, Json(json!(e)))
+    })
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout",
+    responses(
+        (status = 200, description = "Access token and its session's refresh tokens revoked", body = serde_json::Value),
+        (status = 401, description = "Missing, invalid or already revoked token"),
+        (status = 403, description = "Called with an API key instead of a bearer token")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth",
+)]
+pub async fn logout_handler(
+    Extension(service): Extension<DynAuthService>,
+    claims: Option<Extension<Claims>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+    let claims = session_claims(claims)?;
+
+    match service.logout(&claims).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/api/auth/me",
+    params(MeQuery),
     responses(
-        (status = 200, description = "Get Me user", body = ApiResponse<UserResponse>)
+        (status = 200, description = "Get Me user; with `include=saldo` the data is a MeResponse", body = ApiResponse<UserResponse>)
     ),
     security(
         ("bearer_auth" = [])
@@ -72,30 +242,157 @@ pub async fn login_user_handler(
 )]
 pub async fn get_me_handler(
     Extension(service): Extension<DynUserService>,
+    Extension(saldo_service): Extension<DynSaldoService>,
     Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
+    Query(params): Query<MeQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.get_user(user_id).await {
-        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
+    let fail = |e: ErrorResponse| {
+        (
+            e.code,
             Json(json!({
                 "status": "fail",
                 "message": e.message
             })),
-        )),
+        )
+    };
+
+    let response = service
+        .get_user(user_id, &GetUserQuery::default(), role)
+        .await
+        .map_err(fail)?;
+
+    if !params.includes_saldo() {
+        return Ok((StatusCode::OK, Json(json!(response))));
+    }
+
+    let Some(user) = response.data else {
+        return Ok((StatusCode::OK, Json(json!(response))));
+    };
+
+    // Users get their first saldo with their first topup; until then the
+    // lookup 404s and `saldo` is simply null.
+    let saldo = match saldo_service.get_saldo_user(user_id, user_id, role).await {
+        Ok(response) => response.data,
+        Err(e) if e.code == StatusCode::NOT_FOUND => None,
+        Err(e) => return Err(fail(e)),
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(json!(ApiResponse {
+            status: response.status,
+            message: response.message,
+            data: MeResponse { user, saldo },
+        })),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/auth/me",
+    request_body = DeleteAccountRequest,
+    responses(
+        (status = 200, description = "Account and its saldo, topup, withdraw and unsettled transfer records soft-deleted", body = serde_json::Value),
+        (status = 401, description = "Missing token or wrong password"),
+        (status = 403, description = "Called with an API key instead of a bearer token"),
+        (status = 409, description = "A saldo still holds a balance; withdraw it first")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth",
+)]
+pub async fn delete_me_handler(
+    Extension(service): Extension<DynAuthService>,
+    claims: Option<Extension<Claims>>,
+    SimpleValidatedJson(body): SimpleValidatedJson<DeleteAccountRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+    let claims = session_claims(claims)?;
+
+    match service.delete_account(&claims, &body).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/auth/me/logins",
+    responses(
+        (status = 200, description = "The caller's most recent login attempts, newest first", body = ApiResponse<Vec<LoginAuditResponse>>),
+        (status = 401, description = "Unauthorized access")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth",
+)]
+pub async fn get_my_logins_handler(
+    Extension(service): Extension<DynAuthService>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+    match service.get_login_history(user_id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/auth/me/capabilities",
+    responses(
+        (status = 200, description = "What the caller may currently do, derived from their role, verification and saldo state", body = ApiResponse<CapabilitiesResponse>),
+        (status = 401, description = "Unauthorized access")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth",
+)]
+pub async fn get_my_capabilities_handler(
+    Extension(service): Extension<DynAuthService>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+    match service.get_capabilities(user_id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
 pub fn auth_routes(app_state: Arc<AppState>) -> OpenApiRouter {
     let public_routes = OpenApiRouter::new()
         .route("/api/auth/register", post(register_user_handler))
-        .route("/api/auth/login", post(login_user_handler))
-        .layer(Extension(app_state.di_container.auth_service.clone()));
+        .route(
+            "/api/auth/login",
+            post(login_user_handler).route_layer(middleware::from_fn(login_rate_limit)),
+        )
+        .route("/api/auth/refresh", post(refresh_token_handler))
+        .route("/api/auth/forgot-password", post(forgot_password_handler))
+        .route("/api/auth/reset-password", post(reset_password_handler))
+        .route("/api/auth/verify", get(verify_email_handler))
+        .route(
+            "/api/auth/resend-verification",
+            post(resend_verification_handler),
+        )
+        .layer(Extension(app_state.di_container.auth_service.clone()))
+        .layer(Extension(app_state.login_rate_limiter.clone()));
 
     let private_routes = OpenApiRouter::new()
-        .route("/api/auth/me", get(get_me_handler))
+        .route(
+            "/api/auth/me",
+            get(get_me_handler).delete(delete_me_handler),
+        )
+        .route("/api/auth/me/logins", get(get_my_logins_handler))
+        .route(
+            "/api/auth/me/capabilities",
+            get(get_my_capabilities_handler),
+        )
+        .route("/api/auth/logout", post(logout_handler))
         .route_layer(middleware::from_fn(jwt::auth))
+        .layer(Extension(app_state.di_container.auth_service.clone()))
         .layer(Extension(app_state.di_container.user_service.clone()))
+        .layer(Extension(app_state.di_container.saldo_service.clone()))
         .layer(Extension(app_state.jwt_service.clone()));
 
     public_routes.merge(private_routes)
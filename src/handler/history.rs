@@ -0,0 +1,153 @@
+use axum::{
+    Json,
+    body::Body,
+    extract::{Extension, Query},
+    http::{StatusCode, header},
+    middleware,
+    response::IntoResponse,
+    routing::get,
+};
+use serde_json::json;
+use std::sync::Arc;
+use utoipa_axum::router::OpenApiRouter;
+
+use crate::{
+    abstract_trait::DynHistoryService,
+    domain::{
+        request::{FindHistoryRequest, StatementRequest},
+        response::{ApiResponsePagination, history::HistoryEntryResponse},
+        role::Role,
+    },
+    middleware::{jwt, validation::ValidatedPath},
+    state::AppState,
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/history",
+    tag = "History",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID"),
+        FindHistoryRequest
+    ),
+    responses(
+        (status = 200, description = "Merged transaction history, newest first", body = ApiResponsePagination<Vec<HistoryEntryResponse>>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "History belongs to another user", body = String),
+        (status = 422, description = "from_date is after to_date", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn get_history(
+    Extension(service): Extension<DynHistoryService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Query(params): Query<FindHistoryRequest>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.get_history(id, &params, user_id, role).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/history.csv",
+    tag = "History",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID"),
+        FindHistoryRequest
+    ),
+    responses(
+        (status = 200, description = "Transaction history as a CSV attachment, newest first", body = String, content_type = "text/csv"),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "History belongs to another user", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 422, description = "from_date is after to_date", body = String),
+    )
+)]
+pub async fn export_history_csv(
+    Extension(service): Extension<DynHistoryService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Query(params): Query<FindHistoryRequest>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.export_history_csv(id, &params, user_id, role).await {
+        Ok(rows) => Ok((
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"history-user-{id}.csv\""),
+                ),
+            ],
+            Body::from_stream(rows),
+        )),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/statement.pdf",
+    tag = "History",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID"),
+        StatementRequest
+    ),
+    responses(
+        (status = 200, description = "Account statement for the period as a PDF attachment", body = Vec<u8>, content_type = "application/pdf"),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Statement belongs to another user", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 422, description = "from is after to", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn export_statement_pdf(
+    Extension(service): Extension<DynHistoryService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Query(params): Query<StatementRequest>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service
+        .export_statement_pdf(id, &params, user_id, role)
+        .await
+    {
+        Ok(pdf) => Ok((
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE, "application/pdf".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"statement-user-{id}.pdf\""),
+                ),
+            ],
+            Body::from(pdf),
+        )),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+pub fn history_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    OpenApiRouter::new()
+        .route("/api/users/{id}/history", get(get_history))
+        .route("/api/users/{id}/history.csv", get(export_history_csv))
+        .route("/api/users/{id}/statement.pdf", get(export_statement_pdf))
+        .route_layer(middleware::from_fn(jwt::auth))
+        .layer(Extension(app_state.di_container.history_service.clone()))
+        .layer(Extension(app_state.jwt_service.clone()))
+}
@@ -0,0 +1,36 @@
+use axum::{
+    Extension, Router,
+    http::{StatusCode, header},
+    response::IntoResponse,
+    routing::get,
+};
+use std::sync::Arc;
+use tracing::error;
+
+use crate::{config::Metrics, state::AppState};
+
+pub async fn metrics_handler(Extension(metrics): Extension<Arc<Metrics>>) -> impl IntoResponse {
+    match metrics.render() {
+        Ok(body) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+            body,
+        ),
+        Err(e) => {
+            error!("❌ [Metrics] Failed to encode metrics: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                [(header::CONTENT_TYPE, "text/plain")],
+                "Failed to encode metrics".to_string(),
+            )
+        }
+    }
+}
+
+// Plain router rather than OpenApiRouter: the scrape endpoint is for
+// Prometheus, not API clients, and may be served on its own port.
+pub fn metrics_routes(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics_handler))
+        .layer(Extension(app_state.metrics.clone()))
+}
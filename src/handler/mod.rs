@@ -1,82 +1,253 @@
+mod admin;
 mod auth;
+mod health;
+mod history;
+mod metrics;
+mod reconciliation;
 mod saldo;
 mod topup;
 mod transfer;
 mod user;
+mod webhook;
 mod withdraw;
 
-use crate::state::AppState;
+use crate::{
+    domain::{
+        currency::Currency,
+        money::Money,
+        request::{
+            BalanceChangeReason, BatchRecipient, BulkTopupRow, CreateBatchTransferRequest,
+            CreateSaldoRequest, CreateTopupRequest, CreateTransferRequest, CreateUserRequest,
+            CreateWebhookRequest, CreateWithdrawRequest, DeleteAccountRequest,
+            FindUsersBatchRequest, ForgotPasswordRequest, FreezeSaldoRequest, HistoryKind,
+            LoginRequest, MatchMode, RefreshRequest, RegisterRequest, ResendVerificationRequest,
+            ResetPasswordRequest, SortOrder, TransferAllRequest, TransferDirection,
+            UpdateSaldoRequest, UpdateTopupAmount, UpdateTopupRequest, UpdateTransferAmountRequest,
+            UpdateTransferRequest, UpdateUserRequest, UpdateUserRoleRequest, UpdateWithdrawRequest,
+        },
+        response::{
+            ErrorResponse,
+            auth::{CapabilitiesResponse, LoginAuditResponse, MeResponse, TokenPair},
+            history::HistoryEntryResponse,
+            migration::{AppliedMigrationResponse, MigrationStatusResponse},
+            pagination::Pagination,
+            reconciliation::ReconcileResponse,
+            saldo::{SaldoResponse, SaldoStatsResponse},
+            saldo_history::SaldoHistoryResponse,
+            topup::{BulkTopupResult, BulkTopupStatus, TopupMonthlyStatsResponse, TopupResponse},
+            transfer::{NetFlowPeriod, NetFlowResponse, TransferReceiptResponse, TransferResponse},
+            user::CounterpartyResponse,
+            user::UserResponse,
+            user::UserSummaryResponse,
+            webhook::WebhookResponse,
+            withdraw::WithdrawResponse,
+        },
+        role::Role,
+        transfer_status::TransferStatus,
+        withdraw_status::WithdrawStatus,
+    },
+    middleware::{language::localize, metrics::track_metrics, request_id::request_id},
+    state::AppState,
+};
 use anyhow::Result;
-use std::sync::Arc;
+use axum::{Extension, Router, middleware};
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
 use tokio::net::TcpListener;
-use utoipa::{Modify, OpenApi, openapi::security::SecurityScheme};
+use tracing::{error, info};
+use utoipa::{
+    Modify, OpenApi,
+    openapi::security::{ApiKey, ApiKeyValue, SecurityScheme},
+};
 use utoipa_axum::router::OpenApiRouter;
 use utoipa_swagger_ui::SwaggerUi;
 
+pub use self::admin::admin_routes;
 pub use self::auth::auth_routes;
+pub use self::health::health_routes;
+pub use self::history::history_routes;
+pub use self::metrics::metrics_routes;
+pub use self::reconciliation::reconciliation_routes;
 pub use self::saldo::saldos_routes;
 pub use self::topup::topup_routes;
 pub use self::transfer::transfers_routes;
 pub use self::user::users_routes;
+pub use self::webhook::webhook_routes;
 pub use self::withdraw::withdraw_routes;
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
+        admin::get_migrations,
         auth::login_user_handler,
+        auth::refresh_token_handler,
+        auth::forgot_password_handler,
+        auth::reset_password_handler,
+        auth::verify_email_handler,
+        auth::resend_verification_handler,
+        auth::logout_handler,
         auth::get_me_handler,
+        auth::get_my_logins_handler,
+        auth::get_my_capabilities_handler,
+        auth::delete_me_handler,
         auth::register_user_handler,
+        health::health,
+        health::health_db,
+        history::get_history,
+        history::export_history_csv,
+        history::export_statement_pdf,
+        reconciliation::reconcile_user,
+        reconciliation::find_mismatches,
         saldo::get_saldos,
         saldo::get_saldo,
         saldo::get_saldo_users,
         saldo::get_saldo_user,
+        saldo::get_saldo_history,
+        saldo::get_saldo_stats,
         saldo::create_saldo,
         saldo::update_saldo,
         saldo::delete_saldo,
+        saldo::restore_saldo,
+        saldo::freeze_saldo,
+        saldo::unfreeze_saldo,
         topup::get_topups,
         topup::get_topup,
         topup::get_topup_users,
         topup::get_topup_user,
         topup::create_topup,
+        topup::create_bulk_topup,
         topup::update_topup,
         topup::delete_topup,
+        topup::restore_topup,
+        topup::get_topup_monthly_stats,
         transfer::get_transfers,
         transfer::get_transfer,
+        transfer::get_transfer_receipt,
+        transfer::get_scheduled_transfers,
+        transfer::get_transfers_between,
         transfer::get_transfer_users,
         transfer::get_transfer_user,
+        transfer::get_transfer_net_flow,
         transfer::create_transfer,
+        transfer::create_batch_transfer,
+        transfer::transfer_all,
         transfer::update_transfer,
         transfer::delete_transfer,
+        transfer::restore_transfer,
+        transfer::reverse_transfer,
         user::get_users,
         user::get_user,
+        user::get_user_by_noc,
+        user::get_users_batch,
+        user::get_user_summary,
+        user::get_user_counterparties,
         user::create_user,
         user::update_user,
         user::delete_user,
+        user::restore_user,
+        user::rotate_noc_transfer,
+        user::update_user_role,
         withdraw::get_withdraws,
         withdraw::get_withdraw,
         withdraw::get_withdraw_users,
         withdraw::get_withdraw_user,
         withdraw::create_withdraw,
         withdraw::update_withdraw,
-        withdraw::delete_withdraw
+        withdraw::delete_withdraw,
+        withdraw::restore_withdraw,
+        withdraw::approve_withdraw,
+        withdraw::reject_withdraw,
+        webhook::create_webhook
     ),
+    components(schemas(
+        Currency,
+        Money,
+        Role,
+        SortOrder,
+        MatchMode,
+        TransferDirection,
+        HistoryKind,
+        BalanceChangeReason,
+        ErrorResponse,
+        Pagination,
+        ReconcileResponse,
+        RegisterRequest,
+        LoginRequest,
+        RefreshRequest,
+        DeleteAccountRequest,
+        ForgotPasswordRequest,
+        ResetPasswordRequest,
+        ResendVerificationRequest,
+        CreateUserRequest,
+        UpdateUserRequest,
+        UpdateUserRoleRequest,
+        FindUsersBatchRequest,
+        CreateSaldoRequest,
+        UpdateSaldoRequest,
+        FreezeSaldoRequest,
+        CreateTopupRequest,
+        UpdateTopupRequest,
+        UpdateTopupAmount,
+        CreateTransferRequest,
+        CreateBatchTransferRequest,
+        BatchRecipient,
+        TransferAllRequest,
+        UpdateTransferRequest,
+        UpdateTransferAmountRequest,
+        CreateWithdrawRequest,
+        UpdateWithdrawRequest,
+        CreateWebhookRequest,
+        TokenPair,
+        MeResponse,
+        LoginAuditResponse,
+        CapabilitiesResponse,
+        UserResponse,
+        UserSummaryResponse,
+        CounterpartyResponse,
+        SaldoResponse,
+        SaldoStatsResponse,
+        SaldoHistoryResponse,
+        TopupResponse,
+        BulkTopupRow,
+        BulkTopupResult,
+        BulkTopupStatus,
+        TopupMonthlyStatsResponse,
+        TransferResponse,
+        TransferReceiptResponse,
+        NetFlowResponse,
+        NetFlowPeriod,
+        TransferStatus,
+        WithdrawResponse,
+        WithdrawStatus,
+        HistoryEntryResponse,
+        MigrationStatusResponse,
+        AppliedMigrationResponse,
+        WebhookResponse
+    )),
     modifiers(&SecurityAddon),
     tags(
+        (name = "Admin", description = "Operational endpoints for administrators"),
         (name = "Auth", description = "Authentication endpoints"),
+        (name = "Health", description = "Liveness and readiness probes"),
+        (name = "History", description = "Transaction history endpoints"),
+        (name = "Reconciliation", description = "Balance versus ledger consistency checks"),
         (name = "User", description = "User management endpoints"),
         (name = "Saldo", description = "Balance management endpoints"),
         (name = "Topup", description = "Top up endpoints"),
         (name = "Transfer", description = "Transfer endpoints"),
-        (name = "Withdraw", description = "Withdrawal endpoints")
+        (name = "Withdraw", description = "Withdrawal endpoints"),
+        (name = "Webhook", description = "Transaction event notifications")
     )
 )]
-struct ApiDoc;
+pub struct ApiDoc;
 
 struct SecurityAddon;
 
 impl Modify for SecurityAddon {
     fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
-        let components = openapi.components.as_mut().unwrap();
+        let components = openapi.components.get_or_insert_with(Default::default);
 
         components.add_security_scheme(
             "bearer_auth",
@@ -84,35 +255,123 @@ impl Modify for SecurityAddon {
                 utoipa::openapi::security::HttpAuthScheme::Bearer,
             )),
         );
+
+        // Partner backends send `X-Api-Key` together with `X-Timestamp`,
+        // `X-Nonce` and an HMAC `X-Signature`; see `utils::sign_request`.
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                "X-Api-Key",
+                "Partner API key; requests must also carry X-Timestamp, X-Nonce and X-Signature",
+            ))),
+        );
     }
 }
 
 pub struct AppRouter;
 
 impl AppRouter {
-    pub async fn serve(port: u16, app_state: AppState) -> Result<()> {
-        let shared_state = Arc::new(app_state);
-
+    // The full HTTP app without listeners or background tasks, so tests can
+    // drive it directly. `/metrics` is only mounted when `with_metrics` is set.
+    pub fn build(shared_state: Arc<AppState>, with_metrics: bool) -> Router {
         let (router, api) = OpenApiRouter::with_openapi(ApiDoc::openapi())
+            .merge(health_routes(shared_state.clone()))
+            .merge(admin_routes(shared_state.clone()))
             .merge(auth_routes(shared_state.clone()))
             .merge(users_routes(shared_state.clone()))
+            .merge(history_routes(shared_state.clone()))
+            .merge(reconciliation_routes(shared_state.clone()))
             .merge(saldos_routes(shared_state.clone()))
             .merge(topup_routes(shared_state.clone()))
             .merge(transfers_routes(shared_state.clone()))
             .merge(withdraw_routes(shared_state.clone()))
+            .merge(webhook_routes(shared_state.clone()))
             .split_for_parts();
 
-        let app =
-            router.merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", api.clone()));
+        let mut app =
+            router.merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", api));
+
+        if with_metrics {
+            app = app.merge(metrics_routes(shared_state.clone()));
+        }
+
+        // Outermost so `jwt::auth` can fall back to API key auth on any route.
+        app.layer(Extension(shared_state.api_key_service.clone()))
+            .layer(middleware::from_fn(localize))
+            .layer(middleware::from_fn(track_metrics))
+            .layer(Extension(shared_state.metrics.clone()))
+            .layer(middleware::from_fn(request_id))
+    }
+
+    pub async fn serve(
+        host: IpAddr,
+        port: u16,
+        metrics_port: Option<u16>,
+        app_state: AppState,
+    ) -> Result<()> {
+        let shared_state = Arc::new(app_state);
+
+        let webhook_worker = shared_state
+            .webhook_worker
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+
+        if let Some(worker) = webhook_worker {
+            tokio::spawn(worker.run());
+        }
+
+        let cleanup_task = shared_state
+            .cleanup_task
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+
+        if let Some(task) = cleanup_task {
+            tokio::spawn(task.run());
+        }
+
+        let transfer_scheduler = shared_state
+            .transfer_scheduler
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+
+        if let Some(scheduler) = transfer_scheduler {
+            tokio::spawn(scheduler.run());
+        }
+
+        if let Some(metrics_port) = metrics_port {
+            let metrics_addr = SocketAddr::new(host, metrics_port);
+            let metrics_listener = TcpListener::bind(metrics_addr).await?;
+            let metrics_app = metrics_routes(shared_state.clone());
+
+            info!(
+                "Metrics available at http://{}/metrics",
+                metrics_listener.local_addr()?
+            );
+
+            tokio::spawn(async move {
+                if let Err(e) = axum::serve(metrics_listener, metrics_app).await {
+                    error!("❌ Metrics server stopped: {e}");
+                }
+            });
+        }
+
+        let app = Self::build(shared_state, metrics_port.is_none());
 
-        let addr = format!("0.0.0.0:{port}");
-        let listener = TcpListener::bind(&addr).await?;
+        let addr = SocketAddr::new(host, port);
+        let listener = TcpListener::bind(addr).await?;
 
         println!("Server running on http://{}", listener.local_addr()?);
         println!("API Documentation available at:");
         println!("- Swagger UI: http://localhost:{port}/swagger-ui");
 
-        axum::serve(listener, app).await?;
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await?;
         Ok(())
     }
 }
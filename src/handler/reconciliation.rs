@@ -0,0 +1,74 @@
+use axum::{Extension, Json, http::StatusCode, middleware, response::IntoResponse, routing::get};
+use serde_json::json;
+use std::sync::Arc;
+use utoipa_axum::router::OpenApiRouter;
+
+use crate::{
+    abstract_trait::DynReconciliationService,
+    domain::response::{ApiResponse, reconciliation::ReconcileResponse},
+    middleware::{jwt, validation::ValidatedPath},
+    state::AppState,
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/reconcile",
+    tag = "Reconciliation",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "Stored balance compared with the ledger, one entry per saldo", body = ApiResponse<Vec<ReconcileResponse>>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn reconcile_user(
+    Extension(service): Extension<DynReconciliationService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.reconcile_user(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/reconcile",
+    tag = "Reconciliation",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "Saldos whose stored balance differs from the ledger", body = ApiResponse<Vec<ReconcileResponse>>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn find_mismatches(
+    Extension(service): Extension<DynReconciliationService>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.find_mismatches().await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+pub fn reconciliation_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    OpenApiRouter::new()
+        .route("/api/users/reconcile", get(find_mismatches))
+        .route("/api/users/{id}/reconcile", get(reconcile_user))
+        .route_layer(middleware::from_fn(jwt::require_admin))
+        .route_layer(middleware::from_fn(jwt::auth))
+        .layer(Extension(
+            app_state.di_container.reconciliation_service.clone(),
+        ))
+        .layer(Extension(app_state.jwt_service.clone()))
+}
@@ -1,6 +1,6 @@
 use axum::{
     Json,
-    extract::{Extension, Path, Query},
+    extract::{Extension, Query},
     http::StatusCode,
     middleware,
     response::IntoResponse,
@@ -13,10 +13,24 @@ use utoipa_axum::router::OpenApiRouter;
 use crate::{
     abstract_trait::DynSaldoService,
     domain::{
-        request::{CreateSaldoRequest, FindAllSaldoRequest, UpdateSaldoRequest},
-        response::{ApiResponse, ApiResponsePagination, saldo::SaldoResponse},
+        request::{
+            CreateSaldoRequest, FieldsQuery, FindAllSaldoRequest, FindSaldoHistoryRequest,
+            FindUserSaldosRequest, FreezeSaldoRequest, UpdateSaldoRequest,
+        },
+        response::{
+            ApiResponse, ApiResponsePagination,
+            saldo::{SaldoResponse, SaldoStatsResponse},
+            saldo_history::SaldoHistoryResponse,
+        },
+        role::Role,
+    },
+    middleware::{
+        amounts::format_amounts,
+        fields::sparse_fieldsets,
+        jwt,
+        load_shed::shed_load,
+        validation::{SimpleValidatedJson, ValidatedPath},
     },
-    middleware::{jwt, validation::SimpleValidatedJson},
     state::AppState,
 };
 
@@ -27,10 +41,12 @@ use crate::{
     security(
         ("bearer_auth" = [])
     ),
-    params(FindAllSaldoRequest),
+    params(FindAllSaldoRequest, FieldsQuery),
     responses(
         (status = 200, description = "List of saldo records", body = ApiResponsePagination<Vec<SaldoResponse>>),
+        (status = 422, description = "Invalid sort_by column", body = String),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
@@ -40,7 +56,30 @@ pub async fn get_saldos(
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     match service.get_saldos(&params).await {
         Ok(saldoes) => Ok((StatusCode::OK, Json(json!(saldoes)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/saldos/stats",
+    tag = "Saldo",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "Balance totals, average, median and negative count per currency", body = ApiResponse<Vec<SaldoStatsResponse>>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn get_saldo_stats(
+    Extension(service): Extension<DynSaldoService>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.get_saldo_stats().await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -52,23 +91,26 @@ pub async fn get_saldos(
         ("bearer_auth" = [])
     ),
     params(
-        ("id" = i32, Path, description = "Saldo ID")
+        ("id" = i32, Path, description = "Saldo ID"),
+        FieldsQuery
     ),
     responses(
         (status = 200, description = "Saldo details retrieved successfully", body = ApiResponse<Option<SaldoResponse>>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Record belongs to another user", body = String),
         (status = 404, description = "Saldo record not found", body = String),
     )
 )]
 pub async fn get_saldo(
-    Path(id): Path<i32>,
+    ValidatedPath(id): ValidatedPath<i32>,
     Extension(service): Extension<DynSaldoService>,
-    Extension(_user_id): Extension<i64>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.get_saldo(id).await {
+    match service.get_saldo(id, user_id, role).await {
         Ok(saldo) => Ok((StatusCode::OK, Json(json!(saldo)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -80,23 +122,28 @@ pub async fn get_saldo(
         ("bearer_auth" = [])
     ),
     params(
-        ("id" = i32, Path, description = "User ID")
+        ("id" = i32, Path, description = "User ID"),
+        FindUserSaldosRequest,
+        FieldsQuery
     ),
     responses(
-        (status = 200, description = "Saldo details retrieved successfully", body = ApiResponse<Option<Vec<SaldoResponse>>>),
+        (status = 200, description = "The user's saldos, one per currency", body = ApiResponsePagination<Vec<SaldoResponse>>),
         (status = 401, description = "Unauthorized access", body = String),
-        (status = 404, description = "Saldo records not found for the user", body = String),
+        (status = 403, description = "Saldos of another user", body = String),
+        (status = 404, description = "User not found", body = String),
     )
 )]
 pub async fn get_saldo_users(
-    Path(id): Path<i32>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Query(params): Query<FindUserSaldosRequest>,
     Extension(service): Extension<DynSaldoService>,
-    Extension(_user_id): Extension<i64>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.get_saldo_users(id).await {
+    match service.get_saldo_users(id, &params, user_id, role).await {
         Ok(saldo) => Ok((StatusCode::OK, Json(json!(saldo)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -108,23 +155,26 @@ pub async fn get_saldo_users(
         ("bearer_auth" = [])
     ),
     params(
-        ("id" = i32, Path, description = "User ID")
+        ("id" = i32, Path, description = "User ID"),
+        FieldsQuery
     ),
     responses(
         (status = 200, description = "Saldo details retrieved successfully", body = ApiResponse<Option<SaldoResponse>>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Saldo of another user", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn get_saldo_user(
-    Path(id): Path<i32>,
+    ValidatedPath(id): ValidatedPath<i32>,
     Extension(service): Extension<DynSaldoService>,
-    Extension(_user_id): Extension<i64>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.get_saldo_user(id).await {
+    match service.get_saldo_user(id, user_id, role).await {
         Ok(saldo) => Ok((StatusCode::OK, Json(json!(saldo)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -139,6 +189,8 @@ pub async fn get_saldo_user(
     responses(
         (status = 201, description = "Saldo record created successfully", body = ApiResponse<SaldoResponse>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 409, description = "User already has a saldo in this currency", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
@@ -148,7 +200,7 @@ pub async fn create_saldo(
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     match service.create_saldo(&body).await {
         Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -165,21 +217,26 @@ pub async fn create_saldo(
     request_body = UpdateSaldoRequest,
     responses(
         (status = 200, description = "Saldo record updated successfully", body = ApiResponse<SaldoResponse>),
+        (status = 400, description = "Balance would drop below the minimum or the account is frozen", body = String),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Saldo belongs to another user", body = String),
+        (status = 404, description = "Saldo not found", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn update_saldo(
-    Path(id): Path<i32>,
+    ValidatedPath(id): ValidatedPath<i32>,
     Extension(service): Extension<DynSaldoService>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
     SimpleValidatedJson(mut body): SimpleValidatedJson<UpdateSaldoRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     body.saldo_id = id;
 
-    match service.update_saldo(&body).await {
+    match service.update_saldo(&body, user_id, role).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -196,15 +253,18 @@ pub async fn update_saldo(
     responses(
         (status = 200, description = "Saldo record deleted successfully", body = serde_json::Value),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 404, description = "Saldo not found", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn delete_saldo(
-    Path(id): Path<i32>,
+    ValidatedPath(id): ValidatedPath<i32>,
     Extension(service): Extension<DynSaldoService>,
-    Extension(_user_id): Extension<i64>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.delete_saldo(id).await {
+    match service.delete_saldo(id, user_id, role).await {
         Ok(_) => Ok((
             StatusCode::OK,
             Json(json!({
@@ -212,20 +272,181 @@ pub async fn delete_saldo(
                 "message": "Saldo deleted successfully"
             })),
         )),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/saldos/{id}/restore",
+    tag = "Saldo",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "Saldo ID")
+    ),
+    responses(
+        (status = 200, description = "Saldo record restored successfully", body = ApiResponse<SaldoResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 404, description = "No deleted saldo with this ID", body = String),
+    )
+)]
+pub async fn restore_saldo(
+    Extension(service): Extension<DynSaldoService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.restore_saldo(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/saldos/user/{id}/history",
+    tag = "Saldo",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID"),
+        FindSaldoHistoryRequest
+    ),
+    responses(
+        (status = 200, description = "Balance changes, newest first", body = ApiResponsePagination<Vec<SaldoHistoryResponse>>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Saldo belongs to another user", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn get_saldo_history(
+    Extension(service): Extension<DynSaldoService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Query(params): Query<FindSaldoHistoryRequest>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.get_saldo_history(id, &params, user_id, role).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/saldos/user/{id}/freeze",
+    tag = "Saldo",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    request_body = FreezeSaldoRequest,
+    responses(
+        (status = 200, description = "Every saldo of the user frozen", body = ApiResponse<Vec<SaldoResponse>>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 404, description = "User has no saldo", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn freeze_saldo(
+    Extension(service): Extension<DynSaldoService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    SimpleValidatedJson(body): SimpleValidatedJson<FreezeSaldoRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.freeze_saldo(id, &body).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/saldos/user/{id}/unfreeze",
+    tag = "Saldo",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "Every saldo of the user unfrozen", body = ApiResponse<Vec<SaldoResponse>>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 404, description = "User has no saldo", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn unfreeze_saldo(
+    Extension(service): Extension<DynSaldoService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.unfreeze_saldo(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
 pub fn saldos_routes(app_state: Arc<AppState>) -> OpenApiRouter {
-    OpenApiRouter::new()
-        .route("/api/saldos", get(get_saldos))
-        .route("/api/saldos/{id}", get(get_saldo))
-        .route("/api/saldos/users/{id}", get(get_saldo_users))
-        .route("/api/saldos/user/{id}", get(get_saldo_user))
-        .route("/api/saldos", post(create_saldo))
+    let writes = OpenApiRouter::new()
+        .route(
+            "/api/saldos/user/{id}/freeze",
+            post(freeze_saldo).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route(
+            "/api/saldos/user/{id}/unfreeze",
+            post(unfreeze_saldo).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route(
+            "/api/saldos",
+            post(create_saldo).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
         .route("/api/saldos/{id}", put(update_saldo))
-        .route("/api/saldos/{id}", delete(delete_saldo))
+        .route(
+            "/api/saldos/{id}",
+            delete(delete_saldo).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route(
+            "/api/saldos/{id}/restore",
+            post(restore_saldo).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route_layer(middleware::from_fn(jwt::auth))
+        .route_layer(middleware::from_fn(shed_load));
+
+    OpenApiRouter::new()
+        .route(
+            "/api/saldos",
+            get(get_saldos)
+                .route_layer(middleware::from_fn(jwt::require_admin))
+                .layer(middleware::from_fn(sparse_fieldsets::<SaldoResponse>)),
+        )
+        .route(
+            "/api/saldos/stats",
+            get(get_saldo_stats).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route(
+            "/api/saldos/{id}",
+            get(get_saldo).layer(middleware::from_fn(sparse_fieldsets::<SaldoResponse>)),
+        )
+        .route(
+            "/api/saldos/users/{id}",
+            get(get_saldo_users).layer(middleware::from_fn(sparse_fieldsets::<SaldoResponse>)),
+        )
+        .route(
+            "/api/saldos/user/{id}",
+            get(get_saldo_user).layer(middleware::from_fn(sparse_fieldsets::<SaldoResponse>)),
+        )
+        .route("/api/saldos/user/{id}/history", get(get_saldo_history))
         .route_layer(middleware::from_fn(jwt::auth))
+        .merge(writes)
+        .layer(middleware::from_fn(format_amounts::<SaldoResponse>))
         .layer(Extension(app_state.di_container.saldo_service.clone()))
         .layer(Extension(app_state.jwt_service.clone()))
+        .layer(Extension(app_state.load_shedder.clone()))
 }
@@ -1,7 +1,8 @@
 use axum::{
     Json,
-    extract::{Extension, Path, Query},
-    http::StatusCode,
+    body::Bytes,
+    extract::{Extension, Query},
+    http::{HeaderMap, StatusCode, header},
     middleware,
     response::IntoResponse,
     routing::{delete, get, post, put},
@@ -13,11 +14,24 @@ use utoipa_axum::router::OpenApiRouter;
 use crate::{
     abstract_trait::DynTopupService,
     domain::{
-        request::{CreateTopupRequest, FindAllTopupRequest, UpdateTopupRequest},
-        response::{ApiResponse, ApiResponsePagination, topup::TopupResponse},
+        request::{
+            BulkTopupRow, CreateBulkTopupRequest, CreateTopupRequest, FindAllTopupRequest,
+            TopupMonthlyStatsRequest, UpdateTopupRequest,
+        },
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            topup::{BulkTopupResult, TopupMonthlyStatsResponse, TopupResponse},
+        },
+        role::Role,
+    },
+    middleware::{
+        amounts::format_amounts,
+        jwt,
+        load_shed::shed_load,
+        validation::{SimpleValidatedJson, ValidatedPath},
     },
-    middleware::{jwt, validation::SimpleValidatedJson},
     state::AppState,
+    utils::AppError,
 };
 
 #[utoipa::path(
@@ -30,7 +44,10 @@ use crate::{
     params(FindAllTopupRequest),
     responses(
         (status = 200, description = "List of topup records", body = ApiResponsePagination<Vec<TopupResponse>>),
+        (status = 422, description = "Invalid sort_by column", body = String),
+        (status = 422, description = "min_amount > max_amount or from_date > to_date", body = String),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
@@ -40,7 +57,7 @@ pub async fn get_topups(
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     match service.get_topups(&params).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -57,18 +74,20 @@ pub async fn get_topups(
     responses(
         (status = 200, description = "Topup details retrieved successfully", body = ApiResponse<Option<TopupResponse>>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Record belongs to another user", body = String),
         (status = 404, description = "Topup record not found", body = String),
     )
 )]
 pub async fn get_topup(
-    Path(id): Path<i32>,
+    ValidatedPath(id): ValidatedPath<i32>,
     Extension(service): Extension<DynTopupService>,
-    Extension(_user_id): Extension<i64>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.get_topup(id).await {
+    match service.get_topup(id, user_id, role).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -85,18 +104,20 @@ pub async fn get_topup(
     responses(
         (status = 200, description = "Topup details retrieved successfully", body = ApiResponse<Option<Vec<TopupResponse>>>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Topups of another user", body = String),
         (status = 404, description = "Topup records not found for the user", body = String),
     )
 )]
 pub async fn get_topup_users(
-    Path(id): Path<i32>,
+    ValidatedPath(id): ValidatedPath<i32>,
     Extension(service): Extension<DynTopupService>,
-    Extension(_user_id): Extension<i64>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.get_topup_users(id).await {
+    match service.get_topup_users(id, user_id, role).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -113,18 +134,20 @@ pub async fn get_topup_users(
     responses(
         (status = 200, description = "Topup details retrieved successfully", body = ApiResponse<Option<TopupResponse>>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Topup of another user", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn get_topup_user(
-    Path(id): Path<i32>,
+    ValidatedPath(id): ValidatedPath<i32>,
     Extension(service): Extension<DynTopupService>,
-    Extension(_user_id): Extension<i64>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.get_topup_user(id).await {
+    match service.get_topup_user(id, user_id, role).await {
         Ok(saldo) => Ok((StatusCode::OK, Json(json!(saldo)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -139,16 +162,21 @@ pub async fn get_topup_user(
     responses(
         (status = 201, description = "Topup record created successfully", body = ApiResponse<TopupResponse>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 400, description = "Business rule violated", body = String),
+        (status = 403, description = "Topup for another user", body = String),
+        (status = 404, description = "User or saldo not found", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn create_topup(
     Extension(service): Extension<DynTopupService>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
     SimpleValidatedJson(body): SimpleValidatedJson<CreateTopupRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.create_topup(&body).await {
+    match service.create_topup(&body, user_id, role).await {
         Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -166,20 +194,25 @@ pub async fn create_topup(
     responses(
         (status = 200, description = "Topup record updated successfully", body = ApiResponse<TopupResponse>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 400, description = "Business rule violated, e.g. the decrease was already spent or the account is frozen", body = String),
+        (status = 403, description = "Topup belongs to another user", body = String),
+        (status = 404, description = "Topup or saldo not found", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn update_topup(
-    Path(id): Path<i32>,
+    ValidatedPath(id): ValidatedPath<i32>,
     Extension(service): Extension<DynTopupService>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
     SimpleValidatedJson(mut body): SimpleValidatedJson<UpdateTopupRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     body.topup_id = id;
 
-    match service.update_topup(&body).await {
+    match service.update_topup(&body, user_id, role).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -196,15 +229,18 @@ pub async fn update_topup(
     responses(
         (status = 200, description = "Topup record deleted successfully", body = serde_json::Value),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 404, description = "Topup not found", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn delete_topup(
-    Path(id): Path<i32>,
+    ValidatedPath(id): ValidatedPath<i32>,
     Extension(service): Extension<DynTopupService>,
-    Extension(_user_id): Extension<i64>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.delete_topup(id).await {
+    match service.delete_topup(id, user_id, role).await {
         Ok(_) => Ok((
             StatusCode::OK,
             Json(json!({
@@ -212,20 +248,144 @@ pub async fn delete_topup(
                 "message": "Topup deleted successfully"
             })),
         )),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/topups/{id}/restore",
+    tag = "Topup",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "Topup ID")
+    ),
+    responses(
+        (status = 200, description = "Topup record restored successfully", body = ApiResponse<TopupResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 404, description = "No deleted topup with this ID", body = String),
+    )
+)]
+pub async fn restore_topup(
+    Extension(service): Extension<DynTopupService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.restore_topup(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/topups/bulk",
+    tag = "Topup",
+    security(
+        ("bearer_auth" = [])
+    ),
+    request_body(
+        content = Vec<BulkTopupRow>,
+        description = "JSON array of rows, or text/csv with a user_id,amount[,currency] header",
+    ),
+    responses(
+        (status = 200, description = "Per-row outcome of the import", body = ApiResponse<Vec<BulkTopupResult>>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 422, description = "Malformed body, too many rows, or a row with a non-positive amount; nothing was imported", body = String),
+    )
+)]
+pub async fn create_bulk_topup(
+    Extension(service): Extension<DynTopupService>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let is_csv = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/csv"));
+
+    let parsed = if is_csv {
+        std::str::from_utf8(&body)
+            .map_err(|_| AppError::validation("CSV body must be valid UTF-8"))
+            .and_then(CreateBulkTopupRequest::from_csv)
+    } else {
+        serde_json::from_slice::<CreateBulkTopupRequest>(&body)
+            .map_err(|e| AppError::validation(format!("Invalid JSON body: {e}")))
+    };
+
+    let input = parsed.map_err(|e| {
+        let e = ErrorResponse::from(e);
+        (e.code, Json(json!(e)))
+    })?;
+
+    match service.create_bulk_topup(&input).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/topups/stats/monthly",
+    tag = "Topup",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(TopupMonthlyStatsRequest),
+    responses(
+        (status = 200, description = "Topup count and volume per month, oldest first, including empty months", body = ApiResponse<Vec<TopupMonthlyStatsResponse>>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Another user's stats, or all users without the admin role", body = String),
+        (status = 422, description = "from is after to, or the range spans too many months", body = String),
+    )
+)]
+pub async fn get_topup_monthly_stats(
+    Extension(service): Extension<DynTopupService>,
+    Query(params): Query<TopupMonthlyStatsRequest>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.get_monthly_stats(&params, user_id, role).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
 pub fn topup_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    let writes = OpenApiRouter::new()
+        .route("/api/topups", post(create_topup))
+        .route(
+            "/api/topups/bulk",
+            post(create_bulk_topup).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route("/api/topups/{id}", put(update_topup))
+        .route(
+            "/api/topups/{id}",
+            delete(delete_topup).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route(
+            "/api/topups/{id}/restore",
+            post(restore_topup).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route_layer(middleware::from_fn(jwt::auth))
+        .route_layer(middleware::from_fn(shed_load));
+
     OpenApiRouter::new()
-        .route("/api/topups", get(get_topups))
+        .route(
+            "/api/topups",
+            get(get_topups).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route("/api/topups/stats/monthly", get(get_topup_monthly_stats))
         .route("/api/topups/{id}", get(get_topup))
         .route("/api/topups/users/{id}", get(get_topup_users))
         .route("/api/topups/user/{id}", get(get_topup_user))
-        .route("/api/topups", post(create_topup))
-        .route("/api/topups/{id}", put(update_topup))
-        .route("/api/topups/{id}", delete(delete_topup))
         .route_layer(middleware::from_fn(jwt::auth))
+        .merge(writes)
+        .layer(middleware::from_fn(format_amounts::<TopupResponse>))
         .layer(Extension(app_state.di_container.topup_service.clone()))
         .layer(Extension(app_state.jwt_service.clone()))
+        .layer(Extension(app_state.load_shedder.clone()))
 }
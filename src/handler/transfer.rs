@@ -1,6 +1,6 @@
 use axum::{
     Json,
-    extract::{Extension, Path, Query},
+    extract::{Extension, Query},
     http::StatusCode,
     middleware,
     response::IntoResponse,
@@ -13,10 +13,23 @@ use utoipa_axum::router::OpenApiRouter;
 use crate::{
     abstract_trait::DynTransferService,
     domain::{
-        request::{CreateTransferRequest, FindAllTransferRequest, UpdateTransferRequest},
-        response::{ApiResponse, ApiResponsePagination, transfer::TransferResponse},
+        request::{
+            CreateBatchTransferRequest, CreateTransferRequest, FindAllTransferRequest,
+            FindTransfersBetweenRequest, FindUserTransfersRequest, TransferAllRequest,
+            TransferNetFlowRequest, UpdateTransferRequest,
+        },
+        response::{
+            ApiResponse, ApiResponsePagination,
+            transfer::{NetFlowResponse, TransferReceiptResponse, TransferResponse},
+        },
+        role::Role,
+    },
+    middleware::{
+        amounts::format_amounts,
+        jwt,
+        load_shed::shed_load,
+        validation::{SimpleValidatedJson, ValidatedPath},
     },
-    middleware::{jwt, validation::SimpleValidatedJson},
     state::AppState,
 };
 
@@ -30,7 +43,10 @@ use crate::{
     params(FindAllTransferRequest),
     responses(
         (status = 200, description = "List of transfer records", body = ApiResponsePagination<Vec<TransferResponse>>),
+        (status = 422, description = "Invalid sort_by column", body = String),
+        (status = 422, description = "min_amount > max_amount or from_date > to_date", body = String),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
@@ -40,7 +56,7 @@ pub async fn get_transfers(
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     match service.get_transfers(&params).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -57,18 +73,100 @@ pub async fn get_transfers(
     responses(
         (status = 200, description = "Transfer details retrieved successfully", body = ApiResponse<Option<TransferResponse>>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Record belongs to another user", body = String),
         (status = 404, description = "Transfer record not found", body = String),
     )
 )]
 pub async fn get_transfer(
     Extension(service): Extension<DynTransferService>,
-    Path(id): Path<i32>,
-    Extension(_user_id): Extension<i64>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.get_transfer(id).await {
+    match service.get_transfer(id, user_id, role).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/transfers/between",
+    tag = "Transfer",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(FindTransfersBetweenRequest),
+    responses(
+        (status = 200, description = "Transfers sent from one user to another", body = ApiResponsePagination<Vec<TransferResponse>>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Neither user is the caller", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn get_transfers_between(
+    Extension(service): Extension<DynTransferService>,
+    Query(params): Query<FindTransfersBetweenRequest>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.get_transfers_between(&params, user_id, role).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/transfers/scheduled",
+    tag = "Transfer",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "Pending scheduled transfers, soonest first; admins see everyone's", body = ApiResponse<Vec<TransferResponse>>),
+        (status = 401, description = "Unauthorized access", body = String),
+    )
+)]
+pub async fn get_scheduled_transfers(
+    Extension(service): Extension<DynTransferService>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.get_scheduled_transfers(user_id, role).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/transfers/{id}/receipt",
+    tag = "Transfer",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "Transfer ID")
+    ),
+    responses(
+        (status = 200, description = "Receipt with both parties' names", body = ApiResponse<TransferReceiptResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Record belongs to another user", body = String),
+        (status = 404, description = "Transfer record not found", body = String),
+    )
+)]
+pub async fn get_transfer_receipt(
+    Extension(service): Extension<DynTransferService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.get_transfer_receipt(id, user_id, role).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -80,23 +178,27 @@ pub async fn get_transfer(
         ("bearer_auth" = [])
     ),
     params(
-        ("id" = i32, Path, description = "User ID")
+        ("id" = i32, Path, description = "User ID"),
+        FindUserTransfersRequest
     ),
     responses(
-        (status = 200, description = "Transfer details retrieved successfully", body = ApiResponse<Option<Vec<TransferResponse>>>),
+        (status = 200, description = "Page of the user's transfers, newest first", body = ApiResponsePagination<Vec<TransferResponse>>),
         (status = 401, description = "Unauthorized access", body = String),
-        (status = 404, description = "Transfer records not found for the user", body = String),
+        (status = 403, description = "Transfers of another user", body = String),
+        (status = 404, description = "User not found", body = String),
     )
 )]
 pub async fn get_transfer_users(
     Extension(service): Extension<DynTransferService>,
-    Path(id): Path<i32>,
-    Extension(_user_id): Extension<i64>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Query(params): Query<FindUserTransfersRequest>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.get_transfer_users(id).await {
+    match service.get_transfer_users(id, &params, user_id, role).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -113,18 +215,53 @@ pub async fn get_transfer_users(
     responses(
         (status = 200, description = "Transfer details retrieved successfully", body = ApiResponse<Option<TransferResponse>>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Transfer of another user", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn get_transfer_user(
     Extension(service): Extension<DynTransferService>,
-    Path(id): Path<i32>,
-    Extension(_user_id): Extension<i64>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.get_transfer_user(id).await {
+    match service.get_transfer_user(id, user_id, role).await {
         Ok(saldo) => Ok((StatusCode::OK, Json(json!(saldo)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/transfers/user/{id}/net",
+    tag = "Transfer",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID"),
+        TransferNetFlowRequest
+    ),
+    responses(
+        (status = 200, description = "Completed transfers in and out of the user's saldo, and the difference", body = ApiResponse<NetFlowResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Net flow of another user", body = String),
+        (status = 404, description = "User or counterparty not found", body = String),
+        (status = 422, description = "from > to", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn get_transfer_net_flow(
+    Extension(service): Extension<DynTransferService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Query(params): Query<TransferNetFlowRequest>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.get_net_flow(id, &params, user_id, role).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -137,18 +274,88 @@ pub async fn get_transfer_user(
     ),
     request_body = CreateTransferRequest,
     responses(
-        (status = 201, description = "Transfer record created successfully", body = ApiResponse<TransferResponse>),
+        (status = 201, description = "Transfer created, or scheduled as pending when scheduled_at is in the future", body = ApiResponse<TransferResponse>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Transfer from another user's saldo", body = String),
+        (status = 400, description = "Insufficient balance or currency mismatch", body = String),
+        (status = 404, description = "Sender or receiver (by id or noc_transfer) not found", body = String),
+        (status = 422, description = "Neither or both of transfer_to and transfer_to_noc given", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn create_transfer(
     Extension(service): Extension<DynTransferService>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
     SimpleValidatedJson(body): SimpleValidatedJson<CreateTransferRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.create_transfer(&body).await {
+    match service.create_transfer(&body, user_id, role).await {
         Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/transfers/batch",
+    tag = "Transfer",
+    security(
+        ("bearer_auth" = [])
+    ),
+    request_body = CreateBatchTransferRequest,
+    responses(
+        (status = 201, description = "Batch transfer created successfully", body = ApiResponse<Vec<TransferResponse>>),
+        (status = 400, description = "Invalid batch request", body = String),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Batch sent from another user's saldo", body = String),
+        (status = 404, description = "A user in the batch was not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn create_batch_transfer(
+    Extension(service): Extension<DynTransferService>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
+    SimpleValidatedJson(body): SimpleValidatedJson<CreateBatchTransferRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.create_batch_transfer(&body, user_id, role).await {
+        Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/saldos/transfer-all",
+    tag = "Transfer",
+    security(
+        ("bearer_auth" = [])
+    ),
+    request_body = TransferAllRequest,
+    responses(
+        (status = 201, description = "The caller's whole balance, less the minimum balance and the fee, was transferred", body = ApiResponse<Option<TransferResponse>>),
+        (status = 200, description = "Nothing to transfer; data is null", body = ApiResponse<Option<TransferResponse>>),
+        (status = 400, description = "Self-transfer, frozen saldo or no saldo in that currency", body = String),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 404, description = "Receiver not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn transfer_all(
+    Extension(service): Extension<DynTransferService>,
+    Extension(user_id): Extension<i32>,
+    SimpleValidatedJson(body): SimpleValidatedJson<TransferAllRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.transfer_all(user_id, &body).await {
+        Ok(response) => {
+            let status = if response.data.is_some() {
+                StatusCode::CREATED
+            } else {
+                StatusCode::OK
+            };
+            Ok((status, Json(json!(response))))
+        }
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -166,20 +373,26 @@ pub async fn create_transfer(
     responses(
         (status = 200, description = "Transfer record updated successfully", body = ApiResponse<TransferResponse>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Transfer belongs to another user", body = String),
+        (status = 400, description = "Insufficient balance, minimum balance not kept, or account frozen", body = String),
+        (status = 404, description = "Transfer or saldo not found", body = String),
+        (status = 409, description = "Transfer is not completed or is a reversal", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn update_transfer(
     Extension(service): Extension<DynTransferService>,
-    Path(id): Path<i32>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
     SimpleValidatedJson(mut body): SimpleValidatedJson<UpdateTransferRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     body.transfer_id = id;
 
-    match service.update_transfer(&body).await {
+    match service.update_transfer(&body, user_id, role).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -194,38 +407,122 @@ pub async fn update_transfer(
         ("id" = i32, Path, description = "Transfer ID")
     ),
     responses(
-        (status = 200, description = "Transfer record deleted successfully", body = serde_json::Value),
+        (status = 200, description = "Transfer deleted and both balances reversed, fee refunded to the sender, or pending transfer cancelled", body = serde_json::Value),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Transfer belongs to another user", body = String),
+        (status = 404, description = "Transfer not found", body = String),
+        (status = 409, description = "Only admins may delete transfers that already ran", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn delete_transfer(
     Extension(service): Extension<DynTransferService>,
-    Path(id): Path<i32>,
-    Extension(_user_id): Extension<i64>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.delete_transfer(id).await {
-        Ok(_) => Ok((
+    match service.delete_transfer(id, user_id, role).await {
+        Ok(response) => Ok((
             StatusCode::OK,
             Json(json!({
                 "status": "success",
-                "message": "Transfer deleted successfully"
+                "message": response.message
             })),
         )),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/transfers/{id}/restore",
+    tag = "Transfer",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "Transfer ID")
+    ),
+    responses(
+        (status = 200, description = "Transfer restored; a completed one moves the amount and charges the fee again", body = ApiResponse<TransferResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 404, description = "No deleted transfer with this ID", body = String),
+    )
+)]
+pub async fn restore_transfer(
+    Extension(service): Extension<DynTransferService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.restore_transfer(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/transfers/{id}/reverse",
+    tag = "Transfer",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "Transfer ID")
+    ),
+    responses(
+        (status = 201, description = "Compensating transfer from the receiver back to the sender", body = ApiResponse<TransferResponse>),
+        (status = 400, description = "Receiver no longer holds the amount", body = String),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 404, description = "Transfer not found", body = String),
+        (status = 409, description = "Transfer is not completed, already reversed, or itself a reversal", body = String),
+    )
+)]
+pub async fn reverse_transfer(
+    Extension(service): Extension<DynTransferService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.reverse_transfer(id).await {
+        Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
 pub fn transfers_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    let writes = OpenApiRouter::new()
+        .route("/api/transfers", post(create_transfer))
+        .route("/api/transfers/batch", post(create_batch_transfer))
+        .route("/api/saldos/transfer-all", post(transfer_all))
+        .route("/api/transfers/{id}", put(update_transfer))
+        .route("/api/transfers/{id}", delete(delete_transfer))
+        .route(
+            "/api/transfers/{id}/restore",
+            post(restore_transfer).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route(
+            "/api/transfers/{id}/reverse",
+            post(reverse_transfer).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route_layer(middleware::from_fn(jwt::auth))
+        .route_layer(middleware::from_fn(shed_load));
+
     OpenApiRouter::new()
-        .route("/api/transfers", get(get_transfers))
+        .route(
+            "/api/transfers",
+            get(get_transfers).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route("/api/transfers/between", get(get_transfers_between))
+        .route("/api/transfers/scheduled", get(get_scheduled_transfers))
         .route("/api/transfers/{id}", get(get_transfer))
+        .route("/api/transfers/{id}/receipt", get(get_transfer_receipt))
         .route("/api/transfers/users/{id}", get(get_transfer_users))
         .route("/api/transfers/user/{id}", get(get_transfer_user))
-        .route("/api/transfers", post(create_transfer))
-        .route("/api/transfers/{id}", put(update_transfer))
-        .route("/api/transfers/{id}", delete(delete_transfer))
+        .route("/api/transfers/user/{id}/net", get(get_transfer_net_flow))
         .route_layer(middleware::from_fn(jwt::auth))
+        .merge(writes)
+        .layer(middleware::from_fn(format_amounts::<TransferResponse>))
         .layer(Extension(app_state.di_container.transfer_service.clone()))
         .layer(Extension(app_state.jwt_service.clone()))
+        .layer(Extension(app_state.load_shedder.clone()))
 }
@@ -1,6 +1,6 @@
 use axum::{
     Json,
-    extract::{Extension, Path, Query},
+    extract::{Extension, Query},
     http::StatusCode,
     middleware,
     response::IntoResponse,
@@ -13,10 +13,21 @@ use utoipa_axum::router::OpenApiRouter;
 use crate::{
     abstract_trait::DynUserService,
     domain::{
-        request::{FindAllUserRequest, RegisterRequest, UpdateUserRequest},
-        response::{ApiResponse, ApiResponsePagination, user::UserResponse},
+        request::{
+            FieldsQuery, FindAllUserRequest, FindUserCounterpartiesRequest, FindUsersBatchRequest,
+            GetUserQuery, RegisterRequest, UpdateUserRequest, UpdateUserRoleRequest,
+        },
+        response::{
+            ApiResponse, ApiResponsePagination,
+            user::{CounterpartyResponse, UserResponse, UserSummaryResponse},
+        },
+        role::Role,
+    },
+    middleware::{
+        fields::sparse_fieldsets,
+        jwt,
+        validation::{SimpleValidatedJson, ValidatedPath},
     },
-    middleware::{jwt, validation::SimpleValidatedJson},
     state::AppState,
 };
 
@@ -27,10 +38,12 @@ use crate::{
     security(
         ("bearer_auth" = [])
     ),
-    params(FindAllUserRequest),
+    params(FindAllUserRequest, FieldsQuery),
     responses(
         (status = 200, description = "List of user records", body = ApiResponsePagination<Vec<UserResponse>>),
+        (status = 422, description = "Invalid sort_by column", body = String),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
@@ -40,7 +53,7 @@ pub async fn get_users(
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     match service.get_users(&params).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -52,23 +65,145 @@ pub async fn get_users(
         ("bearer_auth" = [])
     ),
     params(
-        ("id" = i32, Path, description = "User ID")
+        ("id" = i32, Path, description = "User ID"),
+        GetUserQuery,
+        FieldsQuery
     ),
     responses(
         (status = 200, description = "User details retrieved successfully", body = ApiResponse<Option<UserResponse>>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "full=true requires the admin role", body = String),
         (status = 404, description = "User not found", body = String),
     )
 )]
 pub async fn get_user(
     Extension(service): Extension<DynUserService>,
-    Path(id): Path<i32>,
-    Extension(_user_id): Extension<i64>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Query(params): Query<GetUserQuery>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.get_user(id).await {
+    match service.get_user(id, &params, role).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/by-noc/{noc}",
+    tag = "User",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("noc" = String, Path, description = "The user's noc_transfer card number"),
+        FieldsQuery
+    ),
+    responses(
+        (status = 200, description = "The user holding this noc_transfer, unmasked", body = ApiResponse<UserResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 404, description = "No user with this noc_transfer", body = String),
+    )
+)]
+pub async fn get_user_by_noc(
+    Extension(service): Extension<DynUserService>,
+    ValidatedPath(noc): ValidatedPath<String>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.get_user_by_noc(&noc).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/batch",
+    tag = "User",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(FieldsQuery),
+    request_body = FindUsersBatchRequest,
+    responses(
+        (status = 200, description = "The users that exist, in the order requested; unknown ids are omitted", body = ApiResponse<Vec<UserResponse>>),
+        (status = 422, description = "No ids or more than 100 ids", body = String),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn get_users_batch(
+    Extension(service): Extension<DynUserService>,
+    SimpleValidatedJson(body): SimpleValidatedJson<FindUsersBatchRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.get_users_batch(&body).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/summary",
+    tag = "User",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "Transaction totals for the user", body = ApiResponse<UserSummaryResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Summary belongs to another user", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn get_user_summary(
+    Extension(service): Extension<DynUserService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.get_user_summary(id, user_id, role).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/counterparties",
+    tag = "User",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID"),
+        FindUserCounterpartiesRequest
+    ),
+    responses(
+        (status = 200, description = "Completed transfers grouped by counterparty, most frequent first", body = ApiResponsePagination<Vec<CounterpartyResponse>>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Counterparties belong to another user", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn get_user_counterparties(
+    Extension(service): Extension<DynUserService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Query(params): Query<FindUserCounterpartiesRequest>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service
+        .get_user_counterparties(id, &params, user_id, role)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -92,7 +227,7 @@ pub async fn create_user(
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     match service.create_user(&body).await {
         Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -110,20 +245,25 @@ pub async fn create_user(
     responses(
         (status = 200, description = "User record updated successfully", body = ApiResponse<UserResponse>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Record belongs to another user", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 409, description = "Email already registered", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn update_user(
     Extension(service): Extension<DynUserService>,
-    Path(id): Path<i32>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
     SimpleValidatedJson(mut body): SimpleValidatedJson<UpdateUserRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     body.id = id;
 
-    match service.update_user(&body).await {
+    match service.update_user(&body, user_id, role).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -140,13 +280,15 @@ pub async fn update_user(
     responses(
         (status = 200, description = "User record deleted successfully", body = serde_json::Value),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 409, description = "User still has a non-zero balance", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn delete_user(
     Extension(service): Extension<DynUserService>,
-    Path(id): Path<i32>,
-    Extension(_user_id): Extension<i64>,
+    ValidatedPath(id): ValidatedPath<i32>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     match service.delete_user(id).await {
         Ok(_) => Ok((
@@ -156,17 +298,134 @@ pub async fn delete_user(
                 "message": "User deleted successfully"
             })),
         )),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/me/noc-transfer/rotate",
+    tag = "User",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "New noc_transfer issued to the current user", body = ApiResponse<UserResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 500, description = "No unique noc_transfer could be generated", body = String),
+    )
+)]
+pub async fn rotate_noc_transfer(
+    Extension(service): Extension<DynUserService>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.rotate_noc_transfer(user_id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/users/{id}/role",
+    tag = "User",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    request_body = UpdateUserRoleRequest,
+    responses(
+        (status = 200, description = "User role updated successfully", body = ApiResponse<UserResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn update_user_role(
+    Extension(service): Extension<DynUserService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    SimpleValidatedJson(body): SimpleValidatedJson<UpdateUserRoleRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.update_user_role(id, &body).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/{id}/restore",
+    tag = "User",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "User record restored successfully", body = ApiResponse<UserResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 404, description = "No deleted user with this ID", body = String),
+    )
+)]
+pub async fn restore_user(
+    Extension(service): Extension<DynUserService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.restore_user(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
 pub fn users_routes(app_state: Arc<AppState>) -> OpenApiRouter {
     OpenApiRouter::new()
-        .route("/api/users", get(get_users))
-        .route("/api/users/{id}", get(get_user))
+        .route(
+            "/api/users",
+            get(get_users)
+                .route_layer(middleware::from_fn(jwt::require_admin))
+                .layer(middleware::from_fn(sparse_fieldsets::<UserResponse>)),
+        )
+        .route(
+            "/api/users/batch",
+            post(get_users_batch).layer(middleware::from_fn(sparse_fieldsets::<UserResponse>)),
+        )
+        .route(
+            "/api/users/{id}",
+            get(get_user).layer(middleware::from_fn(sparse_fieldsets::<UserResponse>)),
+        )
+        .route(
+            "/api/users/by-noc/{noc}",
+            get(get_user_by_noc).layer(middleware::from_fn(sparse_fieldsets::<UserResponse>)),
+        )
+        .route("/api/users/{id}/summary", get(get_user_summary))
+        .route(
+            "/api/users/{id}/counterparties",
+            get(get_user_counterparties),
+        )
         .route("/api/users", post(create_user))
         .route("/api/users/{id}", put(update_user))
-        .route("/api/users/{id}", delete(delete_user))
+        .route(
+            "/api/users/{id}",
+            delete(delete_user).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route(
+            "/api/users/me/noc-transfer/rotate",
+            post(rotate_noc_transfer),
+        )
+        .route(
+            "/api/users/{id}/role",
+            put(update_user_role).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route(
+            "/api/users/{id}/restore",
+            post(restore_user).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
         .route_layer(middleware::from_fn(jwt::auth))
         .layer(Extension(app_state.di_container.user_service.clone()))
         .layer(Extension(app_state.jwt_service.clone()))
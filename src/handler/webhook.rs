@@ -0,0 +1,50 @@
+use axum::{
+    Json, extract::Extension, http::StatusCode, middleware, response::IntoResponse, routing::post,
+};
+use serde_json::json;
+use std::sync::Arc;
+use utoipa_axum::router::OpenApiRouter;
+
+use crate::{
+    abstract_trait::DynWebhookService,
+    domain::{
+        request::CreateWebhookRequest,
+        response::{ApiResponse, webhook::WebhookResponse},
+    },
+    middleware::{jwt, validation::SimpleValidatedJson},
+    state::AppState,
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/webhooks",
+    tag = "Webhook",
+    security(
+        ("bearer_auth" = [])
+    ),
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Webhook registered for the current user", body = ApiResponse<WebhookResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 422, description = "Invalid webhook URL", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn create_webhook(
+    Extension(service): Extension<DynWebhookService>,
+    Extension(user_id): Extension<i32>,
+    SimpleValidatedJson(body): SimpleValidatedJson<CreateWebhookRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.create_webhook(user_id, &body).await {
+        Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+pub fn webhook_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    OpenApiRouter::new()
+        .route("/api/webhooks", post(create_webhook))
+        .route_layer(middleware::from_fn(jwt::auth))
+        .layer(Extension(app_state.di_container.webhook_service.clone()))
+        .layer(Extension(app_state.jwt_service.clone()))
+}
@@ -1,6 +1,6 @@
 use axum::{
     Json,
-    extract::{Extension, Path, Query},
+    extract::{Extension, Query},
     http::StatusCode,
     middleware,
     response::IntoResponse,
@@ -15,8 +15,14 @@ use crate::{
     domain::{
         request::{CreateWithdrawRequest, FindAllWithdrawRequest, UpdateWithdrawRequest},
         response::{ApiResponse, ApiResponsePagination, withdraw::WithdrawResponse},
+        role::Role,
+    },
+    middleware::{
+        amounts::format_amounts,
+        jwt,
+        load_shed::shed_load,
+        validation::{SimpleValidatedJson, ValidatedPath},
     },
-    middleware::{jwt, validation::SimpleValidatedJson},
     state::AppState,
 };
 
@@ -30,7 +36,10 @@ use crate::{
     params(FindAllWithdrawRequest),
     responses(
         (status = 200, description = "List of withdrawals", body = ApiResponsePagination<Vec<WithdrawResponse>>),
+        (status = 422, description = "Invalid sort_by column", body = String),
+        (status = 422, description = "min_amount > max_amount or from_date > to_date", body = String),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
@@ -40,7 +49,7 @@ pub async fn get_withdraws(
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     match service.get_withdraws(&params).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -57,18 +66,20 @@ pub async fn get_withdraws(
     responses(
         (status = 200, description = "Withdrawal details retrieved successfully", body = ApiResponse<Option<WithdrawResponse>>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Record belongs to another user", body = String),
         (status = 404, description = "Withdrawal not found", body = String),
     )
 )]
 pub async fn get_withdraw(
     Extension(service): Extension<DynWithdrawService>,
-    Path(id): Path<i32>,
-    Extension(_user_id): Extension<i64>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.get_withdraw(id).await {
+    match service.get_withdraw(id, user_id, role).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -85,18 +96,20 @@ pub async fn get_withdraw(
     responses(
         (status = 200, description = "List of user withdrawals", body = ApiResponse<Option<Vec<WithdrawResponse>>>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Withdrawals of another user", body = String),
         (status = 404, description = "Withdrawals not found", body = String),
     )
 )]
 pub async fn get_withdraw_users(
     Extension(service): Extension<DynWithdrawService>,
-    Path(id): Path<i32>,
-    Extension(_user_id): Extension<i64>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.get_withdraw_users(id).await {
+    match service.get_withdraw_users(id, user_id, role).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -113,18 +126,20 @@ pub async fn get_withdraw_users(
     responses(
         (status = 200, description = "User withdrawal details", body = ApiResponse<Option<WithdrawResponse>>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Withdrawal of another user", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn get_withdraw_user(
     Extension(service): Extension<DynWithdrawService>,
-    Path(id): Path<i32>,
-    Extension(_user_id): Extension<i64>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.get_withdraw_user(id).await {
+    match service.get_withdraw_user(id, user_id, role).await {
         Ok(saldo) => Ok((StatusCode::OK, Json(json!(saldo)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -137,18 +152,23 @@ pub async fn get_withdraw_user(
     ),
     request_body = CreateWithdrawRequest,
     responses(
-        (status = 201, description = "Withdrawal request created successfully", body = ApiResponse<WithdrawResponse>),
+        (status = 201, description = "Withdrawal completed, or left pending approval when above the threshold", body = ApiResponse<WithdrawResponse>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Withdrawal from another user's saldo", body = String),
+        (status = 400, description = "Insufficient balance or daily limit exceeded", body = String),
+        (status = 404, description = "User or saldo not found", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn create_withdraw(
     Extension(service): Extension<DynWithdrawService>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
     SimpleValidatedJson(body): SimpleValidatedJson<CreateWithdrawRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.create_withdraw(&body).await {
+    match service.create_withdraw(&body, user_id, role).await {
         Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -164,22 +184,28 @@ pub async fn create_withdraw(
     ),
     request_body = UpdateWithdrawRequest,
     responses(
-        (status = 200, description = "Withdrawal record updated successfully", body = ApiResponse<WithdrawResponse>),
+        (status = 200, description = "Withdrawal updated, or returned to pending when raised past the approval threshold", body = ApiResponse<WithdrawResponse>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Withdrawal belongs to another user", body = String),
+        (status = 400, description = "Insufficient balance, minimum balance not kept, or account frozen", body = String),
+        (status = 404, description = "Withdrawal or saldo not found", body = String),
+        (status = 409, description = "Pending or rejected withdrawals cannot be updated", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn update_withdraw(
     Extension(service): Extension<DynWithdrawService>,
-    Path(id): Path<i32>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
     SimpleValidatedJson(mut body): SimpleValidatedJson<UpdateWithdrawRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     body.withdraw_id = id;
 
-    match service.update_withdraw(&body).await {
+    match service.update_withdraw(&body, user_id, role).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -196,15 +222,18 @@ pub async fn update_withdraw(
     responses(
         (status = 200, description = "Withdrawal record deleted successfully", body = serde_json::Value),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 404, description = "Withdrawal not found", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn delete_withdraw(
     Extension(service): Extension<DynWithdrawService>,
-    Path(id): Path<i32>,
-    Extension(_user_id): Extension<i64>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    Extension(user_id): Extension<i32>,
+    Extension(role): Extension<Role>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match service.delete_withdraw(id).await {
+    match service.delete_withdraw(id, user_id, role).await {
         Ok(_) => Ok((
             StatusCode::OK,
             Json(json!({
@@ -212,20 +241,129 @@ pub async fn delete_withdraw(
                 "message": "Withdraw deleted successfully"
             })),
         )),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/withdraws/{id}/restore",
+    tag = "Withdraw",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "Withdraw ID")
+    ),
+    responses(
+        (status = 200, description = "Withdraw record restored successfully", body = ApiResponse<WithdrawResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 404, description = "No deleted withdraw with this ID", body = String),
+    )
+)]
+pub async fn restore_withdraw(
+    Extension(service): Extension<DynWithdrawService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.restore_withdraw(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/withdraws/{id}/approve",
+    tag = "Withdraw",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "Withdraw ID")
+    ),
+    responses(
+        (status = 200, description = "Withdraw approved and balance debited", body = ApiResponse<WithdrawResponse>),
+        (status = 400, description = "Insufficient balance or below minimum balance", body = String),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 404, description = "Withdraw not found", body = String),
+        (status = 409, description = "Withdraw is not pending", body = String),
+    )
+)]
+pub async fn approve_withdraw(
+    Extension(service): Extension<DynWithdrawService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.approve_withdraw(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/withdraws/{id}/reject",
+    tag = "Withdraw",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "Withdraw ID")
+    ),
+    responses(
+        (status = 200, description = "Withdraw rejected; balance untouched", body = ApiResponse<WithdrawResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Admin role required", body = String),
+        (status = 404, description = "Withdraw not found", body = String),
+        (status = 409, description = "Withdraw is not pending", body = String),
+    )
+)]
+pub async fn reject_withdraw(
+    Extension(service): Extension<DynWithdrawService>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match service.reject_withdraw(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
 pub fn withdraw_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    let writes = OpenApiRouter::new()
+        .route("/api/withdraws", post(create_withdraw))
+        .route("/api/withdraws/{id}", put(update_withdraw))
+        .route(
+            "/api/withdraws/{id}",
+            delete(delete_withdraw).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route(
+            "/api/withdraws/{id}/restore",
+            post(restore_withdraw).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route(
+            "/api/withdraws/{id}/approve",
+            post(approve_withdraw).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route(
+            "/api/withdraws/{id}/reject",
+            post(reject_withdraw).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route_layer(middleware::from_fn(jwt::auth))
+        .route_layer(middleware::from_fn(shed_load));
+
     OpenApiRouter::new()
-        .route("/api/withdraws", get(get_withdraws))
-        .route("/api/withdraw_service/{id}", get(get_withdraw))
+        .route(
+            "/api/withdraws",
+            get(get_withdraws).route_layer(middleware::from_fn(jwt::require_admin)),
+        )
+        .route("/api/withdraws/{id}", get(get_withdraw))
         .route("/api/withdraws/users/{id}", get(get_withdraw_users))
         .route("/api/withdraws/user/{id}", get(get_withdraw_user))
-        .route("/api/withdraws", post(create_withdraw))
-        .route("/api/withdraws/{id}", put(update_withdraw))
-        .route("/api/withdraws/{id}", delete(delete_withdraw))
         .route_layer(middleware::from_fn(jwt::auth))
+        .merge(writes)
+        .layer(middleware::from_fn(format_amounts::<WithdrawResponse>))
         .layer(Extension(app_state.di_container.withdraw_service.clone()))
         .layer(Extension(app_state.jwt_service.clone()))
+        .layer(Extension(app_state.load_shedder.clone()))
 }
@@ -9,3 +9,6 @@ pub mod schema;
 pub mod service;
 pub mod state;
 pub mod utils;
+
+#[cfg(test)]
+pub mod test_support;
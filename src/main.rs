@@ -5,26 +5,40 @@ use example_sea_query_payment_gateway::config::{Config, ConnectionManager};
 use example_sea_query_payment_gateway::handler::AppRouter;
 use example_sea_query_payment_gateway::state::AppState;
 use example_sea_query_payment_gateway::utils::tracing;
+use tracing::{info, warn};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenv().ok();
 
-    tracing();
-
     let config = Config::init().context("Failed to load configuration")?;
 
-    let db_pool = ConnectionManager::new_pool(&config.database_url, config.run_migrations)
-        .await
-        .expect("Error initializing database connection pool");
+    tracing(config.log_format, config.log_level.as_deref());
+
+    let db_pool = ConnectionManager::new_pool(
+        &config.database_url,
+        config.run_migrations,
+        config.pool_settings(),
+    )
+    .await
+    .expect("Error initializing database connection pool");
 
-    let port = config.port;
+    let state = AppState::new(db_pool, &config);
 
-    let state = AppState::new(db_pool, &config.jwt_secret);
+    if config.seed_admin
+        && let Some(email) = &config.admin_email
+        && let Err(e) = state
+            .di_container
+            .user_service
+            .seed_admin(email, config.admin_password.as_deref())
+            .await
+    {
+        warn!("⚠️ Failed to seed admin {email}: {}", e.message);
+    }
 
-    println!("🚀 Server started successfully");
+    info!("🚀 Server started successfully");
 
-    AppRouter::serve(port, state)
+    AppRouter::serve(config.host, config.port, config.metrics_port, state)
         .await
         .context("Failed to start server")
 }
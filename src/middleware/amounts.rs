@@ -0,0 +1,168 @@
+use std::sync::RwLock;
+
+use axum::{extract::Request, http::header, middleware::Next, response::Response};
+use serde_json::Value;
+
+use crate::{
+    domain::{
+        money::Money,
+        response::{
+            saldo::SaldoResponse, topup::TopupResponse, transfer::TransferResponse,
+            withdraw::WithdrawResponse,
+        },
+    },
+    middleware::envelope::edit_envelope,
+    utils::Language,
+};
+
+// Set once from `Config` at startup: the locale amounts are formatted in
+// when the request's `Accept-Language` names none we support. `None` leaves
+// such responses with only the raw amounts.
+static DEFAULT_LOCALE: RwLock<Option<Language>> = RwLock::new(None);
+
+pub fn set_default_locale(locale: Option<Language>) {
+    *DEFAULT_LOCALE.write().unwrap_or_else(|e| e.into_inner()) = locale;
+}
+
+fn default_locale() -> Option<Language> {
+    *DEFAULT_LOCALE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+// The amount fields of a response, each paired with the field its formatted
+// string is added as.
+pub trait FormattedAmounts {
+    const AMOUNTS: &'static [(&'static str, &'static str)];
+}
+
+impl FormattedAmounts for SaldoResponse {
+    const AMOUNTS: &'static [(&'static str, &'static str)] =
+        &[("total_balance", "formatted_balance")];
+}
+
+impl FormattedAmounts for TopupResponse {
+    const AMOUNTS: &'static [(&'static str, &'static str)] =
+        &[("topup_amount", "formatted_amount")];
+}
+
+impl FormattedAmounts for TransferResponse {
+    const AMOUNTS: &'static [(&'static str, &'static str)] =
+        &[("transfer_amount", "formatted_amount")];
+}
+
+impl FormattedAmounts for WithdrawResponse {
+    const AMOUNTS: &'static [(&'static str, &'static str)] =
+        &[("withdraw_amount", "formatted_amount")];
+}
+
+// Adds a display string next to each raw amount of `data` (one `T` or a
+// list of them), e.g. `formatted_balance: "1.500.000,00"`, so clients don't
+// each have to format money themselves. The raw integers are left as is.
+pub async fn format_amounts<T: FormattedAmounts>(req: Request, next: Next) -> Response {
+    let language = req
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(Language::preferred)
+        .or_else(default_locale);
+
+    let response = next.run(req).await;
+
+    let Some(language) = language.filter(|_| response.status().is_success()) else {
+        return response;
+    };
+
+    edit_envelope(response, |envelope| {
+        if let Some(data) = envelope.get_mut("data") {
+            add_formatted(data, T::AMOUNTS, language);
+        }
+    })
+    .await
+}
+
+fn add_formatted(data: &mut Value, amounts: &[(&str, &str)], language: Language) {
+    match data {
+        Value::Object(object) => {
+            for (raw, formatted) in amounts {
+                if let Some(amount) = object.get(*raw).and_then(Value::as_i64) {
+                    object.insert(
+                        formatted.to_string(),
+                        Value::from(Money::new(amount).format(language)),
+                    );
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                add_formatted(item, amounts, language);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Json, Router,
+        body::{Body, to_bytes},
+        middleware,
+        routing::get,
+    };
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{
+        domain::{currency::Currency, response::ApiResponse},
+        test_support::saldo,
+    };
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/api/saldos/1",
+                get(|| async {
+                    Json(ApiResponse {
+                        status: "success".to_string(),
+                        message: "Saldo retrieved successfully".to_string(),
+                        data: SaldoResponse::from(saldo(7, Currency::IDR, 150_000_000)),
+                    })
+                }),
+            )
+            .layer(middleware::from_fn(format_amounts::<SaldoResponse>))
+    }
+
+    async fn get_data(accept_language: Option<&str>) -> Value {
+        let mut request = Request::builder().uri("/api/saldos/1");
+        if let Some(language) = accept_language {
+            request = request.header(header::ACCEPT_LANGUAGE, language);
+        }
+
+        let response = app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+
+        body["data"].clone()
+    }
+
+    #[tokio::test]
+    async fn balance_is_formatted_in_the_requested_language() {
+        let indonesian = get_data(Some("id-ID")).await;
+        let english = get_data(Some("en-US")).await;
+
+        assert_eq!(indonesian["formatted_balance"], "1.500.000,00");
+        assert_eq!(english["formatted_balance"], "1,500,000.00");
+        assert_eq!(indonesian["total_balance"], 150_000_000);
+        assert_eq!(english["total_balance"], 150_000_000);
+    }
+
+    #[tokio::test]
+    async fn no_formatted_field_without_a_locale() {
+        let data = get_data(Some("fr")).await;
+
+        assert!(data.get("formatted_balance").is_none(), "{data}");
+        assert_eq!(data["total_balance"], 150_000_000);
+    }
+}
@@ -0,0 +1,74 @@
+use axum::{
+    Json,
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use tracing::info;
+
+use crate::{
+    abstract_trait::{DynApiKeyService, SignedRequest},
+    domain::response::ErrorResponse,
+    utils::AppError,
+};
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+pub const SIGNATURE_HEADER: &str = "x-signature";
+pub const TIMESTAMP_HEADER: &str = "x-timestamp";
+pub const NONCE_HEADER: &str = "x-nonce";
+
+// The whole body has to be buffered to check its signature.
+const MAX_SIGNED_BODY_BYTES: usize = 1024 * 1024;
+
+// Fallback for `jwt::auth` when a request carries `X-Api-Key` instead of a
+// bearer token. On success the request continues as the key's user, with the
+// same `user_id`/`Role` extensions a JWT would have set.
+pub async fn authenticate(
+    api_keys: DynApiKeyService,
+    req: Request<Body>,
+    next: Next,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let (parts, body) = req.into_parts();
+
+    let bytes = to_bytes(body, MAX_SIGNED_BODY_BYTES)
+        .await
+        .map_err(|_| reject(AppError::validation("Signed request body is too large")))?;
+
+    let header = |name: &str| {
+        parts
+            .headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+    };
+
+    let (user_id, role) = api_keys
+        .authenticate(&SignedRequest {
+            api_key: header(API_KEY_HEADER),
+            timestamp: header(TIMESTAMP_HEADER),
+            nonce: header(NONCE_HEADER),
+            signature: header(SIGNATURE_HEADER),
+            method: parts.method.as_str(),
+            path: parts
+                .uri
+                .path_and_query()
+                .map_or(parts.uri.path(), |path| path.as_str()),
+            body: &bytes,
+        })
+        .await
+        .map_err(reject)?;
+
+    info!("🔐 [ApiKey] Authenticated signed request as user_id={user_id}");
+
+    let mut req = Request::from_parts(parts, Body::from(bytes));
+    req.extensions_mut().insert(user_id);
+    req.extensions_mut().insert(role);
+
+    Ok(next.run(req).await)
+}
+
+fn reject(error: AppError) -> (StatusCode, Json<ErrorResponse>) {
+    let e = ErrorResponse::from(error);
+    (e.code, Json(e))
+}
@@ -0,0 +1,46 @@
+use axum::{
+    body::{Body, HttpBody, to_bytes},
+    http::header,
+    response::Response,
+};
+use serde_json::{Map, Value};
+use tracing::warn;
+
+// Lets a middleware edit the JSON envelope (`ApiResponse`,
+// `ApiResponsePagination`, `ErrorResponse`) a handler produced. Anything
+// that isn't one, or is streamed rather than already in memory, passes
+// through untouched.
+pub async fn edit_envelope(
+    response: Response,
+    edit: impl FnOnce(&mut Map<String, Value>),
+) -> Response {
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+    let size = response.body().size_hint().exact();
+
+    let (true, Some(size)) = (is_json, size) else {
+        return response;
+    };
+
+    let (mut parts, body) = response.into_parts();
+
+    let bytes = match to_bytes(body, size as usize).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("✉️ [Envelope] Failed to read response body: {e}");
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+
+    let mut envelope = match serde_json::from_slice::<Value>(&bytes) {
+        Ok(Value::Object(envelope)) if envelope.contains_key("status") => envelope,
+        _ => return Response::from_parts(parts, Body::from(bytes)),
+    };
+
+    edit(&mut envelope);
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(Value::Object(envelope).to_string()))
+}
@@ -0,0 +1,222 @@
+use std::{
+    collections::BTreeSet,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use axum::{
+    extract::{Query, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::Value;
+use utoipa::{
+    PartialSchema,
+    openapi::{RefOr, schema::Schema},
+};
+
+use crate::{
+    domain::{request::FieldsQuery, response::ErrorResponse},
+    middleware::envelope::edit_envelope,
+    utils::AppError,
+};
+
+// Set once from `Config` at startup: whether `?fields=` naming a field the
+// response doesn't have is a 400 rather than silently ignored.
+static STRICT_FIELDS: AtomicBool = AtomicBool::new(false);
+
+pub fn set_strict_fields(strict: bool) {
+    STRICT_FIELDS.store(strict, Ordering::Relaxed);
+}
+
+// Trims `data` (one `T` or a list of them) down to the fields named in
+// `?fields=id,total_balance`, for clients that only need a few of them.
+pub async fn sparse_fieldsets<T: PartialSchema>(req: Request, next: Next) -> Response {
+    let requested = Query::<FieldsQuery>::try_from_uri(req.uri())
+        .ok()
+        .and_then(|Query(query)| query.names());
+
+    let Some(requested) = requested else {
+        return next.run(req).await;
+    };
+
+    if let Err(e) = check_fields(
+        &requested,
+        &schema_fields::<T>(),
+        STRICT_FIELDS.load(Ordering::Relaxed),
+    ) {
+        return ErrorResponse::from(e).into_response();
+    }
+
+    let response = next.run(req).await;
+
+    edit_envelope(response, |envelope| {
+        if let Some(data) = envelope.get_mut("data") {
+            retain_fields(data, &requested);
+        }
+    })
+    .await
+}
+
+// The field names `T` is documented with, which are the ones it serializes.
+fn schema_fields<T: PartialSchema>() -> BTreeSet<String> {
+    match T::schema() {
+        RefOr::T(Schema::Object(object)) => object.properties.into_keys().collect(),
+        _ => BTreeSet::new(),
+    }
+}
+
+fn check_fields(
+    requested: &BTreeSet<String>,
+    known: &BTreeSet<String>,
+    strict: bool,
+) -> Result<(), AppError> {
+    let unknown: Vec<&str> = requested.difference(known).map(String::as_str).collect();
+
+    if strict && !unknown.is_empty() {
+        return Err(AppError::invalid_field(
+            "fields",
+            format!("Unknown fields: {}", unknown.join(", ")),
+        ));
+    }
+
+    Ok(())
+}
+
+fn retain_fields(data: &mut Value, requested: &BTreeSet<String>) {
+    match data {
+        Value::Object(object) => object.retain(|key, _| requested.contains(key)),
+        Value::Array(items) => {
+            for item in items {
+                retain_fields(item, requested);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Json, Router,
+        body::{Body, to_bytes},
+        http::StatusCode,
+        middleware,
+        routing::get,
+    };
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{
+        domain::{
+            currency::Currency,
+            response::{ApiResponse, saldo::SaldoResponse},
+        },
+        test_support::saldo,
+    };
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/api/saldos/1",
+                get(|| async {
+                    Json(ApiResponse {
+                        status: "success".to_string(),
+                        message: "Saldo retrieved successfully".to_string(),
+                        data: SaldoResponse::from(saldo(7, Currency::IDR, 150_000)),
+                    })
+                }),
+            )
+            .route(
+                "/api/saldos",
+                get(|| async {
+                    Json(ApiResponse {
+                        status: "success".to_string(),
+                        message: "Saldos retrieved successfully".to_string(),
+                        data: vec![
+                            SaldoResponse::from(saldo(7, Currency::IDR, 150_000)),
+                            SaldoResponse::from(saldo(8, "USD".parse().unwrap(), 2_000)),
+                        ],
+                    })
+                }),
+            )
+            .layer(middleware::from_fn(sparse_fieldsets::<SaldoResponse>))
+    }
+
+    async fn send(uri: &str) -> (StatusCode, Value) {
+        let response = app()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    fn keys(object: &Value) -> Vec<&str> {
+        object
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn only_requested_fields_are_kept() {
+        let (status, body) = send("/api/saldos/1?fields=id,total_balance").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(keys(&body["data"]), ["id", "total_balance"]);
+        assert_eq!(body["data"]["total_balance"], 150_000);
+        assert_eq!(body["status"], "success");
+    }
+
+    #[tokio::test]
+    async fn every_item_of_a_list_is_trimmed() {
+        let (_, body) = send("/api/saldos?fields=user_id,%20currency").await;
+
+        assert_eq!(
+            body["data"],
+            json!([
+                { "user_id": 7, "currency": "IDR" },
+                { "user_id": 8, "currency": "USD" },
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn without_the_param_the_full_object_is_returned() {
+        let full =
+            serde_json::to_value(SaldoResponse::from(saldo(7, Currency::IDR, 150_000))).unwrap();
+
+        for uri in ["/api/saldos/1", "/api/saldos/1?fields="] {
+            let (_, body) = send(uri).await;
+
+            assert_eq!(keys(&body["data"]), keys(&full), "{uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_fields_are_ignored_by_default() {
+        let (status, body) = send("/api/saldos/1?fields=id,balance").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(keys(&body["data"]), ["id"]);
+    }
+
+    #[test]
+    fn strict_mode_rejects_unknown_fields() {
+        let known = schema_fields::<SaldoResponse>();
+        let requested: BTreeSet<String> = ["id".to_string(), "balance".to_string()].into();
+
+        assert!(check_fields(&requested, &known, false).is_ok());
+        let err = check_fields(&requested, &known, true).unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        assert_eq!(
+            err.to_string(),
+            "Validation error: fields: Unknown fields: balance"
+        );
+    }
+}
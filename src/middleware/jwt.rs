@@ -3,18 +3,32 @@ use axum::{
     body::Body,
     http::{Request, StatusCode, header},
     middleware::Next,
-    response::IntoResponse,
+    response::{IntoResponse, Response},
 };
 use axum_extra::extract::cookie::CookieJar;
 
-use crate::{abstract_trait::DynJwtService, domain::response::ErrorResponse};
+use crate::{
+    abstract_trait::{DynApiKeyService, DynJwtService},
+    domain::{response::ErrorResponse, role::Role},
+    middleware::api_key::{self, API_KEY_HEADER},
+    utils::{AppError, MessageId},
+};
+
+const NOT_LOGGED_IN: &str = "You are not logged in, please provide token";
+
+// Token problems mean "log in again"; wrong passwords stay `InvalidCredentials`.
+fn unauthorized(message: &str) -> (StatusCode, Json<ErrorResponse>) {
+    let e = ErrorResponse::from(AppError::Unauthorized(message.to_string()));
+    (e.code, Json(e))
+}
 
 pub async fn auth(
     cookie_jar: CookieJar,
     Extension(jwt): Extension<DynJwtService>,
+    Extension(api_keys): Extension<DynApiKeyService>,
     mut req: Request<Body>,
     next: Next,
-) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
     let token = cookie_jar
         .get("token")
         .map(|cookie| cookie.value().to_string())
@@ -27,31 +41,51 @@ pub async fn auth(
 
     let token = match token {
         Some(token) => token,
-        None => {
-            return Err((
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    status: "fail".to_string(),
-                    message: "You are not logged in, please provide token".to_string(),
-                }),
-            ));
+        // Partner backends sign their requests instead of logging in.
+        None if req.headers().contains_key(API_KEY_HEADER) => {
+            return api_key::authenticate(api_keys, req, next).await;
         }
+        None => return Err(unauthorized(NOT_LOGGED_IN)),
     };
 
-    let user_id = match jwt.verify_token(&token) {
-        Ok(id) => id as i32,
-        Err(_) => {
-            return Err((
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    status: "fail".to_string(),
-                    message: "Invalid token".to_string(),
-                }),
-            ));
-        }
-    };
+    let claims = jwt.verify_token(&token).map_err(|err| match err {
+        AppError::TokenExpiredError => unauthorized("Token has expired, please log in again"),
+        _ => unauthorized("Token is invalid or has been revoked, please log in again"),
+    })?;
 
-    req.extensions_mut().insert(user_id);
+    req.extensions_mut().insert(claims.user_id as i32);
+    req.extensions_mut().insert(claims.role);
+    req.extensions_mut().insert(claims);
 
     Ok(next.run(req).await)
 }
+
+pub async fn require_role(
+    required: Role,
+    req: Request<Body>,
+    next: Next,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    match req.extensions().get::<Role>() {
+        Some(role) if *role == required => Ok(next.run(req).await),
+        Some(_) => Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse {
+                status: "fail".to_string(),
+                message: "You do not have permission to access this resource".to_string(),
+                message_id: Some(MessageId::Forbidden),
+                errors: None,
+                error_code: None,
+                details: None,
+                code: StatusCode::FORBIDDEN,
+            }),
+        )),
+        None => Err(unauthorized(NOT_LOGGED_IN)),
+    }
+}
+
+pub async fn require_admin(
+    req: Request<Body>,
+    next: Next,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    require_role(Role::Admin, req, next).await
+}
@@ -0,0 +1,151 @@
+use axum::{
+    body::{Body, HttpBody, to_bytes},
+    http::{Request, header},
+    middleware::Next,
+    response::Response,
+};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::utils::{Language, MessageId, message};
+
+// Error bodies are small; anything bigger is passed through untranslated.
+const MAX_ERROR_BODY_BYTES: u64 = 64 * 1024;
+
+// Stores the caller's preferred language as a request extension and
+// translates the `message` of error responses that carry a `message_id`.
+pub async fn localize(mut req: Request<Body>, next: Next) -> Response {
+    let language = req
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .map(Language::from_accept_language)
+        .unwrap_or_default();
+
+    req.extensions_mut().insert(language);
+
+    let response = next.run(req).await;
+
+    if language == Language::English
+        || !(response.status().is_client_error() || response.status().is_server_error())
+    {
+        return response;
+    }
+
+    localize_error(response, language).await
+}
+
+async fn localize_error(response: Response, language: Language) -> Response {
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+    let small = response
+        .body()
+        .size_hint()
+        .upper()
+        .is_some_and(|size| size <= MAX_ERROR_BODY_BYTES);
+
+    if !is_json || !small {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+
+    let bytes = match to_bytes(body, MAX_ERROR_BODY_BYTES as usize).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("🌐 [Language] Failed to read error body for translation: {e}");
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+
+    let Ok(mut json) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    let message_id = json
+        .get("message_id")
+        .cloned()
+        .and_then(|id| serde_json::from_value::<MessageId>(id).ok());
+
+    let Some(message_id) = message_id else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    json["message"] = Value::from(message(message_id, language));
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(json.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, http::StatusCode, middleware, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{domain::response::ErrorResponse, utils::AppError};
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/users/1",
+                get(|| async {
+                    ErrorResponse::from(AppError::NotFound("User with id 1 not found".to_string()))
+                }),
+            )
+            .route(
+                "/conflict",
+                get(|| async {
+                    ErrorResponse::from(AppError::Conflict(
+                        "Transfer 1 has already been reversed".to_string(),
+                    ))
+                }),
+            )
+            .layer(middleware::from_fn(localize))
+    }
+
+    async fn get_json(uri: &str, accept_language: Option<&str>) -> (StatusCode, Value) {
+        let mut request = Request::builder().uri(uri);
+        if let Some(language) = accept_language {
+            request = request.header(header::ACCEPT_LANGUAGE, language);
+        }
+
+        let response = app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn not_found_is_translated_to_indonesian() {
+        let (status, body) = get_json("/users/1", Some("id")).await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["message"], "Data yang diminta tidak ditemukan");
+        assert_eq!(body["message_id"], "not_found");
+    }
+
+    #[tokio::test]
+    async fn english_keeps_the_detailed_message() {
+        for accept_language in [None, Some("en-US"), Some("fr")] {
+            let (status, body) = get_json("/users/1", accept_language).await;
+
+            assert_eq!(status, StatusCode::NOT_FOUND);
+            assert_eq!(body["message"], "User with id 1 not found");
+        }
+    }
+
+    #[tokio::test]
+    async fn messages_without_an_id_are_left_alone() {
+        let (status, body) = get_json("/conflict", Some("id")).await;
+
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["message"], "Transfer 1 has already been reversed");
+    }
+}
@@ -0,0 +1,135 @@
+use axum::{
+    Extension,
+    extract::Request,
+    http::{HeaderValue, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::{
+    config::{ConnectionManager, ConnectionPool},
+    domain::response::ErrorResponse,
+    utils::AppError,
+};
+
+// Sent as `Retry-After`; a saturated pool usually frees up within moments.
+const RETRY_AFTER_SECS: u64 = 1;
+
+// Refuses requests up front while the database pool is nearly exhausted, so
+// a spike gets a quick 503 instead of queueing for a connection until the
+// acquire timeout.
+pub struct LoadShedder {
+    pool: ConnectionPool,
+    min_available: Option<u32>,
+}
+
+impl LoadShedder {
+    pub fn new(pool: ConnectionPool, min_available: Option<u32>) -> Self {
+        Self {
+            pool,
+            min_available,
+        }
+    }
+
+    // Idle connections plus those the pool may still open, i.e. how many
+    // requests could get one right now without waiting.
+    fn available(&self) -> usize {
+        let stats = ConnectionManager::stats(&self.pool);
+
+        stats.idle + stats.max_connections.saturating_sub(stats.size) as usize
+    }
+
+    pub fn is_saturated(&self) -> bool {
+        self.min_available
+            .is_some_and(|min| self.available() < min as usize)
+    }
+}
+
+// Layered only onto routes that move money: reads stay available while the
+// pool is saturated, since they are cheap and callers poll them to see
+// whether a refused write went through.
+pub async fn shed_load(
+    Extension(shedder): Extension<Arc<LoadShedder>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if !shedder.is_saturated() {
+        return next.run(req).await;
+    }
+
+    warn!(
+        "🚦 [LoadShed] Database pool saturated, refusing {} {}",
+        req.method(),
+        req.uri().path()
+    );
+
+    let e = ErrorResponse::from(AppError::ServiceUnavailable(format!(
+        "Server is busy, retry in {RETRY_AFTER_SECS} seconds"
+    )));
+
+    let mut response = e.into_response();
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(RETRY_AFTER_SECS));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, body::Body, http::StatusCode, middleware, routing::post};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::test_support::{lazy_pool, postgres};
+
+    async fn post_transfer(pool: ConnectionPool, min_available: Option<u32>) -> Response {
+        let shedder = Arc::new(LoadShedder::new(pool, min_available));
+
+        let app = Router::new()
+            .route("/api/transfers", post(|| async { "created" }))
+            .layer(middleware::from_fn(shed_load))
+            .layer(Extension(shedder));
+
+        app.oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/transfers")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs Docker for the Postgres container"]
+    async fn saturated_pool_is_answered_with_503() {
+        // A single-connection pool with that connection checked out.
+        let (_container, pool) = postgres().await;
+        let held = pool.acquire().await.expect("connection");
+
+        let response = post_transfer(pool.clone(), Some(1)).await;
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+
+        // Closed rather than dropped, so the pool is free again right away.
+        held.close().await.expect("close connection");
+
+        assert_eq!(post_transfer(pool, Some(1)).await.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn free_pool_lets_requests_through() {
+        assert_eq!(
+            post_transfer(lazy_pool(), Some(1)).await.status(),
+            StatusCode::OK
+        );
+        assert_eq!(
+            post_transfer(lazy_pool(), None).await.status(),
+            StatusCode::OK
+        );
+    }
+}
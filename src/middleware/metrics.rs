@@ -0,0 +1,39 @@
+use axum::{
+    Extension, body::Body, extract::MatchedPath, http::Request, middleware::Next,
+    response::IntoResponse,
+};
+use std::{sync::Arc, time::Instant};
+
+use crate::config::Metrics;
+
+pub async fn track_metrics(
+    Extension(metrics): Extension<Arc<Metrics>>,
+    req: Request<Body>,
+    next: Next,
+) -> impl IntoResponse {
+    // Label by route template rather than raw path so ids don't explode
+    // the series count.
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_owned())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let method = req.method().to_string();
+
+    let start = Instant::now();
+    let response = next.run(req).await;
+    let elapsed = start.elapsed().as_secs_f64();
+
+    let status = response.status().as_u16().to_string();
+
+    metrics
+        .http_requests_total
+        .with_label_values(&[method.as_str(), route.as_str(), status.as_str()])
+        .inc();
+    metrics
+        .http_request_duration_seconds
+        .with_label_values(&[method.as_str(), route.as_str()])
+        .observe(elapsed);
+
+    response
+}
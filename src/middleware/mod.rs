@@ -1,2 +1,11 @@
+pub mod amounts;
+pub mod api_key;
+pub mod envelope;
+pub mod fields;
 pub mod jwt;
+pub mod language;
+pub mod load_shed;
+pub mod metrics;
+pub mod rate_limit;
+pub mod request_id;
 pub mod validation;
@@ -0,0 +1,238 @@
+use axum::{
+    Extension,
+    body::{Body, to_bytes},
+    extract::ConnectInfo,
+    http::{HeaderValue, Request, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use dashmap::DashMap;
+use serde::Deserialize;
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::warn;
+
+use crate::{domain::response::ErrorResponse, utils::AppError};
+
+// Login bodies are tiny; anything larger is rejected before it is parsed.
+const MAX_LOGIN_BODY_BYTES: usize = 16 * 1024;
+
+struct Attempts {
+    failures: u32,
+    window_start: Instant,
+}
+
+// Counts failed logins per client IP + email in a fixed window. The window
+// starts at the first failure and is cleared by a successful login.
+pub struct LoginRateLimiter {
+    attempts: DashMap<String, Attempts>,
+    max_attempts: u32,
+    window: Duration,
+}
+
+#[derive(Clone)]
+pub struct LoginAttemptKey(pub String);
+
+#[derive(Deserialize)]
+struct LoginEmail {
+    email: String,
+}
+
+impl LoginRateLimiter {
+    pub fn new(max_attempts: u32, window: Duration) -> Self {
+        Self {
+            attempts: DashMap::new(),
+            max_attempts,
+            window,
+        }
+    }
+
+    pub fn key(ip: IpAddr, email: &str) -> LoginAttemptKey {
+        LoginAttemptKey(format!("{ip}|{}", email.trim().to_lowercase()))
+    }
+
+    // Returns how long the caller has to wait when the key is locked out.
+    pub fn check(&self, key: &LoginAttemptKey) -> Result<(), Duration> {
+        let Some(entry) = self.attempts.get(&key.0) else {
+            return Ok(());
+        };
+
+        let elapsed = entry.window_start.elapsed();
+
+        if elapsed >= self.window {
+            drop(entry);
+            self.attempts.remove(&key.0);
+            return Ok(());
+        }
+
+        if entry.failures >= self.max_attempts {
+            return Err(self.window - elapsed);
+        }
+
+        Ok(())
+    }
+
+    pub fn record_failure(&self, key: &LoginAttemptKey) {
+        let mut entry = self.attempts.entry(key.0.clone()).or_insert(Attempts {
+            failures: 0,
+            window_start: Instant::now(),
+        });
+
+        if entry.window_start.elapsed() >= self.window {
+            entry.failures = 0;
+            entry.window_start = Instant::now();
+        }
+
+        entry.failures += 1;
+    }
+
+    pub fn reset(&self, key: &LoginAttemptKey) {
+        self.attempts.remove(&key.0);
+    }
+}
+
+pub async fn login_rate_limit(
+    Extension(limiter): Extension<Arc<LoginRateLimiter>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let (parts, body) = req.into_parts();
+
+    let bytes = match to_bytes(body, MAX_LOGIN_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            let e = ErrorResponse::from(AppError::validation("Login request body is too large"));
+            return e.into_response();
+        }
+    };
+
+    // Malformed bodies fall through to the handler, which rejects them
+    // during validation without ever checking a password.
+    let email = serde_json::from_slice::<LoginEmail>(&bytes)
+        .map(|body| body.email)
+        .unwrap_or_default();
+
+    let key = LoginRateLimiter::key(addr.ip(), &email);
+
+    if let Err(retry_after) = limiter.check(&key) {
+        warn!(
+            "🚫 [RateLimit] Too many failed logins from {} for {email}",
+            addr.ip()
+        );
+
+        let retry_after_secs = (retry_after.as_secs_f64().ceil() as u64).max(1);
+        let e = ErrorResponse::from(AppError::TooManyRequests(format!(
+            "Too many failed login attempts, retry in {retry_after_secs} seconds"
+        )));
+
+        let mut response = e.into_response();
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+        return response;
+    }
+
+    let mut req = Request::from_parts(parts, Body::from(bytes));
+    req.extensions_mut().insert(key);
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Router, extract::connect_info::MockConnectInfo, http::StatusCode, middleware, routing::post,
+    };
+    use std::net::Ipv4Addr;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    const IP: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+    #[test]
+    fn locks_out_after_max_failures_until_reset() {
+        let limiter = LoginRateLimiter::new(5, Duration::from_secs(300));
+        let key = LoginRateLimiter::key(IP, "alice@example.com");
+
+        for _ in 0..5 {
+            assert!(limiter.check(&key).is_ok());
+            limiter.record_failure(&key);
+        }
+
+        let retry_after = limiter.check(&key).unwrap_err();
+        assert!(retry_after <= Duration::from_secs(300));
+
+        // Keys are per IP and email, and case-insensitive in the email.
+        assert!(
+            limiter
+                .check(&LoginRateLimiter::key(IP, "bob@example.com"))
+                .is_ok()
+        );
+        assert!(
+            limiter
+                .check(&LoginRateLimiter::key(IP, " ALICE@example.com"))
+                .is_err()
+        );
+
+        limiter.reset(&key);
+        assert!(limiter.check(&key).is_ok());
+    }
+
+    #[test]
+    fn window_expiry_clears_the_count() {
+        let limiter = LoginRateLimiter::new(1, Duration::from_millis(20));
+        let key = LoginRateLimiter::key(IP, "alice@example.com");
+
+        limiter.record_failure(&key);
+        assert!(limiter.check(&key).is_err());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(limiter.check(&key).is_ok());
+    }
+
+    #[tokio::test]
+    async fn locked_out_login_gets_429_with_retry_after() {
+        let limiter = Arc::new(LoginRateLimiter::new(5, Duration::from_secs(300)));
+        let key = LoginRateLimiter::key(IP, "alice@example.com");
+        for _ in 0..5 {
+            limiter.record_failure(&key);
+        }
+
+        let app = Router::new()
+            .route("/api/auth/login", post(|| async { "logged in" }))
+            .layer(middleware::from_fn(login_rate_limit))
+            .layer(Extension(limiter))
+            .layer(MockConnectInfo(SocketAddr::from((IP, 40000))));
+
+        let login = |email: &str| {
+            Request::builder()
+                .method("POST")
+                .uri("/api/auth/login")
+                .body(Body::from(format!(
+                    r#"{{"email":"{email}","password":"wrong"}}"#
+                )))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(login("alice@example.com"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = response.headers()[header::RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((1..=300).contains(&retry_after), "{retry_after}");
+
+        let response = app.oneshot(login("bob@example.com")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,154 @@
+use axum::{
+    body::Body,
+    http::{HeaderName, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use chrono::Utc;
+use serde_json::Value;
+use tracing::{Instrument, info_span};
+use uuid::Uuid;
+
+use crate::middleware::envelope::edit_envelope;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+// Longer ids from upstream proxies are replaced rather than echoed.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+// The correlation id of the current request, available as an extension to
+// handlers that want to log it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    // Reuses a sane id set by a proxy or client so the same id shows up in
+    // their logs and ours; anything else gets a fresh UUID.
+    fn from_header(value: Option<&HeaderValue>) -> Self {
+        let incoming = value
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|id| {
+                !id.is_empty()
+                    && id.len() <= MAX_REQUEST_ID_LEN
+                    && id.bytes().all(|b| b.is_ascii_graphic())
+            });
+
+        match incoming {
+            Some(id) => RequestId(id.to_string()),
+            None => RequestId(Uuid::new_v4().to_string()),
+        }
+    }
+}
+
+// Assigns every request a `RequestId`, echoes it in the `X-Request-Id`
+// response header and stamps JSON envelopes (`ApiResponse`,
+// `ApiResponsePagination`, `ErrorResponse`) with `timestamp` and
+// `request_id`, so no service has to fill them in itself.
+pub async fn request_id(mut req: Request<Body>, next: Next) -> Response {
+    let request_id = RequestId::from_header(req.headers().get(REQUEST_ID_HEADER));
+    req.extensions_mut().insert(request_id.clone());
+
+    let span = info_span!("request", request_id = %request_id.0);
+    let response = next.run(req).instrument(span).await;
+
+    let mut response = stamp_envelope(response, &request_id).await;
+
+    if let Ok(value) = HeaderValue::from_str(&request_id.0) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    response
+}
+
+async fn stamp_envelope(response: Response, request_id: &RequestId) -> Response {
+    edit_envelope(response, |envelope| {
+        envelope
+            .entry("timestamp")
+            .or_insert_with(|| Value::from(Utc::now().to_rfc3339()));
+        envelope
+            .entry("request_id")
+            .or_insert_with(|| Value::from(request_id.0.clone()));
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Json, Router, body::to_bytes, http::StatusCode, middleware, routing::get};
+    use chrono::DateTime;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::domain::response::ApiResponse;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/saldos/1",
+                get(|| async {
+                    Json(ApiResponse {
+                        status: "success".to_string(),
+                        message: "Saldo retrieved successfully".to_string(),
+                        data: json!({ "saldo_id": 1 }),
+                    })
+                }),
+            )
+            .route("/plain", get(|| async { "ok" }))
+            .layer(middleware::from_fn(request_id))
+    }
+
+    async fn send(uri: &str, request_id: Option<&str>) -> (StatusCode, Option<String>, Vec<u8>) {
+        let mut request = Request::builder().uri(uri);
+        if let Some(id) = request_id {
+            request = request.header(REQUEST_ID_HEADER, id);
+        }
+
+        let response = app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let header = response
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .map(|value| value.to_str().unwrap().to_string());
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        (status, header, bytes.to_vec())
+    }
+
+    #[tokio::test]
+    async fn success_envelope_carries_timestamp_and_echoed_request_id() {
+        let (status, header, bytes) = send("/saldos/1", Some("req-42")).await;
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(header.as_deref(), Some("req-42"));
+        assert_eq!(body["request_id"], "req-42");
+        assert_eq!(body["data"]["saldo_id"], 1);
+        assert!(DateTime::parse_from_rfc3339(body["timestamp"].as_str().unwrap()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn missing_or_invalid_request_ids_are_generated() {
+        for incoming in [None, Some(""), Some("has spaces")] {
+            let (_, header, bytes) = send("/saldos/1", incoming).await;
+            let body: Value = serde_json::from_slice(&bytes).unwrap();
+
+            let generated = header.expect("request id header");
+            assert!(Uuid::parse_str(&generated).is_ok(), "{generated}");
+            assert_eq!(body["request_id"], generated.as_str());
+        }
+    }
+
+    #[tokio::test]
+    async fn non_json_bodies_are_left_alone() {
+        let (status, header, bytes) = send("/plain", Some("req-1")).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(header.as_deref(), Some("req-1"));
+        assert_eq!(bytes, b"ok");
+    }
+}
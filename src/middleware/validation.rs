@@ -1,95 +1,403 @@
 use axum::{
-    extract::{FromRequest, Request},
-    http::StatusCode,
+    extract::{
+        FromRequest, FromRequestParts, Path, RawPathParams, Request,
+        path::ErrorKind,
+        rejection::{JsonRejection, PathRejection},
+    },
+    http::{StatusCode, request::Parts},
 };
 use serde::de::DeserializeOwned;
 use serde_json::{Value, json};
-use validator::{Validate, ValidationErrors};
+use validator::{Validate, ValidationError, ValidationErrors};
 
+use crate::{
+    domain::{
+        request::ExtraValidate,
+        response::{ErrorDetails, ErrorResponse, UnknownFieldDetails},
+    },
+    utils::AppError,
+};
+
+// A JSON body that has passed both its `#[validate]` attributes and its
+// `ExtraValidate` rules; a failure of either is a 422.
 pub struct SimpleValidatedJson<T>(pub T);
 
 impl<S, T> FromRequest<S> for SimpleValidatedJson<T>
 where
-    T: DeserializeOwned + Validate + Send,
+    T: DeserializeOwned + Validate + ExtraValidate + Send,
     S: Send + Sync,
 {
     type Rejection = (StatusCode, axum::Json<Value>);
 
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
-        let axum::Json(json_value) =
-            axum::Json::<T>::from_request(req, state)
-                .await
-                .map_err(|rejection| {
-                    let payload = json!({
-                        "error": "Invalid JSON",
-                        "message": rejection.body_text(),
-                    });
-                    (rejection.status(), axum::Json(payload))
-                })?;
-
-        json_value.validate().map_err(|validation_errors| {
-            let payload = json!({
-                "error": "Validation failed",
-                "message": format_validation_errors(&validation_errors),
-                "details": format_validation_errors_detailed(&validation_errors)
-            });
-            (StatusCode::BAD_REQUEST, axum::Json(payload))
+        let axum::Json(json_value) = axum::Json::<T>::from_request(req, state)
+            .await
+            .map_err(json_rejection)?;
+
+        json_value.validate().map_err(unprocessable)?;
+
+        json_value.extra_validate().map_err(|message| {
+            let mut errors = ValidationErrors::new();
+            errors.add(
+                "__all__",
+                ValidationError::new("extra_validate").with_message(message.into()),
+            );
+            unprocessable(errors)
         })?;
 
         Ok(Self(json_value))
     }
 }
 
-pub fn format_validation_errors(errors: &ValidationErrors) -> String {
-    let mut error_messages = Vec::new();
-
-    for (field, field_errors) in errors.field_errors() {
-        for error in field_errors {
-            let message = error
-                .message
-                .as_ref()
-                .map(|m| m.to_string())
-                .unwrap_or_else(|| match error.code.as_ref() {
-                    "email" => "Invalid email format".to_string(),
-                    "url" => "Invalid URL format".to_string(),
-                    "length" => "Invalid length".to_string(),
-                    "range" => "Value out of range".to_string(),
-                    "custom" => "Custom validation failed".to_string(),
-                    _ => format!("Invalid {field}"),
-                });
-            error_messages.push(format!("{field}: {message}"));
+fn unprocessable(errors: ValidationErrors) -> (StatusCode, axum::Json<Value>) {
+    let error = ErrorResponse::from(AppError::Validation(errors));
+    (error.code, axum::Json(json!(error)))
+}
+
+// `Path`, but a malformed parameter such as `/api/topups/abc` is answered
+// with the usual error envelope and a 400 naming the parameter, instead of
+// axum's plain-text rejection.
+pub struct ValidatedPath<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for ValidatedPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, axum::Json<Value>);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let rejection = match Path::<T>::from_request_parts(parts, state).await {
+            Ok(Path(value)) => return Ok(Self(value)),
+            Err(rejection) => rejection,
+        };
+
+        // A single-value `Path<i32>` error doesn't say which parameter it
+        // was, but then the route only has one.
+        let only_param = RawPathParams::from_request_parts(parts, state)
+            .await
+            .ok()
+            .and_then(|params| match params.iter().collect::<Vec<_>>()[..] {
+                [(key, _)] => Some(key.to_string()),
+                _ => None,
+            });
+
+        let error = ErrorResponse::from(path_rejection(rejection, only_param));
+        Err((error.code, axum::Json(json!(error))))
+    }
+}
+
+fn path_rejection(rejection: PathRejection, only_param: Option<String>) -> AppError {
+    let message = rejection.body_text();
+
+    let (key, expected_type) = match &rejection {
+        PathRejection::FailedToDeserializePathParams(error) => match error.kind() {
+            ErrorKind::ParseErrorAtKey {
+                key, expected_type, ..
+            } => (Some(key.clone()), *expected_type),
+            ErrorKind::ParseError { expected_type, .. } => (only_param, *expected_type),
+            _ if rejection.status() == StatusCode::BAD_REQUEST => {
+                return AppError::Custom(message);
+            }
+            _ => return AppError::InternalError(message),
+        },
+        _ => return AppError::InternalError(message),
+    };
+
+    let key = key.unwrap_or_else(|| "path parameter".to_string());
+
+    match expected_type {
+        "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" => {
+            AppError::Custom(format!("{key} must be an integer"))
         }
+        _ => AppError::Custom(format!("{key} must be a valid {expected_type}")),
     }
+}
+
+// Request bodies deny unknown fields, so a misspelled key such as `ammount`
+// fails here instead of silently leaving the real field at its default.
+// That is the client's mistake, so it is reported as a 400 naming the key
+// in `details.field`. Any other unreadable body keeps axum's status.
+fn json_rejection(rejection: JsonRejection) -> (StatusCode, axum::Json<Value>) {
+    let message = rejection.body_text();
+
+    let unknown_field = match &rejection {
+        JsonRejection::JsonDataError(_) => message
+            .split_once("unknown field `")
+            .and_then(|(_, rest)| rest.split_once('`'))
+            .map(|(field, _)| field.to_string()),
+        _ => None,
+    };
+
+    let mut error = ErrorResponse::from(AppError::Custom(message));
 
-    if error_messages.is_empty() {
-        "Validation failed".to_string()
-    } else {
-        error_messages.join("; ")
+    match unknown_field {
+        Some(field) => {
+            error.error_code = Some("unknown_field".to_string());
+            error.details = Some(Box::new(ErrorDetails::UnknownField(UnknownFieldDetails {
+                field,
+            })));
+        }
+        None => error.code = rejection.status(),
     }
+
+    (error.code, axum::Json(json!(error)))
 }
 
-pub fn format_validation_errors_detailed(errors: &ValidationErrors) -> Value {
-    let mut error_map = serde_json::Map::new();
-
-    for (field, field_errors) in errors.field_errors() {
-        let messages: Vec<String> = field_errors
-            .iter()
-            .map(|e| {
-                e.message
-                    .as_ref()
-                    .map(|m| m.to_string())
-                    .unwrap_or_else(|| match e.code.as_ref() {
-                        "email" => "Invalid email format".to_string(),
-                        "url" => "Invalid URL format".to_string(),
-                        "length" => "Invalid length".to_string(),
-                        "range" => "Value out of range".to_string(),
-                        "custom" => "Custom validation failed".to_string(),
-                        _ => format!("Invalid {field}"),
-                    })
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Router,
+        body::{Body, to_bytes},
+        http::header,
+        routing::get,
+    };
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::domain::request::{
+        CreateTransferRequest, FreezeSaldoRequest, UpdateSaldoRequest, auth::RegisterRequest,
+    };
+
+    async fn extract(body: Value) -> Result<CreateTransferRequest, (StatusCode, Value)> {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/transfers")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+
+        SimpleValidatedJson::<CreateTransferRequest>::from_request(request, &())
+            .await
+            .map(|SimpleValidatedJson(value)| value)
+            .map_err(|(status, axum::Json(body))| (status, body))
+    }
+
+    #[tokio::test]
+    async fn misspelled_field_is_rejected_with_its_name() {
+        let (status, body) = extract(json!({
+            "transfer_from": 1,
+            "transfer_to": 2,
+            "ammount": 100000,
+        }))
+        .await
+        .unwrap_err();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["status"], "error");
+        assert_eq!(body["error_code"], "unknown_field");
+        assert_eq!(body["details"]["field"], "ammount");
+        assert!(body["message"].as_str().unwrap().contains("ammount"));
+    }
+
+    #[tokio::test]
+    async fn extra_field_is_rejected() {
+        let (status, body) = extract(json!({
+            "transfer_from": 1,
+            "transfer_to": 2,
+            "transfer_amount": 100000,
+            "fee": 0,
+        }))
+        .await
+        .unwrap_err();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["details"]["field"], "fee");
+    }
+
+    #[tokio::test]
+    async fn wrong_type_is_still_unprocessable() {
+        let (status, body) = extract(json!({
+            "transfer_from": 1,
+            "transfer_to": 2,
+            "transfer_amount": "lots",
+        }))
+        .await
+        .unwrap_err();
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["status"], "error");
+        assert!(body.get("details").is_none());
+    }
+
+    #[tokio::test]
+    async fn known_fields_are_accepted() {
+        let request = extract(json!({
+            "transfer_from": 1,
+            "transfer_to": 2,
+            "transfer_amount": 100000,
+            "note": "rent",
+        }))
+        .await
+        .unwrap();
+
+        assert_eq!(request.note.as_deref(), Some("rent"));
+    }
+
+    #[tokio::test]
+    async fn cross_field_rules_are_unprocessable() {
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/api/saldos/1")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                json!({
+                    "saldo_id": 1,
+                    "user_id": 1,
+                    "total_balance": 100000,
+                    "withdraw_amount": 60000,
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let (status, axum::Json(body)) =
+            SimpleValidatedJson::<UpdateSaldoRequest>::from_request(request, &())
+                .await
+                .map(|_| ())
+                .unwrap_err();
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(
+            body.to_string().contains("Withdraw time must be provided"),
+            "{body}"
+        );
+    }
+
+    #[tokio::test]
+    async fn self_transfer_fails_its_extra_rule() {
+        let (status, body) = extract(json!({
+            "transfer_from": 1,
+            "transfer_to": 1,
+            "transfer_amount": 100000,
+        }))
+        .await
+        .unwrap_err();
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            body["errors"]["__all__"][0], "Cannot transfer to self",
+            "{body}"
+        );
+    }
+
+    #[tokio::test]
+    async fn body_without_extra_rules_passes_through() {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/saldos/1/freeze")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(json!({ "reason": "chargeback" }).to_string()))
+            .unwrap();
+
+        let SimpleValidatedJson(body) =
+            SimpleValidatedJson::<FreezeSaldoRequest>::from_request(request, &())
+                .await
+                .map_err(|(status, _)| status)
+                .unwrap();
+
+        assert_eq!(body.reason.as_deref(), Some("chargeback"));
+    }
+
+    async fn register(body: Value) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/auth/register")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+
+        SimpleValidatedJson::<RegisterRequest>::from_request(request, &())
+            .await
+            .map(|_| (StatusCode::OK, Value::Null))
+            .unwrap_or_else(|(status, axum::Json(body))| (status, body))
+    }
+
+    fn registration(email: &str, confirm_password: &str) -> Value {
+        json!({
+            "firstname": "Alice",
+            "lastname": "Liddell",
+            "email": email,
+            "password": "password123",
+            "confirm_password": confirm_password,
+        })
+    }
+
+    #[tokio::test]
+    async fn bad_email_is_reported_on_its_field() {
+        let (status, body) = register(registration("not-an-email", "password123")).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["status"], "error");
+        assert_eq!(body["errors"]["email"][0], "Invalid email format", "{body}");
+        assert!(body["errors"].get("confirm_password").is_none(), "{body}");
+    }
+
+    #[tokio::test]
+    async fn password_mismatch_is_reported_on_confirm_password() {
+        let (status, body) = register(registration("alice@example.com", "password124")).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            body["errors"]["confirm_password"][0], "Passwords do not match",
+            "{body}"
+        );
+        assert!(body["errors"].get("email").is_none(), "{body}");
+    }
+
+    #[tokio::test]
+    async fn valid_registration_passes() {
+        let (status, body) = register(registration("alice@example.com", "password123")).await;
+
+        assert_eq!(status, StatusCode::OK, "{body}");
+    }
+
+    async fn get_topup(uri: &str) -> (StatusCode, Vec<u8>) {
+        let app = Router::new().route(
+            "/api/topups/{id}",
+            get(|ValidatedPath(id): ValidatedPath<i32>| async move { id.to_string() }),
+        );
+
+        let response = app
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        (status, bytes.to_vec())
+    }
+
+    #[tokio::test]
+    async fn non_numeric_id_gets_the_error_envelope() {
+        let (status, bytes) = get_topup("/api/topups/abc").await;
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body,
+            json!({
+                "status": "error",
+                "message": "id must be an integer",
             })
-            .collect();
-        error_map.insert(field.to_string(), json!(messages));
+        );
     }
 
-    json!(error_map)
+    #[tokio::test]
+    async fn out_of_range_id_is_rejected_too() {
+        let (status, bytes) = get_topup("/api/topups/99999999999").await;
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["message"], "id must be an integer");
+    }
+
+    #[tokio::test]
+    async fn numeric_id_is_extracted() {
+        let (status, bytes) = get_topup("/api/topups/42").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(bytes, b"42");
+    }
 }
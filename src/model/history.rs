@@ -0,0 +1,22 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::{currency::Currency, money::Money, request::history::HistoryKind};
+
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct HistoryEntry {
+    pub source_id: i32,
+    pub kind: HistoryKind,
+    pub amount: Money,
+    pub occurred_at: NaiveDateTime,
+    pub counterparty: Option<i32>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+// What a saldo held at a given instant, replayed from its balance history.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct BalanceSnapshot {
+    pub currency: Currency,
+    pub balance: Money,
+}
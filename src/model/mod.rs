@@ -1,5 +1,14 @@
+pub mod api_key;
+pub mod email_verification;
+pub mod history;
+pub mod login_audit;
+pub mod migration;
+pub mod password_reset;
+pub mod reconciliation;
 pub mod saldo;
+pub mod saldo_history;
 pub mod topup;
 pub mod transfer;
 pub mod user;
+pub mod webhook;
 pub mod withdraw;
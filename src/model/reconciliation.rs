@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::{currency::Currency, money::Money};
+
+// A saldo's stored balance next to the one replayed from its ledger.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct Reconciliation {
+    pub saldo_id: i32,
+    pub user_id: i32,
+    pub currency: Currency,
+    pub stored: Money,
+    pub computed: Money,
+}
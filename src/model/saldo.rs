@@ -2,13 +2,45 @@ use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::{
+    domain::{currency::Currency, money::Money},
+    utils::AppError,
+};
+
 #[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
 pub struct Saldo {
     pub saldo_id: i32,
     pub user_id: i32,
-    pub total_balance: i32,
-    pub withdraw_amount: Option<i32>,
+    pub total_balance: Money,
+    pub currency: Currency,
+    pub withdraw_amount: Option<Money>,
     pub withdraw_time: Option<NaiveDateTime>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub is_frozen: bool,
+    pub frozen_reason: Option<String>,
+}
+
+impl Saldo {
+    // A frozen saldo can neither send nor receive money until an admin
+    // unfreezes it.
+    pub fn ensure_not_frozen(&self) -> Result<(), AppError> {
+        if self.is_frozen {
+            return Err(AppError::Custom("account frozen".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+// Aggregates over the live saldos of one currency.
+#[derive(Debug, FromRow, Clone)]
+pub struct SaldoStats {
+    pub currency: Currency,
+    pub total_balance: Money,
+    pub account_count: i64,
+    pub average_balance: Money,
+    pub median_balance: Money,
+    pub negative_count: i64,
 }
@@ -0,0 +1,17 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::domain::{money::Money, request::saldo_history::BalanceChangeReason};
+
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct SaldoHistory {
+    pub saldo_history_id: i32,
+    pub saldo_id: i32,
+    pub user_id: i32,
+    pub old_balance: Money,
+    pub new_balance: Money,
+    pub reason: BalanceChangeReason,
+    pub reference_id: Option<i32>,
+    pub created_at: Option<NaiveDateTime>,
+}
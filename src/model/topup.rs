@@ -2,14 +2,26 @@ use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::domain::{currency::Currency, money::Money};
+
 #[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
 pub struct Topup {
     pub topup_id: i32,
     pub user_id: i32,
     pub topup_no: String,
-    pub topup_amount: i32,
+    pub topup_amount: Money,
+    pub currency: Currency,
     pub topup_method: String,
     pub topup_time: NaiveDateTime,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+// One `date_trunc('month', created_at)` bucket; months without topups have no row.
+#[derive(Debug, FromRow, Clone)]
+pub struct TopupMonthlyStats {
+    pub month: NaiveDateTime,
+    pub count: i64,
+    pub total_amount: Money,
 }
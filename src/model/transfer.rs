@@ -2,13 +2,59 @@ use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::domain::{currency::Currency, money::Money, transfer_status::TransferStatus};
+
 #[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
 pub struct Transfer {
     pub transfer_id: i32,
     pub transfer_from: i32,
     pub transfer_to: i32,
-    pub transfer_amount: i32,
+    pub transfer_amount: Money,
+    // Charged to the sender on top of `transfer_amount`.
+    pub fee: Money,
+    pub currency: Currency,
     pub transfer_time: NaiveDateTime,
+    pub status: TransferStatus,
+    pub scheduled_at: Option<NaiveDateTime>,
+    pub note: Option<String>,
+    // Set on a compensating transfer: the transfer it reverses.
+    pub reversed_transfer_id: Option<i32>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+// A transfer joined with the names of both parties, for receipts.
+#[derive(Debug, FromRow, Clone)]
+pub struct TransferReceipt {
+    pub transfer_id: i32,
+    pub transfer_from: i32,
+    pub transfer_to: i32,
+    pub from_firstname: String,
+    pub from_lastname: String,
+    pub to_firstname: String,
+    pub to_lastname: String,
+    pub transfer_amount: Money,
+    pub currency: Currency,
+    pub transfer_time: NaiveDateTime,
+}
+
+// Settled transfers into and out of one user's saldo.
+#[derive(Debug, FromRow, Clone)]
+pub struct TransferNetFlow {
+    pub incoming: Money,
+    pub outgoing: Money,
+}
+
+// Settled transfers between a user and one counterparty, in one currency.
+#[derive(Debug, FromRow, Clone)]
+pub struct TransferCounterparty {
+    pub counterparty_id: i32,
+    pub firstname: String,
+    pub lastname: String,
+    pub currency: Currency,
+    pub total_sent: Money,
+    pub total_received: Money,
+    pub transfer_count: i64,
+    pub last_transfer_at: NaiveDateTime,
 }
@@ -2,6 +2,8 @@ use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::domain::role::Role;
+
 #[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
 pub struct User {
     pub user_id: i32,
@@ -10,6 +12,10 @@ pub struct User {
     pub email: String,
     pub password: String,
     pub noc_transfer: String,
+    pub role: Role,
+    pub is_verified: bool,
+    pub last_login_at: Option<NaiveDateTime>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
 }
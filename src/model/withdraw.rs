@@ -2,12 +2,18 @@ use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::domain::{currency::Currency, money::Money, withdraw_status::WithdrawStatus};
+
 #[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
 pub struct Withdraw {
     pub withdraw_id: i32,
     pub user_id: i32,
-    pub withdraw_amount: i32,
+    pub withdraw_amount: Money,
+    pub currency: Currency,
     pub withdraw_time: NaiveDateTime,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub status: WithdrawStatus,
+    pub note: Option<String>,
 }
@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+use sea_query::{Expr, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use tracing::{error, info};
+
+use crate::abstract_trait::ApiKeyRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::model::api_key::ApiKey;
+use crate::schema::api_key::ApiKeys;
+use crate::utils::AppError;
+
+pub struct ApiKeyRepository {
+    db_pool: ConnectionPool,
+}
+
+impl ApiKeyRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepositoryTrait for ApiKeyRepository {
+    async fn find_active(&self, api_key: &str) -> Result<Option<ApiKey>, AppError> {
+        info!("🔐 [ApiKey] Looking up api key {api_key}");
+
+        let (sql, values) = Query::select()
+            .from(ApiKeys::Table)
+            .columns([
+                ApiKeys::ApiKeyId,
+                ApiKeys::UserId,
+                ApiKeys::ApiKey,
+                ApiKeys::Secret,
+                ApiKeys::Name,
+                ApiKeys::CreatedAt,
+                ApiKeys::RevokedAt,
+            ])
+            .and_where(Expr::col(ApiKeys::ApiKey).eq(api_key))
+            .and_where(Expr::col(ApiKeys::RevokedAt).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        let key = sqlx::query_as_with::<_, ApiKey, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [ApiKey] Failed to look up api key {api_key}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        match &key {
+            Some(key) => info!("✅ [ApiKey] Found api key {api_key} ({})", key.name),
+            None => info!("⚠️ [ApiKey] Api key {api_key} is unknown or revoked"),
+        }
+
+        Ok(key)
+    }
+}
@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use sea_query::{Cond, Expr, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::PgConnection;
+use tracing::{error, info};
+
+use crate::abstract_trait::EmailVerificationRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::model::email_verification::EmailVerification;
+use crate::schema::email_verification::EmailVerifications;
+use crate::utils::AppError;
+
+pub struct EmailVerificationRepository {
+    db_pool: ConnectionPool,
+}
+
+impl EmailVerificationRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl EmailVerificationRepositoryTrait for EmailVerificationRepository {
+    async fn create(
+        &self,
+        user_id: i32,
+        token_hash: &str,
+        expires_at: NaiveDateTime,
+    ) -> Result<EmailVerification, AppError> {
+        info!("📨 [EmailVerification] Creating verification token for user_id={user_id}");
+
+        let (sql, values) = Query::insert()
+            .into_table(EmailVerifications::Table)
+            .columns([
+                EmailVerifications::UserId,
+                EmailVerifications::TokenHash,
+                EmailVerifications::ExpiresAt,
+            ])
+            .values_panic([user_id.into(), token_hash.into(), expires_at.into()])
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let verification = sqlx::query_as_with::<_, EmailVerification, _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [EmailVerification] Failed to create verification token for user_id={user_id}: {e}"
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [EmailVerification] Verification token {} created for user_id={user_id}",
+            verification.email_verification_id
+        );
+
+        Ok(verification)
+    }
+
+    // Same single-statement check-and-mark as password resets, so a token can
+    // only ever be redeemed once.
+    async fn consume_tx(
+        &self,
+        conn: &mut PgConnection,
+        token_hash: &str,
+    ) -> Result<Option<EmailVerification>, AppError> {
+        info!("📨 [EmailVerification] Consuming verification token");
+
+        let now = Utc::now().naive_utc();
+
+        let (sql, values) = Query::update()
+            .table(EmailVerifications::Table)
+            .values([(EmailVerifications::UsedAt, now.into())])
+            .and_where(Expr::col(EmailVerifications::TokenHash).eq(token_hash))
+            .and_where(Expr::col(EmailVerifications::UsedAt).is_null())
+            .and_where(Expr::col(EmailVerifications::ExpiresAt).gt(now))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let verification = sqlx::query_as_with::<_, EmailVerification, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [EmailVerification] Failed to consume verification token: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        match &verification {
+            Some(v) => info!(
+                "✅ [EmailVerification] Token {} consumed for user_id={}",
+                v.email_verification_id, v.user_id
+            ),
+            None => info!("⚠️ [EmailVerification] Token is unknown, expired or already used"),
+        }
+
+        Ok(verification)
+    }
+
+    // Expired and already-used tokens can never be redeemed again.
+    async fn delete_expired(&self) -> Result<u64, AppError> {
+        let now = Utc::now().naive_utc();
+
+        let (sql, values) = Query::delete()
+            .from_table(EmailVerifications::Table)
+            .cond_where(
+                Cond::any()
+                    .add(Expr::col(EmailVerifications::ExpiresAt).lte(now))
+                    .add(Expr::col(EmailVerifications::UsedAt).is_not_null()),
+            )
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [EmailVerification] Failed to delete expired tokens: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "📨 [EmailVerification] Deleted {} expired or used token(s)",
+            result.rows_affected()
+        );
+
+        Ok(result.rows_affected())
+    }
+}
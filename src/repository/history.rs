@@ -0,0 +1,278 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_query::{
+    Alias, Asterisk, Expr, Func, IntoColumnRef, Order, PostgresQueryBuilder, Query,
+    SelectStatement, SimpleExpr, UnionType,
+};
+use sea_query_binder::SqlxBinder;
+use tracing::{error, info};
+
+use crate::abstract_trait::HistoryRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::domain::request::{HistoryFilter, HistoryKind};
+use crate::domain::{transfer_status::TransferStatus, withdraw_status::WithdrawStatus};
+use crate::model::history::{BalanceSnapshot, HistoryEntry};
+use crate::schema::{
+    saldo::Saldo, saldo_history::SaldoHistory, topup::Topups, transfer::Transfers,
+    withdraw::Withdraws,
+};
+use crate::utils::AppError;
+
+pub struct HistoryRepository {
+    db_pool: ConnectionPool,
+}
+
+impl HistoryRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+
+    fn branch(
+        kind: HistoryKind,
+        id: SimpleExpr,
+        amount: SimpleExpr,
+        occurred_at: SimpleExpr,
+        counterparty: SimpleExpr,
+        created_at: SimpleExpr,
+    ) -> SelectStatement {
+        Query::select()
+            .expr_as(id, Alias::new("source_id"))
+            .expr_as(Expr::val(kind), Alias::new("kind"))
+            .expr_as(amount, Alias::new("amount"))
+            .expr_as(occurred_at, Alias::new("occurred_at"))
+            .expr_as(counterparty, Alias::new("counterparty"))
+            .expr_as(created_at, Alias::new("created_at"))
+            .to_owned()
+    }
+
+    fn within_dates(
+        branch: &mut SelectStatement,
+        occurred_at: impl IntoColumnRef,
+        filter: HistoryFilter,
+    ) {
+        let occurred_at = Expr::col(occurred_at);
+
+        if let Some(from_date) = filter.from_date {
+            branch.and_where(occurred_at.clone().gte(from_date.naive_utc()));
+        }
+
+        if let Some(to_date) = filter.to_date {
+            branch.and_where(occurred_at.lte(to_date.naive_utc()));
+        }
+    }
+
+    // One SELECT per requested kind, each projected onto the same columns so
+    // they can be UNION ALL'd and paginated as a single set.
+    fn history_union(user_id: i32, filter: HistoryFilter) -> SelectStatement {
+        let wanted = |k: HistoryKind| filter.kind.is_none_or(|kind| kind == k);
+        let no_counterparty = || Expr::cust("NULL::INTEGER");
+
+        let mut branches = Vec::with_capacity(4);
+
+        if wanted(HistoryKind::Topup) {
+            let mut branch = Self::branch(
+                HistoryKind::Topup,
+                Expr::col(Topups::TopupId).into(),
+                Expr::col(Topups::TopupAmount).into(),
+                Expr::col(Topups::TopupTime).into(),
+                no_counterparty(),
+                Expr::col(Topups::CreatedAt).into(),
+            )
+            .from(Topups::Table)
+            .and_where(Expr::col(Topups::UserId).eq(user_id))
+            .and_where(Expr::col(Topups::DeletedAt).is_null())
+            .to_owned();
+            Self::within_dates(&mut branch, Topups::TopupTime, filter);
+            branches.push(branch);
+        }
+
+        if wanted(HistoryKind::TransferIn) {
+            let mut branch = Self::branch(
+                HistoryKind::TransferIn,
+                Expr::col(Transfers::TransferId).into(),
+                Expr::col(Transfers::TransferAmount).into(),
+                Expr::col(Transfers::TransferTime).into(),
+                Expr::col(Transfers::TransferFrom).into(),
+                Expr::col(Transfers::CreatedAt).into(),
+            )
+            .from(Transfers::Table)
+            .and_where(Expr::col(Transfers::TransferTo).eq(user_id))
+            .and_where(Expr::col(Transfers::DeletedAt).is_null())
+            .and_where(Expr::col(Transfers::Status).is_in(TransferStatus::SETTLED))
+            .to_owned();
+            Self::within_dates(&mut branch, Transfers::TransferTime, filter);
+            branches.push(branch);
+        }
+
+        if wanted(HistoryKind::TransferOut) {
+            let mut branch = Self::branch(
+                HistoryKind::TransferOut,
+                Expr::col(Transfers::TransferId).into(),
+                Expr::col(Transfers::TransferAmount).into(),
+                Expr::col(Transfers::TransferTime).into(),
+                Expr::col(Transfers::TransferTo).into(),
+                Expr::col(Transfers::CreatedAt).into(),
+            )
+            .from(Transfers::Table)
+            .and_where(Expr::col(Transfers::TransferFrom).eq(user_id))
+            .and_where(Expr::col(Transfers::DeletedAt).is_null())
+            .and_where(Expr::col(Transfers::Status).is_in(TransferStatus::SETTLED))
+            .to_owned();
+            Self::within_dates(&mut branch, Transfers::TransferTime, filter);
+            branches.push(branch);
+        }
+
+        if wanted(HistoryKind::Withdraw) {
+            let mut branch = Self::branch(
+                HistoryKind::Withdraw,
+                Expr::col(Withdraws::WithdrawId).into(),
+                Expr::col(Withdraws::WithdrawAmount).into(),
+                Expr::col(Withdraws::WithdrawTime).into(),
+                no_counterparty(),
+                Expr::col(Withdraws::CreatedAt).into(),
+            )
+            .from(Withdraws::Table)
+            .and_where(Expr::col(Withdraws::UserId).eq(user_id))
+            .and_where(Expr::col(Withdraws::DeletedAt).is_null())
+            .and_where(Expr::col(Withdraws::Status).is_in(WithdrawStatus::SETTLED))
+            .to_owned();
+            Self::within_dates(&mut branch, Withdraws::WithdrawTime, filter);
+            branches.push(branch);
+        }
+
+        let mut branches = branches.into_iter();
+        let mut union = branches
+            .next()
+            .expect("at least one history kind is always selected");
+
+        for branch in branches {
+            union.union(UnionType::All, branch);
+        }
+
+        union
+    }
+}
+
+#[async_trait]
+impl HistoryRepositoryTrait for HistoryRepository {
+    async fn find_by_user(
+        &self,
+        user_id: i32,
+        page: i32,
+        page_size: i32,
+        filter: HistoryFilter,
+    ) -> Result<(Vec<HistoryEntry>, i64), AppError> {
+        info!(
+            "📜 [History] Fetching history for user_id={user_id} - page: {page}, page_size: {page_size}, filter: {:?}",
+            filter
+        );
+
+        let page = if page > 0 { page } else { 1 };
+        let page_size = if page_size > 0 { page_size } else { 10 };
+        let offset = (page - 1) * page_size;
+
+        let history = Alias::new("history");
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from_subquery(Self::history_union(user_id, filter), history.clone())
+            .order_by(Alias::new("occurred_at"), Order::Desc)
+            .order_by(Alias::new("created_at"), Order::Desc)
+            .order_by(Alias::new("kind"), Order::Asc)
+            .order_by(Alias::new("source_id"), Order::Desc)
+            .limit(page_size as u64)
+            .offset(offset as u64)
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [History] Generated SQL: {sql} | Values: {:?}", values);
+
+        let entries = sqlx::query_as_with::<_, HistoryEntry, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [History] Failed to fetch history for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        let (count_sql, count_values) = Query::select()
+            .expr(Func::count(Expr::col(Asterisk)))
+            .from_subquery(Self::history_union(user_id, filter), history)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (total,) = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [History] Failed to count history for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [History] Returned {} of {total} entries for user_id={user_id}",
+            entries.len()
+        );
+
+        Ok((entries, total))
+    }
+
+    async fn balances_at(
+        &self,
+        user_id: i32,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<BalanceSnapshot>, AppError> {
+        info!("📜 [History] Fetching balances of user_id={user_id} as of {at}");
+
+        let at = at.naive_utc();
+
+        let last_change = Query::select()
+            .column((SaldoHistory::Table, SaldoHistory::NewBalance))
+            .from(SaldoHistory::Table)
+            .and_where(
+                Expr::col((SaldoHistory::Table, SaldoHistory::SaldoId))
+                    .equals((Saldo::Table, Saldo::SaldoId)),
+            )
+            .and_where(Expr::col((SaldoHistory::Table, SaldoHistory::CreatedAt)).lte(at))
+            .order_by((SaldoHistory::Table, SaldoHistory::CreatedAt), Order::Desc)
+            .order_by(
+                (SaldoHistory::Table, SaldoHistory::SaldoHistoryId),
+                Order::Desc,
+            )
+            .limit(1)
+            .to_owned();
+
+        // Without any change before `at` the saldo still held what it was
+        // opened with, or nothing if it didn't exist yet.
+        let untouched = Expr::case(
+            Expr::col((Saldo::Table, Saldo::CreatedAt)).lte(at),
+            Expr::col((Saldo::Table, Saldo::OpeningBalance)),
+        )
+        .finally(Expr::val(0i64));
+
+        let (sql, values) = Query::select()
+            .column((Saldo::Table, Saldo::Currency))
+            .expr_as(
+                Func::coalesce([
+                    SimpleExpr::SubQuery(None, Box::new(last_change.into_sub_query_statement())),
+                    untouched.into(),
+                ]),
+                Alias::new("balance"),
+            )
+            .from(Saldo::Table)
+            .and_where(Expr::col((Saldo::Table, Saldo::UserId)).eq(user_id))
+            .and_where(Expr::col((Saldo::Table, Saldo::DeletedAt)).is_null())
+            .order_by((Saldo::Table, Saldo::Currency), Order::Asc)
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [History] Generated SQL: {sql} | Values: {:?}", values);
+
+        let balances = sqlx::query_as_with::<_, BalanceSnapshot, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [History] Failed to fetch balances for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        Ok(balances)
+    }
+}
@@ -0,0 +1,705 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::PgConnection;
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+use tracing::{Instrument, debug, field, info_span, warn};
+
+use crate::{
+    abstract_trait::{
+        DynSaldoRepository, DynTransferRepository, SaldoRepositoryTrait, TransferRepositoryTrait,
+    },
+    config::DEFAULT_SLOW_QUERY_THRESHOLD_MS,
+    domain::{
+        currency::Currency,
+        money::Money,
+        request::{
+            CreateSaldoRequest, CreateTransferRequest, IncrementSaldoBalance, Search, Sort,
+            TransactionFilter, TransferDirection, UpdateSaldoBalance, UpdateSaldoRequest,
+            UpdateSaldoWithdraw, UpdateTransferAmountRequest, UpdateTransferRequest,
+        },
+        transfer_status::TransferStatus,
+    },
+    model::{
+        saldo::{Saldo, SaldoStats},
+        saldo_history::SaldoHistory,
+        transfer::{Transfer, TransferCounterparty, TransferNetFlow, TransferReceipt},
+    },
+    utils::AppError,
+};
+
+// Each call runs in a `db_query` span carrying its name and duration, so the
+// repository's own logs and sqlx's pool acquire timings nest under it.
+#[derive(Debug, Clone, Copy)]
+pub struct QueryTimer {
+    threshold: Duration,
+}
+
+impl QueryTimer {
+    pub fn new(threshold: Duration) -> Self {
+        Self { threshold }
+    }
+
+    pub async fn time<F: Future>(&self, query: &'static str, future: F) -> F::Output {
+        let span = info_span!("db_query", query, elapsed_ms = field::Empty);
+        let started = Instant::now();
+
+        let output = future.instrument(span.clone()).await;
+
+        let elapsed = started.elapsed();
+        let elapsed_ms = elapsed.as_millis() as u64;
+        span.record("elapsed_ms", elapsed_ms);
+
+        if elapsed > self.threshold {
+            warn!(
+                parent: &span,
+                "🐢 [Database] Slow query {query}: {elapsed_ms}ms (threshold {}ms)",
+                self.threshold.as_millis()
+            );
+        } else {
+            debug!(parent: &span, "⏱️ [Database] {query} took {elapsed_ms}ms");
+        }
+
+        output
+    }
+}
+
+impl Default for QueryTimer {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(DEFAULT_SLOW_QUERY_THRESHOLD_MS))
+    }
+}
+
+pub struct InstrumentedSaldoRepository {
+    inner: DynSaldoRepository,
+    timer: QueryTimer,
+}
+
+impl InstrumentedSaldoRepository {
+    pub fn new(inner: DynSaldoRepository, timer: QueryTimer) -> Self {
+        Self { inner, timer }
+    }
+}
+
+pub struct InstrumentedTransferRepository {
+    inner: DynTransferRepository,
+    timer: QueryTimer,
+}
+
+impl InstrumentedTransferRepository {
+    pub fn new(inner: DynTransferRepository, timer: QueryTimer) -> Self {
+        Self { inner, timer }
+    }
+}
+
+#[async_trait]
+impl SaldoRepositoryTrait for InstrumentedSaldoRepository {
+    async fn find_all(
+        &self,
+        page: i32,
+        page_size: i32,
+        search: Option<Search>,
+        sort: Sort,
+        include_deleted: bool,
+    ) -> Result<(Vec<Saldo>, i64), AppError> {
+        self.timer
+            .time(
+                "saldo.find_all",
+                self.inner
+                    .find_all(page, page_size, search, sort, include_deleted),
+            )
+            .await
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Saldo>, AppError> {
+        self.timer
+            .time("saldo.find_by_id", self.inner.find_by_id(id))
+            .await
+    }
+
+    async fn find_by_user_for_update(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<Vec<Saldo>, AppError> {
+        self.timer
+            .time(
+                "saldo.find_by_user_for_update",
+                self.inner.find_by_user_for_update(conn, user_id),
+            )
+            .await
+    }
+
+    async fn find_by_users_id(
+        &self,
+        id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Saldo>, i64), AppError> {
+        self.timer
+            .time(
+                "saldo.find_by_users_id",
+                self.inner.find_by_users_id(id, page, page_size),
+            )
+            .await
+    }
+
+    async fn find_by_user_and_currency(
+        &self,
+        user_id: i32,
+        currency: Currency,
+    ) -> Result<Option<Saldo>, AppError> {
+        self.timer
+            .time(
+                "saldo.find_by_user_and_currency",
+                self.inner.find_by_user_and_currency(user_id, currency),
+            )
+            .await
+    }
+
+    async fn find_by_user_and_currency_for_update(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+        currency: Currency,
+    ) -> Result<Option<Saldo>, AppError> {
+        self.timer
+            .time(
+                "saldo.find_by_user_and_currency_for_update",
+                self.inner
+                    .find_by_user_and_currency_for_update(conn, user_id, currency),
+            )
+            .await
+    }
+
+    async fn create(&self, input: &CreateSaldoRequest) -> Result<Saldo, AppError> {
+        self.timer
+            .time("saldo.create", self.inner.create(input))
+            .await
+    }
+
+    async fn create_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &CreateSaldoRequest,
+    ) -> Result<Saldo, AppError> {
+        self.timer
+            .time("saldo.create_tx", self.inner.create_tx(conn, input))
+            .await
+    }
+
+    async fn create_initial_tx(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+        currency: Currency,
+    ) -> Result<Saldo, AppError> {
+        self.timer
+            .time(
+                "saldo.create_initial_tx",
+                self.inner.create_initial_tx(conn, user_id, currency),
+            )
+            .await
+    }
+
+    async fn update(&self, input: &UpdateSaldoRequest) -> Result<Saldo, AppError> {
+        self.timer
+            .time("saldo.update", self.inner.update(input))
+            .await
+    }
+
+    async fn update_balance(&self, input: &UpdateSaldoBalance) -> Result<Saldo, AppError> {
+        self.timer
+            .time("saldo.update_balance", self.inner.update_balance(input))
+            .await
+    }
+
+    async fn update_balance_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &UpdateSaldoBalance,
+    ) -> Result<Saldo, AppError> {
+        self.timer
+            .time(
+                "saldo.update_balance_tx",
+                self.inner.update_balance_tx(conn, input),
+            )
+            .await
+    }
+
+    async fn increment_balance_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &IncrementSaldoBalance,
+    ) -> Result<Option<Saldo>, AppError> {
+        self.timer
+            .time(
+                "saldo.increment_balance_tx",
+                self.inner.increment_balance_tx(conn, input),
+            )
+            .await
+    }
+
+    async fn update_saldo_withdraw(&self, input: &UpdateSaldoWithdraw) -> Result<Saldo, AppError> {
+        self.timer
+            .time(
+                "saldo.update_saldo_withdraw",
+                self.inner.update_saldo_withdraw(input),
+            )
+            .await
+    }
+
+    async fn update_saldo_withdraw_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &UpdateSaldoWithdraw,
+    ) -> Result<Saldo, AppError> {
+        self.timer
+            .time(
+                "saldo.update_saldo_withdraw_tx",
+                self.inner.update_saldo_withdraw_tx(conn, input),
+            )
+            .await
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), AppError> {
+        self.timer.time("saldo.delete", self.inner.delete(id)).await
+    }
+
+    async fn delete_by_user_tx(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<u64, AppError> {
+        self.timer
+            .time(
+                "saldo.delete_by_user_tx",
+                self.inner.delete_by_user_tx(conn, user_id),
+            )
+            .await
+    }
+
+    async fn find_history(
+        &self,
+        user_id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<SaldoHistory>, i64), AppError> {
+        self.timer
+            .time(
+                "saldo.find_history",
+                self.inner.find_history(user_id, page, page_size),
+            )
+            .await
+    }
+
+    async fn restore(&self, id: i32) -> Result<Saldo, AppError> {
+        self.timer
+            .time("saldo.restore", self.inner.restore(id))
+            .await
+    }
+
+    async fn set_frozen(
+        &self,
+        user_id: i32,
+        frozen: bool,
+        reason: Option<String>,
+    ) -> Result<Vec<Saldo>, AppError> {
+        self.timer
+            .time(
+                "saldo.set_frozen",
+                self.inner.set_frozen(user_id, frozen, reason),
+            )
+            .await
+    }
+
+    async fn stats(&self) -> Result<Vec<SaldoStats>, AppError> {
+        self.timer.time("saldo.stats", self.inner.stats()).await
+    }
+}
+
+#[async_trait]
+impl TransferRepositoryTrait for InstrumentedTransferRepository {
+    async fn find_all(
+        &self,
+        page: i32,
+        page_size: i32,
+        search: Option<Search>,
+        sort: Sort,
+        filter: TransactionFilter,
+    ) -> Result<(Vec<Transfer>, i64), AppError> {
+        self.timer
+            .time(
+                "transfer.find_all",
+                self.inner.find_all(page, page_size, search, sort, filter),
+            )
+            .await
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Transfer>, AppError> {
+        self.timer
+            .time("transfer.find_by_id", self.inner.find_by_id(id))
+            .await
+    }
+
+    async fn find_receipt(&self, id: i32) -> Result<Option<TransferReceipt>, AppError> {
+        self.timer
+            .time("transfer.find_receipt", self.inner.find_receipt(id))
+            .await
+    }
+
+    async fn find_by_users(
+        &self,
+        id: i32,
+        direction: TransferDirection,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Transfer>, i64), AppError> {
+        self.timer
+            .time(
+                "transfer.find_by_users",
+                self.inner.find_by_users(id, direction, page, page_size),
+            )
+            .await
+    }
+
+    async fn find_by_user(&self, id: i32) -> Result<Option<Transfer>, AppError> {
+        self.timer
+            .time("transfer.find_by_user", self.inner.find_by_user(id))
+            .await
+    }
+
+    async fn sum_sent_by_user(&self, user_id: i32) -> Result<(Money, i64), AppError> {
+        self.timer
+            .time(
+                "transfer.sum_sent_by_user",
+                self.inner.sum_sent_by_user(user_id),
+            )
+            .await
+    }
+
+    async fn sum_received_by_user(&self, user_id: i32) -> Result<(Money, i64), AppError> {
+        self.timer
+            .time(
+                "transfer.sum_received_by_user",
+                self.inner.sum_received_by_user(user_id),
+            )
+            .await
+    }
+
+    async fn net_flow(
+        &self,
+        user_id: i32,
+        counterparty: Option<i32>,
+        from: Option<NaiveDateTime>,
+        to: NaiveDateTime,
+        currency: Currency,
+    ) -> Result<TransferNetFlow, AppError> {
+        self.timer
+            .time(
+                "transfer.net_flow",
+                self.inner
+                    .net_flow(user_id, counterparty, from, to, currency),
+            )
+            .await
+    }
+
+    async fn find_counterparties(
+        &self,
+        user_id: i32,
+        currency: Currency,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<TransferCounterparty>, i64), AppError> {
+        self.timer
+            .time(
+                "transfer.find_counterparties",
+                self.inner
+                    .find_counterparties(user_id, currency, page, page_size),
+            )
+            .await
+    }
+
+    async fn find_between_users(
+        &self,
+        from: i32,
+        to: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Transfer>, i64), AppError> {
+        self.timer
+            .time(
+                "transfer.find_between_users",
+                self.inner.find_between_users(from, to, page, page_size),
+            )
+            .await
+    }
+
+    async fn create(&self, input: &CreateTransferRequest) -> Result<Transfer, AppError> {
+        self.timer
+            .time("transfer.create", self.inner.create(input))
+            .await
+    }
+
+    async fn create_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &CreateTransferRequest,
+    ) -> Result<Transfer, AppError> {
+        self.timer
+            .time("transfer.create_tx", self.inner.create_tx(conn, input))
+            .await
+    }
+
+    async fn update_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &UpdateTransferRequest,
+    ) -> Result<Transfer, AppError> {
+        self.timer
+            .time("transfer.update_tx", self.inner.update_tx(conn, input))
+            .await
+    }
+
+    async fn update_amount(
+        &self,
+        input: &UpdateTransferAmountRequest,
+    ) -> Result<Transfer, AppError> {
+        self.timer
+            .time("transfer.update_amount", self.inner.update_amount(input))
+            .await
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), AppError> {
+        self.timer
+            .time("transfer.delete", self.inner.delete(id))
+            .await
+    }
+
+    async fn delete_tx(&self, conn: &mut PgConnection, id: i32) -> Result<Transfer, AppError> {
+        self.timer
+            .time("transfer.delete_tx", self.inner.delete_tx(conn, id))
+            .await
+    }
+
+    async fn delete_unsettled_by_user_tx(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<u64, AppError> {
+        self.timer
+            .time(
+                "transfer.delete_unsettled_by_user_tx",
+                self.inner.delete_unsettled_by_user_tx(conn, user_id),
+            )
+            .await
+    }
+
+    async fn restore(&self, id: i32) -> Result<Transfer, AppError> {
+        self.timer
+            .time("transfer.restore", self.inner.restore(id))
+            .await
+    }
+
+    async fn restore_tx(&self, conn: &mut PgConnection, id: i32) -> Result<Transfer, AppError> {
+        self.timer
+            .time("transfer.restore_tx", self.inner.restore_tx(conn, id))
+            .await
+    }
+
+    async fn create_scheduled(
+        &self,
+        input: &CreateTransferRequest,
+        scheduled_at: NaiveDateTime,
+    ) -> Result<Transfer, AppError> {
+        self.timer
+            .time(
+                "transfer.create_scheduled",
+                self.inner.create_scheduled(input, scheduled_at),
+            )
+            .await
+    }
+
+    async fn find_scheduled(&self, transfer_from: Option<i32>) -> Result<Vec<Transfer>, AppError> {
+        self.timer
+            .time(
+                "transfer.find_scheduled",
+                self.inner.find_scheduled(transfer_from),
+            )
+            .await
+    }
+
+    async fn find_due(&self, now: NaiveDateTime, limit: u64) -> Result<Vec<Transfer>, AppError> {
+        self.timer
+            .time("transfer.find_due", self.inner.find_due(now, limit))
+            .await
+    }
+
+    async fn find_by_id_for_update_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+    ) -> Result<Option<Transfer>, AppError> {
+        self.timer
+            .time(
+                "transfer.find_by_id_for_update_tx",
+                self.inner.find_by_id_for_update_tx(conn, id),
+            )
+            .await
+    }
+
+    async fn claim_pending_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+    ) -> Result<Option<Transfer>, AppError> {
+        self.timer
+            .time(
+                "transfer.claim_pending_tx",
+                self.inner.claim_pending_tx(conn, id),
+            )
+            .await
+    }
+
+    async fn set_status_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+        status: TransferStatus,
+    ) -> Result<Transfer, AppError> {
+        self.timer
+            .time(
+                "transfer.set_status_tx",
+                self.inner.set_status_tx(conn, id, status),
+            )
+            .await
+    }
+
+    async fn mark_reversed_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+    ) -> Result<Option<Transfer>, AppError> {
+        self.timer
+            .time(
+                "transfer.mark_reversed_tx",
+                self.inner.mark_reversed_tx(conn, id),
+            )
+            .await
+    }
+
+    async fn create_reversal_tx(
+        &self,
+        conn: &mut PgConnection,
+        original: &Transfer,
+    ) -> Result<Transfer, AppError> {
+        self.timer
+            .time(
+                "transfer.create_reversal_tx",
+                self.inner.create_reversal_tx(conn, original),
+            )
+            .await
+    }
+
+    async fn charge_fee_tx(
+        &self,
+        conn: &mut PgConnection,
+        transfer: &Transfer,
+        fee: Money,
+    ) -> Result<Transfer, AppError> {
+        self.timer
+            .time(
+                "transfer.charge_fee_tx",
+                self.inner.charge_fee_tx(conn, transfer, fee),
+            )
+            .await
+    }
+
+    async fn refund_fee_tx(
+        &self,
+        conn: &mut PgConnection,
+        transfer: &Transfer,
+    ) -> Result<(), AppError> {
+        self.timer
+            .time(
+                "transfer.refund_fee_tx",
+                self.inner.refund_fee_tx(conn, transfer),
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::fmt::MakeWriter;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Captured {
+        fn output(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    impl std::io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Captured {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    async fn run_query(sleep: Duration) -> String {
+        let captured = Captured::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(captured.clone())
+            .with_max_level(tracing::Level::WARN)
+            .with_ansi(false)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let timer = QueryTimer::new(Duration::from_millis(20));
+        let rows = timer
+            .time("saldo.find_all", async {
+                tokio::time::sleep(sleep).await;
+                3
+            })
+            .await;
+        assert_eq!(rows, 3);
+
+        captured.output()
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn slow_query_logs_a_warning() {
+        let output = run_query(Duration::from_millis(60)).await;
+
+        assert!(output.contains("WARN"), "{output}");
+        assert!(output.contains("Slow query saldo.find_all"), "{output}");
+        assert!(output.contains("threshold 20ms"), "{output}");
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn fast_query_stays_quiet() {
+        let output = run_query(Duration::ZERO).await;
+
+        assert!(!output.contains("Slow query"), "{output}");
+    }
+}
@@ -0,0 +1,105 @@
+use async_trait::async_trait;
+use sea_query::{Expr, Order, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use tracing::{error, info};
+
+use crate::abstract_trait::LoginAuditRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::domain::request::auth::LoginContext;
+use crate::model::login_audit::LoginAudit;
+use crate::schema::login_audit::LoginAudit as LoginAuditSchema;
+use crate::utils::AppError;
+
+pub struct LoginAuditRepository {
+    db_pool: ConnectionPool,
+}
+
+impl LoginAuditRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl LoginAuditRepositoryTrait for LoginAuditRepository {
+    async fn create(
+        &self,
+        user_id: Option<i32>,
+        email: &str,
+        context: &LoginContext,
+        success: bool,
+    ) -> Result<LoginAudit, AppError> {
+        let (sql, values) = Query::insert()
+            .into_table(LoginAuditSchema::Table)
+            .columns([
+                LoginAuditSchema::UserId,
+                LoginAuditSchema::Email,
+                LoginAuditSchema::Ip,
+                LoginAuditSchema::UserAgent,
+                LoginAuditSchema::Success,
+            ])
+            .values_panic([
+                user_id.into(),
+                email.into(),
+                context.ip.clone().into(),
+                context.user_agent.clone().into(),
+                success.into(),
+            ])
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let audit = sqlx::query_as_with::<_, LoginAudit, _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [LoginAudit] Failed to record login attempt for {email}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "📝 [LoginAudit] Recorded {} login {} for {email}",
+            if success { "successful" } else { "failed" },
+            audit.login_audit_id
+        );
+
+        Ok(audit)
+    }
+
+    async fn find_recent_by_user(
+        &self,
+        user_id: i32,
+        limit: u64,
+    ) -> Result<Vec<LoginAudit>, AppError> {
+        let (sql, values) = Query::select()
+            .columns([
+                LoginAuditSchema::LoginAuditId,
+                LoginAuditSchema::UserId,
+                LoginAuditSchema::Email,
+                LoginAuditSchema::Ip,
+                LoginAuditSchema::UserAgent,
+                LoginAuditSchema::Success,
+                LoginAuditSchema::CreatedAt,
+            ])
+            .from(LoginAuditSchema::Table)
+            .and_where(Expr::col(LoginAuditSchema::UserId).eq(user_id))
+            .order_by(LoginAuditSchema::CreatedAt, Order::Desc)
+            .order_by(LoginAuditSchema::LoginAuditId, Order::Desc)
+            .limit(limit)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let audits = sqlx::query_as_with::<_, LoginAudit, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [LoginAudit] Failed to fetch logins for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "📋 [LoginAudit] Found {} login(s) for user_id={user_id}",
+            audits.len()
+        );
+
+        Ok(audits)
+    }
+}
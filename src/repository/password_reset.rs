@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use sea_query::{Cond, Expr, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::PgConnection;
+use tracing::{error, info};
+
+use crate::abstract_trait::PasswordResetRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::model::password_reset::PasswordReset;
+use crate::schema::password_reset::PasswordResets;
+use crate::utils::AppError;
+
+pub struct PasswordResetRepository {
+    db_pool: ConnectionPool,
+}
+
+impl PasswordResetRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl PasswordResetRepositoryTrait for PasswordResetRepository {
+    async fn create(
+        &self,
+        user_id: i32,
+        token_hash: &str,
+        expires_at: NaiveDateTime,
+    ) -> Result<PasswordReset, AppError> {
+        info!("🔑 [PasswordReset] Creating reset token for user_id={user_id}");
+
+        let (sql, values) = Query::insert()
+            .into_table(PasswordResets::Table)
+            .columns([
+                PasswordResets::UserId,
+                PasswordResets::TokenHash,
+                PasswordResets::ExpiresAt,
+            ])
+            .values_panic([user_id.into(), token_hash.into(), expires_at.into()])
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let reset = sqlx::query_as_with::<_, PasswordReset, _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [PasswordReset] Failed to create reset token for user_id={user_id}: {e}"
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [PasswordReset] Reset token {} created for user_id={user_id}",
+            reset.password_reset_id
+        );
+
+        Ok(reset)
+    }
+
+    // Marks the token used in the same statement that checks it, so two
+    // concurrent resets with the same token cannot both succeed.
+    async fn consume_tx(
+        &self,
+        conn: &mut PgConnection,
+        token_hash: &str,
+    ) -> Result<Option<PasswordReset>, AppError> {
+        info!("🔑 [PasswordReset] Consuming reset token");
+
+        let now = Utc::now().naive_utc();
+
+        let (sql, values) = Query::update()
+            .table(PasswordResets::Table)
+            .values([(PasswordResets::UsedAt, now.into())])
+            .and_where(Expr::col(PasswordResets::TokenHash).eq(token_hash))
+            .and_where(Expr::col(PasswordResets::UsedAt).is_null())
+            .and_where(Expr::col(PasswordResets::ExpiresAt).gt(now))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let reset = sqlx::query_as_with::<_, PasswordReset, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [PasswordReset] Failed to consume reset token: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        match &reset {
+            Some(r) => info!(
+                "✅ [PasswordReset] Token {} consumed for user_id={}",
+                r.password_reset_id, r.user_id
+            ),
+            None => info!("⚠️ [PasswordReset] Token is unknown, expired or already used"),
+        }
+
+        Ok(reset)
+    }
+
+    // Expired and already-used tokens can never be redeemed again.
+    async fn delete_expired(&self) -> Result<u64, AppError> {
+        let now = Utc::now().naive_utc();
+
+        let (sql, values) = Query::delete()
+            .from_table(PasswordResets::Table)
+            .cond_where(
+                Cond::any()
+                    .add(Expr::col(PasswordResets::ExpiresAt).lte(now))
+                    .add(Expr::col(PasswordResets::UsedAt).is_not_null()),
+            )
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [PasswordReset] Failed to delete expired tokens: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "🔑 [PasswordReset] Deleted {} expired or used token(s)",
+            result.rows_affected()
+        );
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,206 @@
+use async_trait::async_trait;
+use sea_query::{
+    Alias, Expr, Func, IntoColumnRef, Order, PostgresQueryBuilder, Query, SelectStatement,
+    SimpleExpr,
+};
+use sea_query_binder::SqlxBinder;
+use tracing::{error, info};
+
+use crate::abstract_trait::ReconciliationRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::domain::request::saldo_history::BalanceChangeReason;
+use crate::domain::{transfer_status::TransferStatus, withdraw_status::WithdrawStatus};
+use crate::model::reconciliation::Reconciliation;
+use crate::schema::{
+    fee::Fees, saldo::Saldo, saldo_history::SaldoHistory, topup::Topups, transfer::Transfers,
+    withdraw::Withdraws,
+};
+use crate::utils::AppError;
+
+pub struct ReconciliationRepository {
+    db_pool: ConnectionPool,
+}
+
+impl ReconciliationRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+
+    // COALESCE((SELECT SUM(...) ...), 0) so an empty ledger counts as zero.
+    fn ledger_sum(sum: SelectStatement) -> SimpleExpr {
+        Func::coalesce([
+            SimpleExpr::SubQuery(None, Box::new(sum.into_sub_query_statement())),
+            Expr::val(0i64).into(),
+        ])
+        .into()
+    }
+
+    // Correlates a ledger subquery with the outer saldo row.
+    fn same_saldo(
+        query: &mut SelectStatement,
+        user_id: impl IntoColumnRef,
+        currency: impl IntoColumnRef,
+    ) -> &mut SelectStatement {
+        query
+            .and_where(Expr::col(user_id).equals((Saldo::Table, Saldo::UserId)))
+            .and_where(Expr::col(currency).equals((Saldo::Table, Saldo::Currency)))
+    }
+
+    // Replays the ledger on top of the opening balance: topups and settled
+    // transfers in, minus settled transfers out, settled withdraws and fees,
+    // plus manual adjustments recorded in the saldo history.
+    fn computed_balance() -> SimpleExpr {
+        let topups = Self::same_saldo(
+            Query::select()
+                .expr(Func::sum(Expr::col((Topups::Table, Topups::TopupAmount))))
+                .from(Topups::Table),
+            (Topups::Table, Topups::UserId),
+            (Topups::Table, Topups::Currency),
+        )
+        .and_where(Expr::col((Topups::Table, Topups::DeletedAt)).is_null())
+        .to_owned();
+
+        let transfers = |party: Transfers| {
+            Self::same_saldo(
+                Query::select()
+                    .expr(Func::sum(Expr::col((
+                        Transfers::Table,
+                        Transfers::TransferAmount,
+                    ))))
+                    .from(Transfers::Table),
+                (Transfers::Table, party),
+                (Transfers::Table, Transfers::Currency),
+            )
+            .and_where(Expr::col((Transfers::Table, Transfers::DeletedAt)).is_null())
+            .and_where(
+                Expr::col((Transfers::Table, Transfers::Status)).is_in(TransferStatus::SETTLED),
+            )
+            .to_owned()
+        };
+
+        let withdraws = Self::same_saldo(
+            Query::select()
+                .expr(Func::sum(Expr::col((
+                    Withdraws::Table,
+                    Withdraws::WithdrawAmount,
+                ))))
+                .from(Withdraws::Table),
+            (Withdraws::Table, Withdraws::UserId),
+            (Withdraws::Table, Withdraws::Currency),
+        )
+        .and_where(Expr::col((Withdraws::Table, Withdraws::DeletedAt)).is_null())
+        .and_where(Expr::col((Withdraws::Table, Withdraws::Status)).is_in(WithdrawStatus::SETTLED))
+        .to_owned();
+
+        // Fees stay charged when their transfer is reversed, so unlike the
+        // transfers themselves they aren't filtered by status.
+        let fees = Self::same_saldo(
+            Query::select()
+                .expr(Func::sum(Expr::col((Fees::Table, Fees::Amount))))
+                .from(Fees::Table),
+            (Fees::Table, Fees::UserId),
+            (Fees::Table, Fees::Currency),
+        )
+        .to_owned();
+
+        let adjustments = Query::select()
+            .expr(Func::sum(
+                Expr::col((SaldoHistory::Table, SaldoHistory::NewBalance))
+                    .sub(Expr::col((SaldoHistory::Table, SaldoHistory::OldBalance))),
+            ))
+            .from(SaldoHistory::Table)
+            .and_where(
+                Expr::col((SaldoHistory::Table, SaldoHistory::SaldoId))
+                    .equals((Saldo::Table, Saldo::SaldoId)),
+            )
+            .and_where(
+                Expr::col((SaldoHistory::Table, SaldoHistory::Reason))
+                    .eq(BalanceChangeReason::Adjustment),
+            )
+            .to_owned();
+
+        let computed = Expr::col((Saldo::Table, Saldo::OpeningBalance))
+            .add(Self::ledger_sum(topups))
+            .add(Self::ledger_sum(transfers(Transfers::TransferTo)))
+            .sub(Self::ledger_sum(transfers(Transfers::TransferFrom)))
+            .sub(Self::ledger_sum(withdraws))
+            .sub(Self::ledger_sum(fees))
+            .add(Self::ledger_sum(adjustments));
+
+        // SUM over BIGINT yields NUMERIC.
+        Func::cast_as(computed, Alias::new("BIGINT")).into()
+    }
+
+    fn reconciliation_query() -> SelectStatement {
+        Query::select()
+            .column((Saldo::Table, Saldo::SaldoId))
+            .column((Saldo::Table, Saldo::UserId))
+            .column((Saldo::Table, Saldo::Currency))
+            .expr_as(
+                Expr::col((Saldo::Table, Saldo::TotalBalance)),
+                Alias::new("stored"),
+            )
+            .expr_as(Self::computed_balance(), Alias::new("computed"))
+            .from(Saldo::Table)
+            .and_where(Expr::col((Saldo::Table, Saldo::DeletedAt)).is_null())
+            .order_by((Saldo::Table, Saldo::UserId), Order::Asc)
+            .order_by((Saldo::Table, Saldo::Currency), Order::Asc)
+            .to_owned()
+    }
+}
+
+#[async_trait]
+impl ReconciliationRepositoryTrait for ReconciliationRepository {
+    async fn find_by_user(&self, user_id: i32) -> Result<Vec<Reconciliation>, AppError> {
+        info!("🧮 [Reconciliation] Reconciling saldos for user_id={user_id}");
+
+        let (sql, values) = Self::reconciliation_query()
+            .and_where(Expr::col((Saldo::Table, Saldo::UserId)).eq(user_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 [Reconciliation] Generated SQL: {sql} | Values: {:?}",
+            values
+        );
+
+        let rows = sqlx::query_as_with::<_, Reconciliation, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Reconciliation] Failed to reconcile user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [Reconciliation] Reconciled {} saldos for user_id={user_id}",
+            rows.len()
+        );
+
+        Ok(rows)
+    }
+
+    async fn find_mismatches(&self) -> Result<Vec<Reconciliation>, AppError> {
+        info!("🧮 [Reconciliation] Scanning all saldos for mismatches");
+
+        let (sql, values) = Self::reconciliation_query()
+            .and_where(Expr::col((Saldo::Table, Saldo::TotalBalance)).ne(Self::computed_balance()))
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 [Reconciliation] Generated SQL: {sql} | Values: {:?}",
+            values
+        );
+
+        let rows = sqlx::query_as_with::<_, Reconciliation, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Reconciliation] Failed to scan saldos: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!("✅ [Reconciliation] Found {} mismatched saldos", rows.len());
+
+        Ok(rows)
+    }
+}
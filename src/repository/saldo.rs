@@ -1,21 +1,41 @@
-use crate::model::saldo::Saldo;
+use crate::domain::request::saldo_history::BalanceChangeReason;
+use crate::domain::request::search::Search;
+use crate::domain::request::sort::{Sort, invalid_sort_by};
+use crate::domain::{currency::Currency, money::Money};
+use crate::model::saldo::{Saldo, SaldoStats};
+use crate::model::saldo_history::SaldoHistory;
 use crate::schema::saldo::Saldo as SaldoSchema;
-use crate::utils::AppError;
+use crate::schema::saldo_history::SaldoHistory as SaldoHistorySchema;
+use crate::utils::{AppError, retry_transient};
 use crate::{
     abstract_trait::SaldoRepositoryTrait,
     config::ConnectionPool,
     domain::request::saldo::{
-        CreateSaldoRequest, UpdateSaldoBalance, UpdateSaldoRequest, UpdateSaldoWithdraw,
+        CreateSaldoRequest, IncrementSaldoBalance, UpdateSaldoBalance, UpdateSaldoRequest,
+        UpdateSaldoWithdraw,
     },
 };
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::{NaiveDateTime, Utc};
-use sea_query::{Expr, Func, Order, PostgresQueryBuilder, Query, SimpleExpr, Value};
+use sea_query::{
+    Alias, Expr, Func, Keyword, LockType, OnConflict, Order, PostgresQueryBuilder, Query,
+    SimpleExpr, Value,
+};
 use sea_query_binder::SqlxBinder;
-use sqlx::Row;
+use sqlx::{PgConnection, Row};
 use tracing::{error, info};
 
+const SORTABLE_COLUMNS: &[&str] = &[
+    "id",
+    "saldo_id",
+    "user_id",
+    "total_balance",
+    "amount",
+    "created_at",
+    "updated_at",
+];
+
 pub struct SaldoRepository {
     db_pool: ConnectionPool,
 }
@@ -24,6 +44,65 @@ impl SaldoRepository {
     pub fn new(db_pool: ConnectionPool) -> Self {
         Self { db_pool }
     }
+
+    fn sort_column(sort_by: Option<&str>) -> Result<SaldoSchema, AppError> {
+        match sort_by.filter(|s| !s.is_empty()).unwrap_or("created_at") {
+            "id" | "saldo_id" => Ok(SaldoSchema::SaldoId),
+            "user_id" => Ok(SaldoSchema::UserId),
+            "total_balance" | "amount" => Ok(SaldoSchema::TotalBalance),
+            "created_at" => Ok(SaldoSchema::CreatedAt),
+            "updated_at" => Ok(SaldoSchema::UpdatedAt),
+            other => Err(invalid_sort_by(other, SORTABLE_COLUMNS)),
+        }
+    }
+
+    // Appends to the saldo_history ledger. Always called on the same
+    // connection/transaction as the balance update it describes.
+    async fn record_history(
+        conn: &mut PgConnection,
+        saldo: &Saldo,
+        old_balance: Money,
+        reason: BalanceChangeReason,
+        reference_id: Option<i32>,
+    ) -> Result<(), AppError> {
+        let (sql, values) = Query::insert()
+            .into_table(SaldoHistorySchema::Table)
+            .columns([
+                SaldoHistorySchema::SaldoId,
+                SaldoHistorySchema::UserId,
+                SaldoHistorySchema::OldBalance,
+                SaldoHistorySchema::NewBalance,
+                SaldoHistorySchema::Reason,
+                SaldoHistorySchema::ReferenceId,
+            ])
+            .values_panic([
+                saldo.saldo_id.into(),
+                saldo.user_id.into(),
+                old_balance.into(),
+                saldo.total_balance.into(),
+                reason.into(),
+                reference_id.into(),
+            ])
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Saldo] Failed to record balance history for saldo_id={}: {e}",
+                    saldo.saldo_id
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "📒 [Saldo] Recorded {reason} for saldo_id={}: {old_balance} → {}",
+            saldo.saldo_id, saldo.total_balance
+        );
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -32,13 +111,17 @@ impl SaldoRepositoryTrait for SaldoRepository {
         &self,
         page: i32,
         page_size: i32,
-        search: Option<String>,
+        search: Option<Search>,
+        sort: Sort,
+        include_deleted: bool,
     ) -> Result<(Vec<Saldo>, i64), AppError> {
         info!(
             "💰 [Saldos] Fetching all saldo records - page: {page}, page_size: {page_size}, search: {:?}",
             search
         );
 
+        let sort_column = Self::sort_column(sort.by.as_deref())?;
+
         let page = if page > 0 { page } else { 1 };
         let page_size = if page_size > 0 { page_size } else { 10 };
         let offset = (page - 1) * page_size;
@@ -55,15 +138,27 @@ impl SaldoRepositoryTrait for SaldoRepository {
                 SaldoSchema::WithdrawTime,
                 SaldoSchema::CreatedAt,
                 SaldoSchema::UpdatedAt,
+                SaldoSchema::DeletedAt,
+                SaldoSchema::Currency,
+                SaldoSchema::IsFrozen,
+                SaldoSchema::FrozenReason,
             ])
             .from(SaldoSchema::Table)
-            .order_by(SaldoSchema::SaldoId, Order::Asc)
+            .order_by(sort_column, sort.order.into())
+            .order_by(SaldoSchema::SaldoId, sort.order.into())
             .limit(page_size as u64)
             .offset(offset as u64);
 
-        if let Some(ref term) = search {
-            select_query.and_where(Expr::col(SaldoSchema::UserId).like(format!("{term}%")));
-            info!("🔍 [Saldos] Filtering by user_id prefix: {term}%");
+        if !include_deleted {
+            select_query.and_where(Expr::col(SaldoSchema::DeletedAt).is_null());
+        }
+
+        if let Some(ref search) = search {
+            select_query.and_where(search.matches_id(SaldoSchema::UserId));
+            info!(
+                "🔍 [Saldos] Filtering by user_id ({:?}): {}",
+                search.mode, search.term
+            );
         }
 
         let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
@@ -92,8 +187,12 @@ impl SaldoRepositoryTrait for SaldoRepository {
             .expr(Func::count(Expr::col(SaldoSchema::SaldoId)))
             .from(SaldoSchema::Table);
 
-        if let Some(ref term) = search {
-            count_query.and_where(Expr::col(SaldoSchema::UserId).like(format!("{term}%")));
+        if !include_deleted {
+            count_query.and_where(Expr::col(SaldoSchema::DeletedAt).is_null());
+        }
+
+        if let Some(ref search) = search {
+            count_query.and_where(search.matches_id(SaldoSchema::UserId));
         }
 
         let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
@@ -138,8 +237,13 @@ impl SaldoRepositoryTrait for SaldoRepository {
                 SaldoSchema::WithdrawTime,
                 SaldoSchema::CreatedAt,
                 SaldoSchema::UpdatedAt,
+                SaldoSchema::DeletedAt,
+                SaldoSchema::Currency,
+                SaldoSchema::IsFrozen,
+                SaldoSchema::FrozenReason,
             ])
             .and_where(Expr::col(SaldoSchema::SaldoId).eq(id))
+            .and_where(Expr::col(SaldoSchema::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
         info!("🧾 [Saldo] Executing query: {sql} | Values: {:?}", values);
@@ -167,8 +271,12 @@ impl SaldoRepositoryTrait for SaldoRepository {
         Ok(row)
     }
 
-    async fn find_by_user_id(&self, user_id: i32) -> Result<Option<Saldo>, AppError> {
-        info!("👤 [Saldo] Finding saldo for user_id: {}", user_id);
+    async fn find_by_user_and_currency(
+        &self,
+        user_id: i32,
+        currency: Currency,
+    ) -> Result<Option<Saldo>, AppError> {
+        info!("👤 [Saldo] Finding {currency} saldo for user_id: {user_id}");
 
         let (sql, values) = Query::select()
             .from(SaldoSchema::Table)
@@ -180,8 +288,14 @@ impl SaldoRepositoryTrait for SaldoRepository {
                 SaldoSchema::WithdrawTime,
                 SaldoSchema::CreatedAt,
                 SaldoSchema::UpdatedAt,
+                SaldoSchema::DeletedAt,
+                SaldoSchema::Currency,
+                SaldoSchema::IsFrozen,
+                SaldoSchema::FrozenReason,
             ])
             .and_where(Expr::col(SaldoSchema::UserId).eq(user_id))
+            .and_where(Expr::col(SaldoSchema::Currency).eq(currency))
+            .and_where(Expr::col(SaldoSchema::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
         info!("🧾 [Saldo] Executing query: {sql} | Values: {:?}", values);
@@ -209,8 +323,13 @@ impl SaldoRepositoryTrait for SaldoRepository {
         Ok(row)
     }
 
-    async fn find_by_users_id(&self, user_id: i32) -> Result<Vec<Saldo>, AppError> {
-        info!("👥 [Saldo] Finding all saldos for user_id: {user_id}");
+    async fn find_by_user_and_currency_for_update(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+        currency: Currency,
+    ) -> Result<Option<Saldo>, AppError> {
+        info!("🔒 [Saldo] Locking {currency} saldo for user_id: {user_id}");
 
         let (sql, values) = Query::select()
             .from(SaldoSchema::Table)
@@ -222,9 +341,107 @@ impl SaldoRepositoryTrait for SaldoRepository {
                 SaldoSchema::WithdrawTime,
                 SaldoSchema::CreatedAt,
                 SaldoSchema::UpdatedAt,
+                SaldoSchema::DeletedAt,
+                SaldoSchema::Currency,
+                SaldoSchema::IsFrozen,
+                SaldoSchema::FrozenReason,
             ])
             .and_where(Expr::col(SaldoSchema::UserId).eq(user_id))
+            .and_where(Expr::col(SaldoSchema::Currency).eq(currency))
+            .and_where(Expr::col(SaldoSchema::DeletedAt).is_null())
+            .lock(LockType::Update)
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [Saldo] Executing query: {sql} | Values: {:?}", values);
+
+        let row = sqlx::query_as_with::<_, Saldo, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Saldo] Failed to lock saldo for user_id={user_id}: {e}",);
+                AppError::SqlxError(e)
+            })?;
+
+        if row.is_none() {
+            info!("🟡 [Saldo] No saldo found for user_id={user_id}");
+        }
+
+        Ok(row)
+    }
+
+    async fn find_by_user_for_update(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<Vec<Saldo>, AppError> {
+        info!("🔒 [Saldo] Locking all saldos for user_id: {user_id}");
+
+        let (sql, values) = Query::select()
+            .from(SaldoSchema::Table)
+            .columns([
+                SaldoSchema::SaldoId,
+                SaldoSchema::UserId,
+                SaldoSchema::TotalBalance,
+                SaldoSchema::WithdrawAmount,
+                SaldoSchema::WithdrawTime,
+                SaldoSchema::CreatedAt,
+                SaldoSchema::UpdatedAt,
+                SaldoSchema::DeletedAt,
+                SaldoSchema::Currency,
+                SaldoSchema::IsFrozen,
+                SaldoSchema::FrozenReason,
+            ])
+            .and_where(Expr::col(SaldoSchema::UserId).eq(user_id))
+            .and_where(Expr::col(SaldoSchema::DeletedAt).is_null())
             .order_by(SaldoSchema::SaldoId, Order::Asc)
+            .lock(LockType::Update)
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [Saldo] Executing query: {sql} | Values: {:?}", values);
+
+        sqlx::query_as_with::<_, Saldo, _>(&sql, values)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Saldo] Failed to lock saldos for user_id={user_id}: {e}",);
+                AppError::SqlxError(e)
+            })
+    }
+
+    async fn find_by_users_id(
+        &self,
+        user_id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Saldo>, i64), AppError> {
+        info!(
+            "👥 [Saldo] Finding saldos for user_id: {user_id} - page: {page}, page_size: {page_size}"
+        );
+
+        let page = if page > 0 { page } else { 1 };
+        let page_size = if page_size > 0 { page_size } else { 10 };
+        let offset = (page - 1) * page_size;
+
+        let (sql, values) = Query::select()
+            .from(SaldoSchema::Table)
+            .columns([
+                SaldoSchema::SaldoId,
+                SaldoSchema::UserId,
+                SaldoSchema::TotalBalance,
+                SaldoSchema::WithdrawAmount,
+                SaldoSchema::WithdrawTime,
+                SaldoSchema::CreatedAt,
+                SaldoSchema::UpdatedAt,
+                SaldoSchema::DeletedAt,
+                SaldoSchema::Currency,
+                SaldoSchema::IsFrozen,
+                SaldoSchema::FrozenReason,
+            ])
+            .and_where(Expr::col(SaldoSchema::UserId).eq(user_id))
+            .and_where(Expr::col(SaldoSchema::DeletedAt).is_null())
+            .order_by(SaldoSchema::SaldoId, Order::Asc)
+            .limit(page_size as u64)
+            .offset(offset as u64)
             .build_sqlx(PostgresQueryBuilder);
 
         info!("🧾 [Saldo] Executing query: {sql} | Values: {:?}", values);
@@ -237,15 +454,43 @@ impl SaldoRepositoryTrait for SaldoRepository {
                 AppError::SqlxError(e)
             })?;
 
+        let (count_sql, count_values) = Query::select()
+            .expr(Func::count(Expr::col(SaldoSchema::SaldoId)))
+            .from(SaldoSchema::Table)
+            .and_where(Expr::col(SaldoSchema::UserId).eq(user_id))
+            .and_where(Expr::col(SaldoSchema::DeletedAt).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (total,) = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Saldo] Failed to count saldos for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
         info!(
-            "✅ [Saldo] Retrieved {} saldo record(s) for user_id={user_id}",
+            "✅ [Saldo] Retrieved {} of {total} saldo record(s) for user_id={user_id}",
             rows.len(),
         );
 
-        Ok(rows)
+        Ok((rows, total))
     }
 
     async fn create(&self, input: &CreateSaldoRequest) -> Result<Saldo, AppError> {
+        let mut conn = self.db_pool.acquire().await.map_err(|e| {
+            error!("❌ [Saldo] Failed to acquire connection: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        self.create_tx(&mut conn, input).await
+    }
+
+    async fn create_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &CreateSaldoRequest,
+    ) -> Result<Saldo, AppError> {
         info!(
             "➕ [Saldo] Creating new saldo for user_id={} with balance={}",
             input.user_id, input.total_balance
@@ -260,12 +505,16 @@ impl SaldoRepositoryTrait for SaldoRepository {
                 SaldoSchema::TotalBalance,
                 SaldoSchema::CreatedAt,
                 SaldoSchema::UpdatedAt,
+                SaldoSchema::Currency,
+                SaldoSchema::OpeningBalance,
             ])
             .values([
                 input.user_id.into(),
                 input.total_balance.into(),
                 now.into(),
                 now.into(),
+                input.currency.into(),
+                input.total_balance.into(),
             ])
             .unwrap()
             .returning_all()
@@ -274,9 +523,25 @@ impl SaldoRepositoryTrait for SaldoRepository {
         info!("🧾 [Saldo] INSERT query: {sql} | Values: {:?}", values);
 
         let inserted: Saldo = sqlx::query_as_with::<_, Saldo, _>(&sql, values)
-            .fetch_one(&self.db_pool)
+            .fetch_one(&mut *conn)
             .await
             .map_err(|e| {
+                let duplicate = e.as_database_error().is_some_and(|db_err| {
+                    db_err.is_unique_violation()
+                        && db_err.constraint() == Some("uq_saldo_user_currency")
+                });
+
+                if duplicate {
+                    error!(
+                        "🛑 [Saldo] user_id={} already has a {} saldo",
+                        input.user_id, input.currency
+                    );
+                    return AppError::Conflict(format!(
+                        "User id {} already has a {} saldo",
+                        input.user_id, input.currency
+                    ));
+                }
+
                 error!(
                     "❌ [Saldo] Failed to create saldo for user_id={}: {e}",
                     input.user_id,
@@ -292,18 +557,106 @@ impl SaldoRepositoryTrait for SaldoRepository {
         Ok(inserted)
     }
 
+    async fn create_initial_tx(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+        currency: Currency,
+    ) -> Result<Saldo, AppError> {
+        info!("➕ [Saldo] Ensuring user_id={user_id} has a {currency} saldo");
+
+        let now = chrono::Utc::now();
+
+        // Two first topups racing each other both get here; the loser's
+        // insert is a no-op and it picks up the winner's row below.
+        let (sql, values) = Query::insert()
+            .into_table(SaldoSchema::Table)
+            .columns([
+                SaldoSchema::UserId,
+                SaldoSchema::TotalBalance,
+                SaldoSchema::CreatedAt,
+                SaldoSchema::UpdatedAt,
+                SaldoSchema::Currency,
+                SaldoSchema::OpeningBalance,
+            ])
+            .values([
+                user_id.into(),
+                Money::ZERO.into(),
+                now.into(),
+                now.into(),
+                currency.into(),
+                Money::ZERO.into(),
+            ])
+            .unwrap()
+            .on_conflict(
+                OnConflict::columns([SaldoSchema::UserId, SaldoSchema::Currency])
+                    .target_and_where(Expr::col(SaldoSchema::DeletedAt).is_null())
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [Saldo] INSERT query: {sql} | Values: {:?}", values);
+
+        let inserted = sqlx::query_as_with::<_, Saldo, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Saldo] Failed to create initial saldo for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        if let Some(saldo) = inserted {
+            info!(
+                "✅ [Saldo] Created initial saldo ID: {} for user_id={user_id}",
+                saldo.saldo_id
+            );
+            return Ok(saldo);
+        }
+
+        self.find_by_user_and_currency_for_update(conn, user_id, currency)
+            .await?
+            .ok_or_else(|| {
+                error!(
+                    "❌ [Saldo] {currency} saldo of user_id={user_id} vanished after a conflict"
+                );
+                AppError::InternalError(format!(
+                    "Could not open a {currency} saldo for user id {user_id}"
+                ))
+            })
+    }
+
     async fn update(&self, input: &UpdateSaldoRequest) -> Result<Saldo, AppError> {
         info!("🔄 [Saldo] Updating saldo with ID: {}", input.saldo_id);
 
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("❌ [Saldo] Failed to begin transaction: {e}");
+            AppError::SqlxError(e)
+        })?;
+
         let (sql, values) = Query::select()
             .from(SaldoSchema::Table)
-            .columns([SaldoSchema::SaldoId, SaldoSchema::TotalBalance])
+            .columns([
+                SaldoSchema::SaldoId,
+                SaldoSchema::UserId,
+                SaldoSchema::TotalBalance,
+                SaldoSchema::WithdrawAmount,
+                SaldoSchema::WithdrawTime,
+                SaldoSchema::CreatedAt,
+                SaldoSchema::UpdatedAt,
+                SaldoSchema::DeletedAt,
+                SaldoSchema::Currency,
+                SaldoSchema::IsFrozen,
+                SaldoSchema::FrozenReason,
+            ])
             .and_where(Expr::col(SaldoSchema::SaldoId).eq(input.saldo_id))
+            .and_where(Expr::col(SaldoSchema::DeletedAt).is_null())
+            .lock(LockType::Update)
             .build_sqlx(PostgresQueryBuilder);
 
-        let saldo_record: Option<(i32, i64)> = sqlx::query_with(&sql, values)
-            .map(|row: sqlx::postgres::PgRow| (row.get("saldo_id"), row.get("total_balance")))
-            .fetch_optional(&self.db_pool)
+        let saldo = sqlx::query_as_with::<_, Saldo, _>(&sql, values)
+            .fetch_optional(&mut *tx)
             .await
             .map_err(|e| {
                 error!(
@@ -311,15 +664,18 @@ impl SaldoRepositoryTrait for SaldoRepository {
                     input.saldo_id,
                 );
                 AppError::SqlxError(e)
-            })?;
+            })?
+            .ok_or_else(|| AppError::NotFound("Saldo not found".into()))?;
+
+        // Checked on the locked row, so a freeze racing this update wins.
+        saldo.ensure_not_frozen()?;
 
-        let (saldo_id, current_balance) =
-            saldo_record.ok_or_else(|| AppError::NotFound("Saldo not found".into()))?;
+        let (saldo_id, current_balance) = (saldo.saldo_id, saldo.total_balance);
 
-        let withdraw_amount = input.withdraw_amount.unwrap_or(0);
-        let updated_balance = current_balance - withdraw_amount as i64;
+        let withdraw_amount = input.withdraw_amount.unwrap_or_default();
+        let updated_balance = current_balance.checked_sub(withdraw_amount)?;
 
-        if updated_balance < 50000 {
+        if updated_balance < Money::new(50000) {
             error!(
                 "⚠️ [Saldo] Insufficient balance after withdrawal: {} - {} = {} < 50000",
                 current_balance, withdraw_amount, updated_balance
@@ -339,6 +695,10 @@ impl SaldoRepositoryTrait for SaldoRepository {
                 (SaldoSchema::TotalBalance, updated_balance.into()),
                 (SaldoSchema::WithdrawAmount, withdraw_amount.into()),
                 (SaldoSchema::WithdrawTime, withdraw_time.into()),
+                (
+                    SaldoSchema::UpdatedAt,
+                    chrono::Utc::now().naive_utc().into(),
+                ),
             ])
             .and_where(Expr::col(SaldoSchema::SaldoId).eq(saldo_id))
             .returning_all()
@@ -350,13 +710,27 @@ impl SaldoRepositoryTrait for SaldoRepository {
         );
 
         let updated: Saldo = sqlx::query_as_with::<_, Saldo, _>(&update_sql, update_values)
-            .fetch_one(&self.db_pool)
+            .fetch_one(&mut *tx)
             .await
             .map_err(|e| {
                 error!("❌ [Saldo] Failed to update saldo ID {saldo_id}: {e}");
                 AppError::SqlxError(e)
             })?;
 
+        Self::record_history(
+            &mut tx,
+            &updated,
+            current_balance,
+            BalanceChangeReason::Adjustment,
+            None,
+        )
+        .await?;
+
+        tx.commit().await.map_err(|e| {
+            error!("❌ [Saldo] Failed to commit update of saldo ID {saldo_id}: {e}");
+            AppError::SqlxError(e)
+        })?;
+
         info!(
             "✅ [Saldo] Successfully updated saldo ID {}: new balance={}, withdraw_amount={}",
             updated.saldo_id,
@@ -368,35 +742,70 @@ impl SaldoRepositoryTrait for SaldoRepository {
     }
 
     async fn update_balance(&self, input: &UpdateSaldoBalance) -> Result<Saldo, AppError> {
+        retry_transient("[Saldo] update_balance", || async {
+            let mut tx = self.db_pool.begin().await.map_err(|e| {
+                error!("❌ [Saldo] Failed to begin transaction: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+            let updated = self.update_balance_tx(&mut tx, input).await?;
+
+            tx.commit().await.map_err(|e| {
+                error!(
+                    "❌ [Saldo] Failed to commit balance update for user_id={}: {e}",
+                    input.user_id
+                );
+                AppError::SqlxError(e)
+            })?;
+
+            Ok(updated)
+        })
+        .await
+    }
+
+    async fn update_balance_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &UpdateSaldoBalance,
+    ) -> Result<Saldo, AppError> {
         info!(
             "💵 [Saldo] Updating balance for user_id={} to {}",
             input.user_id, input.total_balance
         );
 
-        let (sql, values) = Query::select()
+        let (lock_sql, lock_values) = Query::select()
+            .column(SaldoSchema::TotalBalance)
             .from(SaldoSchema::Table)
-            .column(SaldoSchema::SaldoId)
             .and_where(Expr::col(SaldoSchema::UserId).eq(input.user_id))
+            .and_where(Expr::col(SaldoSchema::Currency).eq(input.currency))
+            .and_where(Expr::col(SaldoSchema::DeletedAt).is_null())
+            .lock(LockType::Update)
             .build_sqlx(PostgresQueryBuilder);
 
-        let saldo_id: Option<i32> = sqlx::query_with(&sql, values)
-            .map(|row: sqlx::postgres::PgRow| row.get("saldo_id"))
-            .fetch_optional(&self.db_pool)
+        let (old_balance,): (Money,) = sqlx::query_as_with(&lock_sql, lock_values)
+            .fetch_optional(&mut *conn)
             .await
             .map_err(|e| {
                 error!(
-                    "❌ [Saldo] Database error while fetching saldo_id for user_id={}: {e}",
+                    "❌ [Saldo] Failed to lock saldo for user_id={}: {e}",
                     input.user_id
                 );
                 AppError::SqlxError(e)
-            })?;
-
-        let saldo_id = saldo_id.ok_or(AppError::NotFound("Saldo not found".into()))?;
+            })?
+            .ok_or(AppError::NotFound("Saldo not found".into()))?;
 
         let (update_sql, update_values) = Query::update()
             .table(SaldoSchema::Table)
-            .values([(SaldoSchema::TotalBalance, input.total_balance.into())])
-            .and_where(Expr::col(SaldoSchema::SaldoId).eq(saldo_id))
+            .values([
+                (SaldoSchema::TotalBalance, input.total_balance.into()),
+                (
+                    SaldoSchema::UpdatedAt,
+                    chrono::Utc::now().naive_utc().into(),
+                ),
+            ])
+            .and_where(Expr::col(SaldoSchema::UserId).eq(input.user_id))
+            .and_where(Expr::col(SaldoSchema::Currency).eq(input.currency))
+            .and_where(Expr::col(SaldoSchema::DeletedAt).is_null())
             .returning_all()
             .build_sqlx(PostgresQueryBuilder);
 
@@ -405,13 +814,26 @@ impl SaldoRepositoryTrait for SaldoRepository {
             update_values
         );
 
-        let updated: Saldo = sqlx::query_as_with::<_, Saldo, _>(&update_sql, update_values)
-            .fetch_one(&self.db_pool)
+        let updated = sqlx::query_as_with::<_, Saldo, _>(&update_sql, update_values)
+            .fetch_optional(&mut *conn)
             .await
             .map_err(|e| {
-                error!("❌ [Saldo] Failed to update balance for saldo_id={saldo_id}: {e}",);
+                error!(
+                    "❌ [Saldo] Failed to update balance for user_id={}: {e}",
+                    input.user_id
+                );
                 AppError::SqlxError(e)
-            })?;
+            })?
+            .ok_or(AppError::NotFound("Saldo not found".into()))?;
+
+        Self::record_history(
+            conn,
+            &updated,
+            old_balance,
+            input.reason,
+            input.reference_id,
+        )
+        .await?;
 
         info!(
             "✅ [Saldo] Balance updated successfully: saldo_id={} → {}",
@@ -421,37 +843,134 @@ impl SaldoRepositoryTrait for SaldoRepository {
         Ok(updated)
     }
 
+    async fn increment_balance_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &IncrementSaldoBalance,
+    ) -> Result<Option<Saldo>, AppError> {
+        info!(
+            "💵 [Saldo] Adding {} to the {} balance of user_id={}",
+            input.amount, input.currency, input.user_id
+        );
+
+        // One statement, so concurrent increments queue on the row lock and
+        // each adds to the balance the previous one left.
+        let (sql, values) = Query::update()
+            .table(SaldoSchema::Table)
+            .value(
+                SaldoSchema::TotalBalance,
+                Expr::col(SaldoSchema::TotalBalance).add(input.amount),
+            )
+            .value(SaldoSchema::UpdatedAt, chrono::Utc::now().naive_utc())
+            .and_where(Expr::col(SaldoSchema::UserId).eq(input.user_id))
+            .and_where(Expr::col(SaldoSchema::Currency).eq(input.currency))
+            .and_where(Expr::col(SaldoSchema::DeletedAt).is_null())
+            .and_where(Expr::col(SaldoSchema::IsFrozen).eq(false))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 [Saldo] Executing increment: {sql} | Values: {:?}",
+            values
+        );
+
+        let Some(updated) = sqlx::query_as_with::<_, Saldo, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Saldo] Failed to increment balance for user_id={}: {e}",
+                    input.user_id
+                );
+                AppError::SqlxError(e)
+            })?
+        else {
+            info!(
+                "🟡 [Saldo] No live, unfrozen {} saldo for user_id={}",
+                input.currency, input.user_id
+            );
+            return Ok(None);
+        };
+
+        let old_balance = updated.total_balance.checked_sub(input.amount)?;
+
+        Self::record_history(
+            conn,
+            &updated,
+            old_balance,
+            input.reason,
+            input.reference_id,
+        )
+        .await?;
+
+        info!(
+            "✅ [Saldo] Balance incremented: saldo_id={} → {}",
+            updated.saldo_id, updated.total_balance
+        );
+
+        Ok(Some(updated))
+    }
+
     async fn update_saldo_withdraw(&self, input: &UpdateSaldoWithdraw) -> Result<Saldo, AppError> {
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("❌ [Saldo] Failed to begin transaction: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        let updated = self.update_saldo_withdraw_tx(&mut tx, input).await?;
+
+        tx.commit().await.map_err(|e| {
+            error!(
+                "❌ [Saldo] Failed to commit withdraw for user_id={}: {e}",
+                input.user_id
+            );
+            AppError::SqlxError(e)
+        })?;
+
+        Ok(updated)
+    }
+
+    async fn update_saldo_withdraw_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &UpdateSaldoWithdraw,
+    ) -> Result<Saldo, AppError> {
         info!(
             "💸 [Saldo] Processing withdrawal for user_id={} | Amount: {}",
             input.user_id,
-            input.withdraw_amount.unwrap_or(0)
+            input.withdraw_amount.unwrap_or_default()
         );
 
         let (select_sql, select_values) = Query::select()
             .from(SaldoSchema::Table)
             .columns([SaldoSchema::SaldoId, SaldoSchema::TotalBalance])
             .and_where(Expr::col(SaldoSchema::UserId).eq(input.user_id))
+            .and_where(Expr::col(SaldoSchema::Currency).eq(input.currency))
+            .and_where(Expr::col(SaldoSchema::DeletedAt).is_null())
+            .lock(LockType::Update)
             .build_sqlx(PostgresQueryBuilder);
 
         let row = sqlx::query_with(&select_sql, select_values)
-            .fetch_optional(&self.db_pool)
+            .fetch_optional(&mut *conn)
             .await?
             .ok_or(AppError::NotFound("Saldo not found".into()))?;
 
         let saldo_id: i32 = row.get("saldo_id");
-        let current_balance: i32 = row.get("total_balance");
+        let current_balance: Money = row.get("total_balance");
 
-        let withdraw_amount: i32 = input.withdraw_amount.unwrap_or(0);
+        let withdraw_amount = input.withdraw_amount.unwrap_or_default();
         if current_balance < withdraw_amount {
             error!(
                 "❌ [Saldo] Insufficient balance: {current_balance} < {withdraw_amount} for user_id={}",
                 input.user_id
             );
-            return Err(AppError::Custom("Insufficient balance".into()));
+            return Err(AppError::InsufficientBalance {
+                required: withdraw_amount,
+                available: current_balance,
+            });
         }
 
-        let new_balance = current_balance - withdraw_amount;
+        let new_balance = current_balance.checked_sub(withdraw_amount)?;
 
         let (update_sql, update_values) = Query::update()
             .table(SaldoSchema::Table)
@@ -466,13 +985,17 @@ impl SaldoRepositoryTrait for SaldoRepository {
                         ))))
                     }),
                 ),
+                (
+                    SaldoSchema::UpdatedAt,
+                    chrono::Utc::now().naive_utc().into(),
+                ),
             ])
             .and_where(Expr::col(SaldoSchema::SaldoId).eq(saldo_id))
             .returning_all()
             .build_sqlx(PostgresQueryBuilder);
 
         let updated: Saldo = sqlx::query_as_with::<_, Saldo, _>(&update_sql, update_values)
-            .fetch_one(&self.db_pool)
+            .fetch_one(&mut *conn)
             .await
             .map_err(|e| {
                 error!(
@@ -482,6 +1005,15 @@ impl SaldoRepositoryTrait for SaldoRepository {
                 AppError::SqlxError(e)
             })?;
 
+        Self::record_history(
+            conn,
+            &updated,
+            current_balance,
+            BalanceChangeReason::Withdraw,
+            input.reference_id,
+        )
+        .await?;
+
         info!(
             "✅ [Saldo] Withdraw processed: user_id={} | Old: {}, New: {}",
             input.user_id, current_balance, new_balance
@@ -493,12 +1025,19 @@ impl SaldoRepositoryTrait for SaldoRepository {
     async fn delete(&self, id: i32) -> Result<(), AppError> {
         info!("🗑️ [Saldo] Deleting saldo with ID: {id}");
 
-        let (sql, values) = Query::delete()
-            .from_table(SaldoSchema::Table)
+        let now = chrono::Utc::now().naive_utc();
+
+        let (sql, values) = Query::update()
+            .table(SaldoSchema::Table)
+            .values([
+                (SaldoSchema::DeletedAt, now.into()),
+                (SaldoSchema::UpdatedAt, now.into()),
+            ])
             .and_where(Expr::col(SaldoSchema::SaldoId).eq(id))
+            .and_where(Expr::col(SaldoSchema::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
-        info!("🧾 [Saldo] DELETE query: {sql} | Values: {:?}", values);
+        info!("🧾 [Saldo] Soft DELETE query: {sql} | Values: {:?}", values);
 
         let result = sqlx::query_with(&sql, values)
             .execute(&self.db_pool)
@@ -516,4 +1055,256 @@ impl SaldoRepositoryTrait for SaldoRepository {
         info!("✅ [Saldo] Successfully deleted saldo ID: {id}");
         Ok(())
     }
+
+    async fn delete_by_user_tx(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<u64, AppError> {
+        info!("🗑️ [Saldo] Deleting saldos for user_id: {user_id}");
+
+        let now = chrono::Utc::now().naive_utc();
+
+        let (sql, values) = Query::update()
+            .table(SaldoSchema::Table)
+            .values([
+                (SaldoSchema::DeletedAt, now.into()),
+                (SaldoSchema::UpdatedAt, now.into()),
+            ])
+            .and_where(Expr::col(SaldoSchema::UserId).eq(user_id))
+            .and_where(Expr::col(SaldoSchema::DeletedAt).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [Saldo] Soft DELETE query: {sql} | Values: {:?}", values);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Saldo] Failed to delete saldos for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [Saldo] Deleted {} saldos for user_id={user_id}",
+            result.rows_affected()
+        );
+        Ok(result.rows_affected())
+    }
+
+    async fn find_history(
+        &self,
+        user_id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<SaldoHistory>, i64), AppError> {
+        info!(
+            "📒 [Saldo] Fetching balance history for user_id={user_id} - page: {page}, page_size: {page_size}"
+        );
+
+        let page = if page > 0 { page } else { 1 };
+        let page_size = if page_size > 0 { page_size } else { 10 };
+        let offset = (page - 1) * page_size;
+
+        let (sql, values) = Query::select()
+            .columns([
+                SaldoHistorySchema::SaldoHistoryId,
+                SaldoHistorySchema::SaldoId,
+                SaldoHistorySchema::UserId,
+                SaldoHistorySchema::OldBalance,
+                SaldoHistorySchema::NewBalance,
+                SaldoHistorySchema::Reason,
+                SaldoHistorySchema::ReferenceId,
+                SaldoHistorySchema::CreatedAt,
+            ])
+            .from(SaldoHistorySchema::Table)
+            .and_where(Expr::col(SaldoHistorySchema::UserId).eq(user_id))
+            .order_by(SaldoHistorySchema::CreatedAt, Order::Desc)
+            .order_by(SaldoHistorySchema::SaldoHistoryId, Order::Desc)
+            .limit(page_size as u64)
+            .offset(offset as u64)
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [Saldo] History query: {sql} | Values: {:?}", values);
+
+        let entries = sqlx::query_as_with::<_, SaldoHistory, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Saldo] Failed to fetch balance history for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        let (count_sql, count_values) = Query::select()
+            .expr(Func::count(Expr::col(SaldoHistorySchema::SaldoHistoryId)))
+            .from(SaldoHistorySchema::Table)
+            .and_where(Expr::col(SaldoHistorySchema::UserId).eq(user_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (total,) = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Saldo] Failed to count balance history for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [Saldo] Returned {} of {total} history entries for user_id={user_id}",
+            entries.len()
+        );
+
+        Ok((entries, total))
+    }
+
+    async fn restore(&self, id: i32) -> Result<Saldo, AppError> {
+        info!("♻️ [Saldo] Restoring saldo with ID: {id}");
+
+        let (sql, values) = Query::update()
+            .table(SaldoSchema::Table)
+            .value(SaldoSchema::DeletedAt, Keyword::Null)
+            .value(SaldoSchema::UpdatedAt, chrono::Utc::now().naive_utc())
+            .and_where(Expr::col(SaldoSchema::SaldoId).eq(id))
+            .and_where(Expr::col(SaldoSchema::DeletedAt).is_not_null())
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [Saldo] Restore query: {sql} | Values: {:?}", values);
+
+        let restored = sqlx::query_as_with::<_, Saldo, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Saldo] Failed to restore saldo ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| {
+                error!("🟡 [Saldo] Restore failed: No deleted saldo found with ID {id}");
+                AppError::NotFound(format!("Deleted saldo with ID {id} not found"))
+            })?;
+
+        info!("✅ [Saldo] Successfully restored saldo ID: {id}");
+        Ok(restored)
+    }
+
+    async fn set_frozen(
+        &self,
+        user_id: i32,
+        frozen: bool,
+        reason: Option<String>,
+    ) -> Result<Vec<Saldo>, AppError> {
+        info!("🧊 [Saldo] Setting is_frozen={frozen} on saldos of user_id={user_id}");
+
+        let (sql, values) = Query::update()
+            .table(SaldoSchema::Table)
+            .values([
+                (SaldoSchema::IsFrozen, frozen.into()),
+                (SaldoSchema::FrozenReason, reason.into()),
+                (
+                    SaldoSchema::UpdatedAt,
+                    chrono::Utc::now().naive_utc().into(),
+                ),
+            ])
+            .and_where(Expr::col(SaldoSchema::UserId).eq(user_id))
+            .and_where(Expr::col(SaldoSchema::DeletedAt).is_null())
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [Saldo] Freeze query: {sql} | Values: {:?}", values);
+
+        let mut saldos = sqlx::query_as_with::<_, Saldo, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Saldo] Failed to set is_frozen={frozen} for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        if saldos.is_empty() {
+            error!("🟡 [Saldo] No saldo found for user_id={user_id}");
+            return Err(AppError::NotFound(format!(
+                "Saldo with User id {user_id} not found"
+            )));
+        }
+
+        saldos.sort_by_key(|saldo| saldo.saldo_id);
+
+        info!(
+            "✅ [Saldo] Set is_frozen={frozen} on {} saldo(s) of user_id={user_id}",
+            saldos.len()
+        );
+
+        Ok(saldos)
+    }
+
+    async fn stats(&self) -> Result<Vec<SaldoStats>, AppError> {
+        info!("📊 [Saldo] Computing balance statistics per currency");
+
+        let bigint = || Alias::new("BIGINT");
+
+        let (sql, values) = Query::select()
+            .column(SaldoSchema::Currency)
+            .expr_as(
+                Func::cast_as(
+                    Func::coalesce([
+                        Func::sum(Expr::col(SaldoSchema::TotalBalance)).into(),
+                        Expr::val(0i64).into(),
+                    ]),
+                    bigint(),
+                ),
+                Alias::new("total_balance"),
+            )
+            .expr_as(
+                Func::count(Expr::col(SaldoSchema::SaldoId)),
+                Alias::new("account_count"),
+            )
+            .expr_as(
+                Func::cast_as(
+                    Func::round(Func::avg(Expr::col(SaldoSchema::TotalBalance))),
+                    bigint(),
+                ),
+                Alias::new("average_balance"),
+            )
+            // SeaQuery has no ordered-set aggregates.
+            .expr_as(
+                Func::cast_as(
+                    Func::round(Expr::cust(
+                        r#"percentile_cont(0.5) WITHIN GROUP (ORDER BY "total_balance")"#,
+                    )),
+                    bigint(),
+                ),
+                Alias::new("median_balance"),
+            )
+            .expr_as(
+                Func::count(Expr::case(
+                    Expr::col(SaldoSchema::TotalBalance).lt(0i64),
+                    Expr::val(1),
+                )),
+                Alias::new("negative_count"),
+            )
+            .from(SaldoSchema::Table)
+            .and_where(Expr::col(SaldoSchema::DeletedAt).is_null())
+            .group_by_col(SaldoSchema::Currency)
+            .order_by(SaldoSchema::Currency, Order::Asc)
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [Saldo] Stats query: {sql}");
+
+        let rows = sqlx::query_as_with::<_, SaldoStats, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Saldo] Failed to compute balance statistics: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        for row in rows.iter().filter(|row| row.negative_count > 0) {
+            error!(
+                "🚨 [Saldo] {} {} saldo(s) have a negative balance",
+                row.negative_count, row.currency
+            );
+        }
+
+        Ok(rows)
+    }
 }
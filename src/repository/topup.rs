@@ -1,4 +1,9 @@
-use crate::model::topup::Topup;
+use crate::domain::request::{
+    Search, TransactionFilter,
+    sort::{Sort, invalid_sort_by},
+};
+use crate::domain::{currency::Currency, money::Money};
+use crate::model::topup::{Topup, TopupMonthlyStats};
 use crate::schema::topup::Topups as TopupSchema;
 use crate::utils::AppError;
 use crate::{
@@ -8,11 +13,23 @@ use crate::{
 };
 use anyhow::Result;
 use async_trait::async_trait;
-use chrono::Utc;
-use sea_query::{Expr, Func, Order, PostgresQueryBuilder, Query};
+use chrono::{NaiveDateTime, Utc};
+use sea_query::{Alias, Expr, Func, Keyword, Order, PostgresQueryBuilder, Query};
 use sea_query_binder::SqlxBinder;
+use sqlx::PgConnection;
 use tracing::{error, info};
 
+const SORTABLE_COLUMNS: &[&str] = &[
+    "id",
+    "topup_id",
+    "user_id",
+    "topup_amount",
+    "amount",
+    "topup_time",
+    "created_at",
+    "updated_at",
+];
+
 pub struct TopupRepository {
     db_pool: ConnectionPool,
 }
@@ -21,6 +38,18 @@ impl TopupRepository {
     pub fn new(db_pool: ConnectionPool) -> Self {
         Self { db_pool }
     }
+
+    fn sort_column(sort_by: Option<&str>) -> Result<TopupSchema, AppError> {
+        match sort_by.filter(|s| !s.is_empty()).unwrap_or("created_at") {
+            "id" | "topup_id" => Ok(TopupSchema::TopupId),
+            "user_id" => Ok(TopupSchema::UserId),
+            "topup_amount" | "amount" => Ok(TopupSchema::TopupAmount),
+            "topup_time" => Ok(TopupSchema::TopupTime),
+            "created_at" => Ok(TopupSchema::CreatedAt),
+            "updated_at" => Ok(TopupSchema::UpdatedAt),
+            other => Err(invalid_sort_by(other, SORTABLE_COLUMNS)),
+        }
+    }
 }
 
 #[async_trait]
@@ -29,13 +58,17 @@ impl TopupRepositoryTrait for TopupRepository {
         &self,
         page: i32,
         page_size: i32,
-        search: Option<String>,
+        search: Option<Search>,
+        sort: Sort,
+        filter: TransactionFilter,
     ) -> Result<(Vec<Topup>, i64), AppError> {
         info!(
             "💳 [Topups] Fetching all topups - page: {page}, page_size: {page_size}, search: {:?}",
             search
         );
 
+        let sort_column = Self::sort_column(sort.by.as_deref())?;
+
         let page = if page > 0 { page } else { 1 };
         let page_size = if page_size > 0 { page_size } else { 10 };
         let offset = (page - 1) * page_size;
@@ -53,15 +86,31 @@ impl TopupRepositoryTrait for TopupRepository {
                 TopupSchema::TopupTime,
                 TopupSchema::CreatedAt,
                 TopupSchema::UpdatedAt,
+                TopupSchema::DeletedAt,
+                TopupSchema::Currency,
             ])
             .from(TopupSchema::Table)
-            .order_by(TopupSchema::TopupId, Order::Asc)
+            .order_by(sort_column, sort.order.into())
+            .order_by(TopupSchema::TopupId, sort.order.into())
             .limit(page_size as u64)
             .offset(offset as u64);
 
-        if let Some(ref term) = search {
-            select_query.and_where(Expr::col(TopupSchema::TopupNo).like(format!("{term}%")));
-            info!("🔍 [Topups] Filtering by topup_no prefix: {term}%");
+        if !filter.include_deleted {
+            select_query.and_where(Expr::col(TopupSchema::DeletedAt).is_null());
+        }
+
+        filter.apply(
+            &mut select_query,
+            TopupSchema::TopupAmount,
+            TopupSchema::TopupTime,
+        );
+
+        if let Some(ref search) = search {
+            select_query.and_where(search.matches(Expr::col(TopupSchema::TopupNo)));
+            info!(
+                "🔍 [Topups] Filtering by topup_no ({:?}): {}",
+                search.mode, search.term
+            );
         }
 
         let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
@@ -87,8 +136,18 @@ impl TopupRepositoryTrait for TopupRepository {
             .expr(Func::count(Expr::col(TopupSchema::TopupId)))
             .from(TopupSchema::Table);
 
-        if let Some(ref term) = search {
-            count_query.and_where(Expr::col(TopupSchema::TopupNo).like(format!("{term}%")));
+        if !filter.include_deleted {
+            count_query.and_where(Expr::col(TopupSchema::DeletedAt).is_null());
+        }
+
+        filter.apply(
+            &mut count_query,
+            TopupSchema::TopupAmount,
+            TopupSchema::TopupTime,
+        );
+
+        if let Some(ref search) = search {
+            count_query.and_where(search.matches(Expr::col(TopupSchema::TopupNo)));
         }
 
         let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
@@ -134,8 +193,11 @@ impl TopupRepositoryTrait for TopupRepository {
                 TopupSchema::TopupTime,
                 TopupSchema::CreatedAt,
                 TopupSchema::UpdatedAt,
+                TopupSchema::DeletedAt,
+                TopupSchema::Currency,
             ])
             .and_where(Expr::col(TopupSchema::TopupId).eq(id))
+            .and_where(Expr::col(TopupSchema::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
         info!("🧾 [Topups] Executing query: {sql} | Values: {:?}", values);
@@ -177,8 +239,11 @@ impl TopupRepositoryTrait for TopupRepository {
                 TopupSchema::TopupTime,
                 TopupSchema::CreatedAt,
                 TopupSchema::UpdatedAt,
+                TopupSchema::DeletedAt,
+                TopupSchema::Currency,
             ])
             .and_where(Expr::col(TopupSchema::UserId).eq(id))
+            .and_where(Expr::col(TopupSchema::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
         info!("🧾 [Topups] Executing query: {sql} | Values: {:?}", values);
@@ -213,8 +278,11 @@ impl TopupRepositoryTrait for TopupRepository {
                 TopupSchema::TopupTime,
                 TopupSchema::CreatedAt,
                 TopupSchema::UpdatedAt,
+                TopupSchema::DeletedAt,
+                TopupSchema::Currency,
             ])
             .and_where(Expr::col(TopupSchema::UserId).eq(id))
+            .and_where(Expr::col(TopupSchema::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
         info!("🧾 [Topups] Executing query: {sql} | Values: {:?}", values);
@@ -242,7 +310,113 @@ impl TopupRepositoryTrait for TopupRepository {
         Ok(row)
     }
 
+    async fn sum_by_user(&self, user_id: i32) -> Result<(Money, i64), AppError> {
+        info!("📊 [Topups] Summing topups for user_id={user_id}");
+
+        let (sql, values) = Query::select()
+            .expr(Func::cast_as(
+                Func::coalesce([
+                    Func::sum(Expr::col(TopupSchema::TopupAmount)).into(),
+                    Expr::val(0i64).into(),
+                ]),
+                Alias::new("BIGINT"),
+            ))
+            .expr(Func::count(Expr::col(TopupSchema::TopupId)))
+            .from(TopupSchema::Table)
+            .and_where(Expr::col(TopupSchema::UserId).eq(user_id))
+            .and_where(Expr::col(TopupSchema::DeletedAt).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        let totals = sqlx::query_as_with::<_, (Money, i64), _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Topups] Failed to sum topups for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        Ok(totals)
+    }
+
+    async fn monthly_stats(
+        &self,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+        user_id: Option<i32>,
+        currency: Currency,
+    ) -> Result<Vec<TopupMonthlyStats>, AppError> {
+        info!(
+            "📊 [Topups] Monthly {currency} topup stats from {from} to {to}, user_id={:?}",
+            user_id
+        );
+
+        let mut query = Query::select();
+        query
+            // SeaQuery has no date_trunc; grouped by the output alias below.
+            .expr_as(
+                Expr::cust(r#"date_trunc('month', "created_at")"#),
+                Alias::new("month"),
+            )
+            .expr_as(
+                Func::count(Expr::col(TopupSchema::TopupId)),
+                Alias::new("count"),
+            )
+            .expr_as(
+                Func::cast_as(
+                    Func::coalesce([
+                        Func::sum(Expr::col(TopupSchema::TopupAmount)).into(),
+                        Expr::val(0i64).into(),
+                    ]),
+                    Alias::new("BIGINT"),
+                ),
+                Alias::new("total_amount"),
+            )
+            .from(TopupSchema::Table)
+            .and_where(Expr::col(TopupSchema::CreatedAt).gte(from))
+            .and_where(Expr::col(TopupSchema::CreatedAt).lte(to))
+            .and_where(Expr::col(TopupSchema::Currency).eq(currency))
+            .and_where(Expr::col(TopupSchema::DeletedAt).is_null())
+            .group_by_col(Alias::new("month"))
+            .order_by(Alias::new("month"), Order::Asc);
+
+        if let Some(user_id) = user_id {
+            query.and_where(Expr::col(TopupSchema::UserId).eq(user_id));
+        }
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 [Topups] Monthly stats query: {sql} | Values: {:?}",
+            values
+        );
+
+        let rows = sqlx::query_as_with::<_, TopupMonthlyStats, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Topups] Failed to compute monthly topup stats: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!("✅ [Topups] {} month(s) with topups", rows.len());
+
+        Ok(rows)
+    }
+
     async fn create(&self, input: &CreateTopupRequest) -> Result<Topup, AppError> {
+        let mut conn = self.db_pool.acquire().await.map_err(|e| {
+            error!("❌ [Topups] Failed to acquire connection: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        self.create_tx(&mut conn, input).await
+    }
+
+    async fn create_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &CreateTopupRequest,
+    ) -> Result<Topup, AppError> {
         info!(
             "💳 [Topups] Creating new topup: user_id={}, amount={}, method={}",
             input.user_id, input.topup_amount, input.topup_method
@@ -258,6 +432,7 @@ impl TopupRepositoryTrait for TopupRepository {
                 TopupSchema::TopupAmount,
                 TopupSchema::TopupMethod,
                 TopupSchema::TopupTime,
+                TopupSchema::Currency,
             ])
             .values([
                 input.user_id.into(),
@@ -265,6 +440,7 @@ impl TopupRepositoryTrait for TopupRepository {
                 input.topup_amount.into(),
                 input.topup_method.clone().into(),
                 now.into(),
+                input.currency.into(),
             ])
             .unwrap()
             .returning_all()
@@ -273,7 +449,7 @@ impl TopupRepositoryTrait for TopupRepository {
         info!("🧾 [Topups] Executing INSERT: {sql} | Values: {:?}", values);
 
         let created = sqlx::query_as_with::<_, Topup, _>(&sql, values)
-            .fetch_one(&self.db_pool)
+            .fetch_one(&mut *conn)
             .await
             .map_err(|e| {
                 error!(
@@ -305,6 +481,7 @@ impl TopupRepositoryTrait for TopupRepository {
                 (TopupSchema::TopupAmount, input.topup_amount.into()),
                 (TopupSchema::TopupMethod, input.topup_method.clone().into()),
                 (TopupSchema::TopupTime, now.into()),
+                (TopupSchema::UpdatedAt, now.into()),
             ])
             .and_where(Expr::col(TopupSchema::TopupId).eq(input.topup_id))
             .build_sqlx(PostgresQueryBuilder);
@@ -340,6 +517,19 @@ impl TopupRepositoryTrait for TopupRepository {
     }
 
     async fn update_amount(&self, input: &UpdateTopupAmount) -> Result<Topup, AppError> {
+        let mut conn = self.db_pool.acquire().await.map_err(|e| {
+            error!("❌ [Topups] Failed to acquire connection: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        self.update_amount_tx(&mut conn, input).await
+    }
+
+    async fn update_amount_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &UpdateTopupAmount,
+    ) -> Result<Topup, AppError> {
         info!(
             "💵 [Topups] Updating amount for topup ID {}: {}",
             input.topup_id, input.topup_amount
@@ -347,7 +537,13 @@ impl TopupRepositoryTrait for TopupRepository {
 
         let (sql, values) = Query::update()
             .table(TopupSchema::Table)
-            .values([(TopupSchema::TopupAmount, input.topup_amount.into())])
+            .values([
+                (TopupSchema::TopupAmount, input.topup_amount.into()),
+                (
+                    TopupSchema::UpdatedAt,
+                    chrono::Utc::now().naive_utc().into(),
+                ),
+            ])
             .and_where(Expr::col(TopupSchema::TopupId).eq(input.topup_id))
             .returning_all()
             .build_sqlx(PostgresQueryBuilder);
@@ -358,7 +554,7 @@ impl TopupRepositoryTrait for TopupRepository {
         );
 
         let updated = sqlx::query_as_with::<_, Topup, _>(&sql, values)
-            .fetch_one(&self.db_pool)
+            .fetch_one(&mut *conn)
             .await
             .map_err(|e| match e {
                 sqlx::Error::RowNotFound => {
@@ -386,29 +582,167 @@ impl TopupRepositoryTrait for TopupRepository {
     }
 
     async fn delete(&self, id: i32) -> Result<(), AppError> {
+        let mut conn = self.db_pool.acquire().await.map_err(|e| {
+            error!("❌ [Topups] Failed to acquire connection: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        self.delete_tx(&mut conn, id).await.map(|_| ())
+    }
+
+    async fn delete_tx(&self, conn: &mut PgConnection, id: i32) -> Result<Topup, AppError> {
         info!("🗑️ [Topups] Deleting topup with ID: {id}");
 
-        let (sql, values) = Query::delete()
-            .from_table(TopupSchema::Table)
+        let now = chrono::Utc::now().naive_utc();
+
+        let (sql, values) = Query::update()
+            .table(TopupSchema::Table)
+            .values([
+                (TopupSchema::DeletedAt, now.into()),
+                (TopupSchema::UpdatedAt, now.into()),
+            ])
             .and_where(Expr::col(TopupSchema::TopupId).eq(id))
+            .and_where(Expr::col(TopupSchema::DeletedAt).is_null())
+            .returning_all()
             .build_sqlx(PostgresQueryBuilder);
 
-        info!("🧾 [Topups] Executing DELETE: {sql} | Values: {:?}", values);
+        info!(
+            "🧾 [Topups] Executing soft DELETE: {sql} | Values: {:?}",
+            values
+        );
 
-        let result = sqlx::query_with(&sql, values)
-            .execute(&self.db_pool)
+        let deleted = sqlx::query_as_with::<_, Topup, _>(&sql, values)
+            .fetch_optional(&mut *conn)
             .await
             .map_err(|e| {
                 error!("❌ [Topups] Failed to delete topup ID {id}: {e}");
                 AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| {
+                error!("🟡 [Topups] Deletion failed: No topup found with ID {id}");
+                AppError::NotFound(format!("Topup with ID {id} not found"))
             })?;
 
-        if result.rows_affected() == 0 {
-            error!("🟡 [Topups] Deletion failed: No topup found with ID {id}");
-            return Err(AppError::NotFound(format!("Topup with ID {id} not found")));
+        info!("✅ [Topups] Successfully deleted topup ID: {id}");
+        Ok(deleted)
+    }
+
+    async fn delete_by_user_tx(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<u64, AppError> {
+        info!("🗑️ [Topups] Deleting topups for user_id: {user_id}");
+
+        let now = chrono::Utc::now().naive_utc();
+
+        let (sql, values) = Query::update()
+            .table(TopupSchema::Table)
+            .values([
+                (TopupSchema::DeletedAt, now.into()),
+                (TopupSchema::UpdatedAt, now.into()),
+            ])
+            .and_where(Expr::col(TopupSchema::UserId).eq(user_id))
+            .and_where(Expr::col(TopupSchema::DeletedAt).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 [Topups] Soft DELETE query: {sql} | Values: {:?}",
+            values
+        );
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Topups] Failed to delete topups for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [Topups] Deleted {} topups for user_id={user_id}",
+            result.rows_affected()
+        );
+        Ok(result.rows_affected())
+    }
+
+    async fn restore(&self, id: i32) -> Result<Topup, AppError> {
+        let mut conn = self.db_pool.acquire().await.map_err(|e| {
+            error!("❌ [Topups] Failed to acquire connection: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        self.restore_tx(&mut conn, id).await
+    }
+
+    async fn restore_tx(&self, conn: &mut PgConnection, id: i32) -> Result<Topup, AppError> {
+        info!("♻️ [Topups] Restoring topup with ID: {id}");
+
+        let (sql, values) = Query::update()
+            .table(TopupSchema::Table)
+            .value(TopupSchema::DeletedAt, Keyword::Null)
+            .value(TopupSchema::UpdatedAt, chrono::Utc::now().naive_utc())
+            .and_where(Expr::col(TopupSchema::TopupId).eq(id))
+            .and_where(Expr::col(TopupSchema::DeletedAt).is_not_null())
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [Topups] Restore query: {sql} | Values: {:?}", values);
+
+        let restored = sqlx::query_as_with::<_, Topup, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Topups] Failed to restore topup ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| {
+                error!("🟡 [Topups] Restore failed: No deleted topup found with ID {id}");
+                AppError::NotFound(format!("Deleted topup with ID {id} not found"))
+            })?;
+
+        info!("✅ [Topups] Successfully restored topup ID: {id}");
+        Ok(restored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_column_defaults_to_created_at() {
+        assert!(matches!(
+            TopupRepository::sort_column(None),
+            Ok(TopupSchema::CreatedAt)
+        ));
+        assert!(matches!(
+            TopupRepository::sort_column(Some("")),
+            Ok(TopupSchema::CreatedAt)
+        ));
+    }
+
+    #[test]
+    fn sort_column_accepts_whitelisted_names_and_aliases() {
+        for name in ["topup_amount", "amount"] {
+            assert!(matches!(
+                TopupRepository::sort_column(Some(name)),
+                Ok(TopupSchema::TopupAmount)
+            ));
         }
+        assert!(matches!(
+            TopupRepository::sort_column(Some("id")),
+            Ok(TopupSchema::TopupId)
+        ));
+    }
 
-        info!("✅ [Topups] Successfully deleted topup ID: {id}");
-        Ok(())
+    #[test]
+    fn sort_column_rejects_anything_else() {
+        for name in ["topup_no", "created_at; DROP TABLE topups", "CREATED_AT"] {
+            let err = TopupRepository::sort_column(Some(name)).unwrap_err();
+
+            assert!(matches!(err, AppError::Validation(_)), "{name}: {err}");
+            assert!(err.to_string().contains("expected one of"), "{err}");
+        }
     }
 }
@@ -1,6 +1,14 @@
-use crate::model::transfer::Transfer;
+use crate::domain::request::{
+    Search, TransactionFilter, TransferDirection,
+    sort::{Sort, invalid_sort_by},
+};
+use crate::domain::transfer_status::TransferStatus;
+use crate::domain::{currency::Currency, money::Money};
+use crate::model::transfer::{Transfer, TransferCounterparty, TransferNetFlow, TransferReceipt};
+use crate::schema::fee::Fees as FeeSchema;
 use crate::schema::transfer::Transfers as TransferSchema;
-use crate::utils::AppError;
+use crate::schema::user::Users;
+use crate::utils::{AppError, retry_transient};
 use crate::{
     abstract_trait::TransferRepositoryTrait,
     config::ConnectionPool,
@@ -10,11 +18,25 @@ use crate::{
 };
 use anyhow::Result;
 use async_trait::async_trait;
-use chrono::Utc;
-use sea_query::{Expr, Func, Order, PostgresQueryBuilder, Query};
+use chrono::{NaiveDateTime, Utc};
+use sea_query::{
+    Alias, Cond, Expr, Func, JoinType, Keyword, LockBehavior, LockType, Order,
+    PostgresQueryBuilder, Query, SelectStatement, SimpleExpr,
+};
 use sea_query_binder::SqlxBinder;
+use sqlx::PgConnection;
 use tracing::{error, info};
 
+const SORTABLE_COLUMNS: &[&str] = &[
+    "id",
+    "transfer_id",
+    "transfer_amount",
+    "amount",
+    "transfer_time",
+    "created_at",
+    "updated_at",
+];
+
 pub struct TransferRepository {
     db_pool: ConnectionPool,
 }
@@ -23,6 +45,49 @@ impl TransferRepository {
     pub fn new(db_pool: ConnectionPool) -> Self {
         Self { db_pool }
     }
+
+    fn search_condition(search: &Search, search_notes: bool) -> SimpleExpr {
+        let condition = search.matches_id(TransferSchema::TransferFrom);
+
+        if search_notes {
+            condition.or(search.matches_ignore_case(Expr::col(TransferSchema::Note)))
+        } else {
+            condition
+        }
+    }
+
+    fn sort_column(sort_by: Option<&str>) -> Result<TransferSchema, AppError> {
+        match sort_by.filter(|s| !s.is_empty()).unwrap_or("created_at") {
+            "id" | "transfer_id" => Ok(TransferSchema::TransferId),
+            "transfer_amount" | "amount" => Ok(TransferSchema::TransferAmount),
+            "transfer_time" => Ok(TransferSchema::TransferTime),
+            "created_at" => Ok(TransferSchema::CreatedAt),
+            "updated_at" => Ok(TransferSchema::UpdatedAt),
+            other => Err(invalid_sort_by(other, SORTABLE_COLUMNS)),
+        }
+    }
+
+    fn select_transfers() -> SelectStatement {
+        Query::select()
+            .columns([
+                TransferSchema::TransferId,
+                TransferSchema::TransferFrom,
+                TransferSchema::TransferTo,
+                TransferSchema::TransferAmount,
+                TransferSchema::TransferTime,
+                TransferSchema::CreatedAt,
+                TransferSchema::UpdatedAt,
+                TransferSchema::DeletedAt,
+                TransferSchema::Currency,
+                TransferSchema::Status,
+                TransferSchema::ScheduledAt,
+                TransferSchema::Note,
+                TransferSchema::ReversedTransferId,
+                TransferSchema::Fee,
+            ])
+            .from(TransferSchema::Table)
+            .to_owned()
+    }
 }
 
 #[async_trait]
@@ -31,13 +96,17 @@ impl TransferRepositoryTrait for TransferRepository {
         &self,
         page: i32,
         page_size: i32,
-        search: Option<String>,
+        search: Option<Search>,
+        sort: Sort,
+        filter: TransactionFilter,
     ) -> Result<(Vec<Transfer>, i64), AppError> {
         info!(
             "🔄 [Transfers] Fetching transfers - page: {page}, page_size: {page_size}, search: {:?}",
             search
         );
 
+        let sort_column = Self::sort_column(sort.by.as_deref())?;
+
         let page = if page > 0 { page } else { 1 };
         let page_size = if page_size > 0 { page_size } else { 10 };
         let offset = (page - 1) * page_size;
@@ -54,16 +123,36 @@ impl TransferRepositoryTrait for TransferRepository {
                 TransferSchema::TransferTime,
                 TransferSchema::CreatedAt,
                 TransferSchema::UpdatedAt,
+                TransferSchema::DeletedAt,
+                TransferSchema::Currency,
+                TransferSchema::Status,
+                TransferSchema::ScheduledAt,
+                TransferSchema::Note,
+                TransferSchema::ReversedTransferId,
+                TransferSchema::Fee,
             ])
             .from(TransferSchema::Table)
-            .order_by(TransferSchema::TransferId, Order::Asc)
+            .order_by(sort_column, sort.order.into())
+            .order_by(TransferSchema::TransferId, sort.order.into())
             .limit(page_size as u64)
             .offset(offset as u64);
 
-        if let Some(ref term) = search {
-            select_query
-                .and_where(Expr::col(TransferSchema::TransferFrom).like(format!("{term}%")));
-            info!("🔍 [Transfers] Filtering by sender (transfer_from) like: {term}%");
+        if !filter.include_deleted {
+            select_query.and_where(Expr::col(TransferSchema::DeletedAt).is_null());
+        }
+
+        filter.apply(
+            &mut select_query,
+            TransferSchema::TransferAmount,
+            TransferSchema::TransferTime,
+        );
+
+        if let Some(ref search) = search {
+            select_query.and_where(Self::search_condition(search, filter.search_notes));
+            info!(
+                "🔍 [Transfers] Filtering by sender (transfer_from) ({:?}): {}",
+                search.mode, search.term
+            );
         }
 
         let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
@@ -92,8 +181,18 @@ impl TransferRepositoryTrait for TransferRepository {
             .expr(Func::count(Expr::col(TransferSchema::TransferId)))
             .from(TransferSchema::Table);
 
-        if let Some(ref term) = search {
-            count_query.and_where(Expr::col(TransferSchema::TransferFrom).like(format!("{term}%")));
+        if !filter.include_deleted {
+            count_query.and_where(Expr::col(TransferSchema::DeletedAt).is_null());
+        }
+
+        filter.apply(
+            &mut count_query,
+            TransferSchema::TransferAmount,
+            TransferSchema::TransferTime,
+        );
+
+        if let Some(ref search) = search {
+            count_query.and_where(Self::search_condition(search, filter.search_notes));
         }
 
         let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
@@ -138,8 +237,16 @@ impl TransferRepositoryTrait for TransferRepository {
                 TransferSchema::TransferTime,
                 TransferSchema::CreatedAt,
                 TransferSchema::UpdatedAt,
+                TransferSchema::DeletedAt,
+                TransferSchema::Currency,
+                TransferSchema::Status,
+                TransferSchema::ScheduledAt,
+                TransferSchema::Note,
+                TransferSchema::ReversedTransferId,
+                TransferSchema::Fee,
             ])
             .and_where(Expr::col(TransferSchema::TransferId).eq(id))
+            .and_where(Expr::col(TransferSchema::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
@@ -173,11 +280,107 @@ impl TransferRepositoryTrait for TransferRepository {
         Ok(row)
     }
 
-    async fn find_by_users(&self, id: i32) -> Result<Vec<Transfer>, AppError> {
-        info!("👥 [Transfers] Fetching all transfers sent by user ID: {id}");
+    async fn find_receipt(&self, id: i32) -> Result<Option<TransferReceipt>, AppError> {
+        info!("🧾 [Transfers] Building receipt for transfer ID: {id}");
+
+        let sender = Alias::new("sender");
+        let receiver = Alias::new("receiver");
 
         let (sql, values) = Query::select()
+            .columns([
+                (TransferSchema::Table, TransferSchema::TransferId),
+                (TransferSchema::Table, TransferSchema::TransferFrom),
+                (TransferSchema::Table, TransferSchema::TransferTo),
+                (TransferSchema::Table, TransferSchema::TransferAmount),
+                (TransferSchema::Table, TransferSchema::Currency),
+                (TransferSchema::Table, TransferSchema::TransferTime),
+            ])
+            .expr_as(
+                Expr::col((sender.clone(), Users::Firstname)),
+                Alias::new("from_firstname"),
+            )
+            .expr_as(
+                Expr::col((sender.clone(), Users::Lastname)),
+                Alias::new("from_lastname"),
+            )
+            .expr_as(
+                Expr::col((receiver.clone(), Users::Firstname)),
+                Alias::new("to_firstname"),
+            )
+            .expr_as(
+                Expr::col((receiver.clone(), Users::Lastname)),
+                Alias::new("to_lastname"),
+            )
+            .from(TransferSchema::Table)
+            .join_as(
+                JoinType::InnerJoin,
+                Users::Table,
+                sender.clone(),
+                Expr::col((sender, Users::UserId))
+                    .equals((TransferSchema::Table, TransferSchema::TransferFrom)),
+            )
+            .join_as(
+                JoinType::InnerJoin,
+                Users::Table,
+                receiver.clone(),
+                Expr::col((receiver, Users::UserId))
+                    .equals((TransferSchema::Table, TransferSchema::TransferTo)),
+            )
+            .and_where(Expr::col((TransferSchema::Table, TransferSchema::TransferId)).eq(id))
+            .and_where(Expr::col((TransferSchema::Table, TransferSchema::DeletedAt)).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 [Transfers] Executing query: {sql} | Values: {:?}",
+            values
+        );
+
+        let row = sqlx::query_as_with::<_, TransferReceipt, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Database error while building receipt for ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        if row.is_none() {
+            info!("🟡 [Transfers] No transfer found for receipt with ID: {id}");
+        }
+
+        Ok(row)
+    }
+
+    async fn find_by_users(
+        &self,
+        id: i32,
+        direction: TransferDirection,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Transfer>, i64), AppError> {
+        info!(
+            "👥 [Transfers] Fetching {direction:?} transfers for user ID {id} - page: {page}, page_size: {page_size}"
+        );
+
+        let page = if page > 0 { page } else { 1 };
+        let page_size = if page_size > 0 { page_size } else { 10 };
+        let offset = (page - 1) * page_size;
+
+        let party = match direction {
+            TransferDirection::Outgoing => Expr::col(TransferSchema::TransferFrom).eq(id),
+            TransferDirection::Incoming => Expr::col(TransferSchema::TransferTo).eq(id),
+            TransferDirection::All => Expr::col(TransferSchema::TransferFrom)
+                .eq(id)
+                .or(Expr::col(TransferSchema::TransferTo).eq(id)),
+        };
+
+        let mut query = Query::select();
+        query
             .from(TransferSchema::Table)
+            .and_where(party)
+            .and_where(Expr::col(TransferSchema::DeletedAt).is_null());
+
+        let (sql, values) = query
+            .clone()
             .columns([
                 TransferSchema::TransferId,
                 TransferSchema::TransferFrom,
@@ -186,8 +389,18 @@ impl TransferRepositoryTrait for TransferRepository {
                 TransferSchema::TransferTime,
                 TransferSchema::CreatedAt,
                 TransferSchema::UpdatedAt,
+                TransferSchema::DeletedAt,
+                TransferSchema::Currency,
+                TransferSchema::Status,
+                TransferSchema::ScheduledAt,
+                TransferSchema::Note,
+                TransferSchema::ReversedTransferId,
+                TransferSchema::Fee,
             ])
-            .and_where(Expr::col(TransferSchema::TransferFrom).eq(id))
+            .order_by(TransferSchema::TransferTime, Order::Desc)
+            .order_by(TransferSchema::TransferId, Order::Desc)
+            .limit(page_size as u64)
+            .offset(offset as u64)
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
@@ -199,16 +412,373 @@ impl TransferRepositoryTrait for TransferRepository {
             .fetch_all(&self.db_pool)
             .await
             .map_err(|e| {
-                error!("❌ [Transfers] Failed to fetch transfers for sender user ID {id}: {e}");
+                error!("❌ [Transfers] Failed to fetch transfers for user ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        let (count_sql, count_values) = query
+            .expr(Func::count(Expr::col(TransferSchema::TransferId)))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (total,) = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to count transfers for user ID {id}: {e}");
                 AppError::SqlxError(e)
             })?;
 
         info!(
-            "✅ [Transfers] Successfully fetched {} transfer(s) for sender user ID: {id}",
+            "✅ [Transfers] Returned {} of {total} transfer(s) for user ID: {id}",
             rows.len(),
         );
 
-        Ok(rows)
+        Ok((rows, total))
+    }
+
+    async fn sum_sent_by_user(&self, user_id: i32) -> Result<(Money, i64), AppError> {
+        info!("📊 [Transfers] Summing sent transfers for user_id={user_id}");
+
+        let (sql, values) = Query::select()
+            .expr(Func::cast_as(
+                Func::coalesce([
+                    Func::sum(Expr::col(TransferSchema::TransferAmount)).into(),
+                    Expr::val(0i64).into(),
+                ]),
+                Alias::new("BIGINT"),
+            ))
+            .expr(Func::count(Expr::col(TransferSchema::TransferId)))
+            .from(TransferSchema::Table)
+            .and_where(Expr::col(TransferSchema::TransferFrom).eq(user_id))
+            .and_where(Expr::col(TransferSchema::DeletedAt).is_null())
+            .and_where(Expr::col(TransferSchema::Status).is_in(TransferStatus::SETTLED))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let totals = sqlx::query_as_with::<_, (Money, i64), _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to sum sent transfers for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        Ok(totals)
+    }
+
+    async fn sum_received_by_user(&self, user_id: i32) -> Result<(Money, i64), AppError> {
+        info!("📊 [Transfers] Summing received transfers for user_id={user_id}");
+
+        let (sql, values) = Query::select()
+            .expr(Func::cast_as(
+                Func::coalesce([
+                    Func::sum(Expr::col(TransferSchema::TransferAmount)).into(),
+                    Expr::val(0i64).into(),
+                ]),
+                Alias::new("BIGINT"),
+            ))
+            .expr(Func::count(Expr::col(TransferSchema::TransferId)))
+            .from(TransferSchema::Table)
+            .and_where(Expr::col(TransferSchema::TransferTo).eq(user_id))
+            .and_where(Expr::col(TransferSchema::DeletedAt).is_null())
+            .and_where(Expr::col(TransferSchema::Status).is_in(TransferStatus::SETTLED))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let totals = sqlx::query_as_with::<_, (Money, i64), _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Transfers] Failed to sum received transfers for user_id={user_id}: {e}"
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        Ok(totals)
+    }
+
+    async fn net_flow(
+        &self,
+        user_id: i32,
+        counterparty: Option<i32>,
+        from: Option<NaiveDateTime>,
+        to: NaiveDateTime,
+        currency: Currency,
+    ) -> Result<TransferNetFlow, AppError> {
+        info!(
+            "📊 [Transfers] Computing {currency} net flow for user_id={user_id}, counterparty={counterparty:?}, from={from:?}, to={to}"
+        );
+
+        let total = |party: TransferSchema| {
+            Func::cast_as(
+                Func::coalesce([
+                    Func::sum(
+                        Expr::case(
+                            Expr::col(party).eq(user_id),
+                            Expr::col(TransferSchema::TransferAmount),
+                        )
+                        .finally(Expr::val(0i64)),
+                    )
+                    .into(),
+                    Expr::val(0i64).into(),
+                ]),
+                Alias::new("BIGINT"),
+            )
+        };
+
+        let mut query = Query::select();
+        query
+            .expr_as(total(TransferSchema::TransferTo), Alias::new("incoming"))
+            .expr_as(total(TransferSchema::TransferFrom), Alias::new("outgoing"))
+            .from(TransferSchema::Table)
+            .cond_where(
+                Cond::any()
+                    .add(Expr::col(TransferSchema::TransferFrom).eq(user_id))
+                    .add(Expr::col(TransferSchema::TransferTo).eq(user_id)),
+            )
+            .and_where(Expr::col(TransferSchema::Currency).eq(currency))
+            .and_where(Expr::col(TransferSchema::DeletedAt).is_null())
+            .and_where(Expr::col(TransferSchema::Status).is_in(TransferStatus::SETTLED))
+            .and_where(Expr::col(TransferSchema::TransferTime).lte(to));
+
+        if let Some(counterparty) = counterparty {
+            query.cond_where(
+                Cond::any()
+                    .add(Expr::col(TransferSchema::TransferFrom).eq(counterparty))
+                    .add(Expr::col(TransferSchema::TransferTo).eq(counterparty)),
+            );
+        }
+
+        if let Some(from) = from {
+            query.and_where(Expr::col(TransferSchema::TransferTime).gte(from));
+        }
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 [Transfers] Executing query: {sql} | Values: {:?}",
+            values
+        );
+
+        let totals = sqlx::query_as_with::<_, TransferNetFlow, _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to compute net flow for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [Transfers] Net flow for user_id={user_id}: incoming={}, outgoing={}",
+            totals.incoming, totals.outgoing
+        );
+
+        Ok(totals)
+    }
+
+    async fn find_counterparties(
+        &self,
+        user_id: i32,
+        currency: Currency,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<TransferCounterparty>, i64), AppError> {
+        info!(
+            "👥 [Transfers] Grouping {currency} transfers of user_id={user_id} by counterparty | Page: {page}, Size: {page_size}"
+        );
+
+        let offset = (page - 1) * page_size;
+
+        let sent = Expr::col((TransferSchema::Table, TransferSchema::TransferFrom)).eq(user_id);
+        let received = Expr::col((TransferSchema::Table, TransferSchema::TransferTo)).eq(user_id);
+
+        // Whichever side of the transfer isn't the user.
+        let counterparty: SimpleExpr = Expr::case(
+            sent.clone(),
+            Expr::col((TransferSchema::Table, TransferSchema::TransferTo)),
+        )
+        .finally(Expr::col((
+            TransferSchema::Table,
+            TransferSchema::TransferFrom,
+        )))
+        .into();
+
+        let total = |side: SimpleExpr| {
+            Func::cast_as(
+                Func::sum(
+                    Expr::case(
+                        side,
+                        Expr::col((TransferSchema::Table, TransferSchema::TransferAmount)),
+                    )
+                    .finally(Expr::val(0i64)),
+                ),
+                Alias::new("BIGINT"),
+            )
+        };
+
+        let mut query = Query::select();
+        query
+            .from(TransferSchema::Table)
+            .cond_where(Cond::any().add(sent.clone()).add(received.clone()))
+            .and_where(Expr::col((TransferSchema::Table, TransferSchema::Currency)).eq(currency))
+            .and_where(Expr::col((TransferSchema::Table, TransferSchema::DeletedAt)).is_null())
+            .and_where(
+                Expr::col((TransferSchema::Table, TransferSchema::Status))
+                    .is_in(TransferStatus::SETTLED),
+            );
+
+        let (count_sql, count_values) = query
+            .clone()
+            .expr(Func::count_distinct(counterparty.clone()))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (sql, values) = query
+            .expr_as(
+                Expr::col((Users::Table, Users::UserId)),
+                Alias::new("counterparty_id"),
+            )
+            .column((Users::Table, Users::Firstname))
+            .column((Users::Table, Users::Lastname))
+            .column((TransferSchema::Table, TransferSchema::Currency))
+            .expr_as(total(sent), Alias::new("total_sent"))
+            .expr_as(total(received), Alias::new("total_received"))
+            .expr_as(
+                Func::count(Expr::col((
+                    TransferSchema::Table,
+                    TransferSchema::TransferId,
+                ))),
+                Alias::new("transfer_count"),
+            )
+            .expr_as(
+                Func::max(Expr::col((
+                    TransferSchema::Table,
+                    TransferSchema::TransferTime,
+                ))),
+                Alias::new("last_transfer_at"),
+            )
+            .join(
+                JoinType::InnerJoin,
+                Users::Table,
+                Expr::col((Users::Table, Users::UserId)).eq(counterparty),
+            )
+            .group_by_columns([
+                (Users::Table, Users::UserId),
+                (Users::Table, Users::Firstname),
+                (Users::Table, Users::Lastname),
+            ])
+            .group_by_col((TransferSchema::Table, TransferSchema::Currency))
+            .order_by(Alias::new("transfer_count"), Order::Desc)
+            .order_by(Alias::new("last_transfer_at"), Order::Desc)
+            .order_by(Alias::new("counterparty_id"), Order::Asc)
+            .limit(page_size as u64)
+            .offset(offset as u64)
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 [Transfers] Executing query: {sql} | Values: {:?}",
+            values
+        );
+
+        let rows = sqlx::query_as_with::<_, TransferCounterparty, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to group transfers of user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        let (total,) = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to count counterparties of user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [Transfers] Returned {} of {total} counterparties for user_id={user_id}",
+            rows.len()
+        );
+
+        Ok((rows, total))
+    }
+
+    async fn find_between_users(
+        &self,
+        from: i32,
+        to: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Transfer>, i64), AppError> {
+        info!(
+            "🔁 [Transfers] Fetching transfers from user ID {from} to user ID {to} - page: {page}, page_size: {page_size}"
+        );
+
+        let page = if page > 0 { page } else { 1 };
+        let page_size = if page_size > 0 { page_size } else { 10 };
+        let offset = (page - 1) * page_size;
+
+        let mut query = Query::select();
+        query
+            .from(TransferSchema::Table)
+            .and_where(Expr::col(TransferSchema::TransferFrom).eq(from))
+            .and_where(Expr::col(TransferSchema::TransferTo).eq(to))
+            .and_where(Expr::col(TransferSchema::DeletedAt).is_null());
+
+        let (sql, values) = query
+            .clone()
+            .columns([
+                TransferSchema::TransferId,
+                TransferSchema::TransferFrom,
+                TransferSchema::TransferTo,
+                TransferSchema::TransferAmount,
+                TransferSchema::TransferTime,
+                TransferSchema::CreatedAt,
+                TransferSchema::UpdatedAt,
+                TransferSchema::DeletedAt,
+                TransferSchema::Currency,
+                TransferSchema::Status,
+                TransferSchema::ScheduledAt,
+                TransferSchema::Note,
+                TransferSchema::ReversedTransferId,
+                TransferSchema::Fee,
+            ])
+            .order_by(TransferSchema::TransferTime, Order::Desc)
+            .order_by(TransferSchema::TransferId, Order::Desc)
+            .limit(page_size as u64)
+            .offset(offset as u64)
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 [Transfers] Executing query: {sql} | Values: {:?}",
+            values
+        );
+
+        let rows = sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to fetch transfers from {from} to {to}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        let (count_sql, count_values) = query
+            .expr(Func::count(Expr::col(TransferSchema::TransferId)))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (total,) = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to count transfers from {from} to {to}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [Transfers] Returned {} of {total} transfer(s) from user ID {from} to user ID {to}",
+            rows.len()
+        );
+
+        Ok((rows, total))
     }
 
     async fn find_by_user(&self, user_id: i32) -> Result<Option<Transfer>, AppError> {
@@ -224,8 +794,16 @@ impl TransferRepositoryTrait for TransferRepository {
                 TransferSchema::TransferTime,
                 TransferSchema::CreatedAt,
                 TransferSchema::UpdatedAt,
+                TransferSchema::DeletedAt,
+                TransferSchema::Currency,
+                TransferSchema::Status,
+                TransferSchema::ScheduledAt,
+                TransferSchema::Note,
+                TransferSchema::ReversedTransferId,
+                TransferSchema::Fee,
             ])
             .and_where(Expr::col(TransferSchema::TransferFrom).eq(user_id))
+            .and_where(Expr::col(TransferSchema::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
@@ -259,6 +837,22 @@ impl TransferRepositoryTrait for TransferRepository {
     }
 
     async fn create(&self, input: &CreateTransferRequest) -> Result<Transfer, AppError> {
+        retry_transient("[Transfers] create", || async {
+            let mut conn = self.db_pool.acquire().await.map_err(|e| {
+                error!("❌ [Transfers] Failed to acquire connection: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+            self.create_tx(&mut conn, input).await
+        })
+        .await
+    }
+
+    async fn create_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &CreateTransferRequest,
+    ) -> Result<Transfer, AppError> {
         info!(
             "💸 [Transfers] Creating new transfer: {} → {} | Amount: {}",
             input.transfer_from, input.transfer_to, input.transfer_amount
@@ -273,12 +867,16 @@ impl TransferRepositoryTrait for TransferRepository {
                 TransferSchema::TransferTo,
                 TransferSchema::TransferAmount,
                 TransferSchema::TransferTime,
+                TransferSchema::Currency,
+                TransferSchema::Note,
             ])
             .values([
                 input.transfer_from.into(),
                 input.transfer_to.into(),
                 input.transfer_amount.into(),
                 now.into(),
+                input.currency.into(),
+                input.note.clone().into(),
             ])
             .unwrap()
             .returning_all()
@@ -287,7 +885,7 @@ impl TransferRepositoryTrait for TransferRepository {
         info!("🧾 [Transfers] INSERT query: {sql} | Values: {:?}", values);
 
         let created = sqlx::query_as_with::<_, Transfer, _>(&sql, values)
-            .fetch_one(&self.db_pool)
+            .fetch_one(&mut *conn)
             .await
             .map_err(|e| {
                 error!(
@@ -305,7 +903,11 @@ impl TransferRepositoryTrait for TransferRepository {
         Ok(created)
     }
 
-    async fn update(&self, input: &UpdateTransferRequest) -> Result<Transfer, AppError> {
+    async fn update_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &UpdateTransferRequest,
+    ) -> Result<Transfer, AppError> {
         info!(
             "🔄 [Transfers] Updating full transfer with ID: {}",
             input.transfer_id
@@ -323,12 +925,14 @@ impl TransferRepositoryTrait for TransferRepository {
                 (TransferSchema::UpdatedAt, now.into()),
             ])
             .and_where(Expr::col(TransferSchema::TransferId).eq(input.transfer_id))
+            .and_where(Expr::col(TransferSchema::DeletedAt).is_null())
+            .returning_all()
             .build_sqlx(PostgresQueryBuilder);
 
         info!("🧾 [Transfers] UPDATE query: {sql} | Values: {:?}", values);
 
         let updated = sqlx::query_as_with::<_, Transfer, _>(&sql, values)
-            .fetch_one(&self.db_pool)
+            .fetch_one(&mut *conn)
             .await
             .map_err(|e| match e {
                 sqlx::Error::RowNotFound => {
@@ -412,31 +1016,521 @@ impl TransferRepositoryTrait for TransferRepository {
     }
 
     async fn delete(&self, id: i32) -> Result<(), AppError> {
+        let mut conn = self.db_pool.acquire().await.map_err(|e| {
+            error!("❌ [Transfers] Failed to acquire connection: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        self.delete_tx(&mut conn, id).await.map(|_| ())
+    }
+
+    async fn delete_tx(&self, conn: &mut PgConnection, id: i32) -> Result<Transfer, AppError> {
         info!("🗑️ [Transfers] Deleting transfer with ID: {id}");
 
-        let (sql, values) = Query::delete()
-            .from_table(TransferSchema::Table)
+        let now = chrono::Utc::now().naive_utc();
+
+        let (sql, values) = Query::update()
+            .table(TransferSchema::Table)
+            .values([
+                (TransferSchema::DeletedAt, now.into()),
+                (TransferSchema::UpdatedAt, now.into()),
+            ])
             .and_where(Expr::col(TransferSchema::TransferId).eq(id))
+            .and_where(Expr::col(TransferSchema::DeletedAt).is_null())
+            .returning_all()
             .build_sqlx(PostgresQueryBuilder);
 
-        info!("🧾 [Transfers] DELETE query: {sql} | Values: {:?}", values);
-
-        let result = sqlx::query_with(&sql, values)
-            .execute(&self.db_pool)
+        info!(
+            "🧾 [Transfers] Soft DELETE query: {sql} | Values: {:?}",
+            values
+        );
+
+        let deleted = sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+            .fetch_optional(&mut *conn)
             .await
             .map_err(|e| {
                 error!("❌ [Transfers] Failed to delete transfer ID {id}: {e}");
                 AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| {
+                error!("❌ [Transfers] Deletion failed: No transfer found with ID {id}");
+                AppError::NotFound(format!("Transfer with ID {id} not found"))
+            })?;
+
+        info!("✅ [Transfers] Successfully deleted transfer ID: {id}");
+        Ok(deleted)
+    }
+
+    async fn delete_unsettled_by_user_tx(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<u64, AppError> {
+        info!("🗑️ [Transfers] Deleting unsettled transfers for user_id: {user_id}");
+
+        let now = chrono::Utc::now().naive_utc();
+
+        let (sql, values) = Query::update()
+            .table(TransferSchema::Table)
+            .values([
+                (TransferSchema::DeletedAt, now.into()),
+                (TransferSchema::UpdatedAt, now.into()),
+            ])
+            .and_where(
+                Expr::col(TransferSchema::TransferFrom)
+                    .eq(user_id)
+                    .or(Expr::col(TransferSchema::TransferTo).eq(user_id)),
+            )
+            .and_where(Expr::col(TransferSchema::Status).is_not_in(TransferStatus::SETTLED))
+            .and_where(Expr::col(TransferSchema::DeletedAt).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 [Transfers] Soft DELETE query: {sql} | Values: {:?}",
+            values
+        );
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Transfers] Failed to delete unsettled transfers for user_id={user_id}: {e}"
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [Transfers] Deleted {} unsettled transfers for user_id={user_id}",
+            result.rows_affected()
+        );
+        Ok(result.rows_affected())
+    }
+
+    async fn restore(&self, id: i32) -> Result<Transfer, AppError> {
+        let mut conn = self.db_pool.acquire().await.map_err(|e| {
+            error!("❌ [Transfers] Failed to acquire connection: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        self.restore_tx(&mut conn, id).await
+    }
+
+    async fn restore_tx(&self, conn: &mut PgConnection, id: i32) -> Result<Transfer, AppError> {
+        info!("♻️ [Transfers] Restoring transfer with ID: {id}");
+
+        let (sql, values) = Query::update()
+            .table(TransferSchema::Table)
+            .value(TransferSchema::DeletedAt, Keyword::Null)
+            .value(TransferSchema::UpdatedAt, chrono::Utc::now().naive_utc())
+            .and_where(Expr::col(TransferSchema::TransferId).eq(id))
+            .and_where(Expr::col(TransferSchema::DeletedAt).is_not_null())
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [Transfers] Restore query: {sql} | Values: {:?}", values);
+
+        let restored = sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to restore transfer ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| {
+                error!("🟡 [Transfers] Restore failed: No deleted transfer found with ID {id}");
+                AppError::NotFound(format!("Deleted transfer with ID {id} not found"))
+            })?;
+
+        info!("✅ [Transfers] Successfully restored transfer ID: {id}");
+        Ok(restored)
+    }
+
+    async fn create_scheduled(
+        &self,
+        input: &CreateTransferRequest,
+        scheduled_at: NaiveDateTime,
+    ) -> Result<Transfer, AppError> {
+        info!(
+            "🗓️ [Transfers] Scheduling transfer: {} → {} | Amount: {} | At: {scheduled_at}",
+            input.transfer_from, input.transfer_to, input.transfer_amount
+        );
+
+        // transfer_time is moved to the actual execution time on completion.
+        let (sql, values) = Query::insert()
+            .into_table(TransferSchema::Table)
+            .columns([
+                TransferSchema::TransferFrom,
+                TransferSchema::TransferTo,
+                TransferSchema::TransferAmount,
+                TransferSchema::TransferTime,
+                TransferSchema::Currency,
+                TransferSchema::Status,
+                TransferSchema::ScheduledAt,
+                TransferSchema::Note,
+            ])
+            .values([
+                input.transfer_from.into(),
+                input.transfer_to.into(),
+                input.transfer_amount.into(),
+                scheduled_at.into(),
+                input.currency.into(),
+                TransferStatus::Pending.into(),
+                scheduled_at.into(),
+                input.note.clone().into(),
+            ])
+            .unwrap()
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let created = sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Transfers] Failed to schedule transfer ({} → {}): {e}",
+                    input.transfer_from, input.transfer_to,
+                );
+                AppError::SqlxError(e)
             })?;
 
-        if result.rows_affected() == 0 {
-            error!("❌ [Transfers] Deletion failed: No transfer found with ID {id}");
-            return Err(AppError::NotFound(format!(
-                "Transfer with ID {id} not found"
-            )));
+        info!(
+            "✅ [Transfers] Scheduled transfer ID: {}",
+            created.transfer_id
+        );
+
+        Ok(created)
+    }
+
+    async fn find_scheduled(&self, transfer_from: Option<i32>) -> Result<Vec<Transfer>, AppError> {
+        info!("🗓️ [Transfers] Fetching pending transfers, sender: {transfer_from:?}");
+
+        let mut query = Self::select_transfers();
+        query
+            .and_where(Expr::col(TransferSchema::Status).eq(TransferStatus::Pending))
+            .and_where(Expr::col(TransferSchema::DeletedAt).is_null())
+            .order_by(TransferSchema::ScheduledAt, Order::Asc)
+            .order_by(TransferSchema::TransferId, Order::Asc);
+
+        if let Some(transfer_from) = transfer_from {
+            query.and_where(Expr::col(TransferSchema::TransferFrom).eq(transfer_from));
         }
 
-        info!("✅ [Transfers] Successfully deleted transfer ID: {id}");
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+
+        let rows = sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to fetch pending transfers: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        Ok(rows)
+    }
+
+    async fn find_due(&self, now: NaiveDateTime, limit: u64) -> Result<Vec<Transfer>, AppError> {
+        let mut query = Self::select_transfers();
+        query
+            .and_where(Expr::col(TransferSchema::Status).eq(TransferStatus::Pending))
+            .and_where(Expr::col(TransferSchema::DeletedAt).is_null())
+            .and_where(Expr::col(TransferSchema::ScheduledAt).lte(now))
+            .order_by(TransferSchema::ScheduledAt, Order::Asc)
+            .limit(limit);
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+
+        let rows = sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to fetch due transfers: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        if !rows.is_empty() {
+            info!("🗓️ [Transfers] {} scheduled transfer(s) due", rows.len());
+        }
+
+        Ok(rows)
+    }
+
+    // Locks a still-pending, live transfer. Rows another worker holds are
+    // skipped rather than waited on; None means it is gone, taken or settled.
+    async fn find_by_id_for_update_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+    ) -> Result<Option<Transfer>, AppError> {
+        info!("🔒 [Transfers] Locking transfer ID: {id}");
+
+        let mut query = Self::select_transfers();
+        query
+            .and_where(Expr::col(TransferSchema::TransferId).eq(id))
+            .and_where(Expr::col(TransferSchema::DeletedAt).is_null())
+            .lock(LockType::Update);
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to lock transfer ID {id}: {e}");
+                AppError::SqlxError(e)
+            })
+    }
+
+    async fn claim_pending_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+    ) -> Result<Option<Transfer>, AppError> {
+        let mut query = Self::select_transfers();
+        query
+            .and_where(Expr::col(TransferSchema::TransferId).eq(id))
+            .and_where(Expr::col(TransferSchema::Status).eq(TransferStatus::Pending))
+            .and_where(Expr::col(TransferSchema::DeletedAt).is_null())
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked);
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to claim pending transfer ID {id}: {e}");
+                AppError::SqlxError(e)
+            })
+    }
+
+    async fn set_status_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+        status: TransferStatus,
+    ) -> Result<Transfer, AppError> {
+        info!("🗓️ [Transfers] Marking transfer ID {id} as {status}");
+
+        let now = Utc::now().naive_utc();
+
+        let mut query = Query::update();
+        query
+            .table(TransferSchema::Table)
+            .values([
+                (TransferSchema::Status, status.into()),
+                (TransferSchema::UpdatedAt, now.into()),
+            ])
+            .and_where(Expr::col(TransferSchema::TransferId).eq(id))
+            .returning_all();
+
+        if status == TransferStatus::Completed {
+            query.value(TransferSchema::TransferTime, now);
+        }
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to mark transfer ID {id} as {status}: {e}");
+                AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| AppError::NotFound(format!("Transfer with ID {id} not found")))
+    }
+
+    async fn mark_reversed_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+    ) -> Result<Option<Transfer>, AppError> {
+        info!("↩️ [Transfers] Marking transfer ID {id} as reversed");
+
+        // Conditional on the current state, so of two concurrent reversals
+        // only one gets the row back.
+        let (sql, values) = Query::update()
+            .table(TransferSchema::Table)
+            .values([
+                (TransferSchema::Status, TransferStatus::Reversed.into()),
+                (TransferSchema::UpdatedAt, Utc::now().naive_utc().into()),
+            ])
+            .and_where(Expr::col(TransferSchema::TransferId).eq(id))
+            .and_where(Expr::col(TransferSchema::Status).eq(TransferStatus::Completed))
+            .and_where(Expr::col(TransferSchema::ReversedTransferId).is_null())
+            .and_where(Expr::col(TransferSchema::DeletedAt).is_null())
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to mark transfer ID {id} as reversed: {e}");
+                AppError::SqlxError(e)
+            })
+    }
+
+    async fn create_reversal_tx(
+        &self,
+        conn: &mut PgConnection,
+        original: &Transfer,
+    ) -> Result<Transfer, AppError> {
+        info!(
+            "↩️ [Transfers] Creating reversal of transfer ID {}: {} → {} | Amount: {}",
+            original.transfer_id,
+            original.transfer_to,
+            original.transfer_from,
+            original.transfer_amount
+        );
+
+        let (sql, values) = Query::insert()
+            .into_table(TransferSchema::Table)
+            .columns([
+                TransferSchema::TransferFrom,
+                TransferSchema::TransferTo,
+                TransferSchema::TransferAmount,
+                TransferSchema::TransferTime,
+                TransferSchema::Currency,
+                TransferSchema::Status,
+                TransferSchema::ReversedTransferId,
+            ])
+            .values([
+                original.transfer_to.into(),
+                original.transfer_from.into(),
+                original.transfer_amount.into(),
+                Utc::now().naive_utc().into(),
+                original.currency.into(),
+                TransferStatus::Completed.into(),
+                original.transfer_id.into(),
+            ])
+            .unwrap()
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Transfers] Failed to create reversal of transfer ID {}: {e}",
+                    original.transfer_id
+                );
+                AppError::SqlxError(e)
+            })
+    }
+
+    async fn charge_fee_tx(
+        &self,
+        conn: &mut PgConnection,
+        transfer: &Transfer,
+        fee: Money,
+    ) -> Result<Transfer, AppError> {
+        info!(
+            "🪙 [Transfers] Charging fee of {fee} to user {} for transfer ID {}",
+            transfer.transfer_from, transfer.transfer_id
+        );
+
+        let (sql, values) = Query::update()
+            .table(TransferSchema::Table)
+            .values([
+                (TransferSchema::Fee, fee.into()),
+                (TransferSchema::UpdatedAt, Utc::now().naive_utc().into()),
+            ])
+            .and_where(Expr::col(TransferSchema::TransferId).eq(transfer.transfer_id))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let charged = sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Transfers] Failed to set fee on transfer ID {}: {e}",
+                    transfer.transfer_id
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        // An amount change re-prices the fee; the old charge is replaced
+        // rather than stacked on.
+        let (sql, values) = Query::delete()
+            .from_table(FeeSchema::Table)
+            .and_where(Expr::col(FeeSchema::TransferId).eq(transfer.transfer_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Transfers] Failed to clear fees of transfer ID {}: {e}",
+                    transfer.transfer_id
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        if fee == Money::ZERO {
+            return Ok(charged);
+        }
+
+        let (sql, values) = Query::insert()
+            .into_table(FeeSchema::Table)
+            .columns([
+                FeeSchema::TransferId,
+                FeeSchema::UserId,
+                FeeSchema::Amount,
+                FeeSchema::Currency,
+            ])
+            .values([
+                transfer.transfer_id.into(),
+                transfer.transfer_from.into(),
+                fee.into(),
+                transfer.currency.into(),
+            ])
+            .unwrap()
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Transfers] Failed to record fee for transfer ID {}: {e}",
+                    transfer.transfer_id
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        Ok(charged)
+    }
+
+    async fn refund_fee_tx(
+        &self,
+        conn: &mut PgConnection,
+        transfer: &Transfer,
+    ) -> Result<(), AppError> {
+        info!(
+            "🪙 [Transfers] Refunding fee of {} to user {} for transfer ID {}",
+            transfer.fee, transfer.transfer_from, transfer.transfer_id
+        );
+
+        let (sql, values) = Query::delete()
+            .from_table(FeeSchema::Table)
+            .and_where(Expr::col(FeeSchema::TransferId).eq(transfer.transfer_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Transfers] Failed to refund fees of transfer ID {}: {e}",
+                    transfer.transfer_id
+                );
+                AppError::SqlxError(e)
+            })?;
+
         Ok(())
     }
 }
@@ -1,14 +1,34 @@
 use async_trait::async_trait;
-use sea_query::{Expr, Func, Order, PostgresQueryBuilder, Query};
+use sea_query::{
+    Alias, Cond, Condition, Expr, Func, Keyword, Order, PostgresQueryBuilder, Query, SimpleExpr,
+    extension::postgres::PgBinOper,
+};
 use sea_query_binder::SqlxBinder;
+use sqlx::PgConnection;
 use tracing::{error, info};
 
 use crate::abstract_trait::UserRepositoryTrait;
 use crate::config::ConnectionPool;
-use crate::domain::request::user::{CreateUserRequest, UpdateUserRequest};
+use crate::domain::request::search::Search;
+use crate::domain::request::sort::{Sort, invalid_sort_by};
+use crate::domain::request::user::{CreateUserRequest, UpdateUserRequest, UserSearch};
+use crate::domain::role::Role;
 use crate::model::user::User;
 use crate::schema::user::Users;
-use crate::utils::AppError;
+use crate::utils::{AppError, mask_noc};
+
+const SORTABLE_COLUMNS: &[&str] = &[
+    "id",
+    "user_id",
+    "firstname",
+    "lastname",
+    "email",
+    "created_at",
+    "updated_at",
+];
+
+// Name Postgres gave the inline `UNIQUE` on `users.noc_transfer`.
+const NOC_TRANSFER_KEY: &str = "users_noc_transfer_key";
 
 pub struct UserRepository {
     db_pool: ConnectionPool,
@@ -18,6 +38,57 @@ impl UserRepository {
     pub fn new(db_pool: ConnectionPool) -> Self {
         Self { db_pool }
     }
+
+    fn sort_column(sort_by: Option<&str>) -> Result<Users, AppError> {
+        match sort_by.filter(|s| !s.is_empty()).unwrap_or("created_at") {
+            "id" | "user_id" => Ok(Users::UserId),
+            "firstname" => Ok(Users::Firstname),
+            "lastname" => Ok(Users::Lastname),
+            "email" => Ok(Users::Email),
+            "created_at" => Ok(Users::CreatedAt),
+            "updated_at" => Ok(Users::UpdatedAt),
+            other => Err(invalid_sort_by(other, SORTABLE_COLUMNS)),
+        }
+    }
+
+    // Case-insensitive match on name or email.
+    fn search_condition(search: &Search) -> Condition {
+        [Users::Firstname, Users::Lastname, Users::Email]
+            .into_iter()
+            .fold(Cond::any(), |cond, column| {
+                cond.add(search.matches_ignore_case(Expr::col(column)))
+            })
+    }
+
+    // pg_trgm `%` against each column, so the GIN trigram indexes apply.
+    // Matches above `pg_trgm.similarity_threshold` (0.3 by default).
+    fn fuzzy_condition(term: &str) -> Condition {
+        [Users::Firstname, Users::Lastname, Users::Email]
+            .into_iter()
+            .fold(Cond::any(), |cond, column| {
+                cond.add(Expr::col(column).binary(PgBinOper::Similarity, term))
+            })
+    }
+
+    // Best similarity of the term to any searchable column.
+    fn fuzzy_rank(term: &str) -> SimpleExpr {
+        Func::greatest(
+            [Users::Firstname, Users::Lastname, Users::Email].map(|column| {
+                Func::cust(Alias::new("similarity"))
+                    .arg(Expr::col(column))
+                    .arg(term)
+                    .into()
+            }),
+        )
+        .into()
+    }
+
+    fn filter_condition(search: &UserSearch) -> Condition {
+        match search {
+            UserSearch::Match(search) => Self::search_condition(search),
+            UserSearch::Fuzzy(term) => Self::fuzzy_condition(term),
+        }
+    }
 }
 
 #[async_trait]
@@ -26,13 +97,17 @@ impl UserRepositoryTrait for UserRepository {
         &self,
         page: i32,
         page_size: i32,
-        search: Option<String>,
+        search: Option<UserSearch>,
+        sort: Sort,
+        include_deleted: bool,
     ) -> Result<(Vec<User>, i64), AppError> {
         info!(
             "👥 [Users] Fetching all users - page: {page}, page_size: {page_size}, search: {:?}",
             search
         );
 
+        let sort_column = Self::sort_column(sort.by.as_deref())?;
+
         let page = if page > 0 { page } else { 1 };
         let page_size = if page_size > 0 { page_size } else { 10 };
         let offset = (page - 1) * page_size;
@@ -48,19 +123,43 @@ impl UserRepositoryTrait for UserRepository {
                 Users::Email,
                 Users::Password,
                 Users::NocTransfer,
+                Users::Role,
+                Users::IsVerified,
+                Users::LastLoginAt,
                 Users::CreatedAt,
                 Users::UpdatedAt,
+                Users::DeletedAt,
             ])
             .from(Users::Table)
-            .order_by(Users::UserId, Order::Asc)
             .limit(page_size as u64)
             .offset(offset as u64);
 
-        if let Some(ref term) = search {
-            select_query.and_where(Expr::col(Users::Email).like(format!("{term}%")));
-            info!("🔍 [Users] Filtering by email prefix: {term}%");
+        if !include_deleted {
+            select_query.and_where(Expr::col(Users::DeletedAt).is_null());
         }
 
+        if let Some(ref search) = search {
+            select_query.cond_where(Self::filter_condition(search));
+
+            match search {
+                UserSearch::Match(search) => {
+                    info!(
+                        "🔍 [Users] Filtering by name or email ({:?}): {}",
+                        search.mode, search.term
+                    );
+                }
+                UserSearch::Fuzzy(term) => {
+                    // Closest matches first; the requested sort only breaks ties.
+                    select_query.order_by_expr(Self::fuzzy_rank(term), Order::Desc);
+                    info!("🔍 [Users] Fuzzy matching name or email against: {term}");
+                }
+            }
+        }
+
+        select_query
+            .order_by(sort_column, sort.order.into())
+            .order_by(Users::UserId, sort.order.into());
+
         let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
         info!("🧾 [Users] Generated SQL: {sql} | Values: {:?}", values);
 
@@ -84,8 +183,12 @@ impl UserRepositoryTrait for UserRepository {
             .expr(Func::count(Expr::col(Users::UserId)))
             .from(Users::Table);
 
-        if let Some(ref term) = search {
-            count_query.and_where(Expr::col(Users::Email).like(format!("{term}%")));
+        if !include_deleted {
+            count_query.and_where(Expr::col(Users::DeletedAt).is_null());
+        }
+
+        if let Some(ref search) = search {
+            count_query.cond_where(Self::filter_condition(search));
         }
 
         let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
@@ -120,6 +223,7 @@ impl UserRepositoryTrait for UserRepository {
     async fn find_by_email_exists(&self, email: &str) -> Result<bool, AppError> {
         info!("🔍 Checking if user with email '{email}' exists");
 
+        // Soft-deleted accounts are counted too: the email column is still UNIQUE.
         let (sql, values) = Query::select()
             .expr(Expr::col(Users::UserId).count())
             .from(Users::Table)
@@ -153,6 +257,25 @@ impl UserRepositoryTrait for UserRepository {
         Ok(count > 0)
     }
 
+    async fn admin_exists(&self) -> Result<bool, AppError> {
+        let (sql, values) = Query::select()
+            .expr(Expr::col(Users::UserId).count())
+            .from(Users::Table)
+            .and_where(Expr::col(Users::Role).eq(Role::Admin))
+            .and_where(Expr::col(Users::DeletedAt).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        let count: i64 = sqlx::query_scalar_with(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [User] Failed to count admins: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        Ok(count > 0)
+    }
+
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
         info!("📧 Looking up user by email: '{email}'");
 
@@ -164,11 +287,16 @@ impl UserRepositoryTrait for UserRepository {
                 Users::Email,
                 Users::Password,
                 Users::NocTransfer,
+                Users::Role,
+                Users::IsVerified,
+                Users::LastLoginAt,
                 Users::CreatedAt,
                 Users::UpdatedAt,
+                Users::DeletedAt,
             ])
             .from(Users::Table)
             .and_where(Expr::col(Users::Email).eq(email))
+            .and_where(Expr::col(Users::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
@@ -210,11 +338,16 @@ impl UserRepositoryTrait for UserRepository {
                 Users::Email,
                 Users::Password,
                 Users::NocTransfer,
+                Users::Role,
+                Users::IsVerified,
+                Users::LastLoginAt,
                 Users::CreatedAt,
                 Users::UpdatedAt,
+                Users::DeletedAt,
             ])
             .from(Users::Table)
             .and_where(Expr::col(Users::UserId).eq(id))
+            .and_where(Expr::col(Users::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
@@ -245,6 +378,112 @@ impl UserRepositoryTrait for UserRepository {
         Ok(user)
     }
 
+    async fn find_by_noc(&self, noc_transfer: &str) -> Result<Option<User>, AppError> {
+        let masked = mask_noc(noc_transfer);
+        info!("💳 Looking up user by noc_transfer: {masked}");
+
+        // Served by the unique index on `noc_transfer`.
+        let (sql, values) = Query::select()
+            .columns([
+                Users::UserId,
+                Users::Firstname,
+                Users::Lastname,
+                Users::Email,
+                Users::Password,
+                Users::NocTransfer,
+                Users::Role,
+                Users::IsVerified,
+                Users::LastLoginAt,
+                Users::CreatedAt,
+                Users::UpdatedAt,
+                Users::DeletedAt,
+            ])
+            .from(Users::Table)
+            .and_where(Expr::col(Users::NocTransfer).eq(noc_transfer))
+            .and_where(Expr::col(Users::DeletedAt).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        let user = sqlx::query_as_with::<_, User, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ Database error while fetching user by noc_transfer {masked}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        match user {
+            Some(ref u) => info!("✅ User found by noc_transfer {masked}: ID={}", u.user_id),
+            None => error!("❌ No user with noc_transfer {masked}"),
+        }
+
+        Ok(user)
+    }
+
+    async fn find_existing_ids(&self, ids: &[i32]) -> Result<Vec<i32>, AppError> {
+        info!("🆔 Checking which of {} user IDs exist", ids.len());
+
+        let (sql, values) = Query::select()
+            .column(Users::UserId)
+            .from(Users::Table)
+            .and_where(Expr::col(Users::UserId).is_in(ids.iter().copied()))
+            .and_where(Expr::col(Users::DeletedAt).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 Executing user ID existence check: {sql} | Values: {:?}",
+            values
+        );
+
+        let existing: Vec<i32> = sqlx::query_scalar_with(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ Database error while checking user IDs: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        Ok(existing)
+    }
+
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<User>, AppError> {
+        info!("🆔 Looking up {} users by ID", ids.len());
+
+        let (sql, values) = Query::select()
+            .columns([
+                Users::UserId,
+                Users::Firstname,
+                Users::Lastname,
+                Users::Email,
+                Users::Password,
+                Users::NocTransfer,
+                Users::Role,
+                Users::IsVerified,
+                Users::LastLoginAt,
+                Users::CreatedAt,
+                Users::UpdatedAt,
+                Users::DeletedAt,
+            ])
+            .from(Users::Table)
+            .and_where(Expr::col(Users::UserId).is_in(ids.iter().copied()))
+            .and_where(Expr::col(Users::DeletedAt).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 Executing query to find users by ID: {sql} | Values: {:?}",
+            values
+        );
+
+        let users = sqlx::query_as_with::<_, User, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ Database error while finding users by ID: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        Ok(users)
+    }
+
     async fn create_user(&self, input: &CreateUserRequest) -> Result<User, AppError> {
         info!(
             "👤 [User] Creating new user: {} {}",
@@ -277,6 +516,21 @@ impl UserRepositoryTrait for UserRepository {
             .fetch_one(&self.db_pool)
             .await
             .map_err(|e| {
+                let violated = e
+                    .as_database_error()
+                    .filter(|db_err| db_err.is_unique_violation())
+                    .and_then(|db_err| db_err.constraint());
+
+                if violated == Some("users_email_key") {
+                    error!("🛑 [User] Email already registered: {}", input.email);
+                    return AppError::EmailAlreadyExists;
+                }
+
+                if violated == Some(NOC_TRANSFER_KEY) {
+                    error!("🛑 [User] noc_transfer collision for {}", input.email);
+                    return AppError::NocTransferTaken;
+                }
+
                 error!(
                     "❌ [User] Failed to create user '{} {}': {e}",
                     input.firstname, input.lastname,
@@ -318,6 +572,12 @@ impl UserRepositoryTrait for UserRepository {
             updated_fields.push(format!("email='{email}'"));
         }
 
+        // Expected to be hashed already; never logged.
+        if let Some(ref password) = input.password {
+            query = query.value(Users::Password, password.clone());
+            updated_fields.push("password".to_string());
+        }
+
         if updated_fields.is_empty() {
             info!("🟡 [User] No fields to update for user ID: {id}");
             return Err(AppError::Custom(
@@ -325,10 +585,12 @@ impl UserRepositoryTrait for UserRepository {
             ));
         }
 
-        query = query.returning_all();
+        query = query
+            .value(Users::UpdatedAt, chrono::Utc::now().naive_utc())
+            .returning_all();
 
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
-        info!("🧾 [User] UPDATE query: {sql} | Values: {:?}", values);
+        info!("🧾 [User] UPDATE query: {sql}");
         info!("📝 [User] Updating fields: {}", updated_fields.join(", "));
 
         let user = sqlx::query_as_with(&sql, values)
@@ -339,6 +601,13 @@ impl UserRepositoryTrait for UserRepository {
                     error!("❌ [User] Update failed: User with ID {id} not found");
                     AppError::NotFound(format!("User with ID {id} not found"))
                 }
+                sqlx::Error::Database(ref db_err)
+                    if db_err.is_unique_violation()
+                        && db_err.constraint() == Some("users_email_key") =>
+                {
+                    error!("🛑 [User] Email already registered, cannot update user ID {id}");
+                    AppError::EmailAlreadyExists
+                }
                 _ => {
                     error!("❌ [User] Database error while updating user ID {id}: {e}");
                     AppError::SqlxError(e)
@@ -352,18 +621,205 @@ impl UserRepositoryTrait for UserRepository {
         Ok(user)
     }
 
+    async fn update_role(&self, id: i32, role: Role) -> Result<User, AppError> {
+        info!("🛡️ [User] Setting role of user ID {id} to {role}");
+
+        let (sql, values) = Query::update()
+            .table(Users::Table)
+            .values([
+                (Users::Role, role.into()),
+                (Users::UpdatedAt, chrono::Utc::now().naive_utc().into()),
+            ])
+            .and_where(Expr::col(Users::UserId).eq(id))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [User] UPDATE query: {sql} | Values: {:?}", values);
+
+        let user = sqlx::query_as_with::<_, User, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [User] Failed to update role for user ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| {
+                error!("❌ [User] Role update failed: User with ID {id} not found");
+                AppError::NotFound(format!("User with ID {id} not found"))
+            })?;
+
+        info!("✅ [User] User ID {id} is now {}", user.role);
+        Ok(user)
+    }
+
+    async fn update_noc_transfer(&self, id: i32, noc_transfer: &str) -> Result<User, AppError> {
+        info!("💳 [User] Rotating noc_transfer for user ID {id}");
+
+        let (sql, values) = Query::update()
+            .table(Users::Table)
+            .values([
+                (Users::NocTransfer, noc_transfer.into()),
+                (Users::UpdatedAt, chrono::Utc::now().naive_utc().into()),
+            ])
+            .and_where(Expr::col(Users::UserId).eq(id))
+            .and_where(Expr::col(Users::DeletedAt).is_null())
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let user = sqlx::query_as_with::<_, User, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(|e| {
+                let collided = e.as_database_error().is_some_and(|db_err| {
+                    db_err.is_unique_violation() && db_err.constraint() == Some(NOC_TRANSFER_KEY)
+                });
+
+                if collided {
+                    error!("🛑 [User] noc_transfer collision for user ID {id}");
+                    return AppError::NocTransferTaken;
+                }
+
+                error!("❌ [User] Failed to rotate noc_transfer for user ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| {
+                error!("❌ [User] noc_transfer rotation failed: User with ID {id} not found");
+                AppError::NotFound(format!("User with ID {id} not found"))
+            })?;
+
+        info!("✅ [User] noc_transfer rotated for user ID {id}");
+        Ok(user)
+    }
+
+    async fn update_password(&self, id: i32, password_hash: &str) -> Result<User, AppError> {
+        let mut conn = self.db_pool.acquire().await.map_err(|e| {
+            error!("❌ [User] Failed to acquire connection: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        self.update_password_tx(&mut conn, id, password_hash).await
+    }
+
+    async fn update_password_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+        password_hash: &str,
+    ) -> Result<User, AppError> {
+        info!("🔐 [User] Updating password for user ID {id}");
+
+        let (sql, values) = Query::update()
+            .table(Users::Table)
+            .values([
+                (Users::Password, password_hash.into()),
+                (Users::UpdatedAt, chrono::Utc::now().naive_utc().into()),
+            ])
+            .and_where(Expr::col(Users::UserId).eq(id))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let user = sqlx::query_as_with::<_, User, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [User] Failed to update password for user ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| {
+                error!("❌ [User] Password update failed: User with ID {id} not found");
+                AppError::NotFound(format!("User with ID {id} not found"))
+            })?;
+
+        info!("✅ [User] Password updated for user ID {id}");
+        Ok(user)
+    }
+
+    async fn mark_verified(&self, id: i32) -> Result<User, AppError> {
+        let mut conn = self.db_pool.acquire().await.map_err(|e| {
+            error!("❌ [User] Failed to acquire connection: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        self.mark_verified_tx(&mut conn, id).await
+    }
+
+    async fn mark_verified_tx(&self, conn: &mut PgConnection, id: i32) -> Result<User, AppError> {
+        info!("📨 [User] Marking user ID {id} as verified");
+
+        let (sql, values) = Query::update()
+            .table(Users::Table)
+            .values([
+                (Users::IsVerified, true.into()),
+                (Users::UpdatedAt, chrono::Utc::now().naive_utc().into()),
+            ])
+            .and_where(Expr::col(Users::UserId).eq(id))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let user = sqlx::query_as_with::<_, User, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [User] Failed to mark user ID {id} as verified: {e}");
+                AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| {
+                error!("❌ [User] Verification failed: User with ID {id} not found");
+                AppError::NotFound(format!("User with ID {id} not found"))
+            })?;
+
+        info!("✅ [User] User ID {id} verified");
+        Ok(user)
+    }
+
+    // Deliberately leaves `updated_at` alone; a login is not a profile edit.
+    async fn record_login(&self, id: i32) -> Result<(), AppError> {
+        let (sql, values) = Query::update()
+            .table(Users::Table)
+            .value(Users::LastLoginAt, chrono::Utc::now().naive_utc())
+            .and_where(Expr::col(Users::UserId).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [User] Failed to record login for user ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!("🔐 [User] Recorded login for user ID {id}");
+        Ok(())
+    }
+
     async fn delete_user(&self, id: i32) -> Result<(), AppError> {
+        let mut conn = self.db_pool.acquire().await.map_err(|e| {
+            error!("❌ [User] Failed to acquire connection: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        self.delete_user_tx(&mut conn, id).await
+    }
+
+    async fn delete_user_tx(&self, conn: &mut PgConnection, id: i32) -> Result<(), AppError> {
         info!("🗑️ [User] Deleting user with ID: {id}");
 
-        let (sql, values) = Query::delete()
-            .from_table(Users::Table)
+        let now = chrono::Utc::now().naive_utc();
+
+        let (sql, values) = Query::update()
+            .table(Users::Table)
+            .values([
+                (Users::DeletedAt, now.into()),
+                (Users::UpdatedAt, now.into()),
+            ])
             .and_where(Expr::col(Users::UserId).eq(id))
+            .and_where(Expr::col(Users::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
-        info!("🧾 [User] DELETE query: {sql} | Values: {:?}", values);
+        info!("🧾 [User] Soft DELETE query: {sql} | Values: {:?}", values);
 
         let result = sqlx::query_with(&sql, values)
-            .execute(&self.db_pool)
+            .execute(&mut *conn)
             .await
             .map_err(|e| {
                 error!("❌ [User] Failed to delete user ID {id}: {e}");
@@ -378,4 +834,34 @@ impl UserRepositoryTrait for UserRepository {
         info!("✅ [User] Successfully deleted user ID: {id}");
         Ok(())
     }
+
+    async fn restore(&self, id: i32) -> Result<User, AppError> {
+        info!("♻️ [User] Restoring user with ID: {id}");
+
+        let (sql, values) = Query::update()
+            .table(Users::Table)
+            .value(Users::DeletedAt, Keyword::Null)
+            .value(Users::UpdatedAt, chrono::Utc::now().naive_utc())
+            .and_where(Expr::col(Users::UserId).eq(id))
+            .and_where(Expr::col(Users::DeletedAt).is_not_null())
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [User] Restore query: {sql} | Values: {:?}", values);
+
+        let restored = sqlx::query_as_with::<_, User, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [User] Failed to restore user ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| {
+                error!("🟡 [User] Restore failed: No deleted user found with ID {id}");
+                AppError::NotFound(format!("Deleted user with ID {id} not found"))
+            })?;
+
+        info!("✅ [User] Successfully restored user ID: {id}");
+        Ok(restored)
+    }
 }
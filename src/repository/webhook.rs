@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use sea_query::{Expr, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use tracing::{error, info};
+
+use crate::abstract_trait::WebhookRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::model::webhook::Webhook;
+use crate::schema::webhook::Webhooks;
+use crate::utils::AppError;
+
+pub struct WebhookRepository {
+    db_pool: ConnectionPool,
+}
+
+impl WebhookRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl WebhookRepositoryTrait for WebhookRepository {
+    async fn find_by_user_id(&self, user_id: i32) -> Result<Vec<Webhook>, AppError> {
+        info!("🪝 [Webhook] Finding webhooks for user_id={user_id}");
+
+        let (sql, values) = Query::select()
+            .from(Webhooks::Table)
+            .columns([
+                Webhooks::WebhookId,
+                Webhooks::UserId,
+                Webhooks::Url,
+                Webhooks::Secret,
+                Webhooks::CreatedAt,
+                Webhooks::UpdatedAt,
+            ])
+            .and_where(Expr::col(Webhooks::UserId).eq(user_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let rows = sqlx::query_as_with::<_, Webhook, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Webhook] Failed to fetch webhooks for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [Webhook] Found {} webhook(s) for user_id={user_id}",
+            rows.len()
+        );
+
+        Ok(rows)
+    }
+
+    async fn create(&self, user_id: i32, url: &str, secret: &str) -> Result<Webhook, AppError> {
+        info!("🪝 [Webhook] Registering webhook for user_id={user_id}: {url}");
+
+        let (sql, values) = Query::insert()
+            .into_table(Webhooks::Table)
+            .columns([Webhooks::UserId, Webhooks::Url, Webhooks::Secret])
+            .values_panic([user_id.into(), url.into(), secret.into()])
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let webhook = sqlx::query_as_with::<_, Webhook, _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Webhook] Failed to register webhook for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [Webhook] Webhook {} registered for user_id={user_id}",
+            webhook.webhook_id
+        );
+
+        Ok(webhook)
+    }
+}
@@ -1,3 +1,8 @@
+use crate::domain::request::{
+    Search, TransactionFilter,
+    sort::{Sort, invalid_sort_by},
+};
+use crate::domain::{currency::Currency, money::Money, withdraw_status::WithdrawStatus};
 use crate::model::withdraw::Withdraw;
 use crate::schema::withdraw::Withdraws as WithdrawSchema;
 use crate::utils::AppError;
@@ -8,10 +13,23 @@ use crate::{
 };
 use anyhow::Result;
 use async_trait::async_trait;
-use sea_query::{Expr, Func, Order, PostgresQueryBuilder, Query};
+use chrono::{Duration, NaiveDate, NaiveTime};
+use sea_query::{Alias, Expr, Func, Keyword, LockType, PostgresQueryBuilder, Query, SimpleExpr};
 use sea_query_binder::SqlxBinder;
+use sqlx::PgConnection;
 use tracing::{error, info};
 
+const SORTABLE_COLUMNS: &[&str] = &[
+    "id",
+    "withdraw_id",
+    "user_id",
+    "withdraw_amount",
+    "amount",
+    "withdraw_time",
+    "created_at",
+    "updated_at",
+];
+
 pub struct WithdrawRepository {
     db_pool: ConnectionPool,
 }
@@ -20,6 +38,28 @@ impl WithdrawRepository {
     pub fn new(db_pool: ConnectionPool) -> Self {
         Self { db_pool }
     }
+
+    fn search_condition(search: &Search, search_notes: bool) -> SimpleExpr {
+        let condition = search.matches_id(WithdrawSchema::WithdrawId);
+
+        if search_notes {
+            condition.or(search.matches_ignore_case(Expr::col(WithdrawSchema::Note)))
+        } else {
+            condition
+        }
+    }
+
+    fn sort_column(sort_by: Option<&str>) -> Result<WithdrawSchema, AppError> {
+        match sort_by.filter(|s| !s.is_empty()).unwrap_or("created_at") {
+            "id" | "withdraw_id" => Ok(WithdrawSchema::WithdrawId),
+            "user_id" => Ok(WithdrawSchema::UserId),
+            "withdraw_amount" | "amount" => Ok(WithdrawSchema::WithdrawAmount),
+            "withdraw_time" => Ok(WithdrawSchema::WithdrawTime),
+            "created_at" => Ok(WithdrawSchema::CreatedAt),
+            "updated_at" => Ok(WithdrawSchema::UpdatedAt),
+            other => Err(invalid_sort_by(other, SORTABLE_COLUMNS)),
+        }
+    }
 }
 
 #[async_trait]
@@ -28,13 +68,17 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
         &self,
         page: i32,
         page_size: i32,
-        search: Option<String>,
+        search: Option<Search>,
+        sort: Sort,
+        filter: TransactionFilter,
     ) -> Result<(Vec<Withdraw>, i64), AppError> {
         info!(
             "📄 [Withdraw] Fetching all records - page: {page}, page_size: {page_size}, search: {:?}",
             search
         );
 
+        let sort_column = Self::sort_column(sort.by.as_deref())?;
+
         let page = if page > 0 { page } else { 1 };
         let page_size = if page_size > 0 { page_size } else { 10 };
         let offset = (page - 1) * page_size;
@@ -50,16 +94,33 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
                 WithdrawSchema::WithdrawTime,
                 WithdrawSchema::CreatedAt,
                 WithdrawSchema::UpdatedAt,
+                WithdrawSchema::DeletedAt,
+                WithdrawSchema::Currency,
+                WithdrawSchema::Status,
+                WithdrawSchema::Note,
             ])
             .from(WithdrawSchema::Table)
-            .order_by(WithdrawSchema::WithdrawId, Order::Asc)
+            .order_by(sort_column, sort.order.into())
+            .order_by(WithdrawSchema::WithdrawId, sort.order.into())
             .limit(page_size as u64)
             .offset(offset as u64);
 
-        if let Some(ref term) = search {
-            let search_id: i32 = term.parse().unwrap_or(0);
-            select_query.and_where(Expr::col(WithdrawSchema::WithdrawId).eq(search_id));
-            info!("🔍 [Withdraw] Filtering by withdraw_id = {search_id}");
+        if !filter.include_deleted {
+            select_query.and_where(Expr::col(WithdrawSchema::DeletedAt).is_null());
+        }
+
+        filter.apply(
+            &mut select_query,
+            WithdrawSchema::WithdrawAmount,
+            WithdrawSchema::WithdrawTime,
+        );
+
+        if let Some(ref search) = search {
+            select_query.and_where(Self::search_condition(search, filter.search_notes));
+            info!(
+                "🔍 [Withdraw] Filtering by withdraw_id ({:?}): {}",
+                search.mode, search.term
+            );
         }
 
         let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
@@ -85,9 +146,18 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
             .expr(Func::count(Expr::col(WithdrawSchema::WithdrawId)))
             .from(WithdrawSchema::Table);
 
-        if let Some(ref term) = search {
-            let search_id: i32 = term.parse().unwrap_or(0);
-            count_query.and_where(Expr::col(WithdrawSchema::WithdrawId).eq(search_id));
+        if !filter.include_deleted {
+            count_query.and_where(Expr::col(WithdrawSchema::DeletedAt).is_null());
+        }
+
+        filter.apply(
+            &mut count_query,
+            WithdrawSchema::WithdrawAmount,
+            WithdrawSchema::WithdrawTime,
+        );
+
+        if let Some(ref search) = search {
+            count_query.and_where(Self::search_condition(search, filter.search_notes));
         }
 
         let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
@@ -131,8 +201,13 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
                 WithdrawSchema::WithdrawTime,
                 WithdrawSchema::CreatedAt,
                 WithdrawSchema::UpdatedAt,
+                WithdrawSchema::DeletedAt,
+                WithdrawSchema::Currency,
+                WithdrawSchema::Status,
+                WithdrawSchema::Note,
             ])
             .and_where(Expr::col(WithdrawSchema::WithdrawId).eq(id))
+            .and_where(Expr::col(WithdrawSchema::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
@@ -175,8 +250,13 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
                 WithdrawSchema::WithdrawTime,
                 WithdrawSchema::CreatedAt,
                 WithdrawSchema::UpdatedAt,
+                WithdrawSchema::DeletedAt,
+                WithdrawSchema::Currency,
+                WithdrawSchema::Status,
+                WithdrawSchema::Note,
             ])
             .and_where(Expr::col(WithdrawSchema::UserId).eq(id))
+            .and_where(Expr::col(WithdrawSchema::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
@@ -212,8 +292,13 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
                 WithdrawSchema::WithdrawTime,
                 WithdrawSchema::CreatedAt,
                 WithdrawSchema::UpdatedAt,
+                WithdrawSchema::DeletedAt,
+                WithdrawSchema::Currency,
+                WithdrawSchema::Status,
+                WithdrawSchema::Note,
             ])
             .and_where(Expr::col(WithdrawSchema::UserId).eq(id))
+            .and_where(Expr::col(WithdrawSchema::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
@@ -244,9 +329,96 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
         Ok(row)
     }
 
-    async fn create(&self, input: &CreateWithdrawRequest) -> Result<Withdraw, AppError> {
+    async fn sum_by_user(&self, user_id: i32) -> Result<(Money, i64), AppError> {
+        info!("📊 [Withdraw] Summing withdrawals for user_id={user_id}");
+
+        let (sql, values) = Query::select()
+            .expr(Func::cast_as(
+                Func::coalesce([
+                    Func::sum(Expr::col(WithdrawSchema::WithdrawAmount)).into(),
+                    Expr::val(0i64).into(),
+                ]),
+                Alias::new("BIGINT"),
+            ))
+            .expr(Func::count(Expr::col(WithdrawSchema::WithdrawId)))
+            .from(WithdrawSchema::Table)
+            .and_where(Expr::col(WithdrawSchema::UserId).eq(user_id))
+            .and_where(Expr::col(WithdrawSchema::DeletedAt).is_null())
+            .and_where(Expr::col(WithdrawSchema::Status).is_in(WithdrawStatus::SETTLED))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let totals = sqlx::query_as_with::<_, (Money, i64), _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Withdraw] Failed to sum withdrawals for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        Ok(totals)
+    }
+
+    async fn sum_for_day_tx(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+        currency: Currency,
+        day: NaiveDate,
+    ) -> Result<Money, AppError> {
+        info!("📊 [Withdraw] Summing {currency} withdrawals for user_id={user_id} on {day}");
+
+        let day_start = day.and_time(NaiveTime::MIN);
+        let day_end = day_start + Duration::days(1);
+
+        let (sql, values) = Query::select()
+            .expr(Func::cast_as(
+                Func::coalesce([
+                    Func::sum(Expr::col(WithdrawSchema::WithdrawAmount)).into(),
+                    Expr::val(0i64).into(),
+                ]),
+                Alias::new("BIGINT"),
+            ))
+            .from(WithdrawSchema::Table)
+            .and_where(Expr::col(WithdrawSchema::UserId).eq(user_id))
+            .and_where(Expr::col(WithdrawSchema::Currency).eq(currency))
+            .and_where(Expr::col(WithdrawSchema::DeletedAt).is_null())
+            .and_where(Expr::col(WithdrawSchema::Status).ne(WithdrawStatus::Rejected))
+            .and_where(Expr::col(WithdrawSchema::WithdrawTime).gte(day_start))
+            .and_where(Expr::col(WithdrawSchema::WithdrawTime).lt(day_end))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let total = sqlx::query_scalar_with::<_, Money, _>(&sql, values)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Withdraw] Failed to sum withdrawals for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        Ok(total)
+    }
+
+    async fn create(
+        &self,
+        input: &CreateWithdrawRequest,
+        status: WithdrawStatus,
+    ) -> Result<Withdraw, AppError> {
+        let mut conn = self.db_pool.acquire().await.map_err(|e| {
+            error!("❌ [Withdraw] Failed to acquire connection: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        self.create_tx(&mut conn, input, status).await
+    }
+
+    async fn create_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &CreateWithdrawRequest,
+        status: WithdrawStatus,
+    ) -> Result<Withdraw, AppError> {
         info!(
-            "💸 [Withdraw] Creating new withdrawal: user_id={}, amount={}, time={}",
+            "💸 [Withdraw] Creating new {status} withdrawal: user_id={}, amount={}, time={}",
             input.user_id, input.withdraw_amount, input.withdraw_time
         );
 
@@ -258,11 +430,17 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
                 WithdrawSchema::UserId,
                 WithdrawSchema::WithdrawAmount,
                 WithdrawSchema::WithdrawTime,
+                WithdrawSchema::Currency,
+                WithdrawSchema::Status,
+                WithdrawSchema::Note,
             ])
             .values([
                 input.user_id.into(),
                 input.withdraw_amount.into(),
                 withdraw_time_naive.into(),
+                input.currency.into(),
+                status.into(),
+                input.note.clone().into(),
             ])
             .unwrap()
             .returning_all()
@@ -274,7 +452,7 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
         );
 
         let row = sqlx::query_as_with::<_, Withdraw, _>(&sql, values)
-            .fetch_one(&self.db_pool)
+            .fetch_one(&mut *conn)
             .await
             .map_err(|e| {
                 error!("❌ [Withdraw] Failed to create withdrawal: {e}");
@@ -289,7 +467,11 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
         Ok(row)
     }
 
-    async fn update(&self, input: &UpdateWithdrawRequest) -> Result<Withdraw, AppError> {
+    async fn update_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &UpdateWithdrawRequest,
+    ) -> Result<Withdraw, AppError> {
         info!(
             "🔄 [Withdraw] Updating withdrawal: id={}, amount={}, time={}",
             input.withdraw_id, input.withdraw_amount, input.withdraw_time
@@ -302,8 +484,14 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
             .values([
                 (WithdrawSchema::WithdrawAmount, input.withdraw_amount.into()),
                 (WithdrawSchema::WithdrawTime, withdraw_time_naive.into()),
+                (
+                    WithdrawSchema::UpdatedAt,
+                    chrono::Utc::now().naive_utc().into(),
+                ),
             ])
             .and_where(Expr::col(WithdrawSchema::WithdrawId).eq(input.withdraw_id))
+            .and_where(Expr::col(WithdrawSchema::DeletedAt).is_null())
+            .returning_all()
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
@@ -312,7 +500,7 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
         );
 
         let row = sqlx::query_as_with::<_, Withdraw, _>(&sql, values)
-            .fetch_one(&self.db_pool)
+            .fetch_one(&mut *conn)
             .await
             .map_err(|e| match e {
                 sqlx::Error::RowNotFound => {
@@ -340,34 +528,231 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
     }
 
     async fn delete(&self, id: i32) -> Result<(), AppError> {
-        info!("🗑️ [Withdraw] Deleting withdrawal with ID: {}", id);
+        let mut conn = self.db_pool.acquire().await.map_err(|e| {
+            error!("❌ [Withdraw] Failed to acquire connection: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        self.delete_tx(&mut conn, id).await.map(|_| ())
+    }
+
+    async fn delete_tx(&self, conn: &mut PgConnection, id: i32) -> Result<Withdraw, AppError> {
+        info!("🗑️ [Withdraw] Deleting withdraw with ID: {id}");
 
-        let (sql, values) = Query::delete()
-            .from_table(WithdrawSchema::Table)
+        let now = chrono::Utc::now().naive_utc();
+
+        let (sql, values) = Query::update()
+            .table(WithdrawSchema::Table)
+            .values([
+                (WithdrawSchema::DeletedAt, now.into()),
+                (WithdrawSchema::UpdatedAt, now.into()),
+            ])
             .and_where(Expr::col(WithdrawSchema::WithdrawId).eq(id))
+            .and_where(Expr::col(WithdrawSchema::DeletedAt).is_null())
+            .returning_all()
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
-            "🧾 [Withdraw] Executing DELETE: {} | Values: {:?}",
-            sql, values
+            "🧾 [Withdraw] Executing soft DELETE: {sql} | Values: {:?}",
+            values
         );
 
-        let result = sqlx::query_with(&sql, values)
-            .execute(&self.db_pool)
+        let deleted = sqlx::query_as_with::<_, Withdraw, _>(&sql, values)
+            .fetch_optional(&mut *conn)
             .await
             .map_err(|e| {
                 error!("❌ [Withdraw] Failed to delete withdraw ID {id}: {e}");
                 AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| {
+                error!("🟡 [Withdraw] Deletion failed: No withdraw found with ID {id}");
+                AppError::NotFound(format!("Withdraw with ID {id} not found"))
             })?;
 
-        if result.rows_affected() == 0 {
-            error!("🟡 [Withdraw] No data deleted: Withdraw with ID {id} not found",);
-            return Err(AppError::NotFound(format!(
-                "Withdraw with ID {id} not found",
-            )));
-        }
+        info!("✅ [Withdraw] Successfully deleted withdraw ID: {id}");
+        Ok(deleted)
+    }
+
+    async fn delete_by_user_tx(
+        &self,
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<u64, AppError> {
+        info!("🗑️ [Withdraw] Deleting withdraws for user_id: {user_id}");
+
+        let now = chrono::Utc::now().naive_utc();
+
+        let (sql, values) = Query::update()
+            .table(WithdrawSchema::Table)
+            .values([
+                (WithdrawSchema::DeletedAt, now.into()),
+                (WithdrawSchema::UpdatedAt, now.into()),
+            ])
+            .and_where(Expr::col(WithdrawSchema::UserId).eq(user_id))
+            .and_where(Expr::col(WithdrawSchema::DeletedAt).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 [Withdraw] Soft DELETE query: {sql} | Values: {:?}",
+            values
+        );
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Withdraw] Failed to delete withdraws for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [Withdraw] Deleted {} withdraws for user_id={user_id}",
+            result.rows_affected()
+        );
+        Ok(result.rows_affected())
+    }
+
+    async fn restore(&self, id: i32) -> Result<Withdraw, AppError> {
+        let mut conn = self.db_pool.acquire().await.map_err(|e| {
+            error!("❌ [Withdraw] Failed to acquire connection: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        self.restore_tx(&mut conn, id).await
+    }
+
+    async fn restore_tx(&self, conn: &mut PgConnection, id: i32) -> Result<Withdraw, AppError> {
+        info!("♻️ [Withdraw] Restoring withdraw with ID: {id}");
+
+        let (sql, values) = Query::update()
+            .table(WithdrawSchema::Table)
+            .value(WithdrawSchema::DeletedAt, Keyword::Null)
+            .value(WithdrawSchema::UpdatedAt, chrono::Utc::now().naive_utc())
+            .and_where(Expr::col(WithdrawSchema::WithdrawId).eq(id))
+            .and_where(Expr::col(WithdrawSchema::DeletedAt).is_not_null())
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [Withdraw] Restore query: {sql} | Values: {:?}", values);
+
+        let restored = sqlx::query_as_with::<_, Withdraw, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Withdraw] Failed to restore withdraw ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| {
+                error!("🟡 [Withdraw] Restore failed: No deleted withdraw found with ID {id}");
+                AppError::NotFound(format!("Deleted withdraw with ID {id} not found"))
+            })?;
+
+        info!("✅ [Withdraw] Successfully restored withdraw ID: {id}");
+        Ok(restored)
+    }
+
+    async fn find_by_id_for_update_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+    ) -> Result<Option<Withdraw>, AppError> {
+        info!("🔒 [Withdraw] Locking withdraw ID: {id}");
+
+        let (sql, values) = Query::select()
+            .from(WithdrawSchema::Table)
+            .columns([
+                WithdrawSchema::WithdrawId,
+                WithdrawSchema::UserId,
+                WithdrawSchema::WithdrawAmount,
+                WithdrawSchema::WithdrawTime,
+                WithdrawSchema::CreatedAt,
+                WithdrawSchema::UpdatedAt,
+                WithdrawSchema::DeletedAt,
+                WithdrawSchema::Currency,
+                WithdrawSchema::Status,
+                WithdrawSchema::Note,
+            ])
+            .and_where(Expr::col(WithdrawSchema::WithdrawId).eq(id))
+            .and_where(Expr::col(WithdrawSchema::DeletedAt).is_null())
+            .lock(LockType::Update)
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_as_with::<_, Withdraw, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Withdraw] Failed to lock withdraw ID {id}: {e}");
+                AppError::SqlxError(e)
+            })
+    }
+
+    async fn find_pending_for_update_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+    ) -> Result<Option<Withdraw>, AppError> {
+        info!("🔒 [Withdraw] Locking pending withdraw ID: {id}");
+
+        let (sql, values) = Query::select()
+            .from(WithdrawSchema::Table)
+            .columns([
+                WithdrawSchema::WithdrawId,
+                WithdrawSchema::UserId,
+                WithdrawSchema::WithdrawAmount,
+                WithdrawSchema::WithdrawTime,
+                WithdrawSchema::CreatedAt,
+                WithdrawSchema::UpdatedAt,
+                WithdrawSchema::DeletedAt,
+                WithdrawSchema::Currency,
+                WithdrawSchema::Status,
+                WithdrawSchema::Note,
+            ])
+            .and_where(Expr::col(WithdrawSchema::WithdrawId).eq(id))
+            .and_where(Expr::col(WithdrawSchema::Status).eq(WithdrawStatus::Pending))
+            .and_where(Expr::col(WithdrawSchema::DeletedAt).is_null())
+            .lock(LockType::Update)
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_as_with::<_, Withdraw, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Withdraw] Failed to lock pending withdraw ID {id}: {e}");
+                AppError::SqlxError(e)
+            })
+    }
+
+    async fn set_status_tx(
+        &self,
+        conn: &mut PgConnection,
+        id: i32,
+        status: WithdrawStatus,
+    ) -> Result<Withdraw, AppError> {
+        info!("🔄 [Withdraw] Marking withdraw ID {id} as {status}");
+
+        let (sql, values) = Query::update()
+            .table(WithdrawSchema::Table)
+            .values([
+                (WithdrawSchema::Status, status.into()),
+                (
+                    WithdrawSchema::UpdatedAt,
+                    chrono::Utc::now().naive_utc().into(),
+                ),
+            ])
+            .and_where(Expr::col(WithdrawSchema::WithdrawId).eq(id))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let updated = sqlx::query_as_with::<_, Withdraw, _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Withdraw] Failed to mark withdraw ID {id} as {status}: {e}");
+                AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| AppError::NotFound(format!("Withdraw with ID {id} not found")))?;
 
-        info!("✅ [Withdraw] Successfully deleted: ID={id}");
-        Ok(())
+        info!("✅ [Withdraw] Withdraw ID {id} is now {status}");
+        Ok(updated)
     }
 }
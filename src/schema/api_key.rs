@@ -0,0 +1,13 @@
+use sea_query::Iden;
+
+#[derive(Debug, Iden)]
+pub enum ApiKeys {
+    Table,
+    ApiKeyId,
+    UserId,
+    ApiKey,
+    Secret,
+    Name,
+    CreatedAt,
+    RevokedAt,
+}
@@ -0,0 +1,12 @@
+use sea_query::Iden;
+
+#[derive(Debug, Iden)]
+pub enum EmailVerifications {
+    Table,
+    EmailVerificationId,
+    UserId,
+    TokenHash,
+    ExpiresAt,
+    UsedAt,
+    CreatedAt,
+}
@@ -0,0 +1,12 @@
+use sea_query::Iden;
+
+#[derive(Debug, Iden)]
+pub enum Fees {
+    Table,
+    FeeId,
+    TransferId,
+    UserId,
+    Amount,
+    Currency,
+    CreatedAt,
+}
@@ -0,0 +1,13 @@
+use sea_query::Iden;
+
+#[derive(Debug, Iden)]
+pub enum LoginAudit {
+    Table,
+    LoginAuditId,
+    UserId,
+    Email,
+    Ip,
+    UserAgent,
+    Success,
+    CreatedAt,
+}
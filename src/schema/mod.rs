@@ -1,5 +1,12 @@
+pub mod api_key;
+pub mod email_verification;
+pub mod fee;
+pub mod login_audit;
+pub mod password_reset;
 pub mod saldo;
+pub mod saldo_history;
 pub mod topup;
 pub mod transfer;
 pub mod user;
+pub mod webhook;
 pub mod withdraw;
@@ -10,4 +10,9 @@ pub enum Saldo {
     WithdrawTime,
     CreatedAt,
     UpdatedAt,
+    DeletedAt,
+    Currency,
+    OpeningBalance,
+    IsFrozen,
+    FrozenReason,
 }
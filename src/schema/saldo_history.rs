@@ -0,0 +1,14 @@
+use sea_query::Iden;
+
+#[derive(Debug, Iden)]
+pub enum SaldoHistory {
+    Table,
+    SaldoHistoryId,
+    SaldoId,
+    UserId,
+    OldBalance,
+    NewBalance,
+    Reason,
+    ReferenceId,
+    CreatedAt,
+}
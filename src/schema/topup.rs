@@ -11,4 +11,6 @@ pub enum Topups {
     TopupTime,
     CreatedAt,
     UpdatedAt,
+    DeletedAt,
+    Currency,
 }
@@ -10,4 +10,11 @@ pub enum Transfers {
     TransferTime,
     CreatedAt,
     UpdatedAt,
+    DeletedAt,
+    Currency,
+    Status,
+    ScheduledAt,
+    Note,
+    ReversedTransferId,
+    Fee,
 }
@@ -9,6 +9,10 @@ pub enum Users {
     Email,
     Password,
     NocTransfer,
+    Role,
+    IsVerified,
+    LastLoginAt,
     CreatedAt,
     UpdatedAt,
+    DeletedAt,
 }
@@ -0,0 +1,12 @@
+use sea_query::Iden;
+
+#[derive(Debug, Iden)]
+pub enum Webhooks {
+    Table,
+    WebhookId,
+    UserId,
+    Url,
+    Secret,
+    CreatedAt,
+    UpdatedAt,
+}
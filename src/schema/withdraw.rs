@@ -9,4 +9,8 @@ pub enum Withdraws {
     WithdrawTime,
     CreatedAt,
     UpdatedAt,
+    DeletedAt,
+    Currency,
+    Status,
+    Note,
 }
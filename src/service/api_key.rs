@@ -0,0 +1,269 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use dashmap::{DashMap, mapref::entry::Entry};
+use std::time::Duration;
+use tracing::warn;
+
+use crate::{
+    abstract_trait::{ApiKeyServiceTrait, DynApiKeyRepository, DynUserRepository, SignedRequest},
+    domain::role::Role,
+    utils::{AppError, verify_request_signature},
+};
+
+const MAX_NONCE_LEN: usize = 64;
+
+pub struct ApiKeyService {
+    api_key_repository: DynApiKeyRepository,
+    user_repository: DynUserRepository,
+    max_clock_skew: Duration,
+    // `{api_key}:{nonce}` -> unix time after which the request's timestamp
+    // is stale anyway, so the entry can go. In-memory, so replays are only
+    // caught by the instance that saw the original request.
+    seen_nonces: DashMap<String, i64>,
+}
+
+impl ApiKeyService {
+    pub fn new(
+        api_key_repository: DynApiKeyRepository,
+        user_repository: DynUserRepository,
+        max_clock_skew: Duration,
+    ) -> Self {
+        Self {
+            api_key_repository,
+            user_repository,
+            max_clock_skew,
+            seen_nonces: DashMap::new(),
+        }
+    }
+
+    // Records the nonce, failing if the key already used it while its
+    // request was still fresh.
+    fn remember_nonce(&self, request: &SignedRequest<'_>, timestamp: i64) -> Result<(), AppError> {
+        let now = Utc::now().timestamp();
+        self.seen_nonces.retain(|_, expires_at| *expires_at >= now);
+
+        match self
+            .seen_nonces
+            .entry(format!("{}:{}", request.api_key, request.nonce))
+        {
+            Entry::Occupied(_) => {
+                warn!(
+                    "🚫 [ApiKey] Replayed nonce {} for {}",
+                    request.nonce, request.api_key
+                );
+                Err(AppError::StaleRequest(
+                    "X-Nonce has already been used".to_string(),
+                ))
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(timestamp.saturating_add(self.max_clock_skew.as_secs() as i64));
+                Ok(())
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ApiKeyServiceTrait for ApiKeyService {
+    async fn authenticate(&self, request: &SignedRequest<'_>) -> Result<(i32, Role), AppError> {
+        // Checked before the signature so stale requests are turned away
+        // without a database round-trip.
+        let timestamp = request
+            .timestamp
+            .parse::<i64>()
+            .map_err(|_| AppError::StaleRequest("X-Timestamp must be a unix timestamp".into()))?;
+
+        let skew = Utc::now().timestamp().abs_diff(timestamp);
+
+        if skew > self.max_clock_skew.as_secs() {
+            warn!(
+                "🚫 [ApiKey] Rejected request for {}: timestamp is {skew}s off",
+                request.api_key
+            );
+            return Err(AppError::StaleRequest(format!(
+                "X-Timestamp is {skew} seconds off the server clock"
+            )));
+        }
+
+        if request.nonce.is_empty() || request.nonce.len() > MAX_NONCE_LEN {
+            return Err(AppError::StaleRequest(format!(
+                "X-Nonce must be 1 to {MAX_NONCE_LEN} characters"
+            )));
+        }
+
+        let key = self
+            .api_key_repository
+            .find_active(request.api_key)
+            .await?
+            .ok_or(AppError::InvalidApiKey)?;
+
+        if !verify_request_signature(&key.secret, request) {
+            warn!("🚫 [ApiKey] Signature mismatch for {}", request.api_key);
+            return Err(AppError::InvalidSignature);
+        }
+
+        // Only after the signature checks out, so forged requests can't
+        // burn nonces the real caller is about to use.
+        self.remember_nonce(request, timestamp)?;
+
+        let user = self
+            .user_repository
+            .find_by_id(key.user_id)
+            .await?
+            .ok_or(AppError::InvalidApiKey)?;
+
+        Ok((user.user_id, user.role))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        model::api_key::ApiKey,
+        test_support::{MockApiKeyRepositoryTrait, MockUserRepositoryTrait, user},
+        utils::sign_request,
+    };
+
+    const API_KEY: &str = "pk_test";
+    const SECRET: &str = "partner-secret";
+    const BODY: &[u8] = br#"{"transfer_amount":50000}"#;
+
+    fn service() -> ApiKeyService {
+        let mut api_keys = MockApiKeyRepositoryTrait::new();
+        api_keys.expect_find_active().returning(|api_key| {
+            Ok(Some(ApiKey {
+                api_key_id: 1,
+                user_id: 7,
+                api_key: api_key.to_string(),
+                secret: SECRET.to_string(),
+                name: "partner".to_string(),
+                created_at: None,
+                revoked_at: None,
+            }))
+        });
+
+        let mut users = MockUserRepositoryTrait::new();
+        users.expect_find_by_id().returning(|id| Ok(Some(user(id))));
+
+        ApiKeyService::new(
+            Arc::new(api_keys),
+            Arc::new(users),
+            Duration::from_secs(300),
+        )
+    }
+
+    struct Parts {
+        timestamp: String,
+        nonce: &'static str,
+        signature: String,
+    }
+
+    fn sign(timestamp: i64, nonce: &'static str) -> Parts {
+        let timestamp = timestamp.to_string();
+        let signature = sign_request(SECRET, &timestamp, nonce, "POST", "/api/transfers", BODY);
+
+        Parts {
+            timestamp,
+            nonce,
+            signature,
+        }
+    }
+
+    fn request<'a>(parts: &'a Parts, body: &'a [u8]) -> SignedRequest<'a> {
+        SignedRequest {
+            api_key: API_KEY,
+            timestamp: &parts.timestamp,
+            nonce: parts.nonce,
+            signature: &parts.signature,
+            method: "POST",
+            path: "/api/transfers",
+            body,
+        }
+    }
+
+    #[tokio::test]
+    async fn valid_request_authenticates_as_the_keys_user() {
+        let parts = sign(Utc::now().timestamp(), "nonce-1");
+
+        let (user_id, role) = service()
+            .authenticate(&request(&parts, BODY))
+            .await
+            .unwrap();
+
+        assert_eq!(user_id, 7);
+        assert_eq!(role, Role::User);
+    }
+
+    #[tokio::test]
+    async fn tampered_body_is_rejected() {
+        let parts = sign(Utc::now().timestamp(), "nonce-1");
+
+        let err = service()
+            .authenticate(&request(&parts, br#"{"transfer_amount":90000}"#))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, AppError::InvalidSignature), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn stale_timestamp_is_rejected() {
+        let parts = sign(Utc::now().timestamp() - 301, "nonce-1");
+
+        let err = service()
+            .authenticate(&request(&parts, BODY))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, AppError::StaleRequest(_)), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn replayed_nonce_is_rejected() {
+        let service = service();
+        let now = Utc::now().timestamp();
+
+        let first = sign(now, "nonce-1");
+        assert!(service.authenticate(&request(&first, BODY)).await.is_ok());
+
+        let err = service
+            .authenticate(&request(&first, BODY))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::StaleRequest(_)), "{err:?}");
+
+        // A fresh nonce goes through.
+        let second = sign(now, "nonce-2");
+        assert!(service.authenticate(&request(&second, BODY)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn forged_request_does_not_burn_the_nonce() {
+        let service = service();
+        let now = Utc::now().timestamp();
+
+        let forged = Parts {
+            signature: "sha256=00".to_string(),
+            ..sign(now, "nonce-1")
+        };
+        assert!(service.authenticate(&request(&forged, BODY)).await.is_err());
+
+        let parts = sign(now, "nonce-1");
+        assert!(service.authenticate(&request(&parts, BODY)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn missing_nonce_is_rejected() {
+        let parts = sign(Utc::now().timestamp(), "");
+
+        let err = service()
+            .authenticate(&request(&parts, BODY))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, AppError::StaleRequest(_)), "{err:?}");
+    }
+}
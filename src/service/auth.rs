@@ -1,31 +1,259 @@
 use crate::{
-    abstract_trait::{AuthServiceTrait, DynHashing, DynJwtService, DynUserRepository},
+    abstract_trait::{
+        AuthServiceTrait, DynEmailVerificationRepository, DynHashing, DynJwtService,
+        DynLoginAuditRepository, DynMailer, DynPasswordResetRepository, DynSaldoRepository,
+        DynTopupRepository, DynTransferRepository, DynUserRepository, DynWithdrawRepository,
+    },
+    config::{Claims, ConnectionPool},
     domain::{
-        request::{CreateUserRequest, LoginRequest, RegisterRequest},
-        response::{ApiResponse, ErrorResponse, user::UserResponse},
+        currency::Currency,
+        money::Money,
+        request::{
+            CreateUserRequest, DeleteAccountRequest, ForgotPasswordRequest, LoginContext,
+            LoginRequest, RegisterRequest, ResendVerificationRequest, ResetPasswordRequest,
+            VerifyEmailQuery,
+        },
+        response::{
+            ApiResponse, ErrorResponse,
+            auth::{CapabilitiesResponse, LoginAuditResponse, TokenPair},
+            user::UserResponse,
+        },
+        role::Role,
     },
-    utils::{AppError, random_vcc},
+    model::user::User,
+    utils::{AppError, generate_reset_token, hash_reset_token, with_unique_noc},
 };
 use async_trait::async_trait;
+use chrono::{Duration, Utc};
 use tracing::{error, info};
+use uuid::Uuid;
+
+const PASSWORD_RESET_TTL_MINUTES: i64 = 30;
+const EMAIL_VERIFICATION_TTL_HOURS: i64 = 24;
+const RECENT_LOGINS_LIMIT: u64 = 20;
+
+pub struct AuthRepositories {
+    pub users: DynUserRepository,
+    pub password_resets: DynPasswordResetRepository,
+    pub email_verifications: DynEmailVerificationRepository,
+    pub login_audits: DynLoginAuditRepository,
+    pub saldos: DynSaldoRepository,
+    pub topups: DynTopupRepository,
+    pub transfers: DynTransferRepository,
+    pub withdraws: DynWithdrawRepository,
+}
 
 pub struct AuthService {
+    db_pool: ConnectionPool,
     repository: DynUserRepository,
+    password_reset_repository: DynPasswordResetRepository,
+    email_verification_repository: DynEmailVerificationRepository,
+    login_audit_repository: DynLoginAuditRepository,
+    saldo_repository: DynSaldoRepository,
+    topup_repository: DynTopupRepository,
+    transfer_repository: DynTransferRepository,
+    withdraw_repository: DynWithdrawRepository,
     hashing: DynHashing,
     jwt_config: DynJwtService,
+    mailer: DynMailer,
+    require_email_verification: bool,
 }
 
 impl AuthService {
     pub fn new(
-        repository: DynUserRepository,
+        db_pool: ConnectionPool,
+        repositories: AuthRepositories,
         hashing: DynHashing,
         jwt_config: DynJwtService,
+        mailer: DynMailer,
+        require_email_verification: bool,
     ) -> Self {
         Self {
-            repository,
+            db_pool,
+            repository: repositories.users,
+            password_reset_repository: repositories.password_resets,
+            email_verification_repository: repositories.email_verifications,
+            login_audit_repository: repositories.login_audits,
+            saldo_repository: repositories.saldos,
+            topup_repository: repositories.topups,
+            transfer_repository: repositories.transfers,
+            withdraw_repository: repositories.withdraws,
             hashing,
             jwt_config,
+            mailer,
+            require_email_verification,
+        }
+    }
+
+    async fn send_verification(&self, user: &User) -> Result<(), ErrorResponse> {
+        let token = generate_reset_token();
+        let expires_at = Utc::now().naive_utc() + Duration::hours(EMAIL_VERIFICATION_TTL_HOURS);
+
+        self.email_verification_repository
+            .create(user.user_id, &hash_reset_token(&token), expires_at)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Auth] Failed to store verification token for user {}: {}",
+                    user.user_id, e
+                );
+                ErrorResponse::from(e)
+            })?;
+
+        self.mailer
+            .send_email_verification(&user.email, &token)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Auth] Failed to send verification email to {}: {}",
+                    user.email, e
+                );
+                ErrorResponse::from(e)
+            })?;
+
+        info!("📨 [Auth] Verification issued for user: {}", user.user_id);
+
+        Ok(())
+    }
+
+    // Re-hashes a just-verified password whose stored hash predates the
+    // current algorithm or cost. Best effort: the login goes ahead either way.
+    async fn upgrade_password_hash(&self, user: &User, password: &str) {
+        if !self.hashing.needs_rehash(&user.password) {
+            return;
+        }
+
+        let upgraded = match self.hashing.hash_password(password).await {
+            Ok(hash) => self.repository.update_password(user.user_id, &hash).await,
+            Err(e) => Err(AppError::HashingError(e)),
+        };
+
+        match upgraded {
+            Ok(_) => info!(
+                "🔁 [Auth] Upgraded password hash for user: {}",
+                user.user_id
+            ),
+            Err(e) => error!(
+                "❌ [Auth] Failed to upgrade password hash for user {}: {}",
+                user.user_id, e
+            ),
+        }
+    }
+
+    // Returns the matched user's id, if any, alongside the outcome so the
+    // attempt can be audited either way.
+    async fn attempt_login(
+        &self,
+        input: &LoginRequest,
+    ) -> (Option<i32>, Result<ApiResponse<TokenPair>, ErrorResponse>) {
+        let user = match self.repository.find_by_email(&input.email).await {
+            Ok(Some(user)) => {
+                info!(
+                    "👤 [Auth] User found: ID={}, Email={}",
+                    user.user_id, input.email
+                );
+                user
+            }
+            Ok(None) => {
+                error!("❌ [Auth] Login failed: User not found: {}", input.email);
+                return (
+                    None,
+                    Err(ErrorResponse::from(AppError::NotFound(
+                        "User not found".to_string(),
+                    ))),
+                );
+            }
+            Err(err) => {
+                error!(
+                    "❌ [Auth] Database error during login for {}: {}",
+                    input.email, err
+                );
+                return (None, Err(ErrorResponse::from(err)));
+            }
+        };
+
+        if self
+            .hashing
+            .compare_password(&user.password, &input.password)
+            .await
+            .is_err()
+        {
+            error!("⛔ [Auth] Invalid credentials for user: {}", input.email);
+            return (
+                Some(user.user_id),
+                Err(ErrorResponse::from(AppError::InvalidCredentials)),
+            );
+        }
+
+        self.upgrade_password_hash(&user, &input.password).await;
+
+        if self.require_email_verification && !user.is_verified {
+            error!(
+                "⛔ [Auth] Login rejected, email not verified: {}",
+                input.email
+            );
+            return (
+                Some(user.user_id),
+                Err(ErrorResponse::from(AppError::Custom(
+                    "email not verified".to_string(),
+                ))),
+            );
         }
+
+        let sid = Uuid::new_v4().to_string();
+        let tokens = match self.issue_token_pair(user.user_id as i64, user.role, &sid) {
+            Ok(tokens) => tokens,
+            Err(e) => return (Some(user.user_id), Err(e)),
+        };
+
+        // Like the audit row, a stale `last_login_at` is not worth failing
+        // an otherwise valid login over.
+        let _ = self.repository.record_login(user.user_id).await;
+
+        info!("✅ [Auth] Login successful for user: {}", input.email);
+
+        (
+            Some(user.user_id),
+            Ok(ApiResponse {
+                status: "success".to_string(),
+                message: "Login successful".to_string(),
+                data: tokens,
+            }),
+        )
+    }
+
+    fn issue_token_pair(
+        &self,
+        user_id: i64,
+        role: Role,
+        sid: &str,
+    ) -> Result<TokenPair, ErrorResponse> {
+        let access_token = self
+            .jwt_config
+            .generate_token(user_id, role, sid)
+            .map_err(|e| {
+                error!(
+                    "❌ [Auth] Failed to generate access token for user {}: {}",
+                    user_id, e
+                );
+                ErrorResponse::from(e)
+            })?;
+
+        let refresh_token = self
+            .jwt_config
+            .generate_refresh_token(user_id, sid)
+            .map_err(|e| {
+                error!(
+                    "❌ [Auth] Failed to generate refresh token for user {}: {}",
+                    user_id, e
+                );
+                ErrorResponse::from(e)
+            })?;
+
+        Ok(TokenPair {
+            access_token,
+            refresh_token,
+        })
     }
 }
 
@@ -69,18 +297,20 @@ impl AuthServiceTrait for AuthService {
                 ErrorResponse::from(AppError::HashingError(e))
             })?;
 
-        let noc_transfer = random_vcc().ok();
-
-        let request = CreateUserRequest {
-            firstname: input.firstname.clone(),
-            lastname: input.lastname.clone(),
-            email: input.email.clone(),
-            password: hashed_password,
-            confirm_password: input.confirm_password.clone(),
-            noc_transfer: noc_transfer.to_owned(),
-        };
+        let create_user = with_unique_noc(|noc_transfer| {
+            let request = CreateUserRequest {
+                firstname: input.firstname.clone(),
+                lastname: input.lastname.clone(),
+                email: input.email.clone(),
+                password: hashed_password.clone(),
+                confirm_password: input.confirm_password.clone(),
+                noc_transfer: Some(noc_transfer),
+            };
 
-        let create_user = self.repository.create_user(&request).await.map_err(|e| {
+            async move { self.repository.create_user(&request).await }
+        })
+        .await
+        .map_err(|e| {
             error!("❌ [Auth] Failed to create user in database: {}", e);
             ErrorResponse::from(e)
         })?;
@@ -90,6 +320,16 @@ impl AuthServiceTrait for AuthService {
             create_user.user_id, input.email
         );
 
+        // The account already exists at this point; failing the request would
+        // leave the client unable to retry, so they can use
+        // resend-verification instead.
+        if self.send_verification(&create_user).await.is_err() {
+            error!(
+                "⚠️ [Auth] Registered user {} without a verification email",
+                create_user.user_id
+            );
+        }
+
         Ok(ApiResponse {
             status: "success".to_string(),
             message: "User registered successfully".to_string(),
@@ -97,59 +337,622 @@ impl AuthServiceTrait for AuthService {
         })
     }
 
-    async fn login_user(&self, input: &LoginRequest) -> Result<ApiResponse<String>, ErrorResponse> {
+    async fn login_user(
+        &self,
+        input: &LoginRequest,
+        context: &LoginContext,
+    ) -> Result<ApiResponse<TokenPair>, ErrorResponse> {
         info!("🔐 [Auth] Login attempt for user: {}", input.email);
 
+        let (user_id, result) = self.attempt_login(input).await;
+
+        // The audit trail is best effort; failing to write it must not
+        // change the outcome of the login itself.
+        let _ = self
+            .login_audit_repository
+            .create(user_id, &input.email, context, result.is_ok())
+            .await;
+
+        result
+    }
+
+    async fn get_login_history(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<Vec<LoginAuditResponse>>, ErrorResponse> {
+        let logins: Vec<LoginAuditResponse> = self
+            .login_audit_repository
+            .find_recent_by_user(user_id, RECENT_LOGINS_LIMIT)
+            .await?
+            .into_iter()
+            .map(LoginAuditResponse::from)
+            .collect();
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Login history retrieved successfully".to_string(),
+            data: logins,
+        })
+    }
+
+    async fn get_capabilities(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<CapabilitiesResponse>, ErrorResponse> {
+        let user = self.repository.find_by_id(user_id).await?.ok_or_else(|| {
+            error!("User with id {user_id} not found");
+            ErrorResponse::from(AppError::NotFound(format!(
+                "User with id {user_id} not found"
+            )))
+        })?;
+
+        // Freezing applies to all of a user's saldos at once, so the base one
+        // speaks for the rest. No saldo yet means nothing to freeze.
+        let saldo = self
+            .saldo_repository
+            .find_by_user_and_currency(user_id, Currency::base())
+            .await?;
+
+        let is_frozen = saldo.is_some_and(|saldo| saldo.is_frozen);
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Capabilities retrieved successfully".to_string(),
+            data: CapabilitiesResponse {
+                can_transfer: !is_frozen,
+                can_withdraw: !is_frozen,
+                // The stored role rather than the token's, which may predate
+                // a promotion or demotion.
+                is_admin: user.role == Role::Admin,
+                requires_2fa: false,
+                is_frozen,
+                is_verified: user.is_verified,
+            },
+        })
+    }
+
+    async fn refresh_token(&self, refresh: &str) -> Result<ApiResponse<TokenPair>, ErrorResponse> {
+        info!("🔄 [Auth] Refresh token attempt");
+
+        let claims = self.jwt_config.verify_refresh_token(refresh).map_err(|e| {
+            error!("⛔ [Auth] Invalid refresh token: {}", e);
+            ErrorResponse::from(e)
+        })?;
+        let user_id = claims.user_id;
+
+        // Each refresh token is good for one refresh; a second use, even a
+        // concurrent one, is refused.
+        if !self.jwt_config.revoke_token(&claims.jti, claims.exp) {
+            error!("⛔ [Auth] Refresh token reused by user: {}", user_id);
+            return Err(ErrorResponse::from(AppError::TokenValidationError));
+        }
+
+        let user = match self.repository.find_by_id(user_id as i32).await {
+            Ok(Some(user)) => user,
+            Ok(None) => {
+                error!("❌ [Auth] Refresh failed: User not found: {}", user_id);
+                return Err(ErrorResponse::from(AppError::TokenValidationError));
+            }
+            Err(err) => {
+                error!(
+                    "❌ [Auth] Database error during refresh for user {}: {}",
+                    user_id, err
+                );
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        // Tokens issued before sessions existed carry no `sid`; they start one.
+        let sid = if claims.sid.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            claims.sid
+        };
+        let tokens = self.issue_token_pair(user_id, user.role, &sid)?;
+
+        info!("✅ [Auth] Tokens refreshed for user: {}", user_id);
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Token refreshed successfully".to_string(),
+            data: tokens,
+        })
+    }
+
+    async fn request_password_reset(
+        &self,
+        input: &ForgotPasswordRequest,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        info!("🔑 [Auth] Password reset requested for: {}", input.email);
+
+        // Same response whether or not the account exists, so this endpoint
+        // can't be used to probe for registered emails.
+        let response = ApiResponse {
+            status: "success".to_string(),
+            message: "If the email is registered, a password reset link has been sent".to_string(),
+            data: (),
+        };
+
         let user = match self.repository.find_by_email(&input.email).await {
-            Ok(Some(user)) => {
+            Ok(Some(user)) => user,
+            Ok(None) => {
                 info!(
-                    "👤 [Auth] User found: ID={}, Email={}",
-                    user.user_id, input.email
+                    "🟡 [Auth] Password reset for unknown email: {}",
+                    input.email
                 );
-                user
+                return Ok(response);
             }
+            Err(err) => {
+                error!(
+                    "❌ [Auth] Database error during password reset for {}: {}",
+                    input.email, err
+                );
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        let token = generate_reset_token();
+        let expires_at = Utc::now().naive_utc() + Duration::minutes(PASSWORD_RESET_TTL_MINUTES);
+
+        // Failing here only for registered emails would give them away too,
+        // so these errors are logged and the caller gets the usual answer.
+        if let Err(e) = self
+            .password_reset_repository
+            .create(user.user_id, &hash_reset_token(&token), expires_at)
+            .await
+        {
+            error!(
+                "❌ [Auth] Failed to store reset token for user {}: {}",
+                user.user_id, e
+            );
+            return Ok(response);
+        }
+
+        if let Err(e) = self.mailer.send_password_reset(&user.email, &token).await {
+            error!(
+                "❌ [Auth] Failed to send reset email to {}: {}",
+                user.email, e
+            );
+            return Ok(response);
+        }
+
+        info!("✅ [Auth] Password reset issued for user: {}", user.user_id);
+
+        Ok(response)
+    }
+
+    async fn reset_password(
+        &self,
+        input: &ResetPasswordRequest,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        info!("🔑 [Auth] Password reset attempt");
+
+        let password_hash = self
+            .hashing
+            .hash_password(&input.password)
+            .await
+            .map_err(|e| {
+                error!("🔐 [Auth] Failed to hash password: {}", e);
+                ErrorResponse::from(AppError::HashingError(e))
+            })?;
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin password reset transaction: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        let reset = self
+            .password_reset_repository
+            .consume_tx(&mut tx, &hash_reset_token(&input.token))
+            .await?
+            .ok_or_else(|| {
+                error!("⛔ [Auth] Invalid, expired or reused password reset token");
+                ErrorResponse::from(AppError::invalid_field(
+                    "token",
+                    "Invalid or expired password reset token",
+                ))
+            })?;
+
+        self.repository
+            .update_password_tx(&mut tx, reset.user_id, &password_hash)
+            .await?;
+
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit password reset transaction: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        info!("✅ [Auth] Password reset for user: {}", reset.user_id);
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Password has been reset successfully".to_string(),
+            data: (),
+        })
+    }
+
+    async fn verify_email(
+        &self,
+        input: &VerifyEmailQuery,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        info!("📨 [Auth] Email verification attempt");
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin email verification transaction: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        let verification = self
+            .email_verification_repository
+            .consume_tx(&mut tx, &hash_reset_token(&input.token))
+            .await?
+            .ok_or_else(|| {
+                error!("⛔ [Auth] Invalid, expired or reused verification token");
+                ErrorResponse::from(AppError::invalid_field(
+                    "token",
+                    "Invalid or expired verification token",
+                ))
+            })?;
+
+        let user = self
+            .repository
+            .mark_verified_tx(&mut tx, verification.user_id)
+            .await?;
+
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit email verification transaction: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        info!("✅ [Auth] Email verified for user: {}", user.user_id);
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Email verified successfully".to_string(),
+            data: UserResponse::from(user),
+        })
+    }
+
+    async fn resend_verification(
+        &self,
+        input: &ResendVerificationRequest,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        info!(
+            "📨 [Auth] Verification resend requested for: {}",
+            input.email
+        );
+
+        // Mirrors forgot-password: the response never reveals whether the
+        // email is registered or already verified.
+        let response = ApiResponse {
+            status: "success".to_string(),
+            message: "If the email is registered and unverified, a verification link has been sent"
+                .to_string(),
+            data: (),
+        };
+
+        let user = match self.repository.find_by_email(&input.email).await {
+            Ok(Some(user)) => user,
             Ok(None) => {
-                error!("❌ [Auth] Login failed: User not found: {}", input.email);
-                return Err(ErrorResponse::from(AppError::NotFound(
-                    "User not found".to_string(),
-                )));
+                info!(
+                    "🟡 [Auth] Verification resend for unknown email: {}",
+                    input.email
+                );
+                return Ok(response);
             }
             Err(err) => {
                 error!(
-                    "❌ [Auth] Database error during login for {}: {}",
+                    "❌ [Auth] Database error during verification resend for {}: {}",
                     input.email, err
                 );
                 return Err(ErrorResponse::from(err));
             }
         };
 
+        if user.is_verified {
+            info!("🟡 [Auth] User {} is already verified", user.user_id);
+            return Ok(response);
+        }
+
+        self.send_verification(&user).await?;
+
+        Ok(response)
+    }
+
+    async fn logout(&self, claims: &Claims) -> Result<ApiResponse<()>, ErrorResponse> {
+        // The session takes the refresh token down with it, so a stolen pair
+        // can't mint new access tokens after logout.
+        self.jwt_config.revoke_token(&claims.jti, claims.exp);
+        if !claims.sid.is_empty() {
+            self.jwt_config.revoke_session(&claims.sid);
+        }
+
+        info!("👋 [Auth] User {} logged out", claims.user_id);
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Logged out successfully".to_string(),
+            data: (),
+        })
+    }
+
+    async fn delete_account(
+        &self,
+        claims: &Claims,
+        input: &DeleteAccountRequest,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        let user_id = claims.user_id as i32;
+
+        info!("🗑️ [Auth] Account deletion requested by user: {user_id}");
+
+        let user = self.repository.find_by_id(user_id).await?.ok_or_else(|| {
+            error!("❌ [Auth] Account deletion failed: User not found: {user_id}");
+            ErrorResponse::from(AppError::NotFound(format!(
+                "User with id {user_id} not found"
+            )))
+        })?;
+
         if self
             .hashing
             .compare_password(&user.password, &input.password)
             .await
             .is_err()
         {
-            error!("⛔ [Auth] Invalid credentials for user: {}", input.email);
+            error!("⛔ [Auth] Account deletion rejected, wrong password for user: {user_id}");
             return Err(ErrorResponse::from(AppError::InvalidCredentials));
         }
 
-        let token = self
-            .jwt_config
-            .generate_token(user.user_id as i64)
-            .map_err(|e| {
-                error!(
-                    "❌ [Auth] Failed to generate JWT token for user {}: {}",
-                    input.email, e
-                );
-                ErrorResponse::from(e)
-            })?;
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin account deletion transaction: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
 
-        info!("✅ [Auth] Login successful for user: {}", input.email);
+        // Locked so a transfer can't land between the check and the delete.
+        let saldos = self
+            .saldo_repository
+            .find_by_user_for_update(&mut tx, user_id)
+            .await?;
+
+        if let Some(saldo) = saldos
+            .iter()
+            .find(|saldo| saldo.total_balance != Money::ZERO)
+        {
+            error!(
+                "⛔ [Auth] Account deletion rejected, user {user_id} still holds {} {}",
+                saldo.total_balance, saldo.currency
+            );
+            return Err(ErrorResponse::from(AppError::Conflict(format!(
+                "Your {} saldo still holds {}; withdraw it before deleting your account",
+                saldo.currency, saldo.total_balance
+            ))));
+        }
+
+        self.transfer_repository
+            .delete_unsettled_by_user_tx(&mut tx, user_id)
+            .await?;
+        self.withdraw_repository
+            .delete_by_user_tx(&mut tx, user_id)
+            .await?;
+        self.topup_repository
+            .delete_by_user_tx(&mut tx, user_id)
+            .await?;
+        self.saldo_repository
+            .delete_by_user_tx(&mut tx, user_id)
+            .await?;
+        self.repository.delete_user_tx(&mut tx, user_id).await?;
+
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit account deletion transaction: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        // Refresh tokens and API keys stop working with the user lookup; the
+        // access token has to be revoked explicitly.
+        self.jwt_config.revoke_token(&claims.jti, claims.exp);
+
+        info!("✅ [Auth] Account deleted for user: {user_id}");
 
         Ok(ApiResponse {
             status: "success".to_string(),
-            message: "Login successful".to_string(),
-            data: token,
+            message: "Account deleted successfully".to_string(),
+            data: (),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use chrono::NaiveDateTime;
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{
+        abstract_trait::{HashingTrait, MailerTrait},
+        config::{HashAlgorithm, Hashing, JwtConfig},
+        model::password_reset::PasswordReset,
+        test_support::{
+            MockEmailVerificationRepositoryTrait, MockLoginAuditRepositoryTrait,
+            MockPasswordResetRepositoryTrait, MockSaldoRepositoryTrait, MockTopupRepositoryTrait,
+            MockTransferRepositoryTrait, MockUserRepositoryTrait, MockWithdrawRepositoryTrait,
+            lazy_pool, user,
+        },
+    };
+
+    struct FailingMailer;
+
+    #[async_trait]
+    impl MailerTrait for FailingMailer {
+        async fn send_password_reset(&self, _email: &str, _token: &str) -> Result<(), AppError> {
+            Err(AppError::InternalError(
+                "SMTP server unreachable".to_string(),
+            ))
+        }
+
+        async fn send_email_verification(
+            &self,
+            _email: &str,
+            _token: &str,
+        ) -> Result<(), AppError> {
+            Err(AppError::InternalError(
+                "SMTP server unreachable".to_string(),
+            ))
+        }
+    }
+
+    fn service(
+        users: MockUserRepositoryTrait,
+        password_resets: MockPasswordResetRepositoryTrait,
+    ) -> AuthService {
+        AuthService::new(
+            lazy_pool(),
+            AuthRepositories {
+                users: Arc::new(users),
+                password_resets: Arc::new(password_resets),
+                email_verifications: Arc::new(MockEmailVerificationRepositoryTrait::new()),
+                login_audits: Arc::new(MockLoginAuditRepositoryTrait::new()),
+                saldos: Arc::new(MockSaldoRepositoryTrait::new()),
+                topups: Arc::new(MockTopupRepositoryTrait::new()),
+                transfers: Arc::new(MockTransferRepositoryTrait::new()),
+                withdraws: Arc::new(MockWithdrawRepositoryTrait::new()),
+            },
+            Arc::new(Hashing::new()),
+            Arc::new(JwtConfig::new("test-secret", 3600)),
+            Arc::new(FailingMailer),
+            false,
+        )
+    }
+
+    fn forgot(email: &str) -> ForgotPasswordRequest {
+        ForgotPasswordRequest {
+            email: email.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn password_reset_answers_alike_when_the_mailer_fails() {
+        let registered = user(1);
+        let registered_email = registered.email.clone();
+
+        let mut users = MockUserRepositoryTrait::new();
+        users
+            .expect_find_by_email()
+            .returning(move |email| Ok((email == registered.email).then(|| registered.clone())));
+
+        let mut password_resets = MockPasswordResetRepositoryTrait::new();
+        password_resets.expect_create().times(1).returning(
+            |user_id, token_hash, expires_at: NaiveDateTime| {
+                Ok(PasswordReset {
+                    password_reset_id: 1,
+                    user_id,
+                    token_hash: token_hash.to_string(),
+                    expires_at,
+                    used_at: None,
+                    created_at: None,
+                })
+            },
+        );
+
+        let service = service(users, password_resets);
+
+        let known = service
+            .request_password_reset(&forgot(&registered_email))
+            .await
+            .expect("a mailer failure must not surface");
+        let unknown = service
+            .request_password_reset(&forgot("nobody@example.com"))
+            .await
+            .expect("unknown emails succeed");
+
+        assert_eq!(known.status, unknown.status);
+        assert_eq!(known.message, unknown.message);
+    }
+
+    fn login(email: &str) -> LoginRequest {
+        LoginRequest {
+            email: email.to_string(),
+            password: "password123".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn login_with_an_old_cost_hash_stores_a_rehash() {
+        let old_hash = Hashing::with_params(HashAlgorithm::Bcrypt, 4)
+            .hash_password("password123")
+            .await
+            .unwrap();
+        let stored = Arc::new(Mutex::new(None));
+
+        let mut users = MockUserRepositoryTrait::new();
+        users.expect_find_by_email().times(1).returning(move |_| {
+            Ok(Some(User {
+                password: old_hash.clone(),
+                ..user(1)
+            }))
+        });
+        let captured = stored.clone();
+        users
+            .expect_update_password()
+            .times(1)
+            .returning(move |id, hash| {
+                *captured.lock().unwrap() = Some(hash.to_string());
+                Ok(user(id))
+            });
+        users.expect_record_login().returning(|_| Ok(()));
+
+        let (user_id, result) = service(users, MockPasswordResetRepositoryTrait::new())
+            .attempt_login(&login("user1@example.com"))
+            .await;
+        result.expect("login failed");
+        assert_eq!(user_id, Some(1));
+
+        let rehashed = stored.lock().unwrap().take().expect("rehash stored");
+        let hashing = Hashing::new();
+        assert!(!hashing.needs_rehash(&rehashed), "{rehashed}");
+        hashing
+            .compare_password(&rehashed, "password123")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn login_with_a_current_hash_is_not_rehashed() {
+        let current = Hashing::new().hash_password("password123").await.unwrap();
+
+        let mut users = MockUserRepositoryTrait::new();
+        users.expect_find_by_email().times(1).returning(move |_| {
+            Ok(Some(User {
+                password: current.clone(),
+                ..user(1)
+            }))
+        });
+        // No update_password expectation: nothing may be written.
+        users.expect_record_login().returning(|_| Ok(()));
+
+        let (_, result) = service(users, MockPasswordResetRepositoryTrait::new())
+            .attempt_login(&login("user1@example.com"))
+            .await;
+        result.expect("login failed");
+    }
+
+    #[tokio::test]
+    async fn a_wrong_password_never_triggers_a_rehash() {
+        let old_hash = Hashing::with_params(HashAlgorithm::Bcrypt, 4)
+            .hash_password("something-else")
+            .await
+            .unwrap();
+
+        let mut users = MockUserRepositoryTrait::new();
+        users.expect_find_by_email().times(1).returning(move |_| {
+            Ok(Some(User {
+                password: old_hash.clone(),
+                ..user(1)
+            }))
+        });
+
+        let (_, result) = service(users, MockPasswordResetRepositoryTrait::new())
+            .attempt_login(&login("user1@example.com"))
+            .await;
+        assert!(result.is_err());
+    }
+}
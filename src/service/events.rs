@@ -0,0 +1,62 @@
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::error;
+
+use crate::{
+    abstract_trait::DynWebhookNotifier,
+    config::Metrics,
+    domain::{
+        response::{topup::TopupResponse, transfer::TransferResponse, withdraw::WithdrawResponse},
+        webhook::WebhookEvent,
+    },
+};
+
+/// Side effects of a committed transaction: metric counters and webhook
+/// notifications to the users involved.
+#[derive(Clone)]
+pub struct TransactionEvents {
+    metrics: Arc<Metrics>,
+    webhooks: DynWebhookNotifier,
+}
+
+impl TransactionEvents {
+    pub fn new(metrics: Arc<Metrics>, webhooks: DynWebhookNotifier) -> Self {
+        Self { metrics, webhooks }
+    }
+
+    pub fn topup_created(&self, topup: &TopupResponse) {
+        self.metrics.topups_created_total.inc();
+        self.notify(topup.user_id, WebhookEvent::TopupCreated, topup);
+    }
+
+    pub fn transfers_created(&self, transfers: &[TransferResponse]) {
+        self.metrics
+            .transfers_created_total
+            .inc_by(transfers.len() as u64);
+
+        for transfer in transfers {
+            self.notify(
+                transfer.transfer_from,
+                WebhookEvent::TransferCreated,
+                transfer,
+            );
+            self.notify(
+                transfer.transfer_to,
+                WebhookEvent::TransferCreated,
+                transfer,
+            );
+        }
+    }
+
+    pub fn withdraw_created(&self, withdraw: &WithdrawResponse) {
+        self.metrics.record_withdraw(withdraw.withdraw_amount);
+        self.notify(withdraw.user_id, WebhookEvent::WithdrawCreated, withdraw);
+    }
+
+    fn notify(&self, user_id: i32, event: WebhookEvent, data: &impl Serialize) {
+        match serde_json::to_value(data) {
+            Ok(data) => self.webhooks.notify(user_id, event, data),
+            Err(e) => error!("Failed to encode {} webhook data: {e}", event.as_str()),
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use futures::{StreamExt, stream};
+use tracing::{error, info};
+use validator::Validate;
+
+use crate::{
+    abstract_trait::{
+        DynHistoryRepository, DynUserRepository, HistoryCsvStream, HistoryServiceTrait,
+    },
+    domain::{
+        money::Money,
+        request::{FindHistoryRequest, HistoryFilter, StatementRequest, normalize_page},
+        response::{
+            ApiResponsePagination, ErrorResponse, history::HistoryEntryResponse,
+            pagination::Pagination,
+        },
+        role::Role,
+    },
+    model::{history::BalanceSnapshot, user::User},
+    utils::{AppError, Statement, render_statement_pdf},
+};
+
+pub struct HistoryService {
+    history_repository: DynHistoryRepository,
+    user_repository: DynUserRepository,
+    max_page_size: i32,
+}
+
+// Rows fetched per round-trip while streaming a CSV export.
+const EXPORT_PAGE_SIZE: i32 = 500;
+
+const CSV_HEADER: &str = "date,type,amount,counterparty,reference\n";
+
+impl HistoryService {
+    pub fn new(
+        history_repository: DynHistoryRepository,
+        user_repository: DynUserRepository,
+        max_page_size: i32,
+    ) -> Self {
+        Self {
+            history_repository,
+            user_repository,
+            max_page_size,
+        }
+    }
+
+    async fn ensure_can_read(
+        &self,
+        id: i32,
+        user_id: i32,
+        role: Role,
+    ) -> Result<User, ErrorResponse> {
+        if !role.can_access(user_id, &[id]) {
+            error!("User {user_id} is not allowed to access history of user {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to access history of user {id}"
+            ))));
+        }
+
+        self.user_repository.find_by_id(id).await?.ok_or_else(|| {
+            error!("User with id {id} not found");
+            ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
+        })
+    }
+
+    async fn all_entries(
+        &self,
+        id: i32,
+        filter: HistoryFilter,
+    ) -> Result<Vec<HistoryEntryResponse>, ErrorResponse> {
+        let mut entries = Vec::new();
+
+        for page in 1.. {
+            let (chunk, _) = self
+                .history_repository
+                .find_by_user(id, page, EXPORT_PAGE_SIZE, filter)
+                .await?;
+            let last = chunk.len() < EXPORT_PAGE_SIZE as usize;
+
+            entries.extend(chunk.into_iter().map(HistoryEntryResponse::from));
+
+            if last {
+                break;
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+// Every field is numeric, an RFC 3339 timestamp or a fixed kind name, so no
+// quoting is needed.
+fn csv_row(entry: HistoryEntryResponse) -> String {
+    format!(
+        "{},{},{},{},{}\n",
+        entry.timestamp.to_rfc3339(),
+        entry.kind,
+        entry.amount,
+        entry
+            .counterparty
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+        entry.id
+    )
+}
+
+#[async_trait]
+impl HistoryServiceTrait for HistoryService {
+    async fn get_history(
+        &self,
+        id: i32,
+        req: &FindHistoryRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponsePagination<Vec<HistoryEntryResponse>>, ErrorResponse> {
+        req.validate()
+            .map_err(|e| ErrorResponse::from(AppError::Validation(e)))?;
+
+        self.ensure_can_read(id, user_id, role).await?;
+
+        let (page, page_size) = normalize_page(req.page, req.page_size, self.max_page_size);
+
+        let (entries, total_items) = self
+            .history_repository
+            .find_by_user(id, page, page_size, req.filter())
+            .await?;
+
+        info!("Found {} history entries for user {id}", entries.len());
+
+        Ok(ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Transaction history retrieved successfully".to_string(),
+            data: entries
+                .into_iter()
+                .map(HistoryEntryResponse::from)
+                .collect(),
+            pagination: Pagination::new(page, page_size, total_items),
+        })
+    }
+
+    async fn export_history_csv(
+        &self,
+        id: i32,
+        req: &FindHistoryRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<HistoryCsvStream, ErrorResponse> {
+        req.validate()
+            .map_err(|e| ErrorResponse::from(AppError::Validation(e)))?;
+
+        self.ensure_can_read(id, user_id, role).await?;
+
+        info!("Streaming CSV history export for user {id}");
+
+        let repository = self.history_repository.clone();
+        let filter = req.filter();
+
+        // Pages through the same merged query as the JSON endpoint so only
+        // one page is held in memory at a time.
+        let rows = stream::try_unfold(Some(1), move |page| {
+            let repository = repository.clone();
+
+            async move {
+                let Some(page) = page else {
+                    return Ok(None);
+                };
+
+                let (entries, _) = repository
+                    .find_by_user(id, page, EXPORT_PAGE_SIZE, filter)
+                    .await?;
+
+                if entries.is_empty() {
+                    return Ok(None);
+                }
+
+                let next = (entries.len() == EXPORT_PAGE_SIZE as usize).then_some(page + 1);
+                let chunk: String = entries
+                    .into_iter()
+                    .map(|entry| csv_row(HistoryEntryResponse::from(entry)))
+                    .collect();
+
+                Ok(Some((chunk, next)))
+            }
+        });
+
+        Ok(stream::once(async { Ok(CSV_HEADER.to_string()) })
+            .chain(rows)
+            .boxed())
+    }
+
+    async fn export_statement_pdf(
+        &self,
+        id: i32,
+        req: &StatementRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<Vec<u8>, ErrorResponse> {
+        req.validate()
+            .map_err(|e| ErrorResponse::from(AppError::Validation(e)))?;
+
+        let user = self.ensure_can_read(id, user_id, role).await?;
+
+        let to = req.to.unwrap_or_else(Utc::now);
+        let filter = StatementRequest {
+            from: req.from,
+            to: Some(to),
+        }
+        .filter();
+
+        let mut entries = self.all_entries(id, filter).await?;
+        entries.reverse();
+
+        let closing = self.history_repository.balances_at(id, to).await?;
+
+        // The period includes `from` itself, so the opening balance is taken
+        // just before it. Timestamps are stored with microsecond precision.
+        let opening = match req.from {
+            Some(from) => {
+                self.history_repository
+                    .balances_at(id, from - Duration::microseconds(1))
+                    .await?
+            }
+            None => closing
+                .iter()
+                .map(|snapshot| BalanceSnapshot {
+                    currency: snapshot.currency,
+                    balance: Money::ZERO,
+                })
+                .collect(),
+        };
+
+        info!(
+            "Rendering PDF statement for user {id} with {} entries",
+            entries.len()
+        );
+
+        render_statement_pdf(&Statement {
+            holder: format!("{} {}", user.firstname, user.lastname),
+            noc_transfer: user.noc_transfer,
+            from: req.from,
+            to,
+            opening,
+            closing,
+            entries,
+        })
+        .map_err(ErrorResponse::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use futures::TryStreamExt;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        domain::request::{history::HistoryKind, pagination::DEFAULT_MAX_PAGE_SIZE},
+        model::history::HistoryEntry,
+        test_support::{MockHistoryRepositoryTrait, MockUserRepositoryTrait, user},
+    };
+
+    fn entry(source_id: i32, kind: HistoryKind, amount: i64, day: u32) -> HistoryEntry {
+        HistoryEntry {
+            source_id,
+            kind,
+            amount: Money::new(amount),
+            occurred_at: NaiveDate::from_ymd_opt(2025, 3, day)
+                .unwrap()
+                .and_hms_opt(9, 30, 0)
+                .unwrap(),
+            counterparty: None,
+            created_at: None,
+        }
+    }
+
+    fn service(history: MockHistoryRepositoryTrait) -> HistoryService {
+        let mut users = MockUserRepositoryTrait::new();
+        users.expect_find_by_id().returning(|id| Ok(Some(user(id))));
+
+        HistoryService::new(Arc::new(history), Arc::new(users), DEFAULT_MAX_PAGE_SIZE)
+    }
+
+    fn request() -> FindHistoryRequest {
+        serde_json::from_value(serde_json::json!({})).unwrap()
+    }
+
+    #[tokio::test]
+    async fn csv_has_a_header_and_a_row_per_entry() {
+        let mut history = MockHistoryRepositoryTrait::new();
+        history
+            .expect_find_by_user()
+            .withf(|user_id, page, _, _| *user_id == 7 && *page == 1)
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok((
+                    vec![
+                        entry(12, HistoryKind::Withdraw, 20000, 5),
+                        entry(3, HistoryKind::Topup, 50000, 1),
+                    ],
+                    2,
+                ))
+            });
+
+        let csv: String = service(history)
+            .export_history_csv(7, &request(), 7, Role::User)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap()
+            .concat();
+
+        assert_eq!(
+            csv.lines().collect::<Vec<_>>(),
+            [
+                "date,type,amount,counterparty,reference",
+                "2025-03-05T09:30:00+00:00,withdraw,20000,,12",
+                "2025-03-01T09:30:00+00:00,topup,50000,,3",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn csv_of_another_user_is_forbidden() {
+        let err = service(MockHistoryRepositoryTrait::new())
+            .export_history_csv(7, &request(), 8, Role::User)
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(err.code, axum::http::StatusCode::FORBIDDEN);
+    }
+}
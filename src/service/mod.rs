@@ -1,6 +1,11 @@
+pub mod api_key;
 pub mod auth;
+pub mod events;
+pub mod history;
+pub mod reconciliation;
 pub mod saldo;
 pub mod topup;
 pub mod transfer;
 pub mod user;
+pub mod webhook;
 pub mod withdraw;
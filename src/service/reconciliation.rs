@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use tracing::{error, info, warn};
+
+use crate::{
+    abstract_trait::{DynReconciliationRepository, DynUserRepository, ReconciliationServiceTrait},
+    domain::response::{ApiResponse, ErrorResponse, reconciliation::ReconcileResponse},
+    model::reconciliation::Reconciliation,
+    utils::AppError,
+};
+
+pub struct ReconciliationService {
+    reconciliation_repository: DynReconciliationRepository,
+    user_repository: DynUserRepository,
+}
+
+impl ReconciliationService {
+    pub fn new(
+        reconciliation_repository: DynReconciliationRepository,
+        user_repository: DynUserRepository,
+    ) -> Self {
+        Self {
+            reconciliation_repository,
+            user_repository,
+        }
+    }
+}
+
+fn to_responses(rows: Vec<Reconciliation>) -> Result<Vec<ReconcileResponse>, ErrorResponse> {
+    rows.into_iter()
+        .map(|row| ReconcileResponse::try_from(row).map_err(ErrorResponse::from))
+        .collect()
+}
+
+#[async_trait]
+impl ReconciliationServiceTrait for ReconciliationService {
+    async fn reconcile_user(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<Vec<ReconcileResponse>>, ErrorResponse> {
+        self.user_repository.find_by_id(id).await?.ok_or_else(|| {
+            error!("User with id {id} not found");
+            ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
+        })?;
+
+        let saldos = to_responses(self.reconciliation_repository.find_by_user(id).await?)?;
+
+        for saldo in saldos.iter().filter(|saldo| !saldo.matches) {
+            warn!(
+                "Saldo {} of user {id} is off by {} {}",
+                saldo.saldo_id, saldo.difference, saldo.currency
+            );
+        }
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Saldo reconciled successfully".to_string(),
+            data: saldos,
+        })
+    }
+
+    async fn find_mismatches(&self) -> Result<ApiResponse<Vec<ReconcileResponse>>, ErrorResponse> {
+        let mismatches = to_responses(self.reconciliation_repository.find_mismatches().await?)?;
+
+        info!(
+            "Found {} saldos out of line with their ledger",
+            mismatches.len()
+        );
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Saldo reconciliation completed".to_string(),
+            data: mismatches,
+        })
+    }
+}
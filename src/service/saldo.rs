@@ -4,11 +4,19 @@ use tracing::{error, info};
 use crate::{
     abstract_trait::{DynSaldoRepository, DynUserRepository, SaldoServiceTrait},
     domain::{
-        request::{CreateSaldoRequest, FindAllSaldoRequest, UpdateSaldoRequest},
+        currency::Currency,
+        request::{
+            CreateSaldoRequest, FindAllSaldoRequest, FindSaldoHistoryRequest,
+            FindUserSaldosRequest, FreezeSaldoRequest, Sort, UpdateSaldoRequest, normalize_page,
+            normalize_pagination,
+        },
         response::{
-            ApiResponse, ApiResponsePagination, ErrorResponse, pagination::Pagination,
-            saldo::SaldoResponse,
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            pagination::Pagination,
+            saldo::{SaldoResponse, SaldoStatsResponse},
+            saldo_history::SaldoHistoryResponse,
         },
+        role::Role,
     },
     utils::AppError,
 };
@@ -16,13 +24,19 @@ use crate::{
 pub struct SaldoService {
     user_repository: DynUserRepository,
     saldo_repository: DynSaldoRepository,
+    max_page_size: i32,
 }
 
 impl SaldoService {
-    pub fn new(user_repository: DynUserRepository, saldo_repository: DynSaldoRepository) -> Self {
+    pub fn new(
+        user_repository: DynUserRepository,
+        saldo_repository: DynSaldoRepository,
+        max_page_size: i32,
+    ) -> Self {
         Self {
             user_repository,
             saldo_repository,
+            max_page_size,
         }
     }
 }
@@ -33,23 +47,21 @@ impl SaldoServiceTrait for SaldoService {
         &self,
         req: &FindAllSaldoRequest,
     ) -> Result<ApiResponsePagination<Vec<SaldoResponse>>, ErrorResponse> {
-        let page = if req.page > 0 { req.page } else { 1 };
-        let page_size = if req.page_size > 0 { req.page_size } else { 10 };
-        let search = if req.search.is_empty() {
-            None
-        } else {
-            Some(req.search.clone())
-        };
+        let (page, page_size, search) = normalize_pagination(req, self.max_page_size);
 
         let (saldos, total_items) = self
             .saldo_repository
-            .find_all(page, page_size, search)
+            .find_all(
+                page,
+                page_size,
+                search,
+                Sort::new(req.sort_by.clone(), req.order),
+                req.include_deleted,
+            )
             .await?;
 
         info!("Found {} saldos", saldos.len());
 
-        let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
-
         let saldo_responses: Vec<SaldoResponse> =
             saldos.into_iter().map(SaldoResponse::from).collect();
 
@@ -57,22 +69,26 @@ impl SaldoServiceTrait for SaldoService {
             status: "success".to_string(),
             message: "Saldos retrieved successfully".to_string(),
             data: saldo_responses,
-            pagination: Pagination {
-                page,
-                page_size,
-                total_items,
-                total_pages,
-            },
+            pagination: Pagination::new(page, page_size, total_items),
         })
     }
 
     async fn get_saldo(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<SaldoResponse>>, ErrorResponse> {
         let saldo = self.saldo_repository.find_by_id(id).await?;
 
         if let Some(saldo) = saldo {
+            if !role.can_access(user_id, &[saldo.user_id]) {
+                error!("User {user_id} is not allowed to access saldo {id}");
+                return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                    "You are not allowed to access saldo {id}"
+                ))));
+            }
+
             Ok(ApiResponse {
                 status: "success".to_string(),
                 message: "Saldo retrieved successfully".to_string(),
@@ -85,52 +101,63 @@ impl SaldoServiceTrait for SaldoService {
         }
     }
 
+    // One saldo per currency; `get_saldo_user` is the base-currency shortcut.
     async fn get_saldo_users(
         &self,
         id: i32,
-    ) -> Result<ApiResponse<Option<Vec<SaldoResponse>>>, ErrorResponse> {
-        let _user = self.user_repository.find_by_id(id).await.map_err(|_| {
+        req: &FindUserSaldosRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponsePagination<Vec<SaldoResponse>>, ErrorResponse> {
+        if !role.can_access(user_id, &[id]) {
+            error!("User {user_id} is not allowed to access saldos of user {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to access saldos of user {id}"
+            ))));
+        }
+
+        self.user_repository.find_by_id(id).await?.ok_or_else(|| {
+            error!("User with id {id} not found");
             ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
         })?;
 
-        let saldo = self.saldo_repository.find_by_users_id(id).await?;
-
-        let saldo_responses = if saldo.is_empty() {
-            None
-        } else {
-            Some(saldo.into_iter().map(SaldoResponse::from).collect())
-        };
+        let (page, page_size) = normalize_page(req.page, req.page_size, self.max_page_size);
 
-        if saldo_responses.is_none() {
-            let response = ApiResponse {
-                status: "success".to_string(),
-                data: None,
-                message: format!("No saldo found for user with id {id}"),
-            };
+        let (saldos, total_items) = self
+            .saldo_repository
+            .find_by_users_id(id, page, page_size)
+            .await?;
 
-            return Ok(response);
-        }
+        info!("Found {} saldos for user {id}", saldos.len());
 
-        let response = ApiResponse {
+        Ok(ApiResponsePagination {
             status: "success".to_string(),
-            data: saldo_responses,
-            message: "Success".to_string(),
-        };
-
-        Ok(response)
+            message: "Saldos retrieved successfully".to_string(),
+            data: saldos.into_iter().map(SaldoResponse::from).collect(),
+            pagination: Pagination::new(page, page_size, total_items),
+        })
     }
 
     async fn get_saldo_user(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<SaldoResponse>>, ErrorResponse> {
+        if !role.can_access(user_id, &[id]) {
+            error!("User {user_id} is not allowed to access saldos of user {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to access saldos of user {id}"
+            ))));
+        }
+
         let _user = self.user_repository.find_by_id(id).await.map_err(|_| {
             ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
         })?;
 
         let saldo: Option<SaldoResponse> = self
             .saldo_repository
-            .find_by_user_id(id)
+            .find_by_user_and_currency(id, Currency::base())
             .await?
             .map(SaldoResponse::from);
 
@@ -157,21 +184,38 @@ impl SaldoServiceTrait for SaldoService {
         &self,
         input: &CreateSaldoRequest,
     ) -> Result<ApiResponse<SaldoResponse>, ErrorResponse> {
-        let _user = self
-            .user_repository
+        self.user_repository
             .find_by_id(input.user_id)
-            .await
-            .map_err(|_| {
+            .await?
+            .ok_or_else(|| {
                 ErrorResponse::from(AppError::NotFound(format!(
                     "User with id {} not found",
                     input.user_id
                 )))
             })?;
 
-        info!("Saldo created successfully for user_id: {}", input.user_id);
+        // Friendlier than waiting for the insert to fail; the unique index on
+        // (user_id, currency) still catches concurrent creates.
+        if self
+            .saldo_repository
+            .find_by_user_and_currency(input.user_id, input.currency)
+            .await?
+            .is_some()
+        {
+            error!(
+                "User {} already has a {} saldo",
+                input.user_id, input.currency
+            );
+            return Err(ErrorResponse::from(AppError::Conflict(format!(
+                "User id {} already has a {} saldo",
+                input.user_id, input.currency
+            ))));
+        }
 
         let saldo = self.saldo_repository.create(input).await?;
 
+        info!("Saldo created successfully for user_id: {}", input.user_id);
+
         Ok(ApiResponse {
             status: "success".to_string(),
             message: "Saldo created successfully".to_string(),
@@ -182,50 +226,74 @@ impl SaldoServiceTrait for SaldoService {
     async fn update_saldo(
         &self,
         input: &UpdateSaldoRequest,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<SaldoResponse>>, ErrorResponse> {
-        let _user = self
-            .user_repository
-            .find_by_id(input.user_id)
-            .await
-            .map_err(|_| {
+        let existing_saldo = self
+            .saldo_repository
+            .find_by_id(input.saldo_id)
+            .await?
+            .ok_or_else(|| {
+                error!("Saldo with id {} not found", input.saldo_id);
                 ErrorResponse::from(AppError::NotFound(format!(
-                    "User with id {} not found",
-                    input.user_id
+                    "Saldo with id {} not found",
+                    input.saldo_id
                 )))
             })?;
 
-        let existing_saldo = self.saldo_repository.find_by_id(input.saldo_id).await?;
+        if !role.can_access(user_id, &[existing_saldo.user_id]) {
+            error!(
+                "User {user_id} is not allowed to update saldo {}",
+                input.saldo_id
+            );
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to update saldo {}",
+                input.saldo_id
+            ))));
+        }
 
-        match existing_saldo {
-            Some(_) => {
-                let updated_saldo = self.saldo_repository.update(input).await?;
+        if existing_saldo.user_id != input.user_id {
+            error!(
+                "Saldo {} belongs to user {}, not {}",
+                input.saldo_id, existing_saldo.user_id, input.user_id
+            );
+            return Err(ErrorResponse::from(AppError::NotFound(format!(
+                "Saldo with id {} not found for user {}",
+                input.saldo_id, input.user_id
+            ))));
+        }
 
-                info!("Saldo updated successfully for id: {}", input.saldo_id);
+        let updated_saldo = self.saldo_repository.update(input).await?;
 
-                Ok(ApiResponse {
-                    status: "success".to_string(),
-                    message: "Saldo updated successfully".to_string(),
-                    data: Some(SaldoResponse::from(updated_saldo)),
-                })
-            }
-            None => {
-                error!("Saldo with id {} not found", input.saldo_id);
-                Err(ErrorResponse::from(AppError::NotFound(format!(
-                    "Saldo with id {} not found",
-                    input.saldo_id
-                ))))
-            }
-        }
+        info!("Saldo updated successfully for id: {}", input.saldo_id);
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Saldo updated successfully".to_string(),
+            data: Some(SaldoResponse::from(updated_saldo)),
+        })
     }
 
-    async fn delete_saldo(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
+    async fn delete_saldo(
+        &self,
+        id: i32,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        if !role.can_access(user_id, &[id]) {
+            error!("User {user_id} is not allowed to delete the saldo of user {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to delete the saldo of user {id}"
+            ))));
+        }
+
         let user = self.user_repository.find_by_id(id).await.map_err(|_| {
             ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
         })?;
 
         let existing_saldo = self
             .saldo_repository
-            .find_by_user_id(user.unwrap().user_id)
+            .find_by_user_and_currency(user.unwrap().user_id, Currency::base())
             .await?;
 
         match existing_saldo {
@@ -250,4 +318,111 @@ impl SaldoServiceTrait for SaldoService {
             }
         }
     }
+
+    async fn restore_saldo(&self, id: i32) -> Result<ApiResponse<SaldoResponse>, ErrorResponse> {
+        let saldo = self.saldo_repository.restore(id).await?;
+
+        info!("Saldo restored successfully for id: {id}");
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Saldo restored successfully".to_string(),
+            data: SaldoResponse::from(saldo),
+        })
+    }
+
+    async fn get_saldo_history(
+        &self,
+        id: i32,
+        req: &FindSaldoHistoryRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponsePagination<Vec<SaldoHistoryResponse>>, ErrorResponse> {
+        if !role.can_access(user_id, &[id]) {
+            error!("User {user_id} is not allowed to access saldo history of user {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to access saldo history of user {id}"
+            ))));
+        }
+
+        self.user_repository.find_by_id(id).await?.ok_or_else(|| {
+            error!("User with id {id} not found");
+            ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
+        })?;
+
+        let (page, page_size) = normalize_page(req.page, req.page_size, self.max_page_size);
+
+        let (entries, total_items) = self
+            .saldo_repository
+            .find_history(id, page, page_size)
+            .await?;
+
+        info!(
+            "Found {} saldo history entries for user {id}",
+            entries.len()
+        );
+
+        Ok(ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Saldo history retrieved successfully".to_string(),
+            data: entries
+                .into_iter()
+                .map(SaldoHistoryResponse::from)
+                .collect(),
+            pagination: Pagination::new(page, page_size, total_items),
+        })
+    }
+
+    async fn get_saldo_stats(&self) -> Result<ApiResponse<Vec<SaldoStatsResponse>>, ErrorResponse> {
+        let stats = self.saldo_repository.stats().await?;
+
+        info!("Computed saldo stats for {} currencies", stats.len());
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Saldo stats retrieved successfully".to_string(),
+            data: stats.into_iter().map(SaldoStatsResponse::from).collect(),
+        })
+    }
+
+    async fn freeze_saldo(
+        &self,
+        user_id: i32,
+        input: &FreezeSaldoRequest,
+    ) -> Result<ApiResponse<Vec<SaldoResponse>>, ErrorResponse> {
+        let saldos = self
+            .saldo_repository
+            .set_frozen(user_id, true, input.reason.clone())
+            .await?;
+
+        info!(
+            "Froze {} saldo(s) of user {user_id}, reason: {:?}",
+            saldos.len(),
+            input.reason
+        );
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Saldo frozen successfully".to_string(),
+            data: saldos.into_iter().map(SaldoResponse::from).collect(),
+        })
+    }
+
+    async fn unfreeze_saldo(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<Vec<SaldoResponse>>, ErrorResponse> {
+        let saldos = self
+            .saldo_repository
+            .set_frozen(user_id, false, None)
+            .await?;
+
+        info!("Unfroze {} saldo(s) of user {user_id}", saldos.len());
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Saldo unfrozen successfully".to_string(),
+            data: saldos.into_iter().map(SaldoResponse::from).collect(),
+        })
+    }
 }
@@ -1,40 +1,220 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use sqlx::PgConnection;
+use std::collections::{HashMap, HashSet};
 use tracing::{error, info};
+use uuid::Uuid;
+use validator::Validate;
 
 use crate::{
     abstract_trait::{
         DynSaldoRepository, DynTopupRepository, DynUserRepository, TopupServiceTrait,
     },
+    config::ConnectionPool,
     domain::{
+        currency::Currency,
+        money::Money,
         request::{
-            CreateSaldoRequest, CreateTopupRequest, FindAllTopupRequest, UpdateSaldoBalance,
-            UpdateTopupAmount, UpdateTopupRequest,
+            BalanceChangeReason, CreateBulkTopupRequest, CreateTopupRequest, FindAllTopupRequest,
+            IncrementSaldoBalance, Sort, TopupMonthlyStatsRequest, UpdateSaldoBalance,
+            UpdateTopupAmount, UpdateTopupRequest, normalize_pagination,
         },
         response::{
-            ApiResponse, ApiResponsePagination, ErrorResponse, pagination::Pagination,
-            topup::TopupResponse,
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            pagination::Pagination,
+            topup::{BulkTopupResult, BulkTopupStatus, TopupMonthlyStatsResponse, TopupResponse},
         },
+        role::Role,
+        topup_limits::TopupLimits,
     },
+    model::topup::Topup,
+    service::events::TransactionEvents,
     utils::AppError,
 };
 
 pub struct TopupService {
+    db_pool: ConnectionPool,
     topup_repository: DynTopupRepository,
     saldo_repository: DynSaldoRepository,
     user_repository: DynUserRepository,
+    events: TransactionEvents,
+    topup_limits: TopupLimits,
+    max_page_size: i32,
+}
+
+const BULK_TOPUP_METHOD: &str = "bulk_import";
+
+// Window used when `from` is omitted, and the most months one request may span.
+const DEFAULT_STATS_MONTHS: u32 = 12;
+const MAX_STATS_MONTHS: usize = 120;
+
+fn month_start(at: DateTime<Utc>) -> NaiveDate {
+    at.date_naive().with_day(1).unwrap_or(at.date_naive())
+}
+
+// Every month from `from` through `to`, both truncated to the first day.
+fn months_between(from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<NaiveDate> {
+    let last = month_start(to);
+
+    std::iter::successors(Some(month_start(from)), |month| {
+        month.checked_add_months(Months::new(1))
+    })
+    .take_while(|month| *month <= last)
+    .take(MAX_STATS_MONTHS + 1)
+    .collect()
 }
 
 impl TopupService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
+        db_pool: ConnectionPool,
         topup_repository: DynTopupRepository,
         saldo_repository: DynSaldoRepository,
         user_repository: DynUserRepository,
+        events: TransactionEvents,
+        topup_limits: TopupLimits,
+        max_page_size: i32,
     ) -> Self {
         Self {
+            db_pool,
             topup_repository,
             saldo_repository,
             user_repository,
+            events,
+            topup_limits,
+            max_page_size,
+        }
+    }
+
+    // Records the topup and credits it to the user's saldo, opening an empty
+    // saldo first if the user has none in that currency yet.
+    async fn credit_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &CreateTopupRequest,
+    ) -> Result<Topup, ErrorResponse> {
+        self.topup_limits.ensure_amount(input.topup_amount)?;
+
+        let topup = self.topup_repository.create_tx(conn, input).await?;
+
+        let increment = IncrementSaldoBalance {
+            user_id: input.user_id,
+            currency: topup.currency,
+            amount: topup.topup_amount,
+            reason: BalanceChangeReason::Topup,
+            reference_id: Some(topup.topup_id),
+        };
+
+        let saldo = match self
+            .saldo_repository
+            .increment_balance_tx(conn, &increment)
+            .await?
+        {
+            Some(saldo) => saldo,
+            None => {
+                // Nothing was credited: the saldo is frozen or doesn't
+                // exist yet. Both are rare, so only now take the lock.
+                match self
+                    .saldo_repository
+                    .find_by_user_and_currency_for_update(conn, input.user_id, topup.currency)
+                    .await?
+                {
+                    Some(saldo) => {
+                        saldo.ensure_not_frozen().inspect_err(|_| {
+                            error!("Topup rejected: saldo of user {} is frozen", input.user_id);
+                        })?;
+                    }
+                    None => {
+                        // Opened at zero so the topup lands in the saldo
+                        // history like any other. Not a `CreateSaldoRequest`:
+                        // its 50000 floor is for saldos admins open by hand,
+                        // and would reject a first topup of, say, 10000.
+                        self.saldo_repository
+                            .create_initial_tx(conn, input.user_id, topup.currency)
+                            .await?;
+
+                        info!(
+                            "Initial {} saldo created for user {}",
+                            topup.currency, input.user_id
+                        );
+                    }
+                }
+
+                self.saldo_repository
+                    .increment_balance_tx(conn, &increment)
+                    .await?
+                    .ok_or_else(|| {
+                        error!(
+                            "{} saldo of user {} could not be credited after opening it",
+                            topup.currency, input.user_id
+                        );
+                        ErrorResponse::from(AppError::InternalError(format!(
+                            "Could not credit topup {}",
+                            topup.topup_id
+                        )))
+                    })?
+            }
+        };
+
+        info!(
+            "Topup {} credited to user {}. New balance: {}",
+            topup.topup_id, input.user_id, saldo.total_balance
+        );
+
+        Ok(topup)
+    }
+
+    // Shifts the user's balance by `delta` on a locked saldo row, refusing
+    // to leave it negative. Used to undo a topup on delete, re-apply it on
+    // restore and apply the difference when its amount is corrected.
+    async fn adjust_balance_tx(
+        &self,
+        conn: &mut PgConnection,
+        topup: &Topup,
+        delta: Money,
+    ) -> Result<(), ErrorResponse> {
+        let user_id = topup.user_id;
+        let topup_id = topup.topup_id;
+
+        let saldo = self
+            .saldo_repository
+            .find_by_user_and_currency_for_update(conn, user_id, topup.currency)
+            .await?
+            .ok_or_else(|| {
+                error!("Saldo not found for user_id={user_id}");
+                ErrorResponse::from(AppError::NotFound(format!(
+                    "Saldo with User id {user_id} not found"
+                )))
+            })?;
+
+        let total_balance = saldo.total_balance.checked_add(delta)?;
+
+        if total_balance.is_negative() {
+            let error_msg = format!(
+                "Insufficient balance to adjust topup {topup_id}: user_id={user_id}, current={}, delta={delta}",
+                saldo.total_balance
+            );
+            error!("{}", error_msg);
+            return Err(ErrorResponse::from(AppError::InsufficientBalance {
+                required: Money::ZERO.checked_sub(delta)?,
+                available: saldo.total_balance,
+            }));
         }
+
+        self.saldo_repository
+            .update_balance_tx(
+                conn,
+                &UpdateSaldoBalance {
+                    user_id,
+                    currency: saldo.currency,
+                    total_balance,
+                    reason: BalanceChangeReason::Topup,
+                    reference_id: Some(topup_id),
+                },
+            )
+            .await?;
+
+        Ok(())
     }
 }
 
@@ -44,23 +224,24 @@ impl TopupServiceTrait for TopupService {
         &self,
         req: &FindAllTopupRequest,
     ) -> Result<ApiResponsePagination<Vec<TopupResponse>>, ErrorResponse> {
-        let page = if req.page > 0 { req.page } else { 1 };
-        let page_size = if req.page_size > 0 { req.page_size } else { 10 };
-        let search = if req.search.is_empty() {
-            None
-        } else {
-            Some(req.search.clone())
-        };
+        req.validate()
+            .map_err(|e| ErrorResponse::from(AppError::Validation(e)))?;
+
+        let (page, page_size, search) = normalize_pagination(req, self.max_page_size);
 
         let (topups, total_items) = self
             .topup_repository
-            .find_all(page, page_size, search)
+            .find_all(
+                page,
+                page_size,
+                search,
+                Sort::new(req.sort_by.clone(), req.order),
+                req.filter(),
+            )
             .await?;
 
         info!("Found {} topups", topups.len());
 
-        let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
-
         let topup_responses: Vec<TopupResponse> =
             topups.into_iter().map(TopupResponse::from).collect();
 
@@ -68,18 +249,15 @@ impl TopupServiceTrait for TopupService {
             status: "success".to_string(),
             message: "Topups retrieved successfully".to_string(),
             data: topup_responses,
-            pagination: Pagination {
-                page,
-                page_size,
-                total_items,
-                total_pages,
-            },
+            pagination: Pagination::new(page, page_size, total_items),
         })
     }
 
     async fn get_topup(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<TopupResponse>>, ErrorResponse> {
         info!("Fetching topup with id {id}");
 
@@ -87,6 +265,13 @@ impl TopupServiceTrait for TopupService {
 
         match topup {
             Ok(Some(topup)) => {
+                if !role.can_access(user_id, &[topup.user_id]) {
+                    error!("User {user_id} is not allowed to access topup {id}");
+                    return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                        "You are not allowed to access topup {id}"
+                    ))));
+                }
+
                 info!("Successfully retrieved topup with id {id}");
                 Ok(ApiResponse {
                     status: "success".to_string(),
@@ -110,7 +295,16 @@ impl TopupServiceTrait for TopupService {
     async fn get_topup_users(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<Vec<TopupResponse>>>, ErrorResponse> {
+        if !role.can_access(user_id, &[id]) {
+            error!("User {user_id} is not allowed to access topups of user {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to access topups of user {id}"
+            ))));
+        }
+
         self.user_repository.find_by_id(id).await.map_err(|_| {
             error!("User with id {id} not found");
             ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
@@ -139,7 +333,16 @@ impl TopupServiceTrait for TopupService {
     async fn get_topup_user(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<TopupResponse>>, ErrorResponse> {
+        if !role.can_access(user_id, &[id]) {
+            error!("User {user_id} is not allowed to access topups of user {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to access topups of user {id}"
+            ))));
+        }
+
         let _user = self.user_repository.find_by_id(id).await.map_err(|_| {
             error!("User with id {id} not found");
             ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
@@ -173,12 +376,24 @@ impl TopupServiceTrait for TopupService {
     async fn create_topup(
         &self,
         input: &CreateTopupRequest,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<TopupResponse>, ErrorResponse> {
-        let _user = self
-            .user_repository
+        if !role.can_access(user_id, &[input.user_id]) {
+            error!(
+                "User {user_id} is not allowed to top up user {}",
+                input.user_id
+            );
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to top up user {}",
+                input.user_id
+            ))));
+        }
+
+        self.user_repository
             .find_by_id(input.user_id)
-            .await
-            .map_err(|_| {
+            .await?
+            .ok_or_else(|| {
                 error!("User with id {} not found", input.user_id);
                 ErrorResponse::from(AppError::NotFound(format!(
                     "User with id {} not found",
@@ -191,100 +406,167 @@ impl TopupServiceTrait for TopupService {
             input.user_id
         );
 
-        let topup = self.topup_repository.create(input).await?;
-
-        info!(
-            "Topup created for user with id {}: topup amount {}",
-            input.user_id, topup.topup_amount
-        );
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin topup transaction: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
 
-        match self.saldo_repository.find_by_user_id(input.user_id).await {
-            Ok(Some(current_saldo)) => {
-                let new_balance = current_saldo.total_balance + topup.topup_amount;
-                let request = UpdateSaldoBalance {
-                    user_id: input.user_id,
-                    total_balance: new_balance,
-                };
+        let topup = self.credit_tx(&mut tx, input).await?;
 
-                if let Err(db_err) = self.saldo_repository.update_balance(&request).await {
-                    error!(
-                        "Failed to update saldo balance for user {}: {}",
-                        input.user_id, db_err
-                    );
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit topup transaction: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
 
-                    if let Err(rb_err) = self.topup_repository.delete(topup.topup_id).await {
-                        error!(
-                            "Failed to rollback topup creation for user {}: {}",
-                            input.user_id, rb_err
-                        );
-                    }
+        info!(
+            "Topup successfully created for user {}. Total balance updated.",
+            input.user_id
+        );
+        let topup = TopupResponse::from(topup);
+        self.events.topup_created(&topup);
 
-                    return Err(db_err.into());
-                }
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Topup created successfully".to_string(),
+            data: topup,
+        })
+    }
 
-                info!(
-                    "Saldo updated successfully for user {}. New balance: {new_balance}",
-                    input.user_id,
-                );
-            }
-            Ok(None) => {
-                let create_saldo_request = CreateSaldoRequest {
-                    user_id: input.user_id,
-                    total_balance: topup.topup_amount,
-                };
+    async fn create_bulk_topup(
+        &self,
+        input: &CreateBulkTopupRequest,
+    ) -> Result<ApiResponse<Vec<BulkTopupResult>>, ErrorResponse> {
+        input
+            .validate()
+            .map_err(|e| ErrorResponse::from(AppError::Validation(e)))?;
+
+        if input.rows.len() > self.topup_limits.bulk_max_rows {
+            return Err(ErrorResponse::from(AppError::invalid_field(
+                "rows",
+                format!(
+                    "A bulk topup may contain at most {} rows, got {}",
+                    self.topup_limits.bulk_max_rows,
+                    input.rows.len()
+                ),
+            )));
+        }
 
-                if let Err(db_err) = self.saldo_repository.create(&create_saldo_request).await {
-                    error!(
-                        "Failed to create initial saldo for user {}: {}",
-                        input.user_id, db_err
-                    );
+        let user_ids: Vec<i32> = input
+            .rows
+            .iter()
+            .map(|row| row.user_id)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let existing: HashSet<i32> = self
+            .user_repository
+            .find_existing_ids(&user_ids)
+            .await?
+            .into_iter()
+            .collect();
 
-                    if let Err(rb_err) = self.topup_repository.delete(topup.topup_id).await {
-                        error!(
-                            "Failed to rollback topup creation for user {}: {rb_err}",
-                            input.user_id
-                        );
-                    }
+        // Shared prefix so every topup of one import can be traced back to it.
+        let batch = Uuid::new_v4().simple().to_string();
 
-                    return Err(db_err.into());
-                }
+        info!(
+            "Processing bulk topup {batch} with {} rows",
+            input.rows.len()
+        );
 
-                info!(
-                    "Initial saldo created for user {} with balance {}",
-                    input.user_id, topup.topup_amount
-                );
+        let mut results = Vec::with_capacity(input.rows.len());
+
+        for (index, row) in input.rows.iter().enumerate() {
+            if !existing.contains(&row.user_id) {
+                results.push(BulkTopupResult {
+                    user_id: row.user_id,
+                    status: BulkTopupStatus::Failed,
+                    topup_id: None,
+                    error: Some(format!("User with id {} not found", row.user_id)),
+                });
+                continue;
             }
-            Err(_) => {
-                error!("Failed to retrieve saldo for user {}", input.user_id);
 
-                if let Err(rb_err) = self.topup_repository.delete(topup.topup_id).await {
+            let request = CreateTopupRequest {
+                user_id: row.user_id,
+                topup_no: format!("BULK-{batch}-{}", index + 1),
+                topup_amount: row.amount,
+                topup_method: BULK_TOPUP_METHOD.to_string(),
+                currency: row.currency,
+            };
+
+            // Each row commits on its own so one failure doesn't undo the rest.
+            let outcome = async {
+                let mut tx = self.db_pool.begin().await.map_err(|e| {
+                    error!("Failed to begin bulk topup transaction: {e}");
+                    ErrorResponse::from(AppError::SqlxError(e))
+                })?;
+
+                let topup = self.credit_tx(&mut tx, &request).await?;
+
+                tx.commit().await.map_err(|e| {
+                    error!("Failed to commit bulk topup transaction: {e}");
+                    ErrorResponse::from(AppError::SqlxError(e))
+                })?;
+
+                Ok::<_, ErrorResponse>(topup)
+            }
+            .await;
+
+            match outcome {
+                Ok(topup) => {
+                    let topup = TopupResponse::from(topup);
+                    self.events.topup_created(&topup);
+
+                    results.push(BulkTopupResult {
+                        user_id: row.user_id,
+                        status: BulkTopupStatus::Success,
+                        topup_id: Some(topup.topup_id),
+                        error: None,
+                    });
+                }
+                Err(e) => {
                     error!(
-                        "Failed to rollback topup creation for user {}: {}",
-                        input.user_id, rb_err
+                        "Bulk topup {batch} row {} for user {} failed: {}",
+                        index + 1,
+                        row.user_id,
+                        e.message
                     );
-                }
 
-                return Err(ErrorResponse::from(AppError::NotFound(format!(
-                    "Saldo with user_id {} not found",
-                    input.user_id
-                ))));
+                    results.push(BulkTopupResult {
+                        user_id: row.user_id,
+                        status: BulkTopupStatus::Failed,
+                        topup_id: None,
+                        error: Some(e.message),
+                    });
+                }
             }
         }
 
+        let succeeded = results
+            .iter()
+            .filter(|result| result.status == BulkTopupStatus::Success)
+            .count();
+
         info!(
-            "Topup successfully created for user {}. Total balance updated.",
-            input.user_id
+            "Bulk topup {batch} finished: {succeeded} succeeded, {} failed",
+            results.len() - succeeded
         );
+
         Ok(ApiResponse {
             status: "success".to_string(),
-            message: "Topup created successfully".to_string(),
-            data: TopupResponse::from(topup),
+            message: format!(
+                "Bulk topup processed: {succeeded} succeeded, {} failed",
+                results.len() - succeeded
+            ),
+            data: results,
         })
     }
 
     async fn update_topup(
         &self,
         input: &UpdateTopupRequest,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<TopupResponse>>, ErrorResponse> {
         let _user = self
             .user_repository
@@ -313,121 +595,290 @@ impl TopupServiceTrait for TopupService {
             )))
         })?;
 
-        let topup_difference = input.topup_amount - existing_topup.topup_amount;
+        if !role.can_access(user_id, &[existing_topup.user_id]) {
+            error!(
+                "User {user_id} is not allowed to update topup {}",
+                input.topup_id
+            );
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to update topup {}",
+                input.topup_id
+            ))));
+        }
+
+        // The difference lands on the owner's saldo, so a body naming some
+        // other user must not get that far.
+        if existing_topup.user_id != input.user_id {
+            error!(
+                "Topup {} belongs to user {}, not {}",
+                input.topup_id, existing_topup.user_id, input.user_id
+            );
+            return Err(ErrorResponse::from(AppError::NotFound(format!(
+                "Topup with id {} not found for user {}",
+                input.topup_id, input.user_id
+            ))));
+        }
+
+        // Only the new amount is checked; the old one may predate a change
+        // to the limits and the difference below is applied either way.
+        self.topup_limits.ensure_amount(input.topup_amount)?;
+
+        let topup_difference = input
+            .topup_amount
+            .checked_sub(existing_topup.topup_amount)?;
 
         info!(
             "Calculating topup difference: new amount {} - old amount {} = difference {topup_difference}",
             input.topup_amount, existing_topup.topup_amount,
         );
 
-        let update_topup = UpdateTopupAmount {
-            topup_id: input.topup_id,
-            topup_amount: input.topup_amount,
-        };
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!(
+                "Failed to begin update transaction for topup {}: {e}",
+                input.topup_id
+            );
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
 
-        self.topup_repository.update_amount(&update_topup).await?;
+        // Locked here so the freeze check holds until the difference lands.
+        self.saldo_repository
+            .find_by_user_and_currency_for_update(
+                &mut tx,
+                existing_topup.user_id,
+                existing_topup.currency,
+            )
+            .await?
+            .ok_or_else(|| {
+                error!("Saldo not found for user_id={}", existing_topup.user_id);
+                ErrorResponse::from(AppError::NotFound(format!(
+                    "Saldo with User id {} not found",
+                    existing_topup.user_id
+                )))
+            })?
+            .ensure_not_frozen()
+            .inspect_err(|_| {
+                error!(
+                    "Topup update rejected: saldo of user {} is frozen",
+                    existing_topup.user_id
+                );
+            })?;
 
-        match self.saldo_repository.find_by_user_id(input.user_id).await {
-            Ok(Some(current_saldo)) => {
-                let new_balance = current_saldo.total_balance + topup_difference;
+        let updated_topup = self
+            .topup_repository
+            .update_amount_tx(
+                &mut tx,
+                &UpdateTopupAmount {
+                    topup_id: input.topup_id,
+                    topup_amount: input.topup_amount,
+                },
+            )
+            .await?;
 
-                info!(
-                    "Updating saldo: current balance {} + topup difference {topup_difference} = new balance {new_balance}",
-                    current_saldo.total_balance,
-                );
+        // A smaller amount takes money back, which fails here if the user
+        // has already spent it; the amount update is rolled back with it.
+        if topup_difference != Money::ZERO {
+            self.adjust_balance_tx(&mut tx, &existing_topup, topup_difference)
+                .await?;
+        }
 
-                let request = UpdateSaldoBalance {
-                    user_id: input.user_id,
-                    total_balance: new_balance,
-                };
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit update of topup {}: {e}", input.topup_id);
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
 
-                if let Err(db_err) = self.saldo_repository.update_balance(&request).await {
-                    error!(
-                        "Failed to update saldo balance for user {}: {db_err}",
-                        input.user_id,
-                    );
+        info!(
+            "Topup {} updated for user {}: {} -> {}",
+            input.topup_id, input.user_id, existing_topup.topup_amount, updated_topup.topup_amount
+        );
 
-                    let rollback = UpdateTopupAmount {
-                        topup_id: existing_topup.topup_id,
-                        topup_amount: existing_topup.topup_amount,
-                    };
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Topup updated successfully".to_string(),
+            data: Some(TopupResponse::from(updated_topup)),
+        })
+    }
 
-                    if let Err(rb_err) = self.topup_repository.update_amount(&rollback).await {
-                        error!(
-                            "Failed to rollback topup update for user {}: {}",
-                            input.user_id, rb_err
-                        );
-                    }
+    async fn delete_topup(
+        &self,
+        id: i32,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin delete transaction for topup {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
 
-                    return Err(db_err.into());
-                }
+        let topup = self.topup_repository.delete_tx(&mut tx, id).await?;
 
-                info!(
-                    "Saldo updated successfully for user {}. New balance: {}",
-                    input.user_id, new_balance
-                );
-            }
-            Ok(None) => {
-                error!("No saldo found for user {} to update", input.user_id);
-                return Err(ErrorResponse::from(AppError::NotFound(format!(
-                    "Saldo for user {} not found",
-                    input.user_id
-                ))));
-            }
-            Err(e) => {
-                error!("Failed to retrieve saldo for user {}: {}", input.user_id, e);
-                return Err(e.into());
-            }
+        // Returning drops the transaction, which rolls the delete back.
+        if !role.can_access(user_id, &[topup.user_id]) {
+            error!("User {user_id} is not allowed to delete topup {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to delete topup {id}"
+            ))));
         }
 
-        let updated_topup = self.topup_repository.find_by_id(input.topup_id).await?;
+        self.adjust_balance_tx(
+            &mut tx,
+            &topup,
+            Money::new(-topup.topup_amount.minor_units()),
+        )
+        .await?;
 
-        match updated_topup {
-            Some(topup) => Ok(ApiResponse {
-                status: "success".to_string(),
-                message: "Topup updated successfully".to_string(),
-                data: Some(TopupResponse::from(topup)),
-            }),
-            None => {
-                error!("Topup with id {} not found", input.topup_id);
-                Err(ErrorResponse::from(AppError::NotFound(format!(
-                    "Topup with id {} not found",
-                    input.topup_id
-                ))))
-            }
-        }
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit delete of topup {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        info!("Topup deleted successfully for id: {id}");
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Topup deleted successfully".to_string(),
+            data: (),
+        })
     }
 
-    async fn delete_topup(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
-        let user = self.user_repository.find_by_id(id).await.map_err(|_| {
-            ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
+    async fn restore_topup(&self, id: i32) -> Result<ApiResponse<TopupResponse>, ErrorResponse> {
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin restore transaction for topup {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
         })?;
 
-        let existing_topup = self
-            .topup_repository
-            .find_by_user(user.unwrap().user_id)
+        let topup = self.topup_repository.restore_tx(&mut tx, id).await?;
+
+        self.adjust_balance_tx(&mut tx, &topup, topup.topup_amount)
             .await?;
 
-        match existing_topup {
-            Some(_) => {
-                self.topup_repository
-                    .delete(existing_topup.unwrap().topup_id)
-                    .await?;
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit restore of topup {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
 
-                info!("Topup deleted successfully for id: {id}");
+        info!("Topup restored successfully for id: {id}");
 
-                Ok(ApiResponse {
-                    status: "success".to_string(),
-                    message: "Topup deleted successfully".to_string(),
-                    data: (),
-                })
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Topup restored successfully".to_string(),
+            data: TopupResponse::from(topup),
+        })
+    }
+
+    async fn get_monthly_stats(
+        &self,
+        req: &TopupMonthlyStatsRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<Vec<TopupMonthlyStatsResponse>>, ErrorResponse> {
+        req.validate()
+            .map_err(|e| ErrorResponse::from(AppError::Validation(e)))?;
+
+        match req.user_id {
+            Some(id) if !role.can_access(user_id, &[id]) => {
+                error!("User {user_id} is not allowed to view topup stats of user {id}");
+                return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                    "You are not allowed to view topup stats of user {id}"
+                ))));
             }
-            None => {
-                error!("Topup with id {id} not found");
-                Err(ErrorResponse::from(AppError::NotFound(format!(
-                    "Topup with id {id} not found",
-                ))))
+            None if role != Role::Admin => {
+                error!("User {user_id} asked for topup stats across all users");
+                return Err(ErrorResponse::from(AppError::Forbidden(
+                    "Only admins can view topup stats across all users; pass user_id".to_string(),
+                )));
             }
+            _ => {}
+        }
+
+        let to = req.to.unwrap_or_else(Utc::now);
+        let from = req.from.unwrap_or_else(|| {
+            month_start(to)
+                .checked_sub_months(Months::new(DEFAULT_STATS_MONTHS - 1))
+                .and_then(|month| month.and_hms_opt(0, 0, 0))
+                .map(|month| month.and_utc())
+                .unwrap_or(to)
+        });
+
+        let months = months_between(from, to);
+        if months.len() > MAX_STATS_MONTHS {
+            return Err(ErrorResponse::from(AppError::validation(format!(
+                "Topup stats can span at most {MAX_STATS_MONTHS} months"
+            ))));
         }
+
+        let currency = req.currency.unwrap_or_else(Currency::base);
+
+        let buckets: HashMap<NaiveDate, _> = self
+            .topup_repository
+            .monthly_stats(from.naive_utc(), to.naive_utc(), req.user_id, currency)
+            .await?
+            .into_iter()
+            .map(|row| (row.month.date(), row))
+            .collect();
+
+        info!(
+            "Computed {} month(s) of {currency} topup stats, {} with activity",
+            months.len(),
+            buckets.len()
+        );
+
+        // Months without topups still get a zero row so charts don't skip them.
+        let data = months
+            .into_iter()
+            .map(|month| {
+                let bucket = buckets.get(&month);
+
+                TopupMonthlyStatsResponse {
+                    month,
+                    currency,
+                    count: bucket.map_or(0, |row| row.count),
+                    total_amount: bucket.map_or(Money::ZERO, |row| row.total_amount),
+                }
+            })
+            .collect();
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Monthly topup stats retrieved successfully".to_string(),
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, s).unwrap()
+    }
+
+    fn date(y: i32, m: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, 1).unwrap()
+    }
+
+    #[test]
+    fn months_cover_both_ends_of_the_range() {
+        assert_eq!(
+            months_between(at(2024, 12, 31, 23, 59, 59), at(2025, 2, 1, 0, 0, 0)),
+            [date(2024, 12), date(2025, 1), date(2025, 2)]
+        );
+    }
+
+    #[test]
+    fn a_range_inside_one_month_is_one_bucket() {
+        assert_eq!(
+            months_between(at(2025, 3, 2, 0, 0, 0), at(2025, 3, 30, 0, 0, 0)),
+            [date(2025, 3)]
+        );
+    }
+
+    #[test]
+    fn oversized_ranges_stop_just_past_the_cap() {
+        let months = months_between(at(2000, 1, 1, 0, 0, 0), at(2025, 1, 1, 0, 0, 0));
+
+        assert_eq!(months.len(), MAX_STATS_MONTHS + 1);
     }
 }
@@ -1,40 +1,446 @@
 use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::PgConnection;
+use std::collections::HashMap;
 use tracing::{error, info};
+use validator::Validate;
 
 use crate::{
     abstract_trait::{
         DynSaldoRepository, DynTransferRepository, DynUserRepository, TransferServiceTrait,
     },
+    config::ConnectionPool,
     domain::{
+        balance_policy::BalancePolicy,
+        currency::Currency,
+        fees::FeeSchedule,
+        money::Money,
         request::{
-            CreateTransferRequest, FindAllTransferRequest, UpdateSaldoBalance,
-            UpdateTransferRequest,
+            BalanceChangeReason, CreateBatchTransferRequest, CreateTransferRequest, ExtraValidate,
+            FindAllTransferRequest, FindTransfersBetweenRequest, FindUserTransfersRequest, Sort,
+            TransferAllRequest, TransferNetFlowRequest, UpdateSaldoBalance, UpdateTransferRequest,
+            normalize_page, normalize_pagination,
         },
         response::{
-            ApiResponse, ApiResponsePagination, ErrorResponse, pagination::Pagination,
-            transfer::TransferResponse,
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            pagination::Pagination,
+            transfer::{NetFlowPeriod, NetFlowResponse, TransferReceiptResponse, TransferResponse},
         },
+        role::Role,
+        transfer_status::TransferStatus,
     },
-    utils::AppError,
+    model::{saldo::Saldo, transfer::Transfer},
+    service::events::TransactionEvents,
+    utils::{AppError, mask_noc},
 };
 
+const SCHEDULED_BATCH_SIZE: u64 = 100;
+
+// How strictly `move_balance_tx` checks the payer's resulting balance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DebitCheck {
+    // Money moving back or again; it only has to be there.
+    NonNegative,
+    // A reversal the policy allows to overdraw the receiver.
+    Unchecked,
+}
+
 pub struct TransferService {
+    db_pool: ConnectionPool,
     transfer_repository: DynTransferRepository,
     saldo_repository: DynSaldoRepository,
     user_repository: DynUserRepository,
+    events: TransactionEvents,
+    balance_policy: BalancePolicy,
+    fee_schedule: FeeSchedule,
+    max_page_size: i32,
 }
 
 impl TransferService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
+        db_pool: ConnectionPool,
         transfer_repository: DynTransferRepository,
         saldo_repository: DynSaldoRepository,
         user_repository: DynUserRepository,
+        events: TransactionEvents,
+        balance_policy: BalancePolicy,
+        fee_schedule: FeeSchedule,
+        max_page_size: i32,
     ) -> Self {
         Self {
+            db_pool,
             transfer_repository,
             saldo_repository,
             user_repository,
+            events,
+            balance_policy,
+            fee_schedule,
+            max_page_size,
+        }
+    }
+
+    // Locks the `currency` saldo rows of every given user in ascending user_id
+    // order, so concurrent transfers touching the same users can't deadlock
+    // each other. A user without a saldo in that currency can't take part:
+    // transfers never convert between currencies.
+    async fn lock_saldos(
+        &self,
+        conn: &mut PgConnection,
+        user_ids: &[i32],
+        currency: Currency,
+    ) -> Result<HashMap<i32, Saldo>, ErrorResponse> {
+        let mut ordered = user_ids.to_vec();
+        ordered.sort_unstable();
+        ordered.dedup();
+
+        let mut saldos = HashMap::with_capacity(ordered.len());
+
+        for user_id in ordered {
+            let saldo = self
+                .saldo_repository
+                .find_by_user_and_currency_for_update(conn, user_id, currency)
+                .await?
+                .ok_or_else(|| {
+                    error!("No {currency} saldo for user_id={user_id}");
+                    ErrorResponse::from(AppError::Custom(format!(
+                        "User id {user_id} has no {currency} saldo; cross-currency transfers are not supported"
+                    )))
+                })?;
+
+            saldos.insert(user_id, saldo);
+        }
+
+        Ok(saldos)
+    }
+
+    // Debits `payer` and credits `payee` by the transfer amount on locked
+    // saldo rows, moving the sender's fee along with it: undoing a transfer
+    // refunds the fee and re-applying it charges the fee again. Used on
+    // delete, reversal and restore; these are admin corrections, so the
+    // freeze and minimum balance rules don't apply.
+    async fn move_balance_tx(
+        &self,
+        conn: &mut PgConnection,
+        payer: i32,
+        payee: i32,
+        transfer: &Transfer,
+        check: DebitCheck,
+    ) -> Result<(), ErrorResponse> {
+        let amount = transfer.transfer_amount;
+        let transfer_id = transfer.transfer_id;
+        let saldos = self
+            .lock_saldos(conn, &[payer, payee], transfer.currency)
+            .await?;
+
+        let payer_amount = if payer == transfer.transfer_from {
+            amount.checked_add(transfer.fee)?
+        } else {
+            amount
+        };
+        let payee_amount = if payee == transfer.transfer_from {
+            amount.checked_add(transfer.fee)?
+        } else {
+            amount
+        };
+
+        let new_payer_balance = saldos[&payer].total_balance.checked_sub(payer_amount)?;
+
+        if check != DebitCheck::Unchecked && new_payer_balance.is_negative() {
+            let error_msg = format!(
+                "Insufficient balance to move transfer {transfer_id}: user_id={payer}, current={}, amount={payer_amount}",
+                saldos[&payer].total_balance
+            );
+            error!("{}", error_msg);
+            return Err(ErrorResponse::from(AppError::InsufficientBalance {
+                required: payer_amount,
+                available: saldos[&payer].total_balance,
+            }));
+        }
+
+        let new_payee_balance = saldos[&payee].total_balance.checked_add(payee_amount)?;
+
+        for (user_id, total_balance) in [(payer, new_payer_balance), (payee, new_payee_balance)] {
+            self.saldo_repository
+                .update_balance_tx(
+                    conn,
+                    &UpdateSaldoBalance {
+                        user_id,
+                        currency: transfer.currency,
+                        total_balance,
+                        reason: BalanceChangeReason::Transfer,
+                        reference_id: Some(transfer_id),
+                    },
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    // The sender pays the fee on top; the receiver gets the full amount.
+    // Returns the fee and the sender's balance once both are paid, refusing
+    // a debit the balance or the minimum balance policy doesn't allow.
+    fn debit_with_fee(
+        &self,
+        sender_saldo: &Saldo,
+        transfer_amount: Money,
+    ) -> Result<(Money, Money), ErrorResponse> {
+        let fee = self.fee_schedule.fee_for(transfer_amount)?;
+        let total_debit = transfer_amount.checked_add(fee)?;
+
+        let new_sender_balance = sender_saldo.total_balance.checked_sub(total_debit)?;
+
+        if new_sender_balance.is_negative() {
+            let error_msg = format!(
+                "Insufficient balance: user_id={}, current={}, transfer={transfer_amount}, fee={fee}",
+                sender_saldo.user_id, sender_saldo.total_balance
+            );
+            error!("{}", error_msg);
+            return Err(ErrorResponse::from(AppError::InsufficientBalance {
+                required: total_debit,
+                available: sender_saldo.total_balance,
+            }));
+        }
+
+        self.balance_policy
+            .ensure_minimum(sender_saldo.user_id, new_sender_balance)?;
+
+        Ok((fee, new_sender_balance))
+    }
+
+    // Records `fee` on the transfer row and writes the new sender and
+    // receiver balances, all against `transfer` as the reference.
+    async fn book_tx(
+        &self,
+        conn: &mut PgConnection,
+        mut transfer: Transfer,
+        fee: Money,
+        new_sender_balance: Money,
+        new_receiver_balance: Money,
+    ) -> Result<Transfer, ErrorResponse> {
+        if fee != transfer.fee {
+            transfer = self
+                .transfer_repository
+                .charge_fee_tx(conn, &transfer, fee)
+                .await
+                .inspect_err(|e| {
+                    error!(
+                        "Failed to charge fee for transfer {}: {e}",
+                        transfer.transfer_id
+                    );
+                })?;
+        }
+
+        for (user_id, total_balance) in [
+            (transfer.transfer_from, new_sender_balance),
+            (transfer.transfer_to, new_receiver_balance),
+        ] {
+            self.saldo_repository
+                .update_balance_tx(
+                    conn,
+                    &UpdateSaldoBalance {
+                        user_id,
+                        currency: transfer.currency,
+                        total_balance,
+                        reason: BalanceChangeReason::Transfer,
+                        reference_id: Some(transfer.transfer_id),
+                    },
+                )
+                .await
+                .map_err(|e| {
+                    error!("Failed to update balance for user_id={user_id}: {e}");
+                    e
+                })?;
+        }
+
+        Ok(transfer)
+    }
+
+    // Creates the transfer row and moves `input.transfer_amount` (plus the
+    // fee, paid by the sender) between the two locked saldos. Returns the
+    // transfer with the new sender and receiver balances.
+    async fn settle_tx(
+        &self,
+        conn: &mut PgConnection,
+        input: &CreateTransferRequest,
+        sender_saldo: &Saldo,
+        receiver_saldo: &Saldo,
+    ) -> Result<(Transfer, Money, Money), ErrorResponse> {
+        let (fee, new_sender_balance) = self.debit_with_fee(sender_saldo, input.transfer_amount)?;
+
+        let new_receiver_balance = receiver_saldo
+            .total_balance
+            .checked_add(input.transfer_amount)?;
+
+        let transfer = self
+            .transfer_repository
+            .create_tx(conn, input)
+            .await
+            .map_err(|e| {
+                error!(
+                    "Failed to create transfer in repository: from={}, to={}, amount={}. Error: {:?}",
+                    input.transfer_from, input.transfer_to, input.transfer_amount, e
+                );
+                e
+            })?;
+
+        let transfer = self
+            .book_tx(
+                conn,
+                transfer,
+                fee,
+                new_sender_balance,
+                new_receiver_balance,
+            )
+            .await?;
+
+        Ok((transfer, new_sender_balance, new_receiver_balance))
+    }
+
+    // Settles a claimed scheduled transfer exactly like an immediate one:
+    // frozen saldos are refused and the sender pays the current fee.
+    async fn settle_scheduled_tx(
+        &self,
+        conn: &mut PgConnection,
+        transfer: Transfer,
+    ) -> Result<Transfer, ErrorResponse> {
+        let saldos = self
+            .lock_saldos(
+                conn,
+                &[transfer.transfer_from, transfer.transfer_to],
+                transfer.currency,
+            )
+            .await?;
+
+        for saldo in saldos.values() {
+            saldo.ensure_not_frozen()?;
+        }
+
+        let (fee, new_sender_balance) =
+            self.debit_with_fee(&saldos[&transfer.transfer_from], transfer.transfer_amount)?;
+
+        let new_receiver_balance = saldos[&transfer.transfer_to]
+            .total_balance
+            .checked_add(transfer.transfer_amount)?;
+
+        self.book_tx(
+            conn,
+            transfer,
+            fee,
+            new_sender_balance,
+            new_receiver_balance,
+        )
+        .await
+    }
+
+    // Persists a future-dated transfer as pending without touching balances.
+    // Funds are only checked when the scheduler executes it.
+    async fn schedule_transfer(
+        &self,
+        input: &CreateTransferRequest,
+        scheduled_at: chrono::DateTime<Utc>,
+    ) -> Result<ApiResponse<TransferResponse>, ErrorResponse> {
+        for user_id in [input.transfer_from, input.transfer_to] {
+            self.saldo_repository
+                .find_by_user_and_currency(user_id, input.currency)
+                .await?
+                .ok_or_else(|| {
+                    error!("No {} saldo for user_id={user_id}", input.currency);
+                    ErrorResponse::from(AppError::Custom(format!(
+                        "User id {user_id} has no {} saldo; cross-currency transfers are not supported",
+                        input.currency
+                    )))
+                })?
+                .ensure_not_frozen()?;
+        }
+
+        let transfer = self
+            .transfer_repository
+            .create_scheduled(input, scheduled_at.naive_utc())
+            .await?;
+
+        info!(
+            "Transfer scheduled: transfer_id={}, from={}, to={}, amount={}, at={scheduled_at}",
+            transfer.transfer_id, input.transfer_from, input.transfer_to, input.transfer_amount
+        );
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Transfer scheduled successfully".to_string(),
+            data: TransferResponse::from(transfer),
+        })
+    }
+
+    // Claims and settles one due transfer in its own transaction. Client
+    // errors (insufficient funds, minimum balance, frozen or missing saldo)
+    // mark it failed for good; anything else rolls back so the next tick
+    // retries.
+    async fn execute_scheduled(&self, id: i32) -> Result<bool, ErrorResponse> {
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin transaction for scheduled transfer {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        let Some(transfer) = self
+            .transfer_repository
+            .claim_pending_tx(&mut tx, id)
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        if let Err(e) = self.settle_scheduled_tx(&mut tx, transfer).await {
+            if !e.code.is_client_error() {
+                return Err(e);
+            }
+
+            error!("Scheduled transfer {id} failed: {}", e.message);
+
+            // The saldo updates went through the same transaction; start a
+            // clean one so only the status change is persisted.
+            drop(tx);
+            let mut tx = self.db_pool.begin().await.map_err(|e| {
+                error!("Failed to begin transaction for scheduled transfer {id}: {e}");
+                ErrorResponse::from(AppError::SqlxError(e))
+            })?;
+
+            if self
+                .transfer_repository
+                .claim_pending_tx(&mut tx, id)
+                .await?
+                .is_some()
+            {
+                self.transfer_repository
+                    .set_status_tx(&mut tx, id, TransferStatus::Failed)
+                    .await?;
+            }
+
+            tx.commit().await.map_err(|e| {
+                error!("Failed to commit failure of scheduled transfer {id}: {e}");
+                ErrorResponse::from(AppError::SqlxError(e))
+            })?;
+
+            return Ok(false);
         }
+
+        let transfer = self
+            .transfer_repository
+            .set_status_tx(&mut tx, id, TransferStatus::Completed)
+            .await?;
+
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit scheduled transfer {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        let transfer = TransferResponse::from(transfer);
+        self.events
+            .transfers_created(std::slice::from_ref(&transfer));
+
+        info!("Scheduled transfer executed: transfer_id={id}");
+
+        Ok(true)
     }
 }
 
@@ -44,23 +450,24 @@ impl TransferServiceTrait for TransferService {
         &self,
         req: &FindAllTransferRequest,
     ) -> Result<ApiResponsePagination<Vec<TransferResponse>>, ErrorResponse> {
-        let page = if req.page > 0 { req.page } else { 1 };
-        let page_size = if req.page_size > 0 { req.page_size } else { 10 };
-        let search = if req.search.is_empty() {
-            None
-        } else {
-            Some(req.search.clone())
-        };
+        req.validate()
+            .map_err(|e| ErrorResponse::from(AppError::Validation(e)))?;
+
+        let (page, page_size, search) = normalize_pagination(req, self.max_page_size);
 
         let (transfers, total_items) = self
             .transfer_repository
-            .find_all(page, page_size, search)
+            .find_all(
+                page,
+                page_size,
+                search,
+                Sort::new(req.sort_by.clone(), req.order),
+                req.filter(),
+            )
             .await?;
 
         info!("Found {} transfers", transfers.len());
 
-        let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
-
         let transfer_responses: Vec<TransferResponse> =
             transfers.into_iter().map(TransferResponse::from).collect();
 
@@ -68,22 +475,26 @@ impl TransferServiceTrait for TransferService {
             status: "success".to_string(),
             message: "Transfers retrieved successfully".to_string(),
             data: transfer_responses,
-            pagination: Pagination {
-                page,
-                page_size,
-                total_items,
-                total_pages,
-            },
+            pagination: Pagination::new(page, page_size, total_items),
         })
     }
 
     async fn get_transfer(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<TransferResponse>>, ErrorResponse> {
         let transfer = self.transfer_repository.find_by_id(id).await?;
 
         if let Some(transfer) = transfer {
+            if !role.can_access(user_id, &[transfer.transfer_from, transfer.transfer_to]) {
+                error!("User {user_id} is not allowed to access transfer {id}");
+                return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                    "You are not allowed to access transfer {id}"
+                ))));
+            }
+
             Ok(ApiResponse {
                 status: "success".to_string(),
                 message: "Transfer retrieved successfully".to_string(),
@@ -96,38 +507,195 @@ impl TransferServiceTrait for TransferService {
         }
     }
 
+    async fn get_transfer_receipt(
+        &self,
+        id: i32,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<TransferReceiptResponse>, ErrorResponse> {
+        let receipt = self
+            .transfer_repository
+            .find_receipt(id)
+            .await?
+            .ok_or_else(|| {
+                ErrorResponse::from(AppError::NotFound(format!(
+                    "Transfer with id {id} not found",
+                )))
+            })?;
+
+        if !role.can_access(user_id, &[receipt.transfer_from, receipt.transfer_to]) {
+            error!("User {user_id} is not allowed to access receipt for transfer {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to access transfer {id}"
+            ))));
+        }
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Transfer receipt retrieved successfully".to_string(),
+            data: TransferReceiptResponse::from(receipt),
+        })
+    }
+
+    async fn get_transfers_between(
+        &self,
+        req: &FindTransfersBetweenRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponsePagination<Vec<TransferResponse>>, ErrorResponse> {
+        if !role.can_access(user_id, &[req.from, req.to]) {
+            error!(
+                "User {user_id} is not allowed to access transfers between {} and {}",
+                req.from, req.to
+            );
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to access transfers between users {} and {}",
+                req.from, req.to
+            ))));
+        }
+
+        for id in [req.from, req.to] {
+            self.user_repository.find_by_id(id).await?.ok_or_else(|| {
+                error!("User with id {id} not found");
+                ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
+            })?;
+        }
+
+        let (page, page_size) = normalize_page(req.page, req.page_size, self.max_page_size);
+
+        let (transfers, total_items) = self
+            .transfer_repository
+            .find_between_users(req.from, req.to, page, page_size)
+            .await?;
+
+        info!(
+            "Found {} transfers from user {} to user {}",
+            transfers.len(),
+            req.from,
+            req.to
+        );
+
+        Ok(ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Transfers retrieved successfully".to_string(),
+            data: transfers.into_iter().map(TransferResponse::from).collect(),
+            pagination: Pagination::new(page, page_size, total_items),
+        })
+    }
+
     async fn get_transfer_users(
         &self,
         id: i32,
-    ) -> Result<ApiResponse<Option<Vec<TransferResponse>>>, ErrorResponse> {
-        let _user = self.user_repository.find_by_id(id).await.map_err(|_| {
+        req: &FindUserTransfersRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponsePagination<Vec<TransferResponse>>, ErrorResponse> {
+        if !role.can_access(user_id, &[id]) {
+            error!("User {user_id} is not allowed to access transfers of user {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to access transfers of user {id}"
+            ))));
+        }
+
+        self.user_repository.find_by_id(id).await?.ok_or_else(|| {
+            error!("User with id {id} not found");
             ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
         })?;
 
-        let transfer = self.transfer_repository.find_by_users(id).await?;
+        let (page, page_size) = normalize_page(req.page, req.page_size, self.max_page_size);
 
-        let transfer_response = if transfer.is_empty() {
-            None
-        } else {
-            Some(transfer.into_iter().map(TransferResponse::from).collect())
-        };
+        let (transfers, total_items) = self
+            .transfer_repository
+            .find_by_users(id, req.direction, page, page_size)
+            .await?;
 
-        let message = match &transfer_response {
-            Some(_) => "Success".to_string(),
-            None => format!("No transfer found for user with id {id}"),
-        };
+        info!(
+            "Found {} {:?} transfers for user {id}",
+            transfers.len(),
+            req.direction
+        );
+
+        Ok(ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Transfers retrieved successfully".to_string(),
+            data: transfers.into_iter().map(TransferResponse::from).collect(),
+            pagination: Pagination::new(page, page_size, total_items),
+        })
+    }
+
+    async fn get_net_flow(
+        &self,
+        id: i32,
+        req: &TransferNetFlowRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<NetFlowResponse>, ErrorResponse> {
+        req.validate()
+            .map_err(|e| ErrorResponse::from(AppError::Validation(e)))?;
+
+        if !role.can_access(user_id, &[id]) {
+            error!("User {user_id} is not allowed to view the net flow of user {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to view the net flow of user {id}"
+            ))));
+        }
+
+        for id in std::iter::once(id).chain(req.counterparty) {
+            self.user_repository.find_by_id(id).await?.ok_or_else(|| {
+                error!("User with id {id} not found");
+                ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
+            })?;
+        }
+
+        let to = req.to.unwrap_or_else(Utc::now);
+        let currency = req.currency.unwrap_or_else(Currency::base);
+
+        let totals = self
+            .transfer_repository
+            .net_flow(
+                id,
+                req.counterparty,
+                req.from.map(|from| from.naive_utc()),
+                to.naive_utc(),
+                currency,
+            )
+            .await?;
+
+        let net = totals.incoming.checked_sub(totals.outgoing)?;
+
+        info!(
+            "Computed {currency} net flow for user {id}: incoming={}, outgoing={}, net={net}",
+            totals.incoming, totals.outgoing
+        );
 
         Ok(ApiResponse {
             status: "success".to_string(),
-            message,
-            data: transfer_response,
+            message: "Net flow retrieved successfully".to_string(),
+            data: NetFlowResponse {
+                user_id: id,
+                counterparty: req.counterparty,
+                currency,
+                incoming: totals.incoming,
+                outgoing: totals.outgoing,
+                net,
+                period: NetFlowPeriod { from: req.from, to },
+            },
         })
     }
 
     async fn get_transfer_user(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<TransferResponse>>, ErrorResponse> {
+        if !role.can_access(user_id, &[id]) {
+            error!("User {user_id} is not allowed to access transfers of user {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to access transfers of user {id}"
+            ))));
+        }
+
         let _user = self.user_repository.find_by_id(id).await.map_err(|_| {
             ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
         })?;
@@ -150,14 +718,63 @@ impl TransferServiceTrait for TransferService {
     async fn create_transfer(
         &self,
         input: &CreateTransferRequest,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<TransferResponse>, ErrorResponse> {
+        if !role.can_access(user_id, &[input.transfer_from]) {
+            error!(
+                "User {user_id} is not allowed to transfer from user {}",
+                input.transfer_from
+            );
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to transfer from user {}",
+                input.transfer_from
+            ))));
+        }
+
+        let resolved;
+        let input = match &input.transfer_to_noc {
+            Some(noc) => {
+                let receiver = self
+                    .user_repository
+                    .find_by_noc(noc)
+                    .await?
+                    .ok_or_else(|| {
+                        error!("No receiver with noc_transfer {}", mask_noc(noc));
+                        ErrorResponse::from(AppError::NotFound(
+                            "User receiver with this noc_transfer not found".to_string(),
+                        ))
+                    })?;
+
+                resolved = CreateTransferRequest {
+                    transfer_to: receiver.user_id,
+                    transfer_to_noc: None,
+                    ..input.clone()
+                };
+                &resolved
+            }
+            None => input,
+        };
+
         info!(
             "Creating transfer request: from_user_id={}, to_user_id={}, amount={}",
             input.transfer_from, input.transfer_to, input.transfer_amount
         );
 
-        let _sender_user = self
-            .user_repository
+        input.extra_validate().map_err(|e| {
+            error!(
+                "Rejected transfer from user_id={}: {e}",
+                input.transfer_from
+            );
+            ErrorResponse::from(AppError::Custom(e))
+        })?;
+
+        self.balance_policy
+            .ensure_transfer_limit(input.transfer_amount)?;
+
+        let scheduled_at = input.scheduled_at.filter(|at| *at > Utc::now());
+
+        self.user_repository
             .find_by_id(input.transfer_from)
             .await
             .map_err(|e| {
@@ -167,11 +784,15 @@ impl TransferServiceTrait for TransferService {
                 );
                 error!("{}", error_msg);
                 ErrorResponse::from(AppError::NotFound(error_msg))
+            })?
+            .ok_or_else(|| {
+                let error_msg = format!("User sender with id {} not found", input.transfer_from);
+                error!("{}", error_msg);
+                ErrorResponse::from(AppError::NotFound(error_msg))
             })?;
         info!("Sender user validated: id={}", input.transfer_from);
 
-        let _receiver_user = self
-            .user_repository
+        self.user_repository
             .find_by_id(input.transfer_to)
             .await
             .map_err(|e| {
@@ -181,169 +802,309 @@ impl TransferServiceTrait for TransferService {
                 );
                 error!("{}", error_msg);
                 ErrorResponse::from(AppError::NotFound(error_msg))
+            })?
+            .ok_or_else(|| {
+                let error_msg = format!("User receiver with id {} not found", input.transfer_to);
+                error!("{}", error_msg);
+                ErrorResponse::from(AppError::NotFound(error_msg))
             })?;
         info!("Receiver user validated: id={}", input.transfer_to);
 
-        // Buat entri transfer di database
-        let transfer = self.transfer_repository.create(input).await.map_err(|e| {
+        if let Some(scheduled_at) = scheduled_at {
+            return self.schedule_transfer(input, scheduled_at).await;
+        }
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin transfer transaction: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        let saldos = self
+            .lock_saldos(
+                &mut tx,
+                &[input.transfer_from, input.transfer_to],
+                input.currency,
+            )
+            .await?;
+        let sender_saldo = &saldos[&input.transfer_from];
+        let receiver_saldo = &saldos[&input.transfer_to];
+
+        for saldo in [sender_saldo, receiver_saldo] {
+            saldo.ensure_not_frozen().inspect_err(|_| {
+                error!(
+                    "Transfer rejected: saldo of user {} is frozen",
+                    saldo.user_id
+                );
+            })?;
+        }
+
+        let (transfer, new_sender_balance, new_receiver_balance) = self
+            .settle_tx(&mut tx, input, sender_saldo, receiver_saldo)
+            .await?;
+
+        tx.commit().await.map_err(|e| {
             error!(
-                "Failed to create transfer in repository: from={}, to={}, amount={}. Error: {:?}",
-                input.transfer_from, input.transfer_to, input.transfer_amount, e
+                "Failed to commit transfer transaction: transfer_id={}, error={e}",
+                transfer.transfer_id
             );
-            e
+            ErrorResponse::from(AppError::SqlxError(e))
         })?;
+
+        let transfer = TransferResponse::from(transfer);
+        self.events
+            .transfers_created(std::slice::from_ref(&transfer));
+
         info!(
-            "Transfer record created successfully: transfer_id={}",
-            transfer.transfer_id
+            "Transfer completed successfully: transfer_id={}, from={}, to={}, amount={}, sender_balance={}, receiver_balance={}",
+            transfer.transfer_id,
+            input.transfer_from,
+            input.transfer_to,
+            input.transfer_amount,
+            new_sender_balance,
+            new_receiver_balance
         );
 
-        // Ambil saldo pengirim
-        let sender_saldo = self
-            .saldo_repository
-            .find_by_user_id(input.transfer_from)
-            .await
-            .map_err(|e| {
-                let error_msg = format!(
-                    "Saldo not found for sender user_id={} error={e}",
-                    input.transfer_from
-                );
-                error!("{}", error_msg);
-                ErrorResponse::from(AppError::NotFound(error_msg))
-            })?
-            .ok_or_else(|| {
-                error!("Saldo not found for sender user_id={}", input.transfer_from);
-                ErrorResponse::from(AppError::NotFound(format!(
-                    "Saldo with sender User id {} not found",
-                    input.transfer_from
-                )))
-            })?;
-
-        let new_sender_balance = sender_saldo
-            .total_balance
-            .checked_sub(input.transfer_amount)
-            .ok_or_else(|| {
-                let error_msg = format!(
-                    "Insufficient balance or overflow: user_id={}, current={}, transfer={}",
-                    input.transfer_from, sender_saldo.total_balance, input.transfer_amount
-                );
-                error!("{}", error_msg);
-                ErrorResponse::from(AppError::Custom(error_msg))
-            })?;
-
-        let request_sender_balance = UpdateSaldoBalance {
-            user_id: input.transfer_from,
-            total_balance: new_sender_balance,
-        };
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Transfer created successfully".to_string(),
+            data: transfer,
+        })
+    }
 
-        if let Err(db_err) = self
-            .saldo_repository
-            .update_balance(&request_sender_balance)
-            .await
-        {
+    async fn create_batch_transfer(
+        &self,
+        input: &CreateBatchTransferRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<Vec<TransferResponse>>, ErrorResponse> {
+        if !role.can_access(user_id, &[input.transfer_from]) {
             error!(
-                "Failed to update balance for sender (user_id={}): {}. Rolling back transfer deletion.",
-                input.transfer_from, db_err
+                "User {user_id} is not allowed to send a batch from user {}",
+                input.transfer_from
             );
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to transfer from user {}",
+                input.transfer_from
+            ))));
+        }
+
+        info!(
+            "Creating batch transfer: from_user_id={}, recipients={}",
+            input.transfer_from,
+            input.recipients.len()
+        );
+
+        for recipient in &input.recipients {
+            self.balance_policy
+                .ensure_transfer_limit(recipient.transfer_amount)?;
+        }
+
+        let total_amount = Money::checked_sum(input.recipients.iter().map(|r| r.transfer_amount))?;
+
+        let user_ids: Vec<i32> = std::iter::once(input.transfer_from)
+            .chain(input.recipients.iter().map(|r| r.transfer_to))
+            .collect();
+
+        for &user_id in &user_ids {
+            self.user_repository
+                .find_by_id(user_id)
+                .await?
+                .ok_or_else(|| {
+                    error!("Batch transfer rejected: user with id {user_id} not found");
+                    ErrorResponse::from(AppError::NotFound(format!(
+                        "User with id {user_id} not found"
+                    )))
+                })?;
+        }
 
-            if let Err(rollback_err) = self.transfer_repository.delete(transfer.transfer_id).await {
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin batch transfer transaction: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        let saldos = self.lock_saldos(&mut tx, &user_ids, input.currency).await?;
+
+        for saldo in saldos.values() {
+            saldo.ensure_not_frozen().inspect_err(|_| {
                 error!(
-                    "Critical: Failed to rollback transfer (transfer_id={}) after balance update failed: {}",
-                    transfer.transfer_id, rollback_err
+                    "Batch transfer rejected: saldo of user {} is frozen",
+                    saldo.user_id
                 );
-            }
+            })?;
+        }
+
+        // Recipients are distinct and never the sender, so only the sender's
+        // balance carries over from one item to the next.
+        let mut sender_saldo = saldos[&input.transfer_from].clone();
+        let mut transfers = Vec::with_capacity(input.recipients.len());
+
+        for recipient in &input.recipients {
+            let request = CreateTransferRequest {
+                transfer_from: input.transfer_from,
+                transfer_to: recipient.transfer_to,
+                transfer_to_noc: None,
+                transfer_amount: recipient.transfer_amount,
+                currency: input.currency,
+                scheduled_at: None,
+                note: None,
+            };
+
+            let (transfer, new_sender_balance, _) = self
+                .settle_tx(
+                    &mut tx,
+                    &request,
+                    &sender_saldo,
+                    &saldos[&recipient.transfer_to],
+                )
+                .await?;
 
-            return Err(db_err.into());
+            sender_saldo.total_balance = new_sender_balance;
+            transfers.push(TransferResponse::from(transfer));
         }
 
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit batch transfer transaction: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        self.events.transfers_created(&transfers);
+
         info!(
-            "Sender balance updated successfully: user_id={}, new_balance={}",
-            input.transfer_from, new_sender_balance
+            "Batch transfer completed: from={}, recipients={}, total={}, sender_balance={}",
+            input.transfer_from,
+            transfers.len(),
+            total_amount,
+            sender_saldo.total_balance
         );
 
-        let receiver_saldo = self
-            .saldo_repository
-            .find_by_user_id(input.transfer_to)
-            .await
-            .map_err(|e| {
-                let error_msg = format!(
-                    "Saldo not found for receiver user_id={} and e={e}",
-                    input.transfer_to
-                );
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Batch transfer created successfully".to_string(),
+            data: transfers,
+        })
+    }
+
+    async fn transfer_all(
+        &self,
+        user_id: i32,
+        input: &TransferAllRequest,
+    ) -> Result<ApiResponse<Option<TransferResponse>>, ErrorResponse> {
+        let currency = input.currency;
+
+        if input.transfer_to == user_id {
+            error!("Rejected sweep of user_id={user_id} into their own saldo");
+            return Err(ErrorResponse::from(AppError::Custom(
+                "Cannot transfer to self".to_string(),
+            )));
+        }
+
+        self.user_repository
+            .find_by_id(input.transfer_to)
+            .await?
+            .ok_or_else(|| {
+                let error_msg = format!("User receiver with id {} not found", input.transfer_to);
                 error!("{}", error_msg);
                 ErrorResponse::from(AppError::NotFound(error_msg))
-            })?
-            .ok_or_else(|| {
-                error!("Saldo not found for receiver user_id={}", input.transfer_to);
-                ErrorResponse::from(AppError::NotFound(format!(
-                    "Saldo with receiver User id {} not found",
-                    input.transfer_to
-                )))
             })?;
 
-        let new_receiver_balance = receiver_saldo
-            .total_balance
-            .checked_add(input.transfer_amount)
-            .expect("Receiver balance overflow detected");
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin sweep transaction: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
 
-        let request_receiver_balance = UpdateSaldoBalance {
-            user_id: input.transfer_to,
-            total_balance: new_receiver_balance,
-        };
+        // The amount is sized from the locked balance, so a topup or transfer
+        // landing meanwhile waits for this one rather than being missed or
+        // overdrawn.
+        let saldos = self
+            .lock_saldos(&mut tx, &[user_id, input.transfer_to], currency)
+            .await?;
+        let sender_saldo = &saldos[&user_id];
+        let receiver_saldo = &saldos[&input.transfer_to];
 
-        if let Err(db_err) = self
-            .saldo_repository
-            .update_balance(&request_receiver_balance)
-            .await
-        {
-            error!(
-                "Failed to update balance for receiver (user_id={}): {}. Rolling back transfer and sender balance.",
-                input.transfer_to, db_err
-            );
+        for saldo in [sender_saldo, receiver_saldo] {
+            saldo.ensure_not_frozen().inspect_err(|_| {
+                error!("Sweep rejected: saldo of user {} is frozen", saldo.user_id);
+            })?;
+        }
 
-            let _ = self.transfer_repository.delete(transfer.transfer_id).await;
+        let budget = sender_saldo
+            .total_balance
+            .checked_sub(self.balance_policy.minimum_balance)?;
+        let amount = self.fee_schedule.max_amount_within(budget)?;
 
-            let _ = self
-                .saldo_repository
-                .update_balance(&UpdateSaldoBalance {
-                    user_id: input.transfer_from,
-                    total_balance: sender_saldo.total_balance,
-                })
-                .await;
+        if amount == Money::ZERO {
+            info!("Nothing to sweep from the {currency} saldo of user_id={user_id}");
 
-            return Err(db_err.into());
+            return Ok(ApiResponse {
+                status: "success".to_string(),
+                message: "Nothing to transfer".to_string(),
+                data: None,
+            });
         }
 
-        info!(
-            "Receiver balance updated successfully: user_id={}, new_balance={}",
-            input.transfer_to, new_receiver_balance
-        );
+        self.balance_policy.ensure_transfer_limit(amount)?;
+
+        let request = CreateTransferRequest {
+            transfer_from: user_id,
+            transfer_to: input.transfer_to,
+            transfer_to_noc: None,
+            transfer_amount: amount,
+            currency,
+            scheduled_at: None,
+            note: None,
+        };
+
+        let (transfer, new_sender_balance, _) = self
+            .settle_tx(&mut tx, &request, sender_saldo, receiver_saldo)
+            .await?;
+
+        tx.commit().await.map_err(|e| {
+            error!(
+                "Failed to commit sweep transaction: transfer_id={}, error={e}",
+                transfer.transfer_id
+            );
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        let transfer = TransferResponse::from(transfer);
+        self.events
+            .transfers_created(std::slice::from_ref(&transfer));
 
         info!(
-            "Transfer completed successfully: transfer_id={}, from={}, to={}, amount={}",
-            transfer.transfer_id, input.transfer_from, input.transfer_to, input.transfer_amount
+            "Swept {amount} {currency} from user_id={user_id} to user_id={}: transfer_id={}, sender_balance={new_sender_balance}",
+            input.transfer_to, transfer.transfer_id
         );
 
         Ok(ApiResponse {
             status: "success".to_string(),
-            message: "Transfer created successfully".to_string(),
-            data: TransferResponse::from(transfer),
+            message: "Balance transferred successfully".to_string(),
+            data: Some(transfer),
         })
     }
 
     async fn update_transfer(
         &self,
         input: &UpdateTransferRequest,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<TransferResponse>, ErrorResponse> {
+        self.balance_policy
+            .ensure_transfer_limit(input.transfer_amount)?;
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!(
+                "Failed to begin update transaction for transfer {}: {e}",
+                input.transfer_id
+            );
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        // Locked so two concurrent updates can't both apply a difference
+        // against the same old amount.
         let transfer = self
             .transfer_repository
-            .find_by_id(input.transfer_id)
-            .await
-            .map_err(|_| {
-                ErrorResponse::from(AppError::NotFound(format!(
-                    "Transfer with id {} not found",
-                    input.transfer_id
-                )))
-            })?
+            .find_by_id_for_update_tx(&mut tx, input.transfer_id)
+            .await?
             .ok_or_else(|| {
                 ErrorResponse::from(AppError::NotFound(format!(
                     "Transfer with id {} not found",
@@ -351,94 +1112,142 @@ impl TransferServiceTrait for TransferService {
                 )))
             })?;
 
-        let amount_difference = input.transfer_amount as i64 - transfer.transfer_amount as i64;
+        if !role.can_access(user_id, &[transfer.transfer_from]) {
+            error!(
+                "User {user_id} is not allowed to update transfer {}",
+                input.transfer_id
+            );
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to update transfer {}",
+                input.transfer_id
+            ))));
+        }
 
-        let sender_saldo = self
-            .saldo_repository
-            .find_by_user_id(transfer.transfer_from)
-            .await
-            .map_err(|_| {
-                ErrorResponse::from(AppError::NotFound(format!(
-                    "Saldo with User id {} not found",
-                    transfer.transfer_from
-                )))
-            })?
-            .ok_or_else(|| {
-                ErrorResponse::from(AppError::NotFound(format!(
-                    "Saldo with User id {} not found",
-                    transfer.transfer_from
-                )))
-            })?;
+        // The difference moves between the recorded sender and receiver, so
+        // a body naming other users must not get that far.
+        if (transfer.transfer_from, transfer.transfer_to)
+            != (input.transfer_from, input.transfer_to)
+        {
+            error!(
+                "Transfer {} is from user {} to user {}, not {} to {}",
+                input.transfer_id,
+                transfer.transfer_from,
+                transfer.transfer_to,
+                input.transfer_from,
+                input.transfer_to
+            );
+            return Err(ErrorResponse::from(AppError::NotFound(format!(
+                "Transfer with id {} not found from user {} to user {}",
+                input.transfer_id, input.transfer_from, input.transfer_to
+            ))));
+        }
 
-        let new_sender_balance = sender_saldo.total_balance - amount_difference as i32;
+        if transfer.status != TransferStatus::Completed {
+            return Err(ErrorResponse::from(AppError::Conflict(format!(
+                "Transfer with id {} is {} and cannot be updated",
+                input.transfer_id, transfer.status
+            ))));
+        }
 
-        if new_sender_balance < 0 {
-            return Err(ErrorResponse::from(AppError::Custom(
-                "Insufficient balance for sender".to_string(),
-            )));
+        if transfer.reversed_transfer_id.is_some() {
+            return Err(ErrorResponse::from(AppError::Conflict(format!(
+                "Transfer with id {} is a reversal and cannot be updated",
+                input.transfer_id
+            ))));
         }
 
-        let update_sender_balance = UpdateSaldoBalance {
-            user_id: transfer.transfer_from,
-            total_balance: new_sender_balance,
-        };
+        let saldos = self
+            .lock_saldos(
+                &mut tx,
+                &[transfer.transfer_from, transfer.transfer_to],
+                transfer.currency,
+            )
+            .await?;
 
-        if let Err(db_err) = self
-            .saldo_repository
-            .update_balance(&update_sender_balance)
-            .await
-        {
-            error!("Failed to update sender's saldo: {}", db_err);
-            return Err(db_err.into());
+        for saldo in saldos.values() {
+            saldo.ensure_not_frozen().inspect_err(|_| {
+                error!(
+                    "Transfer update rejected: saldo of user {} is frozen",
+                    saldo.user_id
+                );
+            })?;
         }
 
-        let receiver_saldo = self
-            .saldo_repository
-            .find_by_user_id(transfer.transfer_to)
-            .await
-            .map_err(|_| {
-                ErrorResponse::from(AppError::NotFound(format!(
-                    "Saldo with User id {} not found",
-                    transfer.transfer_to
-                )))
-            })?
-            .ok_or_else(|| {
-                ErrorResponse::from(AppError::NotFound(format!(
-                    "Saldo with User id {} not found",
-                    transfer.transfer_to
-                )))
-            })?;
+        let sender_saldo = &saldos[&transfer.transfer_from];
+        let receiver_saldo = &saldos[&transfer.transfer_to];
 
-        let new_receiver_balance = receiver_saldo.total_balance + amount_difference as i32;
+        let amount_difference = input
+            .transfer_amount
+            .checked_sub(transfer.transfer_amount)?;
 
-        let update_receiver_balance = UpdateSaldoBalance {
-            user_id: transfer.transfer_to,
-            total_balance: new_receiver_balance,
-        };
+        // The fee follows the new amount, so the sender's side moves by the
+        // change in amount plus the change in fee.
+        let fee = self.fee_schedule.fee_for(input.transfer_amount)?;
+        let debit_difference = input
+            .transfer_amount
+            .checked_add(fee)?
+            .checked_sub(transfer.transfer_amount.checked_add(transfer.fee)?)?;
 
-        if let Err(db_err) = self
-            .saldo_repository
-            .update_balance(&update_receiver_balance)
-            .await
-        {
-            error!("Failed to update receiver's saldo: {db_err}");
+        let new_sender_balance = sender_saldo.total_balance.checked_sub(debit_difference)?;
 
-            let rollback_sender_balance = UpdateSaldoBalance {
-                user_id: transfer.transfer_from,
-                total_balance: sender_saldo.total_balance,
-            };
+        if new_sender_balance.is_negative() {
+            error!(
+                "Insufficient balance to increase transfer {}: user_id={}, current={}, difference={debit_difference}",
+                input.transfer_id, transfer.transfer_from, sender_saldo.total_balance
+            );
+            return Err(ErrorResponse::from(AppError::InsufficientBalance {
+                required: debit_difference,
+                available: sender_saldo.total_balance,
+            }));
+        }
 
-            self.saldo_repository
-                .update_balance(&rollback_sender_balance)
-                .await
-                .map_err(|rollback_err| {
-                    error!("Failed to rollback sender's saldo update: {rollback_err}");
-                })
-                .ok();
+        // Only an increase debits the sender further; shrinking a transfer
+        // must stay possible even when the balance sits below the minimum.
+        if debit_difference > Money::ZERO {
+            self.balance_policy
+                .ensure_minimum(transfer.transfer_from, new_sender_balance)?;
+        }
 
-            return Err(db_err.into());
+        // Shrinking takes money back from the receiver, who may have spent it.
+        let new_receiver_balance = receiver_saldo
+            .total_balance
+            .checked_add(amount_difference)?;
+
+        if new_receiver_balance.is_negative() {
+            error!(
+                "Insufficient balance to decrease transfer {}: user_id={}, current={}, difference={amount_difference}",
+                input.transfer_id, transfer.transfer_to, receiver_saldo.total_balance
+            );
+            return Err(ErrorResponse::from(AppError::InsufficientBalance {
+                required: Money::ZERO.checked_sub(amount_difference)?,
+                available: receiver_saldo.total_balance,
+            }));
         }
-        let updated_transfer = self.transfer_repository.update(input).await?;
+
+        let updated_transfer = self.transfer_repository.update_tx(&mut tx, input).await?;
+
+        let updated_transfer = self
+            .book_tx(
+                &mut tx,
+                updated_transfer,
+                fee,
+                new_sender_balance,
+                new_receiver_balance,
+            )
+            .await?;
+
+        tx.commit().await.map_err(|e| {
+            error!(
+                "Failed to commit update of transfer {}: {e}",
+                input.transfer_id
+            );
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        info!(
+            "Transfer {} updated: {} -> {}",
+            input.transfer_id, transfer.transfer_amount, updated_transfer.transfer_amount
+        );
 
         Ok(ApiResponse {
             status: "success".to_string(),
@@ -447,36 +1256,491 @@ impl TransferServiceTrait for TransferService {
         })
     }
 
-    async fn delete_transfer(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
-        let user = self.user_repository.find_by_id(id).await.map_err(|_| {
-            ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
+    async fn delete_transfer(
+        &self,
+        id: i32,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin delete transaction for transfer {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
         })?;
 
-        let existing_transfer = self
-            .transfer_repository
-            .find_by_user(user.unwrap().user_id)
+        let transfer = self.transfer_repository.delete_tx(&mut tx, id).await?;
+
+        // Non-admins may only cancel their own transfers before they run.
+        // Returning drops the transaction, which rolls the delete back.
+        if !role.can_access(user_id, &[transfer.transfer_from]) {
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "Transfer with id {id} does not belong to user {user_id}"
+            ))));
+        }
+
+        if role != Role::Admin && transfer.status != TransferStatus::Pending {
+            return Err(ErrorResponse::from(AppError::Conflict(
+                "Only pending transfers can be cancelled".to_string(),
+            )));
+        }
+
+        // The two halves of a reversal only make sense together.
+        if transfer.status == TransferStatus::Reversed || transfer.reversed_transfer_id.is_some() {
+            return Err(ErrorResponse::from(AppError::Conflict(format!(
+                "Transfer with id {id} is part of a reversal and cannot be deleted"
+            ))));
+        }
+
+        // Undo the transfer: the receiver pays the amount back to the sender,
+        // who also gets the fee back. Pending and failed transfers never
+        // moved money or paid a fee.
+        if transfer.status == TransferStatus::Completed {
+            self.move_balance_tx(
+                &mut tx,
+                transfer.transfer_to,
+                transfer.transfer_from,
+                &transfer,
+                DebitCheck::NonNegative,
+            )
             .await?;
 
-        match existing_transfer {
-            Some(_) => {
+            if transfer.fee != Money::ZERO {
                 self.transfer_repository
-                    .delete(existing_transfer.unwrap().transfer_id)
+                    .refund_fee_tx(&mut tx, &transfer)
                     .await?;
+            }
+        }
+
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit delete of transfer {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        let message = if transfer.status == TransferStatus::Pending {
+            "Transfer cancelled successfully"
+        } else {
+            "Transfer deleted successfully"
+        };
+
+        info!("{message} for id: {id}");
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: message.to_string(),
+            data: (),
+        })
+    }
+
+    async fn restore_transfer(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<TransferResponse>, ErrorResponse> {
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin restore transaction for transfer {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
 
-                info!("Topup deleted successfully for id: {id}",);
+        let transfer = self.transfer_repository.restore_tx(&mut tx, id).await?;
 
-                Ok(ApiResponse {
-                    status: "success".to_string(),
-                    message: "Topup deleted successfully".to_string(),
-                    data: (),
-                })
+        if transfer.status == TransferStatus::Completed {
+            self.move_balance_tx(
+                &mut tx,
+                transfer.transfer_from,
+                transfer.transfer_to,
+                &transfer,
+                DebitCheck::NonNegative,
+            )
+            .await?;
+
+            if transfer.fee != Money::ZERO {
+                self.transfer_repository
+                    .charge_fee_tx(&mut tx, &transfer, transfer.fee)
+                    .await?;
             }
-            None => {
-                error!("Topup with id {id} not found");
-                Err(ErrorResponse::from(AppError::NotFound(format!(
-                    "Topup with id {id} not found",
-                ))))
+        }
+
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit restore of transfer {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        info!("Transfer restored successfully for id: {id}");
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Transfer restored successfully".to_string(),
+            data: TransferResponse::from(transfer),
+        })
+    }
+
+    async fn reverse_transfer(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<TransferResponse>, ErrorResponse> {
+        info!("Reversing transfer {id}");
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin reversal transaction for transfer {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        let Some(original) = self
+            .transfer_repository
+            .mark_reversed_tx(&mut tx, id)
+            .await?
+        else {
+            let transfer = self
+                .transfer_repository
+                .find_by_id(id)
+                .await?
+                .ok_or_else(|| {
+                    error!("Transfer with id {id} not found");
+                    ErrorResponse::from(AppError::NotFound(format!(
+                        "Transfer with id {id} not found"
+                    )))
+                })?;
+
+            let reason = if transfer.status == TransferStatus::Reversed {
+                format!("Transfer with id {id} has already been reversed")
+            } else if transfer.reversed_transfer_id.is_some() {
+                format!("Transfer with id {id} is a reversal and cannot be reversed")
+            } else {
+                format!(
+                    "Transfer with id {id} is {} and cannot be reversed",
+                    transfer.status
+                )
+            };
+
+            error!("{reason}");
+            return Err(ErrorResponse::from(AppError::Conflict(reason)));
+        };
+
+        let reversal = self
+            .transfer_repository
+            .create_reversal_tx(&mut tx, &original)
+            .await?;
+
+        let check = if self.balance_policy.allow_negative_reversal {
+            DebitCheck::Unchecked
+        } else {
+            DebitCheck::NonNegative
+        };
+
+        self.move_balance_tx(
+            &mut tx,
+            reversal.transfer_from,
+            reversal.transfer_to,
+            &reversal,
+            check,
+        )
+        .await?;
+
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit reversal of transfer {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        let reversal = TransferResponse::from(reversal);
+        self.events
+            .transfers_created(std::slice::from_ref(&reversal));
+
+        info!(
+            "Transfer {id} reversed by transfer {}: {} returned from user {} to user {}",
+            reversal.transfer_id,
+            reversal.transfer_amount,
+            reversal.transfer_from,
+            reversal.transfer_to
+        );
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Transfer reversed successfully".to_string(),
+            data: reversal,
+        })
+    }
+
+    async fn get_scheduled_transfers(
+        &self,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<Vec<TransferResponse>>, ErrorResponse> {
+        let transfer_from = (role != Role::Admin).then_some(user_id);
+
+        let transfers = self
+            .transfer_repository
+            .find_scheduled(transfer_from)
+            .await?
+            .into_iter()
+            .map(TransferResponse::from)
+            .collect();
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Scheduled transfers retrieved successfully".to_string(),
+            data: transfers,
+        })
+    }
+
+    async fn execute_due_transfers(&self) -> Result<usize, ErrorResponse> {
+        let due = self
+            .transfer_repository
+            .find_due(Utc::now().naive_utc(), SCHEDULED_BATCH_SIZE)
+            .await?;
+
+        let mut executed = 0;
+
+        for transfer in due {
+            match self.execute_scheduled(transfer.transfer_id).await {
+                Ok(true) => executed += 1,
+                Ok(false) => {}
+                Err(e) => error!(
+                    "Scheduled transfer {} will be retried: {}",
+                    transfer.transfer_id, e.message
+                ),
             }
         }
+
+        Ok(executed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use mockall::predicate::{always, eq};
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        domain::{
+            request::{BatchRecipient, pagination::DEFAULT_MAX_PAGE_SIZE},
+            response::ErrorDetails,
+        },
+        test_support::{
+            FakePostgres, MockSaldoRepositoryTrait, MockTransferRepositoryTrait,
+            MockUserRepositoryTrait, MockWebhookNotifierTrait, events, lazy_pool, saldo, transfer,
+            user,
+        },
+    };
+
+    const SENDER: i32 = 1;
+    const RECEIVER: i32 = 2;
+
+    fn request(transfer_amount: i64) -> CreateTransferRequest {
+        CreateTransferRequest {
+            transfer_from: SENDER,
+            transfer_to: RECEIVER,
+            transfer_to_noc: None,
+            transfer_amount: Money::new(transfer_amount),
+            currency: Currency::IDR,
+            scheduled_at: None,
+            note: None,
+        }
+    }
+
+    // No expectations on the notifier: any webhook, i.e. any committed
+    // transfer, fails the test.
+    fn service(
+        db_pool: ConnectionPool,
+        transfers: MockTransferRepositoryTrait,
+        saldos: MockSaldoRepositoryTrait,
+        users: MockUserRepositoryTrait,
+    ) -> TransferService {
+        TransferService::new(
+            db_pool,
+            Arc::new(transfers),
+            Arc::new(saldos),
+            Arc::new(users),
+            events(MockWebhookNotifierTrait::new()),
+            BalancePolicy::default(),
+            FeeSchedule::default(),
+            DEFAULT_MAX_PAGE_SIZE,
+        )
+    }
+
+    fn existing_users() -> MockUserRepositoryTrait {
+        let mut users = MockUserRepositoryTrait::new();
+        users.expect_find_by_id().returning(|id| Ok(Some(user(id))));
+        users
+    }
+
+    fn saldos_with(sender_balance: i64) -> MockSaldoRepositoryTrait {
+        let mut saldos = MockSaldoRepositoryTrait::new();
+        saldos
+            .expect_find_by_user_and_currency_for_update()
+            .with(always(), always(), eq(Currency::IDR))
+            .returning(move |_, user_id, currency| {
+                let balance = if user_id == SENDER { sender_balance } else { 0 };
+                Ok(Some(saldo(user_id, currency, balance)))
+            });
+        saldos
+    }
+
+    #[tokio::test]
+    async fn create_transfer_rejects_unknown_sender() {
+        let mut users = MockUserRepositoryTrait::new();
+        users
+            .expect_find_by_id()
+            .with(eq(SENDER))
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let service = service(
+            lazy_pool(),
+            MockTransferRepositoryTrait::new(),
+            MockSaldoRepositoryTrait::new(),
+            users,
+        );
+
+        let err = service
+            .create_transfer(&request(50_000), SENDER, Role::User)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::NOT_FOUND);
+        assert!(err.message.contains("sender"), "{}", err.message);
+    }
+
+    #[tokio::test]
+    async fn create_batch_transfer_rejects_another_users_saldo() {
+        // No repository expectations: the caller is turned away up front.
+        let service = service(
+            lazy_pool(),
+            MockTransferRepositoryTrait::new(),
+            MockSaldoRepositoryTrait::new(),
+            MockUserRepositoryTrait::new(),
+        );
+
+        let input = CreateBatchTransferRequest {
+            transfer_from: SENDER,
+            recipients: vec![BatchRecipient {
+                transfer_to: RECEIVER,
+                transfer_amount: Money::new(50_000),
+            }],
+            currency: Currency::IDR,
+        };
+
+        let err = service
+            .create_batch_transfer(&input, RECEIVER, Role::User)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn create_transfer_rejects_a_self_transfer_before_touching_balances() {
+        // No repository expectations: no saldo is read, locked or updated.
+        let service = service(
+            lazy_pool(),
+            MockTransferRepositoryTrait::new(),
+            MockSaldoRepositoryTrait::new(),
+            MockUserRepositoryTrait::new(),
+        );
+
+        let input = CreateTransferRequest {
+            transfer_to: SENDER,
+            ..request(50_000)
+        };
+        let err = service
+            .create_transfer(&input, SENDER, Role::User)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.message, "Cannot transfer to self");
+    }
+
+    #[tokio::test]
+    async fn create_transfer_rejects_amounts_over_the_maximum() {
+        // No repository expectations: the limit is checked before any lookup.
+        let service = TransferService {
+            balance_policy: BalancePolicy {
+                max_transfer_amount: Some(Money::new(1_000_000)),
+                ..BalancePolicy::default()
+            },
+            ..service(
+                lazy_pool(),
+                MockTransferRepositoryTrait::new(),
+                MockSaldoRepositoryTrait::new(),
+                MockUserRepositoryTrait::new(),
+            )
+        };
+
+        let err = service
+            .create_transfer(&request(1_000_001), SENDER, Role::User)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(err.message.contains("maximum"), "{}", err.message);
+    }
+
+    #[tokio::test]
+    async fn create_transfer_rejects_insufficient_balance() {
+        let postgres = FakePostgres::start().await;
+
+        // No create_tx or update_balance_tx expectations: neither may run.
+        let service = service(
+            postgres.pool.clone(),
+            MockTransferRepositoryTrait::new(),
+            saldos_with(40_000),
+            existing_users(),
+        );
+
+        let err = service
+            .create_transfer(&request(50_000), SENDER, Role::User)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.error_code.as_deref(), Some("insufficient_balance"));
+
+        let Some(ErrorDetails::InsufficientBalance(details)) = err.details.map(|d| *d) else {
+            panic!("expected insufficient balance details");
+        };
+        assert_eq!(details.required, Money::new(50_000));
+        assert_eq!(details.available, Money::new(40_000));
+        assert_eq!(details.shortfall, Money::new(10_000));
+        assert_eq!(postgres.statements().await, ["BEGIN", "ROLLBACK"]);
+    }
+
+    #[tokio::test]
+    async fn create_transfer_rolls_back_when_receiver_update_fails() {
+        let postgres = FakePostgres::start().await;
+
+        let mut transfers = MockTransferRepositoryTrait::new();
+        transfers.expect_create_tx().times(1).returning(|_, input| {
+            Ok(transfer(
+                10,
+                input.transfer_from,
+                input.transfer_to,
+                input.transfer_amount,
+                input.currency,
+            ))
+        });
+
+        let mut saldos = saldos_with(100_000);
+        saldos
+            .expect_update_balance_tx()
+            .withf(|_, input| input.user_id == SENDER)
+            .times(1)
+            .returning(|_, input| Ok(saldo(input.user_id, input.currency, 50_000)));
+        saldos
+            .expect_update_balance_tx()
+            .withf(|_, input| input.user_id == RECEIVER)
+            .times(1)
+            .returning(|_, _| Err(AppError::SqlxError(sqlx::Error::RowNotFound)));
+
+        let service = service(postgres.pool.clone(), transfers, saldos, existing_users());
+
+        let err = service
+            .create_transfer(&request(50_000), SENDER, Role::User)
+            .await
+            .unwrap_err();
+
+        // The error surfaces before commit, so the sender debit is rolled back
+        // with the transaction and no transfer event goes out.
+        assert_eq!(err.code, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(postgres.statements().await, ["BEGIN", "ROLLBACK"]);
     }
 }
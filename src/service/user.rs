@@ -1,28 +1,65 @@
 use async_trait::async_trait;
+use std::collections::HashMap;
 use tracing::{error, info};
+use validator::Validate;
 
 use crate::{
-    abstract_trait::{DynHashing, DynUserRepository, UserServiceTrait},
+    abstract_trait::{
+        DynHashing, DynSaldoRepository, DynTopupRepository, DynTransferRepository,
+        DynUserRepository, DynWithdrawRepository, UserServiceTrait,
+    },
+    config::ConnectionPool,
     domain::{
-        request::{CreateUserRequest, FindAllUserRequest, RegisterRequest, UpdateUserRequest},
+        currency::Currency,
+        money::Money,
+        request::{
+            CreateUserRequest, FindAllUserRequest, FindUserCounterpartiesRequest,
+            FindUsersBatchRequest, GetUserQuery, RegisterRequest, Sort, UpdateUserRequest,
+            UpdateUserRoleRequest, UserSearch, normalize_page, normalize_pagination,
+        },
         response::{
-            ApiResponse, ApiResponsePagination, ErrorResponse, pagination::Pagination,
-            user::UserResponse,
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            pagination::Pagination,
+            user::{CounterpartyResponse, UserResponse, UserSummaryResponse},
         },
+        role::Role,
     },
-    utils::{AppError, random_vcc},
+    model::user::User,
+    utils::{AppError, mask_noc, with_unique_noc},
 };
 
 pub struct UserService {
+    db_pool: ConnectionPool,
     repository: DynUserRepository,
     hashing: DynHashing,
+    saldo_repository: DynSaldoRepository,
+    topup_repository: DynTopupRepository,
+    transfer_repository: DynTransferRepository,
+    withdraw_repository: DynWithdrawRepository,
+    max_page_size: i32,
 }
 
 impl UserService {
-    pub fn new(repository: DynUserRepository, hashing: DynHashing) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        db_pool: ConnectionPool,
+        repository: DynUserRepository,
+        hashing: DynHashing,
+        saldo_repository: DynSaldoRepository,
+        topup_repository: DynTopupRepository,
+        transfer_repository: DynTransferRepository,
+        withdraw_repository: DynWithdrawRepository,
+        max_page_size: i32,
+    ) -> Self {
         Self {
+            db_pool,
             repository,
             hashing,
+            saldo_repository,
+            topup_repository,
+            transfer_repository,
+            withdraw_repository,
+            max_page_size,
         }
     }
 }
@@ -33,43 +70,101 @@ impl UserServiceTrait for UserService {
         &self,
         req: &FindAllUserRequest,
     ) -> Result<ApiResponsePagination<Vec<UserResponse>>, ErrorResponse> {
-        let page = if req.page > 0 { req.page } else { 1 };
-        let page_size = if req.page_size > 0 { req.page_size } else { 10 };
-        let search = if req.search.is_empty() {
-            None
-        } else {
-            Some(req.search.clone())
-        };
+        let (page, page_size, search) = normalize_pagination(req, self.max_page_size);
+        let search = search.map(|search| {
+            if req.fuzzy {
+                UserSearch::Fuzzy(search.term)
+            } else {
+                UserSearch::Match(search)
+            }
+        });
 
-        let (users, total_items) = self.repository.find_all(page, page_size, search).await?;
+        let (users, total_items) = self
+            .repository
+            .find_all(
+                page,
+                page_size,
+                search,
+                Sort::new(req.sort_by.clone(), req.order),
+                req.include_deleted,
+            )
+            .await?;
 
         info!("Found {} users", users.len());
 
-        let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
-
-        let user_responses: Vec<UserResponse> = users.into_iter().map(UserResponse::from).collect();
+        let to_response = if req.full {
+            UserResponse::unmasked
+        } else {
+            UserResponse::from
+        };
+        let user_responses: Vec<UserResponse> = users.into_iter().map(to_response).collect();
 
         Ok(ApiResponsePagination {
             status: "success".to_string(),
             message: "Users retrieved successfully".to_string(),
             data: user_responses,
-            pagination: Pagination {
-                page,
-                page_size,
-                total_items,
-                total_pages,
-            },
+            pagination: Pagination::new(page, page_size, total_items),
+        })
+    }
+
+    async fn get_users_batch(
+        &self,
+        input: &FindUsersBatchRequest,
+    ) -> Result<ApiResponse<Vec<UserResponse>>, ErrorResponse> {
+        let mut users: HashMap<i32, User> = self
+            .repository
+            .find_by_ids(&input.ids)
+            .await?
+            .into_iter()
+            .map(|user| (user.user_id, user))
+            .collect();
+
+        // In the order asked for; unknown or deleted ids are left out, and a
+        // repeated id only comes back once.
+        let data: Vec<UserResponse> = input
+            .ids
+            .iter()
+            .filter_map(|id| users.remove(id))
+            .map(UserResponse::from)
+            .collect();
+
+        info!(
+            "Found {} of {} requested users",
+            data.len(),
+            input.ids.len()
+        );
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Users retrieved successfully".to_string(),
+            data,
         })
     }
 
-    async fn get_user(&self, id: i32) -> Result<ApiResponse<Option<UserResponse>>, ErrorResponse> {
+    async fn get_user(
+        &self,
+        id: i32,
+        query: &GetUserQuery,
+        role: Role,
+    ) -> Result<ApiResponse<Option<UserResponse>>, ErrorResponse> {
+        if query.full && role != Role::Admin {
+            error!("Non-admin requested the full noc_transfer of user {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(
+                "Only admins can view the full noc_transfer".to_string(),
+            )));
+        }
+
         let user = self.repository.find_by_id(id).await?;
 
         if let Some(user) = user {
             Ok(ApiResponse {
                 status: "success".to_string(),
                 message: "User retrieved successfully".to_string(),
-                data: Some(UserResponse::from(user)),
+                data: Some(if query.full {
+                    UserResponse::unmasked(user)
+                } else {
+                    UserResponse::from(user)
+                }),
             })
         } else {
             Err(ErrorResponse::from(AppError::NotFound(format!(
@@ -78,6 +173,121 @@ impl UserServiceTrait for UserService {
         }
     }
 
+    async fn get_user_by_noc(
+        &self,
+        noc_transfer: &str,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        let user = self
+            .repository
+            .find_by_noc(noc_transfer)
+            .await?
+            .ok_or_else(|| {
+                error!("No user with noc_transfer {}", mask_noc(noc_transfer));
+                ErrorResponse::from(AppError::NotFound(
+                    "User with this noc_transfer not found".to_string(),
+                ))
+            })?;
+
+        // The caller already knows the number, so there is nothing to mask.
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "User retrieved successfully".to_string(),
+            data: UserResponse::unmasked(user),
+        })
+    }
+
+    async fn get_user_summary(
+        &self,
+        id: i32,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<UserSummaryResponse>, ErrorResponse> {
+        if !role.can_access(user_id, &[id]) {
+            error!("User {user_id} is not allowed to access summary of user {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to access summary of user {id}"
+            ))));
+        }
+
+        self.repository.find_by_id(id).await?.ok_or_else(|| {
+            error!("User with id {id} not found");
+            ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
+        })?;
+
+        let (total_topup, topup_count) = self.topup_repository.sum_by_user(id).await?;
+        let (total_withdraw, withdraw_count) = self.withdraw_repository.sum_by_user(id).await?;
+        let (total_sent, sent_count) = self.transfer_repository.sum_sent_by_user(id).await?;
+        let (total_received, received_count) =
+            self.transfer_repository.sum_received_by_user(id).await?;
+
+        // Reports the base-currency saldo; a user without one yet simply has
+        // nothing in it.
+        let current_balance = self
+            .saldo_repository
+            .find_by_user_and_currency(id, Currency::base())
+            .await?
+            .map(|saldo| saldo.total_balance)
+            .unwrap_or_default();
+
+        info!("Summary computed for user {id}");
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "User summary retrieved successfully".to_string(),
+            data: UserSummaryResponse {
+                total_topup,
+                total_withdraw,
+                total_sent,
+                total_received,
+                current_balance,
+                transaction_count: topup_count + withdraw_count + sent_count + received_count,
+            },
+        })
+    }
+
+    async fn get_user_counterparties(
+        &self,
+        id: i32,
+        req: &FindUserCounterpartiesRequest,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponsePagination<Vec<CounterpartyResponse>>, ErrorResponse> {
+        if !role.can_access(user_id, &[id]) {
+            error!("User {user_id} is not allowed to access counterparties of user {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to access counterparties of user {id}"
+            ))));
+        }
+
+        self.repository.find_by_id(id).await?.ok_or_else(|| {
+            error!("User with id {id} not found");
+            ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
+        })?;
+
+        let (page, page_size) = normalize_page(req.page, req.page_size, self.max_page_size);
+        let currency = req.currency.unwrap_or_else(Currency::base);
+
+        let (counterparties, total_items) = self
+            .transfer_repository
+            .find_counterparties(id, currency, page, page_size)
+            .await?;
+
+        info!(
+            "Found {} {currency} counterparties for user {id}",
+            counterparties.len()
+        );
+
+        Ok(ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Counterparties retrieved successfully".to_string(),
+            data: counterparties
+                .into_iter()
+                .map(CounterpartyResponse::from)
+                .collect(),
+            pagination: Pagination::new(page, page_size, total_items),
+        })
+    }
+
     async fn create_user(
         &self,
         input: &RegisterRequest,
@@ -97,19 +307,20 @@ impl UserServiceTrait for UserService {
             .await
             .map_err(|e| ErrorResponse::from(AppError::HashingError(e)))?;
 
-        let noc_transfer = random_vcc().map(Some).unwrap_or(None);
-
-        let request = &CreateUserRequest {
-            firstname: input.firstname.clone(),
-            lastname: input.lastname.clone(),
-            email: input.email.clone(),
-            password: hashed_password,
-            confirm_password: input.confirm_password.clone(),
-            noc_transfer: noc_transfer.to_owned(),
-        };
-
         info!("Creating user with email: {}", input.email);
-        let create_user = self.repository.create_user(request).await?;
+        let create_user = with_unique_noc(|noc_transfer| {
+            let request = CreateUserRequest {
+                firstname: input.firstname.clone(),
+                lastname: input.lastname.clone(),
+                email: input.email.clone(),
+                password: hashed_password.clone(),
+                confirm_password: input.confirm_password.clone(),
+                noc_transfer: Some(noc_transfer),
+            };
+
+            async move { self.repository.create_user(&request).await }
+        })
+        .await?;
 
         info!("User Create successfully with email: {}", input.email);
 
@@ -123,8 +334,40 @@ impl UserServiceTrait for UserService {
     async fn update_user(
         &self,
         input: &UpdateUserRequest,
+        user_id: i32,
+        role: Role,
     ) -> Result<Option<ApiResponse<UserResponse>>, ErrorResponse> {
-        let user = self.repository.update_user(input).await?;
+        let id = input.id;
+
+        if !role.can_access(user_id, &[id]) {
+            error!("User {user_id} is not allowed to update user {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to update user {id}"
+            ))));
+        }
+
+        if let Some(ref email) = input.email
+            && let Some(existing) = self.repository.find_by_email(email).await?
+            && existing.user_id != id
+        {
+            error!("Email already exists: {email}");
+            return Err(ErrorResponse::from(AppError::EmailAlreadyExists));
+        }
+
+        let mut input = input.clone();
+
+        if let Some(ref password) = input.password {
+            let hashed_password = self
+                .hashing
+                .hash_password(password)
+                .await
+                .map_err(|e| ErrorResponse::from(AppError::HashingError(e)))?;
+
+            input.password = Some(hashed_password);
+            input.confirm_password = None;
+        }
+
+        let user = self.repository.update_user(&input).await?;
 
         Ok(Some(ApiResponse {
             status: "success".to_string(),
@@ -133,8 +376,132 @@ impl UserServiceTrait for UserService {
         }))
     }
 
+    async fn rotate_noc_transfer(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        info!("Rotating noc_transfer for user {user_id}");
+
+        let user = with_unique_noc(|noc_transfer| async move {
+            self.repository
+                .update_noc_transfer(user_id, &noc_transfer)
+                .await
+        })
+        .await?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "noc_transfer rotated successfully".to_string(),
+            // The caller just asked for this number, so it isn't masked.
+            data: UserResponse::unmasked(user),
+        })
+    }
+
+    async fn update_user_role(
+        &self,
+        id: i32,
+        input: &UpdateUserRoleRequest,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        info!("Updating role of user {id} to {}", input.role);
+
+        let user = self.repository.update_role(id, input.role).await?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "User role updated successfully".to_string(),
+            data: UserResponse::from(user),
+        })
+    }
+
+    // Creates the first admin and does nothing once one exists. An account
+    // already registered with `email` is never promoted: whoever signed up
+    // with that address first would otherwise become admin on next boot.
+    async fn seed_admin(&self, email: &str, password: Option<&str>) -> Result<(), ErrorResponse> {
+        if self.repository.admin_exists().await? {
+            info!("An admin already exists, not seeding {email}");
+            return Ok(());
+        }
+
+        if self.repository.find_by_email_exists(email).await? {
+            error!("Cannot seed admin: {email} is already registered");
+            return Err(ErrorResponse::from(AppError::Conflict(format!(
+                "User with email {email} already exists; refusing to promote it to admin"
+            ))));
+        }
+
+        let password = password.ok_or_else(|| {
+            error!("Cannot seed admin {email}: no password set");
+            ErrorResponse::from(AppError::Custom(
+                "Set ADMIN_PASSWORD to create the admin account".to_string(),
+            ))
+        })?;
+
+        let request = RegisterRequest {
+            firstname: "Admin".to_string(),
+            lastname: "User".to_string(),
+            email: email.to_string(),
+            password: password.to_string(),
+            confirm_password: password.to_string(),
+        };
+
+        request
+            .validate()
+            .map_err(|e| ErrorResponse::from(AppError::Validation(e)))?;
+
+        let created = self.create_user(&request).await?.data;
+
+        // Nobody can click a verification link for a seeded account.
+        self.repository.mark_verified(created.id).await?;
+        self.repository.update_role(created.id, Role::Admin).await?;
+
+        info!("Seeded admin {email} as user {}", created.id);
+
+        Ok(())
+    }
+
     async fn delete_user(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
-        self.repository.delete_user(id).await?;
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin user deletion transaction: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        // Same rules as `AuthService::delete_account`: money is never deleted
+        // along with its owner, so a funded account has to be emptied first.
+        let saldos = self
+            .saldo_repository
+            .find_by_user_for_update(&mut tx, id)
+            .await?;
+
+        if let Some(saldo) = saldos
+            .iter()
+            .find(|saldo| saldo.total_balance != Money::ZERO)
+        {
+            error!(
+                "User {id} still holds {} {}, refusing to delete",
+                saldo.total_balance, saldo.currency
+            );
+            return Err(ErrorResponse::from(AppError::Conflict(format!(
+                "User {id} still holds {} {}; it must be withdrawn before the account is deleted",
+                saldo.total_balance, saldo.currency
+            ))));
+        }
+
+        self.transfer_repository
+            .delete_unsettled_by_user_tx(&mut tx, id)
+            .await?;
+        self.withdraw_repository
+            .delete_by_user_tx(&mut tx, id)
+            .await?;
+        self.topup_repository.delete_by_user_tx(&mut tx, id).await?;
+        self.saldo_repository.delete_by_user_tx(&mut tx, id).await?;
+        self.repository.delete_user_tx(&mut tx, id).await?;
+
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit user deletion transaction: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        info!("User deleted successfully for id: {id}");
 
         Ok(ApiResponse {
             status: "success".to_string(),
@@ -142,4 +509,218 @@ impl UserServiceTrait for UserService {
             data: (),
         })
     }
+
+    async fn restore_user(&self, id: i32) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        let user = self.repository.restore(id).await?;
+
+        info!("User restored successfully for id: {id}");
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "User restored successfully".to_string(),
+            data: UserResponse::from(user),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use std::sync::{
+        Arc, Mutex,
+        atomic::{AtomicU32, Ordering},
+    };
+
+    use super::*;
+    use crate::{
+        abstract_trait::HashingTrait,
+        config::Hashing,
+        domain::request::pagination::DEFAULT_MAX_PAGE_SIZE,
+        test_support::{
+            MockSaldoRepositoryTrait, MockTopupRepositoryTrait, MockTransferRepositoryTrait,
+            MockUserRepositoryTrait, MockWithdrawRepositoryTrait, lazy_pool, user,
+        },
+        utils::{MAX_NOC_ATTEMPTS, is_valid_noc},
+    };
+
+    fn service(users: MockUserRepositoryTrait) -> UserService {
+        UserService::new(
+            lazy_pool(),
+            Arc::new(users),
+            Arc::new(Hashing::new()),
+            Arc::new(MockSaldoRepositoryTrait::new()),
+            Arc::new(MockTopupRepositoryTrait::new()),
+            Arc::new(MockTransferRepositoryTrait::new()),
+            Arc::new(MockWithdrawRepositoryTrait::new()),
+            DEFAULT_MAX_PAGE_SIZE,
+        )
+    }
+
+    #[tokio::test]
+    async fn rotation_retries_taken_nocs() {
+        let attempts = Arc::new(AtomicU32::new(0));
+
+        let mut users = MockUserRepositoryTrait::new();
+        let counter = attempts.clone();
+        users
+            .expect_update_noc_transfer()
+            .times(3)
+            .returning(move |id, noc_transfer| {
+                if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                    return Err(AppError::NocTransferTaken);
+                }
+
+                Ok(User {
+                    noc_transfer: noc_transfer.to_string(),
+                    ..user(id)
+                })
+            });
+
+        let response = service(users).rotate_noc_transfer(7).await.unwrap();
+
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(response.data.id, 7);
+        assert!(is_valid_noc(&response.data.noc_transfer));
+    }
+
+    #[tokio::test]
+    async fn rotation_errors_once_every_attempt_collides() {
+        let mut users = MockUserRepositoryTrait::new();
+        users
+            .expect_update_noc_transfer()
+            .times(MAX_NOC_ATTEMPTS as usize)
+            .returning(|_, _| Err(AppError::NocTransferTaken));
+
+        let err = service(users).rotate_noc_transfer(7).await.unwrap_err();
+
+        assert_eq!(err.code, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn seeding_refuses_to_promote_a_registered_account() {
+        let mut users = MockUserRepositoryTrait::new();
+        users.expect_admin_exists().times(1).returning(|| Ok(false));
+        users
+            .expect_find_by_email_exists()
+            .times(1)
+            .returning(|_| Ok(true));
+        // No update_role or create_user expectations: neither may run.
+
+        let err = service(users)
+            .seed_admin("admin@example.com", Some("admin-password"))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn seeding_does_nothing_once_an_admin_exists() {
+        let mut users = MockUserRepositoryTrait::new();
+        users.expect_admin_exists().times(1).returning(|| Ok(true));
+
+        service(users)
+            .seed_admin("admin@example.com", Some("admin-password"))
+            .await
+            .unwrap();
+    }
+
+    fn update(id: i32) -> UpdateUserRequest {
+        UpdateUserRequest {
+            id,
+            firstname: None,
+            lastname: None,
+            email: None,
+            password: None,
+            confirm_password: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn changing_to_a_taken_email_conflicts() {
+        let mut users = MockUserRepositoryTrait::new();
+        users
+            .expect_find_by_email()
+            .times(1)
+            .returning(|_| Ok(Some(user(8))));
+        // No update_user expectation: nothing may be written.
+
+        let input = UpdateUserRequest {
+            email: Some("taken@example.com".to_string()),
+            ..update(7)
+        };
+        let err = service(users)
+            .update_user(&input, 7, Role::User)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn keeping_ones_own_email_is_not_a_conflict() {
+        let mut users = MockUserRepositoryTrait::new();
+        users
+            .expect_find_by_email()
+            .times(1)
+            .returning(|_| Ok(Some(user(7))));
+        users
+            .expect_update_user()
+            .times(1)
+            .returning(|input| Ok(user(input.id)));
+
+        let input = UpdateUserRequest {
+            email: Some("me@example.com".to_string()),
+            ..update(7)
+        };
+        let response = service(users)
+            .update_user(&input, 7, Role::User)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(response.data.id, 7);
+    }
+
+    #[tokio::test]
+    async fn new_password_is_stored_hashed() {
+        let stored = Arc::new(Mutex::new(None));
+
+        let mut users = MockUserRepositoryTrait::new();
+        let captured = stored.clone();
+        users.expect_update_user().times(1).returning(move |input| {
+            *captured.lock().unwrap() = Some(input.clone());
+            Ok(user(input.id))
+        });
+
+        let input = UpdateUserRequest {
+            password: Some("new-password".to_string()),
+            confirm_password: Some("new-password".to_string()),
+            ..update(7)
+        };
+        service(users)
+            .update_user(&input, 7, Role::User)
+            .await
+            .unwrap();
+
+        let written = stored.lock().unwrap().take().expect("update_user input");
+        let hashed = written.password.expect("password");
+        assert_ne!(hashed, "new-password");
+        assert_eq!(written.confirm_password, None);
+        Hashing::new()
+            .compare_password(&hashed, "new-password")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn users_cannot_update_someone_else() {
+        // No repository expectations: the check comes first.
+        let err = service(MockUserRepositoryTrait::new())
+            .update_user(&update(8), 7, Role::User)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::FORBIDDEN);
+    }
 }
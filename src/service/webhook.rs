@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::{
+    abstract_trait::{DynWebhookRepository, WebhookServiceTrait},
+    domain::{
+        request::CreateWebhookRequest,
+        response::{ApiResponse, ErrorResponse, webhook::WebhookResponse},
+    },
+    utils::generate_webhook_secret,
+};
+
+pub struct WebhookService {
+    webhook_repository: DynWebhookRepository,
+}
+
+impl WebhookService {
+    pub fn new(webhook_repository: DynWebhookRepository) -> Self {
+        Self { webhook_repository }
+    }
+}
+
+#[async_trait]
+impl WebhookServiceTrait for WebhookService {
+    async fn create_webhook(
+        &self,
+        user_id: i32,
+        input: &CreateWebhookRequest,
+    ) -> Result<ApiResponse<WebhookResponse>, ErrorResponse> {
+        let secret = generate_webhook_secret();
+
+        let webhook = self
+            .webhook_repository
+            .create(user_id, &input.url, &secret)
+            .await?;
+
+        info!(
+            "Webhook {} registered for user_id={user_id}",
+            webhook.webhook_id
+        );
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Webhook registered successfully".to_string(),
+            data: WebhookResponse::from(webhook),
+        })
+    }
+}
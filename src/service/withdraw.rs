@@ -2,38 +2,127 @@ use crate::{
     abstract_trait::{
         DynSaldoRepository, DynUserRepository, DynWithdrawRepository, WithdrawServiceTrait,
     },
+    config::ConnectionPool,
     domain::{
+        balance_policy::BalancePolicy,
+        money::Money,
         request::{
-            CreateWithdrawRequest, FindAllWithdrawRequest, UpdateSaldoWithdraw,
-            UpdateWithdrawRequest,
+            BalanceChangeReason, CreateWithdrawRequest, FindAllWithdrawRequest, Sort,
+            UpdateSaldoBalance, UpdateSaldoWithdraw, UpdateWithdrawRequest, normalize_pagination,
         },
         response::{
             ApiResponse, ApiResponsePagination, ErrorResponse, pagination::Pagination,
             withdraw::WithdrawResponse,
         },
+        role::Role,
+        withdraw_status::WithdrawStatus,
     },
+    model::withdraw::Withdraw,
+    service::events::TransactionEvents,
     utils::AppError,
 };
 use async_trait::async_trait;
 use chrono::Utc;
+use sqlx::PgConnection;
 use tracing::{error, info};
+use validator::Validate;
 
 pub struct WithdrawService {
+    db_pool: ConnectionPool,
     withdraw_repository: DynWithdrawRepository,
     saldo_repository: DynSaldoRepository,
     user_repository: DynUserRepository,
+    events: TransactionEvents,
+    balance_policy: BalancePolicy,
+    max_page_size: i32,
 }
 
 impl WithdrawService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
+        db_pool: ConnectionPool,
         withdraw_repository: DynWithdrawRepository,
         saldo_repository: DynSaldoRepository,
         user_repository: DynUserRepository,
+        events: TransactionEvents,
+        balance_policy: BalancePolicy,
+        max_page_size: i32,
     ) -> Self {
         Self {
+            db_pool,
             withdraw_repository,
             saldo_repository,
             user_repository,
+            events,
+            balance_policy,
+            max_page_size,
+        }
+    }
+
+    // Shifts the user's balance by `delta` on a locked saldo row, refusing
+    // to leave it negative. Used to undo a withdraw on delete, re-apply it on
+    // restore and settle the difference when its amount is updated.
+    async fn adjust_balance_tx(
+        &self,
+        conn: &mut PgConnection,
+        withdraw: &Withdraw,
+        delta: Money,
+    ) -> Result<(), ErrorResponse> {
+        let user_id = withdraw.user_id;
+        let withdraw_id = withdraw.withdraw_id;
+
+        let saldo = self
+            .saldo_repository
+            .find_by_user_and_currency_for_update(conn, user_id, withdraw.currency)
+            .await?
+            .ok_or_else(|| {
+                error!("Saldo not found for user_id={user_id}");
+                ErrorResponse::from(AppError::NotFound(format!(
+                    "Saldo with User id {user_id} not found"
+                )))
+            })?;
+
+        let total_balance = saldo.total_balance.checked_add(delta)?;
+
+        if total_balance.is_negative() {
+            let error_msg = format!(
+                "Insufficient balance to adjust withdraw {withdraw_id}: user_id={user_id}, current={}, delta={delta}",
+                saldo.total_balance
+            );
+            error!("{}", error_msg);
+            return Err(ErrorResponse::from(AppError::InsufficientBalance {
+                required: Money::ZERO.checked_sub(delta)?,
+                available: saldo.total_balance,
+            }));
+        }
+
+        self.saldo_repository
+            .update_balance_tx(
+                conn,
+                &UpdateSaldoBalance {
+                    user_id,
+                    currency: saldo.currency,
+                    total_balance,
+                    reason: BalanceChangeReason::Withdraw,
+                    reference_id: Some(withdraw_id),
+                },
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    // Explains why `id` could not be locked as a pending withdraw.
+    async fn not_pending(&self, id: i32) -> ErrorResponse {
+        match self.withdraw_repository.find_by_id(id).await {
+            Ok(Some(withdraw)) => ErrorResponse::from(AppError::Conflict(format!(
+                "Withdraw with id {id} is already {}",
+                withdraw.status
+            ))),
+            Ok(None) => ErrorResponse::from(AppError::NotFound(format!(
+                "Withdraw with id {id} not found"
+            ))),
+            Err(e) => ErrorResponse::from(e),
         }
     }
 }
@@ -44,23 +133,24 @@ impl WithdrawServiceTrait for WithdrawService {
         &self,
         req: &FindAllWithdrawRequest,
     ) -> Result<ApiResponsePagination<Vec<WithdrawResponse>>, ErrorResponse> {
-        let page = if req.page > 0 { req.page } else { 1 };
-        let page_size = if req.page_size > 0 { req.page_size } else { 10 };
-        let search = if req.search.is_empty() {
-            None
-        } else {
-            Some(req.search.clone())
-        };
+        req.validate()
+            .map_err(|e| ErrorResponse::from(AppError::Validation(e)))?;
+
+        let (page, page_size, search) = normalize_pagination(req, self.max_page_size);
 
         let (withdraws, total_items) = self
             .withdraw_repository
-            .find_all(page, page_size, search)
+            .find_all(
+                page,
+                page_size,
+                search,
+                Sort::new(req.sort_by.clone(), req.order),
+                req.filter(),
+            )
             .await?;
 
         info!("Found {} withdraws", withdraws.len());
 
-        let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
-
         let withdraw_responses: Vec<WithdrawResponse> =
             withdraws.into_iter().map(WithdrawResponse::from).collect();
 
@@ -68,22 +158,26 @@ impl WithdrawServiceTrait for WithdrawService {
             status: "success".to_string(),
             message: "Withdraws retrieved successfully".to_string(),
             data: withdraw_responses,
-            pagination: Pagination {
-                page,
-                page_size,
-                total_items,
-                total_pages,
-            },
+            pagination: Pagination::new(page, page_size, total_items),
         })
     }
 
     async fn get_withdraw(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<WithdrawResponse>>, ErrorResponse> {
         let withdraw = self.withdraw_repository.find_by_id(id).await?;
 
         if let Some(withdraw) = withdraw {
+            if !role.can_access(user_id, &[withdraw.user_id]) {
+                error!("User {user_id} is not allowed to access withdraw {id}");
+                return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                    "You are not allowed to access withdraw {id}"
+                ))));
+            }
+
             info!("Successfully retrieved withdraw with ID: {id}");
             Ok(ApiResponse {
                 status: "success".to_string(),
@@ -101,7 +195,16 @@ impl WithdrawServiceTrait for WithdrawService {
     async fn get_withdraw_users(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<Vec<WithdrawResponse>>>, ErrorResponse> {
+        if !role.can_access(user_id, &[id]) {
+            error!("User {user_id} is not allowed to access withdraws of user {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to access withdraws of user {id}"
+            ))));
+        }
+
         self.user_repository.find_by_id(id).await.map_err(|_| {
             ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
         })?;
@@ -129,7 +232,16 @@ impl WithdrawServiceTrait for WithdrawService {
     async fn get_withdraw_user(
         &self,
         id: i32,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<WithdrawResponse>>, ErrorResponse> {
+        if !role.can_access(user_id, &[id]) {
+            error!("User {user_id} is not allowed to access withdraws of user {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to access withdraws of user {id}"
+            ))));
+        }
+
         let _user = self.user_repository.find_by_id(id).await.map_err(|_| {
             ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
         })?;
@@ -162,170 +274,578 @@ impl WithdrawServiceTrait for WithdrawService {
     async fn create_withdraw(
         &self,
         input: &CreateWithdrawRequest,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<WithdrawResponse>, ErrorResponse> {
+        if !role.can_access(user_id, &[input.user_id]) {
+            error!(
+                "User {user_id} is not allowed to withdraw from user {}",
+                input.user_id
+            );
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to withdraw from user {}",
+                input.user_id
+            ))));
+        }
+
         info!("Creating withdraw for user_id: {}", input.user_id);
 
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!(
+                "Failed to begin withdraw transaction for user_id {}: {e}",
+                input.user_id
+            );
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        // Locking the saldo row serialises concurrent withdrawals for the
+        // user, so the daily total below can't be raced.
         let saldo = self
             .saldo_repository
-            .find_by_user_id(input.user_id)
-            .await
-            .map_err(|_| {
-                error!("Saldo with user_id {} not found", input.user_id);
-                ErrorResponse::from(AppError::NotFound(format!(
-                    "Saldo with user_id {} not found",
-                    input.user_id
-                )))
+            .find_by_user_and_currency_for_update(&mut tx, input.user_id, input.currency)
+            .await?
+            .ok_or_else(|| {
+                error!(
+                    "{} saldo not found for user_id: {}",
+                    input.currency, input.user_id
+                );
+                ErrorResponse::from(AppError::NotFound("Saldo not found".to_string()))
             })?;
 
-        let saldo_ref = saldo.as_ref().ok_or_else(|| {
-            error!("Saldo not found for user_id: {}", input.user_id);
-            ErrorResponse::from(AppError::NotFound("Saldo not found".to_string()))
-        })?;
-
         info!(
             "Saldo found for user_id: {}. Current balance: {}",
-            input.user_id, saldo_ref.total_balance
+            input.user_id, saldo.total_balance
         );
 
-        if saldo_ref.total_balance < input.withdraw_amount {
+        saldo.ensure_not_frozen().inspect_err(|_| {
+            error!(
+                "Withdraw rejected: saldo of user {} is frozen",
+                input.user_id
+            );
+        })?;
+
+        if let Some(limit) = self.balance_policy.daily_withdraw_limit {
+            let withdrawn_today = self
+                .withdraw_repository
+                .sum_for_day_tx(
+                    &mut tx,
+                    input.user_id,
+                    input.currency,
+                    Utc::now().date_naive(),
+                )
+                .await?;
+
+            if withdrawn_today.checked_add(input.withdraw_amount)? > limit {
+                error!(
+                    "Daily withdraw limit exceeded for user_id: {}. Withdrawn today: {withdrawn_today}, attempted: {}, limit: {limit}",
+                    input.user_id, input.withdraw_amount
+                );
+                return Err(ErrorResponse::from(AppError::Custom(
+                    "daily withdraw limit exceeded".to_string(),
+                )));
+            }
+        }
+
+        if saldo.total_balance < input.withdraw_amount {
             error!(
                 "Insufficient balance for user_id: {}. Attempted withdrawal: {}",
                 input.user_id, input.withdraw_amount
             );
-            return Err(ErrorResponse::from(AppError::Custom(
-                "Insufficient balance".to_string(),
-            )));
+            return Err(ErrorResponse::from(AppError::InsufficientBalance {
+                required: input.withdraw_amount,
+                available: saldo.total_balance,
+            }));
         }
         info!("User has sufficient balance for withdrawal");
 
-        let new_total_balance = saldo_ref.total_balance - input.withdraw_amount;
+        let new_total_balance = saldo.total_balance.checked_sub(input.withdraw_amount)?;
 
-        let _update_saldo_balance = self
-            .saldo_repository
-            .update_saldo_withdraw(&UpdateSaldoWithdraw {
-                user_id: input.user_id,
-                withdraw_amount: Some(input.withdraw_amount),
-                withdraw_time: Some(Utc::now()),
-                total_balance: new_total_balance,
-            })
+        self.balance_policy
+            .ensure_minimum(input.user_id, new_total_balance)?;
+
+        // Large withdrawals are only recorded here; the balance is debited
+        // once an admin approves them.
+        if self.balance_policy.requires_approval(input.withdraw_amount) {
+            let withdraw = self
+                .withdraw_repository
+                .create_tx(&mut tx, input, WithdrawStatus::Pending)
+                .await?;
+
+            tx.commit().await.map_err(|e| {
+                error!(
+                    "Failed to commit pending withdraw for user_id {}: {e}",
+                    input.user_id
+                );
+                ErrorResponse::from(AppError::SqlxError(e))
+            })?;
+
+            info!(
+                "Withdraw {} for user_id: {} is pending approval",
+                withdraw.withdraw_id, input.user_id
+            );
+
+            return Ok(ApiResponse {
+                status: "success".to_string(),
+                message: "Withdraw submitted for approval".to_string(),
+                data: WithdrawResponse::from(withdraw),
+            });
+        }
+
+        let withdraw = self
+            .withdraw_repository
+            .create_tx(&mut tx, input, WithdrawStatus::Completed)
             .await?;
 
-        info!(
-            "Saldo balance updated for user_id: {}. New balance: {new_total_balance}",
-            input.user_id
-        );
+        self.saldo_repository
+            .update_saldo_withdraw_tx(
+                &mut tx,
+                &UpdateSaldoWithdraw {
+                    user_id: input.user_id,
+                    currency: input.currency,
+                    withdraw_amount: Some(input.withdraw_amount),
+                    withdraw_time: Some(Utc::now()),
+                    total_balance: new_total_balance,
+                    reference_id: Some(withdraw.withdraw_id),
+                },
+            )
+            .await?;
 
-        let withdraw_create_result = self.withdraw_repository.create(input).await?;
+        tx.commit().await.map_err(|e| {
+            error!(
+                "Failed to commit withdraw for user_id {}: {e}",
+                input.user_id
+            );
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        let withdraw = WithdrawResponse::from(withdraw);
+        self.events.withdraw_created(&withdraw);
 
         info!(
-            "Withdraw created successfully for user_id: {}",
+            "Withdraw created for user_id: {}. New balance: {new_total_balance}",
             input.user_id
         );
 
         Ok(ApiResponse {
             status: "success".to_string(),
             message: "Withdraw created successfully".to_string(),
-            data: withdraw_create_result.into(),
+            data: withdraw,
         })
     }
 
     async fn update_withdraw(
         &self,
         input: &UpdateWithdrawRequest,
+        user_id: i32,
+        role: Role,
     ) -> Result<ApiResponse<Option<WithdrawResponse>>, ErrorResponse> {
-        let _withdraw = self
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!(
+                "Failed to begin update transaction for withdraw {}: {e}",
+                input.withdraw_id
+            );
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        // Locked so two concurrent updates can't both apply a difference
+        // against the same old amount.
+        let withdraw = self
             .withdraw_repository
-            .find_by_id(input.withdraw_id)
-            .await
-            .map_err(|_| {
+            .find_by_id_for_update_tx(&mut tx, input.withdraw_id)
+            .await?
+            .ok_or_else(|| {
+                error!("Withdraw with id {} not found", input.withdraw_id);
                 ErrorResponse::from(AppError::NotFound(format!(
                     "Withdraw with id {} not found",
                     input.withdraw_id
                 )))
             })?;
 
+        if !role.can_access(user_id, &[withdraw.user_id]) {
+            error!(
+                "User {user_id} is not allowed to update withdraw {}",
+                input.withdraw_id
+            );
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to update withdraw {}",
+                input.withdraw_id
+            ))));
+        }
+
+        // The difference lands on the owner's saldo, so a body naming some
+        // other user must not get that far.
+        if withdraw.user_id != input.user_id {
+            error!(
+                "Withdraw {} belongs to user {}, not {}",
+                input.withdraw_id, withdraw.user_id, input.user_id
+            );
+            return Err(ErrorResponse::from(AppError::NotFound(format!(
+                "Withdraw with id {} not found for user {}",
+                input.withdraw_id, input.user_id
+            ))));
+        }
+
+        if !withdraw.status.is_settled() {
+            return Err(ErrorResponse::from(AppError::Conflict(format!(
+                "Withdraw with id {} is {} and cannot be updated",
+                input.withdraw_id, withdraw.status
+            ))));
+        }
+
         let saldo = self
             .saldo_repository
-            .find_by_user_id(input.user_id)
-            .await
-            .map_err(|_| {
-                ErrorResponse::from(AppError::NotFound(format!(
-                    "Saldo with user_id {} not found",
-                    input.user_id
-                )))
+            .find_by_user_and_currency_for_update(&mut tx, withdraw.user_id, withdraw.currency)
+            .await?
+            .ok_or_else(|| {
+                error!(
+                    "{} saldo not found for user_id: {}",
+                    withdraw.currency, withdraw.user_id
+                );
+                ErrorResponse::from(AppError::NotFound("Saldo not found".to_string()))
             })?;
 
-        let saldo_ref = saldo.as_ref().ok_or_else(|| {
-            ErrorResponse::from(AppError::NotFound("Saldo not found".to_string()))
+        saldo.ensure_not_frozen().inspect_err(|_| {
+            error!(
+                "Withdraw update rejected: saldo of user {} is frozen",
+                withdraw.user_id
+            );
         })?;
 
-        let new_total_balance = saldo_ref.total_balance - input.withdraw_amount;
+        // Raising a withdraw past the approval threshold needs the same
+        // sign-off as creating one that large: the old debit is refunded and
+        // the new amount is only taken once an admin approves it.
+        let needs_approval = input.withdraw_amount > withdraw.withdraw_amount
+            && self.balance_policy.requires_approval(input.withdraw_amount);
+
+        let delta = if needs_approval {
+            withdraw.withdraw_amount
+        } else {
+            withdraw
+                .withdraw_amount
+                .checked_sub(input.withdraw_amount)?
+        };
 
-        let updated_withdraw = self.withdraw_repository.update(input).await;
+        info!(
+            "Updating withdraw {}: {} -> {}, balance delta {delta}",
+            input.withdraw_id, withdraw.withdraw_amount, input.withdraw_amount
+        );
 
-        if let Err(err) = updated_withdraw {
-            let _rollback_saldo = self
-                .saldo_repository
-                .update_saldo_withdraw(&UpdateSaldoWithdraw {
-                    user_id: input.user_id,
-                    withdraw_amount: None,
-                    withdraw_time: None,
-                    total_balance: saldo_ref.total_balance,
-                })
+        if delta.is_negative() {
+            let remaining = saldo.total_balance.checked_add(delta)?;
+
+            if remaining.is_negative() {
+                error!(
+                    "Insufficient balance to raise withdraw {} for user_id: {}",
+                    input.withdraw_id, withdraw.user_id
+                );
+                return Err(ErrorResponse::from(AppError::InsufficientBalance {
+                    required: Money::ZERO.checked_sub(delta)?,
+                    available: saldo.total_balance,
+                }));
+            }
+
+            self.balance_policy
+                .ensure_minimum(withdraw.user_id, remaining)?;
+        }
+
+        let mut updated_withdraw = self.withdraw_repository.update_tx(&mut tx, input).await?;
+
+        if delta != Money::ZERO {
+            self.adjust_balance_tx(&mut tx, &withdraw, delta).await?;
+        }
+
+        if needs_approval {
+            updated_withdraw = self
+                .withdraw_repository
+                .set_status_tx(&mut tx, input.withdraw_id, WithdrawStatus::Pending)
                 .await?;
+        }
 
-            error!("Rollback: Saldo reverted due to withdraw update failure");
+        tx.commit().await.map_err(|e| {
+            error!(
+                "Failed to commit update of withdraw {}: {e}",
+                input.withdraw_id
+            );
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
 
-            return Err(err.into());
+        let message = if needs_approval {
+            "Withdraw resubmitted for approval"
+        } else {
+            "Withdraw updated successfully"
+        };
+
+        info!("{message} for id: {}", input.withdraw_id);
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: message.to_string(),
+            data: Some(updated_withdraw.into()),
+        })
+    }
+
+    async fn delete_withdraw(
+        &self,
+        id: i32,
+        user_id: i32,
+        role: Role,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin delete transaction for withdraw {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        let withdraw = self.withdraw_repository.delete_tx(&mut tx, id).await?;
+
+        // Returning drops the transaction, which rolls the delete back.
+        if !role.can_access(user_id, &[withdraw.user_id]) {
+            error!("User {user_id} is not allowed to delete withdraw {id}");
+            return Err(ErrorResponse::from(AppError::Forbidden(format!(
+                "You are not allowed to delete withdraw {id}"
+            ))));
         }
 
-        let _update_saldo = self
-            .saldo_repository
-            .update_saldo_withdraw(&UpdateSaldoWithdraw {
-                user_id: input.user_id,
-                withdraw_amount: Some(input.withdraw_amount),
-                withdraw_time: Some(Utc::now()),
-                total_balance: new_total_balance,
-            })
+        // Pending and rejected withdraws never debited the balance.
+        if withdraw.status.is_settled() {
+            self.adjust_balance_tx(&mut tx, &withdraw, withdraw.withdraw_amount)
+                .await?;
+        }
+
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit delete of withdraw {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        info!("Withdraw deleted successfully for id: {id}");
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Withdraw deleted successfully".to_string(),
+            data: (),
+        })
+    }
+
+    async fn restore_withdraw(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<WithdrawResponse>, ErrorResponse> {
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin restore transaction for withdraw {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        let withdraw = self.withdraw_repository.restore_tx(&mut tx, id).await?;
+
+        if withdraw.status.is_settled() {
+            self.adjust_balance_tx(
+                &mut tx,
+                &withdraw,
+                Money::new(-withdraw.withdraw_amount.minor_units()),
+            )
             .await?;
+        }
+
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit restore of withdraw {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        info!("Withdraw restored successfully for id: {id}");
 
         Ok(ApiResponse {
             status: "success".to_string(),
-            message: "Withdraw updated successfully".to_string(),
-            data: Some(updated_withdraw.unwrap().into()),
+            message: "Withdraw restored successfully".to_string(),
+            data: WithdrawResponse::from(withdraw),
         })
     }
 
-    async fn delete_withdraw(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
-        let user = self.user_repository.find_by_id(id).await.map_err(|_| {
-            ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
+    async fn approve_withdraw(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<WithdrawResponse>, ErrorResponse> {
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin approve transaction for withdraw {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
         })?;
 
-        let existing = self
+        let Some(withdraw) = self
             .withdraw_repository
-            .find_by_user(user.unwrap().user_id)
+            .find_pending_for_update_tx(&mut tx, id)
+            .await?
+        else {
+            return Err(self.not_pending(id).await);
+        };
+
+        // The balance may have changed since the request was made, so the
+        // checks from create_withdraw are repeated against the locked row.
+        let saldo = self
+            .saldo_repository
+            .find_by_user_and_currency_for_update(&mut tx, withdraw.user_id, withdraw.currency)
+            .await?
+            .ok_or_else(|| {
+                error!(
+                    "{} saldo not found for user_id: {}",
+                    withdraw.currency, withdraw.user_id
+                );
+                ErrorResponse::from(AppError::NotFound("Saldo not found".to_string()))
+            })?;
+
+        saldo.ensure_not_frozen()?;
+
+        if saldo.total_balance < withdraw.withdraw_amount {
+            error!(
+                "Insufficient balance to approve withdraw {id} for user_id: {}",
+                withdraw.user_id
+            );
+            return Err(ErrorResponse::from(AppError::InsufficientBalance {
+                required: withdraw.withdraw_amount,
+                available: saldo.total_balance,
+            }));
+        }
+
+        let new_total_balance = saldo.total_balance.checked_sub(withdraw.withdraw_amount)?;
+
+        self.balance_policy
+            .ensure_minimum(withdraw.user_id, new_total_balance)?;
+
+        self.saldo_repository
+            .update_saldo_withdraw_tx(
+                &mut tx,
+                &UpdateSaldoWithdraw {
+                    user_id: withdraw.user_id,
+                    currency: withdraw.currency,
+                    withdraw_amount: Some(withdraw.withdraw_amount),
+                    withdraw_time: Some(Utc::now()),
+                    total_balance: new_total_balance,
+                    reference_id: Some(id),
+                },
+            )
             .await?;
 
-        match existing {
-            Some(_) => {
-                self.withdraw_repository
-                    .delete(existing.unwrap().withdraw_id)
-                    .await?;
+        let withdraw = self
+            .withdraw_repository
+            .set_status_tx(&mut tx, id, WithdrawStatus::Approved)
+            .await?;
 
-                info!("Withdraw deleted successfully for id: {id}");
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit approval of withdraw {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
 
-                Ok(ApiResponse {
-                    status: "success".to_string(),
-                    message: "Withdraw deleted successfully".to_string(),
-                    data: (),
-                })
-            }
-            None => {
-                error!("Withdraw with id {id} not found");
-                Err(ErrorResponse::from(AppError::NotFound(format!(
-                    "Withdraw with id {id} not found",
-                ))))
-            }
+        let withdraw = WithdrawResponse::from(withdraw);
+        self.events.withdraw_created(&withdraw);
+
+        info!(
+            "Withdraw {id} approved for user_id: {}. New balance: {new_total_balance}",
+            withdraw.user_id
+        );
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Withdraw approved successfully".to_string(),
+            data: withdraw,
+        })
+    }
+
+    async fn reject_withdraw(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<WithdrawResponse>, ErrorResponse> {
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("Failed to begin reject transaction for withdraw {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        if self
+            .withdraw_repository
+            .find_pending_for_update_tx(&mut tx, id)
+            .await?
+            .is_none()
+        {
+            return Err(self.not_pending(id).await);
         }
+
+        let withdraw = self
+            .withdraw_repository
+            .set_status_tx(&mut tx, id, WithdrawStatus::Rejected)
+            .await?;
+
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit rejection of withdraw {id}: {e}");
+            ErrorResponse::from(AppError::SqlxError(e))
+        })?;
+
+        info!("Withdraw {id} rejected");
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Withdraw rejected successfully".to_string(),
+            data: WithdrawResponse::from(withdraw),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        domain::{
+            currency::Currency, request::pagination::DEFAULT_MAX_PAGE_SIZE, response::ErrorDetails,
+        },
+        test_support::{
+            FakePostgres, MockSaldoRepositoryTrait, MockUserRepositoryTrait,
+            MockWebhookNotifierTrait, MockWithdrawRepositoryTrait, events, saldo,
+        },
+    };
+
+    #[tokio::test]
+    async fn create_withdraw_reports_insufficient_balance() {
+        let postgres = FakePostgres::start().await;
+
+        let mut saldos = MockSaldoRepositoryTrait::new();
+        saldos
+            .expect_find_by_user_and_currency_for_update()
+            .times(1)
+            .returning(|_, user_id, currency| Ok(Some(saldo(user_id, currency, 60_000))));
+
+        // No withdraw repository expectations: nothing may be recorded.
+        let service = WithdrawService::new(
+            postgres.pool.clone(),
+            Arc::new(MockWithdrawRepositoryTrait::new()),
+            Arc::new(saldos),
+            Arc::new(MockUserRepositoryTrait::new()),
+            events(MockWebhookNotifierTrait::new()),
+            BalancePolicy::default(),
+            DEFAULT_MAX_PAGE_SIZE,
+        );
+
+        let err = service
+            .create_withdraw(
+                &CreateWithdrawRequest {
+                    user_id: 1,
+                    withdraw_amount: Money::new(100_000),
+                    withdraw_time: Utc::now(),
+                    currency: Currency::IDR,
+                    note: None,
+                },
+                1,
+                Role::User,
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.error_code.as_deref(), Some("insufficient_balance"));
+
+        let Some(ErrorDetails::InsufficientBalance(details)) = err.details.map(|d| *d) else {
+            panic!("expected insufficient balance details");
+        };
+        assert_eq!(details.required, Money::new(100_000));
+        assert_eq!(details.available, Money::new(60_000));
+        assert_eq!(details.shortfall, Money::new(40_000));
+        assert_eq!(postgres.statements().await, ["BEGIN", "ROLLBACK"]);
     }
 }
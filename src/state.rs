@@ -1,26 +1,117 @@
 use crate::{
-    abstract_trait::{DynHashing, DynJwtService},
-    config::{ConnectionPool, Hashing, JwtConfig},
+    abstract_trait::{
+        DynApiKeyRepository, DynApiKeyService, DynEmailVerificationRepository, DynHashing,
+        DynJwtService, DynMailer, DynPasswordResetRepository, DynUserRepository,
+        DynWebhookNotifier, DynWebhookRepository,
+    },
+    config::{
+        CleanupTask, Config, ConnectionPool, Hashing, JwtConfig, LogMailer, Metrics,
+        TransferScheduler, WebhookDispatcher, WebhookWorker,
+    },
+    domain::currency::Currency,
+    middleware::{
+        amounts::set_default_locale, fields::set_strict_fields, load_shed::LoadShedder,
+        rate_limit::LoginRateLimiter,
+    },
+    repository::{
+        api_key::ApiKeyRepository, email_verification::EmailVerificationRepository,
+        password_reset::PasswordResetRepository, user::UserRepository, webhook::WebhookRepository,
+    },
+    service::{api_key::ApiKeyService, events::TransactionEvents},
     utils::DependenciesInject,
 };
-use std::sync::Arc;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 #[derive(Clone)]
 pub struct AppState {
+    pub db_pool: ConnectionPool,
     pub di_container: DependenciesInject,
     pub jwt_service: DynJwtService,
+    pub login_rate_limiter: Arc<LoginRateLimiter>,
+    pub load_shedder: Arc<LoadShedder>,
+    pub api_key_service: DynApiKeyService,
+    pub metrics: Arc<Metrics>,
+    // Taken and spawned by `AppRouter::serve`.
+    pub webhook_worker: Arc<Mutex<Option<WebhookWorker>>>,
+    pub cleanup_task: Arc<Mutex<Option<CleanupTask>>>,
+    pub transfer_scheduler: Arc<Mutex<Option<TransferScheduler>>>,
 }
 
 impl AppState {
-    pub fn new(pool: ConnectionPool, jwt_secret: &str) -> Self {
-        let jwt_service = Arc::new(JwtConfig::new(jwt_secret)) as DynJwtService;
-        let hashing = Arc::new(Hashing::new()) as DynHashing;
+    pub fn new(pool: ConnectionPool, config: &Config) -> Self {
+        let jwt_service = Arc::new(JwtConfig::new(
+            &config.jwt_secret,
+            config.jwt_expiry_seconds,
+        )) as DynJwtService;
+        let hashing = Arc::new(Hashing::with_params(
+            config.password_hash_algorithm,
+            config.password_hash_cost,
+        )) as DynHashing;
+        let mailer = Arc::new(LogMailer::new()) as DynMailer;
+
+        let metrics = Arc::new(Metrics::new());
+
+        set_strict_fields(config.strict_fields);
+        set_default_locale(config.locale);
+        Currency::set_base(config.base_currency);
+
+        let webhook_repository =
+            Arc::new(WebhookRepository::new(pool.clone())) as DynWebhookRepository;
+        let (webhook_dispatcher, webhook_worker) = WebhookDispatcher::new(webhook_repository);
+        let events = TransactionEvents::new(
+            metrics.clone(),
+            Arc::new(webhook_dispatcher) as DynWebhookNotifier,
+        );
+
+        let di_container = DependenciesInject::new(
+            pool.clone(),
+            hashing,
+            jwt_service.clone(),
+            mailer,
+            events,
+            config,
+        );
+
+        let login_rate_limiter = Arc::new(LoginRateLimiter::new(
+            config.login_max_attempts,
+            Duration::from_secs(config.login_window_secs),
+        ));
+
+        let load_shedder = Arc::new(LoadShedder::new(pool.clone(), config.db_shed_min_available));
+
+        let api_key_service = Arc::new(ApiKeyService::new(
+            Arc::new(ApiKeyRepository::new(pool.clone())) as DynApiKeyRepository,
+            Arc::new(UserRepository::new(pool.clone())) as DynUserRepository,
+            Duration::from_secs(config.api_signature_max_skew_secs),
+        )) as DynApiKeyService;
+
+        let cleanup_task = CleanupTask::new(
+            Arc::new(PasswordResetRepository::new(pool.clone())) as DynPasswordResetRepository,
+            Arc::new(EmailVerificationRepository::new(pool.clone()))
+                as DynEmailVerificationRepository,
+            jwt_service.clone(),
+            Duration::from_secs(config.cleanup_interval_secs),
+        );
 
-        let di_container = DependenciesInject::new(pool, hashing, jwt_service.clone());
+        let transfer_scheduler = TransferScheduler::new(
+            di_container.transfer_service.clone(),
+            Duration::from_secs(config.scheduled_transfer_interval_secs),
+        );
 
         Self {
+            db_pool: pool,
             di_container,
             jwt_service,
+            login_rate_limiter,
+            load_shedder,
+            api_key_service,
+            metrics,
+            webhook_worker: Arc::new(Mutex::new(Some(webhook_worker))),
+            cleanup_task: Arc::new(Mutex::new(Some(cleanup_task))),
+            transfer_scheduler: Arc::new(Mutex::new(Some(transfer_scheduler))),
         }
     }
 }
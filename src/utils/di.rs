@@ -1,17 +1,38 @@
 use crate::{
     abstract_trait::{
-        DynAuthService, DynHashing, DynJwtService, DynSaldoRepository, DynSaldoService,
-        DynTopupRepository, DynTopupService, DynTransferRepository, DynTransferService,
-        DynUserRepository, DynUserService, DynWithdrawRepository, DynWithdrawService,
+        DynAuthService, DynEmailVerificationRepository, DynHashing, DynHistoryRepository,
+        DynHistoryService, DynJwtService, DynLoginAuditRepository, DynMailer,
+        DynPasswordResetRepository, DynReconciliationRepository, DynReconciliationService,
+        DynSaldoRepository, DynSaldoService, DynTopupRepository, DynTopupService,
+        DynTransferRepository, DynTransferService, DynUserRepository, DynUserService,
+        DynWebhookRepository, DynWebhookService, DynWithdrawRepository, DynWithdrawService,
     },
-    config::ConnectionPool,
+    config::{Config, ConnectionPool},
     repository::{
-        saldo::SaldoRepository, topup::TopupRepository, transfer::TransferRepository,
-        user::UserRepository, withdraw::WithdrawRepository,
+        email_verification::EmailVerificationRepository,
+        history::HistoryRepository,
+        instrumented::{InstrumentedSaldoRepository, InstrumentedTransferRepository, QueryTimer},
+        login_audit::LoginAuditRepository,
+        password_reset::PasswordResetRepository,
+        reconciliation::ReconciliationRepository,
+        saldo::SaldoRepository,
+        topup::TopupRepository,
+        transfer::TransferRepository,
+        user::UserRepository,
+        webhook::WebhookRepository,
+        withdraw::WithdrawRepository,
     },
     service::{
-        auth::AuthService, saldo::SaldoService, topup::TopupService, transfer::TransferService,
-        user::UserService, withdraw::WithdrawService,
+        auth::{AuthRepositories, AuthService},
+        events::TransactionEvents,
+        history::HistoryService,
+        reconciliation::ReconciliationService,
+        saldo::SaldoService,
+        topup::TopupService,
+        transfer::TransferService,
+        user::UserService,
+        webhook::WebhookService,
+        withdraw::WithdrawService,
     },
 };
 use std::sync::Arc;
@@ -24,54 +45,138 @@ pub struct DependenciesInject {
     pub topup_service: DynTopupService,
     pub transfer_service: DynTransferService,
     pub withdraw_service: DynWithdrawService,
+    pub history_service: DynHistoryService,
+    pub reconciliation_service: DynReconciliationService,
+    pub webhook_service: DynWebhookService,
 }
 
 impl DependenciesInject {
-    pub fn new(pool: ConnectionPool, hashing: DynHashing, jwt_config: DynJwtService) -> Self {
+    pub fn new(
+        pool: ConnectionPool,
+        hashing: DynHashing,
+        jwt_config: DynJwtService,
+        mailer: DynMailer,
+        events: TransactionEvents,
+        config: &Config,
+    ) -> Self {
+        let balance_policy = config.balance_policy();
+        let query_timer = QueryTimer::new(config.pool_settings().slow_query_threshold);
+
         let user_repository = Arc::new(UserRepository::new(pool.clone())) as DynUserRepository;
 
-        let user_service =
-            Arc::new(UserService::new(user_repository.clone(), hashing.clone())) as DynUserService;
+        let password_reset_repository =
+            Arc::new(PasswordResetRepository::new(pool.clone())) as DynPasswordResetRepository;
 
-        let auth_service = Arc::new(AuthService::new(
-            user_repository.clone(),
-            hashing.clone(),
-            jwt_config,
-        )) as DynAuthService;
+        let email_verification_repository = Arc::new(EmailVerificationRepository::new(pool.clone()))
+            as DynEmailVerificationRepository;
+
+        let login_audit_repository =
+            Arc::new(LoginAuditRepository::new(pool.clone())) as DynLoginAuditRepository;
 
-        let saldo_repository = Arc::new(SaldoRepository::new(pool.clone())) as DynSaldoRepository;
+        let saldo_repository = Arc::new(InstrumentedSaldoRepository::new(
+            Arc::new(SaldoRepository::new(pool.clone())),
+            query_timer,
+        )) as DynSaldoRepository;
 
         let topup_repository = Arc::new(TopupRepository::new(pool.clone())) as DynTopupRepository;
 
-        let transfer_repository =
-            Arc::new(TransferRepository::new(pool.clone())) as DynTransferRepository;
+        let transfer_repository = Arc::new(InstrumentedTransferRepository::new(
+            Arc::new(TransferRepository::new(pool.clone())),
+            query_timer,
+        )) as DynTransferRepository;
 
         let withdraw_repository =
             Arc::new(WithdrawRepository::new(pool.clone())) as DynWithdrawRepository;
 
+        let auth_service = Arc::new(AuthService::new(
+            pool.clone(),
+            AuthRepositories {
+                users: user_repository.clone(),
+                password_resets: password_reset_repository,
+                email_verifications: email_verification_repository,
+                login_audits: login_audit_repository,
+                saldos: saldo_repository.clone(),
+                topups: topup_repository.clone(),
+                transfers: transfer_repository.clone(),
+                withdraws: withdraw_repository.clone(),
+            },
+            hashing.clone(),
+            jwt_config,
+            mailer,
+            config.require_email_verification,
+        )) as DynAuthService;
+
+        let user_service = Arc::new(UserService::new(
+            pool.clone(),
+            user_repository.clone(),
+            hashing.clone(),
+            saldo_repository.clone(),
+            topup_repository.clone(),
+            transfer_repository.clone(),
+            withdraw_repository.clone(),
+            config.max_page_size,
+        )) as DynUserService;
+
         let saldo_service = Arc::new(SaldoService::new(
             user_repository.clone(),
             saldo_repository.clone(),
+            config.max_page_size,
         )) as DynSaldoService;
 
         let topup_service = Arc::new(TopupService::new(
+            pool.clone(),
             topup_repository.clone(),
             saldo_repository.clone(),
             user_repository.clone(),
+            events.clone(),
+            config.topup_limits(),
+            config.max_page_size,
         )) as DynTopupService;
 
         let transfer_service = Arc::new(TransferService::new(
+            pool.clone(),
             transfer_repository.clone(),
             saldo_repository.clone(),
             user_repository.clone(),
+            events.clone(),
+            balance_policy,
+            config.fee_schedule(),
+            config.max_page_size,
         )) as DynTransferService;
 
         let withdraw_service = Arc::new(WithdrawService::new(
+            pool.clone(),
             withdraw_repository.clone(),
             saldo_repository.clone(),
             user_repository.clone(),
+            events,
+            balance_policy,
+            config.max_page_size,
         )) as DynWithdrawService;
 
+        let history_repository =
+            Arc::new(HistoryRepository::new(pool.clone())) as DynHistoryRepository;
+
+        let history_service = Arc::new(HistoryService::new(
+            history_repository,
+            user_repository.clone(),
+            config.max_page_size,
+        )) as DynHistoryService;
+
+        let reconciliation_repository =
+            Arc::new(ReconciliationRepository::new(pool.clone())) as DynReconciliationRepository;
+
+        let reconciliation_service = Arc::new(ReconciliationService::new(
+            reconciliation_repository,
+            user_repository.clone(),
+        )) as DynReconciliationService;
+
+        let webhook_repository =
+            Arc::new(WebhookRepository::new(pool.clone())) as DynWebhookRepository;
+
+        let webhook_service =
+            Arc::new(WebhookService::new(webhook_repository)) as DynWebhookService;
+
         Self {
             auth_service,
             user_service,
@@ -79,6 +184,9 @@ impl DependenciesInject {
             topup_service,
             transfer_service,
             withdraw_service,
+            history_service,
+            reconciliation_service,
+            webhook_service,
         }
     }
 }
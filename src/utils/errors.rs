@@ -1,10 +1,14 @@
+use std::borrow::Cow;
+
 use anyhow::Error as AnyhowError;
 use bcrypt::BcryptError;
 use jsonwebtoken::errors::Error as JwtError;
 use serde::Serialize;
 use sqlx::Error as SqlxError;
 use thiserror::Error;
-use validator::ValidationErrors;
+use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
+
+use crate::{domain::money::Money, utils::format_validation_errors};
 
 #[derive(Debug, Error)]
 pub enum AppError {
@@ -17,12 +21,25 @@ pub enum AppError {
     #[error("Invalid credentials")]
     InvalidCredentials,
 
+    // A missing, expired or revoked session, as opposed to a failed login.
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
     #[error("Token expired")]
     TokenExpiredError,
 
     #[error("Token validation error")]
     TokenValidationError,
 
+    #[error("Invalid API key")]
+    InvalidApiKey,
+
+    #[error("Invalid request signature")]
+    InvalidSignature,
+
+    #[error("Stale request: {0}")]
+    StaleRequest(String),
+
     #[error("Token generation error: {0}")]
     TokenGenerationError(#[from] JwtError),
 
@@ -32,11 +49,33 @@ pub enum AppError {
     #[error("Not found: {0}")]
     NotFound(String),
 
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     #[error("Email already exists")]
     EmailAlreadyExists,
 
-    #[error("Validation error: {0}")]
-    ValidationError(ValidationErrors),
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("noc_transfer is already in use")]
+    NocTransferTaken,
+
+    // Every rejected input, whether from `#[validate]` rules or a check made
+    // in code; see `AppError::validation` and `AppError::invalid_field`.
+    #[error("Validation error: {}", format_validation_errors(.0))]
+    Validation(ValidationErrors),
+
+    // `required` is what the operation would debit, `available` the current
+    // balance; both surface in the error response.
+    #[error("Insufficient balance: required {required}, available {available}")]
+    InsufficientBalance { required: Money, available: Money },
+
+    #[error("Too many requests: {0}")]
+    TooManyRequests(String),
+
+    #[error("Service unavailable: {0}")]
+    ServiceUnavailable(String),
 
     #[error("Internal error: {0}")]
     InternalError(String),
@@ -45,6 +84,25 @@ pub enum AppError {
     Custom(String),
 }
 
+impl AppError {
+    // A rule that isn't about one input, reported under `__all__` like the
+    // `ExtraValidate` rules.
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::invalid_field("__all__", message)
+    }
+
+    pub fn invalid_field(field: impl Into<String>, message: impl Into<String>) -> Self {
+        let mut errors = ValidationErrors::new();
+        errors.errors_mut().insert(
+            Cow::Owned(field.into()),
+            ValidationErrorsKind::Field(vec![
+                ValidationError::new("invalid").with_message(Cow::Owned(message.into())),
+            ]),
+        );
+        Self::Validation(errors)
+    }
+}
+
 impl From<AnyhowError> for AppError {
     fn from(err: AnyhowError) -> Self {
         AppError::InternalError(err.to_string())
@@ -0,0 +1,178 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::utils::AppError;
+
+// Languages error messages are available in. English is the default and
+// the language the detailed messages are written in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    Indonesian,
+}
+
+impl Language {
+    fn from_tag(tag: &str) -> Option<Self> {
+        // Only the primary subtag matters: `id-ID` and `id` are the same here.
+        let primary = tag.split('-').next().unwrap_or_default().trim();
+
+        if primary.eq_ignore_ascii_case("en") {
+            Some(Language::English)
+        } else if primary.eq_ignore_ascii_case("id") {
+            Some(Language::Indonesian)
+        } else {
+            None
+        }
+    }
+
+    // Picks the supported language with the highest quality value from an
+    // `Accept-Language` header, keeping header order on ties.
+    pub fn from_accept_language(header: &str) -> Self {
+        Self::preferred(header).unwrap_or_default()
+    }
+
+    // Like `from_accept_language`, but `None` when the header names no
+    // supported language instead of falling back to English.
+    pub fn preferred(header: &str) -> Option<Self> {
+        let mut best: Option<(Language, f32)> = None;
+
+        for item in header.split(',') {
+            let mut parts = item.split(';');
+            let Some(language) = parts.next().and_then(Language::from_tag) else {
+                continue;
+            };
+
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())
+                .unwrap_or(0.0);
+
+            if quality > 0.0 && best.is_none_or(|(_, best_quality)| quality > best_quality) {
+                best = Some((language, quality));
+            }
+        }
+
+        best.map(|(language, _)| language)
+    }
+}
+
+impl FromStr for Language {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Language::from_tag(s).ok_or_else(|| AppError::Custom(format!("Unknown language: {s}")))
+    }
+}
+
+// Identifies an error message independently of its wording, so it can be
+// looked up in the caller's language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageId {
+    NotFound,
+    Forbidden,
+    Unauthorized,
+    InvalidCredentials,
+    TokenExpired,
+    TokenInvalid,
+    InvalidApiKey,
+    InvalidSignature,
+    EmailAlreadyExists,
+    NocTransferTaken,
+    ValidationFailed,
+    InsufficientBalance,
+    TooManyRequests,
+    InternalError,
+}
+
+pub fn message(id: MessageId, language: Language) -> &'static str {
+    use Language::{English, Indonesian};
+    use MessageId::*;
+
+    match (id, language) {
+        (NotFound, English) => "The requested resource was not found",
+        (NotFound, Indonesian) => "Data yang diminta tidak ditemukan",
+        (Forbidden, English) => "You do not have access to this resource",
+        (Forbidden, Indonesian) => "Anda tidak memiliki akses ke data ini",
+        (Unauthorized, English) => "Authentication is required",
+        (Unauthorized, Indonesian) => "Autentikasi diperlukan",
+        (InvalidCredentials, English) => "Invalid credentials",
+        (InvalidCredentials, Indonesian) => "Email atau kata sandi salah",
+        (TokenExpired, English) => "Token has expired",
+        (TokenExpired, Indonesian) => "Token sudah kedaluwarsa",
+        (TokenInvalid, English) => "Token validation failed",
+        (TokenInvalid, Indonesian) => "Validasi token gagal",
+        (InvalidApiKey, English) => "Invalid API key",
+        (InvalidApiKey, Indonesian) => "API key tidak valid",
+        (InvalidSignature, English) => "Invalid request signature",
+        (InvalidSignature, Indonesian) => "Tanda tangan permintaan tidak valid",
+        (EmailAlreadyExists, English) => "Email already exists",
+        (EmailAlreadyExists, Indonesian) => "Email sudah terdaftar",
+        (NocTransferTaken, English) => "noc_transfer is already in use",
+        (NocTransferTaken, Indonesian) => "noc_transfer sudah digunakan",
+        (ValidationFailed, English) => "The request contains invalid fields",
+        (ValidationFailed, Indonesian) => "Permintaan berisi data yang tidak valid",
+        (InsufficientBalance, English) => "Insufficient balance",
+        (InsufficientBalance, Indonesian) => "Saldo tidak mencukupi",
+        (TooManyRequests, English) => "Too many requests, please try again later",
+        (TooManyRequests, Indonesian) => "Terlalu banyak permintaan, silakan coba lagi nanti",
+        (InternalError, English) => "An internal error occurred",
+        (InternalError, Indonesian) => "Terjadi kesalahan pada server",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_or_unsupported_languages_fall_back_to_english() {
+        assert_eq!(Language::from_accept_language(""), Language::English);
+        assert_eq!(Language::from_accept_language("fr, de"), Language::English);
+        assert_eq!(Language::from_accept_language("*"), Language::English);
+    }
+
+    #[test]
+    fn region_subtags_are_ignored() {
+        assert_eq!(
+            Language::from_accept_language("id-ID"),
+            Language::Indonesian
+        );
+        assert_eq!(Language::from_accept_language("ID"), Language::Indonesian);
+    }
+
+    #[test]
+    fn highest_quality_supported_language_wins() {
+        assert_eq!(
+            Language::from_accept_language("fr;q=1.0, en;q=0.5, id;q=0.8"),
+            Language::Indonesian
+        );
+        assert_eq!(
+            Language::from_accept_language("id;q=0.3, en"),
+            Language::English
+        );
+        assert_eq!(
+            Language::from_accept_language("id;q=0, en;q=0.1"),
+            Language::English
+        );
+    }
+
+    #[test]
+    fn ties_keep_header_order() {
+        assert_eq!(
+            Language::from_accept_language("id, en"),
+            Language::Indonesian
+        );
+        assert_eq!(Language::from_accept_language("en, id"), Language::English);
+    }
+
+    #[test]
+    fn preferred_is_none_without_a_supported_language() {
+        assert_eq!(Language::preferred("fr, de"), None);
+        assert_eq!(Language::preferred(""), None);
+        assert_eq!(Language::preferred("fr, id"), Some(Language::Indonesian));
+    }
+}
@@ -1,10 +1,24 @@
 mod di;
 mod errors;
+mod messages;
 mod method_validator;
 mod random_vcc;
+mod reset_token;
+mod retry;
+mod signature;
+mod statement;
 mod tracing;
+mod validation;
 
 pub use self::di::DependenciesInject;
 pub use self::errors::AppError;
-pub use self::random_vcc::random_vcc;
-pub use self::tracing::tracing;
+pub use self::messages::{Language, MessageId, message};
+pub use self::random_vcc::{MAX_NOC_ATTEMPTS, is_valid_noc, mask_noc, random_vcc, with_unique_noc};
+pub use self::reset_token::{generate_reset_token, hash_reset_token};
+pub use self::retry::{is_transient, retry_transient};
+pub use self::signature::{
+    generate_webhook_secret, sign_request, sign_webhook_payload, verify_request_signature,
+};
+pub use self::statement::{Statement, render_statement_pdf};
+pub use self::tracing::{LogFormat, tracing};
+pub use self::validation::{FieldErrors, format_validation_errors, validation_error_map};
@@ -1,10 +1,17 @@
 use rand::Rng;
+use tracing::warn;
+
+use crate::utils::AppError;
+
+// Length of a generated `noc_transfer`: a leading 4, 15 random digits and a
+// Luhn check digit.
+const NOC_LENGTH: usize = 17;
 
 pub fn random_vcc() -> Result<String, &'static str> {
     let mut rng = rand::rng();
 
     let mut random_number = String::new();
-    for _ in 0..15 {
+    for _ in 0..NOC_LENGTH - 2 {
         random_number.push_str(&rng.random_range(0..10).to_string());
     }
 
@@ -15,9 +22,28 @@ pub fn random_vcc() -> Result<String, &'static str> {
     Ok(credit_card_number)
 }
 
+// Keeps only the last four digits, e.g. `**** **** **** 1234`.
+pub fn mask_noc(noc: &str) -> String {
+    let hidden = noc.chars().count().saturating_sub(4);
+    let last_four: String = noc.chars().skip(hidden).collect();
+    format!("**** **** **** {last_four}")
+}
+
+pub fn is_valid_noc(noc: &str) -> bool {
+    noc.len() == NOC_LENGTH
+        && noc.bytes().all(|b| b.is_ascii_digit())
+        && luhn_sum(noc, false).is_multiple_of(10)
+}
+
 fn calculate_check_digit(number: &str) -> u32 {
+    // The check digit will be appended on the right, so every position shifts
+    // by one and doubling starts at the payload's last digit.
+    (10 - luhn_sum(number, true) % 10) % 10
+}
+
+fn luhn_sum(number: &str, double_last: bool) -> u32 {
     let mut sum = 0;
-    let mut alternate = false;
+    let mut alternate = double_last;
 
     for digit_char in number.chars().rev() {
         let mut digit = digit_char.to_digit(10).expect("Invalid digit in number");
@@ -33,5 +59,132 @@ fn calculate_check_digit(number: &str) -> u32 {
         alternate = !alternate;
     }
 
-    (10 - (sum % 10)) % 10
+    sum
+}
+
+// A 15-digit random body makes even a single collision unlikely, so a few
+// attempts are plenty; running out points at a broken generator.
+pub const MAX_NOC_ATTEMPTS: u32 = 5;
+
+// Runs `store` with freshly generated `noc_transfer` values until it no
+// longer fails with `AppError::NocTransferTaken`.
+pub async fn with_unique_noc<T, F, Fut>(mut store: F) -> Result<T, AppError>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<T, AppError>>,
+{
+    for attempt in 1..=MAX_NOC_ATTEMPTS {
+        let noc_transfer = random_vcc().map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        match store(noc_transfer).await {
+            Err(AppError::NocTransferTaken) => {
+                warn!("⚠️ noc_transfer collision on attempt {attempt}, regenerating");
+            }
+            result => return result,
+        }
+    }
+
+    Err(AppError::InternalError(format!(
+        "Could not generate a unique noc_transfer after {MAX_NOC_ATTEMPTS} attempts"
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masking_keeps_only_the_last_four_digits() {
+        assert_eq!(mask_noc("1234567890123456"), "**** **** **** 3456");
+        assert_eq!(mask_noc("12"), "**** **** **** 12");
+    }
+
+    #[tokio::test]
+    async fn collisions_are_retried_with_a_fresh_noc() {
+        let mut tried = Vec::new();
+
+        let stored = with_unique_noc(|noc_transfer| {
+            tried.push(noc_transfer.clone());
+            let attempt = tried.len();
+
+            async move {
+                if attempt <= 2 {
+                    Err(AppError::NocTransferTaken)
+                } else {
+                    Ok(noc_transfer)
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(tried.len(), 3);
+        assert_eq!(stored, tried[2]);
+        assert_ne!(tried[0], tried[1]);
+        assert!(tried.iter().all(|noc| is_valid_noc(noc)));
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let mut attempts = 0;
+
+        let err = with_unique_noc(|_| {
+            attempts += 1;
+            async { Err::<(), _>(AppError::NocTransferTaken) }
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(attempts, MAX_NOC_ATTEMPTS);
+        assert!(matches!(err, AppError::InternalError(_)), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn other_errors_are_not_retried() {
+        let mut attempts = 0;
+
+        let err = with_unique_noc(|_| {
+            attempts += 1;
+            async { Err::<(), _>(AppError::EmailAlreadyExists) }
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(attempts, 1);
+        assert!(matches!(err, AppError::EmailAlreadyExists), "{err:?}");
+    }
+
+    #[test]
+    fn generated_nocs_pass_luhn() {
+        for _ in 0..1000 {
+            let noc = random_vcc().unwrap();
+
+            assert_eq!(noc.len(), NOC_LENGTH, "{noc}");
+            assert!(noc.starts_with('4'), "{noc}");
+            assert!(is_valid_noc(&noc), "{noc}");
+        }
+    }
+
+    #[test]
+    fn tampered_digit_fails_luhn() {
+        let noc = random_vcc().unwrap();
+
+        for position in 0..NOC_LENGTH {
+            let mut digits = noc.clone().into_bytes();
+            digits[position] = b'0' + (digits[position] - b'0' + 1) % 10;
+            let tampered = String::from_utf8(digits).unwrap();
+
+            assert!(!is_valid_noc(&tampered), "{noc} -> {tampered}");
+        }
+    }
+
+    #[test]
+    fn malformed_nocs_are_rejected() {
+        let noc = random_vcc().unwrap();
+
+        assert!(!is_valid_noc(&noc[..NOC_LENGTH - 1]));
+        assert!(!is_valid_noc(&format!("{noc}0")));
+        assert!(!is_valid_noc(&format!("x{}", &noc[1..])));
+        assert!(!is_valid_noc(""));
+    }
 }
@@ -0,0 +1,114 @@
+use std::time::Duration;
+use tracing::warn;
+
+use crate::utils::AppError;
+
+const MAX_ATTEMPTS: u32 = 3;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(50);
+
+// SQLSTATEs worth another try: serialization failure and deadlock. Class 08
+// (connection exceptions) is matched by prefix below.
+const TRANSIENT_SQLSTATES: &[&str] = &["40001", "40P01"];
+
+// Errors that may succeed when the same statement is simply run again.
+// Constraint violations and other logical errors never do.
+pub fn is_transient(error: &AppError) -> bool {
+    let AppError::SqlxError(e) = error else {
+        return false;
+    };
+
+    match e {
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut => true,
+        sqlx::Error::Database(db_err) => db_err.code().is_some_and(|code| {
+            TRANSIENT_SQLSTATES.contains(&code.as_ref()) || code.starts_with("08")
+        }),
+        _ => false,
+    }
+}
+
+// Runs `op` again with exponential backoff while it fails transiently. `op`
+// must be safe to repeat, e.g. a whole transaction rather than one statement
+// inside it.
+pub async fn retry_transient<T, F, Fut>(operation: &str, mut op: F) -> Result<T, AppError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, AppError>>,
+{
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 1;
+
+    loop {
+        match op().await {
+            Err(e) if attempt < MAX_ATTEMPTS && is_transient(&e) => {
+                warn!(
+                    "⚠️ {operation} failed transiently (attempt {attempt}/{MAX_ATTEMPTS}), retrying in {backoff:?}: {e}"
+                );
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn fails_twice_then_succeeds() {
+        let mut attempts = 0;
+
+        let value = retry_transient("test op", || {
+            attempts += 1;
+            let attempt = attempts;
+
+            async move {
+                if attempt <= 2 {
+                    Err(AppError::SqlxError(sqlx::Error::PoolTimedOut))
+                } else {
+                    Ok(42)
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(attempts, 3);
+        assert_eq!(value, 42);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let mut attempts = 0;
+
+        let err = retry_transient("test op", || {
+            attempts += 1;
+            async { Err::<(), _>(AppError::SqlxError(sqlx::Error::PoolTimedOut)) }
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(attempts, MAX_ATTEMPTS);
+        assert!(is_transient(&err), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn logical_errors_are_not_retried() {
+        let mut attempts = 0;
+
+        let err = retry_transient("test op", || {
+            attempts += 1;
+            async { Err::<(), _>(AppError::SqlxError(sqlx::Error::RowNotFound)) }
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(attempts, 1);
+        assert!(
+            matches!(err, AppError::SqlxError(sqlx::Error::RowNotFound)),
+            "{err:?}"
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use chrono::{DateTime, Utc};
+use printpdf::{
+    BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference,
+};
+
+use crate::{
+    domain::{money::Money, request::HistoryKind, response::history::HistoryEntryResponse},
+    model::history::BalanceSnapshot,
+    utils::{AppError, mask_noc},
+};
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 15.0;
+const LINE_HEIGHT: f32 = 6.0;
+const LAYER: &str = "Statement";
+
+// Column offsets in mm for the transaction table.
+const COLUMNS: [f32; 5] = [MARGIN, 55.0, 90.0, 125.0, 160.0];
+
+pub struct Statement {
+    pub holder: String,
+    pub noc_transfer: String,
+    pub from: Option<DateTime<Utc>>,
+    pub to: DateTime<Utc>,
+    pub opening: Vec<BalanceSnapshot>,
+    pub closing: Vec<BalanceSnapshot>,
+    // Oldest first.
+    pub entries: Vec<HistoryEntryResponse>,
+}
+
+struct Writer {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    y: f32,
+}
+
+impl Writer {
+    fn new(title: &str) -> Result<Self, AppError> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), LAYER);
+        let regular = doc
+            .add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(render_error)?;
+        let bold = doc
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(render_error)?;
+        let layer = doc.get_page(page).get_layer(layer);
+
+        Ok(Self {
+            doc,
+            layer,
+            regular,
+            bold,
+            y: PAGE_HEIGHT - MARGIN,
+        })
+    }
+
+    fn text(&self, x: f32, size: f32, bold: bool, text: &str) {
+        let font = if bold { &self.bold } else { &self.regular };
+        self.layer.use_text(text, size, Mm(x), Mm(self.y), font);
+    }
+
+    fn line(&mut self, size: f32, bold: bool, text: &str) {
+        self.text(MARGIN, size, bold, text);
+        self.advance(LINE_HEIGHT);
+    }
+
+    fn row(&mut self, bold: bool, cells: [&str; 5]) {
+        for (x, cell) in COLUMNS.into_iter().zip(cells) {
+            self.text(x, 9.0, bold, cell);
+        }
+        self.advance(LINE_HEIGHT);
+    }
+
+    fn advance(&mut self, by: f32) {
+        self.y -= by;
+
+        if self.y < MARGIN {
+            let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), LAYER);
+            self.layer = self.doc.get_page(page).get_layer(layer);
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+    }
+
+    fn balances(&mut self, heading: &str, balances: &[BalanceSnapshot]) {
+        self.line(11.0, true, heading);
+
+        if balances.is_empty() {
+            self.line(10.0, false, "No saldo");
+        }
+
+        for snapshot in balances {
+            self.line(
+                10.0,
+                false,
+                &format!("{} {}", snapshot.currency, snapshot.balance),
+            );
+        }
+
+        self.advance(LINE_HEIGHT / 2.0);
+    }
+}
+
+fn render_error(e: printpdf::Error) -> AppError {
+    AppError::InternalError(format!("Failed to render statement: {e}"))
+}
+
+fn format_date(date: DateTime<Utc>) -> String {
+    date.format("%Y-%m-%d %H:%M").to_string()
+}
+
+pub fn render_statement_pdf(statement: &Statement) -> Result<Vec<u8>, AppError> {
+    let mut writer = Writer::new("Account statement")?;
+
+    let (mut money_in, mut money_out) = (Money::ZERO, Money::ZERO);
+    for entry in &statement.entries {
+        match entry.kind {
+            HistoryKind::Topup | HistoryKind::TransferIn => {
+                money_in = money_in.checked_add(entry.amount)?
+            }
+            HistoryKind::TransferOut | HistoryKind::Withdraw => {
+                money_out = money_out.checked_add(entry.amount)?
+            }
+        }
+    }
+
+    writer.line(16.0, true, "Account statement");
+    writer.advance(LINE_HEIGHT / 2.0);
+    writer.line(
+        10.0,
+        false,
+        &format!("Account holder: {}", statement.holder),
+    );
+    writer.line(
+        10.0,
+        false,
+        &format!("Account: {}", mask_noc(&statement.noc_transfer)),
+    );
+    writer.line(
+        10.0,
+        false,
+        &format!(
+            "Period: {} to {}",
+            statement
+                .from
+                .map(format_date)
+                .unwrap_or_else(|| "account opening".to_string()),
+            format_date(statement.to)
+        ),
+    );
+    writer.advance(LINE_HEIGHT);
+
+    writer.balances("Opening balance", &statement.opening);
+
+    writer.line(11.0, true, "Transactions");
+    writer.row(
+        true,
+        ["Date", "Type", "Amount", "Counterparty", "Reference"],
+    );
+
+    if statement.entries.is_empty() {
+        writer.line(10.0, false, "No transactions in this period");
+    }
+
+    for entry in &statement.entries {
+        let counterparty = entry
+            .counterparty
+            .map(|id| id.to_string())
+            .unwrap_or_default();
+
+        writer.row(
+            false,
+            [
+                &format_date(entry.timestamp),
+                entry.kind.as_str(),
+                &entry.amount.to_string(),
+                &counterparty,
+                &entry.id.to_string(),
+            ],
+        );
+    }
+    writer.advance(LINE_HEIGHT / 2.0);
+
+    writer.line(11.0, true, "Totals");
+    writer.line(10.0, false, &format!("Money in: {money_in}"));
+    writer.line(10.0, false, &format!("Money out: {money_out}"));
+    writer.advance(LINE_HEIGHT / 2.0);
+
+    writer.balances("Closing balance", &statement.closing);
+
+    writer.doc.save_to_bytes().map_err(render_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+
+    use super::*;
+    use crate::domain::currency::Currency;
+
+    fn statement(entries: usize) -> Statement {
+        let to = Utc.with_ymd_and_hms(2025, 3, 31, 0, 0, 0).unwrap();
+        let balance = |amount| {
+            vec![BalanceSnapshot {
+                currency: Currency::base(),
+                balance: Money::new(amount),
+            }]
+        };
+
+        Statement {
+            holder: "Alice Anders".to_string(),
+            noc_transfer: "1234567890123456".to_string(),
+            from: Some(to - Duration::days(30)),
+            to,
+            opening: balance(0),
+            closing: balance(entries as i64 * 10000),
+            entries: (0..entries)
+                .map(|i| HistoryEntryResponse {
+                    id: i as i32 + 1,
+                    kind: HistoryKind::Topup,
+                    amount: Money::new(10000),
+                    timestamp: to - Duration::hours(i as i64),
+                    counterparty: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn assert_is_pdf(bytes: &[u8]) {
+        assert!(
+            bytes.starts_with(b"%PDF"),
+            "{:?}",
+            &bytes[..bytes.len().min(16)]
+        );
+        assert!(
+            bytes.trim_ascii_end().ends_with(b"%%EOF"),
+            "missing trailer"
+        );
+    }
+
+    #[test]
+    fn an_empty_period_still_renders() {
+        assert_is_pdf(&render_statement_pdf(&statement(0)).unwrap());
+    }
+
+    // Enough rows to spill onto further pages.
+    #[test]
+    fn long_statements_render() {
+        assert_is_pdf(&render_statement_pdf(&statement(120)).unwrap());
+    }
+}
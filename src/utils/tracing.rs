@@ -1,26 +1,158 @@
+use core::fmt;
+use std::str::FromStr;
+
+use tracing::Subscriber;
 use tracing_subscriber::prelude::*;
-use tracing_subscriber::{EnvFilter, fmt};
+use tracing_subscriber::{
+    EnvFilter, Layer, fmt as tracing_fmt, fmt::MakeWriter, registry::LookupSpan,
+};
+
+use crate::utils::AppError;
+
+pub const DEFAULT_LOG_LEVEL: &str = "info";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Pretty,
+    Json,
+}
+
+impl LogFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogFormat::Pretty => "pretty",
+            LogFormat::Json => "json",
+        }
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pretty" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            other => Err(AppError::Custom(format!("Unknown log format: {other}"))),
+        }
+    }
+}
+
+// `RUST_LOG` wins over the configured level so a single run can be made
+// noisier without touching the config.
+pub fn tracing(format: LogFormat, level: Option<&str>) {
+    let filter_layer = EnvFilter::try_from_default_env()
+        .or_else(|_| EnvFilter::try_new(level.unwrap_or(DEFAULT_LOG_LEVEL)))
+        .unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_LEVEL));
+
+    let registry = tracing_subscriber::registry().with(filter_layer);
+
+    match format {
+        LogFormat::Pretty => registry
+            .with(
+                tracing_fmt::layer()
+                    .with_timer(tracing_fmt::time::uptime())
+                    .with_line_number(true)
+                    .with_level(true)
+                    .with_target(true)
+                    .with_ansi(true)
+                    .compact(),
+            )
+            .init(),
+        LogFormat::Json => registry.with(json_layer(std::io::stdout)).init(),
+    }
 
-pub fn tracing() {
-    // Layer untuk formatting output
-    let fmt_layer = fmt::layer()
-        .with_timer(fmt::time::uptime()) // Menampilkan waktu sejak start
+    tracing::info!("Tracing initialized ({format})");
+}
+
+// One object per line, with span fields flattened in, for log shippers.
+fn json_layer<S, W>(make_writer: W) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    tracing_fmt::layer()
+        .json()
+        .flatten_event(true)
+        .with_current_span(true)
+        .with_span_list(false)
         .with_line_number(true)
-        .with_level(true)
         .with_target(true)
-        .with_ansi(true) // Warna ANSI untuk terminal
-        .compact(); // Format compact (bisa diganti dengan .pretty() untuk output lebih rinci)
+        .with_writer(make_writer)
+}
 
-    // Layer untuk filtering level
-    let filter_layer = EnvFilter::try_from_default_env()
-        .or_else(|_| EnvFilter::try_new("info,my_crate=debug")) // Default ke info, tapi debug untuk crate Anda
-        .unwrap();
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
+    use serde_json::Value;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'w> MakeWriter<'w> for Captured {
+        type Writer = Captured;
+
+        fn make_writer(&'w self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn json_mode_emits_one_parseable_object_per_event() {
+        let captured = Captured::default();
+        let subscriber = tracing_subscriber::registry().with(json_layer(captured.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("request", request_id = "abc");
+            let _entered = span.enter();
+
+            tracing::info!(user_id = 7, "first");
+            tracing::warn!("second");
+        });
+
+        let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap_or_else(|e| panic!("{e}: {line}")))
+            .collect();
 
-    tracing_subscriber::registry()
-        .with(fmt_layer)
-        .with(filter_layer)
-        .init();
+        assert_eq!(lines.len(), 2, "{output}");
+        assert_eq!(lines[0]["level"], "INFO");
+        assert_eq!(lines[0]["message"], "first");
+        assert_eq!(lines[0]["user_id"], 7);
+        assert_eq!(lines[0]["span"]["request_id"], "abc");
+        assert_eq!(lines[1]["level"], "WARN");
+        assert_eq!(lines[1]["message"], "second");
+    }
 
-    // Log startup
-    tracing::info!("Tracing initialized");
+    #[test]
+    fn formats_parse() {
+        assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!("pretty".parse::<LogFormat>().unwrap(), LogFormat::Pretty);
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
 }
@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+
+use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
+
+pub type FieldErrors = BTreeMap<String, Vec<String>>;
+
+// Flattens nested struct/list errors into dotted paths such as
+// `recipients[1].amount`, so clients can map every message to an input.
+pub fn validation_error_map(errors: &ValidationErrors) -> FieldErrors {
+    let mut map = FieldErrors::new();
+    collect_errors(errors, None, &mut map);
+    map
+}
+
+pub fn format_validation_errors(errors: &ValidationErrors) -> String {
+    let messages: Vec<String> = validation_error_map(errors)
+        .into_iter()
+        .flat_map(|(field, messages)| {
+            messages
+                .into_iter()
+                .map(move |message| match field.as_str() {
+                    "__all__" => message,
+                    _ => format!("{field}: {message}"),
+                })
+        })
+        .collect();
+
+    if messages.is_empty() {
+        "Validation failed".to_string()
+    } else {
+        messages.join("; ")
+    }
+}
+
+fn collect_errors(errors: &ValidationErrors, prefix: Option<&str>, map: &mut FieldErrors) {
+    for (field, kind) in errors.errors() {
+        let path = match prefix {
+            Some(prefix) => format!("{prefix}.{field}"),
+            None => field.to_string(),
+        };
+
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                map.entry(path.clone())
+                    .or_default()
+                    .extend(field_errors.iter().map(|e| error_message(&path, e)));
+            }
+            ValidationErrorsKind::Struct(nested) => collect_errors(nested, Some(&path), map),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_errors(nested, Some(&format!("{path}[{index}]")), map);
+                }
+            }
+        }
+    }
+}
+
+fn error_message(field: &str, error: &ValidationError) -> String {
+    error
+        .message
+        .as_ref()
+        .map(|m| m.to_string())
+        .unwrap_or_else(|| match error.code.as_ref() {
+            "email" => "Invalid email format".to_string(),
+            "url" => "Invalid URL format".to_string(),
+            "length" => "Invalid length".to_string(),
+            "range" => "Value out of range".to_string(),
+            "must_match" => "Fields do not match".to_string(),
+            "custom" => "Custom validation failed".to_string(),
+            _ => format!("Invalid {field}"),
+        })
+}
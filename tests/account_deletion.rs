@@ -0,0 +1,129 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+use common::TestApp;
+
+// Live (not soft-deleted) rows the user still owns in `table`.
+async fn live_rows(app: &TestApp, table: &str, user_id: i32) -> i64 {
+    sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM {table} WHERE user_id = $1 AND deleted_at IS NULL"
+    ))
+    .bind(user_id)
+    .fetch_one(&app.pool)
+    .await
+    .expect("failed to count rows")
+}
+
+async fn delete_me(app: &TestApp, token: &str) -> (StatusCode, serde_json::Value) {
+    app.request(
+        Method::DELETE,
+        "/api/auth/me",
+        Some(token),
+        Some(json!({ "password": "password123" })),
+    )
+    .await
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn a_funded_account_cannot_be_deleted() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+
+    let (status, body) = delete_me(&app, &alice_token).await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body}");
+    assert!(
+        body["message"].as_str().unwrap().contains("withdraw"),
+        "{body}"
+    );
+
+    let (status, body) = app
+        .request(
+            Method::DELETE,
+            &format!("/api/users/{alice}"),
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body}");
+
+    assert_eq!(live_rows(&app, "users", alice).await, 1);
+    assert_eq!(live_rows(&app, "saldo", alice).await, 1);
+    assert_eq!(live_rows(&app, "topups", alice).await, 1);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn deleting_an_empty_account_cascades_to_its_records() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/saldos/transfer-all",
+            Some(&alice_token),
+            Some(json!({ "transfer_to": bob })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "sweep failed: {body}");
+
+    let (status, body) = delete_me(&app, &alice_token).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    assert_eq!(live_rows(&app, "users", alice).await, 0);
+    assert_eq!(live_rows(&app, "saldo", alice).await, 0);
+    assert_eq!(live_rows(&app, "topups", alice).await, 0);
+
+    // The settled transfer stays, and so does the money it moved.
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{bob}"),
+            Some(&bob_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["total_balance"], 110000, "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn only_admins_delete_other_accounts() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (alice, _) = app.register_and_login("alice@example.com").await;
+    let (_, bob_token) = app.register_and_login("bob@example.com").await;
+
+    let (status, body) = app
+        .request(
+            Method::DELETE,
+            &format!("/api/users/{alice}"),
+            Some(&bob_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+    assert_eq!(live_rows(&app, "users", alice).await, 1);
+
+    let (status, body) = app
+        .request(
+            Method::DELETE,
+            &format!("/api/users/{alice}"),
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(live_rows(&app, "users", alice).await, 0);
+    assert_eq!(live_rows(&app, "saldo", alice).await, 0);
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode, header},
+};
+use chrono::Utc;
+use http_body_util::BodyExt;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use example_sea_query_payment_gateway::utils::sign_request;
+
+use common::TestApp;
+
+const API_KEY: &str = "pk_test_alice";
+const SECRET: &str = "sk_test_alice";
+
+async fn issue_api_key(app: &TestApp, user_id: i32) {
+    sqlx::query("INSERT INTO api_keys (user_id, api_key, secret, name) VALUES ($1, $2, $3, $4)")
+        .bind(user_id)
+        .bind(API_KEY)
+        .bind(SECRET)
+        .bind("partner backend")
+        .execute(&app.pool)
+        .await
+        .expect("failed to insert the api key");
+}
+
+async fn signed(app: &TestApp, method: Method, path: &str, nonce: &str, body: Value) -> StatusCode {
+    let body = body.to_string();
+    let timestamp = Utc::now().timestamp().to_string();
+    let signature = sign_request(
+        SECRET,
+        &timestamp,
+        nonce,
+        method.as_str(),
+        path,
+        body.as_bytes(),
+    );
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(method)
+                .uri(path)
+                .header(header::CONTENT_TYPE, "application/json")
+                .header("x-api-key", API_KEY)
+                .header("x-timestamp", timestamp)
+                .header("x-nonce", nonce)
+                .header("x-signature", signature)
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .expect("router is infallible");
+
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    assert!(
+        status != StatusCode::INTERNAL_SERVER_ERROR,
+        "{}",
+        String::from_utf8_lossy(&bytes)
+    );
+
+    status
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn session_only_routes_turn_api_keys_away() {
+    let app = TestApp::spawn().await;
+    let (alice, _) = app.register_and_login("alice@example.com").await;
+    issue_api_key(&app, alice).await;
+
+    // The key itself works on ordinary routes.
+    let status = signed(&app, Method::GET, "/api/auth/me", "nonce-1", json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let status = signed(&app, Method::POST, "/api/auth/logout", "nonce-2", json!({})).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let status = signed(
+        &app,
+        Method::DELETE,
+        "/api/auth/me",
+        "nonce-3",
+        json!({ "password": "password123" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // The account is still there.
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/auth/login",
+            None,
+            Some(json!({ "email": "alice@example.com", "password": "password123" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode, header},
+};
+use http_body_util::BodyExt;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use common::TestApp;
+
+async fn balance(app: &TestApp, user_id: i32, token: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{user_id}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+
+    body["data"]["total_balance"].clone()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn mixed_rows_report_their_own_outcome() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/topups/bulk",
+            Some(&admin_token),
+            Some(json!([
+                { "user_id": alice, "amount": 100000 },
+                { "user_id": 999999, "amount": 100000 },
+                { "user_id": bob, "amount": 20000 },
+            ])),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let results = body["data"].as_array().expect("per-row results");
+    assert_eq!(results.len(), 3, "{body}");
+
+    assert_eq!(results[0]["user_id"], alice, "{body}");
+    assert_eq!(results[0]["status"], "success", "{body}");
+    assert!(results[0]["topup_id"].is_i64(), "{body}");
+    assert!(results[0].get("error").is_none(), "{body}");
+
+    assert_eq!(results[1]["user_id"], 999999, "{body}");
+    assert_eq!(results[1]["status"], "failed", "{body}");
+    assert!(results[1].get("topup_id").is_none(), "{body}");
+    assert!(results[1]["error"].is_string(), "{body}");
+
+    assert_eq!(results[2]["status"], "success", "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 100000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 20000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn a_non_positive_amount_rejects_the_whole_batch() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/topups/bulk",
+            Some(&admin_token),
+            Some(json!([
+                { "user_id": alice, "amount": 100000 },
+                { "user_id": alice, "amount": -5 },
+            ])),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+
+    // Nothing was imported, so Alice still has no saldo at all.
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{alice}"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn rows_can_be_uploaded_as_csv() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/api/topups/bulk")
+                .header(header::AUTHORIZATION, format!("Bearer {admin_token}"))
+                .header(header::CONTENT_TYPE, "text/csv")
+                .body(Body::from(format!("user_id,amount\n{alice},30000\n")))
+                .unwrap(),
+        )
+        .await
+        .expect("router is infallible");
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"][0]["status"], "success", "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 30000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn batches_over_the_configured_cap_are_refused() {
+    let app = TestApp::spawn_with(|config| config.bulk_topup_max_rows = 1).await;
+    let admin_token = app.login_admin().await;
+    let (alice, _) = app.register_and_login("alice@example.com").await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/topups/bulk",
+            Some(&admin_token),
+            Some(json!([
+                { "user_id": alice, "amount": 100000 },
+                { "user_id": alice, "amount": 200000 },
+            ])),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{Value, json};
+
+use common::TestApp;
+
+async fn capabilities(app: &TestApp, token: &str) -> Value {
+    let (status, body) = app
+        .request(Method::GET, "/api/auth/me/capabilities", Some(token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "capabilities lookup failed: {body}");
+
+    body["data"].clone()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn frozen_user_cannot_move_money() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    app.topup(alice, &alice_token, 100000).await;
+
+    let before = capabilities(&app, &alice_token).await;
+    assert_eq!(before["can_transfer"], true, "{before}");
+    assert_eq!(before["can_withdraw"], true, "{before}");
+    assert_eq!(before["is_frozen"], false, "{before}");
+    assert_eq!(before["is_admin"], false, "{before}");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/api/saldos/user/{alice}/freeze"),
+            Some(&admin_token),
+            Some(json!({})),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "freeze failed: {body}");
+
+    let after = capabilities(&app, &alice_token).await;
+    assert_eq!(after["can_transfer"], false, "{after}");
+    assert_eq!(after["can_withdraw"], false, "{after}");
+    assert_eq!(after["is_frozen"], true, "{after}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn admin_is_reported_as_admin() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    let body = capabilities(&app, &admin_token).await;
+    assert_eq!(body["is_admin"], true, "{body}");
+    assert_eq!(body["requires_2fa"], false, "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn capabilities_require_a_token() {
+    let app = TestApp::spawn().await;
+
+    let (status, body) = app
+        .request(Method::GET, "/api/auth/me/capabilities", None, None)
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "{body}");
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use chrono::{Duration, Utc};
+
+use common::TestApp;
+
+async fn issue_reset(app: &TestApp, user_id: i32, token_hash: &str, expires_in: Duration) {
+    sqlx::query(
+        "INSERT INTO password_resets (user_id, token_hash, expires_at) VALUES ($1, $2, $3)",
+    )
+    .bind(user_id)
+    .bind(token_hash)
+    .bind((Utc::now() + expires_in).naive_utc())
+    .execute(&app.pool)
+    .await
+    .expect("failed to store reset token");
+}
+
+async fn issue_verification(app: &TestApp, user_id: i32, token_hash: &str, expires_in: Duration) {
+    sqlx::query(
+        "INSERT INTO email_verifications (user_id, token_hash, expires_at) VALUES ($1, $2, $3)",
+    )
+    .bind(user_id)
+    .bind(token_hash)
+    .bind((Utc::now() + expires_in).naive_utc())
+    .execute(&app.pool)
+    .await
+    .expect("failed to store verification token");
+}
+
+async fn token_hashes(app: &TestApp, table: &str) -> Vec<String> {
+    sqlx::query_scalar(&format!(
+        "SELECT token_hash FROM {table} ORDER BY token_hash"
+    ))
+    .fetch_all(&app.pool)
+    .await
+    .expect("failed to list tokens")
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn one_run_purges_rows_past_their_ttl() {
+    let app = TestApp::spawn().await;
+    let (alice, _) = app.register_and_login("alice@example.com").await;
+
+    // Registration already issued a live verification token; drop it so the
+    // tables hold only the rows below.
+    sqlx::query("DELETE FROM email_verifications")
+        .execute(&app.pool)
+        .await
+        .expect("failed to clear verifications");
+
+    issue_reset(&app, alice, "reset-expired", Duration::minutes(-1)).await;
+    issue_reset(&app, alice, "reset-live", Duration::minutes(30)).await;
+    issue_verification(&app, alice, "verify-expired", Duration::minutes(-1)).await;
+    issue_verification(&app, alice, "verify-live", Duration::hours(1)).await;
+
+    let task = app
+        .state
+        .cleanup_task
+        .lock()
+        .unwrap()
+        .take()
+        .expect("cleanup task");
+    assert_eq!(task.run_once().await, 2);
+
+    assert_eq!(token_hashes(&app, "password_resets").await, ["reset-live"]);
+    assert_eq!(
+        token_hashes(&app, "email_verifications").await,
+        ["verify-live"]
+    );
+}
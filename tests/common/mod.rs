@@ -0,0 +1,273 @@
+// Compiled into every test binary, each of which uses only some helpers.
+#![allow(dead_code)]
+
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::Arc,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    extract::connect_info::MockConnectInfo,
+    http::{Method, Request, StatusCode, header},
+};
+use http_body_util::BodyExt;
+use serde_json::{Value, json};
+use testcontainers_modules::{
+    postgres::Postgres,
+    testcontainers::{ContainerAsync, runners::AsyncRunner},
+};
+use tower::ServiceExt;
+
+use example_sea_query_payment_gateway::{
+    config::{Config, ConnectionManager, ConnectionPool, HashAlgorithm},
+    domain::{currency::Currency, money::Money, request::topup::MIN_TOPUP_AMOUNT},
+    handler::AppRouter,
+    state::AppState,
+    utils::LogFormat,
+};
+
+const ADMIN_EMAIL: &str = "admin@example.com";
+const ADMIN_PASSWORD: &str = "admin-password";
+
+// Everything `Config::init` would read from the environment, with cheap
+// password hashing and no background intervals worth waiting for.
+pub fn test_config(database_url: String) -> Config {
+    Config {
+        database_url,
+        db_max_connections: 5,
+        db_min_connections: 0,
+        db_acquire_timeout_secs: 30,
+        db_idle_timeout_secs: 600,
+        db_shed_min_available: None,
+        slow_query_threshold_ms: 500,
+        jwt_secret: "test-secret".to_string(),
+        jwt_expiry_seconds: 3600,
+        run_migrations: true,
+        host: IpAddr::V4(Ipv4Addr::LOCALHOST),
+        port: 0,
+        admin_email: None,
+        admin_password: None,
+        seed_admin: false,
+        password_hash_algorithm: HashAlgorithm::Bcrypt,
+        password_hash_cost: 4,
+        login_max_attempts: 5,
+        login_window_secs: 300,
+        metrics_port: None,
+        daily_withdraw_limit: None,
+        withdraw_approval_threshold: None,
+        max_transfer_amount: None,
+        minimum_balance: Money::ZERO,
+        allow_negative_reversal: false,
+        max_page_size: 100,
+        base_currency: Currency::IDR,
+        api_signature_max_skew_secs: 300,
+        require_email_verification: false,
+        cleanup_interval_secs: 3600,
+        scheduled_transfer_interval_secs: 3600,
+        bulk_topup_max_rows: 500,
+        min_topup_amount: Money::new(MIN_TOPUP_AMOUNT),
+        max_topup_amount: None,
+        transfer_fee_flat: Money::ZERO,
+        transfer_fee_basis_points: 0,
+        strict_fields: false,
+        locale: None,
+        log_format: LogFormat::Pretty,
+        log_level: None,
+    }
+}
+
+// A migrated throwaway Postgres and the app wired against it. The container
+// is removed when this is dropped.
+pub struct TestApp {
+    pub router: Router,
+    pub database_url: String,
+    // The app's own pool, for tests that need to tie up its connections.
+    pub pool: ConnectionPool,
+    // For driving background jobs, such as the transfer scheduler, by hand.
+    pub state: Arc<AppState>,
+    _postgres: ContainerAsync<Postgres>,
+}
+
+impl TestApp {
+    pub async fn spawn() -> Self {
+        Self::spawn_with(|_| {}).await
+    }
+
+    // Like `spawn`, with a chance to adjust the config first.
+    pub async fn spawn_with(configure: impl FnOnce(&mut Config)) -> Self {
+        let postgres = Postgres::default()
+            .start()
+            .await
+            .expect("failed to start postgres container");
+
+        let host = postgres.get_host().await.expect("container host");
+        let port = postgres
+            .get_host_port_ipv4(5432)
+            .await
+            .expect("container port");
+
+        let mut config = test_config(format!(
+            "postgres://postgres:postgres@{host}:{port}/postgres"
+        ));
+        configure(&mut config);
+
+        let pool = ConnectionManager::new_pool(
+            &config.database_url,
+            config.run_migrations,
+            config.pool_settings(),
+        )
+        .await
+        .expect("failed to create pool and run migrations");
+
+        let state = Arc::new(AppState::new(pool.clone(), &config));
+
+        state
+            .di_container
+            .user_service
+            .seed_admin(ADMIN_EMAIL, Some(ADMIN_PASSWORD))
+            .await
+            .expect("failed to seed admin");
+
+        // The login rate limiter keys on the peer address.
+        let router = AppRouter::build(state.clone(), false)
+            .layer(MockConnectInfo(SocketAddr::from(([127, 0, 0, 1], 40000))));
+
+        Self {
+            router,
+            database_url: config.database_url,
+            pool,
+            state,
+            _postgres: postgres,
+        }
+    }
+
+    pub async fn request(
+        &self,
+        method: Method,
+        uri: &str,
+        token: Option<&str>,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let mut builder = Request::builder().method(method).uri(uri);
+
+        if let Some(token) = token {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+
+        let request = match body {
+            Some(body) => builder
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => builder.body(Body::empty()),
+        }
+        .expect("valid request");
+
+        let response = self
+            .router
+            .clone()
+            .oneshot(request)
+            .await
+            .expect("router is infallible");
+
+        let status = response.status();
+        let bytes = response
+            .into_body()
+            .collect()
+            .await
+            .expect("response body")
+            .to_bytes();
+
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes)
+                .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into_owned()))
+        };
+
+        (status, body)
+    }
+
+    // Registers a user and logs them in, returning their id and access token.
+    pub async fn register_and_login(&self, email: &str) -> (i32, String) {
+        let password = "password123";
+
+        let (status, body) = self
+            .request(
+                Method::POST,
+                "/api/auth/register",
+                None,
+                Some(json!({
+                    "firstname": "Test",
+                    "lastname": "User",
+                    "email": email,
+                    "password": password,
+                    "confirm_password": password,
+                })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::CREATED, "register failed: {body}");
+
+        let user_id = body["data"]["id"].as_i64().expect("user id") as i32;
+
+        (user_id, self.login(email, password).await)
+    }
+
+    pub async fn login_admin(&self) -> String {
+        self.login(ADMIN_EMAIL, ADMIN_PASSWORD).await
+    }
+
+    async fn login(&self, email: &str, password: &str) -> String {
+        let (status, body) = self
+            .request(
+                Method::POST,
+                "/api/auth/login",
+                None,
+                Some(json!({ "email": email, "password": password })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "login failed: {body}");
+
+        body["data"]["access_token"]
+            .as_str()
+            .expect("access token")
+            .to_string()
+    }
+
+    pub async fn topup(&self, user_id: i32, token: &str, amount: i64) {
+        let (status, body) = self
+            .request(
+                Method::POST,
+                "/api/topups",
+                Some(token),
+                Some(json!({
+                    "user_id": user_id,
+                    "topup_no": format!("TOPUP-{user_id}-{amount}"),
+                    "topup_amount": amount,
+                    "topup_method": "bank_transfer",
+                })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::CREATED, "topup failed: {body}");
+    }
+
+    // Returns the created transfer.
+    pub async fn transfer(&self, from: i32, to: i32, token: &str, amount: i64) -> Value {
+        let (status, body) = self
+            .request(
+                Method::POST,
+                "/api/transfers",
+                Some(token),
+                Some(json!({
+                    "transfer_from": from,
+                    "transfer_to": to,
+                    "transfer_amount": amount,
+                })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::CREATED, "transfer failed: {body}");
+
+        body["data"].clone()
+    }
+}
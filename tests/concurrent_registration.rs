@@ -0,0 +1,45 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use futures::future::join_all;
+use serde_json::json;
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn simultaneous_registrations_with_one_email_create_one_user() {
+    let app = TestApp::spawn().await;
+
+    // All of them can pass the existence check before any insert lands, so
+    // the losers are only stopped by the unique index.
+    let responses = join_all((0..8).map(|_| {
+        app.request(
+            Method::POST,
+            "/api/auth/register",
+            None,
+            Some(json!({
+                "firstname": "Test",
+                "lastname": "User",
+                "email": "alice@example.com",
+                "password": "password123",
+                "confirm_password": "password123",
+            })),
+        )
+    }))
+    .await;
+
+    let created = responses
+        .iter()
+        .filter(|(status, _)| *status == StatusCode::CREATED)
+        .count();
+    assert_eq!(created, 1, "{responses:?}");
+
+    for (status, body) in responses
+        .iter()
+        .filter(|(status, _)| *status != StatusCode::CREATED)
+    {
+        assert_eq!(*status, StatusCode::CONFLICT, "{body}");
+        assert_eq!(body["message"], "Email already exists", "{body}");
+    }
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use futures::future::join_all;
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn simultaneous_topups_all_land_in_the_balance() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    // No saldo yet, so the first few also race to open it.
+    let amounts: Vec<i64> = (0..20).map(|i| 10000 + i * 1000).collect();
+
+    join_all(
+        amounts
+            .iter()
+            .map(|&amount| app.topup(alice, &alice_token, amount)),
+    )
+    .await;
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{alice}"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+    assert_eq!(
+        body["data"]["total_balance"],
+        amounts.iter().sum::<i64>(),
+        "{body}"
+    );
+}
@@ -0,0 +1,200 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::Utc;
+use serde_json::{Value, json};
+
+use common::TestApp;
+use example_sea_query_payment_gateway::domain::money::Money;
+
+async fn balance(app: &TestApp, user_id: i32, token: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{user_id}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+
+    body["data"]["total_balance"].clone()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn deleting_a_topup_takes_its_amount_back_out() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/topups",
+            Some(&alice_token),
+            Some(json!({
+                "user_id": alice,
+                "topup_no": "TOPUP-TO-DELETE",
+                "topup_amount": 50000,
+                "topup_method": "bank_transfer",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "topup failed: {body}");
+    let topup_id = body["data"]["topup_id"].clone();
+    assert_eq!(balance(&app, alice, &alice_token).await, 150000);
+
+    let (status, body) = app
+        .request(
+            Method::DELETE,
+            &format!("/api/topups/{topup_id}"),
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "delete failed: {body}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 100000);
+
+    // Restoring puts it back.
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/api/topups/{topup_id}/restore"),
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "restore failed: {body}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 150000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn deleting_a_spent_topup_is_refused() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    app.topup(bob, &bob_token, 10000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/topups",
+            Some(&alice_token),
+            Some(json!({
+                "user_id": alice,
+                "topup_no": "TOPUP-SPENT",
+                "topup_amount": 100000,
+                "topup_method": "bank_transfer",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "topup failed: {body}");
+    let topup_id = body["data"]["topup_id"].clone();
+
+    app.transfer(alice, bob, &alice_token, 80000).await;
+
+    let (status, body) = app
+        .request(
+            Method::DELETE,
+            &format!("/api/topups/{topup_id}"),
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 20000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn deleting_a_withdraw_gives_its_amount_back() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/withdraws",
+            Some(&alice_token),
+            Some(json!({
+                "user_id": alice,
+                "withdraw_amount": 60000,
+                "withdraw_time": Utc::now(),
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "withdraw failed: {body}");
+    let withdraw_id = body["data"]["withdraw_id"].clone();
+    assert_eq!(balance(&app, alice, &alice_token).await, 40000);
+
+    let (status, body) = app
+        .request(
+            Method::DELETE,
+            &format!("/api/withdraws/{withdraw_id}"),
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "delete failed: {body}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 100000);
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/api/withdraws/{withdraw_id}/restore"),
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "restore failed: {body}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 40000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn deleting_a_pending_withdraw_leaves_the_balance_alone() {
+    let app = TestApp::spawn_with(|config| {
+        config.withdraw_approval_threshold = Some(Money::new(50000));
+    })
+    .await;
+    let admin_token = app.login_admin().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/withdraws",
+            Some(&alice_token),
+            Some(json!({
+                "user_id": alice,
+                "withdraw_amount": 60000,
+                "withdraw_time": Utc::now(),
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "withdraw failed: {body}");
+    assert_eq!(body["data"]["status"], "pending", "{body}");
+    let withdraw_id = body["data"]["withdraw_id"].clone();
+    assert_eq!(balance(&app, alice, &alice_token).await, 100000);
+
+    let (status, body) = app
+        .request(
+            Method::DELETE,
+            &format!("/api/withdraws/{withdraw_id}"),
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "delete failed: {body}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 100000);
+}
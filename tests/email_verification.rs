@@ -0,0 +1,82 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::{Duration, Utc};
+use serde_json::{Value, json};
+
+use common::TestApp;
+use example_sea_query_payment_gateway::utils::hash_reset_token;
+
+const PASSWORD: &str = "password123";
+
+async fn register(app: &TestApp, email: &str) -> i32 {
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/auth/register",
+            None,
+            Some(json!({
+                "firstname": "Test",
+                "lastname": "User",
+                "email": email,
+                "password": PASSWORD,
+                "confirm_password": PASSWORD,
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "register failed: {body}");
+    assert_eq!(body["data"]["is_verified"], false, "{body}");
+
+    body["data"]["id"].as_i64().expect("user id") as i32
+}
+
+async fn login(app: &TestApp, email: &str) -> (StatusCode, Value) {
+    app.request(
+        Method::POST,
+        "/api/auth/login",
+        None,
+        Some(json!({ "email": email, "password": PASSWORD })),
+    )
+    .await
+}
+
+// Stores a verification token directly, since the mailer only logs the real
+// one.
+async fn issue_token(app: &TestApp, user_id: i32, token: &str) {
+    sqlx::query(
+        "INSERT INTO email_verifications (user_id, token_hash, expires_at) VALUES ($1, $2, $3)",
+    )
+    .bind(user_id)
+    .bind(hash_reset_token(token))
+    .bind((Utc::now() + Duration::hours(1)).naive_utc())
+    .execute(&app.pool)
+    .await
+    .expect("failed to store verification token");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn login_is_refused_until_the_email_is_verified() {
+    let app = TestApp::spawn_with(|config| config.require_email_verification = true).await;
+    let alice = register(&app, "alice@example.com").await;
+
+    let (status, body) = login(&app, "alice@example.com").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "{body}");
+    assert_eq!(body["message"], "email not verified", "{body}");
+
+    issue_token(&app, alice, "verify-me").await;
+    let (status, body) = app
+        .request(Method::GET, "/api/auth/verify?token=verify-me", None, None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "verification failed: {body}");
+    assert_eq!(body["data"]["is_verified"], true, "{body}");
+
+    let (status, body) = login(&app, "alice@example.com").await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    // Tokens are single use.
+    let (status, body) = app
+        .request(Method::GET, "/api/auth/verify?token=verify-me", None, None)
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn small_first_topup_opens_a_saldo() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    // Below the 50000 an admin-created saldo must start with.
+    app.topup(alice, &alice_token, 10000).await;
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{alice}"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+    assert_eq!(body["data"]["total_balance"], 10000, "{body}");
+    assert_eq!(body["data"]["currency"], "IDR", "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn later_topups_reuse_the_same_saldo() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &alice_token, 10000).await;
+    app.topup(alice, &alice_token, 15000).await;
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/users/{alice}"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo list failed: {body}");
+
+    let saldos = body["data"].as_array().expect("saldo list");
+    assert_eq!(saldos.len(), 1, "{body}");
+    assert_eq!(saldos[0]["total_balance"], 25000, "{body}");
+}
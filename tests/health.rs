@@ -0,0 +1,24 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn readiness_reports_the_pool() {
+    let app = TestApp::spawn_with(|config| config.db_max_connections = 4).await;
+
+    let (status, body) = app.request(Method::GET, "/health/db", None, None).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["database"], "up", "{body}");
+    assert_eq!(body["pool"]["max_connections"], 4, "{body}");
+
+    // A connection held elsewhere shows up as active.
+    let held = app.pool.acquire().await.expect("connection");
+    let (status, body) = app.request(Method::GET, "/health/db", None, None).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert!(body["pool"]["active"].as_u64().unwrap() >= 1, "{body}");
+    assert!(body["pool"]["size"].as_u64().unwrap() >= 2, "{body}");
+    drop(held);
+}
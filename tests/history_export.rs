@@ -0,0 +1,93 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{HeaderMap, Method, Request, StatusCode, header},
+};
+use chrono::Utc;
+use http_body_util::BodyExt;
+use serde_json::json;
+use tower::ServiceExt;
+
+use common::TestApp;
+
+async fn download(app: &TestApp, uri: &str, token: &str) -> (StatusCode, HeaderMap, Vec<u8>) {
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header(header::AUTHORIZATION, format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("router is infallible");
+
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+
+    (status, headers, bytes.to_vec())
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn csv_export_lists_a_topup_and_a_withdrawal() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/withdraws",
+            Some(&alice_token),
+            Some(json!({
+                "user_id": alice,
+                "withdraw_amount": 60000,
+                "withdraw_time": Utc::now(),
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "withdraw failed: {body}");
+
+    let (status, headers, bytes) = download(
+        &app,
+        &format!("/api/users/{alice}/history.csv"),
+        &alice_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(
+        headers[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/csv")
+    );
+    assert!(
+        headers[header::CONTENT_DISPOSITION]
+            .to_str()
+            .unwrap()
+            .starts_with("attachment")
+    );
+
+    let csv = String::from_utf8(bytes).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "date,type,amount,counterparty,reference");
+    assert_eq!(lines.len(), 3, "{csv}");
+    assert!(lines[1].contains(",withdraw,60000,,"), "{csv}");
+    assert!(lines[2].contains(",topup,100000,,"), "{csv}");
+
+    // The kind filter applies to the export as well.
+    let (status, _, bytes) = download(
+        &app,
+        &format!("/api/users/{alice}/history.csv?kind=topup"),
+        &alice_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let csv = String::from_utf8(bytes).unwrap();
+    assert_eq!(csv.lines().count(), 2, "{csv}");
+}
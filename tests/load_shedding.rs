@@ -0,0 +1,99 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode, header},
+};
+use serde_json::json;
+use tower::ServiceExt;
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn saturated_pool_sheds_money_moving_requests() {
+    let app = TestApp::spawn_with(|config| {
+        config.db_max_connections = 2;
+        config.db_shed_min_available = Some(1);
+    })
+    .await;
+    let (alice, token) = app.register_and_login("alice@example.com").await;
+
+    // Hold every connection the pool may open.
+    let mut held = Vec::new();
+    for _ in 0..2 {
+        held.push(app.pool.acquire().await.expect("connection"));
+    }
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/api/topups")
+        .header(header::AUTHORIZATION, format!("Bearer {token}"))
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            json!({
+                "user_id": alice,
+                "topup_no": "TOPUP-BUSY",
+                "topup_amount": 100000,
+                "topup_method": "bank_transfer",
+            })
+            .to_string(),
+        ))
+        .unwrap();
+
+    let response =
+        tokio::time::timeout(Duration::from_secs(5), app.router.clone().oneshot(request))
+            .await
+            .expect("request hung instead of being shed")
+            .expect("router is infallible");
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert!(response.headers().contains_key(header::RETRY_AFTER));
+
+    // Closed rather than dropped: a dropped connection goes back to the
+    // pool in the background and could still look busy below.
+    for conn in held {
+        conn.close().await.expect("close connection");
+    }
+
+    app.topup(alice, &token, 100000).await;
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn saturated_pool_still_serves_reads() {
+    let app = TestApp::spawn_with(|config| {
+        config.db_max_connections = 2;
+        config.db_shed_min_available = Some(2);
+    })
+    .await;
+    let (alice, token) = app.register_and_login("alice@example.com").await;
+
+    // One connection stays free for the read, yet fewer than the shedder
+    // wants are available.
+    let held = app.pool.acquire().await.expect("connection");
+
+    let (status, body) = app
+        .request(Method::GET, "/api/auth/me", Some(&token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/topups",
+            Some(&token),
+            Some(json!({
+                "user_id": alice,
+                "topup_no": "TOPUP-BUSY",
+                "topup_amount": 100000,
+                "topup_method": "bank_transfer",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE, "{body}");
+
+    held.close().await.expect("close connection");
+}
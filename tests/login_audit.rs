@@ -0,0 +1,60 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn failed_and_successful_logins_are_audited() {
+    let app = TestApp::spawn().await;
+
+    // Registering logs in once, which is the first audited success.
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/auth/login",
+            None,
+            Some(json!({ "email": "alice@example.com", "password": "wrong-password" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "{body}");
+
+    let (status, body) = app
+        .request(Method::GET, "/api/auth/me/logins", Some(&alice_token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "login history failed: {body}");
+
+    let logins = body["data"].as_array().expect("login list");
+    assert_eq!(logins.len(), 2, "{body}");
+
+    // Newest first.
+    assert_eq!(logins[0]["success"], false, "{body}");
+    assert_eq!(logins[1]["success"], true, "{body}");
+    for login in logins {
+        assert_eq!(login["ip"], "127.0.0.1", "{body}");
+        assert!(login["created_at"].is_string(), "{body}");
+    }
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/users/{alice}"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "get user failed: {body}");
+    assert!(body["data"]["last_login_at"].is_string(), "{body}");
+
+    // Another user's attempts never show up in Alice's history.
+    let (_, bob_token) = app.register_and_login("bob@example.com").await;
+    let (status, body) = app
+        .request(Method::GET, "/api/auth/me/logins", Some(&bob_token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "login history failed: {body}");
+    assert_eq!(body["data"].as_array().map(Vec::len), Some(1), "{body}");
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn sixth_failed_login_in_a_row_is_rate_limited() {
+    let app = TestApp::spawn().await;
+    app.register_and_login("alice@example.com").await;
+
+    let login = |password: &'static str| {
+        app.request(
+            Method::POST,
+            "/api/auth/login",
+            None,
+            Some(json!({ "email": "alice@example.com", "password": password })),
+        )
+    };
+
+    for attempt in 1..=5 {
+        let (status, body) = login("wrong-password").await;
+        assert_eq!(
+            status,
+            StatusCode::UNAUTHORIZED,
+            "attempt {attempt}: {body}"
+        );
+    }
+
+    let (status, body) = login("wrong-password").await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS, "{body}");
+
+    // The lockout also holds back the right password until the window ends.
+    let (status, body) = login("password123").await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS, "{body}");
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{Value, json};
+
+use common::TestApp;
+
+// A fresh session, as (access token, refresh token).
+async fn login(app: &TestApp, email: &str) -> (String, String) {
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/auth/login",
+            None,
+            Some(json!({ "email": email, "password": "password123" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "login failed: {body}");
+
+    (
+        body["data"]["access_token"].as_str().unwrap().to_string(),
+        body["data"]["refresh_token"].as_str().unwrap().to_string(),
+    )
+}
+
+async fn refresh(app: &TestApp, refresh_token: &str) -> (StatusCode, Value) {
+    app.request(
+        Method::POST,
+        "/api/auth/refresh",
+        None,
+        Some(json!({ "refresh_token": refresh_token })),
+    )
+    .await
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn token_stops_working_after_logout() {
+    let app = TestApp::spawn().await;
+    let (_, token) = app.register_and_login("alice@example.com").await;
+
+    let (status, body) = app
+        .request(Method::GET, "/api/auth/me", Some(&token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let (status, body) = app
+        .request(Method::POST, "/api/auth/logout", Some(&token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "logout failed: {body}");
+
+    let (status, body) = app
+        .request(Method::GET, "/api/auth/me", Some(&token), None)
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "{body}");
+
+    // Logging out twice with the same token is rejected too.
+    let (status, body) = app
+        .request(Method::POST, "/api/auth/logout", Some(&token), None)
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "{body}");
+
+    // Other sessions are unaffected.
+    let (_, other) = app.register_and_login("bob@example.com").await;
+    let (status, body) = app
+        .request(Method::GET, "/api/auth/me", Some(&other), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn logout_revokes_the_refresh_token_too() {
+    let app = TestApp::spawn().await;
+    app.register_and_login("alice@example.com").await;
+
+    let (access, refresh_token) = login(&app, "alice@example.com").await;
+
+    // Refreshed tokens belong to the same session as the login.
+    let (status, body) = refresh(&app, &refresh_token).await;
+    assert_eq!(status, StatusCode::OK, "refresh failed: {body}");
+    let refreshed = body["data"]["refresh_token"].as_str().unwrap().to_string();
+
+    let (status, body) = app
+        .request(Method::POST, "/api/auth/logout", Some(&access), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "logout failed: {body}");
+
+    let (status, body) = refresh(&app, &refreshed).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn a_refresh_token_works_only_once() {
+    let app = TestApp::spawn().await;
+    app.register_and_login("alice@example.com").await;
+
+    let (_, refresh_token) = login(&app, "alice@example.com").await;
+
+    let (status, body) = refresh(&app, &refresh_token).await;
+    assert_eq!(status, StatusCode::OK, "refresh failed: {body}");
+    let next = body["data"]["refresh_token"].as_str().unwrap().to_string();
+
+    let (status, body) = refresh(&app, &refresh_token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "{body}");
+
+    let (status, body) = refresh(&app, &next).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+}
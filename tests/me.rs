@@ -0,0 +1,50 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::Value;
+
+use common::TestApp;
+
+async fn me(app: &TestApp, token: &str, query: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/auth/me{query}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    body["data"].clone()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn me_embeds_the_saldo_only_when_asked() {
+    let app = TestApp::spawn().await;
+    let (alice, token) = app.register_and_login("alice@example.com").await;
+    app.topup(alice, &token, 100000).await;
+
+    // The default shape is the bare user.
+    let user = me(&app, &token, "").await;
+    assert_eq!(user["id"], alice, "{user}");
+    assert_eq!(user["email"], "alice@example.com", "{user}");
+    assert!(user.get("saldo").is_none(), "{user}");
+
+    let data = me(&app, &token, "?include=saldo").await;
+    assert_eq!(data["user"]["id"], alice, "{data}");
+    assert_eq!(data["saldo"]["user_id"], alice, "{data}");
+    assert_eq!(data["saldo"]["total_balance"], 100000, "{data}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn me_reports_no_saldo_before_the_first_topup() {
+    let app = TestApp::spawn().await;
+    let (alice, token) = app.register_and_login("alice@example.com").await;
+
+    let data = me(&app, &token, "?include=saldo").await;
+    assert_eq!(data["user"]["id"], alice, "{data}");
+    assert!(data["saldo"].is_null(), "{data}");
+}
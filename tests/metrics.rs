@@ -0,0 +1,56 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode, header},
+};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+use common::TestApp;
+use example_sea_query_payment_gateway::handler::AppRouter;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn scrape_counts_the_topup_and_its_request() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+
+    // `TestApp` serves the API as if metrics had their own port.
+    let (status, _) = app.request(Method::GET, "/metrics", None, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let response = AppRouter::build(app.state.clone(), true)
+        .oneshot(
+            Request::builder()
+                .uri("/metrics")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("router is infallible");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain"),
+    );
+
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body = String::from_utf8(bytes.to_vec()).unwrap();
+
+    assert!(body.contains("topups_created_total 1\n"), "{body}");
+    assert!(body.contains("transfers_created_total 0\n"), "{body}");
+    assert!(
+        body.lines()
+            .any(|line| line.starts_with("http_requests_total")
+                && line.contains(r#"method="POST""#)
+                && line.contains(r#"route="/api/topups""#)
+                && line.contains(r#"status="201""#)
+                && line.ends_with(" 1")),
+        "{body}"
+    );
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use sqlx::postgres::PgPoolOptions;
+
+use common::TestApp;
+use example_sea_query_payment_gateway::config::ConnectionManager;
+
+fn up_migration_count() -> usize {
+    std::fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/migrations"))
+        .expect("migrations directory")
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name().to_string_lossy().ends_with(".up.sql"))
+        .count()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn fresh_database_reports_every_migration_applied() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            "/api/admin/migrations",
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "migration status failed: {body}");
+
+    let data = &body["data"];
+    assert_eq!(data["applied_count"], up_migration_count(), "{body}");
+    assert_eq!(data["up_to_date"], true, "{body}");
+    assert_eq!(data["pending"], serde_json::json!([]), "{body}");
+    assert_eq!(data["unknown"], serde_json::json!([]), "{body}");
+
+    let (_, user_token) = app.register_and_login("alice@example.com").await;
+    let (status, body) = app
+        .request(
+            Method::GET,
+            "/api/admin/migrations",
+            Some(&user_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn migrating_a_newer_schema_fails_fast() {
+    let app = TestApp::spawn().await;
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(&app.database_url)
+        .await
+        .expect("failed to connect to postgres container");
+
+    // What the database looks like after a newer build migrated it.
+    sqlx::query(
+        "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time)
+         VALUES (99991231000000, 'from the future', TRUE, '\\x00', 0)",
+    )
+    .execute(&pool)
+    .await
+    .expect("failed to fake a newer migration");
+
+    let err = ConnectionManager::run_migrations(&pool)
+        .await
+        .expect_err("a newer schema must be rejected");
+    assert!(
+        err.to_string().contains("newer than this binary"),
+        "unexpected error: {err}"
+    );
+
+    let status = ConnectionManager::migration_status(&pool)
+        .await
+        .expect("migration status");
+    assert_eq!(status.unknown, vec![99991231000000]);
+    assert!(!status.up_to_date);
+}
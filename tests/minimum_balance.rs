@@ -0,0 +1,85 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::Utc;
+use example_sea_query_payment_gateway::domain::money::Money;
+use serde_json::{Value, json};
+
+use common::TestApp;
+
+async fn withdraw(app: &TestApp, user_id: i32, token: &str, amount: i64) -> (StatusCode, Value) {
+    app.request(
+        Method::POST,
+        "/api/withdraws",
+        Some(token),
+        Some(json!({
+            "user_id": user_id,
+            "withdraw_amount": amount,
+            "withdraw_time": Utc::now(),
+        })),
+    )
+    .await
+}
+
+async fn balance(app: &TestApp, user_id: i32, token: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{user_id}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+
+    body["data"]["total_balance"].clone()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn withdraw_may_leave_exactly_the_minimum_but_not_less() {
+    let app = TestApp::spawn_with(|config| config.minimum_balance = Money::new(10000)).await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &alice_token, 120000).await;
+
+    // Would leave 9,999.
+    let (status, body) = withdraw(&app, alice, &alice_token, 110001).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 120000);
+
+    let (status, body) = withdraw(&app, alice, &alice_token, 110000).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 10000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn transfer_may_leave_exactly_the_minimum_but_not_less() {
+    let app = TestApp::spawn_with(|config| config.minimum_balance = Money::new(10000)).await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 120000).await;
+    app.topup(bob, &bob_token, 10000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/transfers",
+            Some(&alice_token),
+            Some(json!({
+                "transfer_from": alice,
+                "transfer_to": bob,
+                "transfer_amount": 110001,
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 120000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 10000);
+
+    app.transfer(alice, bob, &alice_token, 110000).await;
+    assert_eq!(balance(&app, alice, &alice_token).await, 10000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 120000);
+}
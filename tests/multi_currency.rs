@@ -0,0 +1,168 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{Value, json};
+
+use common::TestApp;
+
+async fn topup(app: &TestApp, user_id: i32, token: &str, amount: i64, currency: &str) {
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/topups",
+            Some(token),
+            Some(json!({
+                "user_id": user_id,
+                "topup_no": format!("TOPUP-{user_id}-{currency}-{amount}"),
+                "topup_amount": amount,
+                "topup_method": "bank_transfer",
+                "currency": currency,
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "topup failed: {body}");
+}
+
+async fn transfer(
+    app: &TestApp,
+    from: i32,
+    to: i32,
+    token: &str,
+    currency: &str,
+) -> (StatusCode, Value) {
+    app.request(
+        Method::POST,
+        "/api/transfers",
+        Some(token),
+        Some(json!({
+            "transfer_from": from,
+            "transfer_to": to,
+            "transfer_amount": 50000,
+            "currency": currency,
+        })),
+    )
+    .await
+}
+
+// The user's balance per currency.
+async fn balances(app: &TestApp, user_id: i32, token: &str) -> Vec<(String, i64)> {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/users/{user_id}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo list failed: {body}");
+
+    let mut balances: Vec<(String, i64)> = body["data"]
+        .as_array()
+        .expect("saldo list")
+        .iter()
+        .map(|saldo| {
+            (
+                saldo["currency"].as_str().unwrap().to_string(),
+                saldo["total_balance"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    balances.sort();
+
+    balances
+}
+
+async fn saldo_page(app: &TestApp, user_id: i32, token: &str, query: &str) -> (StatusCode, Value) {
+    app.request(
+        Method::GET,
+        &format!("/api/saldos/users/{user_id}{query}"),
+        Some(token),
+        None,
+    )
+    .await
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn same_currency_transfer_moves_only_that_balance() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    topup(&app, alice, &alice_token, 200000, "IDR").await;
+    topup(&app, alice, &alice_token, 100000, "USD").await;
+    topup(&app, bob, &bob_token, 10000, "USD").await;
+
+    let (status, body) = transfer(&app, alice, bob, &alice_token, "USD").await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert_eq!(body["data"]["currency"], "USD", "{body}");
+
+    assert_eq!(
+        balances(&app, alice, &alice_token).await,
+        [("IDR".to_string(), 200000), ("USD".to_string(), 50000)]
+    );
+    assert_eq!(
+        balances(&app, bob, &bob_token).await,
+        [("USD".to_string(), 60000)]
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn cross_currency_transfer_is_rejected() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    topup(&app, alice, &alice_token, 100000, "USD").await;
+    topup(&app, bob, &bob_token, 10000, "IDR").await;
+
+    let (status, body) = transfer(&app, alice, bob, &alice_token, "USD").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert!(
+        body["message"]
+            .as_str()
+            .unwrap()
+            .contains("cross-currency transfers are not supported"),
+        "{body}"
+    );
+
+    assert_eq!(
+        balances(&app, alice, &alice_token).await,
+        [("USD".to_string(), 100000)]
+    );
+    assert_eq!(
+        balances(&app, bob, &bob_token).await,
+        [("IDR".to_string(), 10000)]
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn saldo_list_pages_over_a_users_currencies() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    // No topup yet, so no saldo rows at all.
+    let (status, body) = saldo_page(&app, alice, &alice_token, "").await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"], json!([]), "{body}");
+    assert_eq!(body["pagination"]["total_items"], 0, "{body}");
+
+    topup(&app, alice, &alice_token, 200000, "IDR").await;
+    topup(&app, alice, &alice_token, 100000, "USD").await;
+
+    let mut currencies = Vec::new();
+    for query in ["?page=1&page_size=1", "?page=2&page_size=1"] {
+        let (status, body) = saldo_page(&app, alice, &alice_token, query).await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert_eq!(body["pagination"]["total_items"], 2, "{body}");
+        assert_eq!(body["pagination"]["total_pages"], 2, "{body}");
+
+        let saldos = body["data"].as_array().expect("saldo list");
+        assert_eq!(saldos.len(), 1, "{body}");
+        currencies.push(saldos[0]["currency"].as_str().unwrap().to_string());
+    }
+    currencies.sort();
+    assert_eq!(currencies, ["IDR", "USD"]);
+}
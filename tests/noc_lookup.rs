@@ -0,0 +1,116 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+use common::TestApp;
+
+// Reads a user's full noc_transfer the way only an admin can.
+async fn noc_of(app: &TestApp, user_id: i32) -> String {
+    let admin_token = app.login_admin().await;
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/users/{user_id}?full=true"),
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "user lookup failed: {body}");
+
+    body["data"]["noc_transfer"]
+        .as_str()
+        .expect("noc_transfer")
+        .to_string()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn known_noc_resolves_to_its_user() {
+    let app = TestApp::spawn().await;
+    let (_, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, _) = app.register_and_login("bob@example.com").await;
+    let bob_noc = noc_of(&app, bob).await;
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/users/by-noc/{bob_noc}"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "noc lookup failed: {body}");
+    assert_eq!(body["data"]["id"], bob, "{body}");
+    assert_eq!(body["data"]["noc_transfer"], bob_noc, "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn missing_noc_is_not_found() {
+    let app = TestApp::spawn().await;
+    let (_, alice_token) = app.register_and_login("alice@example.com").await;
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            "/api/users/by-noc/40000000000000000",
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body}");
+
+    let (status, _) = app
+        .request(
+            Method::GET,
+            "/api/users/by-noc/40000000000000000",
+            None,
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn transfer_can_name_the_receiver_by_noc() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    let bob_noc = noc_of(&app, bob).await;
+
+    app.topup(alice, &alice_token, 100000).await;
+    app.topup(bob, &bob_token, 10000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/transfers",
+            Some(&alice_token),
+            Some(json!({
+                "transfer_from": alice,
+                "transfer_to_noc": bob_noc,
+                "transfer_amount": 50000,
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "transfer failed: {body}");
+    assert_eq!(body["data"]["transfer_to"], bob, "{body}");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/transfers",
+            Some(&alice_token),
+            Some(json!({
+                "transfer_from": alice,
+                "transfer_to": bob,
+                "transfer_to_noc": bob_noc,
+                "transfer_amount": 50000,
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+}
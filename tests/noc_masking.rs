@@ -0,0 +1,49 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+
+use common::TestApp;
+
+async fn stored_noc(app: &TestApp, user_id: i32) -> String {
+    sqlx::query_scalar("SELECT noc_transfer FROM users WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_one(&app.pool)
+        .await
+        .expect("failed to read noc_transfer")
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn me_is_masked_and_admins_can_ask_for_the_full_number() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    let noc = stored_noc(&app, alice).await;
+    let masked = format!("**** **** **** {}", &noc[noc.len() - 4..]);
+
+    let (status, body) = app
+        .request(Method::GET, "/api/auth/me", Some(&alice_token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["noc_transfer"], masked, "{body}");
+
+    let uri = format!("/api/users/{alice}");
+    let (status, body) = app
+        .request(Method::GET, &uri, Some(&admin_token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["noc_transfer"], masked, "{body}");
+
+    let full_uri = format!("{uri}?full=true");
+    let (status, body) = app
+        .request(Method::GET, &full_uri, Some(&admin_token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["noc_transfer"], noc, "{body}");
+
+    let (status, body) = app
+        .request(Method::GET, &full_uri, Some(&alice_token), None)
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+}
@@ -0,0 +1,28 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn served_spec_registers_the_bearer_scheme() {
+    let app = TestApp::spawn().await;
+
+    let (status, spec) = app
+        .request(Method::GET, "/api-docs/openapi.json", None, None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{spec}");
+
+    let scheme = &spec["components"]["securitySchemes"]["bearer_auth"];
+    assert_eq!(scheme["type"], "http", "{scheme}");
+    assert_eq!(scheme["scheme"], "bearer", "{scheme}");
+
+    // Routes mounted through `OpenApiRouter` land in the served spec too.
+    let get_topup = &spec["paths"]["/api/topups/{id}"]["get"];
+    assert_eq!(
+        get_topup["security"][0]["bearer_auth"],
+        serde_json::json!([])
+    );
+    assert!(spec["components"]["schemas"]["TopupResponse"].is_object());
+}
@@ -0,0 +1,204 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::Utc;
+use serde_json::json;
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn users_cannot_read_each_others_topups() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/topups",
+            Some(&bob_token),
+            Some(json!({
+                "user_id": bob,
+                "topup_no": "TOPUP-BOB",
+                "topup_amount": 50000,
+                "topup_method": "bank_transfer",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "topup failed: {body}");
+    let topup_id = body["data"]["topup_id"].as_i64().expect("topup id");
+
+    for uri in [
+        format!("/api/topups/{topup_id}"),
+        format!("/api/topups/user/{bob}"),
+        format!("/api/topups/users/{bob}"),
+    ] {
+        let (status, body) = app
+            .request(Method::GET, &uri, Some(&alice_token), None)
+            .await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "{uri}: {body}");
+
+        let (status, body) = app.request(Method::GET, &uri, Some(&bob_token), None).await;
+        assert_eq!(status, StatusCode::OK, "{uri}: {body}");
+
+        let (status, body) = app
+            .request(Method::GET, &uri, Some(&admin_token), None)
+            .await;
+        assert_eq!(status, StatusCode::OK, "{uri}: {body}");
+    }
+
+    // Alice's own records stay reachable.
+    app.topup(alice, &alice_token, 50000).await;
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/topups/user/{alice}"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn users_cannot_move_money_out_of_each_others_saldo() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(bob, &bob_token, 200000).await;
+    app.topup(alice, &alice_token, 50000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/transfers",
+            Some(&alice_token),
+            Some(json!({
+                "transfer_from": bob,
+                "transfer_to": alice,
+                "transfer_amount": 50000,
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/withdraws",
+            Some(&alice_token),
+            Some(json!({
+                "user_id": bob,
+                "withdraw_amount": 60000,
+                "withdraw_time": Utc::now(),
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+
+    let transfer = app.transfer(bob, alice, &bob_token, 50000).await;
+    let transfer_id = transfer["transfer_id"].as_i64().expect("transfer id");
+
+    let (status, body) = app
+        .request(
+            Method::PUT,
+            &format!("/api/transfers/{transfer_id}"),
+            Some(&alice_token),
+            Some(json!({
+                "transfer_id": transfer_id,
+                "transfer_from": bob,
+                "transfer_to": alice,
+                "transfer_amount": 150000,
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{bob}"),
+            Some(&bob_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["total_balance"], 150000, "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn users_cannot_credit_or_adjust_each_others_saldo() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(bob, &bob_token, 200000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/topups",
+            Some(&alice_token),
+            Some(json!({
+                "user_id": bob,
+                "topup_no": "TOPUP-FOR-BOB",
+                "topup_amount": 50000,
+                "topup_method": "bank_transfer",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/saldos",
+            Some(&alice_token),
+            Some(json!({ "user_id": alice, "total_balance": 1000000 })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{bob}"),
+            Some(&bob_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let saldo_id = body["data"]["id"].as_i64().expect("saldo id");
+
+    let (status, body) = app
+        .request(
+            Method::PUT,
+            &format!("/api/saldos/{saldo_id}"),
+            Some(&alice_token),
+            Some(json!({
+                "saldo_id": saldo_id,
+                "user_id": bob,
+                "total_balance": 200000,
+                "withdraw_amount": 100000,
+                "withdraw_time": Utc::now().naive_utc(),
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{bob}"),
+            Some(&bob_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["total_balance"], 200000, "{body}");
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::{Duration, Utc};
+use serde_json::{Value, json};
+
+use common::TestApp;
+use example_sea_query_payment_gateway::utils::hash_reset_token;
+
+// Stores a reset token for `user_id` directly, since the mailer only logs
+// the real one.
+async fn issue_token(app: &TestApp, user_id: i32, token: &str, expires_in: Duration) {
+    sqlx::query(
+        "INSERT INTO password_resets (user_id, token_hash, expires_at) VALUES ($1, $2, $3)",
+    )
+    .bind(user_id)
+    .bind(hash_reset_token(token))
+    .bind((Utc::now() + expires_in).naive_utc())
+    .execute(&app.pool)
+    .await
+    .expect("failed to store reset token");
+}
+
+async fn reset(app: &TestApp, token: &str, password: &str) -> (StatusCode, Value) {
+    app.request(
+        Method::POST,
+        "/api/auth/reset-password",
+        None,
+        Some(json!({
+            "token": token,
+            "password": password,
+            "confirm_password": password,
+        })),
+    )
+    .await
+}
+
+async fn login(app: &TestApp, password: &str) -> StatusCode {
+    let (status, _) = app
+        .request(
+            Method::POST,
+            "/api/auth/login",
+            None,
+            Some(json!({ "email": "alice@example.com", "password": password })),
+        )
+        .await;
+
+    status
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn reset_token_works_once() {
+    let app = TestApp::spawn().await;
+    let (alice, _) = app.register_and_login("alice@example.com").await;
+
+    issue_token(&app, alice, "fresh-token", Duration::minutes(30)).await;
+
+    let (status, body) = reset(&app, "fresh-token", "new-password-1").await;
+    assert_eq!(status, StatusCode::OK, "reset failed: {body}");
+    assert_eq!(login(&app, "new-password-1").await, StatusCode::OK);
+
+    let (status, body) = reset(&app, "fresh-token", "new-password-2").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+    assert_eq!(login(&app, "new-password-1").await, StatusCode::OK);
+    assert_eq!(
+        login(&app, "new-password-2").await,
+        StatusCode::UNAUTHORIZED
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn expired_reset_token_is_rejected() {
+    let app = TestApp::spawn().await;
+    let (alice, _) = app.register_and_login("alice@example.com").await;
+
+    issue_token(&app, alice, "stale-token", Duration::minutes(-1)).await;
+
+    let (status, body) = reset(&app, "stale-token", "new-password-1").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+    assert_eq!(login(&app, "password123").await, StatusCode::OK);
+    assert_eq!(
+        login(&app, "new-password-1").await,
+        StatusCode::UNAUTHORIZED
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn forgot_password_answers_alike_for_unknown_emails() {
+    let app = TestApp::spawn().await;
+    app.register_and_login("alice@example.com").await;
+
+    let mut responses = Vec::new();
+    for email in ["alice@example.com", "nobody@example.com"] {
+        let (status, mut body) = app
+            .request(
+                Method::POST,
+                "/api/auth/forgot-password",
+                None,
+                Some(json!({ "email": email })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+
+        // Every response carries its own request id and timestamp.
+        let fields = body.as_object_mut().expect("json object");
+        fields.remove("request_id");
+        fields.remove("timestamp");
+        responses.push(body);
+    }
+
+    assert_eq!(responses[0], responses[1]);
+}
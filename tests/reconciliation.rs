@@ -0,0 +1,69 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::Value;
+
+use common::TestApp;
+
+async fn reconcile(app: &TestApp, token: &str, user_id: i32) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/users/{user_id}/reconcile"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "reconcile failed: {body}");
+
+    body["data"][0].clone()
+}
+
+async fn mismatched_users(app: &TestApp, token: &str) -> Vec<i64> {
+    let (status, body) = app
+        .request(Method::GET, "/api/users/reconcile", Some(token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    body["data"]
+        .as_array()
+        .expect("mismatch list")
+        .iter()
+        .map(|entry| entry["user_id"].as_i64().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn a_corrupted_balance_no_longer_matches_its_ledger() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    app.topup(alice, &alice_token, 300000).await;
+    app.topup(bob, &bob_token, 50000).await;
+    app.transfer(alice, bob, &alice_token, 70000).await;
+    app.transfer(bob, alice, &bob_token, 50000).await;
+
+    let report = reconcile(&app, &admin_token, alice).await;
+    assert_eq!(report["matches"], true, "{report}");
+    assert_eq!(report["stored"], 280000, "{report}");
+    assert_eq!(report["computed"], 280000, "{report}");
+    assert!(mismatched_users(&app, &admin_token).await.is_empty());
+
+    sqlx::query("UPDATE saldo SET total_balance = total_balance + 1234 WHERE user_id = $1")
+        .bind(alice)
+        .execute(&app.pool)
+        .await
+        .expect("failed to corrupt the balance");
+
+    let report = reconcile(&app, &admin_token, alice).await;
+    assert_eq!(report["matches"], false, "{report}");
+    assert_eq!(report["stored"], 281234, "{report}");
+    assert_eq!(report["computed"], 280000, "{report}");
+    assert_eq!(report["difference"], 1234, "{report}");
+
+    assert_eq!(reconcile(&app, &admin_token, bob).await["matches"], true);
+    assert_eq!(mismatched_users(&app, &admin_token).await, [alice as i64]);
+}
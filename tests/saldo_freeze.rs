@@ -0,0 +1,234 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::Utc;
+use serde_json::{Value, json};
+
+use common::TestApp;
+
+async fn topup(app: &TestApp, user_id: i32, token: &str, amount: i64) -> (StatusCode, Value) {
+    app.request(
+        Method::POST,
+        "/api/topups",
+        Some(token),
+        Some(json!({
+            "user_id": user_id,
+            "topup_no": format!("FREEZE-{user_id}-{amount}"),
+            "topup_amount": amount,
+            "topup_method": "bank_transfer",
+        })),
+    )
+    .await
+}
+
+async fn transfer(app: &TestApp, from: i32, to: i32, token: &str) -> (StatusCode, Value) {
+    app.request(
+        Method::POST,
+        "/api/transfers",
+        Some(token),
+        Some(json!({
+            "transfer_from": from,
+            "transfer_to": to,
+            "transfer_amount": 50000,
+        })),
+    )
+    .await
+}
+
+async fn withdraw(app: &TestApp, user_id: i32, token: &str) -> (StatusCode, Value) {
+    app.request(
+        Method::POST,
+        "/api/withdraws",
+        Some(token),
+        Some(json!({
+            "user_id": user_id,
+            "withdraw_amount": 60000,
+            "withdraw_time": Utc::now(),
+        })),
+    )
+    .await
+}
+
+fn assert_frozen((status, body): (StatusCode, Value)) {
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["message"], "account frozen", "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn frozen_saldo_blocks_money_movement_until_unfrozen() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 500000).await;
+    app.topup(bob, &bob_token, 500000).await;
+
+    // Only admins may freeze.
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/api/saldos/user/{alice}/freeze"),
+            Some(&alice_token),
+            Some(json!({})),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/api/saldos/user/{alice}/freeze"),
+            Some(&admin_token),
+            Some(json!({ "reason": "pending investigation" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "freeze failed: {body}");
+    assert_eq!(body["data"][0]["is_frozen"], true, "{body}");
+    assert_eq!(
+        body["data"][0]["frozen_reason"], "pending investigation",
+        "{body}"
+    );
+
+    assert_frozen(topup(&app, alice, &alice_token, 100000).await);
+    assert_frozen(transfer(&app, alice, bob, &alice_token).await);
+    assert_frozen(transfer(&app, bob, alice, &bob_token).await);
+    assert_frozen(withdraw(&app, alice, &alice_token).await);
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/api/saldos/user/{alice}/unfreeze"),
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "unfreeze failed: {body}");
+    assert_eq!(body["data"][0]["is_frozen"], false, "{body}");
+    assert!(body["data"][0].get("frozen_reason").is_none(), "{body}");
+
+    let (status, body) = topup(&app, alice, &alice_token, 100000).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let (status, body) = transfer(&app, alice, bob, &alice_token).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let (status, body) = transfer(&app, bob, alice, &bob_token).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let (status, body) = withdraw(&app, alice, &alice_token).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn frozen_saldo_blocks_edits_of_existing_records() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    let (status, body) = topup(&app, alice, &alice_token, 500000).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let topup_id = body["data"]["topup_id"].clone();
+    let (status, body) = topup(&app, bob, &bob_token, 10000).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    let (status, body) = transfer(&app, alice, bob, &alice_token).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let transfer_id = body["data"]["transfer_id"].clone();
+
+    let (status, body) = withdraw(&app, alice, &alice_token).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let withdraw_id = body["data"]["withdraw_id"].clone();
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{alice}"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let saldo_id = body["data"]["id"].clone();
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/api/saldos/user/{alice}/freeze"),
+            Some(&admin_token),
+            Some(json!({})),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "freeze failed: {body}");
+
+    assert_frozen(
+        app.request(
+            Method::PUT,
+            &format!("/api/topups/{topup_id}"),
+            Some(&alice_token),
+            Some(json!({
+                "user_id": alice,
+                "topup_id": topup_id,
+                "topup_amount": 600000,
+                "topup_method": "bank_transfer",
+            })),
+        )
+        .await,
+    );
+    assert_frozen(
+        app.request(
+            Method::PUT,
+            &format!("/api/transfers/{transfer_id}"),
+            Some(&alice_token),
+            Some(json!({
+                "transfer_id": transfer_id,
+                "transfer_from": alice,
+                "transfer_to": bob,
+                "transfer_amount": 60000,
+            })),
+        )
+        .await,
+    );
+    assert_frozen(
+        app.request(
+            Method::PUT,
+            &format!("/api/withdraws/{withdraw_id}"),
+            Some(&alice_token),
+            Some(json!({
+                "user_id": alice,
+                "withdraw_id": withdraw_id,
+                "withdraw_amount": 70000,
+                "withdraw_time": Utc::now(),
+            })),
+        )
+        .await,
+    );
+    assert_frozen(
+        app.request(
+            Method::PUT,
+            &format!("/api/saldos/{saldo_id}"),
+            Some(&alice_token),
+            Some(json!({
+                "saldo_id": saldo_id,
+                "user_id": alice,
+                "total_balance": 390000,
+                "withdraw_amount": 50000,
+                "withdraw_time": Utc::now().naive_utc(),
+            })),
+        )
+        .await,
+    );
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{alice}"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["total_balance"], 390000, "{body}");
+}
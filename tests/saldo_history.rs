@@ -0,0 +1,147 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::Utc;
+use serde_json::{Value, json};
+
+use common::TestApp;
+
+async fn history(app: &TestApp, user_id: i32, token: &str) -> Vec<Value> {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{user_id}/history?page_size=50"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "history lookup failed: {body}");
+
+    body["data"].as_array().expect("history list").clone()
+}
+
+async fn balance(app: &TestApp, user_id: i32, token: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{user_id}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+
+    body["data"]["total_balance"].clone()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn every_balance_change_is_recorded_newest_first() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+    app.topup(alice, &alice_token, 50000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    let transfer = app.transfer(alice, bob, &alice_token, 50000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/withdraws",
+            Some(&alice_token),
+            Some(json!({
+                "user_id": alice,
+                "withdraw_amount": 60000,
+                "withdraw_time": Utc::now(),
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "withdraw failed: {body}");
+    let withdraw_id = body["data"]["withdraw_id"].clone();
+
+    let entries = history(&app, alice, &alice_token).await;
+    let latest: Vec<(&str, i64, i64)> = entries
+        .iter()
+        .take(3)
+        .map(|entry| {
+            (
+                entry["reason"].as_str().expect("reason"),
+                entry["old_balance"].as_i64().expect("old balance"),
+                entry["new_balance"].as_i64().expect("new balance"),
+            )
+        })
+        .collect();
+    assert_eq!(
+        latest,
+        [
+            ("withdraw", 100000, 40000),
+            ("transfer", 150000, 100000),
+            ("topup", 100000, 150000),
+        ],
+        "{entries:?}"
+    );
+    assert_eq!(entries[0]["reference_id"], withdraw_id);
+    assert_eq!(entries[1]["reference_id"], transfer["transfer_id"]);
+
+    // Each entry starts where the one before it ended.
+    for pair in entries.windows(2) {
+        assert_eq!(pair[0]["old_balance"], pair[1]["new_balance"], "{pair:?}");
+    }
+    assert_eq!(
+        entries[0]["new_balance"],
+        balance(&app, alice, &alice_token).await
+    );
+
+    // The receiving side is recorded under the same transfer.
+    let entries = history(&app, bob, &bob_token).await;
+    assert_eq!(entries[0]["reason"], "transfer", "{entries:?}");
+    assert_eq!(entries[0]["old_balance"], 10000, "{entries:?}");
+    assert_eq!(entries[0]["new_balance"], 60000, "{entries:?}");
+    assert_eq!(entries[0]["reference_id"], transfer["transfer_id"]);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn balance_change_rolls_back_with_its_history_row() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+    let before = history(&app, alice, &alice_token).await;
+
+    sqlx::query(
+        "CREATE FUNCTION fail_history() RETURNS trigger AS $$
+         BEGIN RAISE EXCEPTION 'history write failed'; END
+         $$ LANGUAGE plpgsql",
+    )
+    .execute(&app.pool)
+    .await
+    .expect("failed to create trigger function");
+    sqlx::query(
+        "CREATE TRIGGER fail_history BEFORE INSERT ON saldo_history
+         FOR EACH ROW EXECUTE FUNCTION fail_history()",
+    )
+    .execute(&app.pool)
+    .await
+    .expect("failed to create trigger");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/topups",
+            Some(&alice_token),
+            Some(json!({
+                "user_id": alice,
+                "topup_no": "TOPUP-NO-HISTORY",
+                "topup_amount": 50000,
+                "topup_method": "bank_transfer",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR, "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 100000);
+    assert_eq!(history(&app, alice, &alice_token).await, before);
+}
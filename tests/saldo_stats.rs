@@ -0,0 +1,79 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::Value;
+
+use common::TestApp;
+
+async fn idr_stats(app: &TestApp, token: &str) -> Value {
+    let (status, body) = app
+        .request(Method::GET, "/api/saldos/stats", Some(token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "stats failed: {body}");
+
+    body["data"]
+        .as_array()
+        .expect("stats per currency")
+        .iter()
+        .find(|stats| stats["currency"] == "IDR")
+        .cloned()
+        .unwrap_or_else(|| panic!("no IDR stats: {body}"))
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn stats_match_the_seeded_saldos() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    for (email, amount) in [
+        ("alice@example.com", 100000),
+        ("bob@example.com", 200000),
+        ("carol@example.com", 600000),
+    ] {
+        let (user_id, token) = app.register_and_login(email).await;
+        app.topup(user_id, &token, amount).await;
+    }
+
+    let stats = idr_stats(&app, &admin_token).await;
+    assert_eq!(stats["total_balance"], 900000, "{stats}");
+    assert_eq!(stats["account_count"], 3, "{stats}");
+    assert_eq!(stats["average_balance"], 300000, "{stats}");
+    assert_eq!(stats["median_balance"], 200000, "{stats}");
+    assert_eq!(stats["negative_count"], 0, "{stats}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn a_negative_balance_is_counted() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    app.topup(alice, &alice_token, 100000).await;
+    app.topup(bob, &bob_token, 100000).await;
+
+    // Something the API would never allow.
+    sqlx::query("UPDATE saldo SET total_balance = -5000 WHERE user_id = $1")
+        .bind(bob)
+        .execute(&app.pool)
+        .await
+        .expect("failed to force a negative balance");
+
+    let stats = idr_stats(&app, &admin_token).await;
+    assert_eq!(stats["negative_count"], 1, "{stats}");
+    assert_eq!(stats["total_balance"], 95000, "{stats}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn stats_are_admin_only() {
+    let app = TestApp::spawn().await;
+    let (_, token) = app.register_and_login("alice@example.com").await;
+
+    let (status, body) = app
+        .request(Method::GET, "/api/saldos/stats", Some(&token), None)
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+}
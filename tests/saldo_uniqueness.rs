@@ -0,0 +1,61 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+use common::TestApp;
+use example_sea_query_payment_gateway::{
+    abstract_trait::SaldoRepositoryTrait,
+    domain::{currency::Currency, money::Money, request::saldo::CreateSaldoRequest},
+    repository::saldo::SaldoRepository,
+    utils::AppError,
+};
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn a_second_saldo_for_the_same_user_conflicts() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (alice, _) = app.register_and_login("alice@example.com").await;
+
+    let create = || {
+        app.request(
+            Method::POST,
+            "/api/saldos",
+            Some(&admin_token),
+            Some(json!({ "user_id": alice, "total_balance": 50000 })),
+        )
+    };
+
+    let (status, body) = create().await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    let (status, body) = create().await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body}");
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM saldo WHERE user_id = $1")
+        .bind(alice)
+        .fetch_one(&app.pool)
+        .await
+        .expect("failed to count saldos");
+    assert_eq!(count, 1);
+}
+
+// Two creates racing past the service's lookup still meet the unique index.
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn the_unique_index_backs_up_the_service_check() {
+    let app = TestApp::spawn().await;
+    let (alice, _) = app.register_and_login("alice@example.com").await;
+
+    let repository = SaldoRepository::new(app.pool.clone());
+    let input = CreateSaldoRequest {
+        user_id: alice,
+        total_balance: Money::new(50000),
+        currency: Currency::base(),
+    };
+
+    repository.create(&input).await.expect("first saldo");
+    let err = repository.create(&input).await.unwrap_err();
+    assert!(matches!(err, AppError::Conflict(_)), "{err:?}");
+}
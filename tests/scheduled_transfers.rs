@@ -0,0 +1,177 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::{Duration, Utc};
+use serde_json::{Value, json};
+
+use common::TestApp;
+
+async fn balance(app: &TestApp, user_id: i32, token: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{user_id}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+
+    body["data"]["total_balance"].clone()
+}
+
+async fn schedule(app: &TestApp, from: i32, to: i32, token: &str, amount: i64) -> i64 {
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/transfers",
+            Some(token),
+            Some(json!({
+                "transfer_from": from,
+                "transfer_to": to,
+                "transfer_amount": amount,
+                "scheduled_at": Utc::now() + Duration::hours(1),
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "scheduling failed: {body}");
+    assert_eq!(body["data"]["status"], "pending", "{body}");
+
+    body["data"]["transfer_id"].as_i64().expect("transfer id")
+}
+
+// Moves the transfer's due time into the past, as if the hour had gone by.
+async fn make_due(app: &TestApp, transfer_id: i64) {
+    sqlx::query("UPDATE transfers SET scheduled_at = $1 WHERE transfer_id = $2")
+        .bind((Utc::now() - Duration::minutes(1)).naive_utc())
+        .bind(transfer_id as i32)
+        .execute(&app.pool)
+        .await
+        .expect("failed to make the transfer due");
+}
+
+async fn run_scheduler(app: &TestApp) -> usize {
+    app.state
+        .di_container
+        .transfer_service
+        .execute_due_transfers()
+        .await
+        .expect("scheduler run failed")
+}
+
+async fn scheduled_ids(app: &TestApp, token: &str) -> Vec<i64> {
+    let (status, body) = app
+        .request(Method::GET, "/api/transfers/scheduled", Some(token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    body["data"]
+        .as_array()
+        .expect("scheduled list")
+        .iter()
+        .map(|transfer| transfer["transfer_id"].as_i64().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn a_scheduled_transfer_runs_once_it_is_due() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    let transfer_id = schedule(&app, alice, bob, &alice_token, 50000).await;
+
+    assert_eq!(scheduled_ids(&app, &alice_token).await, vec![transfer_id]);
+
+    // Not due yet: the scheduler leaves it alone.
+    assert_eq!(run_scheduler(&app).await, 0);
+    assert_eq!(balance(&app, alice, &alice_token).await, 100000);
+
+    make_due(&app, transfer_id).await;
+    assert_eq!(run_scheduler(&app).await, 1);
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/transfers/{transfer_id}"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["status"], "completed", "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 50000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 60000);
+    assert!(scheduled_ids(&app, &alice_token).await.is_empty());
+
+    // A second run finds nothing left to do.
+    assert_eq!(run_scheduler(&app).await, 0);
+    assert_eq!(balance(&app, alice, &alice_token).await, 50000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn a_cancelled_transfer_never_runs() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    let transfer_id = schedule(&app, alice, bob, &alice_token, 50000).await;
+    let uri = format!("/api/transfers/{transfer_id}");
+
+    // Only the sender may cancel it.
+    let (status, body) = app
+        .request(Method::DELETE, &uri, Some(&bob_token), None)
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+    assert_eq!(scheduled_ids(&app, &alice_token).await, vec![transfer_id]);
+
+    let (status, body) = app
+        .request(Method::DELETE, &uri, Some(&alice_token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "cancel failed: {body}");
+    assert_eq!(body["message"], "Transfer cancelled successfully", "{body}");
+    assert!(scheduled_ids(&app, &alice_token).await.is_empty());
+
+    make_due(&app, transfer_id).await;
+    assert_eq!(run_scheduler(&app).await, 0);
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 100000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 10000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn a_transfer_that_already_ran_cannot_be_cancelled() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    let transfer_id = schedule(&app, alice, bob, &alice_token, 50000).await;
+    make_due(&app, transfer_id).await;
+    assert_eq!(run_scheduler(&app).await, 1);
+
+    let (status, body) = app
+        .request(
+            Method::DELETE,
+            &format!("/api/transfers/{transfer_id}"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 50000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 60000);
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+use common::TestApp;
+
+async fn admin_count(app: &TestApp) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE role = 'admin' AND deleted_at IS NULL")
+        .fetch_one(&app.pool)
+        .await
+        .expect("failed to count admins")
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn seeding_twice_leaves_exactly_one_admin() {
+    // `spawn` has seeded the admin once already.
+    let app = TestApp::spawn().await;
+    let users = &app.state.di_container.user_service;
+
+    users
+        .seed_admin("admin@example.com", Some("admin-password"))
+        .await
+        .expect("second seed failed");
+    users
+        .seed_admin("other-admin@example.com", Some("admin-password"))
+        .await
+        .expect("seed with another email failed");
+
+    assert_eq!(admin_count(&app).await, 1);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn seeding_never_promotes_a_registered_account() {
+    let app = TestApp::spawn().await;
+    let (_, token) = app.register_and_login("boss@example.com").await;
+
+    app.state
+        .di_container
+        .user_service
+        .seed_admin("boss@example.com", Some("admin-password"))
+        .await
+        .expect("seed failed");
+
+    let (status, body) = app
+        .request(Method::GET, "/api/users", Some(&token), None)
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+    assert_eq!(admin_count(&app).await, 1);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn seeding_a_fresh_database_creates_one_usable_admin() {
+    let app = TestApp::spawn().await;
+    sqlx::query("DELETE FROM users WHERE role = 'admin'")
+        .execute(&app.pool)
+        .await
+        .expect("failed to drop the seeded admin");
+    assert_eq!(admin_count(&app).await, 0);
+
+    let users = &app.state.di_container.user_service;
+    for _ in 0..2 {
+        users
+            .seed_admin("root@example.com", Some("root-password"))
+            .await
+            .expect("seed failed");
+    }
+    assert_eq!(admin_count(&app).await, 1);
+
+    // The password was hashed on the way in, so it logs in like any other.
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/auth/login",
+            None,
+            Some(json!({
+                "email": "root@example.com",
+                "password": "root-password",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let token = body["data"]["access_token"].as_str().expect("access token");
+
+    let (status, body) = app
+        .request(Method::GET, "/api/users", Some(token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+}
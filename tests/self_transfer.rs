@@ -0,0 +1,47 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn a_self_transfer_leaves_the_balance_alone() {
+    let app = TestApp::spawn().await;
+    let (alice, token) = app.register_and_login("alice@example.com").await;
+    app.topup(alice, &token, 100000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/transfers",
+            Some(&token),
+            Some(json!({
+                "transfer_from": alice,
+                "transfer_to": alice,
+                "transfer_amount": 50000,
+            })),
+        )
+        .await;
+    // Caught by the request validator, before the service ever runs.
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+    assert_eq!(body["message"], "Cannot transfer to self", "{body}");
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{alice}"),
+            Some(&token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["total_balance"], 100000, "{body}");
+
+    let (transfers,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM transfers")
+        .fetch_one(&app.pool)
+        .await
+        .expect("failed to count transfers");
+    assert_eq!(transfers, 0);
+}
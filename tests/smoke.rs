@@ -0,0 +1,32 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn register_login_topup_transfer() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 200000).await;
+    app.topup(bob, &bob_token, 50000).await;
+
+    let transfer = app.transfer(alice, bob, &alice_token, 100000).await;
+    assert_eq!(transfer["status"], "completed");
+
+    for (user_id, token, expected) in [(alice, &alice_token, 100000), (bob, &bob_token, 150000)] {
+        let (status, body) = app
+            .request(
+                Method::GET,
+                &format!("/api/saldos/user/{user_id}"),
+                Some(token),
+                None,
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+        assert_eq!(body["data"]["total_balance"], expected);
+    }
+}
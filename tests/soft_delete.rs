@@ -0,0 +1,107 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{Value, json};
+
+use common::TestApp;
+
+// Ids of the topups an admin's listing returns for `query`.
+async fn listed_topups(app: &TestApp, token: &str, query: &str) -> Vec<Value> {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/topups?page_size=100{query}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "listing failed: {body}");
+
+    body["data"]
+        .as_array()
+        .expect("topup list")
+        .iter()
+        .map(|topup| topup["topup_id"].clone())
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn a_deleted_topup_leaves_the_listing_until_restored() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/topups",
+            Some(&alice_token),
+            Some(json!({
+                "user_id": alice,
+                "topup_no": "TOPUP-SOFT-DELETE",
+                "topup_amount": 50000,
+                "topup_method": "bank_transfer",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "topup failed: {body}");
+    let topup_id = body["data"]["topup_id"].clone();
+    let uri = format!("/api/topups/{topup_id}");
+
+    assert!(
+        listed_topups(&app, &admin_token, "")
+            .await
+            .contains(&topup_id)
+    );
+
+    let (status, body) = app
+        .request(Method::DELETE, &uri, Some(&admin_token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "delete failed: {body}");
+
+    assert!(
+        !listed_topups(&app, &admin_token, "")
+            .await
+            .contains(&topup_id)
+    );
+    let (status, body) = app
+        .request(Method::GET, &uri, Some(&admin_token), None)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body}");
+
+    // The row is kept, and admins can still see it.
+    assert!(
+        listed_topups(&app, &admin_token, "&include_deleted=true")
+            .await
+            .contains(&topup_id)
+    );
+    let deleted_at: Option<chrono::NaiveDateTime> =
+        sqlx::query_scalar("SELECT deleted_at FROM topups WHERE topup_id = $1")
+            .bind(topup_id.as_i64().unwrap() as i32)
+            .fetch_one(&app.pool)
+            .await
+            .expect("topup row is kept");
+    assert!(deleted_at.is_some());
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("{uri}/restore"),
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "restore failed: {body}");
+
+    assert!(
+        listed_topups(&app, &admin_token, "")
+            .await
+            .contains(&topup_id)
+    );
+    let (status, body) = app
+        .request(Method::GET, &uri, Some(&admin_token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["topup_amount"], 50000, "{body}");
+}
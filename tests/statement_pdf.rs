@@ -0,0 +1,56 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode, header},
+};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn statement_is_served_as_a_pdf() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    app.topup(alice, &alice_token, 100000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    app.transfer(alice, bob, &alice_token, 50000).await;
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/users/{alice}/statement.pdf"))
+                .header(header::AUTHORIZATION, format!("Bearer {alice_token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("router is infallible");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/pdf",
+        "{:?}",
+        response.headers()
+    );
+
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    assert!(bytes.len() > 100, "{} bytes", bytes.len());
+    assert!(bytes.starts_with(b"%PDF"), "{:?}", &bytes[..16]);
+
+    // Someone else's statement stays private.
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/users/{alice}/statement.pdf"),
+            Some(&bob_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+}
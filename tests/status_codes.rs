@@ -0,0 +1,66 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::Utc;
+use serde_json::json;
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn missing_topup_is_not_found() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    let (status, body) = app
+        .request(Method::GET, "/api/topups/999999", Some(&admin_token), None)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body}");
+    assert_eq!(body["status"], "error", "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn missing_records_are_not_found_across_handlers() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    for uri in [
+        "/api/users/999999",
+        "/api/saldos/999999",
+        "/api/transfers/999999",
+        "/api/withdraws/999999",
+    ] {
+        let (status, body) = app
+            .request(Method::GET, uri, Some(&admin_token), None)
+            .await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{uri}: {body}");
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn creates_answer_with_201() {
+    let app = TestApp::spawn().await;
+
+    // Registration, topups and transfers assert 201 inside the helpers.
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    app.topup(alice, &alice_token, 200000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    app.transfer(alice, bob, &alice_token, 50000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/withdraws",
+            Some(&alice_token),
+            Some(json!({
+                "user_id": alice,
+                "withdraw_amount": 60000,
+                "withdraw_time": Utc::now(),
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+}
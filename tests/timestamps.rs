@@ -0,0 +1,99 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::http::{Method, StatusCode};
+use chrono::NaiveDateTime;
+use serde_json::json;
+
+use common::TestApp;
+
+type Stamps = (NaiveDateTime, NaiveDateTime);
+
+async fn stamps(app: &TestApp, sql: &str, id: i32) -> Stamps {
+    sqlx::query_as(sql)
+        .bind(id)
+        .fetch_one(&app.pool)
+        .await
+        .expect("failed to read timestamps")
+}
+
+async fn topup_stamps(app: &TestApp, topup_id: i32) -> Stamps {
+    stamps(
+        app,
+        "SELECT created_at, updated_at FROM topups WHERE topup_id = $1",
+        topup_id,
+    )
+    .await
+}
+
+async fn saldo_stamps(app: &TestApp, user_id: i32) -> Stamps {
+    stamps(
+        app,
+        "SELECT created_at, updated_at FROM saldo WHERE user_id = $1",
+        user_id,
+    )
+    .await
+}
+
+fn assert_bumped(before: Stamps, after: Stamps, what: &str) {
+    assert_eq!(after.0, before.0, "{what}: created_at moved");
+    assert!(
+        after.1 > before.1,
+        "{what}: updated_at {} is not after {}",
+        after.1,
+        before.1
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn updates_bump_updated_at_and_keep_created_at() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    app.topup(alice, &alice_token, 100000).await;
+    app.topup(bob, &bob_token, 10000).await;
+
+    let topup_id: i32 = sqlx::query_scalar("SELECT topup_id FROM topups WHERE user_id = $1")
+        .bind(alice)
+        .fetch_one(&app.pool)
+        .await
+        .expect("failed to find the topup");
+
+    let topup_before = topup_stamps(&app, topup_id).await;
+    let alice_before = saldo_stamps(&app, alice).await;
+    let bob_before = saldo_stamps(&app, bob).await;
+
+    // Timestamps are microsecond precision; make sure the clock has moved.
+    tokio::time::sleep(Duration::from_millis(5)).await;
+
+    let (status, body) = app
+        .request(
+            Method::PUT,
+            &format!("/api/topups/{topup_id}"),
+            Some(&alice_token),
+            Some(json!({
+                "user_id": alice,
+                "topup_id": topup_id,
+                "topup_amount": 150000,
+                "topup_method": "bank_transfer",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    assert_bumped(topup_before, topup_stamps(&app, topup_id).await, "topup");
+    let alice_after_update = saldo_stamps(&app, alice).await;
+    assert_bumped(alice_before, alice_after_update, "saldo after topup update");
+
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    app.transfer(alice, bob, &alice_token, 50000).await;
+
+    assert_bumped(
+        alice_after_update,
+        saldo_stamps(&app, alice).await,
+        "sender saldo",
+    );
+    assert_bumped(bob_before, saldo_stamps(&app, bob).await, "receiver saldo");
+}
@@ -0,0 +1,30 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn access_token_past_its_lifetime_is_rejected() {
+    // Tokens are issued already two minutes past their expiry.
+    let app = TestApp::spawn_with(|config| config.jwt_expiry_seconds = -120).await;
+    let (_, stale_token) = app.register_and_login("alice@example.com").await;
+
+    let (status, body) = app
+        .request(Method::GET, "/api/auth/me", Some(&stale_token), None)
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn fresh_access_token_is_accepted() {
+    let app = TestApp::spawn_with(|config| config.jwt_expiry_seconds = 60).await;
+    let (_, token) = app.register_and_login("alice@example.com").await;
+
+    let (status, body) = app
+        .request(Method::GET, "/api/auth/me", Some(&token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use example_sea_query_payment_gateway::domain::{money::Money, request::topup::MIN_TOPUP_AMOUNT};
+use serde_json::{Value, json};
+
+use common::TestApp;
+
+async fn topup(app: &TestApp, user_id: i32, token: &str, amount: i64) -> (StatusCode, Value) {
+    app.request(
+        Method::POST,
+        "/api/topups",
+        Some(token),
+        Some(json!({
+            "user_id": user_id,
+            "topup_no": format!("LIMIT-{user_id}-{amount}"),
+            "topup_amount": amount,
+            "topup_method": "bank_transfer",
+        })),
+    )
+    .await
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn topups_below_the_floor_are_rejected() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    for amount in [0, MIN_TOPUP_AMOUNT - 1] {
+        let (status, body) = topup(&app, alice, &alice_token, amount).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+    }
+
+    app.topup(alice, &alice_token, MIN_TOPUP_AMOUNT).await;
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn topups_outside_the_configured_limits_are_rejected() {
+    let app = TestApp::spawn_with(|config| {
+        config.min_topup_amount = Money::new(20000);
+        config.max_topup_amount = Some(Money::new(1_000_000));
+    })
+    .await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    // Passes the request floor but not the configured minimum.
+    let (status, body) = topup(&app, alice, &alice_token, 19999).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+
+    let (status, body) = topup(&app, alice, &alice_token, 1_000_001).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+    assert!(
+        body["message"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("maximum single topup"),
+        "{body}"
+    );
+
+    app.topup(alice, &alice_token, 20000).await;
+    app.topup(bob, &bob_token, 1_000_000).await;
+
+    // The cap is per topup, not on the balance.
+    app.transfer(bob, alice, &bob_token, 500000).await;
+    app.topup(alice, &alice_token, 1_000_000).await;
+}
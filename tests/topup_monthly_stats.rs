@@ -0,0 +1,99 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::NaiveDateTime;
+use serde_json::Value;
+
+use common::TestApp;
+
+// Backdates `user_id`'s topup of `amount`.
+async fn backdate(app: &TestApp, user_id: i32, amount: i64, at: &str) {
+    let at = NaiveDateTime::parse_from_str(at, "%Y-%m-%d %H:%M:%S").unwrap();
+
+    sqlx::query("UPDATE topups SET created_at = $1 WHERE user_id = $2 AND topup_amount = $3")
+        .bind(at)
+        .bind(user_id)
+        .bind(amount)
+        .execute(&app.pool)
+        .await
+        .expect("failed to backdate topup");
+}
+
+fn buckets(body: &Value) -> Vec<(String, i64, i64)> {
+    body["data"]
+        .as_array()
+        .expect("monthly stats")
+        .iter()
+        .map(|month| {
+            (
+                month["month"].as_str().unwrap().to_string(),
+                month["count"].as_i64().unwrap(),
+                month["total_amount"].as_i64().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn topups_either_side_of_midnight_land_in_their_own_month() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &alice_token, 10000).await;
+    app.topup(alice, &alice_token, 20000).await;
+    app.topup(alice, &alice_token, 40000).await;
+    backdate(&app, alice, 10000, "2025-01-31 23:59:59").await;
+    backdate(&app, alice, 20000, "2025-02-01 00:00:00").await;
+    backdate(&app, alice, 40000, "2025-02-28 12:00:00").await;
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            "/api/topups/stats/monthly?from=2024-12-15T00:00:00Z&to=2025-03-10T00:00:00Z",
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    assert_eq!(
+        buckets(&body),
+        [
+            ("2024-12-01".to_string(), 0, 0),
+            ("2025-01-01".to_string(), 1, 10000),
+            ("2025-02-01".to_string(), 2, 60000),
+            ("2025-03-01".to_string(), 0, 0),
+        ],
+        "{body}"
+    );
+
+    // A user may ask about themselves, scoped by user_id.
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!(
+                "/api/topups/stats/monthly?user_id={alice}&from=2025-02-01T00:00:00Z&to=2025-02-28T23:59:59Z"
+            ),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(
+        buckets(&body),
+        [("2025-02-01".to_string(), 2, 60000)],
+        "{body}"
+    );
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            "/api/topups/stats/monthly",
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+}
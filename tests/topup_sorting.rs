@@ -0,0 +1,59 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn topups_sort_by_a_whitelisted_column() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    for amount in [30000, 10000, 20000] {
+        app.topup(alice, &alice_token, amount).await;
+    }
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            "/api/topups?sort_by=topup_amount&order=asc",
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let amounts: Vec<i64> = body["data"]
+        .as_array()
+        .expect("topup list")
+        .iter()
+        .map(|topup| topup["topup_amount"].as_i64().expect("amount"))
+        .collect();
+    assert_eq!(amounts, [10000, 20000, 30000], "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn unknown_sort_column_is_rejected() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            "/api/topups?sort_by=topup_no%3B%20DROP%20TABLE%20topups",
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+    assert!(body["errors"]["sort_by"].is_array(), "{body}");
+    assert!(
+        body["message"]
+            .as_str()
+            .is_some_and(|message| message.contains("Invalid sort_by")),
+        "{body}"
+    );
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{Value, json};
+
+use common::TestApp;
+
+async fn balance(app: &TestApp, user_id: i32, token: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{user_id}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+
+    body["data"]["total_balance"].clone()
+}
+
+// Returns the id of the created topup.
+async fn create_topup(app: &TestApp, user_id: i32, token: &str, amount: i64) -> i64 {
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/topups",
+            Some(token),
+            Some(json!({
+                "user_id": user_id,
+                "topup_no": format!("TOPUP-{user_id}-{amount}"),
+                "topup_amount": amount,
+                "topup_method": "bank_transfer",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "topup failed: {body}");
+
+    body["data"]["topup_id"].as_i64().expect("topup id")
+}
+
+async fn update_topup(
+    app: &TestApp,
+    topup_id: i64,
+    user_id: i32,
+    token: &str,
+    amount: i64,
+) -> (StatusCode, Value) {
+    app.request(
+        Method::PUT,
+        &format!("/api/topups/{topup_id}"),
+        Some(token),
+        Some(json!({
+            "user_id": user_id,
+            "topup_id": topup_id,
+            "topup_amount": amount,
+            "topup_method": "bank_transfer",
+        })),
+    )
+    .await
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn increasing_a_topup_credits_the_difference() {
+    let app = TestApp::spawn().await;
+    let (alice, token) = app.register_and_login("alice@example.com").await;
+
+    let topup_id = create_topup(&app, alice, &token, 100000).await;
+
+    let (status, body) = update_topup(&app, topup_id, alice, &token, 150000).await;
+    assert_eq!(status, StatusCode::OK, "update failed: {body}");
+    assert_eq!(body["data"]["topup_amount"], 150000, "{body}");
+
+    assert_eq!(balance(&app, alice, &token).await, 150000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn decreasing_a_topup_within_the_balance_debits_the_difference() {
+    let app = TestApp::spawn().await;
+    let (alice, token) = app.register_and_login("alice@example.com").await;
+
+    let topup_id = create_topup(&app, alice, &token, 100000).await;
+
+    let (status, body) = update_topup(&app, topup_id, alice, &token, 60000).await;
+    assert_eq!(status, StatusCode::OK, "update failed: {body}");
+
+    assert_eq!(balance(&app, alice, &token).await, 60000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn decreasing_a_spent_topup_is_rejected_and_changes_nothing() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    app.topup(bob, &bob_token, 10000).await;
+
+    let topup_id = create_topup(&app, alice, &alice_token, 100000).await;
+    app.transfer(alice, bob, &alice_token, 80000).await;
+
+    let (status, body) = update_topup(&app, topup_id, alice, &alice_token, 50000).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["error_code"], "insufficient_balance", "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 20000);
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/topups/{topup_id}"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["topup_amount"], 100000, "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn another_users_topup_cannot_be_updated() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    let topup_id = create_topup(&app, alice, &alice_token, 100000).await;
+
+    let (status, body) = update_topup(&app, topup_id, bob, &bob_token, 500000).await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 100000);
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+
+use common::TestApp;
+
+const MARCH: &str = "from_date=2025-03-01T00:00:00Z&to_date=2025-03-31T00:00:00Z";
+
+async fn listed_amounts(app: &TestApp, token: &str, query: &str) -> Vec<i64> {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/topups?sort_by=topup_amount&order=asc&{query}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    body["data"]
+        .as_array()
+        .expect("topup list")
+        .iter()
+        .map(|topup| topup["topup_amount"].as_i64().expect("amount"))
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn amount_and_date_filters_combine_with_inclusive_bounds() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    for (amount, time) in [
+        (50000, "2025-03-15 12:00:00"),
+        (150000, "2025-03-01 00:00:00"),
+        (250000, "2025-03-31 00:00:00"),
+        (300000, "2025-04-02 09:00:00"),
+        (400000, "2025-02-28 23:59:59"),
+    ] {
+        app.topup(alice, &alice_token, amount).await;
+        sqlx::query("UPDATE topups SET topup_time = $1::timestamp WHERE topup_amount = $2")
+            .bind(time)
+            .bind(amount)
+            .execute(&app.pool)
+            .await
+            .expect("failed to backdate the topup");
+    }
+
+    assert_eq!(
+        listed_amounts(&app, &admin_token, &format!("min_amount=150000&{MARCH}")).await,
+        [150000, 250000]
+    );
+    assert_eq!(
+        listed_amounts(&app, &admin_token, "min_amount=150000&max_amount=150000").await,
+        [150000]
+    );
+
+    // Nothing in March is above the largest March topup.
+    assert!(
+        listed_amounts(&app, &admin_token, &format!("min_amount=250001&{MARCH}"))
+            .await
+            .is_empty()
+    );
+    assert!(
+        listed_amounts(
+            &app,
+            &admin_token,
+            "from_date=2025-05-01T00:00:00Z&to_date=2025-05-31T00:00:00Z"
+        )
+        .await
+        .is_empty()
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn inverted_ranges_are_rejected() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    for query in [
+        "min_amount=200000&max_amount=100000",
+        "from_date=2025-03-31T00:00:00Z&to_date=2025-03-01T00:00:00Z",
+    ] {
+        let (status, body) = app
+            .request(
+                Method::GET,
+                &format!("/api/topups?{query}"),
+                Some(&admin_token),
+                None,
+            )
+            .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{query}: {body}");
+    }
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::Utc;
+use serde_json::json;
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn notes_are_stored_returned_and_searchable() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 500000).await;
+    app.topup(bob, &bob_token, 10000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/transfers",
+            Some(&alice_token),
+            Some(json!({
+                "transfer_from": alice,
+                "transfer_to": bob,
+                "transfer_amount": 100000,
+                "note": "  March rent\n",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "transfer failed: {body}");
+    assert_eq!(body["data"]["note"], "March rent", "{body}");
+    let transfer_id = body["data"]["transfer_id"].as_i64().expect("transfer id");
+
+    // Without a note the field is present but null.
+    let plain = app.transfer(alice, bob, &alice_token, 50000).await;
+    assert!(plain["note"].is_null(), "{plain}");
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/transfers/{transfer_id}"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "get transfer failed: {body}");
+    assert_eq!(body["data"]["note"], "March rent", "{body}");
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            "/api/transfers?search=RENT&search_notes=true",
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "search failed: {body}");
+    assert_eq!(body["pagination"]["total_items"], 1, "{body}");
+    assert_eq!(body["data"][0]["transfer_id"], transfer_id, "{body}");
+
+    // Notes are only searched when asked to.
+    let (status, body) = app
+        .request(
+            Method::GET,
+            "/api/transfers?search=rent",
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "search failed: {body}");
+    assert_eq!(body["pagination"]["total_items"], 0, "{body}");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/withdraws",
+            Some(&alice_token),
+            Some(json!({
+                "user_id": alice,
+                "withdraw_amount": 60000,
+                "withdraw_time": Utc::now(),
+                "note": "Cash for the trip",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "withdraw failed: {body}");
+    assert_eq!(body["data"]["note"], "Cash for the trip", "{body}");
+    let withdraw_id = body["data"]["withdraw_id"].as_i64().expect("withdraw id");
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            "/api/withdraws?search=trip&search_notes=true",
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "search failed: {body}");
+    assert_eq!(body["pagination"]["total_items"], 1, "{body}");
+    assert_eq!(body["data"][0]["withdraw_id"], withdraw_id, "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn overlong_note_is_rejected() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, _) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 500000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/transfers",
+            Some(&alice_token),
+            Some(json!({
+                "transfer_from": alice,
+                "transfer_to": bob,
+                "transfer_amount": 100000,
+                "note": "x".repeat(256),
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+    assert!(body["errors"].to_string().contains("note"), "{body}");
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{Value, json};
+
+use common::TestApp;
+use example_sea_query_payment_gateway::domain::money::Money;
+
+async fn balance(app: &TestApp, user_id: i32, token: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{user_id}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+
+    body["data"]["total_balance"].clone()
+}
+
+async fn transfer_all(app: &TestApp, token: &str, to: i32) -> (StatusCode, Value) {
+    app.request(
+        Method::POST,
+        "/api/saldos/transfer-all",
+        Some(token),
+        Some(json!({ "transfer_to": to })),
+    )
+    .await
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn sweeps_the_whole_balance_to_zero() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 120000).await;
+    app.topup(bob, &bob_token, 10000).await;
+
+    let (status, body) = transfer_all(&app, &alice_token, bob).await;
+    assert_eq!(status, StatusCode::CREATED, "sweep failed: {body}");
+    assert_eq!(body["data"]["transfer_amount"], 120000, "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 0);
+    assert_eq!(balance(&app, bob, &bob_token).await, 130000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn sweep_keeps_the_minimum_balance_and_the_fee() {
+    let app = TestApp::spawn_with(|config| {
+        config.minimum_balance = Money::new(10000);
+        config.transfer_fee_flat = Money::new(2500);
+    })
+    .await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+    app.topup(bob, &bob_token, 10000).await;
+
+    let (status, body) = transfer_all(&app, &alice_token, bob).await;
+    assert_eq!(status, StatusCode::CREATED, "sweep failed: {body}");
+    assert_eq!(body["data"]["transfer_amount"], 87500, "{body}");
+    assert_eq!(body["data"]["fee"], 2500, "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 10000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 97500);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn sweeping_an_empty_account_is_a_no_op() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 50000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    let (status, body) = transfer_all(&app, &alice_token, bob).await;
+    assert_eq!(status, StatusCode::CREATED, "first sweep failed: {body}");
+
+    let (status, body) = transfer_all(&app, &alice_token, bob).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert!(body["data"].is_null(), "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 0);
+    assert_eq!(balance(&app, bob, &bob_token).await, 60000);
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{Value, json};
+
+use common::TestApp;
+
+async fn balance(app: &TestApp, user_id: i32, token: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{user_id}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+
+    body["data"]["total_balance"].clone()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn failed_receiver_credit_leaves_no_money_missing() {
+    let app = TestApp::spawn().await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+    app.topup(bob, &bob_token, 50000).await;
+
+    // Fails the receiver's credit after the sender's debit already went
+    // through in the same transaction.
+    sqlx::query(
+        "CREATE FUNCTION fail_credit() RETURNS trigger AS $$
+         BEGIN RAISE EXCEPTION 'receiver credit failed'; END
+         $$ LANGUAGE plpgsql",
+    )
+    .execute(&app.pool)
+    .await
+    .expect("failed to create trigger function");
+    sqlx::query(&format!(
+        "CREATE TRIGGER fail_credit BEFORE UPDATE ON saldo FOR EACH ROW
+         WHEN (NEW.user_id = {bob} AND NEW.total_balance > OLD.total_balance)
+         EXECUTE FUNCTION fail_credit()"
+    ))
+    .execute(&app.pool)
+    .await
+    .expect("failed to create trigger");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/transfers",
+            Some(&alice_token),
+            Some(json!({
+                "transfer_from": alice,
+                "transfer_to": bob,
+                "transfer_amount": 60000,
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR, "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 100000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 50000);
+
+    let transfers: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM transfers WHERE transfer_from = $1")
+            .bind(alice)
+            .fetch_one(&app.pool)
+            .await
+            .expect("failed to count transfers");
+    assert_eq!(transfers, 0, "the transfer row must roll back too");
+}
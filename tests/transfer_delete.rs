@@ -0,0 +1,91 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::Value;
+
+use common::TestApp;
+
+async fn balance(app: &TestApp, user_id: i32, token: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{user_id}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+
+    body["data"]["total_balance"].clone()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn deleting_a_completed_transfer_restores_both_balances() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 300000).await;
+    app.topup(bob, &bob_token, 20000).await;
+    let transfer = app.transfer(alice, bob, &alice_token, 100000).await;
+    let uri = format!("/api/transfers/{}", transfer["transfer_id"]);
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 200000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 120000);
+
+    // The sender may only cancel pending transfers.
+    let (status, body) = app
+        .request(Method::DELETE, &uri, Some(&alice_token), None)
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 200000);
+
+    let (status, body) = app
+        .request(Method::DELETE, &uri, Some(&admin_token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "delete failed: {body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 300000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 20000);
+
+    // A second delete finds nothing and reverses nothing.
+    let (status, body) = app
+        .request(Method::DELETE, &uri, Some(&admin_token), None)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 300000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 20000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn delete_is_refused_when_the_receiver_already_spent_the_money() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    let (carol, carol_token) = app.register_and_login("carol@example.com").await;
+
+    app.topup(alice, &alice_token, 300000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    app.topup(carol, &carol_token, 10000).await;
+    let transfer = app.transfer(alice, bob, &alice_token, 100000).await;
+    app.transfer(bob, carol, &bob_token, 90000).await;
+
+    let (status, body) = app
+        .request(
+            Method::DELETE,
+            &format!("/api/transfers/{}", transfer["transfer_id"]),
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert!(status.is_client_error(), "{status}: {body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 200000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 20000);
+}
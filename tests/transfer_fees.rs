@@ -0,0 +1,302 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::{Duration, Utc};
+use serde_json::{Value, json};
+
+use common::TestApp;
+use example_sea_query_payment_gateway::domain::money::Money;
+
+async fn balance(app: &TestApp, user_id: i32, token: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{user_id}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+
+    body["data"]["total_balance"].clone()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn flat_fee_is_debited_from_the_sender_only() {
+    let app = TestApp::spawn_with(|config| config.transfer_fee_flat = Money::new(2500)).await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    let transfer = app.transfer(alice, bob, &alice_token, 50000).await;
+
+    assert_eq!(transfer["transfer_amount"], 50000, "{transfer}");
+    assert_eq!(transfer["fee"], 2500, "{transfer}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 47500);
+    assert_eq!(balance(&app, bob, &bob_token).await, 60000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn percentage_fee_scales_with_the_amount() {
+    // 1%.
+    let app = TestApp::spawn_with(|config| config.transfer_fee_basis_points = 100).await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 300000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    let transfer = app.transfer(alice, bob, &alice_token, 200000).await;
+
+    assert_eq!(transfer["fee"], 2000, "{transfer}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 98000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 210000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn balance_check_includes_the_fee() {
+    let app = TestApp::spawn_with(|config| config.transfer_fee_flat = Money::new(1000)).await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 50000).await;
+    app.topup(bob, &bob_token, 10000).await;
+
+    // Enough for the amount, not for the amount plus fee.
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/transfers",
+            Some(&alice_token),
+            Some(json!({
+                "transfer_from": alice,
+                "transfer_to": bob,
+                "transfer_amount": 50000,
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["error_code"], "insufficient_balance", "{body}");
+    assert_eq!(body["details"]["required"], 51000, "{body}");
+    assert_eq!(body["details"]["shortfall"], 1000, "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 50000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 10000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn batch_pays_the_fee_on_every_item() {
+    let app = TestApp::spawn_with(|config| config.transfer_fee_flat = Money::new(2500)).await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    let (carol, carol_token) = app.register_and_login("carol@example.com").await;
+
+    app.topup(bob, &bob_token, 50000).await;
+    app.topup(carol, &carol_token, 50000).await;
+    app.topup(alice, &alice_token, 200000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/transfers/batch",
+            Some(&alice_token),
+            Some(json!({
+                "transfer_from": alice,
+                "recipients": [
+                    { "transfer_to": bob, "transfer_amount": 50000 },
+                    { "transfer_to": carol, "transfer_amount": 60000 },
+                ],
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "batch failed: {body}");
+    assert_eq!(body["data"][0]["fee"], 2500, "{body}");
+    assert_eq!(body["data"][1]["fee"], 2500, "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 85000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 100000);
+    assert_eq!(balance(&app, carol, &carol_token).await, 110000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn batch_from_another_users_saldo_is_forbidden() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (_, bob_token) = app.register_and_login("bob@example.com").await;
+    let (carol, _) = app.register_and_login("carol@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/transfers/batch",
+            Some(&bob_token),
+            Some(json!({
+                "transfer_from": alice,
+                "recipients": [{ "transfer_to": carol, "transfer_amount": 50000 }],
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 100000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn scheduled_transfer_pays_the_fee_when_it_runs() {
+    let app = TestApp::spawn_with(|config| config.transfer_fee_flat = Money::new(2500)).await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+    app.topup(bob, &bob_token, 10000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/transfers",
+            Some(&alice_token),
+            Some(json!({
+                "transfer_from": alice,
+                "transfer_to": bob,
+                "transfer_amount": 50000,
+                "scheduled_at": Utc::now() + Duration::hours(1),
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "scheduling failed: {body}");
+    let transfer_id = body["data"]["transfer_id"].as_i64().expect("transfer id");
+
+    // Nothing moves until it is due.
+    assert_eq!(balance(&app, alice, &alice_token).await, 100000);
+
+    sqlx::query("UPDATE transfers SET scheduled_at = $1 WHERE transfer_id = $2")
+        .bind((Utc::now() - Duration::minutes(1)).naive_utc())
+        .bind(transfer_id as i32)
+        .execute(&app.pool)
+        .await
+        .expect("failed to make the transfer due");
+
+    let executed = app
+        .state
+        .di_container
+        .transfer_service
+        .execute_due_transfers()
+        .await
+        .expect("scheduler run failed");
+    assert_eq!(executed, 1);
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/transfers/{transfer_id}"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["status"], "completed", "{body}");
+    assert_eq!(body["data"]["fee"], 2500, "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 47500);
+    assert_eq!(balance(&app, bob, &bob_token).await, 60000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn updating_the_amount_reprices_the_fee() {
+    // 1%.
+    let app = TestApp::spawn_with(|config| config.transfer_fee_basis_points = 100).await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 300000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    let transfer = app.transfer(alice, bob, &alice_token, 100000).await;
+    assert_eq!(balance(&app, alice, &alice_token).await, 199000);
+
+    let (status, body) = app
+        .request(
+            Method::PUT,
+            &format!("/api/transfers/{}", transfer["transfer_id"]),
+            Some(&alice_token),
+            Some(json!({
+                "transfer_id": transfer["transfer_id"],
+                "transfer_from": alice,
+                "transfer_to": bob,
+                "transfer_amount": 200000,
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "update failed: {body}");
+    assert_eq!(body["data"]["fee"], 2000, "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 98000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 210000);
+}
+
+async fn fee_rows(app: &TestApp, transfer_id: &Value) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM fees WHERE transfer_id = $1")
+        .bind(transfer_id.as_i64().unwrap() as i32)
+        .fetch_one(&app.pool)
+        .await
+        .expect("failed to count fees")
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn deleting_a_transfer_refunds_the_fee_and_restoring_charges_it_again() {
+    let app = TestApp::spawn_with(|config| config.transfer_fee_flat = Money::new(2500)).await;
+    let admin_token = app.login_admin().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    let transfer = app.transfer(alice, bob, &alice_token, 50000).await;
+    let transfer_id = &transfer["transfer_id"];
+    assert_eq!(fee_rows(&app, transfer_id).await, 1);
+
+    let (status, body) = app
+        .request(
+            Method::DELETE,
+            &format!("/api/transfers/{transfer_id}"),
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "delete failed: {body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 100000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 10000);
+    assert_eq!(fee_rows(&app, transfer_id).await, 0);
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/api/transfers/{transfer_id}/restore"),
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "restore failed: {body}");
+    assert_eq!(body["data"]["fee"], 2500, "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 47500);
+    assert_eq!(balance(&app, bob, &bob_token).await, 60000);
+    assert_eq!(fee_rows(&app, transfer_id).await, 1);
+}
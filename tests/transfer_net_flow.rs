@@ -0,0 +1,66 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn net_flow_for_receiver_sender_and_both() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    let (carol, carol_token) = app.register_and_login("carol@example.com").await;
+
+    app.topup(alice, &alice_token, 300000).await;
+    app.topup(bob, &bob_token, 50000).await;
+    app.topup(carol, &carol_token, 50000).await;
+
+    // Alice only sends, Carol only receives, Bob does both.
+    app.transfer(alice, bob, &alice_token, 100000).await;
+    app.transfer(bob, carol, &bob_token, 60000).await;
+
+    for (user_id, token, incoming, outgoing, net) in [
+        (alice, &alice_token, 0, 100000, -100000),
+        (carol, &carol_token, 60000, 0, 60000),
+        (bob, &bob_token, 100000, 60000, 40000),
+    ] {
+        let (status, body) = app
+            .request(
+                Method::GET,
+                &format!("/api/transfers/user/{user_id}/net"),
+                Some(token),
+                None,
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK, "net flow failed: {body}");
+        assert_eq!(body["data"]["incoming"], incoming, "{body}");
+        assert_eq!(body["data"]["outgoing"], outgoing, "{body}");
+        assert_eq!(body["data"]["net"], net, "{body}");
+    }
+
+    // Restricted to the counterparty, Bob's outgoing transfer to Carol drops out.
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/transfers/user/{bob}/net?counterparty={alice}"),
+            Some(&bob_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "net flow failed: {body}");
+    assert_eq!(body["data"]["incoming"], 100000);
+    assert_eq!(body["data"]["outgoing"], 0);
+    assert_eq!(body["data"]["net"], 100000);
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/transfers/user/{alice}/net"),
+            Some(&bob_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+}
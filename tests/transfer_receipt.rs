@@ -0,0 +1,70 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+
+use common::TestApp;
+
+async fn rename(app: &TestApp, user_id: i32, firstname: &str, lastname: &str) {
+    sqlx::query("UPDATE users SET firstname = $1, lastname = $2 WHERE user_id = $3")
+        .bind(firstname)
+        .bind(lastname)
+        .bind(user_id)
+        .execute(&app.pool)
+        .await
+        .expect("failed to rename user");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn receipt_names_both_parties() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    rename(&app, alice, "Alice", "Anders").await;
+    rename(&app, bob, "Bob", "Brown").await;
+
+    app.topup(alice, &alice_token, 100000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    let transfer = app.transfer(alice, bob, &alice_token, 50000).await;
+    let transfer_id = transfer["transfer_id"].as_i64().expect("transfer id");
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/transfers/{transfer_id}/receipt"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let receipt = &body["data"];
+    assert_eq!(receipt["transfer_id"], transfer_id, "{receipt}");
+    assert_eq!(receipt["from_name"], "Alice Anders", "{receipt}");
+    assert_eq!(receipt["to_name"], "Bob Brown", "{receipt}");
+    assert_eq!(receipt["amount"], 50000, "{receipt}");
+
+    let reference = receipt["reference"].as_str().expect("reference");
+    assert!(
+        reference.starts_with("TRF-") && reference.ends_with(&format!("-{transfer_id:08}")),
+        "{reference}"
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn receipt_for_a_missing_transfer_is_not_found() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            "/api/transfers/999999/receipt",
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body}");
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::Value;
+
+use common::TestApp;
+
+async fn balance(app: &TestApp, user_id: i32, token: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{user_id}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+
+    body["data"]["total_balance"].clone()
+}
+
+async fn reverse(app: &TestApp, transfer_id: &Value, token: &str) -> (StatusCode, Value) {
+    app.request(
+        Method::POST,
+        &format!("/api/transfers/{transfer_id}/reverse"),
+        Some(token),
+        None,
+    )
+    .await
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn reversal_moves_the_money_back_once() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 300000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    let original = app.transfer(alice, bob, &alice_token, 100000).await;
+    let original_id = &original["transfer_id"];
+
+    // Only admins may reverse.
+    let (status, body) = reverse(&app, original_id, &alice_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+
+    let (status, body) = reverse(&app, original_id, &admin_token).await;
+    assert_eq!(status, StatusCode::CREATED, "reversal failed: {body}");
+    let reversal = &body["data"];
+    assert_eq!(reversal["transfer_from"], bob, "{body}");
+    assert_eq!(reversal["transfer_to"], alice, "{body}");
+    assert_eq!(reversal["transfer_amount"], 100000, "{body}");
+    assert_eq!(reversal["status"], "completed", "{body}");
+    assert_eq!(&reversal["reversed_transfer_id"], original_id, "{body}");
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/transfers/{original_id}"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "get transfer failed: {body}");
+    assert_eq!(body["data"]["status"], "reversed", "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 300000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 10000);
+
+    // Neither half can be reversed again.
+    let (status, body) = reverse(&app, original_id, &admin_token).await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body}");
+
+    let (status, body) = reverse(&app, &reversal["transfer_id"], &admin_token).await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 300000);
+
+    // The ledger still accounts for every balance.
+    let (status, body) = app
+        .request(
+            Method::GET,
+            "/api/users/reconcile",
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "reconcile failed: {body}");
+    assert_eq!(body["data"], Value::Array(vec![]), "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn reversal_is_rejected_when_the_receiver_spent_the_money() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    let (carol, carol_token) = app.register_and_login("carol@example.com").await;
+
+    app.topup(alice, &alice_token, 300000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    app.topup(carol, &carol_token, 10000).await;
+    let original = app.transfer(alice, bob, &alice_token, 100000).await;
+    app.transfer(bob, carol, &bob_token, 70000).await;
+
+    let (status, body) = reverse(&app, &original["transfer_id"], &admin_token).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["error_code"], "insufficient_balance", "{body}");
+
+    // Nothing changed: the original is still completed and can be retried.
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/transfers/{}", original["transfer_id"]),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "get transfer failed: {body}");
+    assert_eq!(body["data"]["status"], "completed", "{body}");
+    assert_eq!(balance(&app, bob, &bob_token).await, 40000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn reversal_may_overdraw_the_receiver_when_allowed() {
+    let app = TestApp::spawn_with(|config| config.allow_negative_reversal = true).await;
+    let admin_token = app.login_admin().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    let (carol, carol_token) = app.register_and_login("carol@example.com").await;
+
+    app.topup(alice, &alice_token, 300000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    app.topup(carol, &carol_token, 10000).await;
+    let original = app.transfer(alice, bob, &alice_token, 100000).await;
+    app.transfer(bob, carol, &bob_token, 70000).await;
+
+    let (status, body) = reverse(&app, &original["transfer_id"], &admin_token).await;
+    assert_eq!(status, StatusCode::CREATED, "reversal failed: {body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 300000);
+    assert_eq!(balance(&app, bob, &bob_token).await, -60000);
+}
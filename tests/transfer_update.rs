@@ -0,0 +1,111 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{Value, json};
+
+use common::TestApp;
+
+async fn balance(app: &TestApp, user_id: i32, token: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{user_id}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+
+    body["data"]["total_balance"].clone()
+}
+
+async fn update_transfer(
+    app: &TestApp,
+    transfer: &Value,
+    from: i32,
+    to: i32,
+    token: &str,
+    amount: i64,
+) -> (StatusCode, Value) {
+    app.request(
+        Method::PUT,
+        &format!("/api/transfers/{}", transfer["transfer_id"]),
+        Some(token),
+        Some(json!({
+            "transfer_id": transfer["transfer_id"],
+            "transfer_from": from,
+            "transfer_to": to,
+            "transfer_amount": amount,
+        })),
+    )
+    .await
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn changing_the_amount_moves_the_difference() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 300000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    let transfer = app.transfer(alice, bob, &alice_token, 100000).await;
+
+    let (status, body) = update_transfer(&app, &transfer, alice, bob, &alice_token, 150000).await;
+    assert_eq!(status, StatusCode::OK, "update failed: {body}");
+    assert_eq!(body["data"]["transfer_amount"], 150000, "{body}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 150000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 160000);
+
+    let (status, body) = update_transfer(&app, &transfer, alice, bob, &alice_token, 50000).await;
+    assert_eq!(status, StatusCode::OK, "update failed: {body}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 250000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 60000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn shrinking_is_refused_once_the_receiver_spent_the_money() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    let (carol, carol_token) = app.register_and_login("carol@example.com").await;
+
+    app.topup(alice, &alice_token, 300000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    app.topup(carol, &carol_token, 10000).await;
+    let transfer = app.transfer(alice, bob, &alice_token, 100000).await;
+    app.transfer(bob, carol, &bob_token, 80000).await;
+
+    let (status, body) = update_transfer(&app, &transfer, alice, bob, &alice_token, 50000).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["error_code"], "insufficient_balance", "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 200000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 30000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn a_body_naming_other_users_changes_nothing() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    let (carol, carol_token) = app.register_and_login("carol@example.com").await;
+
+    app.topup(alice, &alice_token, 300000).await;
+    app.topup(bob, &bob_token, 10000).await;
+    app.topup(carol, &carol_token, 10000).await;
+    let transfer = app.transfer(alice, bob, &alice_token, 100000).await;
+
+    let (status, body) = update_transfer(&app, &transfer, alice, carol, &alice_token, 150000).await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 200000);
+    assert_eq!(balance(&app, bob, &bob_token).await, 110000);
+    assert_eq!(balance(&app, carol, &carol_token).await, 10000);
+}
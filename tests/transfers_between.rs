@@ -0,0 +1,74 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn only_transfers_from_a_to_b_are_listed() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    let (carol, carol_token) = app.register_and_login("carol@example.com").await;
+
+    app.topup(alice, &alice_token, 500000).await;
+    app.topup(bob, &bob_token, 500000).await;
+    app.topup(carol, &carol_token, 500000).await;
+
+    let mut expected = Vec::new();
+    for amount in [50000, 60000, 70000] {
+        let transfer = app.transfer(alice, bob, &alice_token, amount).await;
+        expected.push(transfer["transfer_id"].as_i64().unwrap());
+    }
+    app.transfer(bob, alice, &bob_token, 80000).await;
+    app.transfer(alice, carol, &alice_token, 90000).await;
+    app.transfer(carol, bob, &carol_token, 100000).await;
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/transfers/between?from={alice}&to={bob}&page_size=10"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["pagination"]["total_items"], 3, "{body}");
+
+    let transfers = body["data"].as_array().expect("transfer list");
+    let mut ids: Vec<i64> = transfers
+        .iter()
+        .map(|transfer| transfer["transfer_id"].as_i64().unwrap())
+        .collect();
+    ids.sort_unstable();
+    assert_eq!(ids, expected, "{body}");
+    assert!(
+        transfers
+            .iter()
+            .all(|transfer| transfer["transfer_from"] == alice && transfer["transfer_to"] == bob),
+        "{body}"
+    );
+
+    // Carol is on neither side.
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/transfers/between?from={alice}&to={bob}"),
+            Some(&carol_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/transfers/between?from={alice}&to=999999"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body}");
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn counterparties_are_grouped_and_ordered_by_transfer_count() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    let (carol, carol_token) = app.register_and_login("carol@example.com").await;
+    let (dave, dave_token) = app.register_and_login("dave@example.com").await;
+
+    app.topup(alice, &alice_token, 1000000).await;
+    app.topup(bob, &bob_token, 200000).await;
+    app.topup(carol, &carol_token, 10000).await;
+    app.topup(dave, &dave_token, 200000).await;
+
+    // Bob: two out, one in. Dave: one each way. Carol: a single transfer.
+    app.transfer(alice, bob, &alice_token, 100000).await;
+    app.transfer(alice, bob, &alice_token, 60000).await;
+    app.transfer(bob, alice, &bob_token, 50000).await;
+    app.transfer(alice, dave, &alice_token, 80000).await;
+    app.transfer(dave, alice, &dave_token, 55000).await;
+    app.transfer(alice, carol, &alice_token, 90000).await;
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/users/{alice}/counterparties"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "counterparties failed: {body}");
+    assert_eq!(body["pagination"]["total_items"], 3, "{body}");
+
+    let rows = body["data"].as_array().expect("counterparty list");
+    let expected = [
+        (bob, 160000, 50000, 3),
+        (dave, 80000, 55000, 2),
+        (carol, 90000, 0, 1),
+    ];
+    assert_eq!(rows.len(), expected.len(), "{body}");
+
+    for (row, (id, sent, received, count)) in rows.iter().zip(expected) {
+        assert_eq!(row["counterparty_id"], id, "{body}");
+        assert_eq!(row["counterparty_name"], "Test User", "{body}");
+        assert_eq!(row["total_sent"], sent, "{body}");
+        assert_eq!(row["total_received"], received, "{body}");
+        assert_eq!(row["transfer_count"], count, "{body}");
+        assert!(row["last_transfer_at"].is_string(), "{body}");
+    }
+
+    // From Bob's side the same transfers are mirrored.
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/users/{bob}/counterparties"),
+            Some(&bob_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "counterparties failed: {body}");
+    assert_eq!(body["data"][0]["counterparty_id"], alice, "{body}");
+    assert_eq!(body["data"][0]["total_sent"], 50000, "{body}");
+    assert_eq!(body["data"][0]["total_received"], 160000, "{body}");
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/users/{alice}/counterparties?page=2&page_size=2"),
+            Some(&alice_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "counterparties failed: {body}");
+    assert_eq!(body["pagination"]["total_items"], 3, "{body}");
+    assert_eq!(body["data"].as_array().map(Vec::len), Some(1), "{body}");
+    assert_eq!(body["data"][0]["counterparty_id"], carol, "{body}");
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/users/{alice}/counterparties"),
+            Some(&bob_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+}
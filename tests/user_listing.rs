@@ -0,0 +1,116 @@
+mod common;
+
+use std::collections::HashSet;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{Value, json};
+
+use common::TestApp;
+
+async fn register(app: &TestApp, firstname: &str, lastname: &str, email: &str) {
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/auth/register",
+            None,
+            Some(json!({
+                "firstname": firstname,
+                "lastname": lastname,
+                "email": email,
+                "password": "password123",
+                "confirm_password": "password123",
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "register failed: {body}");
+}
+
+async fn list(app: &TestApp, token: &str, query: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/users?{query}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "listing failed: {body}");
+
+    body
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn admins_page_through_users_matching_a_search() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    for (firstname, email) in [
+        ("Anna", "anna@example.com"),
+        ("Ben", "ben@example.com"),
+        ("Cara", "cara@example.com"),
+        ("Dan", "dan@example.com"),
+        ("Eve", "eve@example.com"),
+    ] {
+        register(&app, firstname, "Smithson", email).await;
+    }
+    register(&app, "Frank", "Jones", "frank@example.com").await;
+    register(&app, "Gina", "Brown", "gina@elsewhere.org").await;
+
+    let mut seen = HashSet::new();
+    for page in 1..=3 {
+        let body = list(
+            &app,
+            &admin_token,
+            &format!("search=SMITH&page={page}&page_size=2"),
+        )
+        .await;
+
+        assert_eq!(body["pagination"]["total_items"], 5, "{body}");
+        assert_eq!(body["pagination"]["total_pages"], 3, "{body}");
+
+        let users = body["data"].as_array().expect("user list");
+        assert_eq!(users.len(), if page < 3 { 2 } else { 1 }, "{body}");
+
+        for user in users {
+            assert_eq!(user["lastname"], "Smithson", "{body}");
+            assert!(user.get("password").is_none(), "{body}");
+            assert!(seen.insert(user["id"].as_i64().unwrap()), "{body}");
+        }
+    }
+    assert_eq!(seen.len(), 5);
+
+    // Email matches too.
+    let body = list(&app, &admin_token, "search=elsewhere").await;
+    assert_eq!(body["pagination"]["total_items"], 1, "{body}");
+    assert_eq!(body["data"][0]["firstname"], "Gina", "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn only_admins_list_users() {
+    let app = TestApp::spawn().await;
+    let (_, token) = app.register_and_login("alice@example.com").await;
+
+    let (status, body) = app
+        .request(Method::GET, "/api/users", Some(&token), None)
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn fuzzy_search_tolerates_a_typo_that_exact_search_misses() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+
+    register(&app, "Jonathan", "Whitfield", "jonathan@example.com").await;
+    register(&app, "Maria", "Lopez", "maria@example.com").await;
+
+    let body = list(&app, &admin_token, "search=Whitfeld").await;
+    assert_eq!(body["pagination"]["total_items"], 0, "{body}");
+
+    let body = list(&app, &admin_token, "search=Whitfeld&fuzzy=true").await;
+    assert_eq!(body["pagination"]["total_items"], 1, "{body}");
+    assert_eq!(body["data"][0]["lastname"], "Whitfield", "{body}");
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::Utc;
+use serde_json::{Value, json};
+
+use common::TestApp;
+
+async fn summary(app: &TestApp, user_id: i32, token: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/users/{user_id}/summary"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "summary failed: {body}");
+
+    body["data"].clone()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn summary_sums_match_the_seeded_transactions() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+
+    app.topup(alice, &alice_token, 300000).await;
+    app.topup(alice, &alice_token, 100000).await;
+    app.topup(bob, &bob_token, 50000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/withdraws",
+            Some(&alice_token),
+            Some(json!({
+                "user_id": alice,
+                "withdraw_amount": 60000,
+                "withdraw_time": Utc::now(),
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "withdraw failed: {body}");
+
+    app.transfer(alice, bob, &alice_token, 70000).await;
+    app.transfer(bob, alice, &bob_token, 50000).await;
+
+    assert_eq!(
+        summary(&app, alice, &alice_token).await,
+        json!({
+            "total_topup": 400000,
+            "total_withdraw": 60000,
+            "total_sent": 70000,
+            "total_received": 50000,
+            "current_balance": 320000,
+            "transaction_count": 5,
+        })
+    );
+    assert_eq!(
+        summary(&app, bob, &bob_token).await,
+        json!({
+            "total_topup": 50000,
+            "total_withdraw": 0,
+            "total_sent": 50000,
+            "total_received": 70000,
+            "current_balance": 70000,
+            "transaction_count": 3,
+        })
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn a_user_without_transactions_sums_to_zero() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (carol, carol_token) = app.register_and_login("carol@example.com").await;
+
+    assert_eq!(
+        summary(&app, carol, &carol_token).await,
+        json!({
+            "total_topup": 0,
+            "total_withdraw": 0,
+            "total_sent": 0,
+            "total_received": 0,
+            "current_balance": 0,
+            "transaction_count": 0,
+        })
+    );
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            "/api/users/999999/summary",
+            Some(&admin_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body}");
+}
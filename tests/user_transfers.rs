@@ -0,0 +1,101 @@
+mod common;
+
+use std::collections::HashSet;
+
+use axum::http::{Method, StatusCode};
+use serde_json::Value;
+
+use common::TestApp;
+
+async fn list(app: &TestApp, user_id: i32, token: &str, query: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/transfers/users/{user_id}?{query}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "listing failed: {body}");
+
+    body
+}
+
+fn parties(body: &Value) -> Vec<(i32, i32)> {
+    body["data"]
+        .as_array()
+        .expect("transfer list")
+        .iter()
+        .map(|transfer| {
+            (
+                transfer["transfer_from"].as_i64().unwrap() as i32,
+                transfer["transfer_to"].as_i64().unwrap() as i32,
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn direction_filter_and_paging() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, bob_token) = app.register_and_login("bob@example.com").await;
+    let (carol, carol_token) = app.register_and_login("carol@example.com").await;
+    app.topup(alice, &alice_token, 500000).await;
+    app.topup(bob, &bob_token, 200000).await;
+    app.topup(carol, &carol_token, 100000).await;
+
+    for amount in [50000, 50001, 50002] {
+        app.transfer(alice, bob, &alice_token, amount).await;
+    }
+    for amount in [50000, 50001] {
+        app.transfer(bob, alice, &bob_token, amount).await;
+    }
+    // Doesn't involve Bob, so it never shows up in his listing.
+    app.transfer(carol, alice, &carol_token, 50000).await;
+
+    let body = list(&app, bob, &bob_token, "direction=incoming").await;
+    assert_eq!(body["pagination"]["total_items"], 3, "{body}");
+    assert!(parties(&body).iter().all(|&p| p == (alice, bob)), "{body}");
+
+    let body = list(&app, bob, &bob_token, "direction=outgoing").await;
+    assert_eq!(body["pagination"]["total_items"], 2, "{body}");
+    assert!(parties(&body).iter().all(|&p| p == (bob, alice)), "{body}");
+
+    let mut seen = HashSet::new();
+    for page in 1..=3 {
+        let body = list(&app, bob, &bob_token, &format!("page={page}&page_size=2")).await;
+        assert_eq!(body["pagination"]["total_items"], 5, "{body}");
+        assert_eq!(body["pagination"]["total_pages"], 3, "{body}");
+
+        let transfers = body["data"].as_array().expect("transfer list");
+        assert_eq!(transfers.len(), if page < 3 { 2 } else { 1 }, "{body}");
+        for transfer in transfers {
+            assert!(
+                seen.insert(transfer["transfer_id"].as_i64().unwrap()),
+                "{body}"
+            );
+        }
+    }
+    assert_eq!(seen.len(), 5);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn users_cannot_list_someone_elses_transfers() {
+    let app = TestApp::spawn().await;
+    let (alice, _) = app.register_and_login("alice@example.com").await;
+    let (_, bob_token) = app.register_and_login("bob@example.com").await;
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/transfers/users/{alice}"),
+            Some(&bob_token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+}
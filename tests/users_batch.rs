@@ -0,0 +1,52 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+use common::TestApp;
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn batch_returns_existing_users_in_request_order() {
+    let app = TestApp::spawn().await;
+
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+    let (bob, _) = app.register_and_login("bob@example.com").await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/users/batch",
+            Some(&alice_token),
+            Some(json!({ "ids": [bob, 999_999, alice, bob] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "batch lookup failed: {body}");
+
+    let ids: Vec<i64> = body["data"]
+        .as_array()
+        .expect("user list")
+        .iter()
+        .map(|user| user["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, [i64::from(bob), i64::from(alice)], "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn batch_rejects_empty_and_oversized_requests() {
+    let app = TestApp::spawn().await;
+    let (_, token) = app.register_and_login("alice@example.com").await;
+
+    for ids in [vec![], (1..=101).collect::<Vec<i32>>()] {
+        let (status, body) = app
+            .request(
+                Method::POST,
+                "/api/users/batch",
+                Some(&token),
+                Some(json!({ "ids": ids })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{body}");
+    }
+}
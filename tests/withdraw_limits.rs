@@ -0,0 +1,82 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::Utc;
+use example_sea_query_payment_gateway::domain::money::Money;
+use serde_json::{Value, json};
+
+use common::TestApp;
+
+async fn withdraw(app: &TestApp, user_id: i32, token: &str, amount: i64) -> (StatusCode, Value) {
+    app.request(
+        Method::POST,
+        "/api/withdraws",
+        Some(token),
+        Some(json!({
+            "user_id": user_id,
+            "withdraw_amount": amount,
+            "withdraw_time": Utc::now(),
+        })),
+    )
+    .await
+}
+
+async fn balance(app: &TestApp, user_id: i32, token: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{user_id}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+
+    body["data"]["total_balance"].clone()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn withdrawing_exactly_the_daily_limit_is_allowed() {
+    let app =
+        TestApp::spawn_with(|config| config.daily_withdraw_limit = Some(Money::new(150000))).await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &alice_token, 300000).await;
+
+    let (status, body) = withdraw(&app, alice, &alice_token, 60000).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let (status, body) = withdraw(&app, alice, &alice_token, 90000).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 150000);
+
+    // The limit is used up, so even the smallest withdraw is refused.
+    let (status, body) = withdraw(&app, alice, &alice_token, 50001).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["message"], "daily withdraw limit exceeded", "{body}");
+    assert_eq!(balance(&app, alice, &alice_token).await, 150000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn withdrawing_past_the_daily_limit_is_refused() {
+    let app =
+        TestApp::spawn_with(|config| config.daily_withdraw_limit = Some(Money::new(150000))).await;
+    let (alice, alice_token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &alice_token, 300000).await;
+
+    let (status, body) = withdraw(&app, alice, &alice_token, 150001).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+
+    let (status, body) = withdraw(&app, alice, &alice_token, 100000).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    // 100,000 + 50,001 is one over the limit.
+    let (status, body) = withdraw(&app, alice, &alice_token, 50001).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["message"], "daily withdraw limit exceeded", "{body}");
+
+    assert_eq!(balance(&app, alice, &alice_token).await, 200000);
+}
@@ -0,0 +1,231 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::Utc;
+use example_sea_query_payment_gateway::domain::money::Money;
+use serde_json::{Value, json};
+
+use common::TestApp;
+
+async fn balance(app: &TestApp, user_id: i32, token: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/saldos/user/{user_id}"),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "saldo lookup failed: {body}");
+
+    body["data"]["total_balance"].clone()
+}
+
+// Returns the created withdraw.
+async fn create_withdraw(app: &TestApp, user_id: i32, token: &str, amount: i64) -> Value {
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/api/withdraws",
+            Some(token),
+            Some(json!({
+                "user_id": user_id,
+                "withdraw_amount": amount,
+                "withdraw_time": Utc::now(),
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "withdraw failed: {body}");
+
+    body["data"].clone()
+}
+
+async fn update_withdraw(
+    app: &TestApp,
+    withdraw: &Value,
+    token: &str,
+    amount: i64,
+) -> (StatusCode, Value) {
+    app.request(
+        Method::PUT,
+        &format!("/api/withdraws/{}", withdraw["withdraw_id"]),
+        Some(token),
+        Some(json!({
+            "user_id": withdraw["user_id"],
+            "withdraw_id": withdraw["withdraw_id"],
+            "withdraw_amount": amount,
+            "withdraw_time": Utc::now(),
+        })),
+    )
+    .await
+}
+
+async fn decide(app: &TestApp, withdraw: &Value, token: &str, decision: &str) -> Value {
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/api/withdraws/{}/{decision}", withdraw["withdraw_id"]),
+            Some(token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{decision} failed: {body}");
+
+    body["data"].clone()
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn updating_a_withdraw_applies_only_the_difference() {
+    let app = TestApp::spawn().await;
+    let (alice, token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &token, 300000).await;
+    let withdraw = create_withdraw(&app, alice, &token, 100000).await;
+    assert_eq!(balance(&app, alice, &token).await, 200000);
+
+    let (status, body) = update_withdraw(&app, &withdraw, &token, 60000).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["withdraw_amount"], 60000, "{body}");
+    assert_eq!(balance(&app, alice, &token).await, 240000);
+
+    let (status, body) = update_withdraw(&app, &withdraw, &token, 150000).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["status"], "completed", "{body}");
+    assert_eq!(balance(&app, alice, &token).await, 150000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn raising_a_withdraw_past_the_balance_is_refused() {
+    let app = TestApp::spawn().await;
+    let (alice, token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &token, 150000).await;
+    let withdraw = create_withdraw(&app, alice, &token, 100000).await;
+
+    let (status, body) = update_withdraw(&app, &withdraw, &token, 160000).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["error_code"], "insufficient_balance", "{body}");
+
+    // Nothing was applied: neither the balance nor the recorded amount.
+    assert_eq!(balance(&app, alice, &token).await, 50000);
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/api/withdraws/{}", withdraw["withdraw_id"]),
+            Some(&token),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["withdraw_amount"], 100000, "{body}");
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn raising_a_withdraw_must_keep_the_minimum_balance() {
+    let app = TestApp::spawn_with(|config| config.minimum_balance = Money::new(10000)).await;
+    let (alice, token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &token, 200000).await;
+    let withdraw = create_withdraw(&app, alice, &token, 100000).await;
+
+    // Would leave 9,999.
+    let (status, body) = update_withdraw(&app, &withdraw, &token, 190001).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(balance(&app, alice, &token).await, 100000);
+
+    let (status, body) = update_withdraw(&app, &withdraw, &token, 190000).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(balance(&app, alice, &token).await, 10000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn withdraws_on_a_frozen_saldo_cannot_be_updated() {
+    let app = TestApp::spawn().await;
+    let admin_token = app.login_admin().await;
+    let (alice, token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &token, 300000).await;
+    let withdraw = create_withdraw(&app, alice, &token, 100000).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/api/saldos/user/{alice}/freeze"),
+            Some(&admin_token),
+            Some(json!({})),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "freeze failed: {body}");
+
+    let (status, body) = update_withdraw(&app, &withdraw, &token, 60000).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["message"], "account frozen", "{body}");
+    assert_eq!(balance(&app, alice, &token).await, 200000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn raising_past_the_approval_threshold_waits_for_approval() {
+    let app = TestApp::spawn_with(|config| {
+        config.withdraw_approval_threshold = Some(Money::new(100000));
+    })
+    .await;
+    let admin_token = app.login_admin().await;
+    let (alice, token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &token, 300000).await;
+    let withdraw = create_withdraw(&app, alice, &token, 80000).await;
+    assert_eq!(withdraw["status"], "completed", "{withdraw}");
+    assert_eq!(balance(&app, alice, &token).await, 220000);
+
+    // The old debit is refunded until an admin signs off on the new amount.
+    let (status, body) = update_withdraw(&app, &withdraw, &token, 150000).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["status"], "pending", "{body}");
+    assert_eq!(body["data"]["withdraw_amount"], 150000, "{body}");
+    assert_eq!(balance(&app, alice, &token).await, 300000);
+
+    // Pending withdraws can't be edited, only approved or rejected.
+    let (status, body) = update_withdraw(&app, &withdraw, &token, 120000).await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body}");
+
+    let approved = decide(&app, &withdraw, &admin_token, "approve").await;
+    assert_eq!(approved["status"], "approved", "{approved}");
+    assert_eq!(balance(&app, alice, &token).await, 150000);
+
+    // Lowering an approved withdraw needs no new approval.
+    let (status, body) = update_withdraw(&app, &withdraw, &token, 120000).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["data"]["status"], "approved", "{body}");
+    assert_eq!(balance(&app, alice, &token).await, 180000);
+}
+
+#[tokio::test]
+#[ignore = "needs Docker for the Postgres container"]
+async fn rejecting_a_raised_withdraw_leaves_the_refund_in_place() {
+    let app = TestApp::spawn_with(|config| {
+        config.withdraw_approval_threshold = Some(Money::new(100000));
+    })
+    .await;
+    let admin_token = app.login_admin().await;
+    let (alice, token) = app.register_and_login("alice@example.com").await;
+
+    app.topup(alice, &token, 300000).await;
+    let withdraw = create_withdraw(&app, alice, &token, 80000).await;
+
+    let (status, body) = update_withdraw(&app, &withdraw, &token, 150000).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(balance(&app, alice, &token).await, 300000);
+
+    let rejected = decide(&app, &withdraw, &admin_token, "reject").await;
+    assert_eq!(rejected["status"], "rejected", "{rejected}");
+    assert_eq!(balance(&app, alice, &token).await, 300000);
+
+    let (status, body) = update_withdraw(&app, &withdraw, &token, 60000).await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body}");
+    assert_eq!(balance(&app, alice, &token).await, 300000);
+}